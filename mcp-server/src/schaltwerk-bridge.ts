@@ -27,6 +27,25 @@ export interface Session {
   original_skip_permissions?: boolean
   pending_name_generation: boolean
   was_auto_generated: boolean
+  version_group_id?: string | null
+  version_number?: number | null
+}
+
+export interface VersionAgentConfig {
+  agent_type?: string
+  skip_permissions?: boolean
+}
+
+export interface VersionGroupOptions {
+  prompt?: string
+  agentConfigs?: VersionAgentConfig[]
+  baseBranch?: string
+  label?: string
+}
+
+export interface VersionGroupCreation {
+  version_group_id: string
+  sessions: Session[]
 }
 
 export interface Epic {
@@ -35,6 +54,27 @@ export interface Epic {
   color?: string | null
 }
 
+export interface ScheduledSpec {
+  id: string
+  spec_name: string
+  not_before: string
+  base_branch?: string | null
+  agent_type?: string | null
+  skip_permissions?: boolean | null
+  skip_if_late: boolean
+  status: 'pending' | 'started' | 'skipped' | 'failed' | 'cancelled'
+  created_at: string
+  finished_at?: string | null
+  error?: string | null
+}
+
+export interface ScheduleSpecOptions {
+  baseBranch?: string
+  agentType?: string
+  skipPermissions?: boolean
+  skipIfLate?: boolean
+}
+
 export interface SpecSummary {
   session_id: string
   display_name?: string
@@ -528,6 +568,83 @@ export class SchaltwerkBridge {
     return epic
   }
 
+  async listScheduledSpecs(): Promise<ScheduledSpec[]> {
+    const response = await this.fetchWithAutoPort('/api/scheduled-specs', {
+      method: 'GET',
+      headers: {
+        Accept: 'application/json',
+        ...this.getProjectHeaders()
+      }
+    })
+
+    const entries = await this.parseJsonResponse<ScheduledSpec[]>(response, 'scheduled specs')
+    return entries ?? []
+  }
+
+  async scheduleSpec(specName: string, notBefore: string, options: ScheduleSpecOptions = {}): Promise<ScheduledSpec> {
+    const response = await this.fetchWithAutoPort('/api/scheduled-specs', {
+      method: 'POST',
+      headers: {
+        'Content-Type': 'application/json',
+        ...this.getProjectHeaders()
+      },
+      body: JSON.stringify({
+        spec_name: specName,
+        not_before: notBefore,
+        base_branch: options.baseBranch,
+        agent_type: options.agentType,
+        skip_permissions: options.skipPermissions,
+        skip_if_late: options.skipIfLate
+      })
+    })
+
+    const entry = await this.parseJsonResponse<ScheduledSpec>(response, 'schedule spec')
+    if (!entry) {
+      throw new Error('Schedule spec response payload missing')
+    }
+    return entry
+  }
+
+  async cancelScheduledSpec(id: string): Promise<ScheduledSpec> {
+    const response = await this.fetchWithAutoPort(`/api/scheduled-specs/${encodeURIComponent(id)}`, {
+      method: 'DELETE',
+      headers: {
+        Accept: 'application/json',
+        ...this.getProjectHeaders()
+      }
+    })
+
+    const entry = await this.parseJsonResponse<ScheduledSpec>(response, 'cancel scheduled spec')
+    if (!entry) {
+      throw new Error('Cancel scheduled spec response payload missing')
+    }
+    return entry
+  }
+
+  async createVersionGroup(baseName: string, count: number, options: VersionGroupOptions = {}): Promise<VersionGroupCreation> {
+    const response = await this.fetchWithAutoPort('/api/version-groups', {
+      method: 'POST',
+      headers: {
+        'Content-Type': 'application/json',
+        ...this.getProjectHeaders()
+      },
+      body: JSON.stringify({
+        base_name: baseName,
+        prompt: options.prompt,
+        count,
+        agent_configs: options.agentConfigs ?? [],
+        base_branch: options.baseBranch,
+        version_group_label: options.label
+      })
+    })
+
+    const created = await this.parseJsonResponse<VersionGroupCreation>(response, 'create version group')
+    if (!created) {
+      throw new Error('Create version group response payload missing')
+    }
+    return created
+  }
+
   async createSession(name: string, prompt?: string, baseBranch?: string, useExistingBranch?: boolean, agentType?: string, skipPermissions?: boolean, epicId?: string): Promise<Session> {
     try {
       const response = await this.fetchWithAutoPort('/api/sessions', {
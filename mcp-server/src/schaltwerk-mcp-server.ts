@@ -11,7 +11,7 @@ import {
   McpError,
   CallToolRequest,
 } from "@modelcontextprotocol/sdk/types.js"
-import { SchaltwerkBridge, Session, MergeModeOption, type PrFeedbackPayload } from "./schaltwerk-bridge.js"
+import { SchaltwerkBridge, Session, MergeModeOption, type PrFeedbackPayload, type ScheduledSpec, type VersionAgentConfig } from "./schaltwerk-bridge.js"
 import { toolOutputSchemas } from "./schemas.js"
 
 const DEFAULT_AGENT = 'claude'
@@ -55,6 +55,28 @@ interface SchaltwerkCreateEpicArgs {
   color?: string
 }
 
+interface SchaltwerkCreateVersionGroupArgs {
+  base_name: string
+  prompt: string
+  count: number
+  agent_configs?: VersionAgentConfig[]
+  base_branch?: string
+  label?: string
+}
+
+interface SchaltwerkScheduleSpecArgs {
+  spec_name: string
+  not_before: string
+  base_branch?: string
+  agent_type?: string
+  skip_permissions?: boolean
+  skip_if_late?: boolean
+}
+
+interface SchaltwerkCancelScheduledSpecArgs {
+  id: string
+}
+
 interface SchaltwerkDraftUpdateArgs {
   session_name: string
   content: string
@@ -332,6 +354,18 @@ const sanitizeSessionSpec = (payload: SessionSpecPayload) => ({
   updated_at: payload.updated_at
 })
 
+const sanitizeScheduledSpec = (entry: ScheduledSpec) => ({
+  id: entry.id,
+  spec_name: entry.spec_name,
+  not_before: entry.not_before,
+  base_branch: entry.base_branch ?? null,
+  agent_type: entry.agent_type ?? null,
+  skip_if_late: entry.skip_if_late,
+  status: entry.status,
+  finished_at: entry.finished_at ?? null,
+  error: entry.error ?? null
+})
+
 const sanitizeDiffSummary = (payload: DiffSummaryPayload) => ({
   scope: payload.scope,
   session_id: payload.session_id ?? null,
@@ -392,6 +426,57 @@ server.setRequestHandler(ListToolsRequestSchema, async () => {
         },
         outputSchema: toolOutputSchemas.schaltwerk_create
       },
+      {
+        name: "schaltwerk_create_version_group",
+        description: `Create several sessions that work on the same prompt in parallel, as one version group. The first version uses base_name and later ones get a _v2, _v3, ... suffix; every version gets its own branch and worktree. Either all versions are created or none: if one fails, the others are removed again.`,
+        inputSchema: {
+          type: "object",
+          properties: {
+            base_name: {
+              type: "string",
+              description: "Name of the first version (alphanumeric, hyphens, underscores)"
+            },
+            prompt: {
+              type: "string",
+              description: "Initial prompt shared by every version"
+            },
+            count: {
+              type: "number",
+              minimum: 1,
+              maximum: 4,
+              description: "Number of versions to create (1-4)"
+            },
+            agent_configs: {
+              type: "array",
+              description: "Agent per version: omit for the project default, pass one entry for all versions, or one entry per version",
+              items: {
+                type: "object",
+                properties: {
+                  agent_type: {
+                    type: "string",
+                    enum: ["claude", "opencode", "gemini", "codex", "qwen", "droid", "amp", "kilo"]
+                  },
+                  skip_permissions: {
+                    type: "boolean"
+                  }
+                },
+                additionalProperties: false
+              }
+            },
+            base_branch: {
+              type: "string",
+              description: "Base branch for every version (default: current branch)"
+            },
+            label: {
+              type: "string",
+              description: "Label shown for the version group"
+            }
+          },
+          required: ["base_name", "prompt", "count"],
+          additionalProperties: false
+        },
+        outputSchema: toolOutputSchemas.schaltwerk_create_version_group
+      },
       {
         name: "schaltwerk_get_setup_script",
         description: `Fetch the project worktree setup script that runs once per new worktree before any agent starts. Always call this before modifying the script so you merge with the current contents (env copies, installs, etc.).`,
@@ -840,6 +925,69 @@ server.setRequestHandler(ListToolsRequestSchema, async () => {
         },
         outputSchema: toolOutputSchemas.schaltwerk_list_epics
       },
+      {
+        name: "schaltwerk_schedule_spec",
+        description: `Schedule an existing spec to start as a session at a later time. The spec starts once 'not_before' has passed and fewer than the configured number of sessions are running. If the project is not open at that time the spec starts when it is opened again, unless skip_if_late is set.`,
+        inputSchema: {
+          type: "object",
+          properties: {
+            spec_name: {
+              type: "string",
+              description: "Name of the spec to start"
+            },
+            not_before: {
+              type: "string",
+              description: "Earliest start time as an RFC 3339 timestamp with offset (e.g. '2026-05-04T18:00:00+02:00')"
+            },
+            base_branch: {
+              type: "string",
+              description: "Base branch for the session (default: project default branch)"
+            },
+            agent_type: {
+              type: "string",
+              enum: ["claude", "opencode", "gemini", "codex", "qwen", "droid", "amp", "kilo"],
+              description: "Agent to run once the spec starts"
+            },
+            skip_permissions: {
+              type: "boolean",
+              description: "Skip permission prompts for the started agent"
+            },
+            skip_if_late: {
+              type: "boolean",
+              description: "Skip the entry instead of starting it when its time passed while the project was closed (default false)"
+            }
+          },
+          required: ["spec_name", "not_before"],
+          additionalProperties: false
+        },
+        outputSchema: toolOutputSchemas.schaltwerk_schedule_spec
+      },
+      {
+        name: "schaltwerk_list_scheduled_specs",
+        description: `List scheduled spec starts in the current project, including finished entries and their status (pending, started, skipped, failed, cancelled).`,
+        inputSchema: {
+          type: "object",
+          properties: {},
+          additionalProperties: false
+        },
+        outputSchema: toolOutputSchemas.schaltwerk_list_scheduled_specs
+      },
+      {
+        name: "schaltwerk_cancel_scheduled_spec",
+        description: `Cancel a pending scheduled spec start. The spec itself is left untouched.`,
+        inputSchema: {
+          type: "object",
+          properties: {
+            id: {
+              type: "string",
+              description: "Id of the scheduled entry, as returned by schaltwerk_schedule_spec or schaltwerk_list_scheduled_specs"
+            }
+          },
+          required: ["id"],
+          additionalProperties: false
+        },
+        outputSchema: toolOutputSchemas.schaltwerk_cancel_scheduled_spec
+      },
       {
         name: "schaltwerk_prepare_merge",
         description: `Open a merge modal in the Schaltwerk UI for user review and confirmation. The modal is pre-filled with the provided commit message and merge mode. The user can review, edit, and confirm to merge. This tool does NOT merge directly - it requires user confirmation via the UI. Works for running and reviewed sessions. Spec sessions are not eligible for merging.`,
@@ -1128,6 +1276,37 @@ ${session.initial_prompt ? `- Initial Prompt: ${session.initial_prompt}` : ''}`
         break
       }
 
+      case "schaltwerk_create_version_group": {
+        const groupArgs = args as unknown as SchaltwerkCreateVersionGroupArgs
+        if (!groupArgs.base_name || groupArgs.base_name.trim().length === 0) {
+          throw new McpError(ErrorCode.InvalidParams, "'base_name' is required when invoking schaltwerk_create_version_group.")
+        }
+        if (!Number.isInteger(groupArgs.count) || groupArgs.count < 1) {
+          throw new McpError(ErrorCode.InvalidParams, "'count' must be a positive integer when invoking schaltwerk_create_version_group.")
+        }
+
+        const created = await bridge.createVersionGroup(groupArgs.base_name, groupArgs.count, {
+          prompt: groupArgs.prompt,
+          agentConfigs: groupArgs.agent_configs,
+          baseBranch: groupArgs.base_branch,
+          label: groupArgs.label
+        })
+        const structured = {
+          version_group_id: created.version_group_id,
+          sessions: created.sessions.map(session => ({
+            name: session.name,
+            branch: session.branch,
+            worktree_path: session.worktree_path,
+            parent_branch: session.parent_branch,
+            agent_type: session.original_agent_type ?? null,
+            version_number: session.version_number ?? null
+          }))
+        }
+        const summary = `Version group ${created.version_group_id} created with ${created.sessions.length} versions: ${created.sessions.map(s => s.name).join(', ')}`
+        response = buildStructuredResponse(structured, { summaryText: summary })
+        break
+      }
+
       case "schaltwerk_get_setup_script": {
         const payload = await bridge.getProjectSetupScript()
         const summary = payload.has_setup_script
@@ -1651,6 +1830,50 @@ ${cancelLine}`
         break
       }
 
+      case "schaltwerk_schedule_spec": {
+        const scheduleArgs = args as unknown as SchaltwerkScheduleSpecArgs
+        if (!scheduleArgs.spec_name || scheduleArgs.spec_name.trim().length === 0) {
+          throw new McpError(ErrorCode.InvalidParams, "'spec_name' is required when invoking schaltwerk_schedule_spec.")
+        }
+        if (!scheduleArgs.not_before) {
+          throw new McpError(ErrorCode.InvalidParams, "'not_before' is required when invoking schaltwerk_schedule_spec.")
+        }
+
+        const entry = await bridge.scheduleSpec(scheduleArgs.spec_name, scheduleArgs.not_before, {
+          baseBranch: scheduleArgs.base_branch,
+          agentType: scheduleArgs.agent_type,
+          skipPermissions: scheduleArgs.skip_permissions,
+          skipIfLate: scheduleArgs.skip_if_late
+        })
+        const structured = { scheduled: sanitizeScheduledSpec(entry) }
+        const summary = `Spec '${entry.spec_name}' scheduled for ${entry.not_before} (id: ${entry.id})`
+        response = buildStructuredResponse(structured, { summaryText: summary })
+        break
+      }
+
+      case "schaltwerk_list_scheduled_specs": {
+        const entries = await bridge.listScheduledSpecs()
+        const structured = { scheduled: entries.map(sanitizeScheduledSpec) }
+        const summary = entries.length === 0
+          ? 'No scheduled specs'
+          : `Scheduled specs (${entries.length}): ${entries.map(e => `${e.spec_name} @ ${e.not_before} [${e.status}]`).join(', ')}`
+        response = buildStructuredResponse(structured, { summaryText: summary, jsonFirst: true })
+        break
+      }
+
+      case "schaltwerk_cancel_scheduled_spec": {
+        const cancelArgs = args as unknown as SchaltwerkCancelScheduledSpecArgs
+        if (!cancelArgs.id) {
+          throw new McpError(ErrorCode.InvalidParams, "'id' is required when invoking schaltwerk_cancel_scheduled_spec.")
+        }
+
+        const entry = await bridge.cancelScheduledSpec(cancelArgs.id)
+        const structured = { scheduled: sanitizeScheduledSpec(entry) }
+        const summary = `Scheduled start of '${entry.spec_name}' cancelled`
+        response = buildStructuredResponse(structured, { summaryText: summary })
+        break
+      }
+
       case "schaltwerk_run_script": {
         const result = await bridge.executeProjectRunScript()
         const summary = result.success
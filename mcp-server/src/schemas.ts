@@ -17,6 +17,23 @@ const epicSchema = {
   additionalProperties: false,
 } as const
 
+const scheduledSpecSchema = {
+  type: 'object',
+  properties: {
+    id: { type: 'string' },
+    spec_name: { type: 'string' },
+    not_before: isoDateTime,
+    base_branch: nullableString,
+    agent_type: nullableString,
+    skip_if_late: { type: 'boolean' },
+    status: { enum: ['pending', 'started', 'skipped', 'failed', 'cancelled'] },
+    finished_at: nullableIsoDateTime,
+    error: nullableString,
+  },
+  required: ['id', 'spec_name', 'not_before', 'skip_if_late', 'status'],
+  additionalProperties: false,
+} as const
+
 const sessionSummarySchema = {
   type: 'object',
   properties: {
@@ -145,6 +162,32 @@ export const toolOutputSchemas = {
     additionalProperties: false,
   },
 
+  schaltwerk_create_version_group: {
+    $schema: draft2020,
+    type: 'object',
+    properties: {
+      version_group_id: { type: 'string' },
+      sessions: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            name: { type: 'string' },
+            branch: { type: 'string' },
+            worktree_path: { type: 'string' },
+            parent_branch: { type: 'string' },
+            agent_type: nullableString,
+            version_number: nullableNumber,
+          },
+          required: ['name', 'branch', 'worktree_path', 'parent_branch'],
+          additionalProperties: false,
+        },
+      },
+    },
+    required: ['version_group_id', 'sessions'],
+    additionalProperties: false,
+  },
+
   schaltwerk_list: {
     $schema: draft2020,
     type: 'object',
@@ -509,6 +552,36 @@ export const toolOutputSchemas = {
     required: ['epics'],
     additionalProperties: false,
   },
+  schaltwerk_schedule_spec: {
+    $schema: draft2020,
+    type: 'object',
+    properties: {
+      scheduled: scheduledSpecSchema,
+    },
+    required: ['scheduled'],
+    additionalProperties: false,
+  },
+  schaltwerk_list_scheduled_specs: {
+    $schema: draft2020,
+    type: 'object',
+    properties: {
+      scheduled: {
+        type: 'array',
+        items: scheduledSpecSchema,
+      },
+    },
+    required: ['scheduled'],
+    additionalProperties: false,
+  },
+  schaltwerk_cancel_scheduled_spec: {
+    $schema: draft2020,
+    type: 'object',
+    properties: {
+      scheduled: scheduledSpecSchema,
+    },
+    required: ['scheduled'],
+    additionalProperties: false,
+  },
 
   schaltwerk_prepare_merge: {
     $schema: draft2020,
@@ -25,6 +25,27 @@ const sampleStructuredOutputs: Record<string, any> = {
       ready_to_merge: false,
     },
   },
+  schaltwerk_create_version_group: {
+    version_group_id: '3f2c6c1e-group',
+    sessions: [
+      {
+        name: 'search',
+        branch: 'schaltwerk/search',
+        worktree_path: '/tmp/project/.schaltwerk/worktrees/search',
+        parent_branch: 'main',
+        agent_type: 'claude',
+        version_number: 1,
+      },
+      {
+        name: 'search_v2',
+        branch: 'schaltwerk/search_v2',
+        worktree_path: '/tmp/project/.schaltwerk/worktrees/search_v2',
+        parent_branch: 'main',
+        agent_type: 'codex',
+        version_number: 2,
+      },
+    ],
+  },
   schaltwerk_list: {
     sessions: [
       {
@@ -244,6 +265,47 @@ const sampleStructuredOutputs: Record<string, any> = {
       },
     ],
   },
+  schaltwerk_schedule_spec: {
+    scheduled: {
+      id: 'sched-1',
+      spec_name: 'nightly-refactor',
+      not_before: '2026-05-04T16:00:00Z',
+      base_branch: null,
+      agent_type: 'claude',
+      skip_if_late: true,
+      status: 'pending',
+      finished_at: null,
+      error: null,
+    },
+  },
+  schaltwerk_list_scheduled_specs: {
+    scheduled: [
+      {
+        id: 'sched-1',
+        spec_name: 'nightly-refactor',
+        not_before: '2026-05-04T16:00:00Z',
+        base_branch: 'main',
+        agent_type: null,
+        skip_if_late: false,
+        status: 'failed',
+        finished_at: '2026-05-04T16:00:30Z',
+        error: 'Spec not found',
+      },
+    ],
+  },
+  schaltwerk_cancel_scheduled_spec: {
+    scheduled: {
+      id: 'sched-2',
+      spec_name: 'docs-pass',
+      not_before: '2026-05-05T08:00:00+02:00',
+      base_branch: null,
+      agent_type: null,
+      skip_if_late: false,
+      status: 'cancelled',
+      finished_at: '2026-05-04T12:00:00Z',
+      error: null,
+    },
+  },
   schaltwerk_run_script: {
     success: true,
     command: 'bun run dev',
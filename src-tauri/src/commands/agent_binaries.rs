@@ -2,7 +2,10 @@ use crate::SETTINGS_MANAGER;
 use futures::future::join_all;
 use log::{debug, info};
 use schaltwerk::binary_detector::{BinaryDetector, DetectedBinary};
-use schaltwerk::services::AgentBinaryConfig;
+use schaltwerk::services::{
+    AGENT_PROBE_TIMEOUT, AgentBinaryConfig, AgentConfigHealth, VERSION_PROBE_TIMEOUT,
+    collect_agent_config_health, detect_agent_binary_version,
+};
 use tauri::async_runtime::spawn_blocking;
 
 pub async fn detect_agent_binaries_nonblocking(
@@ -14,6 +17,23 @@ pub async fn detect_agent_binaries_nonblocking(
         .map_err(|err| format!("Binary detection task failed for {agent_name}: {err}"))
 }
 
+/// Fills `config.version` by asking the effective binary for its version.
+async fn with_binary_version(mut config: AgentBinaryConfig) -> AgentBinaryConfig {
+    let probe = config.clone();
+    config.version = spawn_blocking(move || {
+        detect_agent_binary_version(&probe.agent_name, Some(&probe), VERSION_PROBE_TIMEOUT)
+    })
+    .await
+    .unwrap_or_else(|err| {
+        log::warn!(
+            "Version detection task failed for {}: {err}",
+            config.agent_name
+        );
+        None
+    });
+    config
+}
+
 #[tauri::command]
 pub async fn detect_agent_binaries(agent_name: String) -> Result<Vec<DetectedBinary>, String> {
     info!("Detecting binaries for agent: {agent_name}");
@@ -31,12 +51,14 @@ pub async fn detect_agent_binaries(agent_name: String) -> Result<Vec<DetectedBin
     // Run detection outside the mutex so startup-critical paths are not blocked.
     let detected_binaries = detect_agent_binaries_nonblocking(&agent_name).await?;
 
-    let config = AgentBinaryConfig {
+    let config = with_binary_version(AgentBinaryConfig {
         agent_name: agent_name.clone(),
         custom_path: existing_custom_path,
         auto_detect: true,
         detected_binaries: detected_binaries.clone(),
-    };
+        version: None,
+    })
+    .await;
 
     {
         let mut settings = settings_manager.lock().await;
@@ -68,6 +90,7 @@ pub async fn get_agent_binary_config(agent_name: String) -> Result<AgentBinaryCo
             custom_path: None,
             auto_detect: true,
             detected_binaries,
+            version: None,
         })
     }
 }
@@ -139,12 +162,14 @@ pub async fn set_agent_binary_path(agent_name: String, path: Option<String>) ->
         detect_agent_binaries_nonblocking(&agent_name).await?
     };
 
-    let config = AgentBinaryConfig {
+    let config = with_binary_version(AgentBinaryConfig {
         agent_name,
         custom_path: processed_path,
         auto_detect: path.is_none(),
         detected_binaries,
-    };
+        version: None,
+    })
+    .await;
 
     let mut settings = settings_manager.lock().await;
     settings.set_agent_binary_config(config)
@@ -207,26 +232,31 @@ pub async fn get_all_agent_binary_configs() -> Result<Vec<AgentBinaryConfig>, St
                 settings.get_agent_binary_config(&agent_name)
             };
 
-            if let Some(config) = existing_config {
-                Ok::<AgentBinaryConfig, String>(config)
-            } else {
-                let detected_binaries = detect_agent_binaries_nonblocking(&agent_name).await?;
-                let config = AgentBinaryConfig {
-                    agent_name: agent_name.clone(),
-                    custom_path: None,
-                    auto_detect: true,
-                    detected_binaries,
-                };
-
-                {
-                    let mut settings = settings_manager.lock().await;
-                    if let Err(e) = settings.set_agent_binary_config(config.clone()) {
-                        log::warn!("Failed to save binary config for {agent_name}: {e}");
-                    }
+            // Configs saved before versions were tracked get one detected and cached here.
+            let config = match existing_config {
+                Some(config) if config.version.is_some() => return Ok(config),
+                Some(config) => with_binary_version(config).await,
+                None => {
+                    let detected_binaries = detect_agent_binaries_nonblocking(&agent_name).await?;
+                    with_binary_version(AgentBinaryConfig {
+                        agent_name: agent_name.clone(),
+                        custom_path: None,
+                        auto_detect: true,
+                        detected_binaries,
+                        version: None,
+                    })
+                    .await
                 }
+            };
 
-                Ok(config)
+            {
+                let mut settings = settings_manager.lock().await;
+                if let Err(e) = settings.set_agent_binary_config(config.clone()) {
+                    log::warn!("Failed to save binary config for {agent_name}: {e}");
+                }
             }
+
+            Ok::<AgentBinaryConfig, String>(config)
         }
     });
 
@@ -265,12 +295,14 @@ pub async fn detect_all_agent_binaries() -> Result<Vec<AgentBinaryConfig>, Strin
             let detected_binaries = detect_agent_binaries_nonblocking(&agent_name).await?;
             let auto_detect = custom_path.is_none();
 
-            let config = AgentBinaryConfig {
+            let config = with_binary_version(AgentBinaryConfig {
                 agent_name: agent_name.clone(),
                 custom_path: custom_path.clone(),
                 auto_detect,
                 detected_binaries,
-            };
+                version: None,
+            })
+            .await;
 
             {
                 let mut settings = settings_manager.lock().await;
@@ -311,12 +343,14 @@ pub async fn refresh_agent_binary_detection(
 
     let detected_binaries = detect_agent_binaries_nonblocking(&agent_name).await?;
 
-    let config = AgentBinaryConfig {
+    let config = with_binary_version(AgentBinaryConfig {
         agent_name,
         custom_path: custom_path.clone(),
         auto_detect: custom_path.is_none(),
         detected_binaries,
-    };
+        version: None,
+    })
+    .await;
 
     {
         let mut settings = settings_manager.lock().await;
@@ -325,3 +359,26 @@ pub async fn refresh_agent_binary_detection(
 
     Ok(config)
 }
+
+#[tauri::command]
+pub async fn agent_config_health() -> Result<Vec<AgentConfigHealth>, String> {
+    info!("Computing agent binary configuration health");
+
+    let settings_manager = SETTINGS_MANAGER
+        .get()
+        .cloned()
+        .ok_or_else(|| "Settings manager not initialized".to_string())?;
+
+    let known_agents: Vec<String> =
+        schaltwerk::domains::agents::manifest::AgentManifest::supported_agents();
+
+    let agents = {
+        let settings = settings_manager.lock().await;
+        known_agents
+            .iter()
+            .map(|agent| (agent.clone(), settings.get_agent_binary_config(agent)))
+            .collect::<Vec<_>>()
+    };
+
+    Ok(collect_agent_config_health(agents, &known_agents, AGENT_PROBE_TIMEOUT).await)
+}
@@ -1,28 +1,31 @@
 use schaltwerk::services::{
-    CommitFileChange, HistoryProviderSnapshot, get_commit_file_changes as fetch_commit_files,
-    get_git_history as fetch_git_history, get_git_history_with_head as fetch_git_history_with_head,
+    CommitFileChange, HistoryFilter, HistoryProviderSnapshot,
+    get_commit_file_changes as fetch_commit_files,
+    get_git_history_with_head as fetch_git_history_with_head,
 };
 use std::path::Path;
 
+/// Returns a page of the commit graph. `author` is a case-insensitive substring of the
+/// author name or email and `path` a pathspec the commits must touch; pass the previous
+/// page's `nextCursor` as `cursor` to continue.
 #[tauri::command]
 pub fn get_git_graph_history(
     repo_path: String,
     limit: Option<usize>,
     cursor: Option<String>,
     since_head: Option<String>,
+    author: Option<String>,
+    path: Option<String>,
 ) -> Result<HistoryProviderSnapshot, String> {
-    let path = Path::new(&repo_path);
-    let cursor_ref = cursor.as_deref();
-    let since_head_ref = since_head.as_deref();
-
-    let use_since_head = since_head_ref.is_some();
-    let result = if use_since_head {
-        fetch_git_history_with_head(path, limit, cursor_ref, since_head_ref)
-    } else {
-        fetch_git_history(path, limit, cursor_ref)
-    };
-
-    result.map_err(|e| format!("Failed to get git history: {e}"))
+    let filter = HistoryFilter { author, path };
+    fetch_git_history_with_head(
+        Path::new(&repo_path),
+        limit,
+        cursor.as_deref(),
+        since_head.as_deref(),
+        &filter,
+    )
+    .map_err(|e| format!("Failed to get git history: {e}"))
 }
 
 #[tauri::command]
@@ -1,3 +1,5 @@
+use crate::commands::schaltwerk_core::events::emit_draft_pr_failed;
+use crate::commands::sessions_refresh::{SessionsRefreshReason, request_sessions_refresh};
 use crate::get_project_manager;
 use log::{error, info, warn};
 use schaltwerk::domains::git::service::rename_branch;
@@ -6,12 +8,15 @@ use schaltwerk::project_manager::ProjectManager;
 use schaltwerk::schaltwerk_core::db_project_config::{ProjectConfigMethods, ProjectGithubConfig};
 use schaltwerk::shared::session_metadata_gateway::SessionMetadataGateway;
 use schaltwerk::services::{
-    CommandRunner, CreatePrOptions, CreateSessionPrOptions, GitHubCli, GitHubCliError,
-    GitHubIssueComment, GitHubIssueDetails, GitHubIssueLabel, GitHubIssueSummary,
-    GitHubPrDetails, GitHubPrReview, GitHubPrReviewComment, GitHubPrSummary,
-    GitHubStatusCheck, MergeMode, PrCommitMode, PrContent, sanitize_branch_name,
+    CommandRunner, CreateDraftPrOptions, CreatePrOptions, CreateSessionPrOptions, GitHubCli,
+    GitHubCliError, GitHubIssueComment, GitHubIssueDetails, GitHubIssueLabel, GitHubIssueSummary,
+    GitHubPrDetails, GitHubPrReview, GitHubPrReviewComment, GitHubPrStatus, GitHubPrSummary,
+    GitHubStatusCheck, MergeMode, PrChecksOutcome, PrCommitMode, PrContent, github_lookup_cache,
+    IssueSessionOptions, issue_session_name, pr_status_poll_delay,
+    pr_status_poll_interval_from_env, render_issue_prompt, sanitize_branch_name,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::AppHandle;
@@ -39,6 +44,30 @@ pub struct GitHubPrPayload {
     pub url: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubLookupCacheStatsPayload {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub ttl_secs: u64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubPrStatusPayload {
+    pub project_path: String,
+    pub session_name: String,
+    pub pr_number: u64,
+    pub pr_url: Option<String>,
+    pub state: String,
+    pub review_decision: Option<String>,
+    pub checks: String,
+    pub passing_checks: usize,
+    pub failing_checks: usize,
+    pub pending_checks: usize,
+}
+
 const ISSUE_SEARCH_DEFAULT_LIMIT: usize = 50;
 
 #[derive(Debug, Serialize, Clone)]
@@ -141,6 +170,7 @@ pub struct CreateReviewedPrArgs {
     pub pr_body: Option<String>,
     pub target_branch: Option<String>,
     pub custom_branch_name: Option<String>,
+    pub draft: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -227,13 +257,20 @@ pub async fn github_connect_project(app: AppHandle) -> Result<GitHubRepositoryPa
     {
         let core = project.schaltwerk_core.write().await;
         let db = core.database();
+        let auto_pr_on_review = db
+            .get_project_github_config(&project_path)
+            .ok()
+            .flatten()
+            .is_some_and(|cfg| cfg.auto_pr_on_review);
         let config = ProjectGithubConfig {
             repository: repo_info.name_with_owner.clone(),
             default_branch: repo_info.default_branch.clone(),
+            auto_pr_on_review,
         };
         db.set_project_github_config(&project_path, &config)
             .map_err(|e| format!("Failed to store GitHub repository config: {e}"))?;
     }
+    github_lookup_cache().invalidate();
 
     let payload = GitHubRepositoryPayload {
         name_with_owner: repo_info.name_with_owner,
@@ -245,6 +282,17 @@ pub async fn github_connect_project(app: AppHandle) -> Result<GitHubRepositoryPa
     Ok(payload)
 }
 
+#[tauri::command]
+pub async fn github_get_lookup_cache_stats() -> Result<GitHubLookupCacheStatsPayload, String> {
+    let stats = github_lookup_cache().stats();
+    Ok(GitHubLookupCacheStatsPayload {
+        entries: stats.entries,
+        hits: stats.hits,
+        misses: stats.misses,
+        ttl_secs: stats.ttl.as_secs(),
+    })
+}
+
 #[tauri::command]
 pub async fn github_create_reviewed_pr(
     _app: AppHandle,
@@ -324,6 +372,7 @@ pub async fn github_create_reviewed_pr(
             content,
             target_branch: args.target_branch.as_deref(),
             custom_branch_name: args.custom_branch_name.as_deref(),
+            draft: args.draft.unwrap_or(false),
         })
         .map_err(|err| {
             error!("GitHub PR creation failed: {err}");
@@ -424,6 +473,12 @@ pub async fn github_create_session_pr_impl(
     let pr_mode = match args.mode {
         MergeMode::Squash => PrCommitMode::Squash,
         MergeMode::Reapply => PrCommitMode::Reapply,
+        MergeMode::RebaseContinue => {
+            return Err(
+                "The rebase_continue mode does not apply to pull requests; use squash or reapply"
+                    .to_string(),
+            );
+        }
     };
 
     let session_name_after = args.session_name.clone();
@@ -536,6 +591,196 @@ pub async fn github_get_issue_details(
     github_get_issue_details_impl(Arc::clone(&manager), cli, number).await
 }
 
+#[tauri::command]
+pub async fn github_issue_to_spec(
+    app: AppHandle,
+    issue_number: u64,
+    include_comments: bool,
+) -> Result<String, String> {
+    let manager = get_project_manager().await;
+    let cli = GitHubCli::new();
+    let spec_name =
+        github_issue_to_spec_impl(Arc::clone(&manager), cli, issue_number, include_comments)
+            .await?;
+    request_sessions_refresh(&app, SessionsRefreshReason::SpecSync);
+    Ok(spec_name)
+}
+
+#[tauri::command]
+pub async fn github_create_session_from_issue(
+    app: AppHandle,
+    issue_number: u64,
+    base_branch: Option<String>,
+    agent_type: Option<String>,
+    skip_permissions: Option<bool>,
+    include_comments: Option<bool>,
+) -> Result<String, String> {
+    let manager = get_project_manager().await;
+    let cli = GitHubCli::new();
+    let options = IssueSessionOptions {
+        base_branch,
+        agent_type,
+        skip_permissions,
+        include_comments: include_comments.unwrap_or(false),
+    };
+    let session_name =
+        github_create_session_from_issue_impl(Arc::clone(&manager), cli, issue_number, options)
+            .await?;
+    request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
+    Ok(session_name)
+}
+
+#[tauri::command]
+pub async fn github_set_auto_pr_on_review(enabled: bool) -> Result<(), String> {
+    let project_manager = get_project_manager().await;
+    let project = project_manager
+        .current_project()
+        .await
+        .map_err(|e| format!("No active project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+    let mut config = db
+        .get_project_github_config(&project.path)
+        .map_err(|e| format!("Failed to load GitHub project config: {e}"))?
+        .ok_or_else(repo_not_connected_error)?;
+    config.auto_pr_on_review = enabled;
+    db.set_project_github_config(&project.path, &config)
+        .map_err(|e| format!("Failed to store GitHub repository config: {e}"))
+}
+
+/// Runs the draft-PR-on-review flow for a session that was just marked reviewed.
+/// Failures are reported as `GitOperationFailed` events; the reviewed state is kept.
+pub async fn open_draft_pr_after_review(app: AppHandle, session_name: String) {
+    let manager = get_project_manager().await;
+    let cli = GitHubCli::new();
+    match open_draft_pr_after_review_impl(manager, cli, &session_name).await {
+        Ok(Some(pr)) => {
+            let payload = AutoPrCreatedPayload {
+                session_name,
+                branch: pr.branch,
+                url: pr.url,
+            };
+            if let Err(e) = crate::events::emit_event(
+                &app,
+                crate::events::SchaltEvent::AutoPrCreated,
+                &payload,
+            ) {
+                warn!("Failed to emit AutoPrCreated event: {e}");
+            }
+        }
+        Ok(None) => {}
+        Err(failure) => {
+            warn!(
+                "Automatic draft PR for session '{session_name}' failed: {}",
+                failure.error
+            );
+            emit_draft_pr_failed(
+                &app,
+                &session_name,
+                &failure.session_branch,
+                &failure.parent_branch,
+                &failure.error,
+            );
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoPrCreatedPayload {
+    pub session_name: String,
+    pub branch: String,
+    pub url: String,
+}
+
+#[derive(Debug)]
+struct AutoPrFailure {
+    session_branch: String,
+    parent_branch: String,
+    error: String,
+}
+
+async fn open_draft_pr_after_review_impl<R: CommandRunner + 'static>(
+    project_manager: Arc<ProjectManager>,
+    cli: GitHubCli<R>,
+    session_name: &str,
+) -> Result<Option<GitHubPrPayload>, AutoPrFailure> {
+    let failure = |error: String| AutoPrFailure {
+        session_branch: String::new(),
+        parent_branch: String::new(),
+        error,
+    };
+
+    let project = project_manager
+        .current_project()
+        .await
+        .map_err(|e| failure(format!("No active project: {e}")))?;
+
+    let (config, session) = {
+        let core = project.schaltwerk_core.read().await;
+        let config = core
+            .database()
+            .get_project_github_config(&project.path)
+            .map_err(|e| failure(format!("Failed to load GitHub project config: {e}")))?;
+        let Some(config) = config.filter(|cfg| cfg.auto_pr_on_review) else {
+            return Ok(None);
+        };
+        let session = core
+            .session_manager()
+            .get_session(session_name)
+            .map_err(|e| failure(format!("Session not found: {e}")))?;
+        (config, session)
+    };
+
+    let title = session
+        .display_name
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| session.name.clone());
+    let body = session
+        .spec_content
+        .clone()
+        .or_else(|| session.initial_prompt.clone())
+        .unwrap_or_default();
+    let session_branch = session.branch.clone();
+    let parent_branch = session.parent_branch.clone();
+    let project_path = project.path.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        cli.ensure_installed().map_err(format_cli_error)?;
+        cli.create_draft_pr(CreateDraftPrOptions {
+            repo_path: &project_path,
+            worktree_path: &session.worktree_path,
+            session_slug: &session.name,
+            branch: &session.branch,
+            base_branch: &session.parent_branch,
+            title: &title,
+            body: &body,
+            repository: Some(&config.repository),
+        })
+        .map_err(format_cli_error)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))
+    .and_then(|inner| inner);
+
+    match result {
+        Ok(pr) => {
+            info!("Opened draft PR for reviewed session '{session_name}': {}", pr.url);
+            Ok(Some(GitHubPrPayload {
+                branch: pr.branch,
+                url: pr.url,
+            }))
+        }
+        Err(error) => Err(AutoPrFailure {
+            session_branch,
+            parent_branch,
+            error,
+        }),
+    }
+}
+
 #[tauri::command]
 pub async fn github_search_prs(
     _app: AppHandle,
@@ -714,6 +959,165 @@ pub async fn github_get_pr_feedback(
     github_get_pr_feedback_impl(Arc::clone(&manager), cli, pr_number).await
 }
 
+#[tauri::command]
+pub async fn github_mark_pr_ready(_app: AppHandle, pr_number: u64) -> Result<(), String> {
+    let project = resolve_project(get_project_manager().await).await?;
+    let cli = GitHubCli::new();
+
+    tokio::task::spawn_blocking(move || {
+        cli.ensure_installed().map_err(format_cli_error)?;
+        cli.mark_pr_ready(
+            &pr_number.to_string(),
+            project.repository.as_deref(),
+            &project.path,
+        )
+        .map_err(|err| {
+            error!("Marking GitHub PR #{pr_number} ready failed: {err}");
+            format_cli_error(err)
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+struct WatchedPr {
+    session_name: String,
+    pr_number: u64,
+    pr_url: Option<String>,
+}
+
+/// Background task that polls CI and review status of open session PRs in the current
+/// project and emits [`SchaltEvent::GitHubPrStatusChanged`] whenever a PR changes.
+/// Merged or closed PRs are dropped from polling, and rate-limit failures back off.
+pub async fn run_pr_status_poller(app: AppHandle) {
+    let interval = pr_status_poll_interval_from_env();
+    let mut rate_limited = 0u32;
+    let mut last_seen: HashMap<(PathBuf, u64), GitHubPrStatusPayload> = HashMap::new();
+    let mut finished: HashSet<(PathBuf, u64)> = HashSet::new();
+
+    loop {
+        tokio::time::sleep(pr_status_poll_delay(interval, rate_limited)).await;
+
+        let Some((project, watched)) = collect_watched_prs(&finished).await else {
+            continue;
+        };
+        if watched.is_empty() {
+            continue;
+        }
+
+        let cli = GitHubCli::new();
+        let project_path = project.path.clone();
+        let repository = project.repository.clone();
+        let polled = tokio::task::spawn_blocking(move || {
+            let mut results = Vec::new();
+            for pr in watched {
+                match cli.get_pr_status(&project_path, pr.pr_number, repository.as_deref()) {
+                    Ok(status) => results.push((pr, status)),
+                    Err(err) if err.is_rate_limited() => return (results, true),
+                    Err(err) => warn!("Polling status of PR #{} failed: {err}", pr.pr_number),
+                }
+            }
+            (results, false)
+        })
+        .await;
+
+        let (results, hit_rate_limit) = match polled {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                warn!("PR status poll task failed: {e}");
+                continue;
+            }
+        };
+
+        if hit_rate_limit {
+            rate_limited = rate_limited.saturating_add(1);
+            warn!(
+                "GitHub rate limit hit while polling PR status; next poll in {}s",
+                pr_status_poll_delay(interval, rate_limited).as_secs()
+            );
+        } else {
+            rate_limited = 0;
+        }
+
+        for (pr, status) in results {
+            let key = (project.path.clone(), pr.pr_number);
+            if !status.is_open() {
+                finished.insert(key.clone());
+            }
+            let payload = map_pr_status_payload(&project.path, pr, status);
+            if last_seen.get(&key) == Some(&payload) {
+                continue;
+            }
+            if let Err(e) = emit_event(&app, SchaltEvent::GitHubPrStatusChanged, &payload) {
+                warn!("Failed to emit PR status event: {e}");
+            }
+            last_seen.insert(key, payload);
+        }
+    }
+}
+
+async fn collect_watched_prs(
+    finished: &HashSet<(PathBuf, u64)>,
+) -> Option<(ResolvedProject, Vec<WatchedPr>)> {
+    let project = get_project_manager().await.current_project().await.ok()?;
+    let core = project.schaltwerk_core.read().await;
+    let config = core
+        .database()
+        .get_project_github_config(&project.path)
+        .ok()
+        .flatten()?;
+    let sessions = core
+        .session_manager()
+        .list_sessions()
+        .map_err(|e| warn!("PR status poller could not list sessions: {e}"))
+        .ok()?;
+
+    let watched = sessions
+        .into_iter()
+        .filter_map(|session| {
+            let pr_number = u64::try_from(session.pr_number?).ok()?;
+            (!finished.contains(&(project.path.clone(), pr_number))).then_some(WatchedPr {
+                session_name: session.name,
+                pr_number,
+                pr_url: session.pr_url,
+            })
+        })
+        .collect();
+
+    Some((
+        ResolvedProject {
+            path: project.path.clone(),
+            repository: Some(config.repository),
+        },
+        watched,
+    ))
+}
+
+fn map_pr_status_payload(
+    project_path: &std::path::Path,
+    pr: WatchedPr,
+    status: GitHubPrStatus,
+) -> GitHubPrStatusPayload {
+    let checks = match status.checks {
+        PrChecksOutcome::Passing => "passing",
+        PrChecksOutcome::Failing => "failing",
+        PrChecksOutcome::Pending => "pending",
+        PrChecksOutcome::NoChecks => "none",
+    };
+    GitHubPrStatusPayload {
+        project_path: project_path.display().to_string(),
+        session_name: pr.session_name,
+        pr_number: pr.pr_number,
+        pr_url: pr.pr_url,
+        state: status.state,
+        review_decision: status.review_decision,
+        checks: checks.to_string(),
+        passing_checks: status.passing_checks,
+        failing_checks: status.failing_checks,
+        pending_checks: status.pending_checks,
+    }
+}
+
 pub async fn github_get_pr_feedback_impl<R: CommandRunner + 'static>(
     project_manager: Arc<ProjectManager>,
     cli: GitHubCli<R>,
@@ -875,6 +1279,77 @@ async fn github_get_issue_details_impl<R: CommandRunner + 'static>(
     Ok(map_issue_details_payload(details))
 }
 
+async fn github_issue_to_spec_impl<R: CommandRunner + 'static>(
+    project_manager: Arc<ProjectManager>,
+    cli: GitHubCli<R>,
+    number: u64,
+    include_comments: bool,
+) -> Result<String, String> {
+    let details = fetch_issue_for_local_work(Arc::clone(&project_manager), cli, number).await?;
+
+    let content = render_issue_prompt(&details, include_comments);
+    let name = issue_session_name(details.number, &details.title);
+
+    let project = project_manager
+        .current_project()
+        .await
+        .map_err(|e| format!("No active project: {e}"))?;
+    let core = project.schaltwerk_core.write().await;
+    let spec = core
+        .session_manager()
+        .create_spec_session_with_agent(&name, &content, None, Some(&details.title), None)
+        .map_err(|e| format!("Failed to create spec from issue #{number}: {e}"))?;
+
+    info!("Created spec '{}' from GitHub issue #{number}", spec.name);
+    Ok(spec.name)
+}
+
+async fn github_create_session_from_issue_impl<R: CommandRunner + 'static>(
+    project_manager: Arc<ProjectManager>,
+    cli: GitHubCli<R>,
+    number: u64,
+    options: IssueSessionOptions,
+) -> Result<String, String> {
+    let details = fetch_issue_for_local_work(Arc::clone(&project_manager), cli, number).await?;
+
+    let project = project_manager
+        .current_project()
+        .await
+        .map_err(|e| format!("No active project: {e}"))?;
+    let core = project.schaltwerk_core.write().await;
+    let session = core
+        .session_manager()
+        .create_session_from_issue(&details, &options)
+        .map_err(|e| format!("Failed to create session from issue #{number}: {e}"))?;
+
+    info!(
+        "Created session '{}' on branch '{}' from GitHub issue #{number}",
+        session.name, session.branch
+    );
+    Ok(session.name)
+}
+
+/// Fetches an issue with its comments for turning it into a spec or session; fails with
+/// the "not connected" error when the project has no GitHub repository configured.
+async fn fetch_issue_for_local_work<R: CommandRunner + 'static>(
+    project_manager: Arc<ProjectManager>,
+    cli: GitHubCli<R>,
+    number: u64,
+) -> Result<GitHubIssueDetails, String> {
+    let project = resolve_project(project_manager).await?;
+
+    tokio::task::spawn_blocking(move || {
+        cli.ensure_installed().map_err(format_cli_error)?;
+        cli.get_issue_with_comments(&project.path, number, project.repository.as_deref())
+            .map_err(|err| {
+                error!("GitHub issue #{number} fetch failed: {err}");
+                format_cli_error(err)
+            })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
 async fn github_search_prs_impl<R: CommandRunner + 'static>(
     project_manager: Arc<ProjectManager>,
     cli: GitHubCli<R>,
@@ -1145,6 +1620,11 @@ fn emit_status(app: &AppHandle, status: &GitHubStatusPayload) -> Result<(), Stri
         .map_err(|e| format!("Failed to emit GitHub status event: {e}"))
 }
 
+fn is_auth_failure(output: &str) -> bool {
+    let lowered = output.to_lowercase();
+    lowered.contains("gh auth login") || lowered.contains("not logged into")
+}
+
 fn repo_not_connected_error() -> String {
     "Project is not connected to a GitHub repository. Connect the project in Settings and try again."
         .to_string()
@@ -1166,6 +1646,11 @@ fn format_cli_error(err: GitHubCliError) -> String {
                 "GitHub CLI (gh) is not installed. See https://github.com/cli/cli/blob/trunk/docs/install_linux.md".to_string()
             }
         }
+        GitHubCliError::CommandFailed { stderr, stdout, .. }
+            if is_auth_failure(&stderr) || is_auth_failure(&stdout) =>
+        {
+            "GitHub CLI is not authenticated. Run `gh auth login` and try again.".to_string()
+        }
         GitHubCliError::CommandFailed {
             program,
             args,
@@ -1201,7 +1686,7 @@ mod tests {
     use super::*;
     use git2::Repository;
     use schaltwerk::project_manager::ProjectManager;
-    use schaltwerk::services::CommandOutput;
+    use schaltwerk::services::{CommandOutput, SessionMethods};
     use std::collections::VecDeque;
     use std::io;
     use std::path::{Path, PathBuf};
@@ -1302,6 +1787,7 @@ mod tests {
             let config = ProjectGithubConfig {
                 repository: "example/repo".to_string(),
                 default_branch: "main".to_string(),
+                auto_pr_on_review: true,
             };
             db.set_project_github_config(&project.path, &config)
                 .expect("set github config");
@@ -1447,4 +1933,260 @@ mod tests {
         );
         assert_eq!(runner.calls().len(), 2);
     }
+
+    #[test]
+    fn issue_spec_rendering_includes_backlink_labels_and_comments() {
+        let details = GitHubIssueDetails {
+            number: 1234,
+            title: "Fix login: redirect loop on Safari!".to_string(),
+            url: "https://github.com/example/repo/issues/1234".to_string(),
+            body: "Users get stuck.\n".to_string(),
+            labels: vec![GitHubIssueLabel {
+                name: "bug".to_string(),
+                color: None,
+            }],
+            comments: vec![GitHubIssueComment {
+                author_login: Some("octocat".to_string()),
+                created_at: "2024-01-02T00:00:00Z".to_string(),
+                body: "Reproduced on 17.2".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            issue_session_name(details.number, &details.title),
+            "issue-1234-fix-login-redirect-loop-on-safari"
+        );
+        assert_eq!(issue_session_name(7, "???"), "issue-7");
+        assert!(issue_session_name(8, &"word ".repeat(30)).len() <= "issue-8-".len() + 40);
+
+        let with_comments = render_issue_prompt(&details, true);
+        assert!(with_comments.starts_with("# Fix login: redirect loop on Safari!\n"));
+        assert!(with_comments.contains("[#1234](https://github.com/example/repo/issues/1234)"));
+        assert!(with_comments.contains("Labels: `bug`"));
+        assert!(with_comments.contains("### @octocat (2024-01-02T00:00:00Z)\n\nReproduced on 17.2"));
+
+        let without_comments = render_issue_prompt(&details, false);
+        assert!(!without_comments.contains("## Comments"));
+        assert!(without_comments.contains("Users get stuck."));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn github_create_session_from_issue_impl_requires_repository_connection() {
+        let runner = MockRunner::default();
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let manager = Arc::new(ProjectManager::new());
+        let temp = TempDir::new().unwrap();
+        init_repo(temp.path());
+        let _home_guard = TempHomeGuard::new();
+        manager
+            .switch_to_project(temp.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let err = github_create_session_from_issue_impl(
+            Arc::clone(&manager),
+            cli,
+            42,
+            IssueSessionOptions::default(),
+        )
+        .await
+        .expect_err("should require repo connection");
+
+        assert_eq!(err, repo_not_connected_error());
+        assert!(runner.calls().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn github_issue_to_spec_impl_creates_spec_from_issue() {
+        let runner = MockRunner::default();
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: "gh version 2.0".to_string(),
+            stderr: String::new(),
+        }));
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: "{\"number\":42,\"title\":\"Fix login\",\"url\":\"https://github.com/example/repo/issues/42\",\"body\":\"Steps\",\"labels\":[],\"comments\":[{\"author\":{\"login\":\"octocat\"},\"createdAt\":\"2024-01-02T00:00:00Z\",\"body\":\"Confirm\"}]}".to_string(),
+            stderr: String::new(),
+        }));
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let manager = Arc::new(ProjectManager::new());
+        let temp = TempDir::new().unwrap();
+        init_repo(temp.path());
+        let _home_guard = configure_repo(&manager, temp.path()).await;
+
+        let name = github_issue_to_spec_impl(Arc::clone(&manager), cli, 42, true)
+            .await
+            .expect("spec created");
+        assert_eq!(name, "issue-42-fix-login");
+
+        let project = manager.current_project().await.unwrap();
+        let core = project.schaltwerk_core.read().await;
+        let spec = core.session_manager().get_spec(&name).unwrap();
+        assert!(
+            spec.content
+                .contains("https://github.com/example/repo/issues/42")
+        );
+        assert!(spec.content.contains("Confirm"));
+        assert_eq!(spec.display_name.as_deref(), Some("Fix login"));
+        assert_eq!(runner.calls().len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn github_issue_to_spec_impl_reports_missing_authentication() {
+        let runner = MockRunner::default();
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: "gh version 2.0".to_string(),
+            stderr: String::new(),
+        }));
+        runner.push_response(Ok(CommandOutput {
+            status: Some(4),
+            stdout: String::new(),
+            stderr: "To get started with GitHub CLI, please run:  gh auth login".to_string(),
+        }));
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let manager = Arc::new(ProjectManager::new());
+        let temp = TempDir::new().unwrap();
+        init_repo(temp.path());
+        let _home_guard = configure_repo(&manager, temp.path()).await;
+
+        let err = github_issue_to_spec_impl(Arc::clone(&manager), cli, 42, false)
+            .await
+            .expect_err("should surface auth failure");
+
+        assert_eq!(
+            err,
+            "GitHub CLI is not authenticated. Run `gh auth login` and try again."
+        );
+    }
+
+    fn init_repo_with_commit(path: &Path) {
+        init_repo(path);
+        let repo = Repository::open(path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        std::fs::write(path.join("README.md"), "# Test").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+    }
+
+    fn ok_output(stdout: &str) -> io::Result<CommandOutput> {
+        Ok(CommandOutput {
+            status: Some(0),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn draft_pr_after_review_pushes_branch_and_opens_draft() {
+        let runner = MockRunner::default();
+        runner.push_response(ok_output("gh version 2.0"));
+        runner.push_response(ok_output(""));
+        runner.push_response(ok_output(""));
+        runner.push_response(ok_output("https://github.com/example/repo/pull/7\n"));
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let manager = Arc::new(ProjectManager::new());
+        let temp = TempDir::new().unwrap();
+        init_repo_with_commit(temp.path());
+        let _home_guard = configure_repo(&manager, temp.path()).await;
+
+        let session = {
+            let project = manager.current_project().await.unwrap();
+            let core = project.schaltwerk_core.write().await;
+            let session = core
+                .session_manager()
+                .create_session("auto-pr", Some("Implement the widget"), None)
+                .unwrap();
+            core.database()
+                .update_session_display_name(&session.id, "Widget support")
+                .unwrap();
+            core.session_manager().mark_session_ready("auto-pr").unwrap();
+            session
+        };
+
+        let pr = open_draft_pr_after_review_impl(Arc::clone(&manager), cli, "auto-pr")
+            .await
+            .expect("draft PR created")
+            .expect("auto PR enabled");
+        assert_eq!(pr.url, "https://github.com/example/repo/pull/7");
+        assert_eq!(pr.branch, session.branch);
+
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[1].program, "git");
+        assert_eq!(
+            calls[1].args,
+            vec![
+                "push".to_string(),
+                "--no-verify".to_string(),
+                "origin".to_string(),
+                format!("HEAD:refs/heads/{}", session.branch),
+            ]
+        );
+        let pr_args = &calls[3].args;
+        assert_eq!(&pr_args[..2], &["pr".to_string(), "create".to_string()]);
+        assert!(pr_args.contains(&"--draft".to_string()));
+        let value_after = |flag: &str| {
+            let index = pr_args.iter().position(|arg| arg == flag).unwrap();
+            pr_args[index + 1].clone()
+        };
+        assert_eq!(value_after("--title"), "Widget support");
+        assert_eq!(value_after("--body"), "Implement the widget");
+        assert_eq!(value_after("--base"), session.parent_branch);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn draft_pr_push_failure_keeps_session_reviewed() {
+        let runner = MockRunner::default();
+        runner.push_response(ok_output("gh version 2.0"));
+        runner.push_response(Ok(CommandOutput {
+            status: Some(128),
+            stdout: String::new(),
+            stderr: "fatal: could not read Username for 'https://github.com'".to_string(),
+        }));
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let manager = Arc::new(ProjectManager::new());
+        let temp = TempDir::new().unwrap();
+        init_repo_with_commit(temp.path());
+        let _home_guard = configure_repo(&manager, temp.path()).await;
+
+        {
+            let project = manager.current_project().await.unwrap();
+            let core = project.schaltwerk_core.write().await;
+            core.session_manager()
+                .create_session("push-fails", None, None)
+                .unwrap();
+            core.session_manager()
+                .mark_session_ready("push-fails")
+                .unwrap();
+        }
+
+        let failure = open_draft_pr_after_review_impl(Arc::clone(&manager), cli, "push-fails")
+            .await
+            .expect_err("push failure should surface");
+        assert!(failure.error.contains("could not read Username"));
+        assert!(!failure.session_branch.is_empty());
+        assert_eq!(runner.calls().len(), 2);
+
+        let project = manager.current_project().await.unwrap();
+        let core = project.schaltwerk_core.read().await;
+        let session = core.session_manager().get_session("push-fails").unwrap();
+        assert_eq!(
+            session.session_state,
+            schaltwerk::domains::sessions::SessionState::Reviewed
+        );
+    }
 }
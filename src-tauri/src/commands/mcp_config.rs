@@ -189,6 +189,7 @@ mod client {
                 custom_path: Some(custom.to_string_lossy().to_string()),
                 auto_detect: false,
                 detected_binaries: vec![],
+                version: None,
             };
 
             let result = select_cli_path(Some(config), &[]).expect("cli path");
@@ -205,6 +206,7 @@ mod client {
                 custom_path: None,
                 auto_detect: true,
                 detected_binaries: vec![detected(&detected_path)],
+                version: None,
             };
 
             let result = select_cli_path(Some(config), &[]).expect("cli path");
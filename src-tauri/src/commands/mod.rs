@@ -33,40 +33,79 @@ pub use project::*;
 pub use pty::*;
 pub use schaltwerk_core::{
     schaltwerk_core_append_spec_content, schaltwerk_core_archive_spec_session,
-    schaltwerk_core_cancel_session, schaltwerk_core_cleanup_orphaned_worktrees,
+    schaltwerk_core_cancel_session, schaltwerk_core_classify_worktrees,
+    schaltwerk_core_cleanup_orphaned_worktrees,
     schaltwerk_core_convert_session_to_draft, schaltwerk_core_create_session,
     schaltwerk_core_create_spec_session, schaltwerk_core_delete_archived_spec,
     schaltwerk_core_delete_epic,
     schaltwerk_core_discard_file_in_orchestrator, schaltwerk_core_discard_file_in_session,
+    schaltwerk_core_discard_files_in_session,
+    schaltwerk_core_discard_hunk_in_orchestrator, schaltwerk_core_discard_hunk_in_session,
+    schaltwerk_core_export_spec_to_file, schaltwerk_core_export_specs_to_directory,
     schaltwerk_core_create_epic,
     schaltwerk_core_get_agent_type, schaltwerk_core_get_archive_max_entries,
-    schaltwerk_core_get_font_sizes, schaltwerk_core_get_merge_preview,
+    schaltwerk_core_get_agent_stats, schaltwerk_core_get_session_history,
+    schaltwerk_core_get_session_commits, schaltwerk_core_cherry_pick_session_commits,
+    schaltwerk_core_run_db_maintenance,
+    schaltwerk_core_get_database_info,
+    schaltwerk_core_get_font_sizes,
+    schaltwerk_core_get_merge_preview, schaltwerk_core_suggest_merge_commit_message,
+    schaltwerk_core_preview_discard_file_in_session, schaltwerk_core_preview_update_from_parent,
     schaltwerk_core_get_merge_preview_with_worktree, schaltwerk_core_get_orchestrator_agent_type,
     schaltwerk_core_get_orchestrator_skip_permissions, schaltwerk_core_get_session,
     schaltwerk_core_get_session_agent_content, schaltwerk_core_get_skip_permissions,
     schaltwerk_core_get_spec, schaltwerk_core_has_uncommitted_changes,
+    schaltwerk_core_import_specs_from_directory,
+    schaltwerk_core_watch_specs_dir,
+    schaltwerk_core_unwatch_specs_dir,
     schaltwerk_core_link_session_to_pr, schaltwerk_core_unlink_session_from_pr, schaltwerk_core_list_archived_specs,
     schaltwerk_core_list_codex_models, schaltwerk_core_list_enriched_sessions,
     schaltwerk_core_list_enriched_sessions_sorted, schaltwerk_core_list_project_files,
     schaltwerk_core_list_epics,
     schaltwerk_core_list_sessions, schaltwerk_core_list_sessions_by_state,
+    schaltwerk_core_list_sessions_awaiting_approval, schaltwerk_core_set_focused_terminal,
     schaltwerk_core_mark_session_ready,
     schaltwerk_core_merge_session_to_main, schaltwerk_core_rename_draft_session,
-    schaltwerk_core_update_session_from_parent,
+    schaltwerk_core_fork_session, schaltwerk_core_create_version_group,
+    schaltwerk_core_diff_version_group, schaltwerk_core_get_version_group_comparison,
+    schaltwerk_core_select_version_winner,
+    schaltwerk_core_update_session_from_parent, schaltwerk_core_update_all_sessions_from_parent,
     schaltwerk_core_rename_session_display_name, schaltwerk_core_rename_version_group,
+    schaltwerk_core_set_version_group_label,
     schaltwerk_core_reset_orchestrator,
+    schaltwerk_core_recreate_session_branch,
     schaltwerk_core_reset_session_worktree, schaltwerk_core_restore_archived_spec,
+    schaltwerk_core_revert_file_to_commit,
+    schaltwerk_core_list_scheduled_specs, schaltwerk_core_schedule_spec,
+    schaltwerk_core_cancel_scheduled_spec, run_spec_scheduler,
+    schaltwerk_core_run_setup_script,
+    schaltwerk_core_generate_session_name, run_session_name_retrier,
+    apply_spec_mirror_setting, install_agent_crash_supervisor, install_agent_status_events,
+    schaltwerk_core_restart_crashed_agent,
+    schaltwerk_core_stash_session_changes, schaltwerk_core_unstash_session_changes,
+    schaltwerk_core_create_session_snapshot, schaltwerk_core_list_session_snapshots,
+    schaltwerk_core_restore_session_snapshot,
     schaltwerk_core_set_agent_type, schaltwerk_core_set_archive_max_entries,
     schaltwerk_core_set_font_sizes, schaltwerk_core_set_orchestrator_agent_type,
     schaltwerk_core_set_orchestrator_skip_permissions, schaltwerk_core_set_session_agent_type,
-    schaltwerk_core_set_item_epic,
+    schaltwerk_core_set_item_epic, schaltwerk_core_set_session_group,
+    schaltwerk_core_list_session_groups, schaltwerk_core_set_terminal_recording,
+    schaltwerk_core_get_terminal_recordings,
     schaltwerk_core_set_skip_permissions, schaltwerk_core_start_claude,
     schaltwerk_core_start_claude_orchestrator, schaltwerk_core_start_claude_with_restart,
     schaltwerk_core_start_fresh_orchestrator, schaltwerk_core_start_session_agent,
     schaltwerk_core_start_session_agent_with_restart, schaltwerk_core_unmark_session_ready,
-    schaltwerk_core_update_git_stats, schaltwerk_core_update_session_state,
-    schaltwerk_core_update_spec_content,
-    schaltwerk_core_update_epic,
+    schaltwerk_core_restart_session_preserving_context,
+    schaltwerk_core_get_session_launch_overrides, schaltwerk_core_set_session_launch_overrides,
+    schaltwerk_core_get_session_extra_cli_args, schaltwerk_core_set_session_extra_cli_args,
+    schaltwerk_core_get_session_env_vars, schaltwerk_core_set_session_env_vars,
+    schaltwerk_core_get_session_launch_snapshot, schaltwerk_core_preview_session_launch_command,
+    schaltwerk_core_update_git_stats, schaltwerk_core_fetch_remote_status,
+    schaltwerk_core_refresh_session,
+    generate_commit_message,
+    schaltwerk_core_update_session_state,
+    schaltwerk_core_update_spec_content, schaltwerk_core_set_spec_dependency,
+    schaltwerk_core_update_epic, schaltwerk_core_confirm_skip_permissions,
 };
 pub use preview::*;
 pub use settings::*;
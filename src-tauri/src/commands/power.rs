@@ -2,7 +2,12 @@ use schaltwerk::services::GlobalState;
 use schaltwerk::services::power::{
     disable_global_keep_awake as disable_global_keep_awake_service,
     enable_global_keep_awake as enable_global_keep_awake_service,
+    get_global_keep_awake_auto_mode as get_global_keep_awake_auto_mode_service,
+    get_global_keep_awake_max_minutes as get_global_keep_awake_max_minutes_service,
     get_global_keep_awake_state as get_global_keep_awake_state_service,
+    set_global_keep_awake_activity_minutes as set_global_keep_awake_activity_minutes_service,
+    set_global_keep_awake_auto_mode as set_global_keep_awake_auto_mode_service,
+    set_global_keep_awake_max_minutes as set_global_keep_awake_max_minutes_service,
 };
 
 #[tauri::command]
@@ -19,3 +24,31 @@ pub async fn enable_global_keep_awake() -> Result<GlobalState, String> {
 pub async fn disable_global_keep_awake() -> Result<GlobalState, String> {
     disable_global_keep_awake_service().await
 }
+
+/// Minutes keep-awake may be held continuously before it is released; `0` means no limit.
+#[tauri::command]
+pub async fn get_global_keep_awake_max_minutes() -> Result<u64, String> {
+    get_global_keep_awake_max_minutes_service().await
+}
+
+#[tauri::command]
+pub async fn set_global_keep_awake_max_minutes(minutes: u64) -> Result<GlobalState, String> {
+    set_global_keep_awake_max_minutes_service(minutes).await
+}
+
+#[tauri::command]
+pub async fn get_global_keep_awake_auto_mode() -> Result<bool, String> {
+    get_global_keep_awake_auto_mode_service().await
+}
+
+/// Turns auto mode on or off; manual enable/disable overrides it until this is called again.
+#[tauri::command]
+pub async fn set_global_keep_awake_auto_mode(enabled: bool) -> Result<GlobalState, String> {
+    set_global_keep_awake_auto_mode_service(enabled).await
+}
+
+/// Minutes of agent silence after which a running session no longer keeps the machine awake.
+#[tauri::command]
+pub async fn set_global_keep_awake_activity_minutes(minutes: u64) -> Result<(), String> {
+    set_global_keep_awake_activity_minutes_service(minutes).await
+}
@@ -1,9 +1,16 @@
+use crate::commands::sessions_refresh::{SessionsRefreshReason, request_sessions_refresh};
 use crate::{
-    events::{SchaltEvent, emit_event},
-    get_project_manager, projects,
+    events::{OperationsRecoveredPayload, ProjectValidationErrorPayload, SchaltEvent, emit_event},
+    get_core_read, get_project_manager, get_terminal_manager, projects,
 };
 use log::warn;
-use schaltwerk::services::ServiceHandles;
+use schaltwerk::infrastructure::database::backup::{self, DatabaseBackup};
+use schaltwerk::infrastructure::database::{
+    Database, ProjectConfigMethods, WorkspaceDefinition, WorkspaceMethods,
+};
+use schaltwerk::project_manager::{Project, ProjectSessionSummary};
+use schaltwerk::services::{EnrichedSession, RelocationReport, ServiceHandles};
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, State};
 
 #[tauri::command]
@@ -13,6 +20,23 @@ pub fn get_recent_projects() -> Result<Vec<projects::RecentProject>, String> {
     Ok(history.get_recent_projects())
 }
 
+/// Session counts for every recent project. Each project's database is read on its own,
+/// so the active project's core lock is never taken.
+#[tauri::command]
+pub async fn list_all_projects_summary() -> Result<Vec<ProjectSessionSummary>, String> {
+    let history = projects::ProjectHistory::load()
+        .map_err(|e| format!("Failed to load project history: {e}"))?;
+    let recent = history.get_recent_projects();
+    tokio::task::spawn_blocking(move || {
+        recent
+            .iter()
+            .map(|project| Project::session_summary(&project.path, &project.name))
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to summarize projects: {e}"))
+}
+
 #[tauri::command]
 pub fn add_recent_project(path: String) -> Result<(), String> {
     let mut history = projects::ProjectHistory::load()
@@ -76,9 +100,305 @@ pub async fn initialize_project(
         warn!("Failed to emit ProjectReady event for {path}: {error}");
     }
 
+    // Recovery, the spec mirror and backups all write; a read-only window only views.
+    if get_project_manager().await.is_read_only() {
+        return Ok(());
+    }
+
+    recover_interrupted_operations(&app, &path).await;
+    offer_relocation_if_moved(&app, &path);
+    if let Err(error) = crate::commands::apply_spec_mirror_setting(app.clone()).await {
+        warn!("Failed to start spec file mirror for {path}: {error}");
+    }
+    tokio::spawn(backup_project_database(path));
+
     Ok(())
 }
 
+/// Reports a project whose session worktrees still link into the repository's former
+/// location, so the frontend can offer `schaltwerk_core_relocate_project`.
+fn offer_relocation_if_moved(app: &AppHandle, path: &str) {
+    let Some(old_path) = schaltwerk::services::detect_relocated_repository(Path::new(path)) else {
+        return;
+    };
+    let old_path = old_path.to_string_lossy().to_string();
+    warn!("Project {path} appears to have moved from {old_path}");
+
+    let payload = ProjectValidationErrorPayload {
+        path: path.to_string(),
+        error: format!(
+            "This repository was moved from {old_path}. Relocate the project to restore its sessions."
+        ),
+        detected_old_path: Some(old_path),
+    };
+    if let Err(error) = emit_event(app, SchaltEvent::ProjectValidationError, &payload) {
+        warn!("Failed to emit ProjectValidationError event for {path}: {error}");
+    }
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_relocate_project(
+    app: AppHandle,
+    services: State<'_, ServiceHandles>,
+    old_path: String,
+    new_path: String,
+) -> Result<RelocationReport, String> {
+    let old_root = PathBuf::from(&old_path);
+    let new_root = std::fs::canonicalize(&new_path)
+        .map_err(|e| format!("Failed to resolve {new_path}: {e}"))?;
+    log::info!(
+        "Relocating project from {old_path} to {}",
+        new_root.display()
+    );
+
+    // Close the project first so no open connection keeps using the database being replaced.
+    get_project_manager()
+        .await
+        .remove_project(&new_root)
+        .await?;
+
+    let report = tokio::task::spawn_blocking({
+        let new_root = new_root.clone();
+        move || -> anyhow::Result<RelocationReport> {
+            let db_path = Project::adopt_relocated_database(&old_root, &new_root)?;
+            let db = Database::new(Some(db_path))?;
+            schaltwerk::services::relocate_project(&db, &old_root, &new_root)
+        }
+    })
+    .await
+    .map_err(|e| format!("Relocation task failed: {e}"))?
+    .map_err(|e| format!("Failed to relocate project: {e}"))?;
+
+    let new_path = new_root.to_string_lossy().to_string();
+    match projects::ProjectHistory::load() {
+        Ok(mut history) => {
+            if let Err(e) = history.remove_project(&old_path) {
+                warn!("Failed to remove {old_path} from recent projects: {e}");
+            }
+            if let Err(e) = history.add_project(&new_path) {
+                warn!("Failed to add {new_path} to recent projects: {e}");
+            }
+        }
+        Err(e) => warn!("Failed to load project history: {e}"),
+    }
+
+    services
+        .projects
+        .initialize_project(new_path.clone())
+        .await?;
+    if let Err(error) = emit_event(&app, SchaltEvent::ProjectReady, &new_path) {
+        warn!("Failed to emit ProjectReady event for {new_path}: {error}");
+    }
+    request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
+
+    Ok(report)
+}
+
+/// Repairs session operations that were still in flight when the app last exited.
+async fn recover_interrupted_operations(app: &AppHandle, path: &str) {
+    let (db, repo_path) = match get_core_read().await {
+        Ok(core) => (core.db.clone(), core.repo_path.clone()),
+        Err(e) => {
+            warn!("Skipping crash recovery for {path}: {e}");
+            return;
+        }
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        schaltwerk::services::recover_interrupted_operations(&db, &repo_path)
+    })
+    .await;
+
+    let operations = match result {
+        Ok(Ok(operations)) => operations,
+        Ok(Err(e)) => {
+            warn!("Crash recovery failed for {path}: {e}");
+            return;
+        }
+        Err(e) => {
+            warn!("Crash recovery task panicked for {path}: {e}");
+            return;
+        }
+    };
+
+    if operations.is_empty() {
+        return;
+    }
+
+    let payload = OperationsRecoveredPayload {
+        project_path: path.to_string(),
+        operations,
+    };
+    if let Err(error) = emit_event(app, SchaltEvent::OperationsRecovered, &payload) {
+        warn!("Failed to emit OperationsRecovered event for {path}: {error}");
+    }
+    crate::commands::sessions_refresh::request_sessions_refresh(
+        app,
+        crate::commands::sessions_refresh::SessionsRefreshReason::SessionLifecycle,
+    );
+}
+
+/// Snapshots the project database into `.schaltwerk/backups` unless the project opted out.
+async fn backup_project_database(path: String) {
+    let (db, repo_path) = match get_core_read().await {
+        Ok(core) => (core.db.clone(), core.repo_path.clone()),
+        Err(e) => {
+            warn!("Skipping database backup for {path}: {e}");
+            return;
+        }
+    };
+
+    let result = tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        if !db.get_project_database_backups_enabled(&repo_path)? {
+            log::debug!("Database backups disabled for {}", repo_path.display());
+            return Ok(());
+        }
+        backup::backup_project_database(&db, &repo_path)?;
+        Ok(())
+    })
+    .await;
+
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => warn!("Database backup failed for {path}: {e}"),
+        Err(e) => warn!("Database backup task panicked for {path}: {e}"),
+    }
+}
+
+#[tauri::command]
+pub async fn list_database_backups() -> Result<Vec<DatabaseBackup>, String> {
+    let core = get_core_read().await?;
+    let dir = backup::backup_dir(&core.repo_path);
+    drop(core);
+
+    backup::list_backups(&dir).map_err(|e| format!("Failed to list database backups: {e}"))
+}
+
+/// Restores backup `id` over the project database. Only allowed while nothing can be
+/// writing to it: no journaled session operation (create, cancel, merge) in flight and
+/// no session terminal open. The replaced state is kept as a pre-restore backup.
+#[tauri::command]
+pub async fn restore_database_backup(app: AppHandle, id: String) -> Result<DatabaseBackup, String> {
+    let in_flight = schaltwerk::services::in_flight_operations();
+    if in_flight > 0 {
+        return Err(format!(
+            "Cannot restore a backup while {in_flight} session operation(s) are in progress"
+        ));
+    }
+
+    let open_terminals = get_terminal_manager()
+        .await?
+        .liveness_snapshot()
+        .await
+        .into_keys()
+        .filter(|id| id.starts_with("session-") || id.starts_with("run-terminal-"))
+        .count();
+    if open_terminals > 0 {
+        return Err(format!(
+            "Cannot restore a backup while {open_terminals} session terminal(s) are open. \
+             Close the running sessions first."
+        ));
+    }
+
+    let core = get_core_read().await?;
+    let db = core.db.clone();
+    let dir = backup::backup_dir(&core.repo_path);
+    drop(core);
+
+    let safety = tokio::task::spawn_blocking(move || backup::restore_backup(&db, &dir, &id))
+        .await
+        .map_err(|e| format!("Restore task failed: {e}"))?
+        .map_err(|e| format!("Failed to restore database backup: {e}"))?;
+
+    request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
+    Ok(safety)
+}
+
+#[tauri::command]
+pub fn list_workspaces() -> Result<Vec<WorkspaceDefinition>, String> {
+    let db = crate::open_global_app_config_db()?;
+    db.list_workspaces()
+        .map_err(|e| format!("Failed to list workspaces: {e}"))
+}
+
+/// Stores the multi-root workspace `name`, replacing an existing definition. Every
+/// member must be a git repository.
+#[tauri::command]
+pub fn save_workspace(
+    name: String,
+    repo_paths: Vec<String>,
+) -> Result<WorkspaceDefinition, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Workspace name cannot be empty".to_string());
+    }
+    if let Some(path) = repo_paths
+        .iter()
+        .find(|path| !projects::is_git_repository(Path::new(path)))
+    {
+        return Err(format!("Not a git repository: {path}"));
+    }
+
+    let workspace = WorkspaceDefinition { name, repo_paths };
+    let db = crate::open_global_app_config_db()?;
+    db.save_workspace(&workspace)
+        .map_err(|e| format!("Failed to save workspace: {e}"))?;
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub fn delete_workspace(name: String) -> Result<(), String> {
+    let db = crate::open_global_app_config_db()?;
+    db.delete_workspace(&name)
+        .map_err(|e| format!("Failed to delete workspace: {e}"))
+}
+
+/// Loads every member repository of workspace `name` and makes the first one the
+/// current project. Returns the canonical member paths in workspace order.
+#[tauri::command]
+pub async fn open_workspace(app: AppHandle, name: String) -> Result<Vec<String>, String> {
+    let db = crate::open_global_app_config_db()?;
+    let workspace = db
+        .get_workspace(&name)
+        .map_err(|e| format!("Failed to load workspace: {e}"))?
+        .ok_or_else(|| format!("Workspace '{name}' not found"))?;
+    let repo_paths: Vec<PathBuf> = workspace.repo_paths.iter().map(PathBuf::from).collect();
+
+    let members = get_project_manager()
+        .await
+        .open_workspace(&repo_paths)
+        .await
+        .map_err(|e| format!("Failed to open workspace '{name}': {e}"))?;
+    let member_paths: Vec<String> = members
+        .iter()
+        .map(|project| project.path.to_string_lossy().to_string())
+        .collect();
+    log::info!("Opened workspace '{name}' with members {member_paths:?}");
+
+    if let Some(current) = member_paths.first() {
+        if let Err(error) = emit_event(&app, SchaltEvent::ProjectReady, current) {
+            warn!("Failed to emit ProjectReady event for {current}: {error}");
+        }
+        if !get_project_manager().await.is_read_only() {
+            recover_interrupted_operations(&app, current).await;
+        }
+    }
+    request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
+
+    Ok(member_paths)
+}
+
+/// Sessions of all repositories in the open workspace, each carrying its `repo_label`.
+/// Without a workspace this lists the current project only.
+#[tauri::command]
+pub async fn schaltwerk_core_list_workspace_sessions() -> Result<Vec<EnrichedSession>, String> {
+    get_project_manager()
+        .await
+        .list_workspace_sessions()
+        .await
+        .map_err(|e| format!("Failed to list workspace sessions: {e}"))
+}
+
 #[tauri::command]
 pub async fn get_active_project_path() -> Result<Option<String>, String> {
     let manager = get_project_manager().await;
@@ -1,42 +1,77 @@
 use crate::{
     PROJECT_MANAGER, SETTINGS_MANAGER, commands::session_lookup_cache::global_session_lookup_cache,
-    errors::SchaltError, get_core_read, get_core_write, get_file_watcher_manager,
+    errors::{CommandError, ErrorCode, SchaltError}, get_core_read, get_core_write, get_file_watcher_manager,
     get_terminal_manager,
 };
+use schaltwerk::domains::agents::{
+    CliArgPlaceholders, EnvOverrides, redact_args, redact_command_line,
+};
+use schaltwerk::domains::terminal::recording::TerminalRecordingFile;
+use schaltwerk::infrastructure::agent_status_bridge::{
+    ApprovalRequest, pending_approval_requests, set_focused_agent_terminal, track_agent_terminal,
+};
 use schaltwerk::infrastructure::attention_bridge::clear_session_attention_state;
+use schaltwerk::infrastructure::database::{DatabaseInfo, MaintenanceReport};
 use schaltwerk::infrastructure::events::{SchaltEvent, emit_event};
 use schaltwerk::schaltwerk_core::{AgentLaunchParams, SessionManager};
 use schaltwerk::schaltwerk_core::db_app_config::AppConfigMethods;
 use schaltwerk::schaltwerk_core::db_project_config::{DEFAULT_BRANCH_PREFIX, ProjectConfigMethods};
 use schaltwerk::services::format_branch_name;
+use schaltwerk::services::SessionSnapshot;
 use schaltwerk::services::MergeStateSnapshot;
 use schaltwerk::services::ServiceHandles;
 use schaltwerk::services::SessionMethods;
+use schaltwerk::services::{SkipPermissionsError, confirm_skip_permissions};
+use schaltwerk::services::worktrees::{FileRevertOutcome, StaleHunkError, UnstashOutcome};
+use schaltwerk::services::{SpecImportReport, SpecSyncState, spec_files_in_directory};
+use schaltwerk::services::{
+    VersionAgentConfig, VersionComparisonEntry, VersionGroupCreation, VersionGroupDiff,
+    VersionGroupParams, VersionWinnerSelection,
+};
+use schaltwerk::services::{AgentStatsPeriod, AgentStatsRow};
 use schaltwerk::services::get_project_files_with_status;
 use schaltwerk::services::repository;
-use schaltwerk::services::{AgentManifest, parse_agent_command};
+use schaltwerk::services::{AgentManifest, commit_message, parse_agent_command};
 use schaltwerk::services::{
-    EnrichedSessionEntity as EnrichedSession, FilterMode, Session, SessionState, SortMode,
+    EnrichedSessionEntity as EnrichedSession, FilterMode, GitStats, Session,
+    SessionLaunchOverrides, SessionLaunchSnapshot, SessionState, SessionStateTransition, SortMode,
+    WorktreeClassification,
 };
-use schaltwerk::services::{MergeMode, MergeOutcome, MergePreview, MergeService};
+use schaltwerk::services::{MergeMode, MergeOutcome, MergePreview, MergeService, UpdatePreview};
+use schaltwerk::services::{CherryPickOutcome, SessionCommit};
 use schaltwerk::services::{
     build_login_shell_invocation_with_shell, get_effective_shell, sh_quote_string,
     shell_invocation_to_posix,
 };
+use schaltwerk::services::setup_scripts::{SETUP_DONE_MARKER, SetupRunsRegistry, SetupStatus};
 use schaltwerk::utils::env_adapter::EnvAdapter;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
 use std::time::{Duration, Instant};
 use tauri::State;
 use uuid::Uuid;
 mod agent_ctx;
 pub mod agent_launcher;
+mod agent_supervisor;
 mod codex_model_commands;
 mod codex_models;
 pub mod events;
 mod schaltwerk_core_cli;
+mod scheduled_specs;
+mod session_naming;
+mod setup_scripts;
 pub mod terminals;
 
+pub use agent_supervisor::{install_agent_crash_supervisor, schaltwerk_core_restart_crashed_agent};
+pub use events::install_agent_status_events;
 pub use codex_model_commands::schaltwerk_core_list_codex_models;
+pub use scheduled_specs::{
+    run_spec_scheduler, schaltwerk_core_cancel_scheduled_spec,
+    schaltwerk_core_list_scheduled_specs, schaltwerk_core_schedule_spec,
+};
+pub use session_naming::{run_session_name_retrier, schaltwerk_core_generate_session_name};
+pub use setup_scripts::{schaltwerk_core_run_setup_script, spawn_session_setup};
+use session_naming::spawn_session_name_generation;
 
 // Helper functions for session name parsing
 fn is_version_suffix(s: &str) -> bool {
@@ -90,12 +125,15 @@ fn emit_terminal_agent_started(
     app: &tauri::AppHandle,
     terminal_id: &str,
     session_name: Option<&str>,
+    attempt: Option<u32>,
 ) {
     #[derive(serde::Serialize, Clone)]
     struct TerminalAgentStartedPayload<'a> {
         terminal_id: &'a str,
         #[serde(skip_serializing_if = "Option::is_none")]
         session_name: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        attempt: Option<u32>,
     }
 
     if let Err(err) = emit_event(
@@ -104,6 +142,7 @@ fn emit_terminal_agent_started(
         &TerminalAgentStartedPayload {
             terminal_id,
             session_name,
+            attempt,
         },
     ) {
         log::warn!("Failed to emit terminal-agent-started event for {terminal_id}: {err}");
@@ -138,136 +177,6 @@ async fn get_agent_env_and_cli_args_async(
     }
 }
 
-fn spawn_session_name_generation(app_handle: tauri::AppHandle, session_name: String) {
-    tokio::spawn(async move {
-        let session_name_clone = session_name.clone();
-        let (
-            (session_id, worktree_path, repo_path, current_branch, agent, initial_prompt),
-            db_clone,
-        ) = {
-            let core = match get_core_read().await {
-                Ok(c) => c,
-                Err(e) => {
-                    log::warn!(
-                        "Cannot get schaltwerk_core for session '{session_name_clone}': {e}"
-                    );
-                    return;
-                }
-            };
-            let manager = core.session_manager();
-            let session = match manager.get_session(&session_name_clone) {
-                Ok(s) => s,
-                Err(e) => {
-                    log::warn!("Cannot load session '{session_name_clone}' for naming: {e}");
-                    return;
-                }
-            };
-
-            if !session.pending_name_generation {
-                log::info!(
-                    "Session '{session_name_clone}' does not have pending_name_generation flag, skipping"
-                );
-                return;
-            }
-
-            let agent = session.original_agent_type.clone().unwrap_or_else(|| {
-                core.db
-                    .get_agent_type()
-                    .unwrap_or_else(|_| "claude".to_string())
-            });
-
-            (
-                (
-                    session.id.clone(),
-                    session.worktree_path.clone(),
-                    session.repository_path.clone(),
-                    session.branch.clone(),
-                    agent,
-                    session.initial_prompt.clone(),
-                ),
-                core.db.clone(),
-            )
-        };
-
-        log::info!(
-            "Starting name generation for session '{}' with prompt: {:?}",
-            session_name_clone,
-            initial_prompt.as_ref().map(|p| {
-                let max_len = 50;
-                if p.len() <= max_len {
-                    p.as_str()
-                } else {
-                    let mut end = max_len;
-                    while !p.is_char_boundary(end) && end > 0 {
-                        end -= 1;
-                    }
-                    &p[..end]
-                }
-            })
-        );
-
-        let (mut env_vars, cli_args, binary_path, _) =
-            get_agent_env_and_cli_args_async(&agent).await;
-
-        if let Ok(project_env_vars) = db_clone.get_project_environment_variables(&repo_path) {
-            for (key, value) in project_env_vars {
-                env_vars.push((key, value));
-            }
-        }
-
-        let cli_args = if cli_args.is_empty() {
-            None
-        } else {
-            Some(cli_args)
-        };
-
-        let ctx = schaltwerk::domains::agents::naming::SessionRenameContext {
-            db: &db_clone,
-            session_id: &session_id,
-            worktree_path: &worktree_path,
-            repo_path: &repo_path,
-            current_branch: &current_branch,
-            agent_type: &agent,
-            initial_prompt: initial_prompt.as_deref(),
-            cli_args,
-            env_vars,
-            binary_path,
-        };
-
-        match schaltwerk::domains::agents::naming::generate_display_name_and_rename_branch(ctx)
-            .await
-        {
-            Ok(Some(display_name)) => {
-                log::info!(
-                    "Successfully generated display name '{display_name}' for session '{session_name_clone}'"
-                );
-
-                if let Err(e) = db_clone.set_pending_name_generation(&session_id, false) {
-                    log::warn!(
-                        "Failed to clear pending_name_generation for session '{session_name_clone}': {e}"
-                    );
-                }
-
-                log::info!("Queueing sessions refresh after AI name generation");
-                events::request_sessions_refreshed(
-                    &app_handle,
-                    events::SessionsRefreshReason::SessionLifecycle,
-                );
-            }
-            Ok(None) => {
-                log::warn!("Name generation returned None for session '{session_name_clone}'");
-                let _ = db_clone.set_pending_name_generation(&session_id, false);
-            }
-            Err(e) => {
-                log::error!(
-                    "Failed to generate display name for session '{session_name_clone}': {e}"
-                );
-                let _ = db_clone.set_pending_name_generation(&session_id, false);
-            }
-        }
-    });
-}
-
 fn spawn_spec_name_generation(
     app_handle: tauri::AppHandle,
     spec_id: String,
@@ -406,12 +315,50 @@ pub async fn schaltwerk_core_get_merge_preview_with_worktree(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_preview_update_from_parent(
+    name: String,
+) -> Result<UpdatePreview, String> {
+    let (db, repo_path) = {
+        let core = get_core_read().await?;
+        (core.db.clone(), core.repo_path.clone())
+    };
+
+    let service = MergeService::new(db, repo_path);
+    service
+        .preview_update_from_parent(&name)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_suggest_merge_commit_message(name: String) -> Result<String, String> {
+    let (db, repo_path) = {
+        let core = get_core_read().await?;
+        (core.db.clone(), core.repo_path.clone())
+    };
+
+    let service = MergeService::new(db, repo_path);
+    service
+        .suggest_commit_message(&name)
+        .map_err(|e| e.to_string())
+}
+
 #[derive(Debug, Clone)]
 pub struct MergeCommandError {
     pub message: String,
     pub conflict: bool,
 }
 
+impl From<MergeCommandError> for CommandError {
+    fn from(error: MergeCommandError) -> Self {
+        if error.conflict {
+            CommandError::new(ErrorCode::MergeConflict, error.message)
+        } else {
+            CommandError::from(error.message)
+        }
+    }
+}
+
 pub async fn merge_session_with_events(
     app: &tauri::AppHandle,
     name: &str,
@@ -422,13 +369,17 @@ pub async fn merge_session_with_events(
         Ok(core) => (core.db.clone(), core.repo_path.clone()),
         Err(e) => {
             return Err(MergeCommandError {
-                message: e,
+                message: e.to_string(),
                 conflict: false,
             });
         }
     };
 
-    let service = MergeService::new(db, repo_path);
+    let check_app = app.clone();
+    let check_session = name.to_string();
+    let service = MergeService::new(db, repo_path).with_check_output(move |line| {
+        events::emit_merge_check_output(&check_app, &check_session, line)
+    });
     let manager = service.session_manager();
 
     let session = manager.get_session(name).map_err(|e| MergeCommandError {
@@ -534,11 +485,54 @@ pub async fn schaltwerk_core_merge_session_to_main(
     name: String,
     mode: MergeMode,
     commit_message: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     merge_session_with_events(&app, &name, mode, commit_message)
         .await
         .map(|_| ())
-        .map_err(|err| err.message)
+        .map_err(CommandError::from)
+}
+
+/// Commits the session adds on top of its parent branch, oldest first.
+#[tauri::command]
+pub async fn schaltwerk_core_get_session_commits(
+    session_name: String,
+) -> Result<Vec<SessionCommit>, String> {
+    let (db, repo_path) = {
+        let core = get_core_read().await?;
+        (core.db.clone(), core.repo_path.clone())
+    };
+
+    MergeService::new(db, repo_path)
+        .session_commits(&session_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Applies the selected session commits onto the parent branch. A conflict is returned
+/// in the outcome and leaves the parent branch untouched.
+#[tauri::command]
+pub async fn schaltwerk_core_cherry_pick_session_commits(
+    app: tauri::AppHandle,
+    session_name: String,
+    commit_oids: Vec<String>,
+) -> Result<CherryPickOutcome, String> {
+    let (db, repo_path) = {
+        let core = get_core_write().await?;
+        (core.db.clone(), core.repo_path.clone())
+    };
+
+    let name = session_name.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        MergeService::new(db, repo_path).cherry_pick_commits(&name, &commit_oids)
+    })
+    .await
+    .map_err(|e| format!("Cherry-pick task failed: {e}"))?
+    .map_err(|e| e.to_string())?;
+
+    events::emit_cherry_pick_finished(&app, &session_name, &outcome);
+    if outcome.conflict.is_none() {
+        events::request_sessions_refreshed(&app, events::SessionsRefreshReason::MergeWorkflow);
+    }
+    Ok(outcome)
 }
 
 #[tauri::command]
@@ -571,6 +565,33 @@ pub async fn schaltwerk_core_update_session_from_parent(
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_update_all_sessions_from_parent(
+    app: tauri::AppHandle,
+    filter: Option<SessionState>,
+    parent_branch: Option<String>,
+) -> Result<schaltwerk::services::BatchUpdateReport, String> {
+    let manager = session_manager_read().await?;
+
+    let progress_app = app.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        schaltwerk::services::update_all_sessions_from_parent(
+            &manager,
+            filter,
+            parent_branch.as_deref(),
+            &|batch_id, progress| {
+                events::emit_batch_update_progress(&progress_app, batch_id, &progress)
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Batch update task failed: {e}"))?
+    .map_err(|e| format!("Failed to update sessions from parent: {e}"))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    Ok(report)
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_archive_spec_session(
     app: tauri::AppHandle,
@@ -715,9 +736,22 @@ pub async fn schaltwerk_core_list_enriched_sessions_sorted(
         .parse::<FilterMode>()
         .map_err(|e| format!("Invalid filter mode '{filter_mode_str}': {e}"))?;
 
+    let terminal_liveness = match get_terminal_manager().await {
+        Ok(terminal_manager) => terminal_manager.liveness_snapshot().await,
+        Err(e) => {
+            log::debug!(
+                "list_enriched_sessions_sorted call_id={call_id} no terminal liveness: {e}"
+            );
+            Default::default()
+        }
+    };
     let manager = session_manager_read().await?;
 
-    let result = manager.list_enriched_sessions_sorted(sort_mode, filter_mode);
+    let result = manager.list_enriched_sessions_sorted_with_terminals(
+        sort_mode,
+        filter_mode,
+        &terminal_liveness,
+    );
 
     match &result {
         Ok(sessions) => log::info!(
@@ -747,6 +781,7 @@ pub struct CreateSessionParams {
     user_edited_name: Option<bool>,
     version_group_id: Option<String>,
     version_number: Option<i32>,
+    version_group_label: Option<String>,
     epic_id: Option<String>,
     agent_type: Option<String>,
     skip_permissions: Option<bool>,
@@ -766,11 +801,12 @@ pub async fn schaltwerk_core_create_session(
     user_edited_name: Option<bool>,
     version_group_id: Option<String>,
     version_number: Option<i32>,
+    version_group_label: Option<String>,
     epic_id: Option<String>,
     agent_type: Option<String>,
     skip_permissions: Option<bool>,
     pr_number: Option<i64>,
-) -> Result<Session, SchaltError> {
+) -> Result<Session, CommandError> {
     let params = CreateSessionParams {
         name,
         prompt,
@@ -781,6 +817,7 @@ pub async fn schaltwerk_core_create_session(
         user_edited_name,
         version_group_id,
         version_number,
+        version_group_label,
         epic_id,
         agent_type,
         skip_permissions,
@@ -799,28 +836,28 @@ pub async fn schaltwerk_core_create_session(
         was_auto_generated,
         version_group_id: params.version_group_id.as_deref(),
         version_number: params.version_number,
+        version_group_label: params.version_group_label.as_deref(),
         epic_id: params.epic_id.as_deref(),
         agent_type: params.agent_type.as_deref(),
         skip_permissions: params.skip_permissions,
         pr_number: params.pr_number,
     };
     let (session, epic) = {
-        let core = get_core_write()
-            .await
-            .map_err(|e| SchaltError::DatabaseError {
-                message: e.to_string(),
-            })?;
+        let core = get_core_write().await?;
         let manager = core.session_manager();
+        let report_progress =
+            |progress: schaltwerk::domains::sessions::service::SessionCreationProgress| {
+                let _ = emit_event(&app, SchaltEvent::SessionCreationProgress, &progress);
+            };
         let session = manager
-            .create_session_with_agent(creation_params)
+            .create_session_with_progress(creation_params, &report_progress)
             .map_err(|e| {
-                let msg = e.to_string();
-                if msg.to_lowercase().contains("already exists") {
-                    SchaltError::SessionAlreadyExists {
+                if e.to_string().to_lowercase().contains("already exists") {
+                    CommandError::from(SchaltError::SessionAlreadyExists {
                         session_id: params.name.clone(),
-                    }
+                    })
                 } else {
-                    SchaltError::DatabaseError { message: msg }
+                    CommandError::from(e)
                 }
             })?;
         let epic = session
@@ -864,6 +901,13 @@ pub async fn schaltwerk_core_create_session(
         },
     );
 
+    if let Err(e) = spawn_session_setup(&app, &session).await {
+        log::warn!(
+            "Failed to start setup script for session '{}': {e}",
+            session.name
+        );
+    }
+
     // Only trigger auto-rename for non-versioned Docker-style names
     // Versioned names (ending with _v1, _v2, etc.) will be handled by group rename
     if was_auto_generated && !is_versioned_session_name(&params.name) {
@@ -1060,12 +1104,32 @@ pub async fn schaltwerk_core_rename_version_group(
         let _ = db.set_pending_name_generation(&session.id, false);
     }
 
+    if let Some(group_id) = &version_group_id
+        && let Err(e) = db.set_version_group_label(group_id, Some(&generated_name))
+    {
+        log::warn!("Failed to update label of version group '{group_id}': {e}");
+    }
+
     log::info!("Queueing sessions refresh after version group rename");
     events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SessionLifecycle);
 
     Ok(())
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_set_version_group_label(
+    app: tauri::AppHandle,
+    group_id: String,
+    label: String,
+) -> Result<(), String> {
+    session_manager_read()
+        .await?
+        .set_version_group_label(&group_id, &label)
+        .map_err(|e| format!("Failed to rename version group '{group_id}': {e}"))?;
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SessionLifecycle);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_list_sessions() -> Result<Vec<Session>, String> {
     session_manager_read()
@@ -1074,6 +1138,27 @@ pub async fn schaltwerk_core_list_sessions() -> Result<Vec<Session>, String> {
         .map_err(|e| format!("Failed to list sessions: {e}"))
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_list_sessions_awaiting_approval()
+-> Result<Vec<ApprovalRequest>, String> {
+    let sessions = session_manager_read()
+        .await?
+        .list_sessions()
+        .map_err(|e| format!("Failed to list sessions: {e}"))?;
+    Ok(pending_approval_requests()
+        .into_iter()
+        .filter(|request| sessions.iter().any(|s| s.name == request.session_name))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_set_focused_terminal(
+    terminal_id: Option<String>,
+) -> Result<(), String> {
+    set_focused_agent_terminal(terminal_id);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_list_epics(
 ) -> Result<Vec<schaltwerk::domains::sessions::entity::Epic>, String> {
@@ -1149,6 +1234,64 @@ pub async fn schaltwerk_core_set_item_epic(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_set_session_group(
+    app: tauri::AppHandle,
+    name: String,
+    group: Option<String>,
+) -> Result<Session, String> {
+    let core = get_core_write().await?;
+    let session = core
+        .session_manager()
+        .set_session_group(&name, group.as_deref())
+        .map_err(|e| format!("Failed to set group for session '{name}': {e}"))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::Unknown);
+    Ok(session)
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_list_session_groups() -> Result<Vec<String>, String> {
+    session_manager_read()
+        .await?
+        .list_session_groups()
+        .map_err(|e| format!("Failed to list session groups: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_set_terminal_recording(
+    app: tauri::AppHandle,
+    session_name: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let dir = {
+        let core = get_core_write().await?;
+        let manager = core.session_manager();
+        manager
+            .set_session_recording(&session_name, enabled)
+            .map_err(|e| format!("Failed to update recording for session '{session_name}': {e}"))?;
+        manager.session_recordings_dir(&session_name)
+    };
+
+    let terminal_manager = get_terminal_manager().await?;
+    terminal_manager
+        .set_session_recording(&session_name, enabled.then_some(dir))
+        .await;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::Unknown);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_get_terminal_recordings(
+    session_name: String,
+) -> Result<Vec<TerminalRecordingFile>, String> {
+    session_manager_read()
+        .await?
+        .get_terminal_recordings(&session_name)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_get_session(name: String) -> Result<Session, SchaltError> {
     let manager = session_manager_read()
@@ -1183,13 +1326,14 @@ pub async fn schaltwerk_core_get_spec(
 #[tauri::command]
 pub async fn schaltwerk_core_get_session_agent_content(
     name: String,
-) -> Result<(Option<String>, Option<String>), SchaltError> {
+    if_none_match: Option<String>,
+) -> Result<schaltwerk::domains::sessions::entity::SessionAgentContent, SchaltError> {
     session_manager_read()
         .await
         .map_err(|e| SchaltError::DatabaseError {
             message: e.to_string(),
         })?
-        .get_session_task_content(&name)
+        .get_session_agent_content(&name, if_none_match.as_deref())
         .map_err(|e| SchaltError::from_session_lookup(&name, e))
 }
 
@@ -1197,15 +1341,11 @@ pub async fn schaltwerk_core_get_session_agent_content(
 pub async fn schaltwerk_core_cancel_session(
     app: tauri::AppHandle,
     name: String,
-) -> Result<(), SchaltError> {
+) -> Result<(), CommandError> {
     log::info!("Starting cancel session: {name}");
 
     let (is_spec, repo_path_str, archive_count_after_opt) = {
-        let core = get_core_write()
-            .await
-            .map_err(|e| SchaltError::DatabaseError {
-                message: e.to_string(),
-            })?;
+        let core = get_core_write().await?;
         let manager = core.session_manager();
 
         let session = manager.get_session(&name).map_err(|e| {
@@ -1262,13 +1402,20 @@ pub async fn schaltwerk_core_cancel_session(
         let session_info = match get_core_write().await {
             Ok(core) => {
                 let manager = core.session_manager();
-                manager.get_session_for_cancellation(&name_for_bg)
+                manager
+                    .get_session_for_cancellation(&name_for_bg)
+                    .map(|info| (info, core.db.clone()))
             }
             Err(e) => Err(anyhow::anyhow!(e)),
         };
 
         let cancel_result = match session_info {
-            Ok(info) => {
+            Ok((info, db)) => {
+                let _journal = schaltwerk::services::JournalGuard::begin(
+                    &db,
+                    schaltwerk::services::cancel_journal_entry(&info.session),
+                );
+
                 // Perform slow filesystem operations WITHOUT holding the core write lock
                 use schaltwerk::schaltwerk_core::{
                     CancellationConfig, StandaloneCancellationCoordinator,
@@ -1374,7 +1521,7 @@ pub async fn schaltwerk_core_convert_session_to_draft(
             // Clean up any orphaned worktrees after conversion
             // This handles cases where worktree removal failed during conversion
             // We do this synchronously but with error handling to ensure it doesn't fail the conversion
-            if let Err(e) = manager.cleanup_orphaned_worktrees() {
+            if let Err(e) = manager.cleanup_all_orphaned_worktrees() {
                 log::warn!("Worktree cleanup after conversion failed (non-fatal): {e}");
             } else {
                 log::info!(
@@ -1408,35 +1555,130 @@ pub async fn schaltwerk_core_update_git_stats(session_id: String) -> Result<(),
 }
 
 #[tauri::command]
-pub async fn schaltwerk_core_cleanup_orphaned_worktrees() -> Result<(), String> {
-    let core = get_core_write().await?;
-    let manager = core.session_manager();
+pub async fn schaltwerk_core_fetch_remote_status(
+    app: tauri::AppHandle,
+    session_name: String,
+) -> Result<GitStats, String> {
+    let manager = session_manager_read().await?;
 
-    manager
-        .cleanup_orphaned_worktrees()
-        .map_err(|e| format!("Failed to cleanup orphaned worktrees: {e}"))
+    let stats = tokio::task::spawn_blocking(move || manager.fetch_remote_status(&session_name))
+        .await
+        .map_err(|e| format!("Remote status task failed: {e}"))?
+        .map_err(|e| format!("Failed to fetch remote status: {e}"))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    Ok(stats)
 }
 
+/// Recomputes the complete git state of one session, e.g. after git operations run
+/// outside the app, and publishes it as one `SessionGitStats` and one `SessionRefreshed`.
 #[tauri::command]
-pub async fn schaltwerk_core_start_claude(
+pub async fn schaltwerk_core_refresh_session(
     app: tauri::AppHandle,
     session_name: String,
-    cols: Option<u16>,
-    rows: Option<u16>,
-) -> Result<String, String> {
-    schaltwerk_core_start_claude_with_restart(app, session_name, false, cols, rows).await
+) -> Result<schaltwerk::services::SessionGitRefresh, String> {
+    let manager = session_manager_read().await?;
+
+    let name = session_name.clone();
+    let refresh = tokio::task::spawn_blocking(move || manager.refresh_session_git_state(&name))
+        .await
+        .map_err(|e| format!("Session refresh task failed: {e}"))?
+        .map_err(|e| format!("Failed to refresh session '{session_name}': {e}"))?;
+
+    if let Err(e) = emit_event(&app, SchaltEvent::SessionGitStats, &refresh.stats) {
+        log::warn!("Failed to emit SessionGitStats for {session_name}: {e}");
+    }
+    if let Err(e) = emit_event(&app, SchaltEvent::SessionRefreshed, &refresh) {
+        log::warn!("Failed to emit SessionRefreshed for {session_name}: {e}");
+    }
+    Ok(refresh)
 }
 
-#[derive(serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct StartAgentParams {
-    pub session_name: String,
-    #[serde(default)]
-    pub force_restart: bool,
-    pub cols: Option<u16>,
-    pub rows: Option<u16>,
-    pub terminal_id: Option<String>,
-    pub agent_type: Option<String>,
+/// Drafts a commit message for a session's uncommitted changes with the session's agent,
+/// falling back to a template message. Nothing is committed.
+#[tauri::command]
+pub async fn generate_commit_message(session_name: String) -> Result<String, String> {
+    let (worktree_path, agent_type) = {
+        let core = get_core_read().await?;
+        let session = core
+            .session_manager()
+            .get_session(&session_name)
+            .map_err(|e| format!("Session '{session_name}' not found: {e}"))?;
+        let agent_type = session.original_agent_type.clone().unwrap_or_else(|| {
+            core.db
+                .get_agent_type()
+                .unwrap_or_else(|_| "claude".to_string())
+        });
+        (session.worktree_path, agent_type)
+    };
+
+    let (env_vars, _, binary_path, _) = get_agent_env_and_cli_args_async(&agent_type).await;
+    let request = commit_message::CommitMessageRequest {
+        worktree_path: &worktree_path,
+        agent_type: &agent_type,
+        binary_override: binary_path.as_deref(),
+        env_vars: &env_vars,
+    };
+
+    commit_message::generate_commit_message(
+        &commit_message::ProcessOneshotExecutor,
+        &schaltwerk::domains::agents::unified::AgentRegistry::new(),
+        request,
+        commit_message::COMMIT_MESSAGE_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to generate commit message for '{session_name}': {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_classify_worktrees() -> Result<Vec<WorktreeClassification>, String> {
+    let core = get_core_read().await?;
+    let manager = core.session_manager();
+
+    manager
+        .classify_worktrees()
+        .map_err(|e| format!("Failed to classify worktrees: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_cleanup_orphaned_worktrees(
+    paths: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let core = get_core_write().await?;
+    let manager = core.session_manager();
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+
+    manager
+        .cleanup_orphaned_worktrees(&paths)
+        .map(|removed| {
+            removed
+                .into_iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect()
+        })
+        .map_err(|e| format!("Failed to cleanup orphaned worktrees: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_start_claude(
+    app: tauri::AppHandle,
+    session_name: String,
+    cols: Option<u16>,
+    rows: Option<u16>,
+) -> Result<String, CommandError> {
+    schaltwerk_core_start_claude_with_restart(app, session_name, false, cols, rows).await
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartAgentParams {
+    pub session_name: String,
+    #[serde(default)]
+    pub force_restart: bool,
+    pub cols: Option<u16>,
+    pub rows: Option<u16>,
+    pub terminal_id: Option<String>,
+    pub agent_type: Option<String>,
     #[serde(default)]
     pub prompt: Option<String>,
     pub skip_prompt: Option<bool>,
@@ -1449,7 +1691,7 @@ pub async fn schaltwerk_core_start_session_agent(
     session_name: String,
     cols: Option<u16>,
     rows: Option<u16>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     schaltwerk_core_start_session_agent_with_restart(
         app,
         StartAgentParams {
@@ -1474,7 +1716,7 @@ pub async fn schaltwerk_core_start_claude_with_restart(
     force_restart: bool,
     cols: Option<u16>,
     rows: Option<u16>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     schaltwerk_core_start_agent_in_terminal(
         app,
         AgentStartParams {
@@ -1486,11 +1728,19 @@ pub async fn schaltwerk_core_start_claude_with_restart(
             agent_type_override: None,
             skip_prompt: false,
             skip_permissions_override: None,
+            restart_attempt: None,
         },
     )
     .await
 }
 
+/// Redeems the token from a skip-permissions confirmation request. The frontend
+/// retries the launch afterwards; the approval covers that one launch only.
+#[tauri::command]
+pub async fn schaltwerk_core_confirm_skip_permissions(token: String) -> Result<String, String> {
+    confirm_skip_permissions(&token).map_err(|e| e.to_string())
+}
+
 struct AgentStartParams {
     session_name: String,
     force_restart: bool,
@@ -1500,12 +1750,14 @@ struct AgentStartParams {
     agent_type_override: Option<String>,
     skip_prompt: bool,
     skip_permissions_override: Option<bool>,
+    /// Set when the crash supervisor relaunches the agent automatically.
+    restart_attempt: Option<u32>,
 }
 
 async fn schaltwerk_core_start_agent_in_terminal(
     app: tauri::AppHandle,
     params: AgentStartParams,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let AgentStartParams {
         session_name,
         force_restart,
@@ -1515,6 +1767,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
         agent_type_override,
         skip_prompt,
         skip_permissions_override,
+        restart_attempt,
     } = params;
     log::info!(
         "Starting agent for session: {session_name}, terminal_id_override={terminal_id_override:?}, agent_type_override={agent_type_override:?}, skip_prompt={skip_prompt}, skip_permissions_override={skip_permissions_override:?}"
@@ -1529,7 +1782,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
 
     let session = manager
         .get_session(&session_name)
-        .map_err(|e| format!("Failed to get session: {e}"))?;
+        .map_err(|e| SchaltError::from_session_lookup(&session_name, e))?;
     let agent_type = agent_type_override.clone().unwrap_or_else(|| {
         session
             .original_agent_type
@@ -1576,6 +1829,13 @@ async fn schaltwerk_core_start_agent_in_terminal(
         None
     };
 
+    let env_overrides = manager
+        .get_session_env_overrides(&session_name)
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to load env overrides for session '{session_name}': {e}");
+            EnvOverrides::new()
+        });
+
     let spec = manager
         .start_claude_in_session_with_restart_and_binary(AgentLaunchParams {
             session_name: &session_name,
@@ -1588,13 +1848,38 @@ async fn schaltwerk_core_start_agent_in_terminal(
         })
         .map_err(|e| {
             log::error!("Failed to build {agent_type} command for session {session_name}: {e}");
-            format!("Failed to start {agent_type} in session: {e}")
-        })?;
+            match e.downcast_ref::<SkipPermissionsError>() {
+                Some(SkipPermissionsError::NeedsConfirmation {
+                    session_name,
+                    agent_type,
+                    token,
+                }) => CommandError::from(SchaltError::SkipPermissionsConfirmationRequired {
+                    session_id: session_name.clone(),
+                    agent_type: agent_type.clone(),
+                    token: token.clone(),
+                }),
+                Some(SkipPermissionsError::Forbidden {
+                    session_name,
+                    agent_type,
+                }) => CommandError::from(SchaltError::SkipPermissionsForbidden {
+                    session_id: session_name.clone(),
+                    agent_type: agent_type.clone(),
+                }),
+                None => CommandError {
+                    message: format!("Failed to start {agent_type} in session: {e}"),
+                    ..CommandError::from(e)
+                },
+            }
+        })?
+        .with_env_overrides(env_overrides);
 
     let command = spec.shell_command.clone();
     let initial_command = spec.initial_command.clone();
 
-    log::info!("Claude command for session {session_name}: {command}");
+    log::info!(
+        "Claude command for session {session_name}: {}",
+        spec.format_for_log()
+    );
 
     if agent_type == "amp"
         && let Err(e) = manager.spawn_amp_thread_watcher(&session_name)
@@ -1608,6 +1893,40 @@ async fn schaltwerk_core_start_agent_in_terminal(
         .map(|m| (m.auto_send_initial_command, m.ready_marker.clone()))
         .unwrap_or((false, None));
 
+    let (mut env_vars, cli_args, preferences) =
+        agent_ctx::collect_agent_env_and_cli(&agent_kind, &repo_path, &db, &spec.env_overrides)
+            .await;
+    // Launch overrides and extra args belong to the session's own agent, not to extra agent tabs
+    let (launch_overrides, extra_cli_args) = if agent_type_override.is_none() {
+        let overrides = manager
+            .get_session_launch_overrides(&session_name)
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load launch overrides for session '{session_name}': {e}");
+                SessionLaunchOverrides::default()
+            });
+        let extra_cli_args = manager
+            .get_session_extra_cli_args(&session_name)
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load extra CLI args for session '{session_name}': {e}");
+                None
+            });
+        (overrides, extra_cli_args)
+    } else {
+        (SessionLaunchOverrides::default(), None)
+    };
+    // Expanded before any terminal is touched so a malformed placeholder fails the launch cleanly
+    let cli_args = agent_ctx::session_cli_args(
+        &cli_args,
+        &launch_overrides,
+        extra_cli_args.as_deref(),
+        &CliArgPlaceholders {
+            worktree: &session.worktree_path,
+            session_name: &session_name,
+            parent_branch: &session.parent_branch,
+            repo_root: &repo_path,
+        },
+    )?;
+
     // Use override terminal ID if provided, otherwise derive from session name
     let terminal_id = terminal_id_override
         .unwrap_or_else(|| terminals::terminal_id_for_session_top(&session_name));
@@ -1626,7 +1945,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
                 rows.unwrap_or(24),
             )
             .await;
-        return Err(err);
+        return Err(err.into());
     }
     log::info!("Working directory access confirmed: {cwd}");
 
@@ -1637,6 +1956,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
         );
         terminal_manager.close_terminal(terminal_id.clone()).await?;
     }
+    track_agent_terminal(&terminal_id, &session_name, &agent_type);
 
     if auto_send_initial_command
         && let Some(initial) = initial_command.clone().filter(|v| !v.trim().is_empty())
@@ -1667,8 +1987,6 @@ async fn schaltwerk_core_start_agent_in_terminal(
             .await?;
     }
 
-    let (mut env_vars, cli_args, preferences) =
-        agent_ctx::collect_agent_env_and_cli(&agent_kind, &repo_path, &db).await;
     log::info!(
         "Creating terminal with {agent_name} directly: {terminal_id} with {} env vars and CLI args: '{cli_args}'",
         env_vars.len()
@@ -1696,20 +2014,45 @@ async fn schaltwerk_core_start_agent_in_terminal(
     // We gate with a marker file in the worktree: .schaltwerk/setup.done
     let mut use_shell_chain = false;
     let mut shell_cmd: Option<String> = None;
-    let marker_rel = ".schaltwerk/setup.done";
+    let marker_rel = SETUP_DONE_MARKER;
 
     // For Amp commands with pipes (containing " | amp"), use shell chain to preserve the pipe
     let has_pipe =
         command.contains(" | amp") || (command.contains(" | ") && agent_name.ends_with("/amp"));
     if has_pipe {
-        log::info!("Detected Amp command with pipe, using shell chain to preserve it: {command}");
+        log::info!(
+            "Detected Amp command with pipe, using shell chain to preserve it: {}",
+            redact_command_line(&command)
+        );
         // Extract the actual command part (after " && ")
         if let Some(cmd_part) = command.split(" && ").nth(1) {
             shell_cmd = Some(cmd_part.to_string());
             use_shell_chain = true;
         }
     }
-    if let Ok(Some(setup)) = db.get_project_setup_script(&repo_path)
+    // Sessions whose setup the backend launched in the bottom terminal must not run it
+    // again here; the agent optionally holds until that run finished.
+    let setup_runs = SetupRunsRegistry::global();
+    let setup_launched = setup_runs.status(&session_name).is_some();
+    let wait_for_setup = db
+        .get_project_sessions_settings(&repo_path)
+        .ok()
+        .and_then(|settings| settings.wait_for_setup)
+        .unwrap_or(false);
+    if setup_launched && wait_for_setup {
+        log::info!("Waiting for setup of session '{session_name}' before starting the agent");
+        match setup_runs.wait(&session_name).await {
+            Some(SetupStatus::Failed { exit_code }) => log::warn!(
+                "Setup of session '{session_name}' failed (exit code {exit_code:?}); starting the agent anyway"
+            ),
+            Some(SetupStatus::TimedOut) => {
+                log::warn!("Setup of session '{session_name}' timed out; starting the agent anyway")
+            }
+            _ => {}
+        }
+    }
+    if !setup_launched
+        && let Ok(Some(setup)) = db.get_project_setup_script(&repo_path)
         && !setup.trim().is_empty()
     {
         // Persist setup script to a temp file for reliable execution
@@ -1744,7 +2087,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
                     log::error!(
                         "Shell command missing while attempting to chain piped Amp command"
                     );
-                    return Err("Failed to build chained shell command".to_string());
+                    return Err("Failed to build chained shell command".to_string().into());
                 }
             } else {
                 // Regular agent: build exec command from agent_name and args
@@ -1788,20 +2131,30 @@ async fn schaltwerk_core_start_agent_in_terminal(
         agent_ctx::AgentKind::Fallback => "claude",
     };
     log::info!(
-        "FINAL COMMAND CONSTRUCTION for {kind_str}: command='{agent_name}', args={final_args:?}"
+        "FINAL COMMAND CONSTRUCTION for {kind_str}: command='{agent_name}', args={:?}",
+        redact_args(&final_args)
     );
 
     // Apply command prefix if configured (e.g., "vt" for VibeTunnel)
     let command_prefix = agent_launcher::get_agent_command_prefix().await;
     let (agent_name, final_args) =
         agent_launcher::apply_command_prefix(command_prefix, agent_name, final_args);
+    let launch_snapshot = agent_type_override
+        .is_none()
+        .then(|| SessionLaunchSnapshot {
+            agent_type: agent_type.clone(),
+            command: agent_ctx::format_launch_command(&agent_name, &final_args),
+            overrides: launch_overrides.clone(),
+            force_restart,
+            launched_at: chrono::Utc::now(),
+        });
 
     // Create terminal with initial size if provided
     let create_result = if use_shell_chain {
         let sh_cmd = "sh".to_string();
         let Some(chained_command) = shell_cmd.take() else {
             log::error!("Shell chain requested without prepared command");
-            return Err("Failed to construct shell command chain".to_string());
+            return Err("Failed to construct shell command chain".to_string().into());
         };
         let mut sh_args: Vec<String> = vec!["-lc".to_string(), chained_command];
         if let (Some(c), Some(r)) = (cols, rows) {
@@ -1863,7 +2216,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
                 rows.unwrap_or(24),
             )
             .await;
-        return Err(err);
+        return Err(err.into());
     }
 
     // For OpenCode and other TUI applications, the frontend will handle
@@ -1873,9 +2226,15 @@ async fn schaltwerk_core_start_agent_in_terminal(
     // For Gemini, we rely on the CLI's own interactive prompt flag.
     // Do not implement non-deterministic paste-based workarounds.
 
+    if let Some(snapshot) = launch_snapshot
+        && let Err(e) = manager.record_session_launch(&session_name, &snapshot)
+    {
+        log::warn!("Failed to record launch of session '{session_name}': {e}");
+    }
+
     log::info!("Successfully started agent in terminal: {terminal_id}");
 
-    emit_terminal_agent_started(&app, &terminal_id, Some(&session_name));
+    emit_terminal_agent_started(&app, &terminal_id, Some(&session_name), restart_attempt);
 
     Ok(command)
 }
@@ -1884,7 +2243,7 @@ async fn schaltwerk_core_start_agent_in_terminal(
 pub async fn schaltwerk_core_start_session_agent_with_restart(
     app: tauri::AppHandle,
     params: StartAgentParams,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let StartAgentParams {
         session_name,
         force_restart,
@@ -1918,11 +2277,168 @@ pub async fn schaltwerk_core_start_session_agent_with_restart(
             agent_type_override: agent_type,
             skip_prompt: skip_prompt.unwrap_or(false),
             skip_permissions_override: skip_permissions,
+            restart_attempt: None,
+        },
+    )
+    .await
+}
+
+/// Relaunches the session's agent through the resume path, so agents that can resume
+/// keep their conversation while picking up the current launch overrides.
+#[tauri::command]
+pub async fn schaltwerk_core_restart_session_preserving_context(
+    app: tauri::AppHandle,
+    session_name: String,
+    cols: Option<u16>,
+    rows: Option<u16>,
+) -> Result<String, CommandError> {
+    schaltwerk_core_start_agent_in_terminal(
+        app,
+        AgentStartParams {
+            session_name,
+            force_restart: false,
+            cols,
+            rows,
+            terminal_id_override: None,
+            agent_type_override: None,
+            skip_prompt: true,
+            skip_permissions_override: None,
+            restart_attempt: None,
         },
     )
     .await
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_get_session_launch_overrides(
+    session_name: String,
+) -> Result<SessionLaunchOverrides, String> {
+    session_manager_read()
+        .await?
+        .get_session_launch_overrides(&session_name)
+        .map_err(|e| format!("Failed to load launch overrides: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_set_session_launch_overrides(
+    session_name: String,
+    overrides: SessionLaunchOverrides,
+) -> Result<SessionLaunchOverrides, String> {
+    session_manager_read()
+        .await?
+        .set_session_launch_overrides(&session_name, overrides)
+        .map_err(|e| format!("Failed to save launch overrides: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_set_session_extra_cli_args(
+    session_name: String,
+    extra_cli_args: Option<String>,
+) -> Result<Option<String>, String> {
+    session_manager_read()
+        .await?
+        .set_session_extra_cli_args(&session_name, extra_cli_args.as_deref())
+        .map_err(|e| format!("Failed to save extra CLI args: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_get_session_extra_cli_args(
+    session_name: String,
+) -> Result<Option<String>, String> {
+    session_manager_read()
+        .await?
+        .get_session_extra_cli_args(&session_name)
+        .map_err(|e| format!("Failed to load extra CLI args: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_get_session_env_vars(
+    session_name: String,
+) -> Result<EnvOverrides, String> {
+    session_manager_read()
+        .await?
+        .get_session_env_overrides(&session_name)
+        .map_err(|e| format!("Failed to load session environment variables: {e}"))
+}
+
+/// A `null` value unsets a variable the agent or project settings would pass to the agent.
+#[tauri::command]
+pub async fn schaltwerk_core_set_session_env_vars(
+    session_name: String,
+    env_vars: EnvOverrides,
+) -> Result<EnvOverrides, String> {
+    session_manager_read()
+        .await?
+        .set_session_env_overrides(&session_name, env_vars)
+        .map_err(|e| format!("Failed to save session environment variables: {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_get_session_launch_snapshot(
+    session_name: String,
+) -> Result<Option<SessionLaunchSnapshot>, String> {
+    session_manager_read()
+        .await?
+        .get_session_launch_snapshot(&session_name)
+        .map_err(|e| format!("Failed to load launch record: {e}"))
+}
+
+/// Shows the agent command the session would launch with, including its overrides.
+#[tauri::command]
+pub async fn schaltwerk_core_preview_session_launch_command(
+    session_name: String,
+) -> Result<String, String> {
+    let core = get_core_read().await?;
+    let db = core.db.clone();
+    let repo_path = core.repo_path.clone();
+    let manager = core.session_manager();
+    drop(core);
+
+    let session = manager
+        .get_session(&session_name)
+        .map_err(|e| format!("Failed to get session: {e}"))?;
+    let agent_type = session
+        .original_agent_type
+        .clone()
+        .unwrap_or_else(|| db.get_agent_type().unwrap_or_else(|_| "claude".to_string()));
+    let (_, _, binary_path, _) = get_agent_env_and_cli_args_async(&agent_type).await;
+    let binary_paths = binary_path
+        .map(|path| std::collections::HashMap::from([(agent_type.clone(), path)]))
+        .unwrap_or_default();
+
+    let spec = manager
+        .preview_session_agent_launch(&session_name, &binary_paths)
+        .map_err(|e| format!("Failed to build launch command: {e}"))?;
+    let (_, agent_name, agent_args) = parse_agent_command(&spec.shell_command)?;
+    let agent_kind = agent_ctx::infer_agent_kind(&agent_name);
+    let (_, cli_args, preferences) =
+        agent_ctx::collect_agent_env_and_cli(&agent_kind, &repo_path, &db, &EnvOverrides::new())
+            .await;
+    let overrides = manager
+        .get_session_launch_overrides(&session_name)
+        .map_err(|e| format!("Failed to load launch overrides: {e}"))?;
+    let extra_cli_args = manager
+        .get_session_extra_cli_args(&session_name)
+        .map_err(|e| format!("Failed to load extra CLI args: {e}"))?;
+    let cli_args = agent_ctx::session_cli_args(
+        &cli_args,
+        &overrides,
+        extra_cli_args.as_deref(),
+        &CliArgPlaceholders {
+            worktree: &session.worktree_path,
+            session_name: &session_name,
+            parent_branch: &session.parent_branch,
+            repo_root: &repo_path,
+        },
+    )?;
+    let final_args = agent_ctx::build_final_args(&agent_kind, agent_args, &cli_args, &preferences);
+
+    let command_prefix = agent_launcher::get_agent_command_prefix().await;
+    let (agent_name, final_args) =
+        agent_launcher::apply_command_prefix(command_prefix, agent_name, final_args);
+    Ok(agent_ctx::format_launch_command(&agent_name, &final_args))
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_start_claude_orchestrator(
     app: tauri::AppHandle,
@@ -1939,7 +2455,7 @@ pub async fn schaltwerk_core_start_claude_orchestrator(
         Ok(c) => c,
         Err(e) => {
             log::error!("Failed to get schaltwerk_core for orchestrator: {e}");
-            if e.contains("No active project") {
+            if e.code == ErrorCode::NoActiveProject {
                 return Err("No project is currently open. Please open a project folder first before starting the orchestrator.".to_string());
             }
             return Err(format!("Failed to initialize orchestrator: {e}"));
@@ -2004,16 +2520,23 @@ pub async fn schaltwerk_core_start_claude_orchestrator(
 
     match launch_result {
         Ok(_) => {
-            emit_terminal_agent_started(&app, &terminal_id, None);
+            emit_terminal_agent_started(&app, &terminal_id, None, None);
 
             let base_branch = configured_default_branch.unwrap_or_else(|| {
                 repository::get_default_branch(repo_path.as_path())
                     .unwrap_or_else(|_| "main".to_string())
             });
+            let ignore_patterns = db
+                .get_project_watcher_ignore_patterns(&repo_path)
+                .unwrap_or_default();
 
             if let Ok(manager) = get_file_watcher_manager().await
                 && let Err(err) = manager
-                    .start_watching_orchestrator(repo_path.clone(), base_branch.clone())
+                    .start_watching_orchestrator(
+                        repo_path.clone(),
+                        base_branch.clone(),
+                        &ignore_patterns,
+                    )
                     .await
             {
                 log::warn!(
@@ -2236,10 +2759,10 @@ pub async fn schaltwerk_core_set_font_sizes(
                     .map_err(|e| format!("Failed to set font sizes: {e}"))?;
             }
             Err(err) => {
-                if err.contains("No active project") {
+                if err.code == ErrorCode::NoActiveProject {
                     log::debug!("Skipping project font size update: {err}");
                 } else {
-                    return Err(err);
+                    return Err(err.into());
                 }
             }
         }
@@ -2252,7 +2775,7 @@ pub async fn schaltwerk_core_set_font_sizes(
 pub async fn schaltwerk_core_mark_session_ready(
     app: tauri::AppHandle,
     name: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     log::info!("Marking session {name} as reviewed");
 
     let core = get_core_write().await?;
@@ -2260,7 +2783,10 @@ pub async fn schaltwerk_core_mark_session_ready(
 
     let result = manager
         .mark_session_ready(&name)
-        .map_err(|e| format!("Failed to mark session as reviewed: {e}"))?;
+        .map_err(|e| CommandError {
+            message: format!("Failed to mark session as reviewed: {e}"),
+            ..CommandError::from(e)
+        })?;
 
     if let Ok(session) = manager.get_session(&name)
         && session.worktree_path.exists()
@@ -2306,6 +2832,10 @@ pub async fn schaltwerk_core_mark_session_ready(
     log::info!("Queueing sessions refresh after marking session ready");
     events::request_sessions_refreshed(&app, events::SessionsRefreshReason::MergeWorkflow);
 
+    // The reviewed state is already persisted; the draft PR runs in the background and
+    // reports its own failures so it can never roll the review back.
+    tokio::spawn(crate::commands::github::open_draft_pr_after_review(app, name));
+
     Ok(result)
 }
 
@@ -2435,89 +2965,447 @@ pub async fn schaltwerk_core_update_spec_content(
 }
 
 #[tauri::command]
-pub async fn schaltwerk_core_rename_draft_session(
+pub async fn schaltwerk_core_set_spec_dependency(
     app: tauri::AppHandle,
-    old_name: String,
-    new_name: String,
+    name: String,
+    depends_on: Option<String>,
 ) -> Result<(), String> {
-    log::info!("Renaming spec session from '{old_name}' to '{new_name}'");
-
-    let core = get_core_write().await?;
-    let manager = core.session_manager();
-
-    manager
-        .rename_draft_session(&old_name, &new_name)
-        .map_err(|e| format!("Failed to rename spec session: {e}"))?;
-
-    // Emit sessions-refreshed event to update UI
+    session_manager_read()
+        .await?
+        .set_spec_dependency(&name, depends_on.as_deref())
+        .map_err(|e| format!("Failed to set dependency of spec '{name}': {e}"))?;
     events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
-
     Ok(())
 }
 
 #[tauri::command]
-pub async fn schaltwerk_core_rename_session_display_name(
-    app: tauri::AppHandle,
-    session_id: String,
-    new_display_name: String,
-) -> Result<(), String> {
-    log::info!("Renaming session display name: session_id={session_id}, new_name={new_display_name}");
-
-    let sanitized = schaltwerk::domains::agents::naming::sanitize_name(&new_display_name);
-    if sanitized.is_empty() {
-        return Err("Display name cannot be empty".to_string());
-    }
+pub async fn schaltwerk_core_get_agent_stats(
+    period: Option<String>,
+) -> Result<Vec<AgentStatsRow>, String> {
+    let period = match period.as_deref() {
+        Some(value) => value
+            .parse::<AgentStatsPeriod>()
+            .map_err(|e| e.to_string())?,
+        None => AgentStatsPeriod::default(),
+    };
 
     let core = get_core_read().await?;
     let manager = core.session_manager();
-    let db = core.db.clone();
-
-    let current_name = if let Ok(session) = manager.get_session(&session_id) {
-        session.name.clone()
-    } else if let Ok(spec) = manager.get_spec(&session_id) {
-        spec.name.clone()
-    } else {
-        return Err(format!("Session or spec '{session_id}' not found"));
-    };
-
-    let sessions = manager.list_sessions().map_err(|e| e.to_string())?;
-    let specs = manager.list_specs().map_err(|e| e.to_string())?;
-
-    let duplicate_session = sessions.iter().find(|s| {
-        s.name != current_name
-            && s.display_name
-                .as_ref()
-                .map(|dn| dn == &sanitized)
-                .unwrap_or(false)
-    });
-    let duplicate_spec = specs.iter().find(|s| {
-        s.name != current_name
-            && s.display_name
-                .as_ref()
-                .map(|dn| dn == &sanitized)
-                .unwrap_or(false)
-    });
 
-    if duplicate_session.is_some() || duplicate_spec.is_some() {
-        return Err(format!("A session with the name '{sanitized}' already exists"));
-    }
+    manager
+        .get_agent_stats(period)
+        .map_err(|e| format!("Failed to compute agent stats: {e}"))
+}
 
-    if let Ok(session) = manager.get_session(&session_id) {
-        db.update_session_display_name(&session.id, &sanitized)
-            .map_err(|e| format!("Failed to update session display name: {e}"))?;
-    } else if let Ok(spec) = manager.get_spec(&session_id) {
-        use schaltwerk::infrastructure::database::db_specs::SpecMethods;
-        db.update_spec_display_name(&spec.id, &sanitized)
-            .map_err(|e| format!("Failed to update spec display name: {e}"))?;
+/// Checks integrity, prunes stale rows and compacts the project database. Refused while a journaled
+/// session operation is writing to it.
+#[tauri::command]
+pub async fn schaltwerk_core_run_db_maintenance() -> Result<MaintenanceReport, String> {
+    let in_flight = schaltwerk::services::in_flight_operations();
+    if in_flight > 0 {
+        return Err(format!(
+            "Cannot run database maintenance while {in_flight} session operation(s) are in progress"
+        ));
     }
 
-    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+    let db = get_core_read().await?.db.clone();
+    tokio::task::spawn_blocking(move || db.run_database_maintenance())
+        .await
+        .map_err(|e| format!("Database maintenance task failed: {e}"))?
+        .map_err(|e| format!("Failed to run database maintenance: {e}"))
+}
 
-    Ok(())
+#[tauri::command]
+pub async fn schaltwerk_core_get_database_info() -> Result<DatabaseInfo, String> {
+    let db = get_core_read().await?.db.clone();
+    tokio::task::spawn_blocking(move || db.get_database_info())
+        .await
+        .map_err(|e| format!("Database info task failed: {e}"))?
+        .map_err(|e| format!("Failed to read database info: {e}"))
 }
 
 #[tauri::command]
-pub async fn schaltwerk_core_append_spec_content(
+pub async fn schaltwerk_core_get_session_history(
+    name: String,
+) -> Result<Vec<SessionStateTransition>, String> {
+    let core = get_core_read().await?;
+    let manager = core.session_manager();
+
+    manager
+        .get_session_state_history(&name)
+        .map_err(|e| format!("Failed to load state history for '{name}': {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_export_specs_to_directory(dir: String) -> Result<Vec<String>, String> {
+    log::info!("Exporting specs to directory: {dir}");
+
+    let core = get_core_read().await?;
+    let manager = core.session_manager();
+
+    let written = manager
+        .export_specs_to_directory(Path::new(&dir))
+        .map_err(|e| format!("Failed to export specs: {e}"))?;
+
+    Ok(written
+        .into_iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_export_spec_to_file(name: String, path: String) -> Result<String, String> {
+    log::info!("Exporting spec '{name}' to file: {path}");
+
+    let core = get_core_read().await?;
+    let manager = core.session_manager();
+
+    let written = manager
+        .export_spec_to_file(&name, Path::new(&path))
+        .map_err(|e| format!("Failed to export spec: {e}"))?;
+
+    Ok(written.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_import_specs_from_directory(
+    app: tauri::AppHandle,
+    dir: String,
+    overwrite: bool,
+) -> Result<SpecImportReport, String> {
+    log::info!("Importing specs from directory: {dir} (overwrite={overwrite})");
+
+    let core = get_core_write().await?;
+    let manager = core.session_manager();
+
+    let report = manager
+        .import_specs_from_directory(Path::new(&dir), overwrite)
+        .map_err(|e| format!("Failed to import specs: {e}"))?;
+
+    for skipped in &report.skipped {
+        log::warn!(
+            "Skipped spec file {}: {}",
+            skipped.path.display(),
+            skipped.reason
+        );
+    }
+
+    if !report.created.is_empty() || !report.updated.is_empty() {
+        events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_watch_specs_dir(
+    app: tauri::AppHandle,
+    dir: String,
+) -> Result<(), String> {
+    let dir = PathBuf::from(dir);
+    if !dir.is_dir() {
+        return Err(format!("Spec directory {} does not exist", dir.display()));
+    }
+    log::info!("Watching specs directory: {}", dir.display());
+
+    // Bind to the project that is active now so later project switches do not
+    // redirect file changes into another repository's specs.
+    let core = crate::get_schaltwerk_core().await?;
+    let state = Arc::new(StdMutex::new(SpecSyncState::default()));
+
+    let initial_files = spec_files_in_directory(&dir)
+        .map_err(|e| format!("Failed to list spec files: {e}"))?;
+    {
+        let manager = core.read().await.session_manager();
+        let mut guard = state.lock().map_err(|_| "Spec sync state poisoned".to_string())?;
+        let report = manager
+            .sync_spec_files(&mut guard, &initial_files)
+            .map_err(|e| format!("Failed to sync spec files: {e}"))?;
+        if report.has_changes() {
+            events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+        }
+    }
+
+    let on_change = Arc::new(move |paths: Vec<PathBuf>| {
+        let manager = core.blocking_read().session_manager();
+        let Ok(mut guard) = state.lock() else {
+            log::error!("Spec sync state poisoned; dropping {} changes", paths.len());
+            return;
+        };
+        match manager.sync_spec_files(&mut guard, &paths) {
+            Ok(report) if report.has_changes() => {
+                events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to sync spec files: {e}"),
+        }
+    });
+
+    get_file_watcher_manager()
+        .await?
+        .start_watching_specs_dir(dir, on_change)
+        .await
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_unwatch_specs_dir(dir: String) -> Result<(), String> {
+    get_file_watcher_manager()
+        .await?
+        .stop_watching_specs_dir(Path::new(&dir))
+        .await
+}
+
+/// Starts or stops mirroring the current project's specs to `.schaltwerk/specs` according
+/// to its setting. Enabling reconciles the directory first and then imports external edits.
+pub async fn apply_spec_mirror_setting(app: tauri::AppHandle) -> Result<(), String> {
+    let core = crate::get_schaltwerk_core().await?;
+    let (manager, enabled) = {
+        let guard = core.read().await;
+        let enabled = guard
+            .db
+            .get_project_spec_file_mirror(&guard.repo_path)
+            .map_err(|e| format!("Failed to get spec mirror setting: {e}"))?;
+        (guard.session_manager(), enabled)
+    };
+    let dir = manager.spec_mirror_dir();
+    let watcher = get_file_watcher_manager().await?;
+
+    if !enabled {
+        return watcher.stop_watching_specs_dir(&dir).await;
+    }
+
+    let report = manager
+        .reconcile_spec_mirror()
+        .map_err(|e| format!("Failed to reconcile spec mirror: {e}"))?;
+    if !report.updated.is_empty() {
+        events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+    }
+
+    let on_change = Arc::new(move |paths: Vec<PathBuf>| {
+        let manager = core.blocking_read().session_manager();
+        match manager.sync_spec_mirror_files(&paths) {
+            Ok(report) if !report.updated.is_empty() => {
+                events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to sync spec mirror files: {e}"),
+        }
+    });
+    watcher.start_watching_specs_dir(dir, on_change).await
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_rename_draft_session(
+    app: tauri::AppHandle,
+    old_name: String,
+    new_name: String,
+) -> Result<(), String> {
+    log::info!("Renaming spec session from '{old_name}' to '{new_name}'");
+
+    let core = get_core_write().await?;
+    let manager = core.session_manager();
+
+    manager
+        .rename_draft_session(&old_name, &new_name)
+        .map_err(|e| format!("Failed to rename spec session: {e}"))?;
+
+    // Emit sessions-refreshed event to update UI
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_fork_session(
+    app: tauri::AppHandle,
+    source_name: String,
+    new_name: String,
+) -> Result<Session, String> {
+    log::info!("Forking session '{source_name}' into '{new_name}'");
+
+    let core = get_core_write().await?;
+    let session = core
+        .session_manager()
+        .fork_session(&source_name, &new_name)
+        .map_err(|e| format!("Failed to fork session '{source_name}': {e}"))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SessionLifecycle);
+
+    Ok(session)
+}
+
+/// Creates all versions of a new version group in one call and runs the project setup
+/// script in each of them.
+#[tauri::command]
+pub async fn schaltwerk_core_create_version_group(
+    app: tauri::AppHandle,
+    base_name: String,
+    prompt: Option<String>,
+    count: usize,
+    agent_configs: Option<Vec<VersionAgentConfig>>,
+    base_branch: Option<String>,
+    version_group_label: Option<String>,
+) -> Result<VersionGroupCreation, String> {
+    let agent_configs = agent_configs.unwrap_or_default();
+    let created = get_core_write()
+        .await?
+        .session_manager()
+        .create_version_group(VersionGroupParams {
+            base_name: &base_name,
+            prompt: prompt.as_deref().filter(|p| !p.trim().is_empty()),
+            count,
+            agent_configs: &agent_configs,
+            base_branch: base_branch.as_deref(),
+            version_group_label: version_group_label.as_deref(),
+        })
+        .map_err(|e| format!("Failed to create version group '{base_name}': {e}"))?;
+
+    for session in &created.sessions {
+        if let Err(e) = spawn_session_setup(&app, session).await {
+            log::warn!(
+                "Failed to start setup script for session '{}': {e}",
+                session.name
+            );
+        }
+    }
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SessionLifecycle);
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_diff_version_group(
+    group_id: String,
+) -> Result<Vec<VersionGroupDiff>, String> {
+    session_manager_read()
+        .await?
+        .diff_version_group(&group_id)
+        .map_err(|e| format!("Failed to diff version group '{group_id}': {e}"))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_get_version_group_comparison(
+    version_group_id: String,
+) -> Result<Vec<VersionComparisonEntry>, String> {
+    session_manager_read()
+        .await?
+        .get_version_group_comparison(&version_group_id)
+        .map_err(|e| format!("Failed to compare version group '{version_group_id}': {e}"))
+}
+
+/// Keeps `winning_session` from its version group and cancels the other versions,
+/// archiving their prompts so the attempts stay inspectable.
+#[tauri::command]
+pub async fn schaltwerk_core_select_version_winner(
+    app: tauri::AppHandle,
+    version_group_id: String,
+    winning_session: String,
+) -> Result<VersionWinnerSelection, String> {
+    let (manager, repo_path_str) = {
+        let core = get_core_write().await?;
+        (
+            core.session_manager(),
+            core.repo_path.to_string_lossy().to_string(),
+        )
+    };
+
+    // Close sibling terminals before their worktrees are removed.
+    let versions: Vec<Session> = manager
+        .list_sessions()
+        .map_err(|e| format!("Failed to list sessions: {e}"))?
+        .into_iter()
+        .filter(|s| s.version_group_id.as_deref() == Some(version_group_id.as_str()))
+        .collect();
+    if versions.iter().any(|s| s.name == winning_session) {
+        for sibling in versions.iter().filter(|s| s.name != winning_session) {
+            terminals::close_session_terminals_if_any(&sibling.name).await;
+        }
+    }
+
+    let selection = manager
+        .select_version_winner(&version_group_id, &winning_session)
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to select '{winning_session}' in version group '{version_group_id}': {e}"
+            )
+        })?;
+
+    for name in &selection.cancelled {
+        events::emit_session_removed(&app, name);
+        evict_session_cache_entry_for_repo(&repo_path_str, name).await;
+        clear_session_attention_state(name.clone());
+    }
+    let archive_count = manager.list_archived_specs().map(|v| v.len()).unwrap_or(0);
+    events::emit_archive_updated(&app, &repo_path_str, archive_count);
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SessionLifecycle);
+
+    Ok(selection)
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_rename_session_display_name(
+    app: tauri::AppHandle,
+    session_id: String,
+    new_display_name: String,
+) -> Result<(), String> {
+    log::info!("Renaming session display name: session_id={session_id}, new_name={new_display_name}");
+
+    let sanitized = schaltwerk::domains::agents::naming::sanitize_name(&new_display_name);
+    if sanitized.is_empty() {
+        return Err("Display name cannot be empty".to_string());
+    }
+
+    let core = get_core_read().await?;
+    let manager = core.session_manager();
+    let db = core.db.clone();
+
+    let current_name = if let Ok(session) = manager.get_session(&session_id) {
+        session.name.clone()
+    } else if let Ok(spec) = manager.get_spec(&session_id) {
+        spec.name.clone()
+    } else {
+        return Err(format!("Session or spec '{session_id}' not found"));
+    };
+
+    let sessions = manager.list_sessions().map_err(|e| e.to_string())?;
+    let specs = manager.list_specs().map_err(|e| e.to_string())?;
+
+    let duplicate_session = sessions.iter().find(|s| {
+        s.name != current_name
+            && s.display_name
+                .as_ref()
+                .map(|dn| dn == &sanitized)
+                .unwrap_or(false)
+    });
+    let duplicate_spec = specs.iter().find(|s| {
+        s.name != current_name
+            && s.display_name
+                .as_ref()
+                .map(|dn| dn == &sanitized)
+                .unwrap_or(false)
+    });
+
+    if duplicate_session.is_some() || duplicate_spec.is_some() {
+        return Err(format!("A session with the name '{sanitized}' already exists"));
+    }
+
+    if let Ok(session) = manager.get_session(&session_id) {
+        db.update_session_display_name(&session.id, &sanitized)
+            .map_err(|e| format!("Failed to update session display name: {e}"))?;
+    } else if let Ok(spec) = manager.get_spec(&session_id) {
+        use schaltwerk::infrastructure::database::db_specs::SpecMethods;
+        db.update_spec_display_name(&spec.id, &sanitized)
+            .map_err(|e| format!("Failed to update spec display name: {e}"))?;
+    }
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::SpecSync);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_append_spec_content(
     name: String,
     content: String,
 ) -> Result<(), String> {
@@ -2614,7 +3502,7 @@ pub async fn schaltwerk_core_start_fresh_orchestrator(
         Err(e) => {
             log::error!("Failed to get schaltwerk_core for fresh orchestrator: {e}");
             // If we can't get a schaltwerk_core (no project), create a user-friendly error
-            if e.contains("No active project") {
+            if e.code == ErrorCode::NoActiveProject {
                 return Err("No project is currently open. Please open a project folder first before starting the orchestrator.".to_string());
             }
             return Err(format!("Failed to initialize orchestrator: {e}"));
@@ -2681,6 +3569,10 @@ pub async fn schaltwerk_core_start_fresh_orchestrator(
     )
     .await?;
 
+    let ignore_patterns = core
+        .db
+        .get_project_watcher_ignore_patterns(&core.repo_path)
+        .unwrap_or_default();
     drop(core);
 
     let base_branch = configured_default_branch.unwrap_or_else(|| {
@@ -2690,7 +3582,11 @@ pub async fn schaltwerk_core_start_fresh_orchestrator(
     match get_file_watcher_manager().await {
         Ok(manager) => {
             if let Err(err) = manager
-                .start_watching_orchestrator(repo_path.clone(), base_branch.clone())
+                .start_watching_orchestrator(
+                    repo_path.clone(),
+                    base_branch.clone(),
+                    &ignore_patterns,
+                )
                 .await
             {
                 log::warn!(
@@ -2804,6 +3700,7 @@ mod tests {
 pub async fn reset_session_worktree_impl(
     app: Option<tauri::AppHandle>,
     session_name: String,
+    discard_stash: bool,
 ) -> Result<(), SchaltError> {
     log::info!("Resetting session worktree to base for: {session_name}");
     let core = get_core_write()
@@ -2814,17 +3711,9 @@ pub async fn reset_session_worktree_impl(
     let manager = core.session_manager();
 
     // Delegate to SessionManager (defensive checks live there)
-    manager.reset_session_worktree(&session_name).map_err(|e| {
-        let message = e.to_string();
-        let normalized = message.to_lowercase();
-        if normalized.contains("failed to get session")
-            || normalized.contains("query returned no rows")
-        {
-            SchaltError::from_session_lookup(&session_name, message)
-        } else {
-            SchaltError::git("reset_session_worktree", message)
-        }
-    })?;
+    manager
+        .reset_session_worktree(&session_name, discard_stash)
+        .map_err(|e| session_git_error(&session_name, "reset_session_worktree", e))?;
 
     // Emit sessions refreshed so UI updates its diffs/state when AppHandle is available
     if let Some(app_handle) = app {
@@ -2833,12 +3722,140 @@ pub async fn reset_session_worktree_impl(
     Ok(())
 }
 
+fn session_git_error(session_name: &str, operation: &str, e: anyhow::Error) -> SchaltError {
+    let message = e.to_string();
+    let normalized = message.to_lowercase();
+    if normalized.contains("failed to get session") || normalized.contains("query returned no rows")
+    {
+        SchaltError::from_session_lookup(session_name, message)
+    } else {
+        SchaltError::git(operation, message)
+    }
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_reset_session_worktree(
     app: tauri::AppHandle,
     session_name: String,
+    discard_stash: Option<bool>,
 ) -> Result<(), SchaltError> {
-    reset_session_worktree_impl(Some(app), session_name).await
+    reset_session_worktree_impl(Some(app), session_name, discard_stash.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_stash_session_changes(
+    app: tauri::AppHandle,
+    name: String,
+    message: Option<String>,
+) -> Result<String, SchaltError> {
+    log::info!("Stashing changes in session '{name}'");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let stash_oid = core
+        .session_manager()
+        .stash_session_changes(&name, message.as_deref())
+        .map_err(|e| session_git_error(&name, "stash_session_changes", e))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    Ok(stash_oid)
+}
+
+/// Recreates the branch of a session whose worktree outlived an external
+/// `git branch -D`, pointing it at the worktree's last commit.
+#[tauri::command]
+pub async fn schaltwerk_core_recreate_session_branch(
+    app: tauri::AppHandle,
+    session_name: String,
+) -> Result<(), SchaltError> {
+    log::info!("Recreating branch for session '{session_name}'");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    core.session_manager()
+        .recreate_session_branch(&session_name)
+        .map_err(|e| session_git_error(&session_name, "recreate_session_branch", e))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_unstash_session_changes(
+    app: tauri::AppHandle,
+    name: String,
+) -> Result<UnstashOutcome, SchaltError> {
+    log::info!("Restoring stashed changes in session '{name}'");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let outcome = core
+        .session_manager()
+        .unstash_session_changes(&name)
+        .map_err(|e| session_git_error(&name, "unstash_session_changes", e))?;
+
+    if outcome.applied {
+        events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    }
+    Ok(outcome)
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_create_session_snapshot(
+    session_name: String,
+    label: Option<String>,
+) -> Result<SessionSnapshot, SchaltError> {
+    log::info!("Snapshotting session '{session_name}'");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    core.session_manager()
+        .create_session_snapshot(&session_name, label.as_deref())
+        .map_err(|e| session_git_error(&session_name, "create_session_snapshot", e))
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_list_session_snapshots(
+    session_name: String,
+) -> Result<Vec<SessionSnapshot>, SchaltError> {
+    let core = get_core_read()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    core.session_manager()
+        .list_session_snapshots(&session_name)
+        .map_err(|e| session_git_error(&session_name, "list_session_snapshots", e))
+}
+
+/// Resets the session worktree to a snapshot. Refused while the session is being merged.
+#[tauri::command]
+pub async fn schaltwerk_core_restore_session_snapshot(
+    app: tauri::AppHandle,
+    session_name: String,
+    snapshot_id: String,
+) -> Result<SessionSnapshot, SchaltError> {
+    log::info!("Restoring snapshot '{snapshot_id}' of session '{session_name}'");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let snapshot = core
+        .session_manager()
+        .restore_session_snapshot(&session_name, &snapshot_id)
+        .map_err(|e| session_git_error(&session_name, "restore_session_snapshot", e))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    Ok(snapshot)
 }
 
 #[tauri::command]
@@ -2868,6 +3885,136 @@ pub async fn schaltwerk_core_discard_file_in_session(
         })
 }
 
+/// Outcome of discarding one file of a multi-file discard; `error` is set when that
+/// file was left untouched.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscardFileOutcome {
+    pub path: String,
+    pub error: Option<String>,
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_discard_files_in_session(
+    session_name: String,
+    file_paths: Vec<String>,
+) -> Result<Vec<DiscardFileOutcome>, SchaltError> {
+    log::info!(
+        "Discarding changes of {} files in session '{session_name}'",
+        file_paths.len()
+    );
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let outcomes = core
+        .session_manager()
+        .discard_files_in_session(&session_name, &file_paths)
+        .map_err(|e| {
+            let message = e.to_string();
+            let normalized = message.to_lowercase();
+            if normalized.contains("failed to get session")
+                || normalized.contains("query returned no rows")
+            {
+                SchaltError::from_session_lookup(&session_name, message)
+            } else {
+                SchaltError::git("discard_files_in_session", message)
+            }
+        })?;
+
+    Ok(outcomes
+        .into_iter()
+        .map(|(path, result)| DiscardFileOutcome {
+            path,
+            error: result.err().map(|e| e.to_string()),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_preview_discard_file_in_session(
+    session_name: String,
+    file_path: String,
+) -> Result<String, SchaltError> {
+    let core = get_core_read()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    core.session_manager()
+        .preview_discard_file(&session_name, &file_path)
+        .map_err(|e| {
+            let message = e.to_string();
+            let normalized = message.to_lowercase();
+            if normalized.contains("failed to get session")
+                || normalized.contains("query returned no rows")
+            {
+                SchaltError::from_session_lookup(&session_name, message)
+            } else {
+                SchaltError::git("preview_discard_file_in_session", message)
+            }
+        })
+}
+
+fn discard_hunk_error(operation: &str, error: anyhow::Error) -> SchaltError {
+    match error.downcast_ref::<StaleHunkError>() {
+        Some(stale) => SchaltError::StaleDiff {
+            path: stale.path.clone(),
+        },
+        None => SchaltError::git(operation, error),
+    }
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_discard_hunk_in_session(
+    session_name: String,
+    file_path: String,
+    hunk_header: String,
+    content_hash: String,
+) -> Result<(), SchaltError> {
+    log::info!("Discarding hunk {hunk_header} of {file_path} in session '{session_name}'");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    core.session_manager()
+        .discard_hunk_in_session(&session_name, &file_path, &hunk_header, &content_hash)
+        .map_err(|e| {
+            let normalized = e.to_string().to_lowercase();
+            if normalized.contains("failed to get session")
+                || normalized.contains("query returned no rows")
+            {
+                SchaltError::from_session_lookup(&session_name, e)
+            } else {
+                discard_hunk_error("discard_hunk_in_session", e)
+            }
+        })
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_revert_file_to_commit(
+    app: tauri::AppHandle,
+    session_name: String,
+    file_path: String,
+    commit_oid: String,
+) -> Result<FileRevertOutcome, SchaltError> {
+    log::info!("Reverting {file_path} in session '{session_name}' to commit {commit_oid}");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let outcome = core
+        .session_manager()
+        .revert_file_to_commit(&session_name, &file_path, &commit_oid)
+        .map_err(|e| session_git_error(&session_name, "revert_file_to_commit", e))?;
+
+    events::request_sessions_refreshed(&app, events::SessionsRefreshReason::GitUpdate);
+    Ok(outcome)
+}
+
 #[tauri::command]
 pub async fn schaltwerk_core_discard_file_in_orchestrator(
     file_path: String,
@@ -2897,6 +4044,38 @@ pub async fn schaltwerk_core_discard_file_in_orchestrator(
     .map_err(|e| SchaltError::git("discard_file_in_orchestrator", e))
 }
 
+#[tauri::command]
+pub async fn schaltwerk_core_discard_hunk_in_orchestrator(
+    file_path: String,
+    hunk_header: String,
+    content_hash: String,
+) -> Result<(), SchaltError> {
+    log::info!("Discarding hunk {hunk_header} of {file_path} in orchestrator");
+    let core = get_core_write()
+        .await
+        .map_err(|e| SchaltError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let repo_path = std::path::Path::new(&core.repo_path).to_path_buf();
+
+    // Safety: disallow .schaltwerk paths
+    if file_path.starts_with(".schaltwerk/") {
+        return Err(SchaltError::invalid_input(
+            "file_path",
+            "Refusing to discard changes under .schaltwerk",
+        ));
+    }
+
+    schaltwerk::domains::git::worktrees::discard_hunk_in_worktree(
+        &repo_path,
+        std::path::Path::new(&file_path),
+        None,
+        &hunk_header,
+        &content_hash,
+    )
+    .map_err(|e| discard_hunk_error("discard_hunk_in_orchestrator", e))
+}
+
 #[cfg(test)]
 mod reset_tests {
     use super::*;
@@ -2904,7 +4083,7 @@ mod reset_tests {
     #[tokio::test]
     async fn test_reset_session_worktree_requires_project() {
         // Without a project initialized, expect a readable error
-        let result = reset_session_worktree_impl(None, "nope".to_string()).await;
+        let result = reset_session_worktree_impl(None, "nope".to_string(), false).await;
         assert!(result.is_err());
         let msg = result.err().unwrap().to_string();
         assert!(
@@ -3,8 +3,11 @@ use crate::commands::schaltwerk_core::schaltwerk_core_cli::extract_codex_prompt_
 use crate::commands::schaltwerk_core::schaltwerk_core_cli::{
     fix_codex_single_dash_long_flags, normalize_cli_text, reorder_codex_model_after_profile,
 };
+use schaltwerk::domains::agents::{
+    CliArgPlaceholders, EnvOverrides, expand_cli_placeholders, layer_launch_env,
+};
 use schaltwerk::schaltwerk_core::db_project_config::ProjectConfigMethods;
-use schaltwerk::services::AgentPreference;
+use schaltwerk::services::{AgentPreference, SessionLaunchOverrides};
 use std::path::Path;
 
 pub enum AgentKind {
@@ -65,6 +68,7 @@ pub async fn collect_agent_env_and_cli(
     agent_kind: &AgentKind,
     repo_path: &Path,
     db: &schaltwerk::schaltwerk_core::Database,
+    session_env: &EnvOverrides,
 ) -> (Vec<(String, String)>, String, AgentPreference) {
     let agent_str = match agent_kind {
         AgentKind::Claude => "claude",
@@ -81,15 +85,11 @@ pub async fn collect_agent_env_and_cli(
 
     let (env_vars, cli_args, preferences) = if let Some(settings_manager) = SETTINGS_MANAGER.get() {
         let mgr = settings_manager.lock().await;
-        let mut env = mgr
-            .get_agent_env_vars(agent_str)
-            .into_iter()
-            .collect::<Vec<_>>();
-        if let Ok(project_env) = db.get_project_environment_variables(repo_path) {
-            env.extend(project_env);
-        }
+        let project_env = db
+            .get_project_environment_variables(repo_path)
+            .unwrap_or_default();
         (
-            env,
+            layer_launch_env(mgr.get_agent_env_vars(agent_str), project_env, session_env),
             mgr.get_agent_cli_args(agent_str),
             mgr.get_agent_preferences(agent_str),
         )
@@ -144,18 +144,15 @@ fn strip_codex_sandbox_overrides(args: &mut Vec<String>) -> Option<Vec<String>>
     }
 }
 
+/// Orders the launch args: configured CLI args first, then the agent's own flags, with
+/// the initial prompt kept last.
 pub fn build_final_args(
     agent_kind: &AgentKind,
     mut parsed_agent_args: Vec<String>,
     cli_args_text: &str,
     preferences: &AgentPreference,
 ) -> Vec<String> {
-    let mut additional = if cli_args_text.trim().is_empty() {
-        Vec::new()
-    } else {
-        let normalized = normalize_cli_text(cli_args_text);
-        shell_words::split(&normalized).unwrap_or_else(|_| vec![cli_args_text.to_string()])
-    };
+    let mut additional = split_cli_text(cli_args_text);
 
     apply_agent_preferences(agent_kind, &parsed_agent_args, &mut additional, preferences);
 
@@ -173,19 +170,88 @@ pub fn build_final_args(
                     "Ignoring Codex CLI sandbox override because Schaltwerk manages sandbox mode: {removed_joined}"
                 );
             }
-            parsed_agent_args.extend(additional);
+            additional.extend(parsed_agent_args);
             if let Some(p) = extracted_prompt {
-                parsed_agent_args.push(p);
+                additional.push(p);
             }
-            parsed_agent_args
+            additional
         }
         _ => {
-            parsed_agent_args.extend(additional);
-            parsed_agent_args
+            additional.extend(parsed_agent_args);
+            additional
+        }
+    }
+}
+
+fn split_cli_text(text: &str) -> Vec<String> {
+    if text.trim().is_empty() {
+        Vec::new()
+    } else {
+        let normalized = normalize_cli_text(text);
+        shell_words::split(&normalized).unwrap_or_else(|_| vec![text.to_string()])
+    }
+}
+
+fn strip_model_flags(args: &mut Vec<String>) {
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--model" || args[i] == "-m" {
+            args.remove(i);
+            if i < args.len() && !args[i].starts_with('-') {
+                args.remove(i);
+            }
+        } else if args[i].starts_with("--model=") {
+            args.remove(i);
+        } else {
+            i += 1;
         }
     }
 }
 
+/// Folds a session's launch overrides into the agent's configured CLI args. A model
+/// override replaces any model flag from the global args; extra flags go last.
+pub fn apply_launch_overrides(cli_args_text: &str, overrides: &SessionLaunchOverrides) -> String {
+    if overrides.is_empty() {
+        return cli_args_text.to_string();
+    }
+
+    let mut args = split_cli_text(cli_args_text);
+    if let Some(model) = overrides.model.as_deref() {
+        strip_model_flags(&mut args);
+        args.push("--model".to_string());
+        args.push(model.to_string());
+    }
+    if let Some(extra_flags) = overrides.extra_flags.as_deref() {
+        args.extend(split_cli_text(extra_flags));
+    }
+    shell_words::join(args)
+}
+
+/// Builds the CLI args text for a session launch: the configured args (placeholders
+/// expanded) with the session's launch overrides, then its own extra args.
+pub fn session_cli_args(
+    cli_args_text: &str,
+    overrides: &SessionLaunchOverrides,
+    extra_cli_args: Option<&str>,
+    placeholders: &CliArgPlaceholders,
+) -> Result<String, String> {
+    let expand =
+        |text: &str| expand_cli_placeholders(text, placeholders).map_err(|e| e.to_string());
+    let configured = apply_launch_overrides(&expand(cli_args_text)?, overrides);
+    match extra_cli_args
+        .map(str::trim)
+        .filter(|args| !args.is_empty())
+    {
+        Some(extra) => Ok(format!("{configured} {}", expand(extra)?)),
+        None => Ok(configured),
+    }
+}
+
+/// Renders an agent invocation as a single shell-quoted command line.
+pub fn format_launch_command(agent_name: &str, args: &[String]) -> String {
+    shell_words::join(std::iter::once(agent_name).chain(args.iter().map(String::as_str)))
+}
+
 fn apply_agent_preferences(
     agent_kind: &AgentKind,
     existing_args: &[String],
@@ -346,7 +412,7 @@ mod tests {
             "--extra one",
             &AgentPreference::default(),
         );
-        assert_eq!(args, vec!["--flag", "--extra", "one"]);
+        assert_eq!(args, vec!["--extra", "one", "--flag"]);
     }
 
     #[test]
@@ -361,14 +427,116 @@ mod tests {
         assert_eq!(
             args,
             vec![
-                "--sandbox",
-                "workspace-write",
                 "--profile",
                 "work",
                 "--model",
-                "gpt-4"
+                "gpt-4",
+                "--sandbox",
+                "workspace-write"
+            ]
+        );
+    }
+
+    #[test]
+    fn launch_overrides_replace_model_and_append_flags() {
+        let overrides = SessionLaunchOverrides {
+            model: Some("opus".into()),
+            extra_flags: Some("--verbose".into()),
+        };
+        let cli = apply_launch_overrides("--model sonnet --add-dir ../shared", &overrides);
+        let args = build_final_args(
+            &AgentKind::Claude,
+            vec!["--flag".into()],
+            &cli,
+            &AgentPreference::default(),
+        );
+        assert_eq!(
+            args,
+            vec![
+                "--add-dir",
+                "../shared",
+                "--model",
+                "opus",
+                "--verbose",
+                "--flag"
             ]
         );
+
+        let cleared = apply_launch_overrides("--model sonnet", &SessionLaunchOverrides::default());
+        assert_eq!(cleared, "--model sonnet");
+    }
+
+    #[test]
+    fn launch_model_override_beats_codex_preference() {
+        let overrides = SessionLaunchOverrides {
+            model: Some("gpt-5-codex".into()),
+            extra_flags: None,
+        };
+        let preferences = AgentPreference {
+            model: Some("gpt-4".into()),
+            reasoning_effort: None,
+        };
+        let args = build_final_args(
+            &AgentKind::Codex,
+            Vec::new(),
+            &apply_launch_overrides("-m gpt-4", &overrides),
+            &preferences,
+        );
+        assert_eq!(args, vec!["--model", "gpt-5-codex"]);
+    }
+
+    #[test]
+    fn session_cli_args_expand_and_order_before_agent_flags() {
+        let placeholders = CliArgPlaceholders {
+            worktree: Path::new("/repo/.schaltwerk/worktrees/my task"),
+            session_name: "my-task",
+            parent_branch: "main",
+            repo_root: Path::new("/repo"),
+        };
+        let overrides = SessionLaunchOverrides {
+            model: Some("opus".into()),
+            extra_flags: None,
+        };
+        let cli = session_cli_args(
+            "--add-dir {worktree}",
+            &overrides,
+            Some("--append-system-prompt 'Work on {session_name} against {parent_branch}'"),
+            &placeholders,
+        )
+        .unwrap();
+        let args = build_final_args(
+            &AgentKind::Claude,
+            vec!["--dangerously-skip-permissions".into(), "fix it".into()],
+            &cli,
+            &AgentPreference::default(),
+        );
+
+        assert_eq!(
+            args,
+            vec![
+                "--add-dir",
+                "/repo/.schaltwerk/worktrees/my task",
+                "--model",
+                "opus",
+                "--append-system-prompt",
+                "Work on my-task against main",
+                "--dangerously-skip-permissions",
+                "fix it"
+            ]
+        );
+    }
+
+    #[test]
+    fn session_cli_args_reject_malformed_placeholders() {
+        let placeholders = CliArgPlaceholders {
+            worktree: Path::new("/wt"),
+            session_name: "s",
+            parent_branch: "main",
+            repo_root: Path::new("/repo"),
+        };
+        let defaults = SessionLaunchOverrides::default();
+        assert!(session_cli_args("--dir {worktree", &defaults, None, &placeholders).is_err());
+        assert!(session_cli_args("", &defaults, Some("{branch}"), &placeholders).is_err());
     }
 
     #[test]
@@ -398,7 +566,7 @@ mod tests {
 
         assert_eq!(
             args,
-            vec!["--sandbox", "workspace-write", "--model", "gpt-4"]
+            vec!["--model", "gpt-4", "--sandbox", "workspace-write"]
         );
     }
 
@@ -415,7 +583,7 @@ mod tests {
 
         assert_eq!(
             args,
-            vec!["--sandbox", "workspace-write", "--profile", "work"]
+            vec!["--profile", "work", "--sandbox", "workspace-write"]
         );
     }
 
@@ -436,12 +604,12 @@ mod tests {
         assert_eq!(
             args,
             vec![
-                "--sandbox",
-                "workspace-write",
                 "--model",
                 "o4-mini",
                 "-c",
                 r#"model_reasoning_effort="high""#,
+                "--sandbox",
+                "workspace-write",
             ]
         );
     }
@@ -468,12 +636,12 @@ mod tests {
         assert_eq!(
             args,
             vec![
+                "-c",
+                "model_reasoning_effort=low",
                 "--sandbox",
                 "workspace-write",
                 "--model",
-                "custom",
-                "-c",
-                "model_reasoning_effort=low"
+                "custom"
             ]
         );
     }
@@ -532,9 +700,9 @@ mod tests {
             args,
             vec![
                 "--sandbox",
-                "workspace-write",
+                "danger-full-access",
                 "--sandbox",
-                "danger-full-access"
+                "workspace-write"
             ]
         );
     }
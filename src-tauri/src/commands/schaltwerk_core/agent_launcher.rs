@@ -1,5 +1,11 @@
 use super::{agent_ctx, terminals};
 use crate::{SETTINGS_MANAGER, get_terminal_manager};
+use schaltwerk::domains::agents::{
+    CliArgPlaceholders, expand_cli_placeholders, redact_args, redact_env,
+};
+use schaltwerk::domains::terminal::crash_backoff::{
+    LaunchDecision, agent_launch_decision, reset_agent_crashes,
+};
 use schaltwerk::services::CreateTerminalWithAppAndSizeParams;
 use schaltwerk::services::{AgentLaunchSpec, parse_agent_command};
 use std::collections::HashMap;
@@ -39,11 +45,11 @@ pub async fn launch_in_terminal(
     repo_path: &std::path::Path,
     cols: Option<u16>,
     rows: Option<u16>,
-    _force_restart: bool,
+    force_restart: bool,
 ) -> Result<String, String> {
     log::info!(
         "[AGENT_LAUNCH_TRACE] launch_in_terminal called: terminal_id={terminal_id}, command={}",
-        launch_spec.shell_command
+        launch_spec.format_for_log()
     );
 
     // Acquire (or create) a lock specific to this terminal id and hold it for the
@@ -60,20 +66,64 @@ pub async fn launch_in_terminal(
     let _guard = term_lock.lock().await;
     log::info!("[AGENT_LAUNCH_TRACE] Acquired term_lock for {terminal_id}");
 
+    // Space out relaunches of a crash-looping agent; an explicit restart starts over.
+    if force_restart {
+        reset_agent_crashes(&terminal_id);
+    }
+    match agent_launch_decision(&terminal_id) {
+        LaunchDecision::Proceed => {}
+        LaunchDecision::Delay(delay) => {
+            log::warn!(
+                "[AGENT_LAUNCH_TRACE] Agent in {terminal_id} crashed recently; waiting {}ms before relaunch",
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+        LaunchDecision::GiveUp { crashes } => {
+            log::error!(
+                "[AGENT_LAUNCH_TRACE] Not relaunching {terminal_id}: agent crashed {crashes} times in a row"
+            );
+            return Err(format!(
+                "Agent crashed {crashes} times in a row; automatic relaunch is paused. Restart the agent to try again."
+            ));
+        }
+    }
+
     let command_prefix = get_agent_command_prefix().await;
 
     let launch_future = async {
         let command_line = launch_spec.format_for_shell();
         let (cwd, agent_name, agent_args) = parse_agent_command(&command_line)?;
         log::info!(
-            "[AGENT_LAUNCH_TRACE] Parsed cwd='{cwd}' agent='{agent_name}' args={agent_args:?}"
+            "[AGENT_LAUNCH_TRACE] Parsed cwd='{cwd}' agent='{agent_name}' args={:?}",
+            redact_args(&agent_args)
         );
         terminals::ensure_cwd_access(&cwd)?;
 
         let agent_kind = agent_ctx::infer_agent_kind(&agent_name);
-        let (env_vars, cli_text, preferences) =
-            agent_ctx::collect_agent_env_and_cli(&agent_kind, repo_path, db).await;
+        let (env_vars, cli_text, preferences) = agent_ctx::collect_agent_env_and_cli(
+            &agent_kind,
+            repo_path,
+            db,
+            &launch_spec.env_overrides,
+        )
+        .await;
+        // Launches without a session (the orchestrator) expand session placeholders to ''
+        let cli_text = expand_cli_placeholders(
+            &cli_text,
+            &CliArgPlaceholders {
+                worktree: std::path::Path::new(&cwd),
+                session_name: "",
+                parent_branch: "",
+                repo_root: repo_path,
+            },
+        )
+        .map_err(|e| e.to_string())?;
         let merged_env = merge_env_vars(env_vars, &launch_spec.env_vars);
+        log::debug!(
+            "[AGENT_LAUNCH_TRACE] Agent environment for {terminal_id}: {}",
+            redact_env(&merged_env)
+        );
         let final_args =
             agent_ctx::build_final_args(&agent_kind, agent_args, &cli_text, &preferences);
 
@@ -0,0 +1,134 @@
+use super::{AgentStartParams, schaltwerk_core_start_agent_in_terminal, terminals};
+use crate::errors::CommandError;
+use crate::get_core_read;
+use schaltwerk::domains::terminal::crash_backoff::reset_agent_crashes;
+use schaltwerk::domains::terminal::crash_supervisor::{
+    AgentExit, RestartDecision, decide_agent_restart, reset_agent_restarts,
+    set_agent_exit_listener,
+};
+use schaltwerk::infrastructure::events::{SchaltEvent, emit_event};
+use schaltwerk::schaltwerk_core::db_project_config::{AgentRestartPolicy, ProjectConfigMethods};
+use tauri::AppHandle;
+
+#[derive(serde::Serialize, Clone)]
+struct AgentGaveUpPayload {
+    terminal_id: String,
+    session_name: String,
+    exit_code: u32,
+    restart_attempts: u32,
+    gave_up: bool,
+    stderr_tail: String,
+}
+
+/// Routes agent exits from the terminal layer into the per-project restart policy.
+pub fn install_agent_crash_supervisor(app: AppHandle) {
+    set_agent_exit_listener(move |exit| {
+        let app = app.clone();
+        tokio::spawn(async move { handle_agent_exit(app, exit).await });
+    });
+}
+
+/// Finds the session whose top terminal is `terminal_id` and the project's restart policy.
+async fn resolve_session(
+    terminal_id: &str,
+) -> Result<Option<(String, AgentRestartPolicy)>, String> {
+    let core = get_core_read().await?;
+    let sessions = core
+        .session_manager()
+        .list_sessions()
+        .map_err(|e| format!("Failed to list sessions: {e}"))?;
+    let Some(session) = sessions
+        .into_iter()
+        .find(|s| terminals::terminal_id_for_session_top(&s.name) == terminal_id)
+    else {
+        return Ok(None);
+    };
+    let policy = core
+        .db
+        .get_project_sessions_settings(&core.repo_path)
+        .map_err(|e| format!("Failed to load sessions settings: {e}"))?
+        .agent_restart_policy
+        .unwrap_or_default();
+    Ok(Some((session.name, policy)))
+}
+
+async fn handle_agent_exit(app: AppHandle, exit: AgentExit) {
+    let (session_name, policy) = match resolve_session(&exit.terminal_id).await {
+        Ok(Some(resolved)) => resolved,
+        Ok(None) => return,
+        Err(e) => {
+            log::debug!("Crash supervisor skipped {}: {e}", exit.terminal_id);
+            return;
+        }
+    };
+
+    match decide_agent_restart(&policy, &exit) {
+        RestartDecision::Ignore => {}
+        RestartDecision::Restart { attempt, delay } => {
+            log::warn!(
+                "Agent in session '{session_name}' crashed (exit code {}); restart {attempt}/{} in {}s",
+                exit.exit_code,
+                policy.max_retries,
+                delay.as_secs()
+            );
+            tokio::time::sleep(delay).await;
+            if let Err(e) = relaunch_session_agent(app, session_name.clone(), Some(attempt)).await {
+                log::warn!("Automatic restart of agent in session '{session_name}' failed: {e}");
+            }
+        }
+        RestartDecision::GiveUp {
+            attempts,
+            stderr_tail,
+        } => {
+            log::error!(
+                "Agent in session '{session_name}' kept crashing; giving up after {attempts} restarts"
+            );
+            let payload = AgentGaveUpPayload {
+                terminal_id: exit.terminal_id,
+                session_name,
+                exit_code: exit.exit_code,
+                restart_attempts: attempts,
+                gave_up: true,
+                stderr_tail,
+            };
+            if let Err(e) = emit_event(&app, SchaltEvent::AgentCrashed, &payload) {
+                log::warn!("Failed to emit agent-crashed event: {e}");
+            }
+        }
+    }
+}
+
+/// Relaunches the session's agent through the resume path.
+async fn relaunch_session_agent(
+    app: AppHandle,
+    session_name: String,
+    restart_attempt: Option<u32>,
+) -> Result<String, CommandError> {
+    schaltwerk_core_start_agent_in_terminal(
+        app,
+        AgentStartParams {
+            session_name,
+            force_restart: false,
+            cols: None,
+            rows: None,
+            terminal_id_override: None,
+            agent_type_override: None,
+            skip_prompt: true,
+            skip_permissions_override: None,
+            restart_attempt,
+        },
+    )
+    .await
+}
+
+/// Restarts an agent after the crash supervisor gave up, resetting its restart budget.
+#[tauri::command]
+pub async fn schaltwerk_core_restart_crashed_agent(
+    app: AppHandle,
+    session_name: String,
+) -> Result<String, CommandError> {
+    let terminal_id = terminals::terminal_id_for_session_top(&session_name);
+    reset_agent_restarts(&terminal_id);
+    reset_agent_crashes(&terminal_id);
+    relaunch_session_agent(app, session_name, None).await
+}
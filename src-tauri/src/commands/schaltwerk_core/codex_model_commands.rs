@@ -3,6 +3,7 @@ use crate::{
     commands::schaltwerk_core::{agent_ctx, codex_models, schaltwerk_core_cli},
     get_core_read,
 };
+use schaltwerk::domains::agents::EnvOverrides;
 use schaltwerk::services::AgentManifest;
 
 #[tauri::command]
@@ -18,8 +19,13 @@ pub async fn schaltwerk_core_list_codex_models() -> Result<codex_models::CodexMo
         (core.repo_path.clone(), core.db.clone())
     };
 
-    let (env_vars, cli_args_text, _) =
-        agent_ctx::collect_agent_env_and_cli(&agent_ctx::AgentKind::Codex, &repo_path, &db).await;
+    let (env_vars, cli_args_text, _) = agent_ctx::collect_agent_env_and_cli(
+        &agent_ctx::AgentKind::Codex,
+        &repo_path,
+        &db,
+        &EnvOverrides::new(),
+    )
+    .await;
 
     let cli_args = if cli_args_text.trim().is_empty() {
         Vec::new()
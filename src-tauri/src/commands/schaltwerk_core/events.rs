@@ -1,6 +1,12 @@
 pub use crate::commands::sessions_refresh::SessionsRefreshReason;
 use crate::commands::sessions_refresh::request_sessions_refresh;
+use schaltwerk::infrastructure::agent_status_bridge::{
+    set_agent_status_listener, set_approval_listener,
+};
+use schaltwerk::infrastructure::attention_bridge::update_session_attention_state;
 use schaltwerk::infrastructure::events::{SchaltEvent, emit_event};
+use schaltwerk::services::setup_scripts::SetupStatus;
+use schaltwerk::services::{BatchUpdateProgress, CherryPickOutcome, UpdateFromParentStatus};
 use tauri::AppHandle;
 
 #[derive(serde::Serialize, Clone)]
@@ -29,6 +35,9 @@ pub struct GitOperationPayload {
     pub operation: &'static str,
     pub commit: Option<String>,
     pub status: &'static str,
+    /// Groups the events of one batch update so the UI can show aggregate progress.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_id: Option<String>,
 }
 
 #[derive(serde::Serialize, Clone)]
@@ -99,6 +108,7 @@ pub fn emit_git_operation_started(
         operation: "merge",
         commit: None,
         status: "started",
+        batch_id: None,
     };
     let _ = emit_event(app, SchaltEvent::GitOperationStarted, &payload);
 }
@@ -119,6 +129,7 @@ pub fn emit_git_operation_completed(
         operation: "merge",
         commit: Some(commit.to_string()),
         status: "success",
+        batch_id: None,
     };
     let _ = emit_event(app, SchaltEvent::GitOperationCompleted, &payload);
 }
@@ -141,8 +152,207 @@ pub fn emit_git_operation_failed(
             operation: "merge",
             commit: None,
             status,
+            batch_id: None,
         },
         error: error.to_string(),
     };
     let _ = emit_event(app, SchaltEvent::GitOperationFailed, &payload);
 }
+
+/// Reports a cherry-pick of session commits: `GitOperationCompleted` with the new parent
+/// tip, or `GitOperationFailed` with status `conflict` naming the offending commit.
+pub fn emit_cherry_pick_finished(app: &AppHandle, session_name: &str, outcome: &CherryPickOutcome) {
+    let base = |commit: Option<String>, status: &'static str| GitOperationPayload {
+        session_name: session_name.to_string(),
+        session_branch: outcome.session_branch.clone(),
+        parent_branch: outcome.parent_branch.clone(),
+        mode: "cherry_pick".to_string(),
+        operation: "cherry_pick",
+        commit,
+        status,
+        batch_id: None,
+    };
+
+    let _ = match &outcome.conflict {
+        None => emit_event(
+            app,
+            SchaltEvent::GitOperationCompleted,
+            &base(outcome.new_parent_oid.clone(), "success"),
+        ),
+        Some(conflict) => {
+            let payload = GitOperationFailedPayload {
+                base: base(Some(conflict.commit.clone()), "conflict"),
+                error: format!(
+                    "Commit {} conflicts with '{}' in: {}",
+                    conflict.commit,
+                    outcome.parent_branch,
+                    conflict.conflicting_paths.join(", ")
+                ),
+            };
+            emit_event(app, SchaltEvent::GitOperationFailed, &payload)
+        }
+    };
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct MergeCheckOutputPayload {
+    pub session_name: String,
+    pub line: String,
+}
+
+pub fn emit_merge_check_output(app: &AppHandle, session_name: &str, line: &str) {
+    let payload = MergeCheckOutputPayload {
+        session_name: session_name.to_string(),
+        line: line.to_string(),
+    };
+    let _ = emit_event(app, SchaltEvent::MergeCheckOutput, &payload);
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct SetupScriptFailedPayload {
+    pub session_name: String,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub output_tail: String,
+}
+
+pub fn emit_setup_script_failed(
+    app: &AppHandle,
+    session_name: &str,
+    exit_code: Option<i32>,
+    timed_out: bool,
+    output_tail: &str,
+) {
+    let payload = SetupScriptFailedPayload {
+        session_name: session_name.to_string(),
+        exit_code,
+        timed_out,
+        output_tail: output_tail.to_string(),
+    };
+    let _ = emit_event(app, SchaltEvent::SetupScriptFailed, &payload);
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct SetupScriptOutputPayload {
+    pub session_name: String,
+    pub chunk: String,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct SetupScriptProgressPayload {
+    pub session_name: String,
+    pub status: SetupStatus,
+}
+
+pub fn emit_setup_script_progress(app: &AppHandle, session_name: &str, status: SetupStatus) {
+    let payload = SetupScriptProgressPayload {
+        session_name: session_name.to_string(),
+        status,
+    };
+    let _ = emit_event(app, SchaltEvent::SetupScriptProgress, &payload);
+}
+
+pub fn emit_setup_script_output(app: &AppHandle, session_name: &str, chunk: &str) {
+    let payload = SetupScriptOutputPayload {
+        session_name: session_name.to_string(),
+        chunk: chunk.to_string(),
+    };
+    let _ = emit_event(app, SchaltEvent::SetupScriptOutput, &payload);
+}
+
+pub fn emit_draft_pr_failed(
+    app: &AppHandle,
+    session_name: &str,
+    session_branch: &str,
+    parent_branch: &str,
+    error: &str,
+) {
+    let payload = GitOperationFailedPayload {
+        base: GitOperationPayload {
+            session_name: session_name.to_string(),
+            session_branch: session_branch.to_string(),
+            parent_branch: parent_branch.to_string(),
+            mode: "draft".to_string(),
+            operation: "draft_pr",
+            commit: None,
+            status: "error",
+            batch_id: None,
+        },
+        error: error.to_string(),
+    };
+    let _ = emit_event(app, SchaltEvent::GitOperationFailed, &payload);
+}
+
+/// Reports one session of a batch update from its parent branch. Sessions that were
+/// skipped or left untouched because of conflicts are reported as failures.
+pub fn emit_batch_update_progress(
+    app: &AppHandle,
+    batch_id: &str,
+    progress: &BatchUpdateProgress<'_>,
+) {
+    let base = |status: &'static str| GitOperationPayload {
+        session_name: progress.session().name.clone(),
+        session_branch: progress.session().branch.clone(),
+        parent_branch: progress.session().parent_branch.clone(),
+        mode: "update_from_parent".to_string(),
+        operation: "update_from_parent",
+        commit: None,
+        status,
+        batch_id: Some(batch_id.to_string()),
+    };
+
+    let _ = match progress {
+        BatchUpdateProgress::Started(_) => {
+            emit_event(app, SchaltEvent::GitOperationStarted, &base("started"))
+        }
+        BatchUpdateProgress::Finished(_, result) => match result.status {
+            UpdateFromParentStatus::Success | UpdateFromParentStatus::AlreadyUpToDate => {
+                emit_event(app, SchaltEvent::GitOperationCompleted, &base("success"))
+            }
+            status => {
+                let status = match status {
+                    UpdateFromParentStatus::HasConflicts => "conflict",
+                    UpdateFromParentStatus::HasUncommittedChanges => "skipped",
+                    _ => "error",
+                };
+                let payload = GitOperationFailedPayload {
+                    base: base(status),
+                    error: result.message.clone(),
+                };
+                emit_event(app, SchaltEvent::GitOperationFailed, &payload)
+            }
+        },
+    };
+}
+
+/// Forwards agent status changes parsed from terminal output to the frontend and raises
+/// terminal attention while an agent waits for approval.
+pub fn install_agent_status_events(app: AppHandle) {
+    let approval_app = app.clone();
+    set_agent_status_listener(move |status| {
+        if let Err(e) = emit_event(&app, SchaltEvent::SessionAgentStatus, &status) {
+            log::debug!(
+                "Failed to emit agent status for session '{}': {e}",
+                status.session_name
+            );
+        }
+    });
+
+    set_approval_listener(move |change| {
+        let needs_attention = change.prompt.is_some();
+        let payload = serde_json::json!({
+            "session_id": change.session_name,
+            "terminal_id": change.terminal_id,
+            "needs_attention": needs_attention,
+            "reason": "approval-request",
+            "prompt": change.prompt,
+        });
+        if let Err(e) = emit_event(&approval_app, SchaltEvent::TerminalAttention, &payload) {
+            log::debug!(
+                "Failed to emit approval attention for session '{}': {e}",
+                change.session_name
+            );
+        }
+        update_session_attention_state(change.session_name, needs_attention);
+    });
+}
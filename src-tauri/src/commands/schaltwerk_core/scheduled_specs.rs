@@ -0,0 +1,131 @@
+use crate::commands::schaltwerk_core::events::{SessionsRefreshReason, request_sessions_refreshed};
+use crate::events::{SchaltEvent, ScheduledSpecsChangedPayload, emit_event};
+use crate::{get_core_read, get_core_write, get_project_manager};
+use chrono::{DateTime, Utc};
+use schaltwerk::schaltwerk_core::SessionManager;
+use schaltwerk::services::{ScheduleSpecOptions, ScheduledSpec, scheduled_max_running_from_env};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+fn emit_scheduled_specs_changed(app: &AppHandle, manager: &SessionManager, repo_path: PathBuf) {
+    let entries = match manager.list_scheduled_specs() {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Failed to list scheduled specs for change event: {e}");
+            return;
+        }
+    };
+    let payload = ScheduledSpecsChangedPayload {
+        project_path: repo_path.to_string_lossy().to_string(),
+        entries,
+    };
+    if let Err(e) = emit_event(app, SchaltEvent::ScheduledSpecsChanged, &payload) {
+        log::warn!("Failed to emit ScheduledSpecsChanged event: {e}");
+    }
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_list_scheduled_specs() -> Result<Vec<ScheduledSpec>, String> {
+    let manager = get_core_read().await?.session_manager();
+    manager
+        .list_scheduled_specs()
+        .map_err(|e| format!("Failed to list scheduled specs: {e}"))
+}
+
+/// Schedules spec `name` to start at `not_before`, an RFC 3339 timestamp. The offset
+/// is honoured and the time is stored in UTC.
+#[tauri::command]
+pub async fn schaltwerk_core_schedule_spec(
+    app: AppHandle,
+    name: String,
+    not_before: String,
+    base_branch: Option<String>,
+    agent_type: Option<String>,
+    skip_permissions: Option<bool>,
+    skip_if_late: Option<bool>,
+) -> Result<ScheduledSpec, String> {
+    let not_before = DateTime::parse_from_rfc3339(&not_before)
+        .map_err(|e| format!("Invalid schedule time '{not_before}': {e}"))?
+        .with_timezone(&Utc);
+
+    let (manager, repo_path) = {
+        let core = get_core_write().await?;
+        (core.session_manager(), core.repo_path.clone())
+    };
+    let options = ScheduleSpecOptions {
+        base_branch,
+        agent_type,
+        skip_permissions,
+        skip_if_late: skip_if_late.unwrap_or(false),
+    };
+    let entry = manager
+        .schedule_spec(&name, not_before, options)
+        .map_err(|e| format!("Failed to schedule spec '{name}': {e}"))?;
+
+    emit_scheduled_specs_changed(&app, &manager, repo_path);
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn schaltwerk_core_cancel_scheduled_spec(
+    app: AppHandle,
+    id: String,
+) -> Result<ScheduledSpec, String> {
+    let (manager, repo_path) = {
+        let core = get_core_write().await?;
+        (core.session_manager(), core.repo_path.clone())
+    };
+    let entry = manager
+        .cancel_scheduled_spec(&id)
+        .map_err(|e| format!("Failed to cancel scheduled spec: {e}"))?;
+
+    emit_scheduled_specs_changed(&app, &manager, repo_path);
+    Ok(entry)
+}
+
+/// Starts due scheduled specs of whichever project is open. Entries of other projects
+/// wait until their project is opened again; anything that fell due meanwhile counts
+/// as missed from the moment the project became current.
+pub async fn run_spec_scheduler(app: AppHandle) {
+    let max_running = scheduled_max_running_from_env();
+    let mut active: Option<(PathBuf, DateTime<Utc>)> = None;
+    let mut interval = tokio::time::interval(SCHEDULER_TICK);
+
+    loop {
+        interval.tick().await;
+
+        let Some(path) = get_project_manager().await.current_project_path().await else {
+            active = None;
+            continue;
+        };
+        let active_since = match &active {
+            Some((active_path, since)) if *active_path == path => *since,
+            _ => {
+                let since = Utc::now();
+                active = Some((path.clone(), since));
+                since
+            }
+        };
+
+        let (manager, repo_path) = match get_core_write().await {
+            Ok(core) => (core.session_manager(), core.repo_path.clone()),
+            Err(e) => {
+                log::debug!("Spec scheduler idle: {e}");
+                continue;
+            }
+        };
+
+        match manager.run_due_scheduled_specs(Utc::now(), active_since, max_running) {
+            Ok(changed) if changed.is_empty() => {}
+            Ok(changed) => {
+                log::info!("Spec scheduler processed {} entries", changed.len());
+                emit_scheduled_specs_changed(&app, &manager, repo_path);
+                request_sessions_refreshed(&app, SessionsRefreshReason::SessionLifecycle);
+            }
+            Err(e) => log::warn!("Spec scheduler pass failed: {e}"),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use super::events::{SessionsRefreshReason, request_sessions_refreshed};
+use super::{get_agent_env_and_cli_args_async, is_versioned_session_name};
+use crate::{get_core_read, get_project_manager};
+use chrono::Utc;
+use schaltwerk::domains::agents::naming::{NameGenerationArgs, suggest_display_name};
+use schaltwerk::domains::sessions::entity::Session;
+use schaltwerk::domains::sessions::name_retry::{
+    NameRetryDecision, NameRetryState, decide_name_retry,
+};
+use schaltwerk::schaltwerk_core::db_app_config::AppConfigMethods;
+use schaltwerk::schaltwerk_core::db_project_config::ProjectConfigMethods;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const NAME_RETRY_TICK: Duration = Duration::from_secs(30);
+
+/// Runs the agent naming flow for `session_name` and applies the result. Returns
+/// `Ok(None)` when the agent produced no usable name; the pending flag is left as is.
+async fn run_session_naming(session_name: &str) -> Result<Option<Session>, String> {
+    let (session, agent, db, repo_path) = {
+        let core = get_core_read().await?;
+        let session = core
+            .session_manager()
+            .get_session(session_name)
+            .map_err(|e| format!("Session '{session_name}' not found: {e}"))?;
+        let agent = session.original_agent_type.clone().unwrap_or_else(|| {
+            core.db
+                .get_agent_type()
+                .unwrap_or_else(|_| "claude".to_string())
+        });
+        (session, agent, core.db.clone(), core.repo_path.clone())
+    };
+
+    let (mut env_vars, cli_args, binary_path, _) = get_agent_env_and_cli_args_async(&agent).await;
+    if let Ok(project_env_vars) = db.get_project_environment_variables(&repo_path) {
+        env_vars.extend(project_env_vars);
+    }
+    let cli_args = (!cli_args.is_empty()).then_some(cli_args);
+
+    let suggested = suggest_display_name(NameGenerationArgs {
+        db: &db,
+        target_id: &session.id,
+        worktree_path: &session.worktree_path,
+        agent_type: &agent,
+        initial_prompt: session.initial_prompt.as_deref(),
+        cli_args: cli_args.as_deref(),
+        env_vars: &env_vars,
+        binary_path: binary_path.as_deref(),
+    })
+    .await
+    .map_err(|e| format!("Name generation failed for session '{session_name}': {e}"))?;
+
+    let Some(display_name) = suggested else {
+        return Ok(None);
+    };
+
+    let core = get_core_read().await?;
+    core.session_manager()
+        .apply_generated_session_name(session_name, &display_name)
+        .map(Some)
+        .map_err(|e| format!("Failed to apply generated name to '{session_name}': {e}"))
+}
+
+/// Generates a name for a freshly created session in the background. Failures keep
+/// `pending_name_generation` set so [`run_session_name_retrier`] can try again.
+pub(super) fn spawn_session_name_generation(app: AppHandle, session_name: String) {
+    tokio::spawn(async move {
+        match run_session_naming(&session_name).await {
+            Ok(Some(session)) => {
+                log::info!(
+                    "Generated display name {:?} for session '{session_name}'",
+                    session.display_name
+                );
+                request_sessions_refreshed(&app, SessionsRefreshReason::SessionLifecycle);
+            }
+            Ok(None) => {
+                log::warn!("Name generation returned None for session '{session_name}'");
+            }
+            Err(e) => log::warn!("{e}"),
+        }
+    });
+}
+
+/// Re-runs name generation for one session on demand, regardless of whether it is
+/// still pending, and renames its branch to match.
+#[tauri::command]
+pub async fn schaltwerk_core_generate_session_name(
+    app: AppHandle,
+    session_name: String,
+) -> Result<Session, String> {
+    let session = run_session_naming(&session_name)
+        .await?
+        .ok_or_else(|| format!("Agent did not produce a name for session '{session_name}'"))?;
+    request_sessions_refreshed(&app, SessionsRefreshReason::SessionLifecycle);
+    Ok(session)
+}
+
+/// Background task that retries naming for sessions still pending two minutes after
+/// they started, backing off exponentially. After the last failed attempt the session
+/// keeps its original name and is no longer marked pending.
+pub async fn run_session_name_retrier(app: AppHandle) {
+    let mut retries: HashMap<(PathBuf, String), NameRetryState> = HashMap::new();
+    let mut interval = tokio::time::interval(NAME_RETRY_TICK);
+
+    loop {
+        interval.tick().await;
+
+        let Some(project) = get_project_manager().await.current_project_path().await else {
+            continue;
+        };
+        let pending = match get_core_read().await {
+            Ok(core) => match core.session_manager().list_sessions() {
+                Ok(sessions) => sessions
+                    .into_iter()
+                    .filter(|s| s.pending_name_generation && !is_versioned_session_name(&s.name))
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    log::debug!("Name retrier could not list sessions: {e}");
+                    continue;
+                }
+            },
+            Err(e) => {
+                log::debug!("Name retrier idle: {e}");
+                continue;
+            }
+        };
+        retries.retain(|(path, id), _| *path != project || pending.iter().any(|s| s.id == *id));
+
+        for session in pending {
+            let key = (project.clone(), session.id.clone());
+            let state = retries.get(&key).copied().unwrap_or_default();
+            match decide_name_retry(session.created_at, &state, Utc::now()) {
+                NameRetryDecision::Wait => {}
+                NameRetryDecision::GiveUp => {
+                    log::warn!(
+                        "Giving up on name generation for session '{}' after {} attempts",
+                        session.name,
+                        state.attempts
+                    );
+                    let abandoned = match get_core_read().await {
+                        Ok(core) => core
+                            .session_manager()
+                            .abandon_name_generation(&session.name)
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    if let Err(e) = abandoned {
+                        log::warn!("Failed to clear pending name for '{}': {e}", session.name);
+                    }
+                    retries.remove(&key);
+                }
+                NameRetryDecision::Retry => {
+                    log::info!(
+                        "Retrying name generation for session '{}' (attempt {})",
+                        session.name,
+                        state.attempts + 1
+                    );
+                    match run_session_naming(&session.name).await {
+                        Ok(Some(_)) => {
+                            retries.remove(&key);
+                            request_sessions_refreshed(
+                                &app,
+                                SessionsRefreshReason::SessionLifecycle,
+                            );
+                        }
+                        outcome => {
+                            if let Err(e) = outcome {
+                                log::warn!("{e}");
+                            }
+                            retries.insert(
+                                key,
+                                NameRetryState {
+                                    attempts: state.attempts + 1,
+                                    last_attempt: Some(Utc::now()),
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
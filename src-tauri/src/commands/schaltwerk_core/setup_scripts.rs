@@ -0,0 +1,144 @@
+use crate::commands::schaltwerk_core::events::{
+    SessionsRefreshReason, emit_setup_script_failed, emit_setup_script_output,
+    emit_setup_script_progress, request_sessions_refreshed,
+};
+use crate::{get_core_read, get_terminal_manager};
+use schaltwerk::schaltwerk_core::db_project_config::{
+    DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS, DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS, ProjectConfigMethods,
+};
+use schaltwerk::services::Session;
+use schaltwerk::services::SessionMethods;
+use schaltwerk::services::setup_scripts::{
+    SetupOutcome, SetupRunsRegistry, SetupScriptLaunch, SetupSlots, SetupStatus,
+    launch_setup_script, wait_for_setup_script,
+};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Queues the project setup script for the session's bottom terminal without waiting
+/// for it. Scripts of sessions created together run in parallel, up to the project's
+/// cap; each reports its own state through `SetupScriptProgress` and streams its output
+/// through `SetupScriptOutput`. The session reports setup as running until the script
+/// exits or hits the project's time limit. The result is recorded on the session; a
+/// failure is also reported through `SetupScriptFailed`, but the session is kept and
+/// other setups carry on. Returns false when no setup script is set.
+pub async fn spawn_session_setup(app: &AppHandle, session: &Session) -> Result<bool, String> {
+    let (db, launch, timeout, max_parallel) = {
+        let core = get_core_read().await?;
+        let script = core
+            .db
+            .get_project_setup_script(&core.repo_path)
+            .map_err(|e| format!("Failed to get project setup script: {e}"))?
+            .filter(|script| !script.trim().is_empty());
+        let Some(script) = script else {
+            return Ok(false);
+        };
+        let project_env = core
+            .db
+            .get_project_environment_variables(&core.repo_path)
+            .unwrap_or_default();
+        let timeout = core
+            .db
+            .get_project_setup_script_timeout(&core.repo_path)
+            .unwrap_or(Some(Duration::from_secs(DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS)));
+        let max_parallel = core
+            .db
+            .get_project_max_parallel_setup_scripts(&core.repo_path)
+            .unwrap_or(DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS as usize);
+        let launch = SetupScriptLaunch::prepare(session, &script, project_env)
+            .map_err(|e| format!("Failed to write setup script: {e}"))?;
+        (core.db.clone(), launch, timeout, max_parallel)
+    };
+
+    let terminal_manager = get_terminal_manager().await?;
+    let registry = SetupRunsRegistry::global();
+    if let Err(e) = registry.begin(&launch.session_name) {
+        let _ = std::fs::remove_file(&launch.script_path);
+        return Err(e);
+    }
+    emit_setup_script_progress(app, &launch.session_name, SetupStatus::Queued);
+    request_sessions_refreshed(app, SessionsRefreshReason::SessionLifecycle);
+
+    let app = app.clone();
+    let session_id = session.id.clone();
+    tokio::spawn(async move {
+        let outcome = {
+            let _slot = SetupSlots::global().acquire(max_parallel).await;
+            match launch_setup_script(&terminal_manager, registry, &launch).await {
+                Ok(from_seq) => {
+                    log::info!(
+                        "Running setup script for session '{}' in {}",
+                        launch.session_name,
+                        launch.terminal_id
+                    );
+                    emit_setup_script_progress(&app, &launch.session_name, SetupStatus::Running);
+                    wait_for_setup_script(
+                        &terminal_manager,
+                        registry,
+                        &launch,
+                        from_seq,
+                        timeout,
+                        |chunk| emit_setup_script_output(&app, &launch.session_name, chunk),
+                    )
+                    .await
+                }
+                Err(e) => SetupOutcome {
+                    status: SetupStatus::Failed { exit_code: None },
+                    output_tail: format!("Failed to start setup script: {e}"),
+                },
+            }
+        };
+        let failed = outcome.status != SetupStatus::Succeeded;
+        if let Err(e) = db.set_session_setup_failed(&session_id, failed) {
+            log::warn!(
+                "Failed to record setup result for session '{}': {e}",
+                launch.session_name
+            );
+        }
+        match outcome.status {
+            SetupStatus::Failed { exit_code } => {
+                log::warn!(
+                    "Setup script for session '{}' failed with exit code {exit_code:?}",
+                    launch.session_name
+                );
+                emit_setup_script_failed(
+                    &app,
+                    &launch.session_name,
+                    exit_code,
+                    false,
+                    &outcome.output_tail,
+                );
+            }
+            SetupStatus::TimedOut => emit_setup_script_failed(
+                &app,
+                &launch.session_name,
+                None,
+                true,
+                &outcome.output_tail,
+            ),
+            SetupStatus::Queued | SetupStatus::Running | SetupStatus::Succeeded => {}
+        }
+        emit_setup_script_progress(&app, &launch.session_name, outcome.status);
+        request_sessions_refreshed(&app, SessionsRefreshReason::SessionLifecycle);
+    });
+    Ok(true)
+}
+
+/// Runs the project setup script again in the session's bottom terminal.
+#[tauri::command]
+pub async fn schaltwerk_core_run_setup_script(
+    app: AppHandle,
+    session_name: String,
+) -> Result<(), String> {
+    let session = get_core_read()
+        .await?
+        .session_manager()
+        .get_session(&session_name)
+        .map_err(|e| format!("Session '{session_name}' not found: {e}"))?;
+
+    if spawn_session_setup(&app, &session).await? {
+        Ok(())
+    } else {
+        Err("No setup script configured for this project".to_string())
+    }
+}
@@ -8,11 +8,11 @@ use tokio::sync::Mutex;
 
 use crate::{
     commands::session_lookup_cache::{current_repo_cache_key, global_session_lookup_cache},
-    get_core_read,
+    get_core_read, get_terminal_manager,
 };
 use schaltwerk::infrastructure::events::{SchaltEvent, emit_event};
 use schaltwerk::services::power::sync_running_sessions;
-use schaltwerk::services::{EnrichedSession, SessionState};
+use schaltwerk::services::{EnrichedSession, SessionState, attach_terminal_details};
 use serde::Serialize;
 
 const DEFAULT_COOLDOWN: Duration = Duration::from_millis(125);
@@ -193,8 +193,16 @@ impl RefreshHub {
             let core = get_core_read().await.map_err(|e| anyhow!(e))?;
             core.session_manager()
         };
+        let terminal_liveness = match get_terminal_manager().await {
+            Ok(terminal_manager) => terminal_manager.liveness_snapshot().await,
+            Err(e) => {
+                log::debug!("[SessionsRefreshHub] No terminal liveness available: {e}");
+                Default::default()
+            }
+        };
         let snap_start = Instant::now();
-        let sessions = manager.list_enriched_sessions()?;
+        let mut sessions = manager.list_enriched_sessions()?;
+        attach_terminal_details(&mut sessions, &terminal_liveness);
         let snap_elapsed = snap_start.elapsed().as_millis();
         if snap_elapsed > 400 {
             log::warn!(
@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 
+use crate::mcp_api::request_log::WebhookRequestLog;
 use crate::{PROJECT_MANAGER, get_core_read, get_core_write, get_settings_manager};
+use schaltwerk::domains::sessions::templates::validate_prompt_template;
 use schaltwerk::schaltwerk_core::db_app_config::AppConfigMethods;
 use schaltwerk::schaltwerk_core::db_project_config::{
-    HeaderActionConfig, ProjectConfigMethods, ProjectMergePreferences, ProjectSessionsSettings,
-    RunScript, default_action_buttons,
+    BranchCollisionStrategy, HeaderActionConfig, ProjectConfigMethods, ProjectMergePreferences,
+    ProjectSessionsSettings, PromptTemplate, RunScript, default_action_buttons,
 };
 use schaltwerk::services::{
-    AgentPreference, DiffViewPreferences, McpServerConfig, SessionPreferences, TerminalSettings,
-    TerminalUIPreferences,
+    AgentPreference, DiffViewPreferences, McpServerConfig, SessionPreferences,
+    SkipPermissionsPolicy, TerminalSettings, TerminalUIPreferences, normalize_commit_trailers,
 };
 use tauri::AppHandle;
 
@@ -412,6 +414,357 @@ pub async fn set_project_merge_preferences(
         .map_err(|e| format!("Failed to set project merge preferences: {e}"))
 }
 
+#[tauri::command]
+pub async fn get_project_merge_commit_trailers() -> Result<Vec<String>, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_merge_commit_trailers(&project.path)
+        .map_err(|e| format!("Failed to get project merge commit trailers: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_merge_commit_trailers(trailers: Vec<String>) -> Result<(), String> {
+    let trailers = normalize_commit_trailers(&trailers).map_err(|e| e.to_string())?;
+
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_merge_commit_trailers(&project.path, &trailers)
+        .map_err(|e| format!("Failed to set project merge commit trailers: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_skip_permissions_policy() -> Result<SkipPermissionsPolicy, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    SkipPermissionsPolicy::load(db, &project.path)
+        .map_err(|e| format!("Failed to get project skip-permissions policy: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_skip_permissions_policy(
+    policy: SkipPermissionsPolicy,
+) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_skip_permissions_policy(&project.path, policy.as_str())
+        .map_err(|e| format!("Failed to set project skip-permissions policy: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_worktree_root() -> Result<Option<String>, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_worktree_root(&project.path)
+        .map(|root| root.map(|r| r.to_string_lossy().to_string()))
+        .map_err(|e| format!("Failed to get project worktree root: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_worktree_root(root: Option<String>) -> Result<(), String> {
+    let root = root
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .map(std::path::PathBuf::from);
+    if let Some(root) = &root
+        && !root.is_absolute()
+    {
+        return Err(format!(
+            "Worktree root must be an absolute path: {}",
+            root.display()
+        ));
+    }
+
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_worktree_root(&project.path, root.as_deref())
+        .map_err(|e| format!("Failed to set project worktree root: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_branch_collision_strategy() -> Result<BranchCollisionStrategy, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_branch_collision_strategy(&project.path)
+        .map_err(|e| format!("Failed to get project branch collision strategy: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_branch_collision_strategy(
+    strategy: BranchCollisionStrategy,
+) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_branch_collision_strategy(&project.path, strategy)
+        .map_err(|e| format!("Failed to set project branch collision strategy: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_database_backups_enabled() -> Result<bool, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_database_backups_enabled(&project.path)
+        .map_err(|e| format!("Failed to get project database backup setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_database_backups_enabled(enabled: bool) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_database_backups_enabled(&project.path, enabled)
+        .map_err(|e| format!("Failed to set project database backup setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_keep_recordings() -> Result<bool, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_keep_recordings(&project.path)
+        .map_err(|e| format!("Failed to get project keep-recordings setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_keep_recordings(keep: bool) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_keep_recordings(&project.path, keep)
+        .map_err(|e| format!("Failed to set project keep-recordings setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_spec_file_mirror() -> Result<bool, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_spec_file_mirror(&project.path)
+        .map_err(|e| format!("Failed to get project spec-file mirror setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_spec_file_mirror(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    {
+        let core = project.schaltwerk_core.write().await;
+        let db = core.database();
+        db.set_project_spec_file_mirror(&project.path, enabled)
+            .map_err(|e| format!("Failed to set project spec-file mirror setting: {e}"))?;
+    }
+
+    crate::commands::apply_spec_mirror_setting(app).await
+}
+
+#[tauri::command]
+pub async fn get_project_update_from_parent_on_start() -> Result<bool, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_update_from_parent_on_start(&project.path)
+        .map_err(|e| format!("Failed to get project update-on-start setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn set_project_update_from_parent_on_start(enabled: bool) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    db.set_project_update_from_parent_on_start(&project.path, enabled)
+        .map_err(|e| format!("Failed to set project update-on-start setting: {e}"))
+}
+
+#[tauri::command]
+pub async fn get_project_prompt_templates() -> Result<Vec<PromptTemplate>, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.read().await;
+    let db = core.database();
+
+    db.get_project_prompt_templates(&project.path)
+        .map_err(|e| format!("Failed to get project prompt templates: {e}"))
+}
+
+/// Adds the template, or replaces the existing one with the same name.
+#[tauri::command]
+pub async fn save_project_prompt_template(mut template: PromptTemplate) -> Result<(), String> {
+    template.name = template.name.trim().to_string();
+    if template.name.is_empty() {
+        return Err("Prompt template name cannot be empty".to_string());
+    }
+    validate_prompt_template(&template.content).map_err(|e| e.to_string())?;
+
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    let mut templates = db
+        .get_project_prompt_templates(&project.path)
+        .map_err(|e| format!("Failed to get project prompt templates: {e}"))?;
+    match templates.iter_mut().find(|t| t.name == template.name) {
+        Some(existing) => *existing = template,
+        None => templates.push(template),
+    }
+
+    db.set_project_prompt_templates(&project.path, &templates)
+        .map_err(|e| format!("Failed to save project prompt template: {e}"))
+}
+
+#[tauri::command]
+pub async fn delete_project_prompt_template(name: String) -> Result<(), String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let core = project.schaltwerk_core.write().await;
+    let db = core.database();
+
+    let mut templates = db
+        .get_project_prompt_templates(&project.path)
+        .map_err(|e| format!("Failed to get project prompt templates: {e}"))?;
+    let before = templates.len();
+    templates.retain(|t| t.name != name);
+    if templates.len() == before {
+        return Err(format!("Prompt template '{name}' not found"));
+    }
+
+    db.set_project_prompt_templates(&project.path, &templates)
+        .map_err(|e| format!("Failed to delete project prompt template: {e}"))
+}
+
 #[tauri::command]
 pub async fn get_terminal_settings(app: AppHandle) -> Result<TerminalSettings, String> {
     let settings_manager = get_settings_manager(&app).await?;
@@ -495,6 +848,27 @@ pub async fn set_dev_error_toasts_enabled(app: AppHandle, enabled: bool) -> Resu
     manager.set_dev_error_toasts_enabled(enabled)
 }
 
+#[tauri::command]
+pub async fn get_webhook_request_log_enabled(app: AppHandle) -> Result<bool, String> {
+    let settings_manager = get_settings_manager(&app).await?;
+    let manager = settings_manager.lock().await;
+    Ok(manager.get_webhook_request_log_enabled())
+}
+
+#[tauri::command]
+pub async fn set_webhook_request_log_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let settings_manager = get_settings_manager(&app).await?;
+    let mut manager = settings_manager.lock().await;
+    manager.set_webhook_request_log_enabled(enabled)?;
+    crate::mcp_api::request_log::set_enabled(enabled);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_webhook_request_log(limit: Option<usize>) -> Result<WebhookRequestLog, String> {
+    Ok(crate::mcp_api::request_log::snapshot(limit))
+}
+
 #[tauri::command]
 pub async fn get_last_project_parent_directory(app: AppHandle) -> Result<Option<String>, String> {
     let settings_manager = get_settings_manager(&app).await?;
@@ -977,11 +1351,20 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_set_project_merge_commit_trailers_rejects_invalid_format() {
+        let result = set_project_merge_commit_trailers(vec!["not a trailer".to_string()]).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid commit trailer"));
+    }
+
     #[tokio::test]
     async fn test_set_project_merge_preferences_uninitialized_manager() {
         let preferences = ProjectMergePreferences {
             auto_cancel_after_merge: true,
             auto_cancel_after_pr: false,
+            require_passing_tests: false,
+            test_command: None,
         };
         let result = set_project_merge_preferences(preferences).await;
         assert!(result.is_err());
@@ -1022,6 +1405,14 @@ mod tests {
     async fn test_set_project_sessions_settings_uninitialized_manager() {
         let settings = schaltwerk::schaltwerk_core::db_project_config::ProjectSessionsSettings {
             filter_mode: "running".to_string(),
+            agent_restart_policy: None,
+            setup_script: None,
+            wait_for_setup: None,
+            setup_script_timeout_secs: None,
+            max_parallel_setup_scripts: None,
+            watcher_ignore_patterns: None,
+            worktree_copy_patterns: None,
+            clone_dirs: None,
         };
         let result = set_project_sessions_settings(settings).await;
         assert!(result.is_err());
@@ -1,9 +1,17 @@
-use schaltwerk::services::ServiceHandles;
+use crate::PROJECT_MANAGER;
+use crate::events::{RunScriptCompletedPayload, SchaltEvent, emit_event};
+use schaltwerk::domains::terminal::TerminalManager;
+use schaltwerk::schaltwerk_core::db_project_config::ProjectConfigMethods;
+use schaltwerk::services::run_scripts::{
+    RunOutputScanner, RunScriptLaunch, RunningService, RunningServicesRegistry, launch_run_script,
+};
+use schaltwerk::services::{PlainTextOptions, ServiceHandles, TerminalSnapshot};
 use schaltwerk::services::terminals::{
     CreateRunTerminalRequest, CreateTerminalRequest, CreateTerminalWithSizeRequest,
 };
 use serde::Serialize;
-use tauri::State;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub async fn create_terminal(
@@ -46,6 +54,121 @@ pub async fn create_run_terminal(
         .await
 }
 
+/// Runs the project run script in the run terminal of `session_name`, or of the
+/// orchestrator when omitted. A second run is rejected while one is active unless
+/// `restart` is set, which replaces it.
+#[tauri::command]
+pub async fn run_project_script(
+    app: AppHandle,
+    services: State<'_, ServiceHandles>,
+    session_name: Option<String>,
+    restart: Option<bool>,
+) -> Result<RunningService, String> {
+    let project = PROJECT_MANAGER
+        .get()
+        .ok_or_else(|| "Project manager not initialized".to_string())?
+        .current_project()
+        .await
+        .map_err(|e| format!("Failed to get current project: {e}"))?;
+
+    let launch = {
+        let core = project.schaltwerk_core.read().await;
+        let db = core.database();
+        let script = db
+            .get_project_run_script(&project.path)
+            .map_err(|e| format!("Failed to get project run script: {e}"))?
+            .filter(|script| !script.command.trim().is_empty())
+            .ok_or_else(|| "No run script configured".to_string())?;
+        let project_env = db
+            .get_project_environment_variables(&project.path)
+            .map_err(|e| format!("Failed to get project environment variables: {e}"))?;
+        let base_dir = match session_name.as_deref() {
+            Some(name) => {
+                core.session_manager()
+                    .get_session(name)
+                    .map_err(|e| format!("Session '{name}' not found: {e}"))?
+                    .worktree_path
+            }
+            None => project.path.clone(),
+        };
+        RunScriptLaunch::new(session_name.as_deref(), &base_dir, &script, project_env)
+    };
+
+    let registry = RunningServicesRegistry::global();
+    let service = launch_run_script(
+        services.terminals.as_ref(),
+        registry,
+        launch,
+        restart.unwrap_or(false),
+    )
+    .await?;
+
+    tokio::spawn(watch_run_script(
+        app,
+        Arc::clone(&project.terminal_manager),
+        service.clone(),
+    ));
+    Ok(service)
+}
+
+/// Follows the run terminal output, recording announced ports until the script
+/// reports its exit code or the terminal goes away.
+async fn watch_run_script(app: AppHandle, manager: Arc<TerminalManager>, service: RunningService) {
+    let registry = RunningServicesRegistry::global();
+    let terminal_id = service.terminal_id.clone();
+    let mut scanner = RunOutputScanner::default();
+    let mut exit_code = None;
+
+    let mut next = manager.get_terminal_buffer(terminal_id.clone(), None).await;
+    loop {
+        let snapshot = match next {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                log::debug!("Run script watcher for {terminal_id} ended: {e}");
+                break;
+            }
+        };
+        let signals = scanner.feed(&snapshot.data);
+        for port in signals.ports {
+            if registry.record_port(&terminal_id, service.run_id, port) {
+                log::info!("Run script in {terminal_id} is serving on port {port}");
+            }
+        }
+        if signals.exit_code.is_some() {
+            exit_code = signals.exit_code;
+            break;
+        }
+        if registry.get(&terminal_id).map(|s| s.run_id) != Some(service.run_id) {
+            break;
+        }
+        next = next_run_output(&manager, &terminal_id, snapshot.seq).await;
+    }
+
+    if registry.finish(&terminal_id, service.run_id).is_none() {
+        return;
+    }
+    let payload = RunScriptCompletedPayload {
+        terminal_id,
+        session_name: service.session_name,
+        run_id: service.run_id,
+        exit_code,
+    };
+    if let Err(e) = emit_event(&app, SchaltEvent::RunScriptCompleted, &payload) {
+        log::warn!("Failed to emit RunScriptCompleted event: {e}");
+    }
+}
+
+async fn next_run_output(
+    manager: &TerminalManager,
+    terminal_id: &str,
+    seq: u64,
+) -> Result<TerminalSnapshot, String> {
+    while manager.wait_for_output_change(terminal_id, seq).await? <= seq {}
+    manager
+        .get_terminal_buffer(terminal_id.to_string(), Some(seq))
+        .await
+}
+
 #[tauri::command]
 pub async fn create_terminal_with_size(
     services: State<'_, ServiceHandles>,
@@ -133,23 +256,51 @@ pub struct TerminalBufferResponse {
     pub seq: u64,
     pub start_seq: u64,
     pub data: String,
+    /// Set when the terminal is still at the `if_none_match` sequence; `data` is empty.
+    pub not_modified: bool,
 }
 
+/// Returns the terminal output from `from_seq` on. Callers that pass the sequence they
+/// last saw as `if_none_match` get an empty `not_modified` response while nothing new
+/// has been written.
 #[tauri::command]
 pub async fn get_terminal_buffer(
     services: State<'_, ServiceHandles>,
     id: String,
     from_seq: Option<u64>,
+    if_none_match: Option<u64>,
 ) -> Result<TerminalBufferResponse, String> {
-    let snapshot = services.terminals.get_terminal_buffer(id, from_seq).await?;
+    let snapshot = match if_none_match {
+        Some(seq) => {
+            services
+                .terminals
+                .get_terminal_buffer_if_changed(id, from_seq, seq)
+                .await?
+        }
+        None => services.terminals.get_terminal_buffer(id, from_seq).await?,
+    };
     let data = String::from_utf8_lossy(&snapshot.data).to_string();
     Ok(TerminalBufferResponse {
         seq: snapshot.seq,
         start_seq: snapshot.start_seq,
         data,
+        not_modified: if_none_match == Some(snapshot.seq),
     })
 }
 
+/// Returns the rendered terminal contents as plain text, without escape sequences.
+#[tauri::command]
+pub async fn get_terminal_buffer_text(
+    services: State<'_, ServiceHandles>,
+    id: String,
+    options: Option<PlainTextOptions>,
+) -> Result<String, String> {
+    services
+        .terminals
+        .get_terminal_buffer_text(id, options.unwrap_or_default())
+        .await
+}
+
 #[tauri::command]
 pub async fn get_terminal_activity_status(
     services: State<'_, ServiceHandles>,
@@ -206,8 +357,11 @@ pub async fn resume_session_terminals(
 mod tests {
     use super::*;
     use async_trait::async_trait;
+    use schaltwerk::schaltwerk_core::db_project_config::RunScript;
     use schaltwerk::services::TerminalSnapshot;
     use schaltwerk::services::terminals::{TerminalsBackend, TerminalsServiceImpl};
+    use std::collections::HashMap;
+    use std::path::Path;
     use std::sync::{Arc, Mutex};
 
     struct MockTerminalsBackend {
@@ -221,6 +375,7 @@ mod tests {
         exists_calls: Arc<Mutex<Vec<String>>>,
         exists_bulk_calls: Arc<Mutex<Vec<Vec<String>>>>,
         buffer_calls: Arc<Mutex<Vec<(String, Option<u64>)>>>,
+        buffer_text_calls: Arc<Mutex<Vec<(String, PlainTextOptions)>>>,
         activity_status_calls: Arc<Mutex<Vec<String>>>,
         activity_all_calls: Arc<Mutex<usize>>,
         register_calls: Arc<Mutex<Vec<(String, Option<String>, Vec<String>)>>>,
@@ -242,6 +397,7 @@ mod tests {
                 exists_calls: Arc::new(Mutex::new(Vec::new())),
                 exists_bulk_calls: Arc::new(Mutex::new(Vec::new())),
                 buffer_calls: Arc::new(Mutex::new(Vec::new())),
+                buffer_text_calls: Arc::new(Mutex::new(Vec::new())),
                 activity_status_calls: Arc::new(Mutex::new(Vec::new())),
                 activity_all_calls: Arc::new(Mutex::new(0)),
                 register_calls: Arc::new(Mutex::new(Vec::new())),
@@ -378,6 +534,19 @@ mod tests {
             }
         }
 
+        async fn get_terminal_buffer_text(
+            &self,
+            id: String,
+            options: PlainTextOptions,
+        ) -> Result<String, String> {
+            self.buffer_text_calls.lock().unwrap().push((id, options));
+            if self.should_error {
+                Err("buffer text failed".to_string())
+            } else {
+                Ok("test output".to_string())
+            }
+        }
+
         async fn get_terminal_activity_status(&self, id: String) -> Result<(bool, u64), String> {
             self.activity_status_calls.lock().unwrap().push(id);
             if self.should_error {
@@ -769,6 +938,32 @@ mod tests {
         assert_eq!(calls[0].1, Some(10));
     }
 
+    #[tokio::test]
+    async fn get_terminal_buffer_if_changed_skips_copy_when_sequence_matches() {
+        let backend = MockTerminalsBackend::new();
+        let backend_calls = Arc::clone(&backend.buffer_calls);
+        let service = TerminalsServiceImpl::new(backend);
+
+        let unchanged = service
+            .get_terminal_buffer_if_changed("term-etag".to_string(), None, 42)
+            .await
+            .unwrap();
+        assert_eq!(unchanged.seq, 42);
+        assert_eq!(
+            backend_calls.lock().unwrap().as_slice(),
+            &[("term-etag".to_string(), Some(42))]
+        );
+
+        let changed = service
+            .get_terminal_buffer_if_changed("term-etag".to_string(), None, 40)
+            .await
+            .unwrap();
+        assert_eq!(changed.data, b"test output".to_vec());
+        let calls = backend_calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[2], ("term-etag".to_string(), None));
+    }
+
     #[tokio::test]
     async fn get_terminal_buffer_with_no_from_seq() {
         let backend = MockTerminalsBackend::new();
@@ -784,6 +979,26 @@ mod tests {
         assert_eq!(calls[0].1, None);
     }
 
+    #[tokio::test]
+    async fn get_terminal_buffer_text_forwards_options() {
+        let backend = MockTerminalsBackend::new();
+        let backend_calls = Arc::clone(&backend.buffer_text_calls);
+        let service = TerminalsServiceImpl::new(backend);
+        let options = PlainTextOptions {
+            trim_trailing_whitespace: true,
+            max_lines: Some(50),
+        };
+
+        let text = service
+            .get_terminal_buffer_text("term-text".to_string(), options.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(text, "test output");
+        let calls = backend_calls.lock().unwrap();
+        assert_eq!(calls.as_slice(), &[("term-text".to_string(), options)]);
+    }
+
     #[tokio::test]
     async fn get_terminal_activity_status_returns_tuple() {
         let backend = MockTerminalsBackend::new();
@@ -1062,4 +1277,89 @@ mod tests {
         let calls = backend_calls.lock().unwrap();
         assert_eq!(calls[0].1, Some(u64::MAX));
     }
+
+    fn alpha_launch() -> RunScriptLaunch {
+        let script = RunScript {
+            command: "npm run dev".to_string(),
+            working_directory: None,
+            environment_variables: HashMap::from([("PORT".to_string(), "4000".to_string())]),
+            preview_localhost_on_click: false,
+        };
+        let project_env = HashMap::from([("API_URL".to_string(), "http://api".to_string())]);
+        RunScriptLaunch::new(
+            Some("alpha"),
+            Path::new("/worktrees/alpha"),
+            &script,
+            project_env,
+        )
+    }
+
+    #[tokio::test]
+    async fn run_project_script_spawns_in_fresh_run_terminal() {
+        let backend = MockTerminalsBackend::new();
+        let create_calls = Arc::clone(&backend.create_run_calls);
+        let close_calls = Arc::clone(&backend.close_calls);
+        let write_calls = Arc::clone(&backend.write_calls);
+        let service = TerminalsServiceImpl::new(backend);
+        let registry = RunningServicesRegistry::default();
+
+        let running = launch_run_script(&service, &registry, alpha_launch(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(running.terminal_id, "run-terminal-alpha");
+        assert_eq!(*close_calls.lock().unwrap(), vec!["run-terminal-alpha"]);
+        let creates = create_calls.lock().unwrap();
+        assert_eq!(creates.len(), 1);
+        assert_eq!(creates[0].cwd, "/worktrees/alpha");
+        assert_eq!(
+            creates[0].env,
+            Some(vec![
+                ("API_URL".to_string(), "http://api".to_string()),
+                ("PORT".to_string(), "4000".to_string()),
+            ])
+        );
+        let writes = write_calls.lock().unwrap();
+        let command = String::from_utf8(writes[0].1.clone()).unwrap();
+        assert!(command.contains("; npm run dev; "));
+        assert!(command.contains("__SCHALTWERK_RUN_EXIT__="));
+        assert_eq!(registry.get("run-terminal-alpha"), Some(running));
+    }
+
+    #[tokio::test]
+    async fn run_project_script_rejects_second_run_unless_restarting() {
+        let backend = MockTerminalsBackend::new();
+        let create_calls = Arc::clone(&backend.create_run_calls);
+        let service = TerminalsServiceImpl::new(backend);
+        let registry = RunningServicesRegistry::default();
+
+        let first = launch_run_script(&service, &registry, alpha_launch(), false)
+            .await
+            .unwrap();
+        let rejected = launch_run_script(&service, &registry, alpha_launch(), false).await;
+        assert!(rejected.unwrap_err().contains("already running"));
+        assert_eq!(create_calls.lock().unwrap().len(), 1);
+
+        let restarted = launch_run_script(&service, &registry, alpha_launch(), true)
+            .await
+            .unwrap();
+        assert_ne!(restarted.run_id, first.run_id);
+        assert_eq!(create_calls.lock().unwrap().len(), 2);
+        assert!(
+            registry
+                .finish("run-terminal-alpha", first.run_id)
+                .is_none()
+        );
+        assert_eq!(registry.list(), vec![restarted]);
+    }
+
+    #[tokio::test]
+    async fn run_project_script_releases_terminal_when_spawn_fails() {
+        let registry = RunningServicesRegistry::default();
+
+        let result = launch_run_script(&error_service(), &registry, alpha_launch(), false).await;
+
+        assert!(result.is_err());
+        assert!(registry.list().is_empty());
+    }
 }
@@ -1,4 +1,7 @@
 use crate::get_project_manager;
+use log::LevelFilter;
+use schaltwerk::domains::terminal::process_registry::{OrphanedProcess, reaped_orphans};
+use schaltwerk::infrastructure::logging::{self, LogEntry, export_recent_logs, recent_logs};
 use std::path::Path;
 use std::process::ExitStatus;
 
@@ -56,6 +59,12 @@ pub fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Agent processes a previous instance left running that were killed at startup.
+#[tauri::command]
+pub fn get_orphaned_processes() -> Vec<OrphanedProcess> {
+    reaped_orphans()
+}
+
 #[cfg(test)]
 mod current_directory_tests {
     use super::*;
@@ -107,6 +116,47 @@ pub fn schaltwerk_core_log_frontend_message(level: String, message: String) -> R
     Ok(())
 }
 
+/// Recent backend log records at or above `level_filter` (default `trace`), optionally
+/// limited to targets starting with `module_prefix`, oldest first.
+#[tauri::command]
+pub fn get_recent_logs(
+    level_filter: Option<String>,
+    limit: Option<usize>,
+    module_prefix: Option<String>,
+) -> Result<Vec<LogEntry>, String> {
+    let level = match level_filter.as_deref() {
+        Some(level) => parse_log_level(level)?,
+        None => LevelFilter::Trace,
+    };
+    Ok(recent_logs(level, limit, module_prefix.as_deref()))
+}
+
+/// Sets one log level for every module until restart; `None` restores the defaults.
+#[tauri::command]
+pub fn set_log_level(level: Option<String>) -> Result<(), String> {
+    let level = level.as_deref().map(parse_log_level).transpose()?;
+    logging::set_log_level(level);
+    log::info!("Log level set to {level:?}");
+    Ok(())
+}
+
+/// Writes the buffered log records to `path` and returns how many were written.
+#[tauri::command]
+pub async fn export_logs_to_file(path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        export_recent_logs(Path::new(&path))
+            .map_err(|e| format!("Failed to export logs to {path}: {e}"))
+    })
+    .await
+    .map_err(|e| format!("Log export task failed: {e}"))?
+}
+
+fn parse_log_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .parse::<LevelFilter>()
+        .map_err(|_| format!("Invalid log level '{level}'"))
+}
+
 const ALLOWED_ENV_VARS: &[&str] = &["SCHALTWERK_TERMINAL_TRANSPORT"];
 
 #[tauri::command]
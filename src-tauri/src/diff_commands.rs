@@ -12,20 +12,93 @@ use schaltwerk::domains::sessions::entity::ChangedFile;
 use schaltwerk::domains::workspace::diff_engine::{
     DiffResponse, FileInfo, SplitDiffResponse, add_collapsible_sections, calculate_diff_stats,
     add_collapsible_sections_split, calculate_split_diff_stats, compute_split_diff,
-    compute_unified_diff, get_file_language,
+    compute_split_diff_with_intraline, compute_unified_diff, get_file_language,
 };
 use schaltwerk::domains::workspace::file_utils;
+use schaltwerk::infrastructure::events::{SchaltEvent, emit_event};
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use tauri::AppHandle;
+
+/// Diff base last used per session, so a change of the compare ref is announced once.
+static SESSION_DIFF_BASES: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone)]
+struct DiffBaseBranchChangedPayload {
+    session_name: String,
+    new_base_branch: String,
+}
+
+fn emit_diff_base_branch_changed(app: &AppHandle, session_name: &str, new_base_branch: &str) {
+    if let Err(e) = emit_event(
+        app,
+        SchaltEvent::DiffBaseBranchChanged,
+        &DiffBaseBranchChangedPayload {
+            session_name: session_name.to_string(),
+            new_base_branch: new_base_branch.to_string(),
+        },
+    ) {
+        log::warn!("Failed to emit DiffBaseBranchChanged event: {e}");
+    }
+}
+
+/// Emits `DiffBaseBranchChanged` when `diff_base` differs from what the session was
+/// last diffed against, starting from its base branch.
+fn announce_diff_base(app: &AppHandle, session_name: &str, base_branch: &str, diff_base: &str) {
+    let changed = {
+        let mut bases = SESSION_DIFF_BASES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        bases
+            .insert(session_name.to_string(), diff_base.to_string())
+            .map_or(diff_base != base_branch, |previous| previous != diff_base)
+    };
+    if changed {
+        emit_diff_base_branch_changed(app, session_name, diff_base);
+    }
+}
+
+/// Trims `compare_ref` and checks that it resolves in `repo`. A blank ref counts as
+/// absent.
+fn validate_compare_ref(
+    repo: &Repository,
+    compare_ref: Option<String>,
+) -> Result<Option<String>, SchaltError> {
+    let Some(reference) = compare_ref
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+    else {
+        return Ok(None);
+    };
+    repo.revparse_single(&reference).map_err(|e| {
+        SchaltError::invalid_input("compare_ref", format!("Unknown ref '{reference}': {e}"))
+    })?;
+    Ok(Some(reference))
+}
 
 #[tauri::command]
 pub async fn get_changed_files_from_main(
+    app: AppHandle,
     session_name: Option<String>,
     compare_mode: Option<git::DiffCompareMode>,
+    compare_ref: Option<String>,
 ) -> Result<Vec<ChangedFile>, SchaltError> {
     let session_ref = session_name.as_deref();
     let repo_path = resolve_repo_path_structured(session_ref).await?;
-    let base_branch = resolve_base_branch_structured(session_ref).await?;
+    let compare_ref = {
+        let repo =
+            Repository::open(&repo_path).map_err(|e| SchaltError::git("open_repository", e))?;
+        validate_compare_ref(&repo, compare_ref)?
+    };
+    let session_base = resolve_base_branch_structured(session_ref).await?;
+    if let Some(name) = session_ref {
+        let diff_base = compare_ref.as_deref().unwrap_or(&session_base);
+        announce_diff_base(&app, name, &session_base, diff_base);
+    }
+    let base_branch = compare_ref.unwrap_or(session_base);
     let mode = compare_mode.unwrap_or_default();
 
     let session_branch = if mode == git::DiffCompareMode::UnpushedOnly {
@@ -48,6 +121,26 @@ pub async fn get_changed_files_from_main(
     Ok(result)
 }
 
+/// Changed files of `session_b` since it diverged from `session_a`, the equivalent of
+/// `git diff <branch_a>...<branch_b>`.
+#[tauri::command]
+pub async fn compare_sessions(
+    session_a: String,
+    session_b: String,
+) -> Result<Vec<ChangedFile>, SchaltError> {
+    let repo_path = resolve_repo_path_structured(None).await?;
+    let branch_a = resolve_session_branch(Some(&session_a)).await?;
+    let branch_b = resolve_session_branch(Some(&session_b)).await?;
+
+    let result = git::get_changed_files_between_refs(Path::new(&repo_path), &branch_a, &branch_b)
+        .map_err(|e| SchaltError::git("compare_sessions", e))?;
+    log::info!(
+        "compare_sessions: '{session_a}' ({branch_a}) -> '{session_b}' ({branch_b}), files_count={}",
+        result.len()
+    );
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn has_remote_tracking_branch(session_name: String) -> Result<bool, SchaltError> {
     let repo_path = resolve_repo_path_structured(Some(&session_name)).await?;
@@ -100,7 +193,12 @@ fn collect_working_directory_changes(repo: &Repository) -> anyhow::Result<Vec<Ch
     let mut find_opts = DiffFindOptions::new();
     diff.find_similar(Some(&mut find_opts))?;
 
-    build_changed_files_from_diff(&diff)
+    let staged = git::operations::staged_paths(repo)?;
+    let mut files = build_changed_files_from_diff(&diff)?;
+    for file in &mut files {
+        file.staged = staged.contains(&file.path);
+    }
+    Ok(files)
 }
 
 #[tauri::command]
@@ -121,6 +219,55 @@ pub async fn get_orchestrator_working_changes() -> Result<Vec<ChangedFile>, Stri
         .map_err(|e| format!("Failed to compute changed files: {e}"))
 }
 
+async fn run_in_orchestrator_repo<T: Send + 'static>(
+    operation: &'static str,
+    f: impl FnOnce(&Path) -> anyhow::Result<T> + Send + 'static,
+) -> Result<T, SchaltError> {
+    let repo_path = resolve_repo_path_structured(None).await?;
+    tokio::task::spawn_blocking(move || f(Path::new(&repo_path)))
+        .await
+        .map_err(|e| SchaltError::git(operation, e))?
+        .map_err(|e| SchaltError::git(operation, e))
+}
+
+#[tauri::command]
+pub async fn stage_orchestrator_file(path: String) -> Result<(), SchaltError> {
+    log::info!("Staging orchestrator file: {path}");
+    run_in_orchestrator_repo("stage_orchestrator_file", move |repo| {
+        git::operations::stage_file(repo, &path)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn unstage_orchestrator_file(path: String) -> Result<(), SchaltError> {
+    log::info!("Unstaging orchestrator file: {path}");
+    run_in_orchestrator_repo("unstage_orchestrator_file", move |repo| {
+        git::operations::unstage_file(repo, &path)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn stage_all_orchestrator_changes() -> Result<(), SchaltError> {
+    run_in_orchestrator_repo(
+        "stage_all_orchestrator_changes",
+        git::operations::stage_all_changes,
+    )
+    .await
+}
+
+/// Commits the staged orchestrator changes and returns the new commit id.
+#[tauri::command]
+pub async fn commit_orchestrator_changes(message: String) -> Result<String, SchaltError> {
+    let oid = run_in_orchestrator_repo("commit_orchestrator_changes", move |repo| {
+        git::operations::commit_staged_changes(repo, &message)
+    })
+    .await?;
+    log::info!("Committed staged orchestrator changes as {oid}");
+    Ok(oid.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,6 +567,7 @@ mod tests {
                     was_auto_generated: false,
                     version_group_id: None,
                     version_number: None,
+                    version_group_label: None,
                     epic_id: None,
                     agent_type: None,
                     skip_permissions: None,
@@ -436,7 +584,7 @@ mod tests {
             fs::write(worktree_path.join("README.md"), session_edit).unwrap();
 
             let (old_content, new_content) =
-                get_file_diff_from_main(Some(session_name.clone()), "README.md".to_string())
+                get_file_diff_from_main(Some(session_name.clone()), "README.md".to_string(), None)
                     .await
                     .unwrap();
 
@@ -595,6 +743,7 @@ mod tests {
 pub async fn get_file_diff_from_main(
     session_name: Option<String>,
     file_path: String,
+    compare_ref: Option<String>,
 ) -> Result<(String, String), SchaltError> {
     let session_ref = session_name.as_deref();
     let repo_path = resolve_repo_path_structured(session_ref).await?;
@@ -611,10 +760,11 @@ pub async fn get_file_diff_from_main(
         }
     }
 
+    let repo = Repository::open(&repo_path).map_err(|e| SchaltError::git("open_repository", e))?;
+    let compare_ref = validate_compare_ref(&repo, compare_ref)?;
+
     // For orchestrator (no session), get diff against HEAD (working changes) using git2
-    if session_ref.is_none() {
-        let repo =
-            Repository::open(&repo_path).map_err(|e| SchaltError::git("open_repository", e))?;
+    if session_ref.is_none() && compare_ref.is_none() {
         let base_text = read_blob_from_commit_path(&repo, None, &file_path)
             .map_err(|e| SchaltError::git("read_blob_from_commit_path", e))?;
         let worktree_text = read_workdir_text(&worktree_path).map_err(|e| {
@@ -623,9 +773,11 @@ pub async fn get_file_diff_from_main(
         return Ok((base_text, worktree_text));
     }
 
-    // For sessions, compare merge-base(HEAD, parent_branch) to working directory using git2
-    let repo = Repository::open(&repo_path).map_err(|e| SchaltError::git("open_repository", e))?;
-    let parent_branch = resolve_base_branch_structured(session_ref).await?;
+    // Otherwise compare merge-base(HEAD, compare ref or parent_branch) to working directory
+    let parent_branch = match compare_ref {
+        Some(reference) => reference,
+        None => resolve_base_branch_structured(session_ref).await?,
+    };
     let base_text = read_blob_from_merge_base(&repo, &parent_branch, &file_path)
         .map_err(|e| SchaltError::git("read_blob_from_merge_base", e))?;
     let worktree_text = read_workdir_text(&worktree_path)
@@ -1195,6 +1347,8 @@ pub async fn compute_commit_unified_diff(
             is_large_file,
             is_binary: Some(is_binary_flag),
             unsupported_reason: Some(reason),
+            hunk_headers: Vec::new(),
+            content_hash: None,
         });
     }
 
@@ -1245,6 +1399,8 @@ pub async fn compute_commit_unified_diff(
         is_large_file,
         is_binary: Some(false),
         unsupported_reason: None,
+        hunk_headers: Vec::new(),
+        content_hash: None,
     })
 }
 
@@ -1252,6 +1408,7 @@ pub async fn compute_commit_unified_diff(
 pub async fn compute_unified_diff_backend(
     session_name: Option<String>,
     file_path: String,
+    compare_ref: Option<String>,
 ) -> Result<DiffResponse, SchaltError> {
     use std::time::Instant;
     let start_total = Instant::now();
@@ -1269,12 +1426,14 @@ pub async fn compute_unified_diff_backend(
             is_large_file: false,
             is_binary: Some(true),
             unsupported_reason: reason,
+            hunk_headers: Vec::new(),
+            content_hash: None,
         });
     }
 
     // Profile file content loading
     let start_load = Instant::now();
-    let (old_content, new_content) = match get_file_diff_from_main(session_name, file_path.clone()).await {
+    let (old_content, new_content) = match get_file_diff_from_main(session_name, file_path.clone(), compare_ref).await {
         Ok(contents) => contents,
         Err(SchaltError::InvalidInput { field, message }) if field == "file_path" => {
             return Ok(DiffResponse {
@@ -1287,6 +1446,8 @@ pub async fn compute_unified_diff_backend(
                 is_large_file: false,
                 is_binary: None,
                 unsupported_reason: Some(message),
+                hunk_headers: Vec::new(),
+                content_hash: None,
             });
         }
         Err(e) => return Err(e),
@@ -1306,6 +1467,8 @@ pub async fn compute_unified_diff_backend(
             is_large_file: new_content_bytes.len() > 5 * 1024 * 1024,
             is_binary: Some(true),
             unsupported_reason: Some(reason),
+            hunk_headers: Vec::new(),
+            content_hash: None,
         });
     }
 
@@ -1330,6 +1493,15 @@ pub async fn compute_unified_diff_backend(
     };
 
     let is_large_file = new_content.len() > 5 * 1024 * 1024;
+
+    // Hunk identities for partial discards, taken from the same contents as the diff
+    let hunk_headers = git::diff_hunk_headers(old_content.as_bytes(), new_content.as_bytes())
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to compute hunk headers for {file_path}: {e}");
+            Vec::new()
+        });
+    let content_hash = git::worktree_content_hash(new_content.as_bytes()).ok();
+
     let total_duration = start_total.elapsed();
 
     // Log performance metrics
@@ -1354,13 +1526,19 @@ pub async fn compute_unified_diff_backend(
         is_large_file,
         is_binary: Some(false),
         unsupported_reason: None,
+        hunk_headers,
+        content_hash,
     })
 }
 
+/// Split diff of `file_path`. With `with_intraline`, paired removed/added lines also
+/// carry the changed byte ranges within each line.
 #[tauri::command]
 pub async fn compute_split_diff_backend(
     session_name: Option<String>,
     file_path: String,
+    with_intraline: Option<bool>,
+    compare_ref: Option<String>,
 ) -> Result<SplitDiffResponse, SchaltError> {
     use std::time::Instant;
     let start_total = Instant::now();
@@ -1383,7 +1561,7 @@ pub async fn compute_split_diff_backend(
 
     // Profile file content loading
     let start_load = Instant::now();
-    let (old_content, new_content) = match get_file_diff_from_main(session_name, file_path.clone()).await {
+    let (old_content, new_content) = match get_file_diff_from_main(session_name, file_path.clone(), compare_ref).await {
         Ok(contents) => contents,
         Err(SchaltError::InvalidInput { field, message }) if field == "file_path" => {
             return Ok(SplitDiffResponse {
@@ -1420,7 +1598,11 @@ pub async fn compute_split_diff_backend(
 
     // Profile diff computation
     let start_diff = Instant::now();
-    let split_result = compute_split_diff(&old_content, &new_content);
+    let split_result = compute_split_diff_with_intraline(
+        &old_content,
+        &new_content,
+        with_intraline.unwrap_or(false),
+    );
     let diff_duration = start_diff.elapsed();
 
     // Profile collapsible sections
@@ -1474,7 +1656,6 @@ pub async fn set_session_diff_base_branch(
     new_base_branch: String,
 ) -> Result<(), String> {
     use schaltwerk::domains::sessions::db_sessions::SessionMethods;
-    use schaltwerk::infrastructure::events::{emit_event, SchaltEvent};
 
     let project_manager = get_project_manager().await;
     let project = project_manager
@@ -1517,22 +1698,11 @@ pub async fn set_session_diff_base_branch(
         "Updated diff base branch for session '{session_name}' to '{new_base_branch}'"
     );
 
-    #[derive(serde::Serialize, Clone)]
-    struct DiffBaseBranchChangedPayload {
-        session_name: String,
-        new_base_branch: String,
-    }
-
-    if let Err(e) = emit_event(
-        &app,
-        SchaltEvent::DiffBaseBranchChanged,
-        &DiffBaseBranchChangedPayload {
-            session_name: session_name.clone(),
-            new_base_branch: new_base_branch.clone(),
-        },
-    ) {
-        log::warn!("Failed to emit DiffBaseBranchChanged event: {e}");
-    }
+    SESSION_DIFF_BASES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&session_name);
+    emit_diff_base_branch_changed(&app, &session_name, &new_base_branch);
 
     Ok(())
 }
@@ -1,6 +1,7 @@
 use super::format_binary_invocation;
 use super::launch_spec::AgentLaunchSpec;
 use super::manifest::AgentDefinition;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -18,6 +19,24 @@ pub struct AgentLaunchContext<'a> {
     pub manifest: &'a AgentDefinition,
 }
 
+/// A non-interactive agent invocation that answers a single prompt on stdout and exits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentOneshotCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl AgentOneshotCommand {
+    pub fn new(program: &str, args: &[&str], prompt: &str) -> Self {
+        let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        args.push(prompt.to_string());
+        Self {
+            program: program.to_string(),
+            args,
+        }
+    }
+}
+
 pub trait AgentAdapter: Send + Sync {
     fn find_session(&self, path: &Path) -> Option<AgentSessionInfo> {
         let _ = path;
@@ -25,6 +44,13 @@ pub trait AgentAdapter: Send + Sync {
     }
 
     fn build_launch_spec(&self, ctx: AgentLaunchContext) -> AgentLaunchSpec;
+
+    /// Builds a headless invocation of `binary` for `prompt`, or `None` when the agent
+    /// has no non-interactive mode.
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        let _ = (binary, prompt);
+        None
+    }
 }
 
 pub struct DefaultAdapter;
@@ -58,6 +84,274 @@ impl AgentAdapter for DefaultAdapter {
     }
 }
 
+/// Longest line kept while waiting for a line break; longer output is parsed in pieces.
+const MAX_OUTPUT_LINE: usize = 4096;
+
+/// What an agent is doing, as far as its terminal output tells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentRunState {
+    Thinking,
+    ToolRunning,
+    AwaitingInput,
+    Done,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentOutputStatus {
+    pub state: Option<AgentRunState>,
+    pub last_tool: Option<String>,
+    /// Token count as printed by the agent, so `1.2k` becomes 1200.
+    pub tokens: Option<u64>,
+    /// Approval prompt the agent waits on while `state` is `AwaitingInput`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+/// Extracts status from the output of one agent, a line at a time.
+pub trait AgentOutputParser: Send {
+    /// `line` is complete, trimmed and free of escape sequences.
+    fn parse_line(&mut self, line: &str, status: &mut AgentOutputStatus);
+
+    /// Receives the line still being written, where prompts usually wait for input.
+    /// It may arrive several times while it grows, so updates must be idempotent.
+    fn parse_partial_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+        let _ = (line, status);
+    }
+}
+
+/// Parser for agents whose output format is unknown.
+pub struct NoopOutputParser;
+
+impl AgentOutputParser for NoopOutputParser {
+    fn parse_line(&mut self, _line: &str, _status: &mut AgentOutputStatus) {}
+}
+
+/// Marks `status` as waiting on the approval prompt in `line` if it matches one of
+/// `patterns`.
+pub fn detect_approval_prompt(
+    line: &str,
+    patterns: &[&str],
+    status: &mut AgentOutputStatus,
+) -> bool {
+    if !patterns.iter().any(|pattern| line.contains(pattern)) {
+        return false;
+    }
+    let excerpt = line.trim_matches(|c: char| c.is_whitespace() || matches!(c, '│' | '┃' | '|'));
+    status.state = Some(AgentRunState::AwaitingInput);
+    status.prompt = Some(excerpt.to_string());
+    true
+}
+
+pub fn output_parser_for(agent_type: &str) -> Box<dyn AgentOutputParser> {
+    match agent_type {
+        "claude" => Box::new(super::claude::ClaudeOutputParser),
+        "codex" => Box::new(super::codex::CodexOutputParser),
+        _ => Box::new(NoopOutputParser),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EscapeState {
+    Text,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape,
+}
+
+/// Turns raw terminal output into lines for an [`AgentOutputParser`]. Output may be
+/// split anywhere, including inside escape sequences and UTF-8 characters.
+pub struct AgentOutputScanner {
+    parser: Box<dyn AgentOutputParser>,
+    status: AgentOutputStatus,
+    line: String,
+    pending: Vec<u8>,
+    escape: EscapeState,
+}
+
+impl AgentOutputScanner {
+    pub fn new(parser: Box<dyn AgentOutputParser>) -> Self {
+        Self {
+            parser,
+            status: AgentOutputStatus::default(),
+            line: String::new(),
+            pending: Vec::new(),
+            escape: EscapeState::Text,
+        }
+    }
+
+    pub fn for_agent(agent_type: &str) -> Self {
+        Self::new(output_parser_for(agent_type))
+    }
+
+    pub fn status(&self) -> &AgentOutputStatus {
+        &self.status
+    }
+
+    /// Consumes a chunk of output and returns the new status if it changed.
+    pub fn feed(&mut self, bytes: &[u8]) -> Option<AgentOutputStatus> {
+        let before = self.status.clone();
+
+        let mut data = std::mem::take(&mut self.pending);
+        data.extend_from_slice(bytes);
+        let mut start = 0;
+        while start < data.len() {
+            match std::str::from_utf8(&data[start..]) {
+                Ok(text) => {
+                    self.scan(text);
+                    break;
+                }
+                Err(e) => {
+                    let valid_end = start + e.valid_up_to();
+                    self.scan(&String::from_utf8_lossy(&data[start..valid_end]));
+                    match e.error_len() {
+                        Some(len) => start = valid_end + len,
+                        None => {
+                            self.pending = data[valid_end..].to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Prompts are often drawn without a trailing newline
+        let partial = self.line.trim();
+        if !partial.is_empty() {
+            self.parser.parse_partial_line(partial, &mut self.status);
+        }
+        if self.status.state != Some(AgentRunState::AwaitingInput) {
+            self.status.prompt = None;
+        }
+
+        (self.status != before).then(|| self.status.clone())
+    }
+
+    fn scan(&mut self, text: &str) {
+        for ch in text.chars() {
+            match self.escape {
+                EscapeState::Text => match ch {
+                    '\x1b' => self.escape = EscapeState::Escape,
+                    '\n' | '\r' => self.end_line(),
+                    '\t' => self.push(' '),
+                    c if c.is_control() => {}
+                    c => self.push(c),
+                },
+                EscapeState::Escape => {
+                    self.escape = match ch {
+                        '[' => EscapeState::Csi,
+                        ']' => EscapeState::Osc,
+                        _ => EscapeState::Text,
+                    };
+                }
+                EscapeState::Csi => {
+                    if ('@'..='~').contains(&ch) {
+                        self.escape = EscapeState::Text;
+                        match ch {
+                            // Cursor movement to another row starts a new line
+                            'A' | 'B' | 'E' | 'F' | 'H' | 'd' | 'f' => self.end_line(),
+                            'C' => self.push(' '),
+                            _ => {}
+                        }
+                    }
+                }
+                EscapeState::Osc => match ch {
+                    '\x07' => self.escape = EscapeState::Text,
+                    '\x1b' => self.escape = EscapeState::OscEscape,
+                    _ => {}
+                },
+                EscapeState::OscEscape => {
+                    self.escape = if ch == '\\' {
+                        EscapeState::Text
+                    } else {
+                        EscapeState::Osc
+                    };
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, ch: char) {
+        self.line.push(ch);
+        if self.line.len() >= MAX_OUTPUT_LINE {
+            self.end_line();
+        }
+    }
+
+    fn end_line(&mut self) {
+        let line = self.line.trim();
+        if !line.is_empty() {
+            self.parser.parse_line(line, &mut self.status);
+        }
+        self.line.clear();
+    }
+}
+
+/// Finds a token count such as `↑ 1.2k tokens`, `tokens used: 12,345` or `total=830`.
+pub fn parse_token_count(line: &str) -> Option<u64> {
+    if let Some(idx) = line.find("total=") {
+        let rest = &line[idx + "total=".len()..];
+        if let Some(count) = rest.split_whitespace().next().and_then(parse_count) {
+            return Some(count);
+        }
+    }
+
+    let words: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || matches!(c, '·' | '•' | '(' | ')' | ':'))
+        .filter(|word| !word.is_empty())
+        .collect();
+    for (i, word) in words.iter().enumerate() {
+        if !word.to_ascii_lowercase().starts_with("token") {
+            continue;
+        }
+        if i > 0
+            && let Some(count) = parse_count(words[i - 1])
+        {
+            return Some(count);
+        }
+        if words.get(i + 1) == Some(&"used")
+            && let Some(count) = words.get(i + 2).and_then(|w| parse_count(w))
+        {
+            return Some(count);
+        }
+    }
+    None
+}
+
+fn parse_count(word: &str) -> Option<u64> {
+    let word = word.trim_start_matches(['↑', '↓']).replace(',', "");
+    let (number, factor) = match word.char_indices().last()? {
+        (idx, 'k' | 'K') => (&word[..idx], 1_000.0),
+        (idx, 'm' | 'M') => (&word[..idx], 1_000_000.0),
+        _ => (word.as_str(), 1.0),
+    };
+    let value: f64 = number.parse().ok()?;
+    (value.is_finite() && value >= 0.0).then(|| (value * factor).round() as u64)
+}
+
+/// Feeds `output` in pseudo-random chunk sizes derived from `seed`.
+#[cfg(test)]
+pub(crate) fn feed_in_random_chunks(
+    scanner: &mut AgentOutputScanner,
+    output: &[u8],
+    seed: u64,
+) -> Vec<AgentOutputStatus> {
+    let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+    let mut updates = Vec::new();
+    let mut rest = output;
+    while !rest.is_empty() {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let size = ((state >> 33) % 17 + 1) as usize;
+        let (chunk, tail) = rest.split_at(size.min(rest.len()));
+        updates.extend(scanner.feed(chunk));
+        rest = tail;
+    }
+    updates
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +429,55 @@ mod tests {
         let spec = adapter.build_launch_spec(ctx);
         assert!(spec.shell_command.contains("/custom/binary"));
     }
+
+    struct EchoParser(Vec<String>);
+
+    impl AgentOutputParser for EchoParser {
+        fn parse_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+            if self.0.last().map(String::as_str) != Some(line) {
+                self.0.push(line.to_string());
+            }
+            status.last_tool = Some(line.to_string());
+        }
+
+        fn parse_partial_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+            self.parse_line(line, status);
+        }
+    }
+
+    #[test]
+    fn test_scanner_strips_escapes_split_across_chunks() {
+        let mut scanner = AgentOutputScanner::new(Box::new(EchoParser(Vec::new())));
+        scanner.feed(b"\x1b[3");
+        scanner.feed(b"2mgr\xc3");
+        scanner.feed(b"\xbcn\x1b[0m\r\n\x1b]0;title\x07next");
+        assert_eq!(
+            scanner.status().last_tool.as_deref(),
+            Some("next"),
+            "partial line should be parsed"
+        );
+        scanner.feed(b"\x1b[2;1Hrow");
+        assert_eq!(scanner.status().last_tool.as_deref(), Some("row"));
+    }
+
+    #[test]
+    fn test_parse_token_count_formats() {
+        assert_eq!(
+            parse_token_count("✻ Thinking… (12s · ↑ 1.2k tokens · esc to interrupt)"),
+            Some(1200)
+        );
+        assert_eq!(parse_token_count("tokens used: 12,345"), Some(12345));
+        assert_eq!(
+            parse_token_count("Token usage: total=830 input=700 output=130"),
+            Some(830)
+        );
+        assert_eq!(parse_token_count("no counts here"), None);
+    }
+
+    #[test]
+    fn test_unknown_agents_use_noop_parser() {
+        let mut scanner = AgentOutputScanner::for_agent("gemini");
+        assert_eq!(scanner.feed(b"Do you want to proceed?\n"), None);
+        assert_eq!(scanner.status(), &AgentOutputStatus::default());
+    }
 }
@@ -1,3 +1,6 @@
+use super::adapter::{
+    AgentOutputParser, AgentOutputStatus, AgentRunState, detect_approval_prompt, parse_token_count,
+};
 use super::format_binary_invocation;
 use crate::shared::resolve_windows_executable;
 use std::collections::HashSet;
@@ -7,6 +10,14 @@ use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 const CLAUDE_SESSION_SCAN_LIMIT: usize = 64;
+/// Lines of the permission dialogs Claude Code shows before running a tool.
+pub const CLAUDE_APPROVAL_PATTERNS: &[&str] = &[
+    "Do you want to proceed?",
+    "Do you want to make this edit",
+    "Do you want to create",
+    "Do you want to allow",
+    "Waiting for approval",
+];
 
 fn get_home_dir() -> Option<String> {
     super::get_home_dir()
@@ -329,13 +340,66 @@ pub fn build_claude_command_with_config(
         // Claude will start a new session by default with no additional flags
     }
 
-    log::info!("Claude command builder: Final command: '{cmd}'");
+    log::info!(
+        "Claude command builder: Final command: '{}'",
+        super::redact_command_line(&cmd)
+    );
     cmd
 }
 
+/// Reads Claude Code's interactive output: the spinner line, `⏺ Tool(args)` calls,
+/// their `⎿` results and permission prompts.
+pub struct ClaudeOutputParser;
+
+impl AgentOutputParser for ClaudeOutputParser {
+    fn parse_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+        if let Some(tokens) = parse_token_count(line) {
+            status.tokens = Some(tokens);
+        }
+
+        if let Some(tool) = claude_tool_call(line) {
+            status.last_tool = Some(tool.to_string());
+            status.state = Some(AgentRunState::ToolRunning);
+        } else if let Some(result) = line.strip_prefix('⎿') {
+            if !result.trim_start().starts_with("Running") {
+                status.state = Some(AgentRunState::Thinking);
+            }
+        } else if line.contains("esc to interrupt") {
+            // The spinner keeps turning while a tool runs
+            if status.state != Some(AgentRunState::ToolRunning) {
+                status.state = Some(AgentRunState::Thinking);
+            }
+        } else {
+            self.parse_partial_line(line, status);
+        }
+    }
+
+    fn parse_partial_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+        if detect_approval_prompt(line, CLAUDE_APPROVAL_PATTERNS, status) {
+            return;
+        }
+        if line.contains("? for shortcuts") {
+            status.state = Some(AgentRunState::Done);
+        }
+    }
+}
+
+/// Name of the tool in a `⏺ Bash(cargo test)` line.
+fn claude_tool_call(line: &str) -> Option<&str> {
+    let rest = line
+        .strip_prefix('⏺')
+        .or_else(|| line.strip_prefix('●'))?
+        .trim_start();
+    let (name, _) = rest.split_once('(')?;
+    let is_tool_name = name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    is_tool_name.then_some(name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domains::agents::adapter::{AgentOutputScanner, feed_in_random_chunks};
     use filetime::{FileTime, set_file_mtime};
     use std::fs::{self, File};
     use std::io::Write as _;
@@ -712,4 +776,94 @@ mod tests {
             "cd /path/to/worktree && claude --dangerously-skip-permissions --continue"
         );
     }
+
+    const CLAUDE_SESSION_FIXTURE: &[u8] = "\x1b[2K\x1b[1G> fix the failing test\r\n\r\n\
+\x1b[38;5;174m✻\x1b[39m Thinking… \x1b[2m(3s · ↑ 212 tokens · esc to interrupt)\x1b[22m\r\
+\x1b[2K\x1b[38;5;174m✢\x1b[39m Thinking… \x1b[2m(9s · ↓ 1.4k tokens · esc to interrupt)\x1b[22m\r\n\
+\x1b[1m⏺\x1b[22m Bash(cargo test -p core)\r\n  ⎿  Running…\r\n\
+\x1b[38;5;174m✳\x1b[39m Running… (14s · ↓ 1.6k tokens · esc to interrupt)\r\n\
+\x1b[2K\x1b[1A\x1b[2K  ⎿  test result: ok. 12 passed\r\n\
+⏺ Update(src/lib.rs)\r\n\
+╭──────────────────────────────────────────╮\r\n\
+│ Do you want to make this edit to lib.rs? │\r\n\
+│ ❯ 1. Yes                                 │\r\n\
+╰──────────────────────────────────────────╯\r\n\
+⏺ Fixed the assertion in lib.rs.\r\n\r\n\
+\x1b[2m  ? for shortcuts\x1b[22m"
+        .as_bytes();
+
+    #[test]
+    fn test_output_parser_tracks_claude_session_in_any_chunking() {
+        for seed in 0..32 {
+            let mut scanner = AgentOutputScanner::for_agent("claude");
+            let updates = feed_in_random_chunks(&mut scanner, CLAUDE_SESSION_FIXTURE, seed);
+
+            let mut states: Vec<AgentRunState> = Vec::new();
+            for state in updates.iter().filter_map(|u| u.state) {
+                if states.last() != Some(&state) {
+                    states.push(state);
+                }
+            }
+            assert_eq!(
+                states,
+                vec![
+                    AgentRunState::Thinking,
+                    AgentRunState::ToolRunning,
+                    AgentRunState::Thinking,
+                    AgentRunState::ToolRunning,
+                    AgentRunState::AwaitingInput,
+                    AgentRunState::Done,
+                ],
+                "seed {seed}"
+            );
+            assert!(
+                updates
+                    .iter()
+                    .any(|u| u.last_tool.as_deref() == Some("Bash"))
+            );
+            assert_eq!(
+                scanner.status(),
+                &AgentOutputStatus {
+                    state: Some(AgentRunState::Done),
+                    last_tool: Some("Update".to_string()),
+                    tokens: Some(1600),
+                    prompt: None,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_claude_tool_call_requires_tool_syntax() {
+        assert_eq!(claude_tool_call("⏺ Read(src/main.rs)"), Some("Read"));
+        assert_eq!(claude_tool_call("⏺ I'll run the tests (quickly)"), None);
+        assert_eq!(claude_tool_call("Bash(ls)"), None);
+    }
+
+    #[test]
+    fn test_detects_claude_approval_prompt_until_answered() {
+        let prompt = "⏺ Bash(rm -rf target)\r\n\
+╭──────────────────────────────────────╮\r\n\
+│ Bash command                         │\r\n\
+│   rm -rf target                      │\r\n\
+│ Do you want to proceed?              │\r\n\
+│ ❯ 1. Yes                             │\r\n\
+│   2. No, and tell Claude what to do  │\r\n\
+╰──────────────────────────────────────╯\r\n";
+
+        for seed in 0..16 {
+            let mut scanner = AgentOutputScanner::for_agent("claude");
+            feed_in_random_chunks(&mut scanner, prompt.as_bytes(), seed);
+            assert_eq!(scanner.status().state, Some(AgentRunState::AwaitingInput));
+            assert_eq!(
+                scanner.status().prompt.as_deref(),
+                Some("Do you want to proceed?"),
+                "seed {seed}"
+            );
+
+            scanner.feed("  ⎿  (No content)\r\n".as_bytes());
+            assert_eq!(scanner.status().state, Some(AgentRunState::Thinking));
+            assert_eq!(scanner.status().prompt, None);
+        }
+    }
 }
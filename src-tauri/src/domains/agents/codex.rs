@@ -1,3 +1,6 @@
+use super::adapter::{
+    AgentOutputParser, AgentOutputStatus, AgentRunState, detect_approval_prompt, parse_token_count,
+};
 use super::format_binary_invocation;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -1017,14 +1020,78 @@ pub fn build_codex_command_with_config(
         log::debug!("🆕 Codex will start a new session by default with no additional flags");
     }
 
-    log::debug!("🚀 Codex command builder: Final command: {cmd:?}");
+    log::debug!(
+        "🚀 Codex command builder: Final command: {:?}",
+        super::redact_command_line(&cmd)
+    );
 
     cmd
 }
 
+/// Approval requests Codex shows before running a command or applying a patch.
+pub const CODEX_APPROVAL_PATTERNS: &[&str] = &[
+    "Would you like to run the following command?",
+    "Would you like to make the following edits?",
+    "Allow command?",
+    "Waiting for approval",
+];
+
+/// Reads the Codex TUI output: `• Running`/`• Ran` command cells, patch cells, approval
+/// requests and the token summary printed on exit.
+pub struct CodexOutputParser;
+
+impl AgentOutputParser for CodexOutputParser {
+    fn parse_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+        if let Some(tokens) = parse_token_count(line) {
+            status.tokens = Some(tokens);
+        }
+
+        let cell = line
+            .strip_prefix('•')
+            .or_else(|| line.strip_prefix('⚡'))
+            .map(str::trim_start);
+        match cell {
+            Some(cell) if cell.starts_with("Running ") => {
+                status.last_tool = Some("shell".to_string());
+                status.state = Some(AgentRunState::ToolRunning);
+            }
+            Some(cell) if cell.starts_with("Ran ") => {
+                status.last_tool = Some("shell".to_string());
+                status.state = Some(AgentRunState::Thinking);
+            }
+            Some(cell) if cell.starts_with("Edited ") || cell.starts_with("Applying patch") => {
+                status.last_tool = Some("apply_patch".to_string());
+                status.state = Some(AgentRunState::Thinking);
+            }
+            Some(cell) if cell.starts_with("Updated Plan") => {
+                status.last_tool = Some("update_plan".to_string());
+            }
+            _ if line.contains("esc to interrupt") => {
+                if status.state != Some(AgentRunState::ToolRunning) {
+                    status.state = Some(AgentRunState::Thinking);
+                }
+            }
+            _ if line.starts_with("Token usage:") => {
+                status.state = Some(AgentRunState::Done);
+            }
+            _ => self.parse_partial_line(line, status),
+        }
+    }
+
+    fn parse_partial_line(&mut self, line: &str, status: &mut AgentOutputStatus) {
+        if detect_approval_prompt(line, CODEX_APPROVAL_PATTERNS, status) {
+            return;
+        }
+        if line.contains("⏎ send") {
+            status.state = Some(AgentRunState::Done);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domains::agents::adapter::{AgentOutputScanner, feed_in_random_chunks};
     use filetime::{FileTime, set_file_mtime};
     use std::env;
     use std::fs;
@@ -1599,4 +1666,83 @@ mod tests {
         let global_newest = find_newest_session(sessions_root.as_path()).unwrap();
         assert_eq!(global_newest, Some(new_session));
     }
+
+    const CODEX_SESSION_FIXTURE: &[u8] = "\x1b[1m›\x1b[0m explain the build failure\r\n\r\n\
+\x1b[2K• \x1b[1mWorking\x1b[0m (2s • esc to interrupt)\r\
+\x1b[2K• \x1b[1mWorking\x1b[0m (6s • esc to interrupt)\r\n\
+\x1b[32m•\x1b[0m Running cargo build\r\n  └ Compiling core v0.1.0\r\n\
+\x1b[32m•\x1b[0m Ran cargo build\r\n  └ error[E0308]: mismatched types\r\n\
+• Edited src/lib.rs (+1 -1)\r\n\
+\x1b[5;1HWould you like to run the following command?\r\n\
+  $ cargo test\r\n\
+\x1b[36m›\x1b[0m 1. Yes, proceed\r\n\
+• Ran cargo test\r\n\
+─ Worked for 1m 02s ─\r\n\
+• The build now passes.\r\n\
+Token usage: total=18,240 input=16,002 output=2,238\r\n"
+        .as_bytes();
+
+    #[test]
+    fn test_output_parser_tracks_codex_session_in_any_chunking() {
+        for seed in 0..32 {
+            let mut scanner = AgentOutputScanner::for_agent("codex");
+            let updates = feed_in_random_chunks(&mut scanner, CODEX_SESSION_FIXTURE, seed);
+
+            let mut states: Vec<AgentRunState> = Vec::new();
+            for state in updates.iter().filter_map(|u| u.state) {
+                if states.last() != Some(&state) {
+                    states.push(state);
+                }
+            }
+            assert_eq!(
+                states,
+                vec![
+                    AgentRunState::Thinking,
+                    AgentRunState::ToolRunning,
+                    AgentRunState::Thinking,
+                    AgentRunState::AwaitingInput,
+                    AgentRunState::Thinking,
+                    AgentRunState::Done,
+                ],
+                "seed {seed}"
+            );
+            assert!(
+                updates
+                    .iter()
+                    .any(|u| u.last_tool.as_deref() == Some("apply_patch"))
+            );
+            assert_eq!(
+                scanner.status(),
+                &AgentOutputStatus {
+                    state: Some(AgentRunState::Done),
+                    last_tool: Some("shell".to_string()),
+                    tokens: Some(18240),
+                    prompt: None,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_detects_codex_approval_prompt_until_answered() {
+        let prompt = "\x1b[1mWould you like to run the following command?\x1b[0m\r\n\r\n\
+  $ cargo publish --dry-run\r\n\r\n\
+\x1b[36m›\x1b[0m 1. Yes, proceed\r\n\
+  2. No, and tell Codex what to do differently\r\n";
+
+        for seed in 0..16 {
+            let mut scanner = AgentOutputScanner::for_agent("codex");
+            feed_in_random_chunks(&mut scanner, prompt.as_bytes(), seed);
+            assert_eq!(scanner.status().state, Some(AgentRunState::AwaitingInput));
+            assert_eq!(
+                scanner.status().prompt.as_deref(),
+                Some("Would you like to run the following command?"),
+                "seed {seed}"
+            );
+
+            scanner.feed("\x1b[32m•\x1b[0m Running cargo publish --dry-run\r\n".as_bytes());
+            assert_eq!(scanner.status().state, Some(AgentRunState::ToolRunning));
+            assert_eq!(scanner.status().prompt, None);
+        }
+    }
 }
@@ -0,0 +1,398 @@
+use super::adapter::AgentOneshotCommand;
+use super::unified::AgentRegistry;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use git2::{DiffFormat, DiffOptions, Repository};
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Hard limit for a headless agent run before falling back to the template message.
+pub const COMMIT_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Upper bound for the diff text included in the prompt.
+pub const COMMIT_MESSAGE_CONTEXT_LIMIT: usize = 8 * 1024;
+
+const FALLBACK_LISTED_FILES: usize = 3;
+
+/// Uncommitted changes of a worktree (staged, unstaged and untracked) relative to HEAD.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UncommittedDiff {
+    pub files: Vec<String>,
+    pub patch: String,
+    pub truncated: bool,
+}
+
+impl UncommittedDiff {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Runs a one-shot agent command; abstracted so tests don't spawn real agents.
+#[async_trait]
+pub trait OneshotExecutor: Send + Sync {
+    async fn run(
+        &self,
+        command: &AgentOneshotCommand,
+        cwd: &Path,
+        env_vars: &[(String, String)],
+        timeout: Duration,
+    ) -> Result<String>;
+}
+
+pub struct ProcessOneshotExecutor;
+
+#[async_trait]
+impl OneshotExecutor for ProcessOneshotExecutor {
+    async fn run(
+        &self,
+        command: &AgentOneshotCommand,
+        cwd: &Path,
+        env_vars: &[(String, String)],
+        timeout: Duration,
+    ) -> Result<String> {
+        let mut process = Command::new(&command.program);
+        process
+            .args(&command.args)
+            .current_dir(cwd)
+            .env("NO_COLOR", "1")
+            .env("CLICOLOR", "0")
+            .env("TERM", "dumb")
+            .env("CI", "1")
+            .env("NONINTERACTIVE", "1")
+            .envs(env_vars.iter().cloned())
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+
+        let output = tokio::time::timeout(timeout, process.output())
+            .await
+            .map_err(|_| anyhow!("{} timed out after {timeout:?}", command.program))?
+            .map_err(|e| anyhow!("Failed to execute {}: {e}", command.program))?;
+
+        if !output.status.success() {
+            let code = output.status.code().unwrap_or(-1);
+            return Err(anyhow!(
+                "{} exited with code {code}: {}",
+                command.program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+pub struct CommitMessageRequest<'a> {
+    pub worktree_path: &'a Path,
+    pub agent_type: &'a str,
+    pub binary_override: Option<&'a str>,
+    pub env_vars: &'a [(String, String)],
+}
+
+/// Collects the uncommitted diff of `worktree_path`, keeping at most `limit` bytes of patch text.
+pub fn collect_uncommitted_diff(worktree_path: &Path, limit: usize) -> Result<UncommittedDiff> {
+    let repo = Repository::open(worktree_path)?;
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(_) => None,
+    };
+
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))?;
+
+    let mut summary = UncommittedDiff::default();
+    for delta in diff.deltas() {
+        let path = delta.new_file().path().or_else(|| delta.old_file().path());
+        if let Some(path) = path {
+            let path = path.to_string_lossy().to_string();
+            if path != ".schaltwerk" && !path.starts_with(".schaltwerk/") {
+                summary.files.push(path);
+            }
+        }
+    }
+
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        let prefix = match line.origin() {
+            origin @ ('+' | '-' | ' ') => Some(origin),
+            _ => None,
+        };
+        let content = String::from_utf8_lossy(line.content());
+        let rendered = match prefix {
+            Some(origin) => format!("{origin}{content}"),
+            None => content.into_owned(),
+        };
+
+        if summary.patch.len() + rendered.len() > limit {
+            summary.truncated = true;
+            return false;
+        }
+        summary.patch.push_str(&rendered);
+        true
+    })
+    .or_else(|err| {
+        // Returning false from the callback aborts printing, which git2 reports as an error.
+        if summary.truncated { Ok(()) } else { Err(err) }
+    })?;
+
+    Ok(summary)
+}
+
+pub fn build_commit_message_prompt(diff: &UncommittedDiff) -> String {
+    let truncated_note = if diff.truncated {
+        "\n(The diff was truncated; infer the rest from the file list.)"
+    } else {
+        ""
+    };
+    format!(
+        r#"IMPORTANT: Do not use any tools. Answer this message directly without searching or reading files.
+
+Write a git commit message for the changes below.
+
+Rules:
+- First line: imperative summary, 72 characters or less
+- Optionally a blank line followed by a short body explaining why
+- Return ONLY the commit message, no quotes or code fences
+
+Changed files:
+{files}
+
+Diff:{truncated_note}
+{patch}"#,
+        files = diff.files.join("\n"),
+        patch = diff.patch,
+    )
+}
+
+/// Deterministic message used when no agent can produce one, e.g. `update 2 files: a.rs, b.rs`.
+pub fn fallback_commit_message(files: &[String]) -> String {
+    let noun = if files.len() == 1 { "file" } else { "files" };
+    let mut listed = files
+        .iter()
+        .take(FALLBACK_LISTED_FILES)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if files.len() > FALLBACK_LISTED_FILES {
+        listed.push_str(&format!(
+            " and {} more",
+            files.len() - FALLBACK_LISTED_FILES
+        ));
+    }
+    format!("update {} {noun}: {listed}", files.len())
+}
+
+fn clean_agent_output(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    let unfenced = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.split_once('\n').map(|(_, body)| body))
+        .and_then(|body| body.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed);
+    let message = unfenced.trim().trim_matches('"').trim();
+    (!message.is_empty()).then(|| message.to_string())
+}
+
+/// Generates a commit message for the uncommitted changes with the session's agent,
+/// falling back to [`fallback_commit_message`] when the agent is unavailable, fails or
+/// exceeds `timeout`. Nothing is committed.
+pub async fn generate_commit_message<E: OneshotExecutor>(
+    executor: &E,
+    registry: &AgentRegistry,
+    request: CommitMessageRequest<'_>,
+    timeout: Duration,
+) -> Result<String> {
+    let diff = collect_uncommitted_diff(request.worktree_path, COMMIT_MESSAGE_CONTEXT_LIMIT)?;
+    if diff.is_empty() {
+        return Err(anyhow!("There are no uncommitted changes to describe"));
+    }
+
+    let prompt = build_commit_message_prompt(&diff);
+    let Some(command) =
+        registry.build_oneshot_command(request.agent_type, &prompt, request.binary_override)
+    else {
+        log::info!(
+            "Agent '{}' has no headless mode; using template commit message",
+            request.agent_type
+        );
+        return Ok(fallback_commit_message(&diff.files));
+    };
+
+    match executor
+        .run(&command, request.worktree_path, request.env_vars, timeout)
+        .await
+    {
+        Ok(output) => match clean_agent_output(&output) {
+            Some(message) => Ok(message),
+            None => {
+                log::warn!(
+                    "Agent '{}' returned an empty commit message; using template",
+                    request.agent_type
+                );
+                Ok(fallback_commit_message(&diff.files))
+            }
+        },
+        Err(err) => {
+            log::warn!(
+                "Commit message generation with '{}' failed: {err}; using template",
+                request.agent_type
+            );
+            Ok(fallback_commit_message(&diff.files))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    struct MockExecutor {
+        response: Mutex<Option<Result<String>>>,
+        calls: Mutex<Vec<AgentOneshotCommand>>,
+    }
+
+    impl MockExecutor {
+        fn new(response: Result<String>) -> Self {
+            Self {
+                response: Mutex::new(Some(response)),
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl OneshotExecutor for MockExecutor {
+        async fn run(
+            &self,
+            command: &AgentOneshotCommand,
+            _cwd: &Path,
+            _env_vars: &[(String, String)],
+            _timeout: Duration,
+        ) -> Result<String> {
+            self.calls.lock().unwrap().push(command.clone());
+            self.response
+                .lock()
+                .unwrap()
+                .take()
+                .unwrap_or_else(|| Err(anyhow!("unexpected call")))
+        }
+    }
+
+    fn repo_with_changes(files: &[(&str, &str)]) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Tester", "tester@example.com").unwrap();
+        std::fs::write(temp.path().join("README.md"), "hello\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+
+        for (path, content) in files {
+            std::fs::write(temp.path().join(path), content).unwrap();
+        }
+        temp
+    }
+
+    fn request<'a>(path: &'a Path, agent_type: &'a str) -> CommitMessageRequest<'a> {
+        CommitMessageRequest {
+            worktree_path: path,
+            agent_type,
+            binary_override: None,
+            env_vars: &[],
+        }
+    }
+
+    #[tokio::test]
+    async fn uses_agent_output_for_commit_message() {
+        let temp = repo_with_changes(&[("README.md", "hello world\n"), ("notes.txt", "new\n")]);
+        let executor = MockExecutor::new(Ok("```\nDocument greeting\n```\n".to_string()));
+
+        let message = generate_commit_message(
+            &executor,
+            &AgentRegistry::new(),
+            request(temp.path(), "claude"),
+            COMMIT_MESSAGE_TIMEOUT,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(message, "Document greeting");
+        let calls = executor.calls.lock().unwrap();
+        assert_eq!(calls[0].program, "claude");
+        assert_eq!(calls[0].args[0], "-p");
+        assert!(calls[0].args[1].contains("notes.txt"));
+        assert!(calls[0].args[1].contains("+hello world"));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_template_when_agent_fails_or_has_no_headless_mode() {
+        let temp = repo_with_changes(&[("README.md", "changed\n"), ("a.rs", "a\n")]);
+
+        let failing = MockExecutor::new(Err(anyhow!("claude timed out after 30s")));
+        let message = generate_commit_message(
+            &failing,
+            &AgentRegistry::new(),
+            request(temp.path(), "claude"),
+            COMMIT_MESSAGE_TIMEOUT,
+        )
+        .await
+        .unwrap();
+        assert_eq!(message, "update 2 files: README.md, a.rs");
+
+        let unused = MockExecutor::new(Ok("ignored".to_string()));
+        let message = generate_commit_message(
+            &unused,
+            &AgentRegistry::new(),
+            request(temp.path(), "terminal"),
+            COMMIT_MESSAGE_TIMEOUT,
+        )
+        .await
+        .unwrap();
+        assert_eq!(message, "update 2 files: README.md, a.rs");
+        assert!(unused.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn clean_worktree_is_an_error() {
+        let temp = repo_with_changes(&[]);
+        let executor = MockExecutor::new(Ok("unused".to_string()));
+
+        let result = generate_commit_message(
+            &executor,
+            &AgentRegistry::new(),
+            request(temp.path(), "claude"),
+            COMMIT_MESSAGE_TIMEOUT,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn diff_context_is_bounded() {
+        let big = "line of content\n".repeat(2_000);
+        let temp = repo_with_changes(&[("big.txt", big.as_str())]);
+
+        let diff = collect_uncommitted_diff(temp.path(), 1024).unwrap();
+        assert_eq!(diff.files, vec!["big.txt"]);
+        assert!(diff.truncated);
+        assert!(diff.patch.len() <= 1024);
+    }
+
+    #[test]
+    fn fallback_lists_first_files() {
+        let files: Vec<String> = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        assert_eq!(
+            fallback_commit_message(&files),
+            "update 5 files: a, b, c and 2 more"
+        );
+        assert_eq!(fallback_commit_message(&files[..1]), "update 1 file: a");
+    }
+}
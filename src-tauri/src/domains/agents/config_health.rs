@@ -0,0 +1,285 @@
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use log::{debug, warn};
+use semver::Version;
+use serde::Serialize;
+
+use super::resolve_agent_binary;
+use crate::domains::settings::AgentBinaryConfig;
+use crate::utils::binary_utils::{InstallationMethod, check_binary};
+
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+pub const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AgentConfigHealth {
+    pub agent_name: String,
+    pub configured_path: Option<String>,
+    pub resolved_path: String,
+    pub exists: bool,
+    pub version: Option<String>,
+    pub on_allowlist: bool,
+}
+
+/// Mirrors `get_effective_agent_binary_path`: custom path first, then the recommended
+/// detection, then the first detection, then the bare command name resolved through PATH.
+pub fn effective_binary_path(agent_name: &str, config: Option<&AgentBinaryConfig>) -> String {
+    let Some(config) = config else {
+        return resolve_agent_binary(agent_name);
+    };
+
+    if let Some(custom) = config.custom_path.as_deref().map(str::trim)
+        && !custom.is_empty()
+    {
+        return custom.to_string();
+    }
+
+    config
+        .detected_binaries
+        .iter()
+        .find(|b| b.is_recommended)
+        .or_else(|| config.detected_binaries.first())
+        .map(|b| b.path.clone())
+        .unwrap_or_else(|| resolve_agent_binary(agent_name))
+}
+
+/// Runs `<binary> --version` for the agent's effective binary and returns the semver it
+/// prints. Binaries that are missing, reject the flag, hang past `timeout`, or print no
+/// recognisable version yield `None`.
+pub fn detect_agent_binary_version(
+    agent_id: &str,
+    config: Option<&AgentBinaryConfig>,
+    timeout: Duration,
+) -> Option<String> {
+    let binary = effective_binary_path(agent_id, config);
+    let mut child = Command::new(&binary)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| debug!("Cannot run '{binary} --version' for {agent_id}: {e}"))
+        .ok()?;
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            Ok(None) => {
+                debug!("'{binary} --version' for {agent_id} timed out after {timeout:?}");
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Err(e) => {
+                warn!("Failed to wait for '{binary} --version' for {agent_id}: {e}");
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        debug!("'{binary} --version' for {agent_id} exited with {status}");
+        return None;
+    }
+
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    parse_version(&output).map(|v| v.to_string())
+}
+
+/// Finds the first semver-looking token, e.g. `2.0.14` in `2.0.14 (Claude Code)` or
+/// `0.58.0` in `codex-cli 0.58.0`.
+fn parse_version(output: &str) -> Option<Version> {
+    output
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+'))
+        .map(|token| token.trim_start_matches('v').trim_end_matches('.'))
+        .find_map(|token| Version::parse(token).ok())
+}
+
+pub async fn probe_agent_config(
+    agent_name: String,
+    config: Option<AgentBinaryConfig>,
+    allowlist: &[String],
+    probe_timeout: Duration,
+) -> AgentConfigHealth {
+    let configured_path = config
+        .as_ref()
+        .and_then(|c| c.custom_path.clone())
+        .filter(|p| !p.trim().is_empty());
+    let resolved_path = effective_binary_path(&agent_name, config.as_ref());
+    let on_allowlist = allowlist.iter().any(|a| a == &agent_name);
+
+    let probe_path = resolved_path.clone();
+    let probe = tokio::task::spawn_blocking(move || {
+        let path = Path::new(&probe_path);
+        let exists = path.is_file();
+        let version = if exists {
+            check_binary(path, InstallationMethod::Manual).and_then(|b| b.version)
+        } else {
+            None
+        };
+        (exists, version)
+    });
+
+    let (exists, version) = match tokio::time::timeout(probe_timeout, probe).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(err)) => {
+            warn!("Health probe for agent '{agent_name}' failed: {err}");
+            (Path::new(&resolved_path).is_file(), None)
+        }
+        Err(_) => {
+            debug!("Health probe for agent '{agent_name}' timed out after {probe_timeout:?}");
+            (Path::new(&resolved_path).is_file(), None)
+        }
+    };
+
+    AgentConfigHealth {
+        agent_name,
+        configured_path,
+        resolved_path,
+        exists,
+        version,
+        on_allowlist,
+    }
+}
+
+/// Probes every agent concurrently; the result is sorted by agent name.
+pub async fn collect_agent_config_health(
+    agents: Vec<(String, Option<AgentBinaryConfig>)>,
+    allowlist: &[String],
+    probe_timeout: Duration,
+) -> Vec<AgentConfigHealth> {
+    let probes = agents
+        .into_iter()
+        .map(|(name, config)| probe_agent_config(name, config, allowlist, probe_timeout));
+
+    let mut report = join_all(probes).await;
+    report.sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config_with_path(agent: &str, path: &str) -> AgentBinaryConfig {
+        AgentBinaryConfig {
+            agent_name: agent.to_string(),
+            custom_path: Some(path.to_string()),
+            auto_detect: false,
+            detected_binaries: Vec::new(),
+            version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn report_flags_missing_path_and_healthy_binary() {
+        let temp = TempDir::new().unwrap();
+        let healthy = temp.path().join("claude");
+        std::fs::write(&healthy, "#!/bin/sh\necho \"claude 1.0.0\"\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&healthy, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let missing = temp.path().join("does-not-exist").join("codex");
+
+        let allowlist = vec!["claude".to_string(), "codex".to_string()];
+        let report = collect_agent_config_health(
+            vec![
+                (
+                    "codex".to_string(),
+                    Some(config_with_path("codex", &missing.to_string_lossy())),
+                ),
+                (
+                    "claude".to_string(),
+                    Some(config_with_path("claude", &healthy.to_string_lossy())),
+                ),
+            ],
+            &allowlist,
+            DEFAULT_PROBE_TIMEOUT,
+        )
+        .await;
+
+        assert_eq!(report.len(), 2);
+        let claude = &report[0];
+        assert_eq!(claude.agent_name, "claude");
+        assert!(claude.exists);
+        assert!(claude.on_allowlist);
+        assert_eq!(claude.resolved_path, healthy.to_string_lossy());
+        #[cfg(unix)]
+        assert_eq!(claude.version.as_deref(), Some("claude 1.0.0"));
+
+        let codex = &report[1];
+        assert_eq!(codex.agent_name, "codex");
+        assert!(!codex.exists);
+        assert_eq!(codex.version, None);
+        assert_eq!(
+            codex.configured_path.as_deref(),
+            Some(missing.to_string_lossy().as_ref())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn binary_version_is_parsed_from_fake_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let write_script = |name: &str, body: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            config_with_path("claude", &path.to_string_lossy())
+        };
+
+        let versioned = write_script("claude", "echo \"2.0.14 (Claude Code)\"");
+        assert_eq!(
+            detect_agent_binary_version("claude", Some(&versioned), VERSION_PROBE_TIMEOUT),
+            Some("2.0.14".to_string())
+        );
+
+        let rejects_flag = write_script("no-flag", "echo \"unknown option\" >&2\nexit 2");
+        assert_eq!(
+            detect_agent_binary_version("claude", Some(&rejects_flag), VERSION_PROBE_TIMEOUT),
+            None
+        );
+
+        let hangs = write_script("hangs", "sleep 5");
+        assert_eq!(
+            detect_agent_binary_version("claude", Some(&hangs), Duration::from_millis(200)),
+            None
+        );
+    }
+
+    #[test]
+    fn version_parsing_handles_common_cli_formats() {
+        let parse = |s: &str| parse_version(s).map(|v| v.to_string());
+        assert_eq!(parse("codex-cli 0.58.0\n"), Some("0.58.0".to_string()));
+        assert_eq!(parse("v1.4.2-beta.1"), Some("1.4.2-beta.1".to_string()));
+        assert_eq!(parse("gemini version 0.9.0."), Some("0.9.0".to_string()));
+        assert_eq!(parse("no version here"), None);
+    }
+
+    #[tokio::test]
+    async fn agents_outside_allowlist_are_flagged() {
+        let report = probe_agent_config(
+            "unknown-agent".to_string(),
+            None,
+            &["claude".to_string()],
+            DEFAULT_PROBE_TIMEOUT,
+        )
+        .await;
+
+        assert!(!report.on_allowlist);
+        assert_eq!(report.configured_path, None);
+    }
+}
@@ -1,11 +1,124 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use super::command_parser::parse_agent_command;
+use super::redaction::redact_command_line;
+use crate::domains::terminal::sh_quote_string;
+use anyhow::{Result, anyhow};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Flags that take the initial prompt as their value, e.g. `--prompt-interactive "..."`.
+const PROMPT_FLAGS: &[&str] = &["--prompt", "--prompt-interactive", "-i"];
+
+fn quote_arg(arg: &str) -> String {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c));
+    if is_plain {
+        arg.to_string()
+    } else {
+        sh_quote_string(arg)
+    }
+}
+
+/// Values for the placeholders allowed in configured agent CLI args.
+#[derive(Debug, Clone, Copy)]
+pub struct CliArgPlaceholders<'a> {
+    pub worktree: &'a Path,
+    pub session_name: &'a str,
+    pub parent_branch: &'a str,
+    pub repo_root: &'a Path,
+}
+
+impl CliArgPlaceholders<'_> {
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "worktree" => Some(self.worktree.display().to_string()),
+            "session_name" => Some(self.session_name.to_string()),
+            "parent_branch" => Some(self.parent_branch.to_string()),
+            "repo_root" => Some(self.repo_root.display().to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces `{worktree}`, `{session_name}`, `{parent_branch}` and `{repo_root}` in
+/// `cli_args` with shell-quoted values. `{{` and `}}` stand for literal braces; any
+/// other brace is an error.
+pub fn expand_cli_placeholders(cli_args: &str, values: &CliArgPlaceholders) -> Result<String> {
+    let mut expanded = String::with_capacity(cli_args.len());
+    let mut chars = cli_args.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                expanded.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                expanded.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(anyhow!(
+                                "Unclosed placeholder '{{{name}' in CLI args '{cli_args}'"
+                            ));
+                        }
+                    }
+                }
+                let value = values.value(&name).ok_or_else(|| {
+                    anyhow!(
+                        "Unknown placeholder '{{{name}}}' in CLI args '{cli_args}'; use {{worktree}}, {{session_name}}, {{parent_branch}} or {{repo_root}}"
+                    )
+                })?;
+                expanded.push_str(&sh_quote_string(&value));
+            }
+            '}' => {
+                return Err(anyhow!("Unmatched '}}' in CLI args '{cli_args}'"));
+            }
+            c => expanded.push(c),
+        }
+    }
+    Ok(expanded)
+}
+
+/// Environment variables set on a single session. A `None` value unsets a variable the
+/// agent or project settings would otherwise pass to the agent.
+pub type EnvOverrides = BTreeMap<String, Option<String>>;
+
+/// Resolves the environment for an agent launch: agent defaults, then project
+/// variables, then the session's overrides, with later layers winning.
+pub fn layer_launch_env(
+    agent_env: impl IntoIterator<Item = (String, String)>,
+    project_env: impl IntoIterator<Item = (String, String)>,
+    session_env: &EnvOverrides,
+) -> Vec<(String, String)> {
+    let mut env: BTreeMap<String, String> = agent_env.into_iter().collect();
+    env.extend(project_env);
+    for (key, value) in session_env {
+        match value {
+            Some(value) => {
+                env.insert(key.clone(), value.clone());
+            }
+            None => {
+                env.remove(key);
+            }
+        }
+    }
+    env.into_iter().collect()
+}
 
 #[derive(Debug, Clone)]
 pub struct AgentLaunchSpec {
     pub shell_command: String,
     pub initial_command: Option<String>,
     pub env_vars: HashMap<String, String>,
+    /// Session-level overrides layered over the agent and project environment.
+    pub env_overrides: EnvOverrides,
     pub working_dir: PathBuf,
 }
 
@@ -15,6 +128,7 @@ impl AgentLaunchSpec {
             shell_command,
             initial_command: None,
             env_vars: HashMap::new(),
+            env_overrides: EnvOverrides::new(),
             working_dir,
         }
     }
@@ -29,9 +143,64 @@ impl AgentLaunchSpec {
         self
     }
 
+    pub fn with_env_overrides(mut self, env_overrides: EnvOverrides) -> Self {
+        self.env_overrides = env_overrides;
+        self
+    }
+
     pub fn format_for_shell(&self) -> String {
         self.shell_command.clone()
     }
+
+    /// The shell command with API keys and tokens masked, for logging.
+    pub fn format_for_log(&self) -> String {
+        redact_command_line(&self.shell_command)
+    }
+
+    /// Appends user-configured CLI args after the agent's own flags and before the
+    /// initial prompt (or the flag that carries it).
+    pub fn with_cli_args(mut self, cli_args: &str, initial_prompt: Option<&str>) -> Result<Self> {
+        let extra = shell_words::split(cli_args)
+            .map_err(|e| anyhow!("Failed to parse CLI args '{cli_args}': {e}"))?;
+        if extra.is_empty() {
+            return Ok(self);
+        }
+        let quoted_extra: Vec<String> = extra.iter().map(|arg| quote_arg(arg)).collect();
+
+        // Piped launches (`echo ... | amp`) feed the prompt through stdin, so the args go last
+        if self.shell_command.contains(" | ") {
+            self.shell_command = format!("{} {}", self.shell_command, quoted_extra.join(" "));
+            return Ok(self);
+        }
+
+        let (_, agent, mut args) =
+            parse_agent_command(&self.shell_command).map_err(|e| anyhow!(e))?;
+        let cd_part = self
+            .shell_command
+            .split_once(" && ")
+            .map(|(cd, _)| cd)
+            .ok_or_else(|| anyhow!("Invalid command format: {}", self.shell_command))?;
+
+        let prompt_index = initial_prompt
+            .and_then(|prompt| args.iter().rposition(|arg| arg == prompt))
+            .map(|idx| {
+                if idx > 0 && PROMPT_FLAGS.contains(&args[idx - 1].as_str()) {
+                    idx - 1
+                } else {
+                    idx
+                }
+            })
+            .unwrap_or(args.len());
+        args.splice(prompt_index..prompt_index, extra);
+
+        let mut command = format!("{cd_part} && {}", quote_arg(&agent));
+        for arg in &args {
+            command.push(' ');
+            command.push_str(&quote_arg(arg));
+        }
+        self.shell_command = command;
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +245,48 @@ mod tests {
         assert_eq!(spec.env_vars, env);
     }
 
+    fn pairs(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_launch_env_layers_agent_project_then_session() {
+        let agent = pairs(&[("MODEL", "agent"), ("LOG", "agent"), ("ONLY_AGENT", "1")]);
+        let project = pairs(&[("MODEL", "project"), ("LOG", "project")]);
+        let session = EnvOverrides::from([
+            ("LOG".to_string(), Some("session".to_string())),
+            ("ONLY_SESSION".to_string(), Some("1".to_string())),
+        ]);
+
+        let env = layer_launch_env(agent, project, &session);
+
+        assert_eq!(
+            env,
+            pairs(&[
+                ("LOG", "session"),
+                ("MODEL", "project"),
+                ("ONLY_AGENT", "1"),
+                ("ONLY_SESSION", "1"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unset_session_override_removes_inherited_variable() {
+        let agent = pairs(&[("API_KEY", "agent"), ("KEEP", "1")]);
+        let project = pairs(&[("API_KEY", "project")]);
+        let session = EnvOverrides::from([
+            ("API_KEY".to_string(), None),
+            ("NEVER_SET".to_string(), None),
+        ]);
+
+        let env = layer_launch_env(agent, project, &session);
+
+        assert_eq!(env, pairs(&[("KEEP", "1")]));
+    }
+
     #[test]
     fn test_format_for_shell() {
         let spec = AgentLaunchSpec::new(
@@ -85,4 +296,119 @@ mod tests {
 
         assert_eq!(spec.format_for_shell(), "cd /test && claude --flag");
     }
+
+    #[test]
+    fn test_format_for_log_masks_api_key_but_keeps_executed_command() {
+        let command = "cd /test && claude --api-key sk-ant-REDACTED --model opus";
+        let spec = AgentLaunchSpec::new(command.to_string(), Path::new("/test").to_path_buf());
+
+        assert_eq!(
+            spec.format_for_log(),
+            "cd /test && claude --api-key *** --model opus"
+        );
+        assert_eq!(spec.format_for_shell(), command);
+    }
+
+    fn spec_with_cli_args(agent: &str, prompt: &str) -> String {
+        crate::domains::agents::unified::AgentRegistry::new()
+            .build_launch_spec(agent, Path::new("/test"), None, Some(prompt), true, None)
+            .unwrap()
+            .with_cli_args(r#"--verbose --foo "bar baz""#, Some(prompt))
+            .unwrap()
+            .shell_command
+    }
+
+    #[test]
+    fn test_cli_args_land_before_the_prompt() {
+        let claude = spec_with_cli_args("claude", "fix it");
+        assert_eq!(
+            claude,
+            "cd /test && claude --dangerously-skip-permissions --verbose --foo 'bar baz' 'fix it'"
+        );
+        let (_, _, args) = parse_agent_command(&claude).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "--dangerously-skip-permissions",
+                "--verbose",
+                "--foo",
+                "bar baz",
+                "fix it"
+            ]
+        );
+
+        let codex = spec_with_cli_args("codex", "fix it");
+        let (_, _, args) = parse_agent_command(&codex).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "--sandbox",
+                "danger-full-access",
+                "--verbose",
+                "--foo",
+                "bar baz",
+                "fix it"
+            ]
+        );
+
+        let gemini = spec_with_cli_args("gemini", "fix it");
+        assert!(gemini.ends_with("--verbose --foo 'bar baz' --prompt-interactive 'fix it'"));
+    }
+
+    fn placeholders(worktree: &Path) -> CliArgPlaceholders<'_> {
+        CliArgPlaceholders {
+            worktree,
+            session_name: "fix-login",
+            parent_branch: "main",
+            repo_root: Path::new("/repo"),
+        }
+    }
+
+    #[test]
+    fn test_cli_placeholders_expand_to_single_quoted_args() {
+        let worktree = Path::new("/work trees/fix login");
+        let expanded = expand_cli_placeholders(
+            "--add-dir {worktree} --name={session_name} -c base={parent_branch} {repo_root} {{x}}",
+            &placeholders(worktree),
+        )
+        .unwrap();
+
+        assert_eq!(
+            shell_words::split(&expanded).unwrap(),
+            vec![
+                "--add-dir",
+                "/work trees/fix login",
+                "--name=fix-login",
+                "-c",
+                "base=main",
+                "/repo",
+                "{x}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_cli_placeholders_are_rejected() {
+        let values = placeholders(Path::new("/wt"));
+        for (args, message) in [
+            ("--dir {worktree", "Unclosed placeholder"),
+            ("--dir {workspace}", "Unknown placeholder '{workspace}'"),
+            ("--dir worktree}", "Unmatched '}'"),
+        ] {
+            let err = expand_cli_placeholders(args, &values).unwrap_err();
+            assert!(err.to_string().contains(message), "{args}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_empty_cli_args_leave_command_untouched() {
+        let spec = AgentLaunchSpec::new(
+            "cd /test && claude \"hi\"".to_string(),
+            Path::new("/test").to_path_buf(),
+        )
+        .with_cli_args("  ", Some("hi"))
+        .unwrap();
+
+        assert_eq!(spec.shell_command, "cd /test && claude \"hi\"");
+    }
 }
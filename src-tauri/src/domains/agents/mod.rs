@@ -3,6 +3,8 @@ pub mod amp;
 pub mod claude;
 pub mod codex;
 pub mod command_parser;
+pub mod commit_message;
+pub mod config_health;
 pub mod copilot;
 pub mod db_kilo;
 pub mod droid;
@@ -13,6 +15,7 @@ pub mod manifest;
 pub mod naming;
 pub mod opencode;
 pub mod qwen;
+pub mod redaction;
 pub mod unified;
 
 use std::path::PathBuf;
@@ -20,9 +23,15 @@ use std::path::PathBuf;
 #[cfg(windows)]
 use crate::shared::resolve_windows_executable;
 
-pub use adapter::{AgentAdapter, AgentLaunchContext, AgentSessionInfo};
+pub use adapter::{
+    AgentAdapter, AgentLaunchContext, AgentOneshotCommand, AgentOutputParser, AgentOutputScanner,
+    AgentOutputStatus, AgentRunState, AgentSessionInfo,
+};
 pub use command_parser::parse_agent_command;
-pub use launch_spec::AgentLaunchSpec;
+pub use launch_spec::{
+    AgentLaunchSpec, CliArgPlaceholders, EnvOverrides, expand_cli_placeholders, layer_launch_env,
+};
+pub use redaction::{redact_args, redact_command_line, redact_env};
 
 pub(crate) fn get_home_dir() -> Option<String> {
     #[cfg(unix)]
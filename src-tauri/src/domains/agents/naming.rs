@@ -327,7 +327,10 @@ Respond with JSON: {{"name": "short-kebab-case-name"}}"#
         args.push(tmp_file.to_string_lossy().to_string());
         args.push(prompt_plain.clone());
 
-        log::info!("codex exec args for namegen: {args:?}");
+        log::info!(
+            "codex exec args for namegen: {:?}",
+            super::redact_args(&args)
+        );
         let output = Command::new("codex")
             .args(&args)
             .current_dir(&run_dir)
@@ -703,6 +706,11 @@ pub async fn generate_display_name(args: NameGenerationArgs<'_>) -> Result<Optio
     .await
 }
 
+/// Asks the agent for a name without persisting it, leaving the caller to apply it.
+pub async fn suggest_display_name(args: NameGenerationArgs<'_>) -> Result<Option<String>> {
+    generate_display_name_core(args, |_, _| Ok(())).await
+}
+
 pub async fn generate_spec_display_name(args: NameGenerationArgs<'_>) -> Result<Option<String>> {
     let target_id = args.target_id;
     generate_display_name_core(args, move |db, name| {
@@ -0,0 +1,175 @@
+//! Masks credentials in agent command lines and environments before they are logged.
+//! Only the logged copy is redacted; the command that runs is never touched.
+
+const REDACTED: &str = "***";
+
+/// Prefixes of well-known API key and access token formats.
+const SECRET_VALUE_PREFIXES: &[&str] = &[
+    "sk-",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "ghu_",
+    "github_pat_",
+    "glpat-",
+    "xoxb-",
+    "xoxp-",
+    "AIza",
+];
+
+/// Shortest value after a known prefix that is treated as a secret, so that words like
+/// `sk-learn` in a prompt stay readable.
+const MIN_SECRET_SUFFIX_LEN: usize = 12;
+
+/// True for flag and variable names that carry a credential, e.g. `--api-key`,
+/// `OPENAI_API_KEY` or `GITHUB_TOKEN`.
+pub fn is_secret_name(name: &str) -> bool {
+    let name = name
+        .trim_start_matches('-')
+        .to_ascii_uppercase()
+        .replace('-', "_");
+    name == "TOKEN"
+        || name == "KEY"
+        || name == "APIKEY"
+        || name.ends_with("_TOKEN")
+        || name.ends_with("_KEY")
+        || name.ends_with("_APIKEY")
+        || name.contains("SECRET")
+        || name.contains("PASSWORD")
+}
+
+fn looks_like_secret(value: &str) -> bool {
+    SECRET_VALUE_PREFIXES.iter().any(|prefix| {
+        value
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.len() >= MIN_SECRET_SUFFIX_LEN)
+    })
+}
+
+fn strip_quotes(word: &str) -> &str {
+    word.trim_matches(|c| c == '\'' || c == '"')
+}
+
+/// Redacts secrets in a single argument. `redact_next` carries over from a preceding
+/// `--api-key` or `Bearer` word whose value follows as its own argument.
+fn redact_word(word: &str, redact_next: &mut bool) -> String {
+    let bare = strip_quotes(word);
+    if bare.is_empty() {
+        return word.to_string();
+    }
+    if std::mem::take(redact_next) {
+        return REDACTED.to_string();
+    }
+    if let Some((name, _)) = word.split_once('=')
+        && is_secret_name(strip_quotes(name))
+    {
+        return format!("{name}={REDACTED}");
+    }
+    if (bare.starts_with('-') && is_secret_name(bare)) || bare.eq_ignore_ascii_case("bearer") {
+        *redact_next = true;
+        return word.to_string();
+    }
+    if looks_like_secret(bare) {
+        return REDACTED.to_string();
+    }
+    word.to_string()
+}
+
+/// Returns `command` with the values of secret flags (`--api-key sk-...`,
+/// `--token=...`), secret assignments (`OPENAI_API_KEY=...`), bearer tokens and
+/// well-known key formats replaced by `***`.
+pub fn redact_command_line(command: &str) -> String {
+    let mut redact_next = false;
+    command
+        .split(' ')
+        .map(|word| redact_word(word, &mut redact_next))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Argument-list counterpart of [`redact_command_line`]. An argument may hold several
+/// words, e.g. a quoted `Authorization: Bearer ...` header.
+pub fn redact_args(args: &[String]) -> Vec<String> {
+    let mut redact_next = false;
+    args.iter()
+        .map(|arg| {
+            arg.split(' ')
+                .map(|word| redact_word(word, &mut redact_next))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// Formats environment variables for a log line, masking the values of secret names and
+/// of values that look like keys.
+pub fn redact_env(env: &[(String, String)]) -> String {
+    env.iter()
+        .map(|(name, value)| {
+            if is_secret_name(name) || looks_like_secret(value) {
+                format!("{name}={REDACTED}")
+            } else {
+                format!("{name}={value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "sk-ant-REDACTED";
+
+    #[test]
+    fn test_secret_flag_values_are_redacted() {
+        let command = format!("cd /repo && claude --api-key {KEY} --model opus");
+        assert_eq!(
+            redact_command_line(&command),
+            "cd /repo && claude --api-key *** --model opus"
+        );
+        assert_eq!(
+            redact_command_line(&format!("codex --github-token='{KEY}' exec")),
+            "codex --github-token=*** exec"
+        );
+    }
+
+    #[test]
+    fn test_assignments_bearer_and_key_formats_are_redacted() {
+        let command = format!(
+            "OPENAI_API_KEY={KEY} amp -H 'Authorization: Bearer abc.def' --note ghp_0123456789abcdef"
+        );
+        assert_eq!(
+            redact_command_line(&command),
+            "OPENAI_API_KEY=*** amp -H 'Authorization: Bearer *** --note ***"
+        );
+    }
+
+    #[test]
+    fn test_ordinary_arguments_are_untouched() {
+        let command = "claude --model opus --add-dir /tmp/x 'fix sk-learn import'";
+        assert_eq!(redact_command_line(command), command);
+    }
+
+    #[test]
+    fn test_args_and_env_are_redacted() {
+        let args = vec![
+            "--api-key".to_string(),
+            KEY.to_string(),
+            "--sandbox".to_string(),
+            "workspace-write".to_string(),
+        ];
+        assert_eq!(
+            redact_args(&args),
+            vec!["--api-key", "***", "--sandbox", "workspace-write"]
+        );
+
+        let env = [
+            ("GITHUB_TOKEN".to_string(), "plain".to_string()),
+            ("EDITOR".to_string(), "vim".to_string()),
+            ("CUSTOM".to_string(), KEY.to_string()),
+        ];
+        assert_eq!(redact_env(&env), "GITHUB_TOKEN=*** EDITOR=vim CUSTOM=***");
+    }
+}
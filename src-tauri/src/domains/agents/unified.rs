@@ -1,4 +1,6 @@
-use super::adapter::{AgentAdapter, AgentLaunchContext, AgentSessionInfo, DefaultAdapter};
+use super::adapter::{
+    AgentAdapter, AgentLaunchContext, AgentOneshotCommand, AgentSessionInfo, DefaultAdapter,
+};
 use super::amp;
 use super::copilot;
 use super::droid;
@@ -37,6 +39,10 @@ impl AgentAdapter for ClaudeAdapter {
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["-p"], prompt))
+    }
 }
 
 pub struct CodexAdapter;
@@ -90,10 +96,18 @@ impl AgentAdapter for CodexAdapter {
         log::info!(
             "[CodexAdapter] Launch command prepared for worktree {}: {}",
             ctx.worktree_path.display(),
-            command
+            super::redact_command_line(&command)
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(
+            binary,
+            &["exec", "--sandbox", "read-only", "--skip-git-repo-check"],
+            prompt,
+        ))
+    }
 }
 
 pub struct GeminiAdapter;
@@ -123,6 +137,10 @@ impl AgentAdapter for GeminiAdapter {
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["--prompt"], prompt))
+    }
 }
 
 pub struct KilocodeAdapter;
@@ -158,6 +176,10 @@ impl AgentAdapter for KilocodeAdapter {
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["run"], prompt))
+    }
 }
 
 pub struct OpenCodeAdapter;
@@ -219,6 +241,10 @@ impl AgentAdapter for DroidAdapter {
 
         spec
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["exec"], prompt))
+    }
 }
 
 impl AgentAdapter for OpenCodeAdapter {
@@ -253,6 +279,10 @@ impl AgentAdapter for OpenCodeAdapter {
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["run"], prompt))
+    }
 }
 
 pub struct QwenAdapter;
@@ -282,6 +312,10 @@ impl AgentAdapter for QwenAdapter {
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["-p"], prompt))
+    }
 }
 
 pub struct AmpAdapter;
@@ -311,6 +345,10 @@ impl AgentAdapter for AmpAdapter {
         );
         AgentLaunchSpec::new(command, ctx.worktree_path.to_path_buf())
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["-x"], prompt))
+    }
 }
 
 pub struct TerminalAdapter;
@@ -382,6 +420,18 @@ impl AgentRegistry {
 
         Some(adapter.build_launch_spec(ctx))
     }
+
+    pub fn build_oneshot_command(
+        &self,
+        agent_type: &str,
+        prompt: &str,
+        binary_override: Option<&str>,
+    ) -> Option<AgentOneshotCommand> {
+        let adapter = self.get(agent_type)?;
+        let manifest = AgentManifest::get(agent_type)?;
+        let binary = binary_override.unwrap_or(&manifest.default_binary_path);
+        adapter.build_oneshot_command(binary, prompt)
+    }
 }
 
 impl Default for AgentRegistry {
@@ -444,6 +494,30 @@ mod tests {
         assert!(spec.shell_command.contains("test prompt"));
     }
 
+    #[test]
+    fn test_build_oneshot_command() {
+        let registry = AgentRegistry::new();
+
+        let claude = registry
+            .build_oneshot_command("claude", "summarize", Some("/opt/claude"))
+            .unwrap();
+        assert_eq!(claude.program, "/opt/claude");
+        assert_eq!(claude.args, vec!["-p", "summarize"]);
+
+        let codex = registry
+            .build_oneshot_command("codex", "summarize", None)
+            .unwrap();
+        assert_eq!(codex.program, "codex");
+        assert_eq!(codex.args.first().map(String::as_str), Some("exec"));
+        assert_eq!(codex.args.last().map(String::as_str), Some("summarize"));
+
+        assert!(
+            registry
+                .build_oneshot_command("terminal", "summarize", None)
+                .is_none()
+        );
+    }
+
     mod claude_tests {
         use super::*;
 
@@ -868,4 +942,8 @@ impl AgentAdapter for CopilotAdapter {
             .with_initial_command(initial_command)
             .with_env_vars(env)
     }
+
+    fn build_oneshot_command(&self, binary: &str, prompt: &str) -> Option<AgentOneshotCommand> {
+        Some(AgentOneshotCommand::new(binary, &["-p"], prompt))
+    }
 }
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
 use std::env;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command as StdCommand;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use anyhow::Error as AnyhowError;
 use git2::Repository;
@@ -177,6 +180,32 @@ pub struct GitHubReviewThread {
     pub comments: Vec<GitHubReviewThreadComment>,
 }
 
+/// CI outcome of a PR summarised over all of its status checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrChecksOutcome {
+    Passing,
+    Failing,
+    Pending,
+    NoChecks,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubPrStatus {
+    pub state: String,
+    pub review_decision: Option<String>,
+    pub checks: PrChecksOutcome,
+    pub passing_checks: usize,
+    pub failing_checks: usize,
+    pub pending_checks: usize,
+}
+
+impl GitHubPrStatus {
+    /// Merged and closed PRs no longer change, so pollers can stop watching them.
+    pub fn is_open(&self) -> bool {
+        self.state.eq_ignore_ascii_case("OPEN")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitHubPrFeedback {
     pub state: String,
@@ -242,6 +271,18 @@ impl std::error::Error for GitHubCliError {
     }
 }
 
+impl GitHubCliError {
+    /// Whether `gh` failed because GitHub's primary or secondary rate limit was hit.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            GitHubCliError::CommandFailed { stdout, stderr, .. } => [stdout, stderr]
+                .iter()
+                .any(|text| text.to_lowercase().contains("rate limit")),
+            _ => false,
+        }
+    }
+}
+
 impl From<serde_json::Error> for GitHubCliError {
     fn from(value: serde_json::Error) -> Self {
         GitHubCliError::Json(value)
@@ -359,9 +400,160 @@ fn sh_quote(s: &str) -> String {
     quoted
 }
 
+/// How often open session PRs are checked for CI and review changes.
+pub const DEFAULT_PR_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Upper bound for the poll delay while GitHub keeps reporting rate limits.
+pub const MAX_PR_STATUS_POLL_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
+/// Reads `SCHALTWERK_PR_STATUS_POLL_SECS`, falling back to
+/// [`DEFAULT_PR_STATUS_POLL_INTERVAL`] when unset or invalid.
+pub fn pr_status_poll_interval_from_env() -> Duration {
+    env::var("SCHALTWERK_PR_STATUS_POLL_SECS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_PR_STATUS_POLL_INTERVAL)
+}
+
+/// Delay before the next PR status poll: `interval`, doubled for every consecutive
+/// rate-limited poll and capped at [`MAX_PR_STATUS_POLL_BACKOFF`].
+pub fn pr_status_poll_delay(interval: Duration, consecutive_rate_limits: u32) -> Duration {
+    let factor = 1u32 << consecutive_rate_limits.min(16);
+    interval
+        .saturating_mul(factor)
+        .min(MAX_PR_STATUS_POLL_BACKOFF.max(interval))
+}
+
+/// How long issue and PR lookups are served from [`GitHubLookupCache`].
+pub const DEFAULT_GITHUB_LOOKUP_TTL: Duration = Duration::from_secs(60);
+
+/// Reads `SCHALTWERK_GITHUB_LOOKUP_TTL_SECS`, falling back to
+/// [`DEFAULT_GITHUB_LOOKUP_TTL`] when unset or invalid. `0` disables the cache.
+pub fn github_lookup_ttl_from_env() -> Duration {
+    env::var("SCHALTWERK_GITHUB_LOOKUP_TTL_SECS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_GITHUB_LOOKUP_TTL)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum LookupKey {
+    IssueSearch {
+        project: PathBuf,
+        repository: Option<String>,
+        query: String,
+        limit: usize,
+    },
+    IssueDetails {
+        project: PathBuf,
+        repository: Option<String>,
+        number: u64,
+    },
+    PrSearch {
+        project: PathBuf,
+        repository: Option<String>,
+        query: String,
+        limit: usize,
+    },
+    PrDetails {
+        project: PathBuf,
+        repository: Option<String>,
+        number: u64,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum CachedLookup {
+    Issues(Vec<GitHubIssueSummary>),
+    Issue(GitHubIssueDetails),
+    Prs(Vec<GitHubPrSummary>),
+    Pr(GitHubPrDetails),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GitHubLookupCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub ttl: Duration,
+}
+
+/// In-memory TTL cache for issue and PR lookups, so repeated searches do not spend
+/// GitHub API quota. Failed lookups are never cached.
+pub struct GitHubLookupCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<LookupKey, (Instant, CachedLookup)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl GitHubLookupCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn get(&self, key: &LookupKey) -> Option<CachedLookup> {
+        let mut entries = self.entries.lock().unwrap();
+        let fresh = match entries.get(key) {
+            Some((stored_at, value)) if stored_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        let counter = if fresh.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        fresh
+    }
+
+    fn insert(&self, key: LookupKey, value: CachedLookup) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), value));
+    }
+
+    pub fn invalidate(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn stats(&self) -> GitHubLookupCacheStats {
+        GitHubLookupCacheStats {
+            entries: self.entries.lock().unwrap().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            ttl: self.ttl,
+        }
+    }
+}
+
+static GITHUB_LOOKUP_CACHE: LazyLock<Arc<GitHubLookupCache>> =
+    LazyLock::new(|| Arc::new(GitHubLookupCache::new(github_lookup_ttl_from_env())));
+
+/// The cache shared by every [`GitHubCli::new`] instance.
+pub fn github_lookup_cache() -> Arc<GitHubLookupCache> {
+    Arc::clone(&GITHUB_LOOKUP_CACHE)
+}
+
 pub struct GitHubCli<R: CommandRunner = SystemCommandRunner> {
     runner: R,
     program: String,
+    lookup_cache: Arc<GitHubLookupCache>,
 }
 
 impl GitHubCli<SystemCommandRunner> {
@@ -369,6 +561,7 @@ impl GitHubCli<SystemCommandRunner> {
         Self {
             runner: SystemCommandRunner,
             program: resolve_github_cli_program(),
+            lookup_cache: github_lookup_cache(),
         }
     }
 }
@@ -384,7 +577,24 @@ impl<R: CommandRunner> GitHubCli<R> {
         Self {
             runner,
             program: "gh".to_string(),
+            lookup_cache: Arc::new(GitHubLookupCache::new(DEFAULT_GITHUB_LOOKUP_TTL)),
+        }
+    }
+
+    fn cached_lookup<T>(
+        &self,
+        key: LookupKey,
+        extract: impl FnOnce(CachedLookup) -> Option<T>,
+        store: impl FnOnce(&T) -> CachedLookup,
+        fetch: impl FnOnce() -> Result<T, GitHubCliError>,
+    ) -> Result<T, GitHubCliError> {
+        if let Some(hit) = self.lookup_cache.get(&key).and_then(extract) {
+            debug!("[GitHubCli] Serving cached lookup {key:?}");
+            return Ok(hit);
         }
+        let value = fetch()?;
+        self.lookup_cache.insert(key, store(&value));
+        Ok(value)
     }
 
     pub fn ensure_installed(&self) -> Result<(), GitHubCliError> {
@@ -556,6 +766,30 @@ impl<R: CommandRunner> GitHubCli<R> {
         query: &str,
         limit: usize,
         repository: Option<&str>,
+    ) -> Result<Vec<GitHubIssueSummary>, GitHubCliError> {
+        let key = LookupKey::IssueSearch {
+            project: project_path.to_path_buf(),
+            repository: repository.map(str::to_string),
+            query: query.trim().to_string(),
+            limit,
+        };
+        self.cached_lookup(
+            key,
+            |cached| match cached {
+                CachedLookup::Issues(issues) => Some(issues),
+                _ => None,
+            },
+            |issues| CachedLookup::Issues(issues.clone()),
+            || self.fetch_issue_search(project_path, query, limit, repository),
+        )
+    }
+
+    fn fetch_issue_search(
+        &self,
+        project_path: &Path,
+        query: &str,
+        limit: usize,
+        repository: Option<&str>,
     ) -> Result<Vec<GitHubIssueSummary>, GitHubCliError> {
         debug!(
             "[GitHubCli] Searching issues for project={}, query='{}', limit={}",
@@ -661,6 +895,28 @@ impl<R: CommandRunner> GitHubCli<R> {
         project_path: &Path,
         number: u64,
         repository: Option<&str>,
+    ) -> Result<GitHubIssueDetails, GitHubCliError> {
+        let key = LookupKey::IssueDetails {
+            project: project_path.to_path_buf(),
+            repository: repository.map(str::to_string),
+            number,
+        };
+        self.cached_lookup(
+            key,
+            |cached| match cached {
+                CachedLookup::Issue(issue) => Some(issue),
+                _ => None,
+            },
+            |issue| CachedLookup::Issue(issue.clone()),
+            || self.fetch_issue_with_comments(project_path, number, repository),
+        )
+    }
+
+    fn fetch_issue_with_comments(
+        &self,
+        project_path: &Path,
+        number: u64,
+        repository: Option<&str>,
     ) -> Result<GitHubIssueDetails, GitHubCliError> {
         debug!(
             "[GitHubCli] Fetching issue details for project={}, number={}",
@@ -745,6 +1001,30 @@ impl<R: CommandRunner> GitHubCli<R> {
         query: &str,
         limit: usize,
         repository: Option<&str>,
+    ) -> Result<Vec<GitHubPrSummary>, GitHubCliError> {
+        let key = LookupKey::PrSearch {
+            project: project_path.to_path_buf(),
+            repository: repository.map(str::to_string),
+            query: query.trim().to_string(),
+            limit,
+        };
+        self.cached_lookup(
+            key,
+            |cached| match cached {
+                CachedLookup::Prs(prs) => Some(prs),
+                _ => None,
+            },
+            |prs| CachedLookup::Prs(prs.clone()),
+            || self.fetch_pr_search(project_path, query, limit, repository),
+        )
+    }
+
+    fn fetch_pr_search(
+        &self,
+        project_path: &Path,
+        query: &str,
+        limit: usize,
+        repository: Option<&str>,
     ) -> Result<Vec<GitHubPrSummary>, GitHubCliError> {
         debug!(
             "[GitHubCli] Searching PRs for project={}, query='{}', limit={}",
@@ -845,6 +1125,28 @@ impl<R: CommandRunner> GitHubCli<R> {
         project_path: &Path,
         number: u64,
         repository: Option<&str>,
+    ) -> Result<GitHubPrDetails, GitHubCliError> {
+        let key = LookupKey::PrDetails {
+            project: project_path.to_path_buf(),
+            repository: repository.map(str::to_string),
+            number,
+        };
+        self.cached_lookup(
+            key,
+            |cached| match cached {
+                CachedLookup::Pr(pr) => Some(pr),
+                _ => None,
+            },
+            |pr| CachedLookup::Pr(pr.clone()),
+            || self.fetch_pr_with_comments(project_path, number, repository),
+        )
+    }
+
+    fn fetch_pr_with_comments(
+        &self,
+        project_path: &Path,
+        number: u64,
+        repository: Option<&str>,
     ) -> Result<GitHubPrDetails, GitHubCliError> {
         debug!(
             "[GitHubCli] Fetching PR details for project={}, number={}",
@@ -1192,12 +1494,13 @@ impl<R: CommandRunner> GitHubCli<R> {
 
         self.push_branch(opts.worktree_path, &push_branch)?;
 
-        let pr_url = self.create_pull_request(
+        let pr_url = self.create_pull_request_inner(
             &push_branch,
             opts.repository,
             opts.worktree_path,
             opts.content.clone(),
             opts.target_branch,
+            opts.draft,
         )?;
 
         Ok(GitHubPrResult {
@@ -1378,6 +1681,42 @@ impl<R: CommandRunner> GitHubCli<R> {
         Err(command_failure("git", &retry_args_vec, retry_output))
     }
 
+    /// Pushes the session branch as-is and opens a draft PR for it. Unlike
+    /// [`Self::create_session_pr`], nothing is committed or squashed first.
+    pub fn create_draft_pr(
+        &self,
+        opts: CreateDraftPrOptions<'_>,
+    ) -> Result<GitHubPrResult, GitHubCliError> {
+        info!(
+            "Creating draft PR for session '{}' (branch='{}', base='{}')",
+            opts.session_slug, opts.branch, opts.base_branch
+        );
+
+        ensure_git_remote_exists(opts.repo_path)?;
+        push_head_to_remote_branch(self, opts.worktree_path, opts.branch)?;
+
+        if let Err(e) = set_upstream_tracking(self, opts.worktree_path, opts.branch) {
+            warn!("Failed to set upstream tracking for '{}': {e}", opts.branch);
+        }
+
+        let url = self.create_pull_request_inner(
+            opts.branch,
+            opts.repository,
+            opts.worktree_path,
+            PrContent::Explicit {
+                title: opts.title,
+                body: opts.body,
+            },
+            Some(opts.base_branch),
+            true,
+        )?;
+
+        Ok(GitHubPrResult {
+            branch: opts.branch.to_string(),
+            url,
+        })
+    }
+
     fn create_pull_request(
         &self,
         branch_name: &str,
@@ -1385,6 +1724,25 @@ impl<R: CommandRunner> GitHubCli<R> {
         worktree_path: &Path,
         content: PrContent<'_>,
         target_branch: Option<&str>,
+    ) -> Result<String, GitHubCliError> {
+        self.create_pull_request_inner(
+            branch_name,
+            repository,
+            worktree_path,
+            content,
+            target_branch,
+            false,
+        )
+    }
+
+    fn create_pull_request_inner(
+        &self,
+        branch_name: &str,
+        repository: Option<&str>,
+        worktree_path: &Path,
+        content: PrContent<'_>,
+        target_branch: Option<&str>,
+        draft: bool,
     ) -> Result<String, GitHubCliError> {
         let env = [("GH_PROMPT_DISABLED", "1"), ("NO_COLOR", "1")];
         let mut args_vec = vec![
@@ -1394,6 +1752,10 @@ impl<R: CommandRunner> GitHubCli<R> {
             branch_name.to_string(),
         ];
 
+        if draft {
+            args_vec.push("--draft".to_string());
+        }
+
         match content {
             PrContent::Explicit { title, body } => {
                 args_vec.push("--title".to_string());
@@ -1495,6 +1857,81 @@ impl<R: CommandRunner> GitHubCli<R> {
         Ok(Some(response.url))
     }
 
+    /// Fetches the current state, review decision and CI checks of a PR, bypassing the
+    /// lookup cache.
+    pub fn get_pr_status(
+        &self,
+        project_path: &Path,
+        pr_number: u64,
+        repository: Option<&str>,
+    ) -> Result<GitHubPrStatus, GitHubCliError> {
+        let env = [("GH_PROMPT_DISABLED", "1"), ("NO_COLOR", "1")];
+        let mut args_vec = vec![
+            "pr".to_string(),
+            "view".to_string(),
+            pr_number.to_string(),
+            "--json".to_string(),
+            "state,statusCheckRollup,reviewDecision".to_string(),
+        ];
+        if let Some(repo) = repository {
+            args_vec.push("--repo".to_string());
+            args_vec.push(repo.to_string());
+        }
+
+        let arg_refs: Vec<&str> = args_vec.iter().map(|s| s.as_str()).collect();
+        let output = self
+            .runner
+            .run(&self.program, &arg_refs, Some(project_path), &env)
+            .map_err(map_runner_error)?;
+
+        if !output.success() {
+            return Err(command_failure(&self.program, &args_vec, output));
+        }
+
+        let clean_output = strip_ansi_codes(&output.stdout);
+        let parsed: PrStatusResponse =
+            serde_json::from_str(clean_output.trim()).map_err(|err| {
+                log::error!(
+                    "[GitHubCli] Failed to parse PR status response: {err}; raw={}",
+                    output.stdout.trim()
+                );
+                GitHubCliError::InvalidOutput(
+                    "GitHub CLI returned PR status in an unexpected format.".to_string(),
+                )
+            })?;
+
+        Ok(summarize_pr_status(parsed))
+    }
+
+    /// Marks a draft PR as ready for review. `pr` may be a PR number, URL or branch.
+    pub fn mark_pr_ready(
+        &self,
+        pr: &str,
+        repository: Option<&str>,
+        worktree_path: &Path,
+    ) -> Result<(), GitHubCliError> {
+        let env = [("GH_PROMPT_DISABLED", "1"), ("NO_COLOR", "1")];
+        let mut args_vec = vec!["pr".to_string(), "ready".to_string(), pr.to_string()];
+
+        if let Some(repo) = repository {
+            args_vec.push("--repo".to_string());
+            args_vec.push(repo.to_string());
+        }
+
+        let arg_refs: Vec<&str> = args_vec.iter().map(|s| s.as_str()).collect();
+        let output = self
+            .runner
+            .run(&self.program, &arg_refs, Some(worktree_path), &env)
+            .map_err(map_runner_error)?;
+
+        if !output.success() {
+            return Err(command_failure(&self.program, &args_vec, output));
+        }
+
+        info!("Marked PR '{pr}' as ready for review");
+        Ok(())
+    }
+
     pub fn authenticate(&self) -> Result<(), GitHubCliError> {
         Err(GitHubCliError::CommandFailed {
             program: "gh".to_string(),
@@ -1533,6 +1970,8 @@ pub struct CreatePrOptions<'a> {
     pub content: PrContent<'a>,
     pub target_branch: Option<&'a str>,
     pub custom_branch_name: Option<&'a str>,
+    /// Open the PR as a draft.
+    pub draft: bool,
 }
 
 pub struct CreateSessionPrOptions<'a> {
@@ -1548,6 +1987,17 @@ pub struct CreateSessionPrOptions<'a> {
     pub mode: PrCommitMode,
 }
 
+pub struct CreateDraftPrOptions<'a> {
+    pub repo_path: &'a Path,
+    pub worktree_path: &'a Path,
+    pub session_slug: &'a str,
+    pub branch: &'a str,
+    pub base_branch: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub repository: Option<&'a str>,
+}
+
 fn run_git<R: CommandRunner>(
     cli: &GitHubCli<R>,
     cwd: &Path,
@@ -2136,6 +2586,53 @@ struct StatusCheckRollupNode {
     name: Option<String>,
     status: Option<String>,
     conclusion: Option<String>,
+    /// Set instead of `status`/`conclusion` for commit status contexts.
+    state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrStatusResponse {
+    state: String,
+    #[serde(rename = "reviewDecision")]
+    review_decision: Option<String>,
+    #[serde(rename = "statusCheckRollup")]
+    status_check_rollup: Option<Vec<StatusCheckRollupNode>>,
+}
+
+fn summarize_pr_status(response: PrStatusResponse) -> GitHubPrStatus {
+    let (mut passing, mut failing, mut pending) = (0, 0, 0);
+    for check in response.status_check_rollup.unwrap_or_default() {
+        let outcome = check
+            .conclusion
+            .as_deref()
+            .or(check.state.as_deref())
+            .filter(|value| !value.is_empty())
+            .map(str::to_ascii_uppercase);
+        match outcome.as_deref() {
+            Some("SUCCESS" | "NEUTRAL" | "SKIPPED") => passing += 1,
+            Some("PENDING" | "EXPECTED") | None => pending += 1,
+            Some(_) => failing += 1,
+        }
+    }
+
+    let checks = if failing > 0 {
+        PrChecksOutcome::Failing
+    } else if pending > 0 {
+        PrChecksOutcome::Pending
+    } else if passing > 0 {
+        PrChecksOutcome::Passing
+    } else {
+        PrChecksOutcome::NoChecks
+    };
+
+    GitHubPrStatus {
+        state: response.state,
+        review_decision: response.review_decision.filter(|d| !d.is_empty()),
+        checks,
+        passing_checks: passing,
+        failing_checks: failing,
+        pending_checks: pending,
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -2600,6 +3097,72 @@ mod tests {
         assert!(args.contains(&"--json".to_string()));
     }
 
+    #[test]
+    fn search_issues_serves_repeated_lookups_from_cache() {
+        let runner = MockRunner::default();
+        for _ in 0..2 {
+            runner.push_response(Ok(CommandOutput {
+                status: Some(0),
+                stdout: "[{\"number\":7,\"title\":\"Feature\",\"state\":\"OPEN\",\"updatedAt\":\"2024-02-02T00:00:00Z\",\"author\":null,\"labels\":[],\"url\":\"https://github.com/example/repo/issues/7\"}]".to_string(),
+                stderr: String::new(),
+            }));
+        }
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let temp = TempDir::new().unwrap();
+        let repo_path = temp.path();
+        let repo = git2::Repository::init(repo_path).unwrap();
+        repo.remote("origin", "https://github.com/example/repo")
+            .unwrap();
+
+        let first = cli.search_issues(repo_path, "feature", 5, None).unwrap();
+        let second = cli.search_issues(repo_path, " feature ", 5, None).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(runner.calls().len(), 1);
+
+        cli.search_issues(repo_path, "other", 5, None).unwrap();
+        assert_eq!(runner.calls().len(), 2);
+
+        let stats = cli.lookup_cache.stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses), (2, 1, 2));
+    }
+
+    #[test]
+    fn issue_details_are_cached_until_invalidated() {
+        let runner = MockRunner::default();
+        for _ in 0..2 {
+            runner.push_response(Ok(CommandOutput {
+                status: Some(0),
+                stdout: json!({
+                    "number": 12,
+                    "title": "Cached",
+                    "url": "https://github.com/example/repo/issues/12",
+                    "body": "",
+                    "labels": [],
+                    "comments": []
+                })
+                .to_string(),
+                stderr: String::new(),
+            }));
+        }
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let temp = TempDir::new().unwrap();
+        let repo_path = temp.path();
+        let repo = git2::Repository::init(repo_path).unwrap();
+        repo.remote("origin", "https://github.com/example/repo")
+            .unwrap();
+
+        cli.get_issue_with_comments(repo_path, 12, None).unwrap();
+        cli.get_issue_with_comments(repo_path, 12, None).unwrap();
+        assert_eq!(runner.calls().len(), 1);
+
+        cli.lookup_cache.invalidate();
+        let details = cli.get_issue_with_comments(repo_path, 12, None).unwrap();
+        assert_eq!(details.title, "Cached");
+        assert_eq!(runner.calls().len(), 2);
+    }
+
     #[test]
     fn get_issue_with_comments_handles_array_response() {
         let runner = MockRunner::default();
@@ -2701,6 +3264,7 @@ mod tests {
             content: PrContent::Fill,
             target_branch: None,
             custom_branch_name: None,
+            draft: false,
         };
 
         let result = cli.create_pr_from_worktree(opts).expect("pr result");
@@ -2773,6 +3337,7 @@ mod tests {
             },
             target_branch: Some("develop"),
             custom_branch_name: None,
+            draft: false,
         };
 
         let result = cli.create_pr_from_worktree(opts).expect("pr result");
@@ -2857,6 +3422,7 @@ mod tests {
             content: PrContent::Fill,
             target_branch: None,
             custom_branch_name: None,
+            draft: false,
         };
 
         let result = cli.create_pr_from_worktree(opts).expect("pr result");
@@ -3038,6 +3604,181 @@ mod tests {
         assert!(!args.contains(&"--body".to_string()));
     }
 
+    #[test]
+    fn create_pull_request_passes_draft_flag_only_when_requested() {
+        for draft in [true, false] {
+            let runner = MockRunner::default();
+            runner.push_response(Ok(CommandOutput {
+                status: Some(0),
+                stdout: "https://github.com/owner/repo/pull/125\n".to_string(),
+                stderr: String::new(),
+            }));
+            let cli = GitHubCli::with_runner(runner.clone());
+
+            let temp = TempDir::new().unwrap();
+            let url = cli
+                .create_pull_request_inner(
+                    "feature-branch",
+                    Some("owner/repo"),
+                    temp.path(),
+                    PrContent::Fill,
+                    Some("main"),
+                    draft,
+                )
+                .unwrap();
+            assert_eq!(url, "https://github.com/owner/repo/pull/125");
+
+            let calls = runner.calls();
+            assert_eq!(calls.len(), 1);
+            assert_eq!(
+                calls[0].args.contains(&"--draft".to_string()),
+                draft,
+                "draft={draft}, args: {:?}",
+                calls[0].args
+            );
+        }
+    }
+
+    #[test]
+    fn mark_pr_ready_invokes_gh_pr_ready() {
+        let runner = MockRunner::default();
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+        }));
+        runner.push_response(Ok(CommandOutput {
+            status: Some(1),
+            stdout: String::new(),
+            stderr: "pull request is not a draft".to_string(),
+        }));
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let temp = TempDir::new().unwrap();
+        cli.mark_pr_ready("42", Some("owner/repo"), temp.path())
+            .unwrap();
+        let err = cli.mark_pr_ready("42", None, temp.path()).unwrap_err();
+        assert!(matches!(err, GitHubCliError::CommandFailed { .. }));
+
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0].args,
+            vec!["pr", "ready", "42", "--repo", "owner/repo"]
+        );
+        assert_eq!(calls[0].cwd.as_deref(), Some(temp.path()));
+        assert_eq!(calls[1].args, vec!["pr", "ready", "42"]);
+    }
+
+    #[test]
+    fn get_pr_status_summarises_checks_and_review() {
+        let runner = MockRunner::default();
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: json!({
+                "state": "OPEN",
+                "reviewDecision": "APPROVED",
+                "statusCheckRollup": [
+                    { "name": "build", "status": "COMPLETED", "conclusion": "SUCCESS" },
+                    { "name": "lint", "status": "COMPLETED", "conclusion": "SKIPPED" },
+                    { "name": "test", "status": "COMPLETED", "conclusion": "FAILURE" },
+                    { "name": "e2e", "status": "IN_PROGRESS", "conclusion": "" },
+                    { "context": "ci/legacy", "state": "SUCCESS" }
+                ]
+            })
+            .to_string(),
+            stderr: String::new(),
+        }));
+        let cli = GitHubCli::with_runner(runner.clone());
+
+        let temp = TempDir::new().unwrap();
+        let status = cli
+            .get_pr_status(temp.path(), 42, Some("owner/repo"))
+            .unwrap();
+
+        assert!(status.is_open());
+        assert_eq!(status.review_decision.as_deref(), Some("APPROVED"));
+        assert_eq!(status.checks, PrChecksOutcome::Failing);
+        assert_eq!(
+            (
+                status.passing_checks,
+                status.failing_checks,
+                status.pending_checks
+            ),
+            (3, 1, 1)
+        );
+        assert_eq!(
+            runner.calls()[0].args,
+            vec![
+                "pr",
+                "view",
+                "42",
+                "--json",
+                "state,statusCheckRollup,reviewDecision",
+                "--repo",
+                "owner/repo"
+            ]
+        );
+    }
+
+    #[test]
+    fn get_pr_status_reports_pending_missing_and_closed_states() {
+        let runner = MockRunner::default();
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: json!({
+                "state": "OPEN",
+                "reviewDecision": "",
+                "statusCheckRollup": [
+                    { "name": "build", "status": "COMPLETED", "conclusion": "SUCCESS" },
+                    { "context": "deploy", "state": "PENDING" }
+                ]
+            })
+            .to_string(),
+            stderr: String::new(),
+        }));
+        runner.push_response(Ok(CommandOutput {
+            status: Some(0),
+            stdout: r#"{"state":"MERGED","reviewDecision":null,"statusCheckRollup":[]}"#
+                .to_string(),
+            stderr: String::new(),
+        }));
+        let cli = GitHubCli::with_runner(runner);
+
+        let temp = TempDir::new().unwrap();
+        let pending = cli.get_pr_status(temp.path(), 7, None).unwrap();
+        assert_eq!(pending.checks, PrChecksOutcome::Pending);
+        assert_eq!(pending.review_decision, None);
+
+        let merged = cli.get_pr_status(temp.path(), 7, None).unwrap();
+        assert_eq!(merged.checks, PrChecksOutcome::NoChecks);
+        assert!(!merged.is_open());
+    }
+
+    #[test]
+    fn rate_limit_failures_are_detected_and_back_off() {
+        let runner = MockRunner::default();
+        runner.push_response(Ok(CommandOutput {
+            status: Some(1),
+            stdout: String::new(),
+            stderr: "GraphQL: API rate limit exceeded for user ID 1.".to_string(),
+        }));
+        let cli = GitHubCli::with_runner(runner);
+
+        let temp = TempDir::new().unwrap();
+        let err = cli.get_pr_status(temp.path(), 7, None).unwrap_err();
+        assert!(err.is_rate_limited());
+        assert!(!GitHubCliError::NoGitRemote.is_rate_limited());
+
+        let interval = Duration::from_secs(120);
+        assert_eq!(pr_status_poll_delay(interval, 0), interval);
+        assert_eq!(pr_status_poll_delay(interval, 2), Duration::from_secs(480));
+        assert_eq!(
+            pr_status_poll_delay(interval, 40),
+            MAX_PR_STATUS_POLL_BACKOFF
+        );
+    }
+
     #[test]
     fn parse_owner_name_splits_valid_input() {
         let (owner, name) = parse_owner_name("octocat/hello-world").unwrap();
@@ -138,6 +138,16 @@ pub fn get_commit_file_changes(
     Ok(files)
 }
 
+/// Narrows the commits returned by [`get_git_history_with_head`]. Matching commits keep
+/// the order of the unfiltered walk, so a cursor from one page stays valid for the next.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Case-insensitive substring of the author name or email.
+    pub author: Option<String>,
+    /// Pathspec a commit must change to be included.
+    pub path: Option<String>,
+}
+
 const DEFAULT_HISTORY_LIMIT: usize = 100;
 
 pub fn get_git_history(
@@ -145,7 +155,7 @@ pub fn get_git_history(
     limit: Option<usize>,
     cursor: Option<&str>,
 ) -> Result<HistoryProviderSnapshot> {
-    get_git_history_with_head(repo_path, limit, cursor, None)
+    get_git_history_with_head(repo_path, limit, cursor, None, &HistoryFilter::default())
 }
 
 pub fn get_git_history_with_head(
@@ -153,6 +163,7 @@ pub fn get_git_history_with_head(
     limit: Option<usize>,
     cursor: Option<&str>,
     since_head: Option<&str>,
+    filter: &HistoryFilter,
 ) -> Result<HistoryProviderSnapshot> {
     let repo = Repository::open(repo_path).context("Failed to open git repository")?;
 
@@ -246,13 +257,19 @@ pub fn get_git_history_with_head(
     let mut visited = HashSet::new();
     let mut last_full_oid = None;
     let mut has_more = false;
+    let author_filter = filter
+        .author
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_lowercase);
+    let path_filter = filter
+        .path
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
 
     for oid_result in revwalk {
-        if items.len() >= effective_limit {
-            has_more = true;
-            break;
-        }
-
         let oid = oid_result?;
 
         if !visited.insert(oid) {
@@ -269,6 +286,22 @@ pub fn get_git_history_with_head(
         }
 
         let commit = repo.find_commit(oid)?;
+        if let Some(needle) = author_filter.as_deref()
+            && !author_matches(&commit, needle)
+        {
+            continue;
+        }
+        if let Some(pathspec) = path_filter
+            && !commit_touches_path(&repo, &commit, pathspec)?
+        {
+            continue;
+        }
+
+        if items.len() >= effective_limit {
+            has_more = true;
+            break;
+        }
+
         let parent_ids: Vec<String> = commit
             .parent_ids()
             .map(|id| id.to_string()[..7].to_string())
@@ -290,7 +323,7 @@ pub fn get_git_history_with_head(
     }
 
     if cursor_value.is_some() && !cursor_seen {
-        return get_git_history(repo_path, Some(effective_limit), None);
+        return get_git_history_with_head(repo_path, Some(effective_limit), None, None, filter);
     }
 
     Ok(HistoryProviderSnapshot {
@@ -309,6 +342,40 @@ pub fn get_git_history_with_head(
     })
 }
 
+fn author_matches(commit: &git2::Commit<'_>, needle: &str) -> bool {
+    let author = commit.author();
+    [author.name(), author.email()]
+        .into_iter()
+        .flatten()
+        .any(|value| value.to_lowercase().contains(needle))
+}
+
+/// Whether `commit` changes a path matching `pathspec`. Like `git log -- <path>`, a merge
+/// only counts when it differs from every parent there.
+fn commit_touches_path(
+    repo: &Repository,
+    commit: &git2::Commit<'_>,
+    pathspec: &str,
+) -> Result<bool> {
+    let tree = commit.tree()?;
+    let mut opts = DiffOptions::new();
+    opts.pathspec(pathspec);
+
+    if commit.parent_count() == 0 {
+        let diff = repo.diff_tree_to_tree(None, Some(&tree), Some(&mut opts))?;
+        return Ok(diff.deltas().len() > 0);
+    }
+
+    for parent in commit.parents() {
+        let parent_tree = parent.tree()?;
+        let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&tree), Some(&mut opts))?;
+        if diff.deltas().len() == 0 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 fn resolve_current_refs(repo: &Repository) -> (Option<HistoryItemRef>, Option<HistoryItemRef>) {
     let current_ref = repo.head().ok().and_then(|head| {
         let name = head.name()?;
@@ -466,4 +533,90 @@ mod tests {
         assert_eq!(second_page.items.len(), 2);
         assert!(second_page.items[0].id != first_page.items[0].id);
     }
+
+    fn collect_pages(
+        repo_path: &Path,
+        page_size: usize,
+        filter: &HistoryFilter,
+    ) -> Vec<HistoryProviderSnapshot> {
+        let mut pages = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = get_git_history_with_head(
+                repo_path,
+                Some(page_size),
+                cursor.as_deref(),
+                None,
+                filter,
+            )
+            .expect("history page");
+            let has_more = page.has_more == Some(true);
+            cursor = page.next_cursor.clone();
+            pages.push(page);
+            if !has_more {
+                return pages;
+            }
+        }
+    }
+
+    fn page_hashes(pages: &[HistoryProviderSnapshot]) -> Vec<String> {
+        pages
+            .iter()
+            .flat_map(|page| page.items.iter())
+            .map(|item| item.full_hash.clone().expect("full hash"))
+            .collect()
+    }
+
+    #[test]
+    fn pages_cover_history_in_order_without_duplicates() {
+        let (_dir, repo, commits) = seed_linear_history(25).expect("seed repo");
+
+        let pages = collect_pages(repo.workdir().unwrap(), 10, &HistoryFilter::default());
+
+        let sizes: Vec<usize> = pages.iter().map(|page| page.items.len()).collect();
+        assert_eq!(sizes, vec![10, 10, 5]);
+        let expected: Vec<String> = commits.into_iter().rev().collect();
+        assert_eq!(page_hashes(&pages), expected);
+    }
+
+    #[test]
+    fn path_and_author_filters_prune_pages() {
+        let (_dir, repo) = init_repo().expect("seed repo");
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::create_dir_all(workdir.join("docs")).unwrap();
+
+        let mut parent: Option<Oid> = None;
+        let mut docs_commits = Vec::new();
+        for idx in 0..30 {
+            write_file(&repo, idx).unwrap();
+            if idx % 5 == 0 {
+                std::fs::write(workdir.join("docs/notes.md"), format!("notes {idx}")).unwrap();
+            }
+            let parent_commit = parent.map(|oid| repo.find_commit(oid).unwrap());
+            let commit =
+                create_commit(&repo, &format!("commit-{idx}"), parent_commit.as_ref()).unwrap();
+            if idx % 5 == 0 {
+                docs_commits.push(commit.id().to_string());
+            }
+            parent = Some(commit.id());
+        }
+
+        let docs_filter = HistoryFilter {
+            author: Some("TESTER@example".to_string()),
+            path: Some("docs".to_string()),
+        };
+        let pages = collect_pages(&workdir, 2, &docs_filter);
+        assert_eq!(pages.len(), 3);
+        let expected: Vec<String> = docs_commits.into_iter().rev().collect();
+        assert_eq!(page_hashes(&pages), expected);
+
+        let other_author = HistoryFilter {
+            author: Some("someone-else".to_string()),
+            path: None,
+        };
+        let page = get_git_history_with_head(&workdir, Some(10), None, None, &other_author)
+            .expect("history");
+        assert!(page.items.is_empty());
+        assert_eq!(page.has_more, Some(false));
+    }
 }
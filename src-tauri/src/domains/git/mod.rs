@@ -5,6 +5,7 @@ pub mod history;
 pub mod operations;
 pub mod repository;
 pub mod service;
+pub mod snapshots;
 pub mod stats;
 pub mod worktrees;
 
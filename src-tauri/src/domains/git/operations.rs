@@ -1,6 +1,7 @@
-use anyhow::{Result, anyhow};
-use git2::{IndexAddOption, Repository, Status, StatusOptions};
-use std::path::Path;
+use anyhow::{Result, anyhow, bail};
+use git2::{IndexAddOption, Oid, Repository, Status, StatusOptions};
+use std::collections::HashSet;
+use std::path::{Component, Path};
 
 #[inline]
 fn is_internal_tooling_path(path: &str) -> bool {
@@ -179,6 +180,122 @@ pub fn commit_all_changes(worktree_path: &Path, message: &str) -> Result<()> {
     Ok(())
 }
 
+const STAGED_BITS: Status = Status::INDEX_NEW
+    .union(Status::INDEX_MODIFIED)
+    .union(Status::INDEX_DELETED)
+    .union(Status::INDEX_RENAMED)
+    .union(Status::INDEX_TYPECHANGE);
+
+/// Rejects paths that are absolute, leave the repository or point into `.schaltwerk`.
+fn validate_stage_path(path: &str) -> Result<&Path> {
+    let relative = Path::new(path);
+    if path.is_empty()
+        || relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!("Invalid path '{path}': expected a path relative to the repository root");
+    }
+    if is_internal_tooling_path(path) {
+        bail!("Refusing to stage changes under .schaltwerk");
+    }
+    Ok(relative)
+}
+
+/// Paths whose changes are at least partly staged, i.e. listed under "Changes to be
+/// committed" by `git status`.
+pub fn staged_paths(repo: &Repository) -> Result<HashSet<String>> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(false);
+    let statuses = repo.statuses(Some(&mut opts))?;
+    Ok(statuses
+        .iter()
+        .filter(|entry| entry.status().intersects(STAGED_BITS))
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .filter(|path| !is_internal_tooling_path(path))
+        .collect())
+}
+
+/// Stages the working-tree state of `path`, including its deletion.
+pub fn stage_file(repo_path: &Path, path: &str) -> Result<()> {
+    let relative = validate_stage_path(path)?;
+    let repo = Repository::open(repo_path)?;
+    let mut index = repo.index()?;
+    if repo_path.join(relative).symlink_metadata().is_ok() {
+        index.add_path(relative)?;
+    } else {
+        index.remove_path(relative)?;
+    }
+    index.write()?;
+    Ok(())
+}
+
+/// Resets the index entry of `path` to HEAD, leaving the working tree untouched.
+pub fn unstage_file(repo_path: &Path, path: &str) -> Result<()> {
+    let relative = validate_stage_path(path)?;
+    let repo = Repository::open(repo_path)?;
+    match repo.head().ok().and_then(|head| head.peel_to_commit().ok()) {
+        Some(head) => repo.reset_default(Some(head.as_object()), [relative])?,
+        None => {
+            let mut index = repo.index()?;
+            index.remove_path(relative)?;
+            index.write()?;
+        }
+    }
+    Ok(())
+}
+
+/// Stages every change in the working tree except `.schaltwerk` internals.
+pub fn stage_all_changes(repo_path: &Path) -> Result<()> {
+    let repo = Repository::open(repo_path)?;
+    let mut index = repo.index()?;
+    let mut skip_internal = |path: &Path, _: &[u8]| -> i32 {
+        i32::from(is_internal_tooling_path(&path.to_string_lossy()))
+    };
+    index.add_all(
+        ["*"].iter(),
+        IndexAddOption::DEFAULT,
+        Some(&mut skip_internal),
+    )?;
+    index.update_all(["*"].iter(), Some(&mut skip_internal))?;
+    index.write()?;
+    Ok(())
+}
+
+/// Commits the index as it is. Fails instead of creating an empty commit when nothing
+/// is staged.
+pub fn commit_staged_changes(repo_path: &Path, message: &str) -> Result<Oid> {
+    if message.trim().is_empty() {
+        bail!("Commit message must not be empty");
+    }
+    let repo = Repository::open(repo_path)?;
+    let mut index = repo.index()?;
+    let tree_id = index.write_tree()?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+
+    let unchanged = match &parent {
+        Some(parent) => parent.tree_id() == tree_id,
+        None => index.is_empty(),
+    };
+    if unchanged {
+        bail!("Nothing staged to commit");
+    }
+
+    let signature = repo.signature()
+        .map_err(|e| anyhow!("Failed to get signature from git config: {e}. Please configure git user.name and user.email"))?;
+    let tree = repo.find_tree(tree_id)?;
+    let parents: Vec<_> = parent.iter().collect();
+    let oid = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+    Ok(oid)
+}
+
 pub fn is_valid_session_name(name: &str) -> bool {
     if name.is_empty() || name.len() > 100 {
         return false;
@@ -599,4 +716,103 @@ mod tests {
         let detected = has_conflicts(temp_dir.path()).expect("Conflict detection should succeed");
         assert!(detected, "Conflict must be reported");
     }
+
+    fn init_repo_with_tracked_files(files: &[&str]) -> TempDir {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        run_git(temp_dir.path(), &["init"]);
+        run_git(
+            temp_dir.path(),
+            &["config", "user.email", "test@example.com"],
+        );
+        run_git(temp_dir.path(), &["config", "user.name", "Test User"]);
+        for file in files {
+            fs::write(temp_dir.path().join(file), "base\n").unwrap();
+        }
+        run_git(temp_dir.path(), &["add", "."]);
+        run_git(temp_dir.path(), &["commit", "-m", "initial"]);
+        temp_dir
+    }
+
+    fn staged(path: &Path) -> HashSet<String> {
+        staged_paths(&Repository::open(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_stage_and_unstage_file_round_trip() {
+        let temp_dir = init_repo_with_tracked_files(&["tracked.txt", "gone.txt"]);
+        let path = temp_dir.path();
+        fs::write(path.join("tracked.txt"), "changed\n").unwrap();
+        fs::write(path.join("new.txt"), "new\n").unwrap();
+        fs::remove_file(path.join("gone.txt")).unwrap();
+        assert!(staged(path).is_empty());
+
+        for file in ["tracked.txt", "new.txt", "gone.txt"] {
+            stage_file(path, file).expect("stage file");
+        }
+        assert_eq!(
+            staged(path),
+            HashSet::from(["tracked.txt", "new.txt", "gone.txt"].map(String::from))
+        );
+
+        unstage_file(path, "tracked.txt").expect("unstage tracked file");
+        unstage_file(path, "new.txt").expect("unstage new file");
+        assert_eq!(staged(path), HashSet::from(["gone.txt".to_string()]));
+        assert_eq!(
+            fs::read_to_string(path.join("tracked.txt")).unwrap(),
+            "changed\n",
+            "Unstaging must keep the working tree"
+        );
+        assert!(path.join("new.txt").exists());
+    }
+
+    #[test]
+    fn test_commit_staged_changes_commits_only_the_index() {
+        let temp_dir = init_repo_with_tracked_files(&["a.txt", "b.txt"]);
+        let path = temp_dir.path();
+        fs::write(path.join("a.txt"), "staged\n").unwrap();
+        fs::write(path.join("b.txt"), "unstaged\n").unwrap();
+        stage_file(path, "a.txt").unwrap();
+
+        let oid = commit_staged_changes(path, "Stage a only").expect("commit");
+
+        let repo = Repository::open(path).unwrap();
+        let commit = repo.find_commit(oid).unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(oid));
+        assert_eq!(commit.message(), Some("Stage a only"));
+        let tree = commit.tree().unwrap();
+        let blob_content = |name: &str| {
+            let entry = tree.get_name(name).unwrap();
+            let blob = repo.find_blob(entry.id()).unwrap();
+            String::from_utf8(blob.content().to_vec()).unwrap()
+        };
+        assert_eq!(blob_content("a.txt"), "staged\n");
+        assert_eq!(blob_content("b.txt"), "base\n");
+        assert!(has_uncommitted_changes(path).unwrap());
+
+        let err = commit_staged_changes(path, "Empty").unwrap_err();
+        assert!(err.to_string().contains("Nothing staged"), "{err}");
+        assert_eq!(repo.head().unwrap().target(), Some(oid));
+    }
+
+    #[test]
+    fn test_staging_guard_blocks_internal_paths() {
+        let temp_dir = init_repo_with_tracked_files(&["a.txt"]);
+        let path = temp_dir.path();
+        fs::create_dir_all(path.join(".schaltwerk")).unwrap();
+        fs::write(path.join(".schaltwerk/state.json"), "{}").unwrap();
+        fs::write(path.join("a.txt"), "changed\n").unwrap();
+
+        for rejected in [
+            ".schaltwerk/state.json",
+            ".schaltwerk",
+            "../outside.txt",
+            "/a.txt",
+        ] {
+            assert!(stage_file(path, rejected).is_err(), "{rejected}");
+            assert!(unstage_file(path, rejected).is_err(), "{rejected}");
+        }
+
+        stage_all_changes(path).expect("stage all");
+        assert_eq!(staged(path), HashSet::from(["a.txt".to_string()]));
+    }
 }
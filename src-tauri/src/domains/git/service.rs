@@ -11,22 +11,30 @@ pub use super::branches::{
 #[cfg(test)]
 pub use super::repository::{get_commit_hash, get_current_branch};
 pub use super::worktrees::{
-    create_worktree_for_existing_branch, create_worktree_from_base, create_worktree_from_pr,
-    get_worktree_for_branch, list_worktrees, prune_worktrees, remove_worktree,
-    update_worktree_branch,
+    FileRevertOutcome, StaleHunkError, UnstashOutcome, create_worktree_for_existing_branch,
+    create_worktree_from_base, create_worktree_from_pr, diff_hunk_headers,
+    discard_hunk_in_worktree, drop_worktree_stash, get_worktree_for_branch, list_worktrees,
+    preview_discard_path_in_worktree, prune_worktrees, recreate_worktree_branch, remove_worktree,
+    revert_path_to_commit, stash_worktree_changes, unstash_worktree_changes,
+    update_worktree_branch, worktree_content_hash,
 };
 
 pub use super::history::{
-    CommitFileChange, HistoryProviderSnapshot, get_commit_file_changes, get_git_history,
-    get_git_history_with_head,
+    CommitFileChange, HistoryFilter, HistoryProviderSnapshot, get_commit_file_changes,
+    get_git_history, get_git_history_with_head,
 };
 pub use super::operations::{
     commit_all_changes, has_conflicts, has_uncommitted_changes, is_valid_branch_name,
     is_valid_session_name,
 };
+pub use super::snapshots::{
+    WorktreeSnapshot, create_worktree_snapshot, delete_session_snapshot_refs, delete_snapshot_ref,
+    restore_worktree_snapshot, snapshot_ref_name,
+};
 pub use super::stats::{
-    calculate_git_stats_fast, get_changed_files, get_changed_files_with_mode,
-    has_remote_tracking_branch, DiffCompareMode,
+    DiffCompareMode, calculate_git_stats_fast, calculate_git_stats_incremental,
+    count_commits_ahead_of_parent, fetch_remote_status, get_changed_files,
+    get_changed_files_between_refs, get_changed_files_with_mode, has_remote_tracking_branch,
 };
 #[cfg(test)]
 pub use super::worktrees::is_worktree_registered;
@@ -196,6 +204,51 @@ mod performance_tests {
         assert_eq!(first.files_changed, second.files_changed);
     }
 
+    #[test]
+    fn test_incremental_stats_skip_recompute_when_heads_unchanged() {
+        let (_temp, repo_path, worktree_path) = setup_test_repo_with_many_files(10);
+        let current_branch = get_current_branch(&repo_path).unwrap();
+
+        clear_stats_cache();
+        reset_git_stats_call_count();
+        let _scope = track_git_stats_on_current_thread();
+
+        let first = calculate_git_stats_incremental(&worktree_path, &current_branch, None).unwrap();
+        assert_eq!(get_git_stats_call_count(), 1);
+        assert!(first.head_oid.is_some());
+        assert!(first.parent_oid.is_some());
+
+        let reused =
+            calculate_git_stats_incremental(&worktree_path, &current_branch, Some(&first)).unwrap();
+        assert_eq!(
+            get_git_stats_call_count(),
+            1,
+            "unchanged HEAD and parent tip must not trigger a recompute"
+        );
+        assert_eq!(reused.calculated_at, first.calculated_at);
+        assert_eq!(reused.files_changed, first.files_changed);
+
+        StdCommand::new("git")
+            .args(["add", "."])
+            .current_dir(&worktree_path)
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .args(["commit", "-m", "session work"])
+            .current_dir(&worktree_path)
+            .output()
+            .unwrap();
+
+        let moved =
+            calculate_git_stats_incremental(&worktree_path, &current_branch, Some(&first)).unwrap();
+        assert_eq!(
+            get_git_stats_call_count(),
+            2,
+            "a new worktree HEAD must trigger a recompute"
+        );
+        assert_ne!(moved.head_oid, first.head_oid);
+    }
+
     #[test]
     fn test_fast_version_with_no_changes() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,243 @@
+use anyhow::{Context, Result, anyhow};
+use git2::build::CheckoutBuilder;
+use git2::{IndexAddOption, Oid, Repository, ResetType, StatusOptions};
+use std::path::Path;
+
+/// Snapshot refs live outside `refs/heads` so they never show up as branches, but
+/// still keep the captured commits reachable.
+const SNAPSHOT_REF_PREFIX: &str = "refs/schaltwerk/snapshots";
+
+pub fn snapshot_ref_name(session_name: &str, snapshot_id: &str) -> String {
+    format!("{SNAPSHOT_REF_PREFIX}/{session_name}/{snapshot_id}")
+}
+
+/// What `create_worktree_snapshot` captured: `commit_oid` holds the worktree contents
+/// and has the branch tip `head_oid` as its only parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeSnapshot {
+    pub commit_oid: Oid,
+    pub head_oid: Oid,
+    pub dirty_files: usize,
+}
+
+/// Records the worktree's branch tip plus all uncommitted changes, untracked files
+/// included and ignored files excluded, as a commit on top of the tip and points
+/// `ref_name` at it. The worktree, index and branch are left untouched.
+pub fn create_worktree_snapshot(
+    worktree_path: &Path,
+    ref_name: &str,
+    message: &str,
+) -> Result<WorktreeSnapshot> {
+    let repo = Repository::open(worktree_path)?;
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| anyhow!("Cannot snapshot a worktree without a commit: {e}"))?;
+
+    let mut status_opts = StatusOptions::new();
+    status_opts
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let dirty_files = repo.statuses(Some(&mut status_opts))?.len();
+
+    // Stage everything into the in-memory index only; it is never written back, so the
+    // user's staging area survives.
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let signature = repo.signature()?;
+    let commit_oid = repo.commit(None, &signature, &signature, message, &tree, &[&head])?;
+    repo.reference(ref_name, commit_oid, false, message)
+        .with_context(|| format!("Failed to create snapshot ref {ref_name}"))?;
+
+    log::info!(
+        "Snapshotted {} ({dirty_files} dirty file(s)) as {commit_oid}",
+        worktree_path.display()
+    );
+    Ok(WorktreeSnapshot {
+        commit_oid,
+        head_oid: head.id(),
+        dirty_files,
+    })
+}
+
+/// Hard-resets the worktree's current branch to the tip recorded by the snapshot at
+/// `ref_name`, removes untracked files and checks the captured changes back out as
+/// unstaged changes. Ignored files are kept.
+pub fn restore_worktree_snapshot(worktree_path: &Path, ref_name: &str) -> Result<()> {
+    let repo = Repository::open(worktree_path)?;
+    let snapshot = repo
+        .find_reference(ref_name)
+        .and_then(|reference| reference.peel_to_commit())
+        .map_err(|_| anyhow!("Snapshot {ref_name} no longer exists"))?;
+    let tip = snapshot.parent(0)?;
+
+    repo.reset(tip.as_object(), ResetType::Hard, None)?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force().remove_untracked(true)))?;
+    repo.checkout_tree(snapshot.as_object(), Some(CheckoutBuilder::new().force()))?;
+
+    // checkout_tree staged the snapshot contents; unstage them again
+    let mut index = repo.index()?;
+    index.read_tree(&tip.tree()?)?;
+    index.write()?;
+
+    log::info!(
+        "Restored snapshot {ref_name} in {} at {}",
+        worktree_path.display(),
+        tip.id()
+    );
+    Ok(())
+}
+
+/// Deletes a single snapshot ref. A ref that is already gone is not an error.
+pub fn delete_snapshot_ref(repo_path: &Path, ref_name: &str) -> Result<()> {
+    let repo = Repository::open(repo_path)?;
+    if let Ok(mut reference) = repo.find_reference(ref_name) {
+        reference.delete()?;
+    }
+    Ok(())
+}
+
+/// Deletes every snapshot ref of `session_name` and returns how many were removed.
+pub fn delete_session_snapshot_refs(repo_path: &Path, session_name: &str) -> Result<usize> {
+    let repo = Repository::open(repo_path)?;
+    let names = repo
+        .references_glob(&format!("{SNAPSHOT_REF_PREFIX}/{session_name}/*"))?
+        .names()
+        .map(|name| name.map(str::to_string))
+        .collect::<Result<Vec<_>, _>>()?;
+    for name in &names {
+        repo.find_reference(name)?.delete()?;
+    }
+    Ok(names.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn setup_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path();
+        git(path, &["init"]);
+        git(path, &["config", "user.email", "test@example.com"]);
+        git(path, &["config", "user.name", "Test User"]);
+        fs::write(path.join(".gitignore"), "build/\n").unwrap();
+        fs::write(path.join("a.txt"), "a\n").unwrap();
+        fs::write(path.join("b.txt"), "b\n").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-m", "Initial commit"]);
+        temp
+    }
+
+    #[test]
+    fn snapshot_captures_dirty_files_and_restore_brings_them_back() {
+        let temp = setup_repo();
+        let path = temp.path();
+        let tip = git(path, &["rev-parse", "HEAD"]);
+
+        fs::write(path.join("a.txt"), "a changed\n").unwrap();
+        fs::remove_file(path.join("b.txt")).unwrap();
+        fs::create_dir(path.join("src")).unwrap();
+        fs::write(path.join("src/new.rs"), "fn main() {}\n").unwrap();
+        fs::write(path.join("staged.txt"), "staged\n").unwrap();
+        git(path, &["add", "staged.txt"]);
+        fs::create_dir(path.join("build")).unwrap();
+        fs::write(path.join("build/out.o"), "binary").unwrap();
+        let status_before = git(path, &["status", "--porcelain"]);
+
+        let ref_name = snapshot_ref_name("session", "one");
+        let snapshot = create_worktree_snapshot(path, &ref_name, "Snapshot: before").unwrap();
+
+        assert_eq!(snapshot.dirty_files, 4);
+        assert_eq!(snapshot.head_oid.to_string(), tip);
+        assert_eq!(
+            git(path, &["rev-parse", &ref_name]),
+            snapshot.commit_oid.to_string()
+        );
+        assert_eq!(git(path, &["status", "--porcelain"]), status_before);
+        assert_eq!(
+            git(path, &["ls-tree", "-r", "--name-only", &ref_name]),
+            ".gitignore\na.txt\nsrc/new.rs\nstaged.txt"
+        );
+
+        // The agent commits, edits and litters the worktree
+        fs::write(path.join("a.txt"), "agent\n").unwrap();
+        git(path, &["commit", "-am", "Agent commit"]);
+        fs::write(path.join("b.txt"), "agent b\n").unwrap();
+        fs::write(path.join("junk.txt"), "junk\n").unwrap();
+        fs::remove_file(path.join("src/new.rs")).unwrap();
+
+        restore_worktree_snapshot(path, &ref_name).unwrap();
+
+        assert_eq!(git(path, &["rev-parse", "HEAD"]), tip);
+        assert_eq!(
+            fs::read_to_string(path.join("a.txt")).unwrap(),
+            "a changed\n"
+        );
+        assert!(!path.join("b.txt").exists());
+        assert!(!path.join("junk.txt").exists());
+        assert_eq!(
+            fs::read_to_string(path.join("src/new.rs")).unwrap(),
+            "fn main() {}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("staged.txt")).unwrap(),
+            "staged\n"
+        );
+        assert!(path.join("build/out.o").exists(), "ignored files are kept");
+        assert_eq!(
+            git(path, &["status", "--porcelain"]),
+            " M a.txt\n D b.txt\n?? src/\n?? staged.txt"
+        );
+    }
+
+    #[test]
+    fn deleting_session_refs_only_touches_that_session() {
+        let temp = setup_repo();
+        let path = temp.path();
+        for (session, id) in [("alpha", "1"), ("alpha", "2"), ("beta", "1")] {
+            create_worktree_snapshot(path, &snapshot_ref_name(session, id), "Snapshot").unwrap();
+        }
+
+        assert_eq!(delete_session_snapshot_refs(path, "alpha").unwrap(), 2);
+        assert_eq!(delete_session_snapshot_refs(path, "alpha").unwrap(), 0);
+        assert_eq!(
+            git(
+                path,
+                &["for-each-ref", "--format=%(refname)", SNAPSHOT_REF_PREFIX]
+            ),
+            snapshot_ref_name("beta", "1")
+        );
+
+        delete_snapshot_ref(path, &snapshot_ref_name("beta", "1")).unwrap();
+        delete_snapshot_ref(path, &snapshot_ref_name("beta", "1")).unwrap();
+        assert_eq!(
+            git(
+                path,
+                &["for-each-ref", "--format=%(refname)", SNAPSHOT_REF_PREFIX]
+            ),
+            ""
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::binary_detection::is_binary_file_by_extension;
 use crate::domains::sessions::entity::{ChangedFile, GitStats};
-use anyhow::Result;
+use anyhow::{Context, Result, anyhow};
 use chrono::Utc;
-use git2::{Diff, DiffFindOptions, DiffFormat, DiffOptions, Oid, Repository, StatusOptions};
+use git2::{Diff, DiffFindOptions, DiffFormat, DiffOptions, Oid, Patch, Repository, StatusOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, hash_map::Entry};
 use std::fs;
@@ -212,6 +212,7 @@ pub fn track_git_stats_on_current_thread() -> GitStatsThreadScope {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct StatsCacheKey {
     head: Option<Oid>,
+    parent: Option<Oid>,
     index_signature: Option<u64>,
     status_signature: u64,
 }
@@ -243,6 +244,68 @@ fn is_internal_tooling_path(path: &str) -> bool {
     path == ".schaltwerk" || path.starts_with(".schaltwerk/")
 }
 
+fn resolve_stats_oids(repo: &Repository, parent_branch: &str) -> (Option<Oid>, Option<Oid>) {
+    let head = repo.head().ok().and_then(|h| h.target());
+    let parent = repo
+        .revparse_single(parent_branch)
+        .ok()
+        .and_then(|obj| obj.peel_to_commit().ok())
+        .map(|c| c.id());
+    (head, parent)
+}
+
+/// Returns `previous` untouched when the worktree HEAD, the parent tip and the
+/// `origin/<branch>` tip still match the oids it was computed against; otherwise recomputes via `calculate_git_stats_fast`.
+/// Uncommitted edits do not move either oid, so callers that react to file changes
+/// (the watcher, the activity poller) must keep using the full computation.
+pub fn calculate_git_stats_incremental(
+    worktree_path: &Path,
+    parent_branch: &str,
+    previous: Option<&GitStats>,
+) -> Result<GitStats> {
+    if let Some(previous) = previous
+        && let (Some(prev_head), Some(prev_parent)) =
+            (previous.head_oid.as_deref(), previous.parent_oid.as_deref())
+    {
+        let repo = Repository::open(worktree_path)?;
+        let (head, parent) = resolve_stats_oids(&repo, parent_branch);
+        let remote = remote_tracking_oid(&repo);
+        if head.map(|oid| oid.to_string()).as_deref() == Some(prev_head)
+            && parent.map(|oid| oid.to_string()).as_deref() == Some(prev_parent)
+            && remote.map(|oid| oid.to_string()) == previous.remote_oid
+        {
+            log::debug!(
+                "git_stats: reuse path={} head={} parent={}",
+                worktree_path.display(),
+                prev_head,
+                prev_parent
+            );
+            return Ok(previous.clone());
+        }
+    }
+
+    calculate_git_stats_fast(worktree_path, parent_branch)
+}
+
+/// Totals of a diff as (files, insertions, deletions) from libgit2's `DiffStats`,
+/// leaving out the deltas at `excluded` (internal tooling files). Binary files count
+/// as changed without contributing lines.
+fn diff_line_totals(diff: &Diff, excluded: &[usize]) -> Result<(u32, u32, u32)> {
+    let stats = diff.stats()?;
+    let mut files = stats.files_changed();
+    let mut insertions = stats.insertions();
+    let mut deletions = stats.deletions();
+    for &idx in excluded {
+        files = files.saturating_sub(1);
+        if let Some(patch) = Patch::from_diff(diff, idx)? {
+            let (_, added, removed) = patch.line_stats()?;
+            insertions = insertions.saturating_sub(added);
+            deletions = deletions.saturating_sub(removed);
+        }
+    }
+    Ok((files as u32, insertions as u32, deletions as u32))
+}
+
 pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Result<GitStats> {
     #[cfg(test)]
     increment_git_stats_call_count();
@@ -278,28 +341,28 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
         .include_untracked(true)
         .recurse_untracked_dirs(true);
     let statuses = repo.statuses(Some(&mut status_opts))?;
-    // Compute filtered has_uncommitted: ignore .schaltwerk internal files
-    let has_uncommitted_filtered = statuses.iter().any(|entry| {
-        if let Some(path) = entry.path()
-            && is_internal_tooling_path(path)
-        {
-            return false;
-        }
-        true
-    }) && !statuses.is_empty();
-    // Sample a few offending paths for diagnostics
+    // One pass over the statuses: has_uncommitted ignores .schaltwerk internal files,
+    // a few offending paths are sampled for diagnostics and every entry feeds the
+    // status signature.
+    let mut has_uncommitted_filtered = false;
     let mut sample: Vec<String> = Vec::new();
+    let mut status_sig: u64 = 1469598103934665603;
     for entry in statuses.iter() {
-        if let Some(path) = entry.path()
-            && is_internal_tooling_path(path)
-        {
+        let s = entry.status().bits() as u64;
+        status_sig ^= s.wrapping_mul(1099511628211);
+        let Some(path) = entry.path() else {
+            has_uncommitted_filtered = true;
+            continue;
+        };
+        for b in path.as_bytes() {
+            status_sig ^= (*b as u64).wrapping_mul(1099511628211);
+        }
+        if is_internal_tooling_path(path) {
             continue;
         }
-        if let Some(path) = entry.path() {
+        has_uncommitted_filtered = true;
+        if sample.len() < 5 {
             sample.push(path.to_string());
-            if sample.len() >= 5 {
-                break;
-            }
         }
     }
     log::debug!(
@@ -310,16 +373,6 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
         has_uncommitted_filtered,
         sample
     );
-    let mut status_sig: u64 = 1469598103934665603;
-    for entry in statuses.iter() {
-        let s = entry.status().bits() as u64;
-        status_sig ^= s.wrapping_mul(1099511628211);
-        if let Some(path) = entry.path() {
-            for b in path.as_bytes() {
-                status_sig ^= (*b as u64).wrapping_mul(1099511628211);
-            }
-        }
-    }
 
     let index_signature = repo.index().ok().map(|idx| {
         let mut sig: u64 = 1469598103934665603;
@@ -335,8 +388,12 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
         sig
     });
 
+    let parent_oid = base_commit.as_ref().map(|c| c.id());
+    // Not part of the cache key: the remote ref moves independently of the diff.
+    let remote = remote_status(&repo, head_oid);
     let key = StatsCacheKey {
         head: head_oid,
+        parent: parent_oid,
         index_signature,
         status_signature: status_sig,
     };
@@ -450,6 +507,12 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
             has_uncommitted: has_uncommitted_filtered,
             calculated_at: Utc::now(),
             last_diff_change_ts,
+            head_oid: head_oid.map(|oid| oid.to_string()),
+            parent_oid: parent_oid.map(|oid| oid.to_string()),
+            has_remote: remote.has_remote,
+            remote_ahead: remote.ahead,
+            remote_behind: remote.behind,
+            remote_oid: remote.oid.map(|oid| oid.to_string()),
         });
     }
 
@@ -463,20 +526,26 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
     opts.include_untracked(true)
         .recurse_untracked_dirs(true)
         .show_untracked_content(true)
-        .show_binary(true)
-        .ignore_submodules(true);
+        .ignore_submodules(true)
+        .patience(false)
+        .minimal(false);
 
     if let Some(ref bt) = base_tree
         && let Ok(mut diff) = repo.diff_tree_to_workdir_with_index(Some(bt), Some(&mut opts))
     {
         let mut find_opts = DiffFindOptions::new();
+        find_opts.renames(true);
         diff.find_similar(Some(&mut find_opts)).ok();
 
         let mut delta_count: usize = 0;
-        for delta in diff.deltas() {
+        let mut internal_deltas: Vec<usize> = Vec::new();
+        for (idx, delta) in diff.deltas().enumerate() {
             if let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path())
                 && let Some(path_str) = path.to_str()
             {
+                if is_internal_tooling_path(path_str) {
+                    internal_deltas.push(idx);
+                }
                 files_for_mtime.insert(path_str.to_string());
             }
 
@@ -505,12 +574,10 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
             );
         }
 
-        if let Ok(changed_files) = build_changed_files_from_diff(&diff) {
-            files_changed = changed_files.len() as u32;
-            for file in &changed_files {
-                insertions += file.additions;
-                deletions += file.deletions;
-            }
+        if let Ok((files, added, removed)) = diff_line_totals(&diff, &internal_deltas) {
+            files_changed = files;
+            insertions = added;
+            deletions = removed;
         }
     }
 
@@ -568,6 +635,12 @@ pub fn calculate_git_stats_fast(worktree_path: &Path, parent_branch: &str) -> Re
         has_uncommitted: has_uncommitted_filtered,
         calculated_at: Utc::now(),
         last_diff_change_ts,
+        head_oid: head_oid.map(|oid| oid.to_string()),
+        parent_oid: parent_oid.map(|oid| oid.to_string()),
+        has_remote: remote.has_remote,
+        remote_ahead: remote.ahead,
+        remote_behind: remote.behind,
+        remote_oid: remote.oid.map(|oid| oid.to_string()),
     };
 
     let map = STATS_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
@@ -663,6 +736,50 @@ pub fn get_changed_files_with_mode(
     }
 }
 
+/// Committed changes on `head_ref` since it diverged from `base_ref`, the equivalent of
+/// `git diff base_ref...head_ref`. Refs without a common ancestor are compared directly.
+pub fn get_changed_files_between_refs(
+    repo_path: &Path,
+    base_ref: &str,
+    head_ref: &str,
+) -> Result<Vec<ChangedFile>> {
+    let repo = Repository::open(repo_path)?;
+    let base_commit = repo
+        .revparse_single(base_ref)
+        .with_context(|| format!("Failed to resolve '{base_ref}'"))?
+        .peel_to_commit()?;
+    let head_commit = repo
+        .revparse_single(head_ref)
+        .with_context(|| format!("Failed to resolve '{head_ref}'"))?
+        .peel_to_commit()?;
+
+    let merge_base = repo
+        .merge_base(base_commit.id(), head_commit.id())
+        .unwrap_or(base_commit.id());
+    let base_tree = repo.find_commit(merge_base)?.tree()?;
+    let head_tree = head_commit.tree()?;
+
+    let mut opts = DiffOptions::new();
+    opts.show_binary(true).ignore_submodules(true);
+    let mut diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))?;
+    let mut find_opts = DiffFindOptions::new();
+    diff.find_similar(Some(&mut find_opts))?;
+    build_changed_files_from_diff(&diff)
+}
+
+/// Number of commits on the worktree HEAD that `parent_branch` does not contain.
+pub fn count_commits_ahead_of_parent(worktree_path: &Path, parent_branch: &str) -> Result<u32> {
+    let repo = Repository::open(worktree_path)?;
+    let head = repo.head()?.peel_to_commit()?.id();
+    let parent = repo
+        .revparse_single(parent_branch)
+        .with_context(|| format!("Failed to resolve '{parent_branch}'"))?
+        .peel_to_commit()?
+        .id();
+    let (ahead, _) = repo.graph_ahead_behind(head, parent)?;
+    Ok(ahead as u32)
+}
+
 pub fn has_remote_tracking_branch(worktree_path: &Path, branch_name: &str) -> bool {
     let Ok(repo) = Repository::open(worktree_path) else {
         return false;
@@ -671,6 +788,66 @@ pub fn has_remote_tracking_branch(worktree_path: &Path, branch_name: &str) -> bo
     repo.find_reference(&remote_ref).is_ok()
 }
 
+/// Tip of `origin/<branch>` for the branch checked out in the worktree, if it exists.
+fn remote_tracking_oid(repo: &Repository) -> Option<Oid> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let branch = head.shorthand()?;
+    repo.find_reference(&format!("refs/remotes/origin/{branch}"))
+        .ok()?
+        .target()
+}
+
+#[derive(Default)]
+struct RemoteStatus {
+    has_remote: bool,
+    ahead: Option<u32>,
+    behind: Option<u32>,
+    oid: Option<Oid>,
+}
+
+/// Counts commits of HEAD ahead of and behind `origin/<branch>`. The counts stay
+/// `None` when there is no remote branch to compare against.
+fn remote_status(repo: &Repository, head_oid: Option<Oid>) -> RemoteStatus {
+    let Some(remote_oid) = remote_tracking_oid(repo) else {
+        return RemoteStatus::default();
+    };
+    let counts = head_oid.and_then(|head| repo.graph_ahead_behind(head, remote_oid).ok());
+    RemoteStatus {
+        has_remote: true,
+        ahead: counts.map(|(ahead, _)| ahead as u32),
+        behind: counts.map(|(_, behind)| behind as u32),
+        oid: Some(remote_oid),
+    }
+}
+
+/// Fetches `origin` for the worktree and recomputes its stats so the remote
+/// ahead/behind counts reflect the remote as it is now. Worktrees without an
+/// `origin` remote skip the fetch and report no remote branch.
+pub fn fetch_remote_status(worktree_path: &Path, parent_branch: &str) -> Result<GitStats> {
+    let repo = Repository::open(worktree_path)?;
+    if repo.find_remote("origin").is_ok() {
+        let output = std::process::Command::new("git")
+            .args(["fetch", "origin"])
+            .current_dir(worktree_path)
+            .output()
+            .with_context(|| format!("Failed to run git fetch in {}", worktree_path.display()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("git fetch origin failed: {}", stderr.trim()));
+        }
+    } else {
+        log::debug!(
+            "Skipping fetch for {}: no origin remote configured",
+            worktree_path.display()
+        );
+    }
+
+    calculate_git_stats_fast(worktree_path, parent_branch)
+}
+
 #[cfg(test)]
 pub fn parse_numstat_line(line: &str) -> Option<(u32, u32, &str)> {
     let parts: Vec<&str> = line.split('\t').collect();
@@ -850,6 +1027,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn changed_files_between_refs_use_their_merge_base() {
+        let repo = init_repo();
+        let p = repo.path();
+
+        git(p, &["checkout", "-b", "attempt-a"]);
+        commit_file(p, "a.txt");
+        git(p, &["checkout", "main"]);
+        git(p, &["checkout", "-b", "attempt-b"]);
+        commit_file(p, "b1.txt");
+        commit_file(p, "b2.txt");
+        git(p, &["tag", "v-b"]);
+
+        let files = get_changed_files_between_refs(p, "attempt-a", "attempt-b").unwrap();
+        let mut paths: Vec<_> = files.iter().map(|f| f.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["b1.txt", "b2.txt"]);
+        assert!(files.iter().all(|f| f.change_type == "added"));
+
+        let files = get_changed_files_between_refs(p, "v-b", "attempt-a").unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt"]);
+
+        assert!(get_changed_files_between_refs(p, "main", "no-such-ref").is_err());
+    }
+
     #[test]
     fn changed_files_include_line_stats_and_binary_flag() {
         let repo = init_repo();
@@ -1069,4 +1272,255 @@ mod tests {
             "Should not have remote tracking branch for non-existent branch"
         );
     }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit_file(dir: &Path, name: &str) {
+        fs::write(dir.join(name), format!("{name}\n")).unwrap();
+        git(dir, &["add", name]);
+        git(
+            dir,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=t@example.com",
+                "commit",
+                "-m",
+                name,
+            ],
+        );
+    }
+
+    /// Local repo on a pushed session branch, with a bare repo acting as origin.
+    fn init_repo_with_origin() -> (TempDir, TempDir) {
+        let repo = init_repo();
+        let origin = TempDir::new().unwrap();
+        git(origin.path(), &["init", "--bare"]);
+        let p = repo.path();
+        git(
+            p,
+            &["remote", "add", "origin", origin.path().to_str().unwrap()],
+        );
+        git(p, &["checkout", "-b", "schaltwerk/remote"]);
+        git(p, &["push", "-u", "origin", "schaltwerk/remote"]);
+        (repo, origin)
+    }
+
+    /// Pushes a commit to origin's session branch from a separate clone.
+    fn push_from_other_clone(origin: &Path, name: &str) {
+        let other = TempDir::new().unwrap();
+        let clone = other.path().join("clone");
+        git(
+            other.path(),
+            &[
+                "clone",
+                "-b",
+                "schaltwerk/remote",
+                origin.to_str().unwrap(),
+                "clone",
+            ],
+        );
+        commit_file(&clone, name);
+        git(&clone, &["push", "origin", "schaltwerk/remote"]);
+    }
+
+    #[test]
+    fn remote_status_is_empty_without_origin() {
+        let repo = init_repo();
+        let p = repo.path();
+        git(p, &["checkout", "-b", "schaltwerk/local-only"]);
+        commit_file(p, "local.txt");
+
+        let stats = fetch_remote_status(p, "main").unwrap();
+        assert!(!stats.has_remote);
+        assert_eq!(stats.remote_ahead, None);
+        assert_eq!(stats.remote_behind, None);
+        assert_eq!(stats.remote_oid, None);
+        assert_eq!(stats.files_changed, 1);
+    }
+
+    #[test]
+    fn remote_status_tracks_ahead_behind_and_diverged() {
+        let (repo, origin) = init_repo_with_origin();
+        let p = repo.path();
+
+        let stats = calculate_git_stats_fast(p, "main").unwrap();
+        assert!(stats.has_remote);
+        assert_eq!(
+            (stats.remote_ahead, stats.remote_behind),
+            (Some(0), Some(0))
+        );
+
+        commit_file(p, "ahead.txt");
+        let stats = calculate_git_stats_fast(p, "main").unwrap();
+        assert_eq!(
+            (stats.remote_ahead, stats.remote_behind),
+            (Some(1), Some(0))
+        );
+
+        git(p, &["push", "origin", "schaltwerk/remote"]);
+        push_from_other_clone(origin.path(), "behind.txt");
+
+        // Without a fetch the remote-tracking ref still points at the old tip.
+        let stale = calculate_git_stats_fast(p, "main").unwrap();
+        assert_eq!(
+            (stale.remote_ahead, stale.remote_behind),
+            (Some(0), Some(0))
+        );
+
+        let stats = fetch_remote_status(p, "main").unwrap();
+        assert_eq!(
+            (stats.remote_ahead, stats.remote_behind),
+            (Some(0), Some(1))
+        );
+
+        commit_file(p, "diverged.txt");
+        let stats = calculate_git_stats_fast(p, "main").unwrap();
+        assert_eq!(
+            (stats.remote_ahead, stats.remote_behind),
+            (Some(1), Some(1))
+        );
+    }
+
+    #[test]
+    fn incremental_stats_recompute_when_remote_ref_moves() {
+        let (repo, origin) = init_repo_with_origin();
+        let p = repo.path();
+
+        let first = calculate_git_stats_incremental(p, "main", None).unwrap();
+        assert_eq!(first.remote_behind, Some(0));
+
+        push_from_other_clone(origin.path(), "upstream.txt");
+        git(p, &["fetch", "origin"]);
+
+        let second = calculate_git_stats_incremental(p, "main", Some(&first)).unwrap();
+        assert_ne!(second.calculated_at, first.calculated_at);
+        assert_eq!(second.remote_behind, Some(1));
+    }
+
+    fn git_stdout(dir: &Path, args: &[&str]) -> String {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    #[test]
+    fn stats_match_git_numstat_for_renames_binaries_and_staged_changes() {
+        let repo = init_repo();
+        let p = repo.path();
+
+        let lines: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+        fs::write(p.join("old_name.txt"), &lines).unwrap();
+        fs::write(p.join("edit.txt"), &lines).unwrap();
+        fs::write(p.join("logo.bin"), [0u8, 1, 2, 3, 0, 255]).unwrap();
+        git(p, &["add", "."]);
+        git(p, &["commit", "-m", "fixtures"]);
+
+        git(p, &["checkout", "-b", "feature"]);
+        git(p, &["mv", "old_name.txt", "new_name.txt"]);
+        fs::write(p.join("edit.txt"), lines.replace("line 2\n", "line two\n")).unwrap();
+        git(p, &["add", "-A"]);
+        git(p, &["commit", "-m", "rename and edit"]);
+
+        // Staged: another edit plus a new file
+        fs::write(
+            p.join("edit.txt"),
+            lines
+                .replace("line 2\n", "line two\n")
+                .replace("line 9\n", "line nine\nline nine and a half\n"),
+        )
+        .unwrap();
+        fs::write(p.join("staged.txt"), "a\nb\nc\n").unwrap();
+        git(p, &["add", "edit.txt", "staged.txt"]);
+
+        // Unstaged: a text edit and a binary edit
+        fs::write(p.join("README.md"), "root\nmore\n").unwrap();
+        fs::write(p.join("logo.bin"), [0u8, 9, 9, 9, 0, 255]).unwrap();
+
+        // Internal tooling files never count towards the stats
+        fs::create_dir_all(p.join(".schaltwerk")).unwrap();
+        fs::write(p.join(".schaltwerk/state.json"), "{}\n").unwrap();
+
+        let merge_base = git_stdout(p, &["merge-base", "main", "HEAD"]);
+        let numstat = git_stdout(p, &["diff", "--numstat", "-M", merge_base.trim()]);
+        let parsed: Vec<(u32, u32, &str)> =
+            numstat.lines().filter_map(parse_numstat_line).collect();
+        assert!(
+            parsed.iter().any(|(_, _, path)| path.contains("=>")),
+            "fixture should contain a rename: {numstat}"
+        );
+
+        let stats = calculate_git_stats_fast(p, "main").unwrap();
+        assert_eq!(stats.files_changed, parsed.len() as u32, "{numstat}");
+        assert_eq!(
+            stats.lines_added,
+            parsed.iter().map(|(added, _, _)| added).sum::<u32>(),
+            "{numstat}"
+        );
+        assert_eq!(
+            stats.lines_removed,
+            parsed.iter().map(|(_, removed, _)| removed).sum::<u32>(),
+            "{numstat}"
+        );
+        assert!(stats.has_uncommitted);
+    }
+
+    #[test]
+    fn stats_ignore_uncommitted_internal_files() {
+        let repo = init_repo();
+        let p = repo.path();
+        git(p, &["checkout", "-b", "feature"]);
+
+        fs::create_dir_all(p.join(".schaltwerk")).unwrap();
+        fs::write(p.join(".schaltwerk/state.json"), "{}\n").unwrap();
+
+        let stats = calculate_git_stats_fast(p, "main").unwrap();
+        assert_eq!(
+            (stats.files_changed, stats.lines_added, stats.lines_removed),
+            (0, 0, 0)
+        );
+        assert!(!stats.has_uncommitted);
+    }
+
+    #[test]
+    fn stats_for_many_changed_files_finish_within_generous_bound() {
+        let repo = init_repo();
+        let p = repo.path();
+        for i in 0..300 {
+            fs::write(p.join(format!("file_{i}.txt")), "one\ntwo\nthree\n").unwrap();
+        }
+        git(p, &["add", "."]);
+        git(p, &["commit", "-m", "many files"]);
+        git(p, &["checkout", "-b", "feature"]);
+        for i in 0..300 {
+            fs::write(p.join(format!("file_{i}.txt")), "one\n2\nthree\nfour\n").unwrap();
+        }
+
+        let start = std::time::Instant::now();
+        let stats = calculate_git_stats_fast(p, "main").unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(stats.files_changed, 300);
+        assert_eq!((stats.lines_added, stats.lines_removed), (600, 300));
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "stats took {elapsed:?}"
+        );
+    }
 }
@@ -1,9 +1,11 @@
 use super::{branches::ensure_branch_at_head, repository::get_commit_hash};
 use anyhow::{Context, Result, anyhow};
 use git2::{
-    BranchType, ErrorCode, Oid, Repository, ResetType, WorktreeAddOptions, WorktreePruneOptions,
-    build::CheckoutBuilder,
+    BranchType, Commit, ErrorCode, Oid, Repository, ResetType, StashFlags, StatusOptions, Tree,
+    TreeWalkMode, TreeWalkResult, WorktreeAddOptions, WorktreePruneOptions, build::CheckoutBuilder,
 };
+use serde::Serialize;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
@@ -25,27 +27,25 @@ pub fn discard_path_in_worktree(
     file_path: &Path,
     base_reference: Option<&str>,
 ) -> Result<()> {
+    discard_paths_in_worktree(worktree_path, &[file_path], base_reference)?
+        .into_iter()
+        .next()
+        .unwrap_or(Ok(()))
+}
+
+/// Discards changes for several paths as [`discard_path_in_worktree`] does, opening the
+/// repository and resolving HEAD and the base reference once. Fails as a whole only
+/// when those cannot be read; otherwise returns one result per path, in order.
+pub fn discard_paths_in_worktree(
+    worktree_path: &Path,
+    file_paths: &[&Path],
+    base_reference: Option<&str>,
+) -> Result<Vec<Result<()>>> {
     let repo = Repository::open(worktree_path)?;
 
-    // Build absolute path and ensure it resides within the worktree
     let abs_worktree = worktree_path
         .canonicalize()
         .unwrap_or_else(|_| worktree_path.to_path_buf());
-    let candidate = if file_path.is_absolute() {
-        file_path.to_path_buf()
-    } else {
-        abs_worktree.join(file_path)
-    };
-    let abs_candidate = candidate.canonicalize().unwrap_or(candidate.clone());
-    if !abs_candidate.starts_with(&abs_worktree) {
-        return Err(anyhow!("Refusing to discard path outside of worktree"));
-    }
-
-    // Compute repo-relative pathspec
-    let rel = abs_candidate
-        .strip_prefix(&abs_worktree)
-        .map_err(|_| anyhow!("Failed to compute relative path"))?;
-    let rel_str = rel.to_string_lossy().to_string();
 
     let head_tree = match repo.head() {
         Ok(head) => head
@@ -68,24 +68,62 @@ pub fn discard_path_in_worktree(
         (None, None)
     };
 
+    Ok(file_paths
+        .iter()
+        .map(|file_path| {
+            discard_resolved_path(
+                &repo,
+                &abs_worktree,
+                head_tree.as_ref(),
+                base_commit.as_ref(),
+                base_tree.as_ref(),
+                file_path,
+            )
+        })
+        .collect())
+}
+
+fn discard_resolved_path(
+    repo: &Repository,
+    abs_worktree: &Path,
+    head_tree: Option<&Tree<'_>>,
+    base_commit: Option<&Commit<'_>>,
+    base_tree: Option<&Tree<'_>>,
+    file_path: &Path,
+) -> Result<()> {
+    // Build absolute path and ensure it resides within the worktree
+    let candidate = if file_path.is_absolute() {
+        file_path.to_path_buf()
+    } else {
+        abs_worktree.join(file_path)
+    };
+    let abs_candidate = candidate.canonicalize().unwrap_or(candidate.clone());
+    if !abs_candidate.starts_with(abs_worktree) {
+        return Err(anyhow!("Refusing to discard path outside of worktree"));
+    }
+
+    // Compute repo-relative pathspec
+    let rel = abs_candidate
+        .strip_prefix(abs_worktree)
+        .map_err(|_| anyhow!("Failed to compute relative path"))?;
+    let rel_str = rel.to_string_lossy().to_string();
+
     let tracked_in_head = head_tree
-        .as_ref()
         .map(|tree| tree.get_path(rel).is_ok())
         .unwrap_or(false);
     let tracked_in_base = base_tree
-        .as_ref()
         .map(|tree| tree.get_path(rel).is_ok())
         .unwrap_or(false);
 
     // Prefer restoring from the provided base reference when available.
-    if let Some(commit) = base_commit.as_ref() {
+    if let Some(commit) = base_commit {
         if tracked_in_base {
             repo.reset_default(Some(commit.as_object()), [rel_str.as_str()])
                 .with_context(|| {
                     format!("Failed to reset index for {rel_str} to base reference")
                 })?;
 
-            if let Some(tree) = base_tree.as_ref() {
+            if let Some(tree) = base_tree {
                 let mut builder = CheckoutBuilder::new();
                 builder.force().path(&rel_str).update_index(true);
                 repo.checkout_tree(tree.as_object(), Some(&mut builder))
@@ -96,9 +134,9 @@ pub fn discard_path_in_worktree(
         }
 
         // Base reference does not contain this path: remove it (with optional backups).
-        remove_from_index(&repo, rel)?;
+        remove_from_index(repo, rel)?;
         remove_path_with_optional_backup(
-            &abs_worktree,
+            abs_worktree,
             &abs_candidate,
             rel,
             !tracked_in_head && !tracked_in_base,
@@ -124,9 +162,9 @@ pub fn discard_path_in_worktree(
         repo.checkout_head(Some(&mut builder))
             .with_context(|| format!("Failed to restore {rel_str} from HEAD"))?;
     } else {
-        remove_from_index(&repo, rel)?;
+        remove_from_index(repo, rel)?;
         remove_path_with_optional_backup(
-            &abs_worktree,
+            abs_worktree,
             &abs_candidate,
             rel,
             !tracked_in_head && !tracked_in_base,
@@ -136,6 +174,65 @@ pub fn discard_path_in_worktree(
     Ok(())
 }
 
+/// Unified diff of the changes [`discard_path_in_worktree`] would drop for `file_path`:
+/// from the content it restores (the base reference when it resolves, HEAD otherwise) to
+/// the worktree file. Empty when the file already matches.
+pub fn preview_discard_path_in_worktree(
+    worktree_path: &Path,
+    file_path: &Path,
+    base_reference: Option<&str>,
+) -> Result<String> {
+    if file_path.is_absolute()
+        || file_path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err(anyhow!(
+            "Refusing to preview path outside of worktree: {}",
+            file_path.display()
+        ));
+    }
+
+    let repo = Repository::open(worktree_path)?;
+    let base_oid = match base_reference {
+        Some(branch) => {
+            validate_branch_name(branch)?;
+            resolve_branch_commit_oid(&repo, branch)?
+        }
+        None => None,
+    };
+    let restored = match base_oid.or_else(|| repo.head().ok().and_then(|h| h.target())) {
+        Some(oid) => blob_content_at(&repo, oid, file_path)?,
+        None => Vec::new(),
+    };
+    let current = match fs::read(worktree_path.join(file_path)) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(anyhow!("Failed to read {}: {e}", file_path.display())),
+    };
+    if restored == current {
+        return Ok(String::new());
+    }
+
+    let mut patch =
+        git2::Patch::from_buffers(&restored, Some(file_path), &current, Some(file_path), None)?;
+    Ok(String::from_utf8_lossy(&patch.to_buf()?).into_owned())
+}
+
+/// Content of `file_path` in the tree of `commit_oid`, empty when the path is missing.
+fn blob_content_at(repo: &Repository, commit_oid: Oid, file_path: &Path) -> Result<Vec<u8>> {
+    match repo.find_commit(commit_oid)?.tree()?.get_path(file_path) {
+        Ok(entry) => Ok(entry
+            .to_object(repo)?
+            .into_blob()
+            .map_err(|_| anyhow!("{} is not a file at {commit_oid}", file_path.display()))?
+            .content()
+            .to_vec()),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
 fn remove_path_with_optional_backup(
     abs_worktree: &Path,
     abs_candidate: &Path,
@@ -200,6 +297,228 @@ fn resolve_branch_commit_oid(repo: &Repository, branch: &str) -> Result<Option<O
     Ok(None)
 }
 
+/// Result of reverting a single file to its content at an earlier commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRevertOutcome {
+    pub path: String,
+    pub commit: String,
+    /// Git blob id of the restored content; `None` when the file did not exist at
+    /// that commit and was removed.
+    pub content_hash: Option<String>,
+}
+
+/// Rewrites `file_path` in the worktree to its content at `commit_oid`, leaving the
+/// change unstaged. The commit must be reachable from `branch`. A path missing from
+/// that commit's tree is removed from the worktree.
+pub fn revert_path_to_commit(
+    worktree_path: &Path,
+    file_path: &Path,
+    branch: &str,
+    commit_oid: &str,
+) -> Result<FileRevertOutcome> {
+    if file_path.is_absolute()
+        || file_path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err(anyhow!(
+            "Refusing to revert path outside of worktree: {}",
+            file_path.display()
+        ));
+    }
+
+    let repo = Repository::open(worktree_path)?;
+    let oid = Oid::from_str(commit_oid).map_err(|_| anyhow!("Invalid commit id '{commit_oid}'"))?;
+    let commit = repo
+        .find_commit(oid)
+        .map_err(|_| anyhow!("Commit {commit_oid} not found"))?;
+    let tip = resolve_branch_commit_oid(&repo, branch)?
+        .ok_or_else(|| anyhow!("Branch '{branch}' not found"))?;
+    if tip != oid && !repo.graph_descendant_of(tip, oid)? {
+        return Err(anyhow!(
+            "Commit {commit_oid} is not reachable from branch '{branch}'"
+        ));
+    }
+
+    let abs_path = worktree_path.join(file_path);
+    let rel_str = file_path.to_string_lossy().to_string();
+    let entry = match commit.tree()?.get_path(file_path) {
+        Ok(entry) => entry,
+        Err(e) if e.code() == ErrorCode::NotFound => {
+            if abs_path.is_file() || abs_path.is_symlink() {
+                fs::remove_file(&abs_path)
+                    .with_context(|| format!("Failed to remove {rel_str}"))?;
+            }
+            return Ok(FileRevertOutcome {
+                path: rel_str,
+                commit: oid.to_string(),
+                content_hash: None,
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let blob = entry
+        .to_object(&repo)?
+        .into_blob()
+        .map_err(|_| anyhow!("{rel_str} is not a file at commit {commit_oid}"))?;
+    if entry.filemode() == i32::from(git2::FileMode::Link) {
+        return Err(anyhow!("Reverting symlink {rel_str} is not supported"));
+    }
+    if let Some(parent) = abs_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&abs_path, blob.content()).with_context(|| format!("Failed to write {rel_str}"))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let executable = entry.filemode() == i32::from(git2::FileMode::BlobExecutable);
+        fs::set_permissions(
+            &abs_path,
+            fs::Permissions::from_mode(if executable { 0o755 } else { 0o644 }),
+        )?;
+    }
+
+    Ok(FileRevertOutcome {
+        path: rel_str,
+        commit: oid.to_string(),
+        content_hash: Some(blob.id().to_string()),
+    })
+}
+
+/// Returned when a hunk discard no longer matches the worktree file: it changed after
+/// the diff was computed, so the caller should refresh the diff instead of retrying.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleHunkError {
+    pub path: String,
+}
+
+impl std::fmt::Display for StaleHunkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} changed since its diff was computed", self.path)
+    }
+}
+
+impl std::error::Error for StaleHunkError {}
+
+/// Git blob id of `content`, used to tell whether a worktree file still matches a diff.
+pub fn worktree_content_hash(content: &[u8]) -> Result<String> {
+    Ok(Oid::hash_object(git2::ObjectType::Blob, content)?.to_string())
+}
+
+/// Hunk headers (`@@ -a,b +c,d @@`) of the diff from `old` to `new` with git's default
+/// three lines of context, in file order.
+pub fn diff_hunk_headers(old: &[u8], new: &[u8]) -> Result<Vec<String>> {
+    let patch = git2::Patch::from_buffers(old, None, new, None, None)?;
+    (0..patch.num_hunks())
+        .map(|idx| {
+            let (hunk, _) = patch.hunk(idx)?;
+            Ok(hunk_range_header(hunk.header()))
+        })
+        .collect()
+}
+
+/// Keeps the `@@ ... @@` part of a hunk header, dropping git's function context.
+fn hunk_range_header(header: &[u8]) -> String {
+    let header = String::from_utf8_lossy(header);
+    let header = header.trim();
+    if let Some(ranges) = header.strip_prefix("@@")
+        && let Some(end) = ranges.find("@@")
+    {
+        return format!("@@{}@@", &ranges[..end]);
+    }
+    header.to_string()
+}
+
+/// Reverts one hunk of the diff between the base and the worktree file, leaving the
+/// rest of the file untouched. The base is the merge-base with `base_reference` when
+/// given (as in session diffs) and HEAD otherwise. Fails with [`StaleHunkError`] when
+/// the file no longer hashes to `expected_content_hash` or the hunk is gone.
+pub fn discard_hunk_in_worktree(
+    worktree_path: &Path,
+    file_path: &Path,
+    base_reference: Option<&str>,
+    hunk_header: &str,
+    expected_content_hash: &str,
+) -> Result<()> {
+    if file_path.is_absolute()
+        || file_path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err(anyhow!(
+            "Refusing to discard path outside of worktree: {}",
+            file_path.display()
+        ));
+    }
+
+    let rel_str = file_path.to_string_lossy().to_string();
+    let stale = || {
+        anyhow::Error::new(StaleHunkError {
+            path: rel_str.clone(),
+        })
+    };
+
+    let abs_path = worktree_path.join(file_path);
+    let current = match fs::read(&abs_path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(anyhow!("Failed to read {rel_str}: {e}")),
+    };
+    if worktree_content_hash(&current)? != expected_content_hash {
+        return Err(stale());
+    }
+
+    let repo = Repository::open(worktree_path)?;
+    let head = repo.head().ok().and_then(|h| h.target());
+    let base_oid = match base_reference {
+        Some(branch) => {
+            validate_branch_name(branch)?;
+            let branch_oid = resolve_branch_commit_oid(&repo, branch)?
+                .ok_or_else(|| anyhow!("Branch '{branch}' not found"))?;
+            head.and_then(|h| repo.merge_base(h, branch_oid).ok())
+                .unwrap_or(branch_oid)
+        }
+        None => head.ok_or_else(|| anyhow!("Repository has no HEAD commit"))?,
+    };
+    let base = blob_content_at(&repo, base_oid, file_path)?;
+
+    let patch = git2::Patch::from_buffers(&base, None, &current, None, None)?;
+    let mut target = None;
+    for idx in 0..patch.num_hunks() {
+        let (hunk, _) = patch.hunk(idx)?;
+        if hunk_range_header(hunk.header()) == hunk_header.trim() {
+            target = Some(hunk);
+            break;
+        }
+    }
+    let hunk = target.ok_or_else(stale)?;
+
+    // A zero-length range starts after the given line rather than at it
+    let range = |start: u32, len: u32| {
+        let start = start as usize;
+        let first = if len == 0 { start } else { start - 1 };
+        first..first + len as usize
+    };
+    let old_range = range(hunk.old_start(), hunk.old_lines());
+    let new_range = range(hunk.new_start(), hunk.new_lines());
+
+    let base_lines: Vec<&[u8]> = base.split_inclusive(|b| *b == b'\n').collect();
+    let current_lines: Vec<&[u8]> = current.split_inclusive(|b| *b == b'\n').collect();
+    let mut restored = Vec::with_capacity(current.len());
+    for line in current_lines[..new_range.start]
+        .iter()
+        .chain(&base_lines[old_range])
+        .chain(&current_lines[new_range.end..])
+    {
+        restored.extend_from_slice(line);
+    }
+
+    fs::write(&abs_path, restored).with_context(|| format!("Failed to write {rel_str}"))?;
+    Ok(())
+}
+
 pub fn create_worktree_from_base(
     repo_path: &Path,
     branch_name: &str,
@@ -612,6 +931,48 @@ pub fn update_worktree_branch(worktree_path: &Path, new_branch: &str) -> Result<
     Ok(())
 }
 
+/// Recreates `branch` for a worktree whose branch was deleted behind its back and points
+/// the worktree's HEAD at it. The branch starts at the commit HEAD still resolves to, or
+/// the newest HEAD reflog entry when HEAD is left unborn. The index and working tree are
+/// not touched, so uncommitted changes survive.
+pub fn recreate_worktree_branch(worktree_path: &Path, branch: &str) -> Result<Oid> {
+    let repo = Repository::open(worktree_path)?;
+    if repo.find_branch(branch, BranchType::Local).is_ok() {
+        return Err(anyhow!("Branch '{branch}' already exists"));
+    }
+
+    let target = match repo.head().ok().and_then(|head| head.target()) {
+        Some(oid) => oid,
+        None => {
+            let reflog = repo.reflog("HEAD")?;
+            reflog
+                .get(0)
+                .map(|entry| entry.id_new())
+                .filter(|oid| !oid.is_zero())
+                .ok_or_else(|| {
+                    anyhow!("Cannot recreate branch '{branch}': HEAD is unborn and has no reflog")
+                })?
+        }
+    };
+
+    let commit = repo.find_commit(target)?;
+    let created = repo
+        .branch(branch, &commit, false)
+        .map_err(|e| anyhow!("Failed to create branch '{branch}': {e}"))?;
+    repo.set_head(
+        created
+            .get()
+            .name()
+            .ok_or_else(|| anyhow!("Branch reference has no name"))?,
+    )?;
+
+    log::info!(
+        "Recreated branch '{branch}' at {target} for worktree {}",
+        worktree_path.display()
+    );
+    Ok(target)
+}
+
 fn extract_session_name_from_path(worktree_path: &Path) -> Result<String> {
     worktree_path
         .file_name()
@@ -704,6 +1065,172 @@ pub fn reset_worktree_to_base(worktree_path: &Path, base_branch: &str) -> Result
     Ok(())
 }
 
+/// Hard-resets the worktree and its checked-out branch to `commit` and removes untracked
+/// and ignored files, like [`reset_worktree_to_base`].
+pub fn reset_worktree_to_commit(worktree_path: &Path, commit: Oid) -> Result<()> {
+    let repo = Repository::open(worktree_path)?;
+
+    if !repo.is_worktree() {
+        return Err(anyhow!("Target repository is not a git worktree"));
+    }
+
+    let target_obj = repo.find_object(commit, None)?;
+    repo.reset(&target_obj, ResetType::Hard, None)?;
+    repo.checkout_head(Some(
+        CheckoutBuilder::new()
+            .force()
+            .remove_untracked(true)
+            .remove_ignored(true),
+    ))?;
+
+    log::info!(
+        "Reset worktree at {} to commit {commit}",
+        worktree_path.display()
+    );
+    Ok(())
+}
+
+/// Result of restoring a stash into a worktree. When `applied` is false nothing was
+/// touched and `conflicting_paths` lists the files that blocked the restore.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnstashOutcome {
+    pub applied: bool,
+    pub conflicting_paths: Vec<String>,
+}
+
+/// Stash all local changes in a worktree, including untracked files, and return the
+/// stash commit id. Fails when there is nothing to stash.
+pub fn stash_worktree_changes(worktree_path: &Path, message: &str) -> Result<Oid> {
+    let mut repo = Repository::open(worktree_path)?;
+    let sig = repo.signature()?;
+
+    match repo.stash_save(&sig, message, Some(StashFlags::INCLUDE_UNTRACKED)) {
+        Ok(oid) => {
+            log::info!("Stashed changes in {} as {oid}", worktree_path.display());
+            Ok(oid)
+        }
+        Err(e) if e.code() == ErrorCode::NotFound => Err(anyhow!("No local changes to stash")),
+        Err(e) => Err(anyhow!("Failed to stash changes: {e}")),
+    }
+}
+
+/// Restore a stash into a worktree and drop it. The stash is only applied when none of
+/// its paths conflict with the current branch or local changes, so a refused restore
+/// leaves both the worktree and the stash untouched.
+pub fn unstash_worktree_changes(worktree_path: &Path, stash_oid: Oid) -> Result<UnstashOutcome> {
+    let mut repo = Repository::open(worktree_path)?;
+    let index = find_stash_index(&mut repo, stash_oid)?
+        .ok_or_else(|| anyhow!("Stash {stash_oid} no longer exists"))?;
+
+    let conflicting_paths = {
+        let stash = repo.find_commit(stash_oid)?;
+        stash_conflicting_paths(&repo, worktree_path, &stash)?
+    };
+    if !conflicting_paths.is_empty() {
+        return Ok(UnstashOutcome {
+            applied: false,
+            conflicting_paths,
+        });
+    }
+
+    repo.stash_apply(index, None)
+        .map_err(|e| anyhow!("Failed to apply stash: {e}"))?;
+    repo.stash_drop(index)?;
+
+    log::info!("Restored stash {stash_oid} in {}", worktree_path.display());
+    Ok(UnstashOutcome {
+        applied: true,
+        conflicting_paths: Vec::new(),
+    })
+}
+
+/// Drop a stash by commit id. A stash that is already gone is not an error.
+pub fn drop_worktree_stash(worktree_path: &Path, stash_oid: Oid) -> Result<()> {
+    let mut repo = Repository::open(worktree_path)?;
+    if let Some(index) = find_stash_index(&mut repo, stash_oid)? {
+        repo.stash_drop(index)?;
+    }
+    Ok(())
+}
+
+// Stashes live in the shared refs/stash of the main repository, so the position of a
+// session's stash shifts as other worktrees stash; resolve it by commit id instead.
+fn find_stash_index(repo: &mut Repository, stash_oid: Oid) -> Result<Option<usize>> {
+    let mut found = None;
+    repo.stash_foreach(|index, _message, oid| {
+        if *oid == stash_oid {
+            found = Some(index);
+            false
+        } else {
+            true
+        }
+    })?;
+    Ok(found)
+}
+
+fn stash_conflicting_paths(
+    repo: &Repository,
+    worktree_path: &Path,
+    stash: &Commit<'_>,
+) -> Result<Vec<String>> {
+    let base_tree = stash.parent(0)?.tree()?;
+    let stash_tree = stash.tree()?;
+    let head_tree = repo.head()?.peel_to_tree()?;
+    let mut conflicts = BTreeSet::new();
+
+    // Stashed edits that clash with commits made on the branch since stashing
+    let merged = repo.merge_trees(&base_tree, &head_tree, &stash_tree, None)?;
+    if merged.has_conflicts() {
+        for conflict in merged.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.their.or(conflict.our).or(conflict.ancestor) {
+                conflicts.insert(String::from_utf8_lossy(&entry.path).to_string());
+            }
+        }
+    }
+
+    // Stashed edits that would overwrite local changes made since stashing
+    let mut status_opts = StatusOptions::new();
+    status_opts
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+    let dirty: BTreeSet<String> = repo
+        .statuses(Some(&mut status_opts))?
+        .iter()
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect();
+
+    let diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&stash_tree), None)?;
+    for delta in diff.deltas() {
+        for file in [delta.old_file(), delta.new_file()] {
+            if let Some(path) = file.path().and_then(Path::to_str)
+                && dirty.contains(path)
+            {
+                conflicts.insert(path.to_string());
+            }
+        }
+    }
+
+    // Stashed untracked files whose path is taken again in the worktree
+    if stash.parent_count() > 2 {
+        let untracked_tree = stash.parent(2)?.tree()?;
+        untracked_tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() != Some(git2::ObjectType::Tree)
+                && let Some(name) = entry.name()
+            {
+                let path = format!("{dir}{name}");
+                if worktree_path.join(&path).exists() {
+                    conflicts.insert(path);
+                }
+            }
+            TreeWalkResult::Ok
+        })?;
+    }
+
+    Ok(conflicts.into_iter().collect())
+}
+
 #[cfg(test)]
 mod unit_logic_tests {
 
@@ -907,3 +1434,476 @@ fn validate_branch_name(name: &str) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod stash_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn init_repo_with_file(dir: &Path) -> Repository {
+        let repo = Repository::init(dir).unwrap();
+        {
+            let mut cfg = repo.config().unwrap();
+            cfg.set_str("user.name", "Test").unwrap();
+            cfg.set_str("user.email", "test@example.com").unwrap();
+        }
+        std::fs::write(dir.join("a.txt"), "base\n").unwrap();
+        commit_all(&repo, "init");
+        repo
+    }
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents = match repo.head() {
+            Ok(head) => vec![head.peel_to_commit().unwrap()],
+            Err(_) => Vec::new(),
+        };
+        let parent_refs: Vec<&Commit<'_>> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+    }
+
+    #[test]
+    fn stash_and_unstash_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let _repo = init_repo_with_file(tmp.path());
+        std::fs::write(tmp.path().join("a.txt"), "edited\n").unwrap();
+        std::fs::write(tmp.path().join("new.txt"), "untracked\n").unwrap();
+
+        let oid = stash_worktree_changes(tmp.path(), "wip").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "base\n"
+        );
+        assert!(!tmp.path().join("new.txt").exists());
+        assert!(stash_worktree_changes(tmp.path(), "nothing").is_err());
+
+        let outcome = unstash_worktree_changes(tmp.path(), oid).unwrap();
+        assert!(outcome.applied);
+        assert!(outcome.conflicting_paths.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "edited\n"
+        );
+        assert!(tmp.path().join("new.txt").exists());
+
+        let mut repo = Repository::open(tmp.path()).unwrap();
+        assert_eq!(find_stash_index(&mut repo, oid).unwrap(), None);
+    }
+
+    #[test]
+    fn conflicting_unstash_leaves_worktree_and_stash_untouched() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo_with_file(tmp.path());
+        std::fs::write(tmp.path().join("a.txt"), "stashed\n").unwrap();
+        std::fs::write(tmp.path().join("b.txt"), "stashed untracked\n").unwrap();
+        let oid = stash_worktree_changes(tmp.path(), "wip").unwrap();
+
+        std::fs::write(tmp.path().join("a.txt"), "committed\n").unwrap();
+        commit_all(&repo, "diverge");
+        std::fs::write(tmp.path().join("b.txt"), "local\n").unwrap();
+
+        let outcome = unstash_worktree_changes(tmp.path(), oid).unwrap();
+        assert!(!outcome.applied);
+        assert_eq!(
+            outcome.conflicting_paths,
+            vec!["a.txt".to_string(), "b.txt".to_string()]
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "committed\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("b.txt")).unwrap(),
+            "local\n"
+        );
+
+        let mut repo = Repository::open(tmp.path()).unwrap();
+        assert_eq!(find_stash_index(&mut repo, oid).unwrap(), Some(0));
+        drop_worktree_stash(tmp.path(), oid).unwrap();
+        assert_eq!(find_stash_index(&mut repo, oid).unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod revert_to_commit_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit_all(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents = match repo.head() {
+            Ok(head) => vec![head.peel_to_commit().unwrap()],
+            Err(_) => Vec::new(),
+        };
+        let parent_refs: Vec<&Commit<'_>> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    fn init_repo(dir: &Path) -> Repository {
+        let repo = Repository::init(dir).unwrap();
+        {
+            let mut cfg = repo.config().unwrap();
+            cfg.set_str("user.name", "Test").unwrap();
+            cfg.set_str("user.email", "test@example.com").unwrap();
+        }
+        repo
+    }
+
+    fn head_branch(repo: &Repository) -> String {
+        repo.head().unwrap().shorthand().unwrap().to_string()
+    }
+
+    #[test]
+    fn reverts_text_file_and_leaves_it_unstaged() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        std::fs::write(tmp.path().join("a.txt"), "v1\n").unwrap();
+        let first = commit_all(&repo, "v1");
+        std::fs::write(tmp.path().join("a.txt"), "v2\n").unwrap();
+        commit_all(&repo, "v2");
+
+        let outcome = revert_path_to_commit(
+            tmp.path(),
+            Path::new("a.txt"),
+            &head_branch(&repo),
+            &first.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "v1\n"
+        );
+        let expected = Oid::hash_object(git2::ObjectType::Blob, b"v1\n").unwrap();
+        assert_eq!(outcome.content_hash, Some(expected.to_string()));
+        let status = repo.status_file(Path::new("a.txt")).unwrap();
+        assert_eq!(status, git2::Status::WT_MODIFIED);
+    }
+
+    #[test]
+    fn reverts_binary_file_byte_for_byte() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        let original = vec![0u8, 159, 146, 150, 0, 255];
+        std::fs::write(tmp.path().join("img.bin"), &original).unwrap();
+        let first = commit_all(&repo, "binary");
+        std::fs::write(tmp.path().join("img.bin"), [1u8, 2, 3]).unwrap();
+        commit_all(&repo, "changed");
+
+        revert_path_to_commit(
+            tmp.path(),
+            Path::new("img.bin"),
+            &head_branch(&repo),
+            &first.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(std::fs::read(tmp.path().join("img.bin")).unwrap(), original);
+    }
+
+    #[test]
+    fn removes_file_missing_at_commit() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        std::fs::write(tmp.path().join("keep.txt"), "keep\n").unwrap();
+        let first = commit_all(&repo, "init");
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src/new.txt"), "later\n").unwrap();
+        commit_all(&repo, "add new");
+
+        let outcome = revert_path_to_commit(
+            tmp.path(),
+            Path::new("src/new.txt"),
+            &head_branch(&repo),
+            &first.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.content_hash, None);
+        assert!(!tmp.path().join("src/new.txt").exists());
+        assert!(tmp.path().join("keep.txt").exists());
+    }
+
+    #[test]
+    fn rejects_invalid_or_unreachable_commits_and_escaping_paths() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        std::fs::write(tmp.path().join("a.txt"), "v1\n").unwrap();
+        let first = commit_all(&repo, "v1");
+        let branch = head_branch(&repo);
+
+        // A commit on another branch is not part of the session's history
+        let other = {
+            let sig = repo.signature().unwrap();
+            let tree = repo.find_commit(first).unwrap().tree().unwrap();
+            repo.commit(None, &sig, &sig, "dangling", &tree, &[])
+                .unwrap()
+        };
+
+        let path = Path::new("a.txt");
+        assert!(revert_path_to_commit(tmp.path(), path, &branch, "not-a-sha").is_err());
+        assert!(revert_path_to_commit(tmp.path(), path, &branch, &"0".repeat(40)).is_err());
+        assert!(revert_path_to_commit(tmp.path(), path, &branch, &other.to_string()).is_err());
+        assert!(
+            revert_path_to_commit(
+                tmp.path(),
+                Path::new("../outside.txt"),
+                &branch,
+                &first.to_string()
+            )
+            .is_err()
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "v1\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod discard_hunk_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents = match repo.head() {
+            Ok(head) => vec![head.peel_to_commit().unwrap()],
+            Err(_) => Vec::new(),
+        };
+        let parent_refs: Vec<&Commit<'_>> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+    }
+
+    fn init_repo(dir: &Path) -> Repository {
+        let repo = Repository::init(dir).unwrap();
+        {
+            let mut cfg = repo.config().unwrap();
+            cfg.set_str("user.name", "Test").unwrap();
+            cfg.set_str("user.email", "test@example.com").unwrap();
+            cfg.set_bool("core.autocrlf", false).unwrap();
+        }
+        repo
+    }
+
+    fn numbered_lines(eol: &str, edits: &[(usize, &str)]) -> String {
+        (1..=20)
+            .map(|n| {
+                let line = edits
+                    .iter()
+                    .find(|(at, _)| *at == n)
+                    .map(|(_, text)| text.to_string())
+                    .unwrap_or_else(|| format!("line {n}"));
+                format!("{line}{eol}")
+            })
+            .collect()
+    }
+
+    fn hash_of(path: &Path) -> String {
+        worktree_content_hash(&std::fs::read(path).unwrap()).unwrap()
+    }
+
+    /// The diff text from its first hunk on, leaving out the file headers.
+    fn hunks(diff: &str) -> &str {
+        &diff[diff.find("@@").expect("diff has a hunk")..]
+    }
+
+    #[test]
+    fn discard_preview_matches_the_working_tree_diff() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        let file = tmp.path().join("notes.txt");
+        std::fs::write(&file, numbered_lines("\n", &[])).unwrap();
+        commit_all(&repo, "base");
+        std::fs::write(&file, numbered_lines("\n", &[(3, "edited"), (15, "also")])).unwrap();
+
+        let head_tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let mut opts = git2::DiffOptions::new();
+        opts.pathspec("notes.txt");
+        let diff = repo
+            .diff_tree_to_workdir(Some(&head_tree), Some(&mut opts))
+            .unwrap();
+        let mut patch = git2::Patch::from_diff(&diff, 0).unwrap().unwrap();
+        let expected = String::from_utf8(patch.to_buf().unwrap().to_vec()).unwrap();
+
+        let preview =
+            preview_discard_path_in_worktree(tmp.path(), Path::new("notes.txt"), None).unwrap();
+        assert_eq!(hunks(&preview), hunks(&expected));
+        assert!(preview.contains("-line 3\n+edited\n"));
+    }
+
+    #[test]
+    fn discard_preview_covers_committed_changes_and_is_empty_once_discarded() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        let file = tmp.path().join("notes.txt");
+        std::fs::write(&file, numbered_lines("\n", &[])).unwrap();
+        commit_all(&repo, "base");
+        let parent = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("session", &head, false).unwrap();
+        repo.set_head("refs/heads/session").unwrap();
+        std::fs::write(&file, numbered_lines("\n", &[(10, "committed")])).unwrap();
+        commit_all(&repo, "session change");
+        std::fs::write(
+            &file,
+            numbered_lines("\n", &[(2, "early"), (10, "committed")]),
+        )
+        .unwrap();
+
+        let preview =
+            preview_discard_path_in_worktree(tmp.path(), Path::new("notes.txt"), Some(&parent))
+                .unwrap();
+        assert!(preview.contains("-line 2\n+early\n"));
+        assert!(preview.contains("-line 10\n+committed\n"));
+
+        discard_path_in_worktree(tmp.path(), Path::new("notes.txt"), Some(&parent)).unwrap();
+        assert_eq!(
+            preview_discard_path_in_worktree(tmp.path(), Path::new("notes.txt"), Some(&parent))
+                .unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn discard_preview_of_clean_file_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        std::fs::write(tmp.path().join("notes.txt"), numbered_lines("\n", &[])).unwrap();
+        commit_all(&repo, "base");
+
+        let preview =
+            preview_discard_path_in_worktree(tmp.path(), Path::new("notes.txt"), None).unwrap();
+        assert_eq!(preview, "");
+        assert!(
+            preview_discard_path_in_worktree(tmp.path(), Path::new("../notes.txt"), None).is_err()
+        );
+    }
+
+    #[test]
+    fn discards_only_the_middle_hunk_against_the_merge_base() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        let file = tmp.path().join("notes.txt");
+        std::fs::write(&file, numbered_lines("\n", &[])).unwrap();
+        commit_all(&repo, "base");
+        let parent = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("session", &head, false).unwrap();
+        repo.set_head("refs/heads/session").unwrap();
+        std::fs::write(&file, numbered_lines("\n", &[(10, "committed")])).unwrap();
+        commit_all(&repo, "session change");
+        let edits = [(2, "early"), (10, "committed"), (18, "late")];
+        std::fs::write(&file, numbered_lines("\n", &edits)).unwrap();
+
+        let base = numbered_lines("\n", &[]);
+        let current = std::fs::read(&file).unwrap();
+        let headers = diff_hunk_headers(base.as_bytes(), &current).unwrap();
+        assert_eq!(headers.len(), 3);
+
+        discard_hunk_in_worktree(
+            tmp.path(),
+            Path::new("notes.txt"),
+            Some(&parent),
+            &headers[1],
+            &hash_of(&file),
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            numbered_lines("\n", &[(2, "early"), (18, "late")])
+        );
+    }
+
+    #[test]
+    fn rejects_discard_when_file_changed_since_diff() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        let file = tmp.path().join("notes.txt");
+        std::fs::write(&file, numbered_lines("\n", &[])).unwrap();
+        commit_all(&repo, "base");
+
+        std::fs::write(&file, numbered_lines("\n", &[(5, "edited")])).unwrap();
+        let headers = diff_hunk_headers(
+            numbered_lines("\n", &[]).as_bytes(),
+            &std::fs::read(&file).unwrap(),
+        )
+        .unwrap();
+        let stale_hash = hash_of(&file);
+
+        let newer = numbered_lines("\n", &[(5, "edited"), (6, "again")]);
+        std::fs::write(&file, &newer).unwrap();
+        let err = discard_hunk_in_worktree(
+            tmp.path(),
+            Path::new("notes.txt"),
+            None,
+            &headers[0],
+            &stale_hash,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<StaleHunkError>(),
+            Some(&StaleHunkError {
+                path: "notes.txt".to_string()
+            })
+        );
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), newer);
+    }
+
+    #[test]
+    fn preserves_crlf_line_endings() {
+        let tmp = TempDir::new().unwrap();
+        let repo = init_repo(tmp.path());
+        let file = tmp.path().join("windows.txt");
+        let base = numbered_lines("\r\n", &[]);
+        std::fs::write(&file, &base).unwrap();
+        commit_all(&repo, "base");
+
+        std::fs::write(
+            &file,
+            numbered_lines("\r\n", &[(3, "first"), (17, "second")]),
+        )
+        .unwrap();
+        let headers = diff_hunk_headers(base.as_bytes(), &std::fs::read(&file).unwrap()).unwrap();
+        assert_eq!(headers.len(), 2);
+
+        discard_hunk_in_worktree(
+            tmp.path(),
+            Path::new("windows.txt"),
+            None,
+            &headers[0],
+            &hash_of(&file),
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            numbered_lines("\r\n", &[(17, "second")])
+        );
+    }
+}
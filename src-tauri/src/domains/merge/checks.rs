@@ -0,0 +1,106 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+
+use anyhow::{Context, Result, anyhow};
+
+/// Lines of check output kept for the failure message.
+const CAPTURED_OUTPUT_LINES: usize = 200;
+
+/// Outcome of a pre-merge check run in a session worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeCheckResult {
+    pub command: String,
+    /// `None` when the process was terminated by a signal.
+    pub exit_code: Option<i32>,
+    /// Trailing stdout and stderr lines, interleaved in arrival order.
+    pub output: String,
+}
+
+impl MergeCheckResult {
+    pub fn passed(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Runs `command` through `sh -c` in `cwd`, handing every stdout and stderr line to
+/// `on_output` as it arrives.
+pub fn run_merge_check(
+    command: &str,
+    cwd: &Path,
+    env: &HashMap<String, String>,
+    on_output: &dyn Fn(&str),
+) -> Result<MergeCheckResult> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .current_dir(cwd)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start pre-merge check `{command}`"))?;
+
+    let (sender, receiver) = mpsc::channel();
+    let readers = [
+        child
+            .stdout
+            .take()
+            .map(|out| forward_lines(out, sender.clone())),
+        child
+            .stderr
+            .take()
+            .map(|err| forward_lines(err, sender.clone())),
+    ];
+    drop(sender);
+
+    let mut captured = VecDeque::with_capacity(CAPTURED_OUTPUT_LINES);
+    for line in receiver {
+        on_output(&line);
+        if captured.len() == CAPTURED_OUTPUT_LINES {
+            captured.pop_front();
+        }
+        captured.push_back(line);
+    }
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for pre-merge check `{command}`"))?;
+    Ok(MergeCheckResult {
+        command: command.to_string(),
+        exit_code: status.code(),
+        output: Vec::from(captured).join("\n"),
+    })
+}
+
+fn forward_lines(
+    stream: impl Read + Send + 'static,
+    sender: mpsc::Sender<String>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Turns a failed check into the error that aborts the merge.
+pub fn merge_check_error(session_name: &str, result: &MergeCheckResult) -> anyhow::Error {
+    let status = match result.exit_code {
+        Some(code) => format!("exited with code {code}"),
+        None => "was terminated".to_string(),
+    };
+    anyhow!(
+        "Pre-merge check `{}` {status}; session '{session_name}' was not merged.\n{}",
+        result.command,
+        result.output
+    )
+}
@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow, bail};
+use git2::{Oid, Repository, Sort};
+use log::{debug, info};
+
+use crate::domains::merge::service::{
+    collect_conflicting_paths, fast_forward_branch, resolve_branch_oid,
+};
+use crate::domains::merge::types::{CherryPickConflict, CherryPickOutcome, SessionCommit};
+
+fn unique_commit_oids(repo: &Repository, session_oid: Oid, parent_oid: Oid) -> Result<Vec<Oid>> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    revwalk.push(session_oid)?;
+    revwalk.hide(parent_oid)?;
+    Ok(revwalk.collect::<Result<_, _>>()?)
+}
+
+/// Commits on `session_branch` that `parent_branch` does not contain, oldest first.
+pub fn unique_session_commits(
+    repo: &Repository,
+    session_branch: &str,
+    parent_branch: &str,
+) -> Result<Vec<SessionCommit>> {
+    let session_oid = resolve_branch_oid(repo, session_branch)?;
+    let parent_oid = resolve_branch_oid(repo, parent_branch)?;
+    unique_commit_oids(repo, session_oid, parent_oid)?
+        .into_iter()
+        .map(|oid| {
+            let commit = repo.find_commit(oid)?;
+            Ok(SessionCommit {
+                oid: oid.to_string(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                author: commit.author().name().unwrap_or_default().to_string(),
+                timestamp: commit.author().when().seconds(),
+            })
+        })
+        .collect()
+}
+
+/// Applies the selected session commits onto `parent_branch`, in the order they appear
+/// on the session branch. Every pick is built as a detached commit first; the parent
+/// branch only moves once all of them applied cleanly, so a conflict leaves it as it was.
+pub fn cherry_pick_session_commits(
+    repo_path: &Path,
+    session_branch: &str,
+    parent_branch: &str,
+    commit_oids: &[String],
+) -> Result<CherryPickOutcome> {
+    if commit_oids.is_empty() {
+        bail!("Select at least one commit to cherry-pick");
+    }
+
+    let repo = Repository::open(repo_path)
+        .with_context(|| format!("Failed to open git repository at {}", repo_path.display()))?;
+    let session_oid = resolve_branch_oid(&repo, session_branch)?;
+    let parent_oid = resolve_branch_oid(&repo, parent_branch)?;
+    let unique = unique_commit_oids(&repo, session_oid, parent_oid)?;
+
+    let mut selected = HashSet::new();
+    for raw in commit_oids {
+        let oid = repo
+            .revparse_single(raw.trim())
+            .and_then(|object| object.peel_to_commit())
+            .map(|commit| commit.id())
+            .map_err(|_| anyhow!("Unknown commit '{raw}'"))?;
+        if !unique.contains(&oid) {
+            bail!(
+                "Commit '{raw}' is not one of the commits '{session_branch}' adds to '{parent_branch}'"
+            );
+        }
+        selected.insert(oid);
+    }
+
+    let signature = repo.signature().ok();
+    let mut tip = repo.find_commit(parent_oid)?;
+    let mut picked_commits = Vec::new();
+    for oid in unique.into_iter().filter(|oid| selected.contains(oid)) {
+        let commit = repo.find_commit(oid)?;
+        if commit.parent_count() != 1 {
+            bail!("Cannot cherry-pick merge commit {oid}");
+        }
+
+        let mut index = repo.cherrypick_commit(&commit, &tip, 0, None)?;
+        if index.has_conflicts() {
+            let conflicting_paths = collect_conflicting_paths(&index)?;
+            info!(
+                "Cherry-pick of {oid} onto '{parent_branch}' conflicts in {}; parent branch left untouched",
+                conflicting_paths.join(", ")
+            );
+            return Ok(CherryPickOutcome {
+                session_branch: session_branch.to_string(),
+                parent_branch: parent_branch.to_string(),
+                new_parent_oid: None,
+                picked_commits: Vec::new(),
+                conflict: Some(CherryPickConflict {
+                    commit: oid.to_string(),
+                    summary: commit.summary().unwrap_or_default().to_string(),
+                    conflicting_paths,
+                }),
+            });
+        }
+
+        let tree_id = index.write_tree_to(&repo)?;
+        if tree_id == tip.tree_id() {
+            debug!("Skipping cherry-pick of {oid}: its changes are already on '{parent_branch}'");
+            continue;
+        }
+        let tree = repo.find_tree(tree_id)?;
+        let committer = signature
+            .clone()
+            .unwrap_or_else(|| commit.committer().to_owned());
+        let new_oid = repo.commit(
+            None,
+            &commit.author(),
+            &committer,
+            commit.message().unwrap_or_default(),
+            &tree,
+            &[&tip],
+        )?;
+        tip = repo.find_commit(new_oid)?;
+        picked_commits.push(oid.to_string());
+    }
+
+    fast_forward_branch(&repo, parent_branch, tip.id())?;
+    info!(
+        "Cherry-picked {} commit(s) from '{session_branch}' onto '{parent_branch}' at {}",
+        picked_commits.len(),
+        tip.id()
+    );
+
+    Ok(CherryPickOutcome {
+        session_branch: session_branch.to_string(),
+        parent_branch: parent_branch.to_string(),
+        new_parent_oid: Some(tip.id().to_string()),
+        picked_commits,
+        conflict: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn commit_file(path: &Path, name: &str, contents: &str, message: &str) -> String {
+        std::fs::write(path.join(name), contents).unwrap();
+        git(path, &["add", name]);
+        git(path, &["commit", "-m", message]);
+        git(path, &["rev-parse", "HEAD"])
+    }
+
+    /// Repository on `main` with a `session` branch holding the returned commits.
+    fn repo_with_session(temp: &TempDir, session_files: &[(&str, &str)]) -> Vec<String> {
+        let path = temp.path();
+        git(path, &["init"]);
+        git(path, &["config", "user.email", "test@example.com"]);
+        git(path, &["config", "user.name", "Test User"]);
+        commit_file(path, "README.md", "initial\n", "Initial commit");
+        git(path, &["branch", "-M", "main"]);
+
+        git(path, &["checkout", "-b", "session"]);
+        let oids = session_files
+            .iter()
+            .map(|(name, contents)| commit_file(path, name, contents, &format!("Add {name}")))
+            .collect();
+        git(path, &["checkout", "main"]);
+        oids
+    }
+
+    #[test]
+    fn picks_selected_commits_onto_parent() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path();
+        let oids = repo_with_session(
+            &temp,
+            &[("a.txt", "a\n"), ("b.txt", "b\n"), ("c.txt", "c\n")],
+        );
+        let main_before = git(path, &["rev-parse", "main"]);
+
+        let repo = Repository::open(path).unwrap();
+        let commits = unique_session_commits(&repo, "session", "main").unwrap();
+        assert_eq!(
+            commits.iter().map(|c| c.oid.clone()).collect::<Vec<_>>(),
+            oids
+        );
+        assert_eq!(commits[0].summary, "Add a.txt");
+
+        let outcome = cherry_pick_session_commits(
+            path,
+            "session",
+            "main",
+            &[oids[2].clone(), oids[0].clone()],
+        )
+        .unwrap();
+
+        assert!(outcome.conflict.is_none());
+        assert_eq!(
+            outcome.picked_commits,
+            vec![oids[0].clone(), oids[2].clone()]
+        );
+        let main_after = git(path, &["rev-parse", "main"]);
+        assert_eq!(outcome.new_parent_oid.as_deref(), Some(main_after.as_str()));
+        assert_eq!(
+            git(
+                path,
+                &["log", "--format=%s", &format!("{main_before}..main")]
+            ),
+            "Add c.txt\nAdd a.txt"
+        );
+        assert_eq!(
+            git(path, &["ls-tree", "--name-only", "main"]),
+            "README.md\na.txt\nc.txt"
+        );
+        assert!(path.join("c.txt").exists(), "checked out parent is updated");
+        assert!(!path.join("b.txt").exists());
+
+        let err = cherry_pick_session_commits(path, "session", "main", &[main_before]).unwrap_err();
+        assert!(
+            err.to_string().contains("is not one of the commits"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn conflict_mid_sequence_leaves_parent_untouched() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path();
+        let oids = repo_with_session(
+            &temp,
+            &[
+                ("a.txt", "a\n"),
+                ("README.md", "session\n"),
+                ("c.txt", "c\n"),
+            ],
+        );
+        commit_file(path, "README.md", "main\n", "Edit README on main");
+        let main_before = git(path, &["rev-parse", "main"]);
+        let session_before = git(path, &["rev-parse", "session"]);
+
+        let outcome = cherry_pick_session_commits(path, "session", "main", &oids).unwrap();
+
+        let conflict = outcome.conflict.expect("conflict report");
+        assert_eq!(conflict.commit, oids[1]);
+        assert_eq!(conflict.summary, "Add README.md");
+        assert_eq!(conflict.conflicting_paths, vec!["README.md".to_string()]);
+        assert!(outcome.new_parent_oid.is_none());
+        assert!(outcome.picked_commits.is_empty());
+
+        assert_eq!(git(path, &["rev-parse", "main"]), main_before);
+        assert_eq!(git(path, &["rev-parse", "session"]), session_before);
+        assert_eq!(git(path, &["status", "--porcelain"]), "");
+        assert!(!path.join("a.txt").exists());
+    }
+}
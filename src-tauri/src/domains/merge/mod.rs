@@ -1,9 +1,16 @@
+pub mod checks;
+pub mod cherry_pick;
 pub mod lock;
 pub mod service;
+pub mod trailers;
 pub mod types;
 
-pub use service::{update_session_from_parent, MergeService};
+pub use service::{
+    update_all_sessions_from_parent, update_session_from_parent, BatchUpdateProgress, MergeService,
+};
+pub use trailers::{append_commit_trailers, normalize_commit_trailers, validate_commit_trailer};
 pub use types::{
-    MergeMode, MergeOutcome, MergePreview, MergeState, UpdateFromParentStatus,
+    BatchSessionUpdate, BatchUpdateReport, CherryPickConflict, CherryPickOutcome, MergeMode,
+    MergeOutcome, MergePreview, MergeState, SessionCommit, UpdateFromParentStatus, UpdatePreview,
     UpdateSessionFromParentResult,
 };
@@ -1,11 +1,11 @@
 use std::collections::BTreeSet;
 #[cfg(test)]
 use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 #[cfg(test)]
-use std::path::Path;
-use std::path::PathBuf;
-#[cfg(test)]
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{Context, Result, anyhow};
@@ -26,15 +26,22 @@ use crate::domains::git::operations::{
     uncommitted_sample_paths,
 };
 use crate::domains::git::service as git;
+use crate::domains::merge::checks::{merge_check_error, run_merge_check};
+use crate::domains::merge::cherry_pick::{cherry_pick_session_commits, unique_session_commits};
 use crate::domains::merge::lock;
+use crate::domains::merge::trailers::{append_commit_trailers, normalize_commit_trailers};
 use crate::domains::merge::types::{
-    MergeMode, MergeOutcome, MergePreview, MergeState, UpdateFromParentStatus,
+    BatchSessionUpdate, BatchUpdateReport, CherryPickOutcome, MergeMode, MergeOutcome,
+    MergePreview, MergeState, SessionCommit, UpdateFromParentStatus, UpdatePreview,
     UpdateSessionFromParentResult,
 };
+use crate::domains::sessions::db_journal::{JournalEntry, OperationKind};
 use crate::domains::sessions::db_sessions::SessionMethods;
-use crate::domains::sessions::entity::SessionState;
+use crate::domains::sessions::lifecycle::recovery::JournalGuard;
+use crate::domains::sessions::entity::{Session, SessionState, SessionStatus};
 use crate::domains::sessions::service::SessionManager;
 use crate::infrastructure::database::Database;
+use crate::infrastructure::database::db_project_config::ProjectConfigMethods;
 
 const MERGE_TIMEOUT: Duration = Duration::from_secs(180);
 const OPERATION_LABEL: &str = "merge_session";
@@ -55,11 +62,22 @@ struct SessionMergeContext {
 pub struct MergeService {
     db: Database,
     repo_path: PathBuf,
+    check_output: Option<Arc<dyn Fn(&str) + Send + Sync>>,
 }
 
 impl MergeService {
     pub fn new(db: Database, repo_path: PathBuf) -> Self {
-        Self { db, repo_path }
+        Self {
+            db,
+            repo_path,
+            check_output: None,
+        }
+    }
+
+    /// Receives each output line of the pre-merge check while it runs.
+    pub fn with_check_output(mut self, on_output: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.check_output = Some(Arc::new(on_output));
+        self
     }
 
     fn assess_context(&self, context: &SessionMergeContext) -> Result<MergeState> {
@@ -137,13 +155,7 @@ impl MergeService {
         let parent_tree = parent_commit.tree()?;
         let base_tree = merge_base_commit.tree()?;
 
-        // Build synthetic tree representing working directory (committed + staged + unstaged + untracked)
-        let mut index = repo.index()?;
-        index.read_tree(&head_tree)?;
-        index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
-        index.update_all(["*"], None)?;
-        let worktree_tree_oid = index.write_tree_to(&repo)?;
-        let worktree_tree = repo.find_tree(worktree_tree_oid)?;
+        let worktree_tree = worktree_snapshot_tree(&repo, &head_tree)?;
 
         // Conflict simulation
         let mut merge_opts = MergeOptions::new();
@@ -173,6 +185,8 @@ impl MergeService {
         let is_up_to_date = diff.deltas().len() == 0;
 
         let default_message = format!("Merge session {} into {}", session.name, parent_branch);
+        let (rebase_continue_commands, rebase_continue_summary) =
+            rebase_continue_preview(&session.name, parent_branch);
 
         Ok(MergePreview {
             session_branch: session.branch.clone(),
@@ -189,6 +203,8 @@ impl MergeService {
                     parent_branch
                 ),
             ],
+            rebase_continue_commands,
+            rebase_continue_summary,
             default_commit_message: default_message,
             has_conflicts,
             conflicting_paths,
@@ -196,6 +212,97 @@ impl MergeService {
         })
     }
 
+    /// Simulates bringing the parent branch into the session branch, the reverse direction
+    /// of [`Self::preview`]. Uncommitted worktree changes take part in the simulation, but
+    /// neither the worktree nor the index is written.
+    pub fn preview_update_from_parent(&self, session_name: &str) -> Result<UpdatePreview> {
+        let manager = self.session_manager();
+        let session = manager
+            .get_session(session_name)
+            .with_context(|| format!("Session '{session_name}' not found"))?;
+
+        if session.session_state == SessionState::Spec {
+            return Err(anyhow!(
+                "Session '{session_name}' is still a spec. Start it before updating."
+            ));
+        }
+
+        if !session.worktree_path.exists() {
+            return Err(anyhow!(
+                "Worktree for session '{session_name}' is missing at {}",
+                session.worktree_path.display()
+            ));
+        }
+
+        let parent_branch = session.parent_branch.trim();
+        if parent_branch.is_empty() {
+            return Err(anyhow!(
+                "Session '{session_name}' has no recorded parent branch"
+            ));
+        }
+
+        let repo = Repository::open(&session.worktree_path).with_context(|| {
+            format!(
+                "Failed to open git repository at {}",
+                session.worktree_path.display()
+            )
+        })?;
+
+        let session_ref = find_branch(&repo, &session.branch).with_context(|| {
+            format!(
+                "Session branch '{}' not found for session '{session_name}'",
+                session.branch
+            )
+        })?;
+        let parent_ref = find_branch(&repo, parent_branch).with_context(|| {
+            format!("Parent branch '{parent_branch}' not found for session '{session_name}'")
+        })?;
+
+        let session_commit = session_ref.get().peel_to_commit()?;
+        let parent_commit = parent_ref.get().peel_to_commit()?;
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(parent_commit.id())?;
+        revwalk.hide(session_commit.id())?;
+        let commits_behind = revwalk.count();
+
+        let mut preview = UpdatePreview {
+            session_branch: session.branch.clone(),
+            parent_branch: parent_branch.to_string(),
+            commits_behind,
+            has_conflicts: false,
+            conflicting_paths: Vec::new(),
+            is_up_to_date: commits_behind == 0,
+        };
+        if preview.is_up_to_date {
+            return Ok(preview);
+        }
+
+        let merge_base_oid = repo.merge_base(session_commit.id(), parent_commit.id())?;
+        let base_tree = repo.find_commit(merge_base_oid)?.tree()?;
+        let worktree_tree = worktree_snapshot_tree(&repo, &session_commit.tree()?)?;
+        let parent_tree = parent_commit.tree()?;
+
+        let mut merge_opts = MergeOptions::new();
+        merge_opts.fail_on_conflict(false);
+
+        let merge_index = repo
+            .merge_trees(&base_tree, &worktree_tree, &parent_tree, Some(&merge_opts))
+            .with_context(|| {
+                format!(
+                    "Failed to simulate updating '{}' from parent '{parent_branch}'",
+                    session.name
+                )
+            })?;
+
+        if merge_index.has_conflicts() {
+            preview.conflicting_paths = collect_conflicting_paths(&merge_index)?;
+            preview.has_conflicts = !preview.conflicting_paths.is_empty();
+        }
+
+        Ok(preview)
+    }
+
     pub fn preview(&self, session_name: &str) -> Result<MergePreview> {
         let context = self.prepare_context(session_name)?;
         let default_message = format!(
@@ -219,6 +326,9 @@ impl MergeService {
             ),
         ];
 
+        let (rebase_continue_commands, rebase_continue_summary) =
+            rebase_continue_preview(&context.session_name, &context.parent_branch);
+
         let assessment = self.assess_context(&context)?;
 
         Ok(MergePreview {
@@ -226,6 +336,8 @@ impl MergeService {
             parent_branch: context.parent_branch,
             squash_commands,
             reapply_commands,
+            rebase_continue_commands,
+            rebase_continue_summary,
             default_commit_message: default_message,
             has_conflicts: assessment.has_conflicts,
             conflicting_paths: assessment.conflicting_paths,
@@ -233,6 +345,65 @@ impl MergeService {
         })
     }
 
+    /// Builds a default squash commit message from the subjects of the session's commits
+    /// that are not yet on the parent branch, oldest first and without duplicates.
+    /// Commits of the session branch that its parent branch does not contain yet,
+    /// oldest first.
+    pub fn session_commits(&self, session_name: &str) -> Result<Vec<SessionCommit>> {
+        let (session, parent_branch) = self.session_with_local_parent(session_name)?;
+        let repo = Repository::open(&session.repository_path)?;
+        unique_session_commits(&repo, &session.branch, &parent_branch)
+    }
+
+    /// Applies the given session commits onto the parent branch without merging the
+    /// rest of the session. A conflict is reported in the outcome and changes nothing.
+    pub fn cherry_pick_commits(
+        &self,
+        session_name: &str,
+        commit_oids: &[String],
+    ) -> Result<CherryPickOutcome> {
+        let (session, parent_branch) = self.session_with_local_parent(session_name)?;
+        let _lock = lock::try_acquire(session_name)
+            .ok_or_else(|| anyhow!("Merge already running for session '{session_name}'"))?;
+        cherry_pick_session_commits(
+            &session.repository_path,
+            &session.branch,
+            &parent_branch,
+            commit_oids,
+        )
+    }
+
+    fn session_with_local_parent(&self, session_name: &str) -> Result<(Session, String)> {
+        let session = self
+            .session_manager()
+            .get_session(session_name)
+            .with_context(|| format!("Session '{session_name}' not found"))?;
+        if session.session_state == SessionState::Spec {
+            return Err(anyhow!(
+                "Session '{session_name}' is still a spec and has no commits"
+            ));
+        }
+        let repo = Repository::open(&session.repository_path).with_context(|| {
+            format!(
+                "Failed to open git repository at {}",
+                session.repository_path.display()
+            )
+        })?;
+        let parent_branch = git::normalize_branch_to_local(&repo, &session.parent_branch)
+            .with_context(|| {
+                format!(
+                    "Parent branch '{}' is unavailable as a local branch for session '{session_name}'",
+                    session.parent_branch
+                )
+            })?;
+        Ok((session, parent_branch))
+    }
+
+    pub fn suggest_commit_message(&self, session_name: &str) -> Result<String> {
+        let context = self.prepare_context(session_name)?;
+        suggest_commit_message_for_context(&context)
+    }
+
     pub async fn merge_from_modal(
         &self,
         session_name: &str,
@@ -263,7 +434,7 @@ impl MergeService {
                 preview.conflicting_paths.join(", ")
             ));
         }
-        if preview.is_up_to_date {
+        if preview.is_up_to_date && mode.updates_parent() {
             return Err(anyhow!(
                 "Nothing to merge: the session is already up to date with parent branch '{}'.",
                 preview.parent_branch
@@ -276,7 +447,7 @@ impl MergeService {
                     .as_ref()
                     .map(|m| m.trim().to_string())
                     .filter(|m| !m.is_empty())
-                    .ok_or_else(|| anyhow!("Commit message is required for squash merges"))?;
+                    .unwrap_or_else(|| format!("Uncommitted changes of session {session_name}"));
 
                 let dirty = get_uncommitted_changes_status(&session.worktree_path)?;
                 if dirty.has_tracked_changes || dirty.has_untracked_changes {
@@ -308,6 +479,13 @@ impl MergeService {
                     })?;
                 }
             }
+            MergeMode::RebaseContinue => {
+                if has_uncommitted_changes(&session.worktree_path)? {
+                    return Err(anyhow!(
+                        "Uncommitted changes detected. Please commit your changes before rebasing the session."
+                    ));
+                }
+            }
         }
 
         self.merge(session_name, mode, commit_message).await
@@ -340,33 +518,42 @@ impl MergeService {
             ));
         }
 
-        if assessment.is_up_to_date {
+        if mode.updates_parent() {
+            if assessment.is_up_to_date {
+                return Err(anyhow!(
+                    "Session '{}' has no commits to merge into parent branch '{}'.",
+                    context.session_name,
+                    context.parent_branch
+                ));
+            }
+
+            self.ensure_parent_branch_clean(&context)?;
+        } else if !needs_rebase(&context)? {
             return Err(anyhow!(
-                "Session '{}' has no commits to merge into parent branch '{}'.",
+                "Session '{}' is already based on the tip of parent branch '{}'.",
                 context.session_name,
                 context.parent_branch
             ));
         }
 
-        self.ensure_parent_branch_clean(&context)?;
-
         let commit_message = match mode {
             MergeMode::Squash => {
-                let message = commit_message
-                    .and_then(|m| {
-                        let trimmed = m.trim().to_string();
-                        if trimmed.is_empty() {
-                            None
-                        } else {
-                            Some(trimmed)
-                        }
-                    })
-                    .ok_or_else(|| anyhow!("Commit message is required for squash merges"))?;
-                Some(message)
+                let message = match commit_message
+                    .map(|m| m.trim().to_string())
+                    .filter(|m| !m.is_empty())
+                {
+                    Some(message) => message,
+                    None => suggest_commit_message_for_context(&context)?,
+                };
+                Some(append_commit_trailers(
+                    &message,
+                    &self.merge_commit_trailers(&context)?,
+                ))
             }
             MergeMode::Reapply => commit_message
                 .map(|m| m.trim().to_string())
                 .filter(|m| !m.is_empty()),
+            MergeMode::RebaseContinue => None,
         };
 
         let lock_guard = lock::try_acquire(&context.session_name).ok_or_else(|| {
@@ -376,6 +563,18 @@ impl MergeService {
             )
         })?;
 
+        if mode.updates_parent() {
+            self.run_pre_merge_check(&context).await?;
+        }
+
+        let mut journal_entry =
+            JournalEntry::new(&context.repo_path, OperationKind::Merge, &context.session_name);
+        journal_entry.session_id = Some(context.session_id.clone());
+        journal_entry.branch = Some(context.session_branch.clone());
+        journal_entry.worktree_path = Some(context.worktree_path.clone());
+        journal_entry.base_commit = Some(context.session_oid.to_string());
+        let journal = JournalGuard::begin(&self.db, journal_entry);
+
         let context_clone = context.clone();
         let commit_message_clone = commit_message.clone();
 
@@ -386,6 +585,7 @@ impl MergeService {
         .await;
 
         drop(lock_guard);
+        drop(journal);
 
         let outcome = match result {
             Ok(inner) => inner?,
@@ -398,11 +598,85 @@ impl MergeService {
             }
         }?;
 
-        self.after_success(&context)?;
+        if mode.updates_parent() {
+            self.after_success(&context)?;
+        } else {
+            self.after_rebase_continue(&context);
+        }
 
         Ok(outcome)
     }
 
+    /// Runs the test command, or the project run script, in the session worktree when
+    /// the project requires passing tests, and fails when it does not exit cleanly.
+    async fn run_pre_merge_check(&self, context: &SessionMergeContext) -> Result<()> {
+        let preferences = self.db.get_project_merge_preferences(&context.repo_path)?;
+        if !preferences.require_passing_tests {
+            return Ok(());
+        }
+
+        let mut env = self
+            .db
+            .get_project_environment_variables(&context.repo_path)
+            .unwrap_or_default();
+        let mut cwd = context.worktree_path.clone();
+        let test_command = preferences
+            .test_command
+            .filter(|command| !command.trim().is_empty());
+        let command = match test_command {
+            Some(command) => command,
+            None => {
+                let script = self
+                    .db
+                    .get_project_run_script(&context.repo_path)?
+                    .filter(|script| !script.command.trim().is_empty())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Passing tests are required before merging, but no test command or run script is configured"
+                        )
+                    })?;
+                if let Some(dir) = script.working_directory.as_deref().map(Path::new)
+                    && !dir.as_os_str().is_empty()
+                    && dir.is_relative()
+                {
+                    cwd = cwd.join(dir);
+                }
+                env.extend(script.environment_variables);
+                script.command
+            }
+        };
+
+        info!(
+            "{OPERATION_LABEL}: running pre-merge check `{command}` for session '{session_name}'",
+            session_name = context.session_name
+        );
+        let on_output = self.check_output.clone();
+        let result = task::spawn_blocking(move || {
+            run_merge_check(&command, &cwd, &env, &|line| {
+                if let Some(on_output) = &on_output {
+                    on_output(line);
+                }
+            })
+        })
+        .await
+        .map_err(|e| anyhow!("Pre-merge check task panicked: {e}"))??;
+
+        if result.passed() {
+            Ok(())
+        } else {
+            Err(merge_check_error(&context.session_name, &result))
+        }
+    }
+
+    fn merge_commit_trailers(&self, context: &SessionMergeContext) -> Result<Vec<String>> {
+        let trailers = self
+            .db
+            .get_project_merge_commit_trailers(&context.repo_path)
+            .unwrap_or_default();
+        normalize_commit_trailers(&trailers)
+            .context("Project merge commit trailers are invalid")
+    }
+
     fn ensure_parent_branch_clean(&self, context: &SessionMergeContext) -> Result<()> {
         let repo = Repository::open(&context.repo_path)?;
         let head = match repo.head() {
@@ -447,6 +721,12 @@ impl MergeService {
             );
         }
         manager.update_session_state(&context.session_name, SessionState::Reviewed)?;
+        if let Err(err) = self.db.mark_session_merged(&context.session_id) {
+            warn!(
+                "{OPERATION_LABEL}: failed to record merge time for '{session_name}': {err}",
+                session_name = context.session_name
+            );
+        }
 
         if let Err(err) = manager.update_git_stats(&context.session_id) {
             warn!(
@@ -458,6 +738,23 @@ impl MergeService {
         Ok(())
     }
 
+    /// The session keeps its state and worktree path, so agent resume files stay valid;
+    /// only the cached git stats need to reflect the rebased head.
+    fn after_rebase_continue(&self, context: &SessionMergeContext) {
+        info!(
+            "{OPERATION_LABEL}: session '{session_name}' rebased onto '{parent}' and continues running",
+            session_name = context.session_name,
+            parent = context.parent_branch
+        );
+        let manager = self.session_manager();
+        if let Err(err) = manager.update_git_stats(&context.session_id) {
+            warn!(
+                "{OPERATION_LABEL}: failed to refresh git stats for '{session_name}': {err}",
+                session_name = context.session_name
+            );
+        }
+    }
+
     fn prepare_context(&self, session_name: &str) -> Result<SessionMergeContext> {
         let manager = self.session_manager();
         let session = manager
@@ -578,6 +875,7 @@ impl MergeService {
                 perform_squash(context_for_task, message)
             }
             MergeMode::Reapply => perform_reapply(context_for_task),
+            MergeMode::RebaseContinue => perform_rebase_continue(context_for_task),
         })
         .await
         .map_err(|e| anyhow!("Merge task panicked: {e}"))
@@ -642,6 +940,67 @@ fn perform_reapply(context: SessionMergeContext) -> Result<MergeOutcome> {
     })
 }
 
+fn perform_rebase_continue(context: SessionMergeContext) -> Result<MergeOutcome> {
+    info!(
+        "{OPERATION_LABEL}: rebasing branch '{branch}' onto '{parent}' and keeping the session alive",
+        branch = context.session_branch.as_str(),
+        parent = context.parent_branch.as_str()
+    );
+
+    if let Err(err) = rebase_session_branch(&context) {
+        if let Err(restore_err) = restore_session_head(&context) {
+            warn!(
+                "{OPERATION_LABEL}: failed to restore worktree for '{session}' after aborted rebase: {restore_err}",
+                session = context.session_name
+            );
+        }
+        return Err(err);
+    }
+
+    let repo = Repository::open(&context.repo_path)?;
+    let head_oid = resolve_branch_oid(&repo, &context.session_branch)?;
+
+    Ok(MergeOutcome {
+        session_branch: context.session_branch,
+        parent_branch: context.parent_branch,
+        new_commit: head_oid.to_string(),
+        mode: MergeMode::RebaseContinue,
+    })
+}
+
+/// Puts the session branch and worktree back on the commit recorded before the rebase started.
+fn restore_session_head(context: &SessionMergeContext) -> Result<()> {
+    let repo = Repository::open(&context.worktree_path).with_context(|| {
+        format!(
+            "Failed to open worktree repository at {}",
+            context.worktree_path.display()
+        )
+    })?;
+
+    if let Ok(mut rebase) = repo.open_rebase(None) {
+        let _ = rebase.abort();
+    }
+
+    let original = repo.find_commit(context.session_oid)?;
+    repo.reset(original.as_object(), git2::ResetType::Hard, None)
+        .with_context(|| {
+            format!(
+                "Failed to reset session branch '{}' to {}",
+                context.session_branch, context.session_oid
+            )
+        })?;
+
+    Ok(())
+}
+
+fn rebase_continue_preview(session_name: &str, parent_branch: &str) -> (Vec<String>, String) {
+    let commands = vec![format!("git rebase {parent_branch}")];
+    let summary = format!(
+        "Rebases session '{session_name}' onto the current tip of '{parent_branch}' inside its worktree. '{parent_branch}' is not updated and the session keeps running."
+    );
+    (commands, summary)
+}
+
 fn needs_rebase(context: &SessionMergeContext) -> Result<bool> {
     let repo = Repository::open(&context.repo_path)?;
     let latest_parent_oid = resolve_branch_oid(&repo, &context.parent_branch)?;
@@ -797,6 +1156,44 @@ fn rebase_session_branch(context: &SessionMergeContext) -> Result<()> {
     Ok(())
 }
 
+fn suggest_commit_message_for_context(context: &SessionMergeContext) -> Result<String> {
+    let repo = Repository::open(&context.repo_path).with_context(|| {
+        format!(
+            "Failed to open git repository at {}",
+            context.repo_path.display()
+        )
+    })?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    revwalk.push(context.session_oid)?;
+    revwalk.hide(context.parent_oid)?;
+
+    let mut seen = BTreeSet::new();
+    let mut subjects = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let Some(subject) = commit.summary().map(str::trim) else {
+            continue;
+        };
+        if !subject.is_empty() && seen.insert(subject.to_string()) {
+            subjects.push(subject.to_string());
+        }
+    }
+
+    let mut message = format!(
+        "Merge session {} into {}",
+        context.session_name, context.parent_branch
+    );
+    if !subjects.is_empty() {
+        message.push('\n');
+        for subject in subjects {
+            message.push_str(&format!("\n- {subject}"));
+        }
+    }
+    Ok(message)
+}
+
 fn create_squash_commit(context: &SessionMergeContext, commit_message: &str) -> Result<Oid> {
     let repo = Repository::open(&context.worktree_path).with_context(|| {
         format!(
@@ -965,7 +1362,7 @@ fn commits_ahead(repo: &Repository, session_oid: Oid, parent_oid: Oid) -> Result
     Ok(revwalk.next().is_some())
 }
 
-fn collect_conflicting_paths(index: &git2::Index) -> Result<Vec<String>> {
+pub(super) fn collect_conflicting_paths(index: &git2::Index) -> Result<Vec<String>> {
     let mut seen = BTreeSet::new();
     let mut conflicts_iter = index
         .conflicts()
@@ -998,7 +1395,7 @@ fn collect_conflicting_paths(index: &git2::Index) -> Result<Vec<String>> {
     Ok(seen.into_iter().collect())
 }
 
-fn fast_forward_branch(repo: &Repository, branch: &str, new_oid: Oid) -> Result<()> {
+pub(super) fn fast_forward_branch(repo: &Repository, branch: &str, new_oid: Oid) -> Result<()> {
     let reference_name = normalize_branch_ref(branch);
     let mut reference = repo
         .find_reference(&reference_name)
@@ -1076,6 +1473,20 @@ fn normalize_branch_ref(branch: &str) -> String {
     }
 }
 
+/// Builds a tree of the working directory (committed + staged + unstaged + untracked)
+/// on top of `head_tree`, without writing the index back to disk.
+fn worktree_snapshot_tree<'repo>(
+    repo: &'repo Repository,
+    head_tree: &git2::Tree,
+) -> Result<git2::Tree<'repo>> {
+    let mut index = repo.index()?;
+    index.read_tree(head_tree)?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    let tree_oid = index.write_tree_to(repo)?;
+    Ok(repo.find_tree(tree_oid)?)
+}
+
 fn find_branch<'repo>(repo: &'repo Repository, name: &str) -> Result<git2::Branch<'repo>> {
     repo.find_branch(name, BranchType::Local)
         .or_else(|_| repo.find_branch(name, BranchType::Remote))
@@ -1536,6 +1947,116 @@ pub fn update_session_from_parent(
     }
 }
 
+/// Maximum number of sessions updated at the same time by
+/// `update_all_sessions_from_parent`.
+const BATCH_UPDATE_CONCURRENCY: usize = 3;
+
+/// Progress reported by `update_all_sessions_from_parent` for each session it visits,
+/// alongside the id of the batch.
+pub enum BatchUpdateProgress<'a> {
+    Started(&'a Session),
+    Finished(&'a Session, &'a UpdateSessionFromParentResult),
+}
+
+impl BatchUpdateProgress<'_> {
+    pub fn session(&self) -> &Session {
+        match self {
+            BatchUpdateProgress::Started(session) | BatchUpdateProgress::Finished(session, _) => {
+                session
+            }
+        }
+    }
+}
+
+fn update_batch_member(session: &Session) -> UpdateSessionFromParentResult {
+    match has_uncommitted_changes(&session.worktree_path) {
+        Ok(false) => update_session_from_parent(
+            &session.name,
+            &session.worktree_path,
+            &session.repository_path,
+            &session.parent_branch,
+        ),
+        Ok(true) => UpdateSessionFromParentResult {
+            status: UpdateFromParentStatus::HasUncommittedChanges,
+            parent_branch: session.parent_branch.clone(),
+            message: "Skipped: session has uncommitted changes".to_string(),
+            conflicting_paths: Vec::new(),
+        },
+        Err(e) => UpdateSessionFromParentResult {
+            status: UpdateFromParentStatus::MergeFailed,
+            parent_branch: session.parent_branch.clone(),
+            message: format!("Failed to inspect worktree: {e}"),
+            conflicting_paths: Vec::new(),
+        },
+    }
+}
+
+/// Updates every active session in `state` (running by default) from its parent branch,
+/// optionally limited to sessions whose parent is `parent_branch`. Sessions with
+/// uncommitted changes are skipped and sessions that would conflict are left untouched;
+/// both are reported in the returned batch.
+pub fn update_all_sessions_from_parent(
+    manager: &SessionManager,
+    state: Option<SessionState>,
+    parent_branch: Option<&str>,
+    on_progress: &(dyn Fn(&str, BatchUpdateProgress<'_>) + Sync),
+) -> Result<BatchUpdateReport> {
+    let state = state.unwrap_or(SessionState::Running);
+    if state == SessionState::Spec {
+        return Err(anyhow!("Spec sessions have no worktree to update"));
+    }
+
+    let sessions: Vec<Session> = manager
+        .list_sessions()?
+        .into_iter()
+        .filter(|session| {
+            session.status == SessionStatus::Active
+                && session.session_state == state
+                && parent_branch.is_none_or(|parent| session.parent_branch == parent)
+        })
+        .collect();
+
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    info!(
+        "Batch {batch_id}: updating {} session(s) from their parent branch",
+        sessions.len()
+    );
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<UpdateSessionFromParentResult>>> =
+        Mutex::new(vec![None; sessions.len()]);
+    std::thread::scope(|scope| {
+        for _ in 0..BATCH_UPDATE_CONCURRENCY.min(sessions.len()) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(session) = sessions.get(index) else {
+                        break;
+                    };
+                    on_progress(&batch_id, BatchUpdateProgress::Started(session));
+                    let result = update_batch_member(session);
+                    on_progress(&batch_id, BatchUpdateProgress::Finished(session, &result));
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    let sessions = sessions
+        .into_iter()
+        .zip(results.into_inner().unwrap())
+        .filter_map(|(session, result)| {
+            result.map(|result| BatchSessionUpdate {
+                session_name: session.name,
+                session_branch: session.branch,
+                result,
+            })
+        })
+        .collect();
+
+    Ok(BatchUpdateReport { batch_id, sessions })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1687,6 +2208,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1758,6 +2280,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1829,6 +2352,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1872,6 +2396,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1911,6 +2436,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1960,6 +2486,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1993,14 +2520,15 @@ mod tests {
     }
 
     #[test]
-    fn update_session_from_parent_detects_local_change_conflicts_without_stashing() {
+    fn preview_update_from_parent_lists_conflicting_paths_without_writing() {
         let temp = TempDir::new().unwrap();
-        let (manager, _db, repo_path) = create_session_manager(&temp);
+        let (manager, db, repo_path) = create_session_manager(&temp);
 
         commit_file(&repo_path, "conflict.txt", "base\n", "add conflict file");
+        commit_file(&repo_path, "other.txt", "base\n", "add other file");
 
         let params = SessionCreationParams {
-            name: "update-local-conflict",
+            name: "preview-update",
             prompt: Some("conflict"),
             base_branch: Some("main"),
             custom_branch: None,
@@ -2009,6 +2537,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2016,8 +2545,67 @@ mod tests {
         };
 
         let session = manager.create_session_with_agent(params).unwrap();
+        let service = MergeService::new(db.clone(), repo_path.clone());
 
-        // Parent advances conflict.txt
+        let fresh = service.preview_update_from_parent(&session.name).unwrap();
+        assert!(fresh.is_up_to_date);
+        assert_eq!(fresh.commits_behind, 0);
+
+        commit_file(
+            &session.worktree_path,
+            "conflict.txt",
+            "session change\n",
+            "session edit",
+        );
+        commit_file(&repo_path, "conflict.txt", "parent change\n", "parent edit");
+        commit_file(&repo_path, "other.txt", "parent\n", "other edit");
+
+        let session_head_before = Repository::open(&session.worktree_path)
+            .unwrap()
+            .head()
+            .unwrap()
+            .target()
+            .unwrap();
+
+        let preview = service.preview_update_from_parent(&session.name).unwrap();
+        assert!(!preview.is_up_to_date);
+        assert_eq!(preview.commits_behind, 2);
+        assert!(preview.has_conflicts);
+        assert_eq!(preview.conflicting_paths, vec!["conflict.txt".to_string()]);
+
+        let repo = Repository::open(&session.worktree_path).unwrap();
+        assert_eq!(repo.head().unwrap().target().unwrap(), session_head_before);
+        assert!(!git_has_merge_head(&session.worktree_path));
+        assert!(!has_uncommitted_changes(&session.worktree_path).unwrap());
+    }
+
+    #[test]
+    fn update_session_from_parent_detects_local_change_conflicts_without_stashing() {
+        let temp = TempDir::new().unwrap();
+        let (manager, _db, repo_path) = create_session_manager(&temp);
+
+        commit_file(&repo_path, "conflict.txt", "base\n", "add conflict file");
+
+        let params = SessionCreationParams {
+            name: "update-local-conflict",
+            prompt: Some("conflict"),
+            base_branch: Some("main"),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: None,
+            skip_permissions: None,
+            pr_number: None,
+        };
+
+        let session = manager.create_session_with_agent(params).unwrap();
+
+        // Parent advances conflict.txt
         commit_file(&repo_path, "conflict.txt", "parent change\n", "parent edit");
 
         // Local uncommitted change in session worktree.
@@ -2077,6 +2665,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2141,6 +2730,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2268,6 +2858,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2298,6 +2889,114 @@ mod tests {
         );
     }
 
+    #[test]
+    fn update_all_sessions_from_parent_skips_dirty_and_conflicting_sessions() {
+        let temp = TempDir::new().unwrap();
+        let (manager, _db, repo_path) = create_session_manager(&temp);
+
+        commit_file(&repo_path, "conflict.txt", "base\n", "add conflict file");
+
+        let create = |name: &'static str| {
+            manager
+                .create_session_with_agent(SessionCreationParams {
+                    name,
+                    prompt: None,
+                    base_branch: Some("main"),
+                    custom_branch: None,
+                    use_existing_branch: false,
+                    sync_with_origin: false,
+                    was_auto_generated: false,
+                    version_group_id: None,
+                    version_number: None,
+                    version_group_label: None,
+                    epic_id: None,
+                    agent_type: None,
+                    skip_permissions: None,
+                    pr_number: None,
+                })
+                .unwrap()
+        };
+        let clean = create("batch-clean");
+        let conflicting = create("batch-conflict");
+        let dirty = create("batch-dirty");
+
+        write_session_file(&clean.worktree_path, "clean.txt", "clean\n");
+        commit_file(
+            &conflicting.worktree_path,
+            "conflict.txt",
+            "session change\n",
+            "session edit",
+        );
+        std::fs::write(dirty.worktree_path.join("wip.txt"), "wip\n").unwrap();
+        commit_file(&repo_path, "conflict.txt", "parent change\n", "parent edit");
+
+        let head_of = |path: &Path| {
+            Repository::open(path)
+                .unwrap()
+                .head()
+                .unwrap()
+                .target()
+                .unwrap()
+        };
+        let conflicting_head = head_of(&conflicting.worktree_path);
+        let dirty_head = head_of(&dirty.worktree_path);
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let report =
+            update_all_sessions_from_parent(&manager, None, Some("main"), &|batch, progress| {
+                assert!(!batch.is_empty());
+                let event = match progress {
+                    BatchUpdateProgress::Started(session) => format!("started:{}", session.name),
+                    BatchUpdateProgress::Finished(session, _) => {
+                        format!("finished:{}", session.name)
+                    }
+                };
+                events.lock().unwrap().push(event);
+            })
+            .unwrap();
+
+        let status_of = |name: &str| {
+            report
+                .sessions
+                .iter()
+                .find(|update| update.session_name == name)
+                .map(|update| update.result.status)
+                .unwrap()
+        };
+        assert_eq!(report.sessions.len(), 3);
+        assert_eq!(status_of("batch-clean"), UpdateFromParentStatus::Success);
+        assert_eq!(
+            status_of("batch-conflict"),
+            UpdateFromParentStatus::HasConflicts
+        );
+        assert_eq!(
+            status_of("batch-dirty"),
+            UpdateFromParentStatus::HasUncommittedChanges
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(clean.worktree_path.join("conflict.txt")).unwrap(),
+            "parent change\n"
+        );
+        assert_eq!(head_of(&conflicting.worktree_path), conflicting_head);
+        assert!(!git_has_merge_head(&conflicting.worktree_path));
+        assert_eq!(head_of(&dirty.worktree_path), dirty_head);
+        assert!(dirty.worktree_path.join("wip.txt").exists());
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 6);
+        for name in ["batch-clean", "batch-conflict", "batch-dirty"] {
+            let started = events.iter().position(|e| *e == format!("started:{name}"));
+            let finished = events.iter().position(|e| *e == format!("finished:{name}"));
+            assert!(started.unwrap() < finished.unwrap());
+        }
+
+        assert!(
+            update_all_sessions_from_parent(&manager, Some(SessionState::Spec), None, &|_, _| {})
+                .is_err()
+        );
+    }
+
     #[test]
     fn update_session_from_parent_blocks_when_merge_in_progress() {
         let temp = TempDir::new().unwrap();
@@ -2315,6 +3014,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2366,6 +3066,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2409,6 +3110,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2452,6 +3154,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2491,6 +3194,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2531,6 +3235,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2602,6 +3307,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2712,6 +3418,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2786,6 +3493,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2817,6 +3525,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2851,6 +3560,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2884,6 +3594,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2917,6 +3628,198 @@ mod tests {
         assert_eq!(session_after.session_state, SessionState::Reviewed);
     }
 
+    #[tokio::test]
+    async fn squash_merge_without_message_uses_suggested_commit_subjects() {
+        let temp = TempDir::new().unwrap();
+        let (manager, db, repo_path) = create_session_manager(&temp);
+
+        let params = SessionCreationParams {
+            name: "suggest-session",
+            prompt: Some("do work"),
+            base_branch: Some("main"),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: None,
+            skip_permissions: None,
+            pr_number: None,
+        };
+
+        let session = manager.create_session_with_agent(params).unwrap();
+        commit_file(&session.worktree_path, "a.txt", "a\n", "Add parser");
+        commit_file(&session.worktree_path, "b.txt", "b\n", "Fix lexer");
+        commit_file(&session.worktree_path, "c.txt", "c\n", "Add parser");
+        manager.mark_session_ready(&session.name).unwrap();
+
+        let service = MergeService::new(db.clone(), repo_path.clone());
+        let suggestion = service.suggest_commit_message(&session.name).unwrap();
+        assert_eq!(
+            suggestion,
+            "Merge session suggest-session into main\n\n- Add parser\n- Fix lexer"
+        );
+
+        let outcome = service
+            .merge(&session.name, MergeMode::Squash, None)
+            .await
+            .expect("squash merge should fall back to the suggested message");
+        let repo = Repository::open(&session.repository_path).unwrap();
+        let parent_commit = repo
+            .find_commit(resolve_branch_oid(&repo, &outcome.parent_branch).unwrap())
+            .unwrap();
+        let message = parent_commit.message().unwrap();
+        assert!(message.contains("- Add parser"));
+        assert!(message.contains("- Fix lexer"));
+    }
+
+    #[tokio::test]
+    async fn squash_merge_appends_configured_trailers() {
+        let temp = TempDir::new().unwrap();
+        let (manager, db, repo_path) = create_session_manager(&temp);
+        db.set_project_merge_commit_trailers(
+            &repo_path,
+            &[
+                "Reviewed-by: Jane <jane@example.com>".to_string(),
+                "Co-authored-by: Bob <bob@example.com>".to_string(),
+            ],
+        )
+        .unwrap();
+
+        let params = SessionCreationParams {
+            name: "trailer-session",
+            prompt: Some("do work"),
+            base_branch: Some("main"),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: None,
+            skip_permissions: None,
+            pr_number: None,
+        };
+
+        let session = manager.create_session_with_agent(params).unwrap();
+        write_session_file(&session.worktree_path, "src/lib.rs", "pub fn demo() {}\n");
+
+        let service = MergeService::new(db.clone(), repo_path.clone());
+        let outcome = service
+            .merge(
+                &session.name,
+                MergeMode::Squash,
+                Some("Squash merge\n\nExplain the change".into()),
+            )
+            .await
+            .unwrap();
+
+        let repo = Repository::open(&session.repository_path).unwrap();
+        let commit = repo
+            .find_commit(Oid::from_str(&outcome.new_commit).unwrap())
+            .unwrap();
+        assert_eq!(
+            commit.message().unwrap().trim_end(),
+            "Squash merge\n\nExplain the change\n\nReviewed-by: Jane <jane@example.com>\nCo-authored-by: Bob <bob@example.com>"
+        );
+    }
+
+    fn session_with_required_check(
+        temp: &TempDir,
+        test_command: &str,
+    ) -> (SessionManager, Database, PathBuf, Session) {
+        let (manager, db, repo_path) = create_session_manager(temp);
+        db.set_project_merge_preferences(
+            &repo_path,
+            &crate::infrastructure::database::ProjectMergePreferences {
+                auto_cancel_after_merge: false,
+                auto_cancel_after_pr: false,
+                require_passing_tests: true,
+                test_command: Some(test_command.to_string()),
+            },
+        )
+        .unwrap();
+
+        let params = SessionCreationParams {
+            name: "checked-session",
+            prompt: Some("do work"),
+            base_branch: Some("main"),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: None,
+            skip_permissions: None,
+            pr_number: None,
+        };
+        let session = manager.create_session_with_agent(params).unwrap();
+        write_session_file(&session.worktree_path, "src/lib.rs", "pub fn demo() {}\n");
+        (manager, db, repo_path, session)
+    }
+
+    #[tokio::test]
+    async fn squash_merge_runs_when_required_check_passes() {
+        let temp = TempDir::new().unwrap();
+        let (_manager, db, repo_path, session) =
+            session_with_required_check(&temp, "test -f src/lib.rs && echo checks ok");
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let captured = lines.clone();
+        let service = MergeService::new(db, repo_path)
+            .with_check_output(move |line| captured.lock().unwrap().push(line.to_string()));
+        let outcome = service
+            .merge(
+                &session.name,
+                MergeMode::Squash,
+                Some("Checked merge".into()),
+            )
+            .await
+            .unwrap();
+
+        let repo = Repository::open(&session.repository_path).unwrap();
+        let parent = resolve_branch_oid(&repo, &outcome.parent_branch).unwrap();
+        assert_eq!(parent.to_string(), outcome.new_commit);
+        assert_eq!(*lines.lock().unwrap(), vec!["checks ok".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn squash_merge_aborts_when_required_check_fails() {
+        let temp = TempDir::new().unwrap();
+        let (_manager, db, repo_path, session) =
+            session_with_required_check(&temp, "echo tests broken; exit 1");
+
+        let repo = Repository::open(&session.repository_path).unwrap();
+        let before = resolve_branch_oid(&repo, &session.parent_branch).unwrap();
+
+        let service = MergeService::new(db, repo_path);
+        let err = service
+            .merge(
+                &session.name,
+                MergeMode::Squash,
+                Some("Checked merge".into()),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err.contains("exited with code 1"),
+            "unexpected error: {err}"
+        );
+        assert!(err.contains("tests broken"), "missing check output: {err}");
+        let after = resolve_branch_oid(&repo, &session.parent_branch).unwrap();
+        assert_eq!(before, after);
+    }
+
     #[tokio::test]
     async fn squash_merge_preserves_parent_tree_files() {
         let temp = TempDir::new().unwrap();
@@ -2932,6 +3835,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3014,6 +3918,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3111,6 +4016,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3171,6 +4077,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3241,6 +4148,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3319,6 +4227,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3379,6 +4288,124 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn rebase_continue_rebases_session_and_leaves_parent_untouched() {
+        let temp = TempDir::new().unwrap();
+        let (manager, db, repo_path) = create_session_manager(&temp);
+
+        let params = SessionCreationParams {
+            name: "rebase-continue",
+            prompt: Some("long running work"),
+            base_branch: Some("main"),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: None,
+            skip_permissions: None,
+            pr_number: None,
+        };
+
+        let session = manager.create_session_with_agent(params).unwrap();
+        write_session_file(&session.worktree_path, "src/lib.rs", "pub fn demo() {}\n");
+        commit_file(&repo_path, "README.md", "parent update", "main update");
+
+        let repo_before = Repository::open(&repo_path).unwrap();
+        let parent_head_before = resolve_branch_oid(&repo_before, "main").unwrap();
+
+        let service = MergeService::new(db.clone(), repo_path.clone());
+        let preview = service.preview(&session.name).unwrap();
+        assert_eq!(preview.rebase_continue_commands, vec!["git rebase main"]);
+        assert!(preview.rebase_continue_summary.contains("not updated"));
+
+        let outcome = service
+            .merge(&session.name, MergeMode::RebaseContinue, None)
+            .await
+            .unwrap();
+        assert_eq!(outcome.mode, MergeMode::RebaseContinue);
+
+        let repo_after = Repository::open(&repo_path).unwrap();
+        let parent_head_after = resolve_branch_oid(&repo_after, "main").unwrap();
+        let session_head_after = resolve_branch_oid(&repo_after, &session.branch).unwrap();
+        assert_eq!(parent_head_after, parent_head_before);
+        assert_eq!(session_head_after.to_string(), outcome.new_commit);
+        assert!(
+            repo_after
+                .graph_descendant_of(session_head_after, parent_head_after)
+                .unwrap()
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(session.worktree_path.join("README.md")).unwrap(),
+            "parent update"
+        );
+        assert!(session.worktree_path.join("src/lib.rs").exists());
+
+        let session_after = manager.get_session(&session.name).unwrap();
+        assert_eq!(session_after.session_state, SessionState::Running);
+        assert!(!session_after.ready_to_merge);
+        assert_eq!(session_after.worktree_path, session.worktree_path);
+
+        let err = service
+            .merge(&session.name, MergeMode::RebaseContinue, None)
+            .await
+            .expect_err("second rebase should report nothing to do");
+        assert!(err.to_string().contains("already based on the tip"));
+    }
+
+    #[tokio::test]
+    async fn rebase_continue_conflict_restores_worktree() {
+        let temp = TempDir::new().unwrap();
+        let (manager, db, repo_path) = create_session_manager(&temp);
+
+        let params = SessionCreationParams {
+            name: "rebase-continue-conflict",
+            prompt: Some("conflict work"),
+            base_branch: Some("main"),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: None,
+            skip_permissions: None,
+            pr_number: None,
+        };
+
+        let session = manager.create_session_with_agent(params).unwrap();
+        write_session_file(&session.worktree_path, "conflict.txt", "session change\n");
+        commit_file(&repo_path, "conflict.txt", "parent change\n", "parent conflict");
+
+        let repo_before = Repository::open(&repo_path).unwrap();
+        let session_head_before = resolve_branch_oid(&repo_before, &session.branch).unwrap();
+
+        let service = MergeService::new(db.clone(), repo_path.clone());
+        let err = service
+            .merge(&session.name, MergeMode::RebaseContinue, None)
+            .await
+            .expect_err("conflicting rebase must abort");
+        assert!(err.to_string().to_lowercase().contains("conflict"));
+
+        let repo_after = Repository::open(&repo_path).unwrap();
+        let session_head_after = resolve_branch_oid(&repo_after, &session.branch).unwrap();
+        assert_eq!(session_head_after, session_head_before);
+        assert_eq!(
+            std::fs::read_to_string(session.worktree_path.join("conflict.txt")).unwrap(),
+            "session change\n"
+        );
+        assert!(!has_uncommitted_changes(&session.worktree_path).unwrap());
+
+        let session_after = manager.get_session(&session.name).unwrap();
+        assert_eq!(session_after.session_state, SessionState::Running);
+    }
+
     #[tokio::test]
     #[serial]
     async fn merge_squash_preserves_session_on_conflict() {
@@ -3395,6 +4422,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3474,6 +4502,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3541,6 +4570,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3629,6 +4659,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3738,6 +4769,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -3836,6 +4868,7 @@ mod tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -0,0 +1,103 @@
+use anyhow::{Result, anyhow};
+
+/// Parses a `Key: Value` trailer line. Keys follow git's trailer token rules
+/// (alphanumerics and hyphens); values must be non-empty and single-line.
+fn split_trailer(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let value = value.trim();
+    let valid_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !key.starts_with('-');
+    if valid_key && !value.is_empty() {
+        Some((key, value))
+    } else {
+        None
+    }
+}
+
+pub fn validate_commit_trailer(trailer: &str) -> Result<()> {
+    if trailer.contains('\n') || trailer.contains('\r') {
+        return Err(anyhow!("Commit trailer must be a single line: '{trailer}'"));
+    }
+    split_trailer(trailer.trim())
+        .map(|_| ())
+        .ok_or_else(|| anyhow!("Invalid commit trailer '{trailer}': expected 'Key: Value'"))
+}
+
+/// Validates every trailer and returns them trimmed, dropping blank entries.
+pub fn normalize_commit_trailers(trailers: &[String]) -> Result<Vec<String>> {
+    let mut normalized = Vec::new();
+    for trailer in trailers {
+        let trimmed = trailer.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        validate_commit_trailer(trimmed)?;
+        let (key, value) = split_trailer(trimmed).expect("validated trailer");
+        normalized.push(format!("{key}: {value}"));
+    }
+    Ok(normalized)
+}
+
+/// Appends trailers after the message body, separated by a blank line unless the
+/// message already ends in a trailer block. Trailers already present are not repeated.
+pub fn append_commit_trailers(message: &str, trailers: &[String]) -> String {
+    let body = message.trim_end();
+    let missing: Vec<&String> = trailers
+        .iter()
+        .filter(|trailer| !body.lines().any(|line| line.trim() == trailer.as_str()))
+        .collect();
+    if missing.is_empty() {
+        return body.to_string();
+    }
+
+    let last_paragraph = body.rsplit("\n\n").next().unwrap_or_default();
+    let ends_with_trailers = body.contains("\n\n")
+        && last_paragraph
+            .lines()
+            .all(|line| split_trailer(line.trim()).is_some());
+
+    let mut out = body.to_string();
+    out.push_str(if ends_with_trailers { "\n" } else { "\n\n" });
+    out.push_str(
+        &missing
+            .iter()
+            .map(|trailer| trailer.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_rejects_malformed_trailers() {
+        assert!(validate_commit_trailer("Reviewed-by: Jane <jane@example.com>").is_ok());
+        assert!(validate_commit_trailer("Reviewed by: Jane").is_err());
+        assert!(validate_commit_trailer("Reviewed-by:").is_err());
+        assert!(validate_commit_trailer("no separator").is_err());
+        assert!(validate_commit_trailer("Key: a\nb").is_err());
+    }
+
+    #[test]
+    fn appends_after_body_and_extends_existing_block() {
+        let trailers = vec!["Reviewed-by: Jane <jane@example.com>".to_string()];
+        assert_eq!(
+            append_commit_trailers("Subject\n\nBody text\n", &trailers),
+            "Subject\n\nBody text\n\nReviewed-by: Jane <jane@example.com>"
+        );
+        assert_eq!(
+            append_commit_trailers("Subject\n\nCo-authored-by: Bob <bob@example.com>", &trailers),
+            "Subject\n\nCo-authored-by: Bob <bob@example.com>\nReviewed-by: Jane <jane@example.com>"
+        );
+        assert_eq!(
+            append_commit_trailers("Subject\n\nReviewed-by: Jane <jane@example.com>", &trailers),
+            "Subject\n\nReviewed-by: Jane <jane@example.com>"
+        );
+    }
+}
@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 pub enum MergeMode {
     Squash,
     Reapply,
+    /// Rebase the session branch onto the parent tip in place and keep the session running.
+    /// The parent branch is never updated in this mode.
+    RebaseContinue,
 }
 
 impl MergeMode {
@@ -12,8 +15,13 @@ impl MergeMode {
         match self {
             MergeMode::Squash => "squash",
             MergeMode::Reapply => "reapply",
+            MergeMode::RebaseContinue => "rebase_continue",
         }
     }
+
+    pub fn updates_parent(&self) -> bool {
+        !matches!(self, MergeMode::RebaseContinue)
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -23,6 +31,8 @@ pub struct MergePreview {
     pub parent_branch: String,
     pub squash_commands: Vec<String>,
     pub reapply_commands: Vec<String>,
+    pub rebase_continue_commands: Vec<String>,
+    pub rebase_continue_summary: String,
     pub default_commit_message: String,
     pub has_conflicts: bool,
     pub conflicting_paths: Vec<String>,
@@ -106,10 +116,81 @@ pub struct UpdateSessionFromParentResult {
     pub conflicting_paths: Vec<String>,
 }
 
+/// Simulated update of a session branch from its parent; nothing is written.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePreview {
+    pub session_branch: String,
+    pub parent_branch: String,
+    /// Parent commits the session branch does not contain yet.
+    pub commits_behind: usize,
+    pub has_conflicts: bool,
+    pub conflicting_paths: Vec<String>,
+    pub is_up_to_date: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSessionUpdate {
+    pub session_name: String,
+    pub session_branch: String,
+    pub result: UpdateSessionFromParentResult,
+}
+
+/// Outcome of updating every matching session from its parent branch in one batch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUpdateReport {
+    pub batch_id: String,
+    pub sessions: Vec<BatchSessionUpdate>,
+}
+
+/// A commit on a session branch that its parent branch does not contain.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCommit {
+    pub oid: String,
+    pub summary: String,
+    pub author: String,
+    /// Author time in epoch seconds.
+    pub timestamp: i64,
+}
+
+/// The first selected commit that did not apply cleanly onto the parent branch.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CherryPickConflict {
+    pub commit: String,
+    pub summary: String,
+    pub conflicting_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CherryPickOutcome {
+    pub session_branch: String,
+    pub parent_branch: String,
+    /// New tip of the parent branch; `None` when a conflict left it untouched.
+    pub new_parent_oid: Option<String>,
+    /// Session commits that were applied, oldest first.
+    pub picked_commits: Vec<String>,
+    pub conflict: Option<CherryPickConflict>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn rebase_continue_mode_round_trips_and_keeps_parent() {
+        let mode: MergeMode = serde_json::from_str("\"rebase_continue\"").unwrap();
+        assert_eq!(mode, MergeMode::RebaseContinue);
+        assert_eq!(mode.as_str(), "rebase_continue");
+        assert!(!mode.updates_parent());
+        assert!(MergeMode::Squash.updates_parent());
+        assert!(MergeMode::Reapply.updates_parent());
+    }
+
     #[test]
     fn snapshot_from_none_defaults() {
         let snapshot = MergeStateSnapshot::from_state(None);
@@ -149,6 +230,8 @@ mod tests {
             parent_branch: "main".into(),
             squash_commands: vec![],
             reapply_commands: vec![],
+            rebase_continue_commands: vec![],
+            rebase_continue_summary: String::new(),
             default_commit_message: String::new(),
             has_conflicts: false,
             conflicting_paths: vec!["conflict.txt".into()],
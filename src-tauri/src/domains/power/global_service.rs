@@ -13,7 +13,7 @@ use tokio::sync::Mutex;
 
 use super::platform::{PlatformAdapter, default_adapter};
 use super::security::{ProcessInspector, SecurityConfig, SecurityContext};
-use super::types::{GlobalState, InhibitorState, ProcessInfo};
+use super::types::{GlobalState, InhibitorState, KeepAwakeReason, ProcessInfo};
 
 #[cfg(test)]
 type AppRuntime = tauri::test::MockRuntime;
@@ -131,10 +131,54 @@ impl ProcessInspector for SystemProcessInspector {
     }
 }
 
+/// Keep-awake is released after this long by default, even while sessions stay active.
+pub const DEFAULT_MAX_INHIBIT_MINUTES: u64 = 8 * 60;
+
+fn default_max_inhibit_minutes() -> u64 {
+    DEFAULT_MAX_INHIBIT_MINUTES
+}
+
+/// Converts the persisted limit into a duration; `0` disables the limit.
+fn max_inhibit_from_minutes(minutes: u64) -> Option<Duration> {
+    (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct PersistedSettings {
     enabled: bool,
+    #[serde(default = "default_max_inhibit_minutes")]
+    max_inhibit_minutes: u64,
+    #[serde(default)]
+    auto_mode: bool,
+    #[serde(default)]
+    manual_override: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_activity_minutes: Option<u64>,
+}
+
+impl Default for PersistedSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_inhibit_minutes: DEFAULT_MAX_INHIBIT_MINUTES,
+            auto_mode: false,
+            manual_override: false,
+            auto_activity_minutes: None,
+        }
+    }
+}
+
+impl PersistedSettings {
+    fn from_state(state: &InhibitorState) -> Self {
+        Self {
+            enabled: state.user_enabled,
+            max_inhibit_minutes: state.max_inhibit.map_or(0, |d| d.as_secs() / 60),
+            auto_mode: state.auto_mode,
+            manual_override: state.manual_override,
+            auto_activity_minutes: state.auto_activity_window.map(|d| d.as_secs() / 60),
+        }
+    }
 }
 
 struct KeepAwakeStore {
@@ -146,9 +190,9 @@ impl KeepAwakeStore {
         Self { path }
     }
 
-    fn load(&self) -> Result<bool, SchaltError> {
+    fn load(&self) -> Result<PersistedSettings, SchaltError> {
         if !self.path.exists() {
-            return Ok(false);
+            return Ok(PersistedSettings::default());
         }
 
         let raw = fs::read_to_string(&self.path).map_err(|e| SchaltError::IoError {
@@ -158,15 +202,15 @@ impl KeepAwakeStore {
         })?;
 
         match serde_json::from_str::<PersistedSettings>(&raw) {
-            Ok(parsed) => Ok(parsed.enabled),
+            Ok(parsed) => Ok(parsed),
             Err(e) => {
                 warn!("Failed to parse power settings, using defaults: {e}");
-                Ok(false)
+                Ok(PersistedSettings::default())
             }
         }
     }
 
-    fn save(&self, enabled: bool) -> Result<(), SchaltError> {
+    fn save(&self, settings: &PersistedSettings) -> Result<(), SchaltError> {
         if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent).map_err(|e| SchaltError::IoError {
                 operation: "create_power_settings_dir".into(),
@@ -175,8 +219,7 @@ impl KeepAwakeStore {
             })?;
         }
 
-        let payload = PersistedSettings { enabled };
-        let body = serde_json::to_string_pretty(&payload).map_err(|e| SchaltError::IoError {
+        let body = serde_json::to_string_pretty(settings).map_err(|e| SchaltError::IoError {
             operation: "serialize_power_settings".into(),
             path: self.path.display().to_string(),
             message: e.to_string(),
@@ -208,8 +251,12 @@ impl GlobalInhibitorService {
         store_path: PathBuf,
         process_inspector: Arc<dyn ProcessInspector>,
     ) -> Arc<Self> {
+        let state = InhibitorState {
+            max_inhibit: max_inhibit_from_minutes(DEFAULT_MAX_INHIBIT_MINUTES),
+            ..InhibitorState::default()
+        };
         Arc::new(Self {
-            state: Arc::new(Mutex::new(InhibitorState::default())),
+            state: Arc::new(Mutex::new(state)),
             security,
             platform,
             app_handle,
@@ -267,10 +314,16 @@ impl GlobalInhibitorService {
             e
         })?;
 
-        let enabled = store.load()?;
+        let settings = store.load()?;
 
         let state = InhibitorState {
-            user_enabled: enabled,
+            user_enabled: settings.enabled,
+            auto_mode: settings.auto_mode,
+            manual_override: settings.auto_mode && settings.manual_override,
+            auto_activity_window: settings
+                .auto_activity_minutes
+                .filter(|minutes| *minutes > 0)
+                .map(|minutes| Duration::from_secs(minutes * 60)),
             active_sessions: HashSet::new(),
             running_sessions: HashSet::new(),
             running_by_project: std::collections::HashMap::new(),
@@ -279,6 +332,10 @@ impl GlobalInhibitorService {
             last_watchdog_check: Instant::now(),
             idle_deadline: None,
             last_emitted_state: None,
+            last_emitted_reason: None,
+            inhibiting_since: None,
+            max_inhibit: max_inhibit_from_minutes(settings.max_inhibit_minutes),
+            inhibit_timed_out: false,
         };
 
         let service = Arc::new(Self {
@@ -336,27 +393,31 @@ impl GlobalInhibitorService {
         let next = self.evaluate_state(&mut guard).await?;
         let active_count = guard.active_sessions.len();
         let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
         drop(guard);
         if should_emit {
-            self.emit_state(next.clone(), active_count);
+            self.emit_state(next.clone(), active_count, reason);
         }
         Ok(next)
     }
 
     pub async fn enable_global(&self) -> Result<GlobalState, SchaltError> {
         let mut guard = self.state.lock().await;
-        if guard.user_enabled {
+        if guard.user_enabled && guard.reason() == KeepAwakeReason::Manual {
             let state = self.current_state(&guard);
             return Ok(state);
         }
         guard.user_enabled = true;
-        self.store.save(true)?;
+        guard.manual_override = guard.auto_mode;
+        guard.inhibit_timed_out = false;
+        self.store.save(&PersistedSettings::from_state(&guard))?;
         let next = self.evaluate_state(&mut guard).await?;
         let active_count = guard.active_sessions.len();
         let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
         drop(guard);
         if should_emit {
-            self.emit_state(next.clone(), active_count);
+            self.emit_state(next.clone(), active_count, reason);
         }
         Ok(next)
     }
@@ -364,20 +425,85 @@ impl GlobalInhibitorService {
     pub async fn disable_global(&self) -> Result<GlobalState, SchaltError> {
         let mut guard = self.state.lock().await;
         guard.user_enabled = false;
+        guard.manual_override = guard.auto_mode;
         guard.idle_deadline = None;
+        guard.inhibit_timed_out = false;
         self.stop_inhibitor_locked(&mut guard).await?;
         if let Ok(Some(pid)) = self.platform.find_existing_inhibitor() {
             warn!("Disabling keep-awake: terminating stray inhibitor pid={pid}");
             let _ = self.security.kill_process_gracefully(pid);
             let _ = self.security.delete_pid_file();
         }
-        self.store.save(false)?;
+        self.store.save(&PersistedSettings::from_state(&guard))?;
         let next = GlobalState::Disabled;
         let active_count = guard.active_sessions.len();
         let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
+        drop(guard);
+        if should_emit {
+            self.emit_state(next.clone(), active_count, reason);
+        }
+        Ok(next)
+    }
+
+    /// Switches auto mode, in which agent activity of running sessions alone decides
+    /// whether the machine is kept awake. Either way a previous manual override ends here.
+    pub async fn set_auto_mode(&self, enabled: bool) -> Result<GlobalState, SchaltError> {
+        let mut guard = self.state.lock().await;
+        guard.auto_mode = enabled;
+        guard.manual_override = false;
+        guard.inhibit_timed_out = false;
+        self.store.save(&PersistedSettings::from_state(&guard))?;
+        let next = self.evaluate_state(&mut guard).await?;
+        let active_count = guard.active_sessions.len();
+        let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
+        drop(guard);
+        if should_emit {
+            self.emit_state(next.clone(), active_count, reason);
+        }
+        Ok(next)
+    }
+
+    pub async fn auto_mode(&self) -> bool {
+        self.state.lock().await.auto_mode
+    }
+
+    /// Agent silence after which a running session stops keeping the machine awake;
+    /// `None` when the activity tracker's default applies.
+    pub async fn auto_activity_window(&self) -> Option<Duration> {
+        self.state.lock().await.auto_activity_window
+    }
+
+    /// Persists the activity window; callers apply it to the activity tracker.
+    pub async fn set_auto_activity_minutes(&self, minutes: u64) -> Result<Duration, SchaltError> {
+        let window = Duration::from_secs(minutes.max(1) * 60);
+        let mut guard = self.state.lock().await;
+        guard.auto_activity_window = Some(window);
+        self.store.save(&PersistedSettings::from_state(&guard))?;
+        Ok(window)
+    }
+
+    /// Current inhibit limit in minutes; `0` means keep-awake is never released early.
+    pub async fn max_inhibit_minutes(&self) -> u64 {
+        let guard = self.state.lock().await;
+        PersistedSettings::from_state(&guard).max_inhibit_minutes
+    }
+
+    /// Updates and persists the inhibit limit. A session that already timed out may keep
+    /// the machine awake again under the new limit.
+    pub async fn set_max_inhibit_minutes(&self, minutes: u64) -> Result<GlobalState, SchaltError> {
+        let mut guard = self.state.lock().await;
+        guard.max_inhibit = max_inhibit_from_minutes(minutes);
+        guard.inhibit_timed_out = false;
+        self.store.save(&PersistedSettings::from_state(&guard))?;
+        let next = self.evaluate_state(&mut guard).await?;
+        let active_count = guard.active_sessions.len();
+        let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
         drop(guard);
         if should_emit {
-            self.emit_state(next.clone(), active_count);
+            self.emit_state(next.clone(), active_count, reason);
         }
         Ok(next)
     }
@@ -392,9 +518,10 @@ impl GlobalInhibitorService {
         let state = self.current_state(&guard);
         let active = guard.active_sessions.len();
         let should_emit = self.mark_state_if_changed(&mut guard, &state);
+        let reason = guard.reason();
         drop(guard);
         if should_emit {
-            self.emit_state(state.clone(), active);
+            self.emit_state(state.clone(), active, reason);
         }
         state
     }
@@ -449,19 +576,61 @@ impl GlobalInhibitorService {
         let next = self.evaluate_state(&mut guard).await?;
         let active_count = guard.active_sessions.len();
         let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
         drop(guard);
         if should_emit {
-            self.emit_state(next.clone(), active_count);
+            self.emit_state(next.clone(), active_count, reason);
+        }
+        Ok(next)
+    }
+
+    /// Replace the active sessions with the running sessions among `active`, the sessions
+    /// whose agent terminal produced output within the idle window. The inhibitor is held
+    /// while at least one of them remains and released once all agents went idle.
+    pub async fn sync_active_agents(
+        &self,
+        active: HashSet<String>,
+    ) -> Result<GlobalState, SchaltError> {
+        let mut guard = self.state.lock().await;
+        debug!(
+            "[keep-awake] syncing active agents: incoming={} running={} previous_active={}",
+            active.len(),
+            guard.running_sessions.len(),
+            guard.active_sessions.len()
+        );
+
+        let running_snapshot = guard.running_sessions.clone();
+        guard.active_sessions = active
+            .into_iter()
+            .filter(|id| running_snapshot.contains(id))
+            .collect();
+        guard.idle_deadline = if guard.active_sessions.is_empty() {
+            Some(Instant::now())
+        } else {
+            None
+        };
+
+        let next = self.evaluate_state(&mut guard).await?;
+        let active_count = guard.active_sessions.len();
+        let should_emit = self.mark_state_if_changed(&mut guard, &next);
+        let reason = guard.reason();
+        drop(guard);
+        if should_emit {
+            self.emit_state(next.clone(), active_count, reason);
         }
         Ok(next)
     }
 
     async fn evaluate_state(&self, state: &mut InhibitorState) -> Result<GlobalState, SchaltError> {
-        if !state.user_enabled {
+        if !state.keep_awake_enabled() {
             self.stop_inhibitor_locked(state).await?;
             return Ok(GlobalState::Disabled);
         }
 
+        if state.active_sessions.is_empty() {
+            state.inhibit_timed_out = false;
+        }
+
         if state.running_sessions.is_empty() {
             state.active_sessions.clear();
             state.idle_deadline = None;
@@ -479,6 +648,13 @@ impl GlobalInhibitorService {
 
         if !state.active_sessions.is_empty() {
             state.idle_deadline = None;
+            if state.inhibit_timed_out {
+                debug!(
+                    "[keep-awake] decision=auto_paused reason=max_inhibit_elapsed active={}",
+                    state.active_sessions.len()
+                );
+                return Ok(GlobalState::AutoPaused);
+            }
             if state.process_info.is_none() {
                 self.spawn_inhibitor_locked(state).await?;
             }
@@ -556,11 +732,13 @@ impl GlobalInhibitorService {
             spawned_at: SystemTime::now(),
         });
         state.child = Some(child);
+        state.inhibiting_since.get_or_insert_with(Instant::now);
         info!("Spawned keep-awake inhibitor pid={pid}");
         Ok(())
     }
 
     async fn stop_inhibitor_locked(&self, state: &mut InhibitorState) -> Result<(), SchaltError> {
+        state.inhibiting_since = None;
         if let Some(info) = state.process_info.take() {
             info!("Stopping inhibitor pid={}", info.pid);
             self.security.kill_process_gracefully(info.pid)?;
@@ -573,7 +751,7 @@ impl GlobalInhibitorService {
     }
 
     fn current_state(&self, state: &InhibitorState) -> GlobalState {
-        if !state.user_enabled {
+        if !state.keep_awake_enabled() {
             return GlobalState::Disabled;
         }
         if state.running_sessions.is_empty() {
@@ -613,15 +791,16 @@ impl GlobalInhibitorService {
             if guard.process_info.is_some() {
                 self.stop_inhibitor_locked(&mut guard).await?;
             }
-            let next = if guard.user_enabled {
+            let next = if guard.keep_awake_enabled() {
                 GlobalState::AutoPaused
             } else {
                 GlobalState::Disabled
             };
             let should_emit = self.mark_state_if_changed(&mut guard, &next);
+            let reason = guard.reason();
             drop(guard);
             if should_emit {
-                self.emit_state(next, 0);
+                self.emit_state(next, 0, reason);
             }
             return Ok(());
         }
@@ -633,9 +812,32 @@ impl GlobalInhibitorService {
             self.stop_inhibitor_locked(&mut guard).await?;
             let next = GlobalState::AutoPaused;
             let should_emit = self.mark_state_if_changed(&mut guard, &next);
+            let reason = guard.reason();
             drop(guard);
             if should_emit {
-                self.emit_state(next, 0);
+                self.emit_state(next, 0, reason);
+            }
+            return Ok(());
+        }
+
+        if let (Some(since), Some(limit)) = (guard.inhibiting_since, guard.max_inhibit)
+            && guard.process_info.is_some()
+            && now.duration_since(since) >= limit
+        {
+            warn!(
+                "Keep-awake held for {} minutes with {} active session(s); releasing it until sessions go idle",
+                limit.as_secs() / 60,
+                guard.active_sessions.len()
+            );
+            self.stop_inhibitor_locked(&mut guard).await?;
+            guard.inhibit_timed_out = true;
+            let next = GlobalState::AutoPaused;
+            let active_count = guard.active_sessions.len();
+            let should_emit = self.mark_state_if_changed(&mut guard, &next);
+            let reason = guard.reason();
+            drop(guard);
+            if should_emit {
+                self.emit_state(next, active_count, reason);
             }
             return Ok(());
         }
@@ -648,19 +850,20 @@ impl GlobalInhibitorService {
                 guard.child = None;
                 self.security.delete_pid_file()?;
 
-                let next = if guard.user_enabled && !guard.active_sessions.is_empty() {
+                let next = if guard.keep_awake_enabled() && !guard.active_sessions.is_empty() {
                     self.spawn_inhibitor_locked(&mut guard).await?;
                     GlobalState::Active
-                } else if guard.user_enabled {
+                } else if guard.keep_awake_enabled() {
                     GlobalState::AutoPaused
                 } else {
                     GlobalState::Disabled
                 };
                 let active_count = guard.active_sessions.len();
                 let should_emit = self.mark_state_if_changed(&mut guard, &next);
+                let reason = guard.reason();
                 drop(guard);
                 if should_emit {
-                    self.emit_state(next, active_count);
+                    self.emit_state(next, active_count, reason);
                 }
                 return Ok(());
             } else {
@@ -670,7 +873,7 @@ impl GlobalInhibitorService {
                     guard.process_info = None;
                     guard.child = None;
                     self.security.delete_pid_file()?;
-                    let next = if guard.user_enabled {
+                    let next = if guard.keep_awake_enabled() {
                         if guard.active_sessions.is_empty() {
                             GlobalState::AutoPaused
                         } else {
@@ -681,14 +884,18 @@ impl GlobalInhibitorService {
                     };
                     let active_count = guard.active_sessions.len();
                     let should_emit = self.mark_state_if_changed(&mut guard, &next);
+                    let reason = guard.reason();
                     drop(guard);
                     if should_emit {
-                        self.emit_state(next, active_count);
+                        self.emit_state(next, active_count, reason);
                     }
                     return Ok(());
                 }
             }
-        } else if guard.user_enabled && !guard.active_sessions.is_empty() {
+        } else if guard.keep_awake_enabled()
+            && !guard.active_sessions.is_empty()
+            && !guard.inhibit_timed_out
+        {
             self.spawn_inhibitor_locked(&mut guard).await?;
         }
 
@@ -704,22 +911,30 @@ impl GlobalInhibitorService {
         self.watchdog_check().await
     }
 
+    #[cfg(test)]
+    pub async fn backdate_inhibitor(&self, by: Duration) {
+        let mut guard = self.state.lock().await;
+        if let Some(since) = guard.inhibiting_since {
+            guard.inhibiting_since = Some(since - by);
+        }
+    }
+
     fn mark_state_if_changed(&self, guard: &mut InhibitorState, next: &GlobalState) -> bool {
-        let should_emit = guard
-            .last_emitted_state
-            .as_ref()
-            .map(|prev| prev != next)
-            .unwrap_or(true);
+        let reason = guard.reason();
+        let should_emit = guard.last_emitted_state.as_ref() != Some(next)
+            || guard.last_emitted_reason != Some(reason);
         if should_emit {
             guard.last_emitted_state = Some(next.clone());
+            guard.last_emitted_reason = Some(reason);
         }
         should_emit
     }
 
-    fn emit_state(&self, state: GlobalState, active_count: usize) {
+    fn emit_state(&self, state: GlobalState, active_count: usize, reason: KeepAwakeReason) {
         let payload = serde_json::json!({
             "state": state,
             "activeCount": active_count,
+            "reason": reason,
         });
         if let Err(e) = emit_event(
             &self.app_handle,
@@ -807,6 +1022,7 @@ mod tests {
     struct FakePlatform {
         inspector: Arc<FakeInspector>,
         spawns: Arc<AtomicUsize>,
+        lookups: Arc<AtomicUsize>,
         existing: Arc<StdMutex<Option<u32>>>,
     }
 
@@ -815,10 +1031,15 @@ mod tests {
             Self {
                 inspector,
                 spawns: Arc::new(AtomicUsize::new(0)),
+                lookups: Arc::new(AtomicUsize::new(0)),
                 existing: Arc::new(StdMutex::new(None)),
             }
         }
 
+        fn lookup_count(&self) -> usize {
+            self.lookups.load(Ordering::SeqCst)
+        }
+
         fn set_existing(&self, pid: Option<u32>) {
             *self.existing.lock().unwrap() = pid;
         }
@@ -836,6 +1057,7 @@ mod tests {
         }
 
         fn find_existing_inhibitor(&self) -> Result<Option<u32>, SchaltError> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
             Ok(*self.existing.lock().unwrap())
         }
     }
@@ -983,6 +1205,53 @@ mod tests {
         assert_eq!(service.get_state().await, GlobalState::AutoPaused);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn sync_active_agents_follows_agent_activity_of_running_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::new("caffeinate -d # schaltwerk-keep-awake"));
+        let platform = Arc::new(FakePlatform::new(inspector.clone()));
+        let service = build_service(&tmp, inspector.clone(), platform.clone());
+
+        service.enable_global().await.unwrap();
+
+        // Activity of a session that is not running does not keep the machine awake
+        let state = service
+            .sync_active_agents(HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::AutoPaused);
+        assert_eq!(platform.spawn_count(), 0);
+
+        let state = service
+            .sync_running_sessions("project-a".to_string(), HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(platform.spawn_count(), 1);
+        inspector.set_running(true);
+
+        // All agents went idle
+        let state = service.sync_active_agents(HashSet::new()).await.unwrap();
+        assert_eq!(state, GlobalState::AutoPaused);
+        assert_eq!(inspector.term_calls(), 1);
+
+        let state = service
+            .sync_active_agents(HashSet::from(["ghost".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::AutoPaused);
+        assert_eq!(platform.spawn_count(), 1);
+
+        // The agent produces output again
+        let state = service
+            .sync_active_agents(HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(platform.spawn_count(), 2);
+    }
+
     #[tokio::test]
     #[serial]
     async fn sync_treats_newly_started_sessions_as_active() {
@@ -1044,4 +1313,185 @@ mod tests {
             "re-syncing should not reactivate sessions that went idle"
         );
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn inhibitor_is_released_after_max_duration_until_sessions_go_idle() {
+        let tmp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::new("caffeinate -d # schaltwerk-keep-awake"));
+        let platform = Arc::new(FakePlatform::new(inspector.clone()));
+        let service = build_service(&tmp, inspector.clone(), platform.clone());
+
+        service.enable_global().await.unwrap();
+        service.set_max_inhibit_minutes(60).await.unwrap();
+        service
+            .handle_session_activity("s1".to_string(), false)
+            .await
+            .unwrap();
+        assert_eq!(platform.spawn_count(), 1);
+
+        service
+            .backdate_inhibitor(Duration::from_secs(59 * 60))
+            .await;
+        service.force_watchdog_check().await.unwrap();
+        assert_eq!(service.get_state().await, GlobalState::Active);
+
+        service.backdate_inhibitor(Duration::from_secs(60)).await;
+        service.force_watchdog_check().await.unwrap();
+        assert_eq!(service.get_state().await, GlobalState::AutoPaused);
+        assert_eq!(inspector.term_calls(), 1);
+
+        // A hung agent keeps reporting activity; neither it nor the watchdog respawns
+        let state = service
+            .handle_session_activity("s1".to_string(), false)
+            .await
+            .unwrap();
+        service.force_watchdog_check().await.unwrap();
+        assert_eq!(state, GlobalState::AutoPaused);
+        assert_eq!(platform.spawn_count(), 1);
+
+        service
+            .handle_session_activity("s1".to_string(), true)
+            .await
+            .unwrap();
+        let state = service
+            .handle_session_activity("s1".to_string(), false)
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(platform.spawn_count(), 2);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn zero_max_duration_never_releases_inhibitor() {
+        let tmp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::new("caffeinate -d # schaltwerk-keep-awake"));
+        let platform = Arc::new(FakePlatform::new(inspector.clone()));
+        let service = build_service(&tmp, inspector.clone(), platform.clone());
+
+        assert_eq!(
+            service.max_inhibit_minutes().await,
+            DEFAULT_MAX_INHIBIT_MINUTES
+        );
+        service.enable_global().await.unwrap();
+        service.set_max_inhibit_minutes(0).await.unwrap();
+        service
+            .handle_session_activity("s1".to_string(), false)
+            .await
+            .unwrap();
+
+        service
+            .backdate_inhibitor(Duration::from_secs(90 * 60))
+            .await;
+        service.force_watchdog_check().await.unwrap();
+
+        assert_eq!(service.get_state().await, GlobalState::Active);
+        assert_eq!(inspector.term_calls(), 0);
+        let saved = fs::read_to_string(tmp.path().join("power_settings.json")).unwrap();
+        assert!(saved.contains("\"maxInhibitMinutes\": 0"), "{saved}");
+    }
+
+    async fn last_reason(service: &GlobalInhibitorService) -> Option<KeepAwakeReason> {
+        service.state.lock().await.last_emitted_reason
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn auto_mode_follows_agent_activity_without_manual_enable() {
+        let tmp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::new("caffeinate -d # schaltwerk-keep-awake"));
+        let platform = Arc::new(FakePlatform::new(inspector.clone()));
+        let service = build_service(&tmp, inspector.clone(), platform.clone());
+
+        let state = service.set_auto_mode(true).await.unwrap();
+        assert_eq!(state, GlobalState::AutoPaused);
+        assert_eq!(last_reason(&service).await, Some(KeepAwakeReason::AgentActivity));
+        assert_eq!(platform.spawn_count(), 0);
+        assert_eq!(platform.lookup_count(), 0);
+
+        let state = service
+            .sync_running_sessions("project-a".to_string(), HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(platform.spawn_count(), 1);
+        assert_eq!(platform.lookup_count(), 1);
+        inspector.set_running(true);
+
+        // The agent went silent for the activity window
+        let state = service.sync_active_agents(HashSet::new()).await.unwrap();
+        assert_eq!(state, GlobalState::AutoPaused);
+        assert_eq!(inspector.term_calls(), 1);
+
+        let state = service
+            .sync_active_agents(HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(platform.spawn_count(), 2);
+        assert_eq!(platform.lookup_count(), 2);
+        assert_eq!(last_reason(&service).await, Some(KeepAwakeReason::AgentActivity));
+
+        let saved = fs::read_to_string(tmp.path().join("power_settings.json")).unwrap();
+        assert!(saved.contains("\"autoMode\": true"), "{saved}");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn manual_toggle_overrides_auto_mode_until_it_is_switched_back_on() {
+        let tmp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::new("caffeinate -d # schaltwerk-keep-awake"));
+        let platform = Arc::new(FakePlatform::new(inspector.clone()));
+        let service = build_service(&tmp, inspector.clone(), platform.clone());
+
+        service.set_auto_mode(true).await.unwrap();
+        service
+            .sync_running_sessions("project-a".to_string(), HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(platform.spawn_count(), 1);
+        inspector.set_running(true);
+
+        let state = service.disable_global().await.unwrap();
+        assert_eq!(state, GlobalState::Disabled);
+        assert_eq!(last_reason(&service).await, Some(KeepAwakeReason::Manual));
+        assert_eq!(inspector.term_calls(), 1);
+
+        // Agent activity no longer keeps the machine awake while manually disabled
+        let state = service
+            .sync_active_agents(HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        service.force_watchdog_check().await.unwrap();
+        assert_eq!(state, GlobalState::Disabled);
+        assert_eq!(platform.spawn_count(), 1);
+
+        let state = service.set_auto_mode(true).await.unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(last_reason(&service).await, Some(KeepAwakeReason::AgentActivity));
+        assert_eq!(platform.spawn_count(), 2);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn manual_enable_takes_over_from_auto_mode() {
+        let tmp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::new("caffeinate -d # schaltwerk-keep-awake"));
+        let platform = Arc::new(FakePlatform::new(inspector.clone()));
+        let service = build_service(&tmp, inspector.clone(), platform.clone());
+
+        service.set_auto_mode(true).await.unwrap();
+        service.enable_global().await.unwrap();
+        assert_eq!(last_reason(&service).await, Some(KeepAwakeReason::Manual));
+
+        service.set_auto_mode(false).await.unwrap();
+        let state = service
+            .sync_running_sessions("project-a".to_string(), HashSet::from(["s1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(state, GlobalState::Active);
+        assert_eq!(last_reason(&service).await, Some(KeepAwakeReason::Manual));
+        assert_eq!(platform.spawn_count(), 1);
+    }
 }
@@ -6,4 +6,4 @@ pub mod types;
 pub use security::{
     DEFAULT_SIGNATURE, PidFileData, ProcessInspector, SecurityConfig, SecurityContext,
 };
-pub use types::{GlobalState, InhibitorState, ProcessInfo};
+pub use types::{GlobalState, InhibitorState, KeepAwakeReason, ProcessInfo};
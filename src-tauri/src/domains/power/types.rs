@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::process::Child;
-use std::time::{Instant, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -11,6 +11,15 @@ pub enum GlobalState {
     AutoPaused,
 }
 
+/// Who decides whether keep-awake is on: the user's manual toggle, or agent activity
+/// while auto mode is enabled and not overridden.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeepAwakeReason {
+    AgentActivity,
+    Manual,
+}
+
 #[derive(Debug, Clone)]
 pub struct ProcessInfo {
     pub pid: u32,
@@ -21,6 +30,15 @@ pub struct ProcessInfo {
 #[derive(Debug)]
 pub struct InhibitorState {
     pub user_enabled: bool,
+    /// Keep the machine awake whenever a running session's agent is active, without
+    /// the user enabling keep-awake manually.
+    pub auto_mode: bool,
+    /// Set when the user toggled keep-awake manually while auto mode was on; the manual
+    /// choice wins until auto mode is switched on again.
+    pub manual_override: bool,
+    /// Agent silence after which a session stops counting as active in auto mode;
+    /// `None` keeps the activity tracker's default.
+    pub auto_activity_window: Option<Duration>,
     pub active_sessions: HashSet<String>,
     pub running_sessions: HashSet<String>,
     pub running_by_project: std::collections::HashMap<String, HashSet<String>>,
@@ -29,12 +47,22 @@ pub struct InhibitorState {
     pub last_watchdog_check: Instant,
     pub idle_deadline: Option<Instant>,
     pub last_emitted_state: Option<GlobalState>,
+    pub last_emitted_reason: Option<KeepAwakeReason>,
+    /// When the machine started being kept awake; survives watchdog respawns.
+    pub inhibiting_since: Option<Instant>,
+    /// Longest continuous inhibit before the watchdog releases it; `None` means no limit.
+    pub max_inhibit: Option<Duration>,
+    /// Set once `max_inhibit` elapsed; blocks respawning until every session went idle.
+    pub inhibit_timed_out: bool,
 }
 
 impl Default for InhibitorState {
     fn default() -> Self {
         Self {
             user_enabled: false,
+            auto_mode: false,
+            manual_override: false,
+            auto_activity_window: None,
             active_sessions: HashSet::new(),
             running_sessions: HashSet::new(),
             running_by_project: std::collections::HashMap::new(),
@@ -43,6 +71,28 @@ impl Default for InhibitorState {
             last_watchdog_check: Instant::now(),
             idle_deadline: None,
             last_emitted_state: None,
+            last_emitted_reason: None,
+            inhibiting_since: None,
+            max_inhibit: None,
+            inhibit_timed_out: false,
+        }
+    }
+}
+
+impl InhibitorState {
+    pub fn reason(&self) -> KeepAwakeReason {
+        if self.auto_mode && !self.manual_override {
+            KeepAwakeReason::AgentActivity
+        } else {
+            KeepAwakeReason::Manual
+        }
+    }
+
+    /// Whether keep-awake may hold the inhibitor, given manual toggle and auto mode.
+    pub fn keep_awake_enabled(&self) -> bool {
+        match self.reason() {
+            KeepAwakeReason::AgentActivity => true,
+            KeepAwakeReason::Manual => self.user_enabled,
         }
     }
 }
@@ -1,18 +1,24 @@
 use crate::infrastructure::events::{SchaltEvent, emit_event};
-use crate::shared::merge_snapshot_gateway::MergeSnapshotGateway;
+use crate::shared::merge_snapshot_gateway::{MergeSnapshotGateway, MergeStateSnapshot};
 use crate::{
-    domains::git::service as git, domains::sessions::db_sessions::SessionMethods,
+    domains::git::service as git,
+    domains::sessions::cache,
+    domains::sessions::db_sessions::SessionMethods,
+    domains::sessions::entity::{GitStats, Session},
     infrastructure::database::Database,
 };
 use anyhow::Result;
 #[cfg(test)]
 use chrono::DateTime;
 use chrono::{TimeZone, Utc};
+use futures::stream::{self, StreamExt};
 use git2::Repository;
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 #[cfg(test)]
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 #[cfg(test)]
 use std::time::UNIX_EPOCH;
 use tauri::AppHandle;
@@ -23,6 +29,7 @@ use walkdir::WalkDir;
 pub trait EventEmitter: Send + Sync {
     fn emit_session_activity(&self, payload: SessionActivityUpdated) -> Result<()>;
     fn emit_session_git_stats(&self, payload: SessionGitStatsUpdated) -> Result<()>;
+    fn emit_session_idle_changed(&self, payload: SessionIdleChanged) -> Result<()>;
 }
 
 impl EventEmitter for AppHandle {
@@ -35,140 +42,415 @@ impl EventEmitter for AppHandle {
         emit_event(self, SchaltEvent::SessionGitStats, &payload)
             .map_err(|e| anyhow::anyhow!("Failed to emit git stats: {e}"))
     }
+
+    fn emit_session_idle_changed(&self, payload: SessionIdleChanged) -> Result<()> {
+        let event = if payload.idle {
+            SchaltEvent::SessionIdle
+        } else {
+            SchaltEvent::SessionResumed
+        };
+        emit_event(self, event, &payload)
+            .map_err(|e| anyhow::anyhow!("Failed to emit session idle change: {e}"))
+    }
+}
+
+/// Upper bound on concurrent `calculate_git_stats_fast` calls during a polling pass.
+pub const DEFAULT_GIT_STATS_CONCURRENCY: usize = 4;
+
+/// Reads `SCHALTWERK_GIT_STATS_CONCURRENCY`, falling back to
+/// [`DEFAULT_GIT_STATS_CONCURRENCY`] when unset or invalid.
+pub fn git_stats_concurrency_from_env() -> usize {
+    std::env::var("SCHALTWERK_GIT_STATS_CONCURRENCY")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|cap| *cap > 0)
+        .unwrap_or(DEFAULT_GIT_STATS_CONCURRENCY)
+}
+
+/// How long an agent terminal may stay silent before its session stops counting as active.
+pub const DEFAULT_AGENT_IDLE_WINDOW: Duration = Duration::from_secs(120);
+
+const AGENT_IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reads `SCHALTWERK_AGENT_IDLE_WINDOW_SECS`, falling back to
+/// [`DEFAULT_AGENT_IDLE_WINDOW`] when unset or invalid.
+pub fn agent_idle_window_from_env() -> Duration {
+    std::env::var("SCHALTWERK_AGENT_IDLE_WINDOW_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_AGENT_IDLE_WINDOW)
+}
+
+/// Receives the sessions with an active agent whenever that set changes.
+pub trait AgentActivitySink: Send + Sync {
+    fn active_agents_changed(&self, active: HashSet<String>);
+}
+
+/// Sessions that went idle and the idle/resume transitions not yet emitted.
+#[derive(Default)]
+struct IdleTransitions {
+    idle: HashSet<String>,
+    pending: Vec<SessionIdleChanged>,
+}
+
+/// Tracks which sessions had agent terminal output within the idle window. The sink is
+/// told about every change of the active set, so consumers can follow the aggregate
+/// active-agent count without polling. Sessions going idle and resuming after being
+/// idle are also queued as [`SessionIdleChanged`] transitions.
+pub struct AgentActivityMonitor {
+    idle_window: Mutex<Duration>,
+    last_output: Mutex<HashMap<String, Instant>>,
+    transitions: Mutex<IdleTransitions>,
+    sink: Box<dyn AgentActivitySink>,
+}
+
+impl AgentActivityMonitor {
+    pub fn new(idle_window: Duration, sink: Box<dyn AgentActivitySink>) -> Self {
+        Self {
+            idle_window: Mutex::new(idle_window),
+            last_output: Mutex::new(HashMap::new()),
+            transitions: Mutex::new(IdleTransitions::default()),
+            sink,
+        }
+    }
+
+    fn transitions(&self) -> std::sync::MutexGuard<'_, IdleTransitions> {
+        self.transitions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Takes the idle and resume transitions recorded since the last call, oldest first.
+    pub fn take_idle_transitions(&self) -> Vec<SessionIdleChanged> {
+        std::mem::take(&mut self.transitions().pending)
+    }
+
+    /// Records agent output for `session_id`, marking the session active.
+    pub fn record_output(&self, session_id: &str, now: Instant) {
+        let changed = {
+            let mut last_output = self
+                .last_output
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let was_idle = last_output.insert(session_id.to_string(), now).is_none();
+            was_idle.then(|| last_output.keys().cloned().collect())
+        };
+        if let Some(active) = changed {
+            log::debug!("[agent-activity] session={session_id} became active");
+            let mut transitions = self.transitions();
+            if transitions.idle.remove(session_id) {
+                transitions.pending.push(SessionIdleChanged {
+                    session_id: session_id.to_string(),
+                    idle: false,
+                });
+            }
+            drop(transitions);
+            self.sink.active_agents_changed(active);
+        }
+    }
+
+    /// Replaces the idle window; takes effect at the next [`Self::expire_idle`].
+    pub fn set_idle_window(&self, idle_window: Duration) {
+        *self
+            .idle_window
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = idle_window;
+    }
+
+    /// Drops sessions whose agent has been silent for the whole idle window.
+    pub fn expire_idle(&self, now: Instant) {
+        let idle_window = *self
+            .idle_window
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut expired = Vec::new();
+        let changed = {
+            let mut last_output = self
+                .last_output
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            last_output.retain(|session_id, last| {
+                let active = now.duration_since(*last) < idle_window;
+                if !active {
+                    expired.push(session_id.clone());
+                }
+                active
+            });
+            (!expired.is_empty()).then(|| last_output.keys().cloned().collect())
+        };
+        if !expired.is_empty() {
+            let mut transitions = self.transitions();
+            for session_id in expired {
+                transitions.idle.insert(session_id.clone());
+                transitions.pending.push(SessionIdleChanged {
+                    session_id,
+                    idle: true,
+                });
+            }
+        }
+        if let Some(active) = changed {
+            log::debug!(
+                "[agent-activity] agents went idle; {} still active",
+                active.len()
+            );
+            self.sink.active_agents_changed(active);
+        }
+    }
+}
+
+/// Git-derived state for one session, computed off the async runtime.
+struct SessionGitSnapshot {
+    stats: Result<GitStats>,
+    has_conflicts: bool,
+    merge_snapshot: MergeStateSnapshot,
+}
+
+fn compute_session_git_snapshot(session: &Session) -> SessionGitSnapshot {
+    let stats_start = std::time::Instant::now();
+    let stats = git::calculate_git_stats_fast(&session.worktree_path, &session.parent_branch)
+        .map(|mut stats| {
+            stats.session_id = session.id.clone();
+            stats
+        });
+    log::debug!(
+        "activity: session={} git_stats elapsed={}ms",
+        session.name,
+        stats_start.elapsed().as_millis()
+    );
+
+    if stats.is_err() {
+        return SessionGitSnapshot {
+            stats,
+            has_conflicts: false,
+            merge_snapshot: MergeStateSnapshot::default(),
+        };
+    }
+
+    let has_conflicts = match git::has_conflicts(&session.worktree_path) {
+        Ok(value) => value,
+        Err(err) => {
+            log::warn!("Failed to detect conflicts for {}: {err}", session.name);
+            false
+        }
+    };
+
+    let merge_snapshot = Repository::open(&session.repository_path)
+        .ok()
+        .and_then(|repo| {
+            let session_oid =
+                MergeSnapshotGateway::resolve_branch_oid(&repo, &session.branch).ok()?;
+            let parent_oid =
+                MergeSnapshotGateway::resolve_branch_oid(&repo, &session.parent_branch).ok()?;
+            MergeSnapshotGateway::compute(
+                &repo,
+                session_oid,
+                parent_oid,
+                &session.branch,
+                &session.parent_branch,
+            )
+            .map_err(|err| {
+                log::warn!(
+                    "Merge assessment failed for session '{}': {}",
+                    session.name,
+                    err
+                );
+            })
+            .ok()
+        })
+        .unwrap_or_default();
+
+    log::debug!(
+        "activity: session={} git_refresh total={}ms",
+        session.name,
+        stats_start.elapsed().as_millis()
+    );
+
+    SessionGitSnapshot {
+        stats,
+        has_conflicts,
+        merge_snapshot,
+    }
+}
+
+/// Computes git snapshots for sessions with existing worktrees, running at most
+/// `concurrency` blocking computations at once. Results are returned in completion order.
+async fn compute_git_snapshots_concurrently(
+    sessions: Vec<Session>,
+    concurrency: usize,
+) -> Vec<(Session, SessionGitSnapshot)> {
+    stream::iter(
+        sessions
+            .into_iter()
+            .filter(|session| session.worktree_path.exists()),
+    )
+    .map(|session| async move {
+        let name = session.name.clone();
+        match tokio::task::spawn_blocking(move || {
+            let snapshot = compute_session_git_snapshot(&session);
+            (session, snapshot)
+        })
+        .await
+        {
+            Ok(result) => Some(result),
+            Err(err) => {
+                log::warn!("Git stats task for session '{name}' failed: {err}");
+                None
+            }
+        }
+    })
+    .buffer_unordered(concurrency.max(1))
+    .filter_map(|result| async move { result })
+    .collect()
+    .await
 }
 
 pub struct ActivityTracker<E: EventEmitter> {
     db: Arc<Database>,
     emitter: E,
+    git_stats_concurrency: usize,
+    agent_activity: Option<Arc<AgentActivityMonitor>>,
 }
 
 impl<E: EventEmitter> ActivityTracker<E> {
     pub fn new(db: Arc<Database>, emitter: E) -> Self {
-        Self { db, emitter }
+        Self {
+            db,
+            emitter,
+            git_stats_concurrency: git_stats_concurrency_from_env(),
+            agent_activity: None,
+        }
+    }
+
+    pub fn with_git_stats_concurrency(mut self, concurrency: usize) -> Self {
+        self.git_stats_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Expire idle agents of `monitor` while polling.
+    pub fn with_agent_activity(mut self, monitor: Arc<AgentActivityMonitor>) -> Self {
+        self.agent_activity = Some(monitor);
+        self
     }
 
     pub async fn start_polling(self) {
         let mut interval = interval(Duration::from_secs(60));
+        let mut agent_interval = interval(AGENT_IDLE_CHECK_INTERVAL);
 
         loop {
-            interval.tick().await;
-
-            if let Err(e) = self.update_all_activities().await {
-                log::error!("Failed to update activities: {e}");
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(e) = self.update_all_activities().await {
+                        log::error!("Failed to update activities: {e}");
+                    }
+                }
+                _ = agent_interval.tick() => {
+                    if let Some(monitor) = &self.agent_activity {
+                        monitor.expire_idle(Instant::now());
+                        self.emit_idle_transitions(monitor);
+                    }
+                }
             }
         }
     }
 
+    /// Emits the idle and resume transitions `monitor` recorded since the last check.
+    fn emit_idle_transitions(&self, monitor: &AgentActivityMonitor) {
+        for transition in monitor.take_idle_transitions() {
+            let _ = self.emitter.emit_session_idle_changed(transition);
+        }
+    }
+
     async fn update_all_activities(&self) -> Result<()> {
         let active_sessions = self.db.list_all_active_sessions()?;
+        let session_count = active_sessions.len();
+        let start = std::time::Instant::now();
+
+        let snapshots =
+            compute_git_snapshots_concurrently(active_sessions, self.git_stats_concurrency).await;
 
-        for session in active_sessions {
-            self.refresh_stats_and_activity_for_session(&session)?;
+        log::debug!(
+            "activity: refreshed git stats for {} of {} sessions in {}ms (concurrency={})",
+            snapshots.len(),
+            session_count,
+            start.elapsed().as_millis(),
+            self.git_stats_concurrency
+        );
+
+        for (session, snapshot) in snapshots {
+            self.apply_git_snapshot(&session, snapshot)?;
         }
 
         Ok(())
     }
 
-    fn refresh_stats_and_activity_for_session(
-        &self,
-        session: &crate::domains::sessions::entity::Session,
-    ) -> Result<bool> {
-        let mut emitted_activity = false;
-
-        if session.worktree_path.exists() {
-            match git::calculate_git_stats_fast(&session.worktree_path, &session.parent_branch) {
-                Ok(mut stats) => {
-                    stats.session_id = session.id.clone();
-
-                    let has_conflicts = match git::has_conflicts(&session.worktree_path) {
-                        Ok(value) => value,
-                        Err(err) => {
-                            log::warn!(
-                                "Failed to detect conflicts for {}: {err}",
-                                session.name
-                            );
-                            false
-                        }
-                    };
-
-                    let merge_snapshot = Repository::open(&session.repository_path)
-                        .ok()
-                        .and_then(|repo| {
-                            let session_oid = MergeSnapshotGateway::resolve_branch_oid(
-                                &repo,
-                                &session.branch,
-                            )
-                            .ok()?;
-                            let parent_oid = MergeSnapshotGateway::resolve_branch_oid(
-                                &repo,
-                                &session.parent_branch,
-                            )
-                            .ok()?;
-                            MergeSnapshotGateway::compute(
-                                &repo,
-                                session_oid,
-                                parent_oid,
-                                &session.branch,
-                                &session.parent_branch,
-                            )
-                            .map_err(|err| {
-                                log::warn!(
-                                    "Merge assessment failed for session '{}': {}",
-                                    session.name,
-                                    err
-                                );
-                            })
-                            .ok()
-                        })
-                        .unwrap_or_default();
-
-                    let payload = SessionGitStatsUpdated {
-                        session_id: session.id.clone(),
-                        session_name: session.name.clone(),
-                        files_changed: stats.files_changed,
-                        lines_added: stats.lines_added,
-                        lines_removed: stats.lines_removed,
-                        has_uncommitted: stats.has_uncommitted,
-                        has_conflicts,
-                        top_uncommitted_paths: None,
-                        merge_has_conflicts: merge_snapshot.merge_has_conflicts,
-                        merge_conflicting_paths: merge_snapshot.merge_conflicting_paths,
-                        merge_is_up_to_date: merge_snapshot.merge_is_up_to_date,
-                    };
-                    let _ = self.emitter.emit_session_git_stats(payload);
-
-                    if let Some(mut ts) = stats.last_diff_change_ts {
-                        let now = Utc::now().timestamp();
-                        if ts > now + 120 {
-                            ts = now;
-                        }
-                        if let Some(dt) = Utc.timestamp_opt(ts, 0).single() {
-                            self.db.set_session_activity(&session.id, dt)?;
-                            let session_info = self.db.get_session_by_id(&session.id)?;
-                            let payload = SessionActivityUpdated {
-                                session_id: session.id.clone(),
-                                session_name: session.name.clone(),
-                                last_activity_ts: dt.timestamp(),
-                                current_task: session_info.initial_prompt.clone(),
-                                todo_percentage: None,
-                                is_blocked: None,
-                            };
-                            let _ = self.emitter.emit_session_activity(payload);
-                            emitted_activity = true;
-                        }
-                    }
-                }
-                Err(e) => {
-                    log::warn!(
-                        "Failed to compute fast git stats for {}: {}",
-                        session.name,
-                        e
-                    );
-                }
+    #[cfg(test)]
+    fn refresh_stats_and_activity_for_session(&self, session: &Session) -> Result<bool> {
+        if !session.worktree_path.exists() {
+            return Ok(false);
+        }
+        let snapshot = compute_session_git_snapshot(session);
+        self.apply_git_snapshot(session, snapshot)
+    }
+
+    fn apply_git_snapshot(&self, session: &Session, snapshot: SessionGitSnapshot) -> Result<bool> {
+        let stats = match snapshot.stats {
+            Ok(stats) => stats,
+            Err(e) => {
+                log::warn!(
+                    "Failed to compute fast git stats for {}: {}",
+                    session.name,
+                    e
+                );
+                return Ok(false);
             }
+        };
+
+        if let Err(e) = self.db.save_git_stats(&stats) {
+            log::debug!("Failed to persist git stats for {}: {e}", session.name);
         }
+        cache::cache_conflicts(&session.id, snapshot.has_conflicts);
 
-        Ok(emitted_activity)
+        let merge_snapshot = snapshot.merge_snapshot;
+        let payload = SessionGitStatsUpdated {
+            session_id: session.id.clone(),
+            session_name: session.name.clone(),
+            files_changed: stats.files_changed,
+            lines_added: stats.lines_added,
+            lines_removed: stats.lines_removed,
+            has_uncommitted: stats.has_uncommitted,
+            has_conflicts: snapshot.has_conflicts,
+            top_uncommitted_paths: None,
+            merge_has_conflicts: merge_snapshot.merge_has_conflicts,
+            merge_conflicting_paths: merge_snapshot.merge_conflicting_paths,
+            merge_is_up_to_date: merge_snapshot.merge_is_up_to_date,
+        };
+        let _ = self.emitter.emit_session_git_stats(payload);
+
+        let Some(mut ts) = stats.last_diff_change_ts else {
+            return Ok(false);
+        };
+        let now = Utc::now().timestamp();
+        if ts > now + 120 {
+            ts = now;
+        }
+        let Some(dt) = Utc.timestamp_opt(ts, 0).single() else {
+            return Ok(false);
+        };
+
+        self.db.set_session_activity(&session.id, dt)?;
+        let session_info = self.db.get_session_by_id(&session.id)?;
+        let payload = SessionActivityUpdated {
+            session_id: session.id.clone(),
+            session_name: session.name.clone(),
+            last_activity_ts: dt.timestamp(),
+            current_task: session_info.initial_prompt.clone(),
+            todo_percentage: None,
+            is_blocked: None,
+        };
+        let _ = self.emitter.emit_session_activity(payload);
+        Ok(true)
     }
 
     #[cfg(test)]
@@ -219,6 +501,13 @@ pub struct SessionActivityUpdated {
     pub is_blocked: Option<bool>,
 }
 
+/// Payload of `SessionIdle` (`idle` is true) and `SessionResumed` (`idle` is false).
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SessionIdleChanged {
+    pub session_id: String,
+    pub idle: bool,
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct SessionGitStatsUpdated {
     pub session_id: String,
@@ -238,8 +527,12 @@ pub struct SessionGitStatsUpdated {
     pub merge_is_up_to_date: Option<bool>,
 }
 
-pub fn start_activity_tracking_with_app(db: Arc<Database>, app: AppHandle) {
-    let tracker = ActivityTracker::new(db, app);
+pub fn start_activity_tracking_with_app(
+    db: Arc<Database>,
+    app: AppHandle,
+    agent_activity: Arc<AgentActivityMonitor>,
+) {
+    let tracker = ActivityTracker::new(db, app).with_agent_activity(agent_activity);
     tokio::spawn(async move {
         tracker.start_polling().await;
     });
@@ -262,6 +555,7 @@ mod tests {
     struct MockEmitter {
         activity_events: Arc<Mutex<Vec<SessionActivityUpdated>>>,
         git_stats_events: Arc<Mutex<Vec<SessionGitStatsUpdated>>>,
+        idle_events: Arc<Mutex<Vec<SessionIdleChanged>>>,
     }
 
     impl MockEmitter {
@@ -269,6 +563,7 @@ mod tests {
             Self {
                 activity_events: Arc::new(Mutex::new(Vec::new())),
                 git_stats_events: Arc::new(Mutex::new(Vec::new())),
+                idle_events: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
@@ -291,6 +586,11 @@ mod tests {
             self.git_stats_events.lock().unwrap().push(payload);
             Ok(())
         }
+
+        fn emit_session_idle_changed(&self, payload: SessionIdleChanged) -> Result<()> {
+            self.idle_events.lock().unwrap().push(payload);
+            Ok(())
+        }
     }
 
     #[test]
@@ -468,6 +768,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.clone(),
             repository_name: "repo".into(),
@@ -491,6 +792,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
         db.create_session(&session).unwrap();
 
@@ -538,6 +840,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: temp.path().to_path_buf(),
             repository_name: "repo".into(),
@@ -561,6 +864,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
         db.create_session(&session).unwrap();
 
@@ -574,4 +878,201 @@ mod tests {
         let events = mock_emitter.get_activity_events();
         assert_eq!(events.len(), 0);
     }
+
+    fn init_repo_with_changes(path: &std::path::Path, files: usize) -> String {
+        let run = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(path)
+                .output()
+                .unwrap();
+        };
+        std::fs::create_dir_all(path).unwrap();
+        run(&["init"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "Test User"]);
+        std::fs::write(path.join("README.md"), "Initial").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-m", "init"]);
+        for i in 0..files {
+            std::fs::write(path.join(format!("file_{i}.txt")), "x\n".repeat(200)).unwrap();
+        }
+        get_current_branch(path).unwrap()
+    }
+
+    fn bench_session(name: &str, repo_path: &std::path::Path, parent_branch: &str) -> Session {
+        Session {
+            id: format!("id-{name}"),
+            name: name.into(),
+            display_name: None,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            repository_path: repo_path.to_path_buf(),
+            repository_name: "repo".into(),
+            branch: parent_branch.into(),
+            parent_branch: parent_branch.into(),
+            original_parent_branch: Some(parent_branch.into()),
+            worktree_path: repo_path.to_path_buf(),
+            status: SessionStatus::Active,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_activity: None,
+            initial_prompt: None,
+            ready_to_merge: false,
+            original_agent_type: None,
+            original_skip_permissions: None,
+            pending_name_generation: false,
+            was_auto_generated: false,
+            spec_content: None,
+            session_state: SessionState::Running,
+            resume_allowed: true,
+            amp_thread_id: None,
+            pr_number: None,
+            pr_url: None,
+            group: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_git_stats_refresh_is_faster_than_serial() {
+        let temp = TempDir::new().unwrap();
+        let sessions: Vec<Session> = (0..16)
+            .map(|i| {
+                let repo_path = temp.path().join(format!("repo-{i}"));
+                let parent = init_repo_with_changes(&repo_path, 200);
+                bench_session(&format!("bench-{i}"), &repo_path, &parent)
+            })
+            .collect();
+
+        crate::domains::git::stats::clear_stats_cache();
+        let serial_start = std::time::Instant::now();
+        let serial = compute_git_snapshots_concurrently(sessions.clone(), 1).await;
+        let serial_elapsed = serial_start.elapsed();
+
+        crate::domains::git::stats::clear_stats_cache();
+        let parallel_start = std::time::Instant::now();
+        let parallel =
+            compute_git_snapshots_concurrently(sessions, DEFAULT_GIT_STATS_CONCURRENCY).await;
+        let parallel_elapsed = parallel_start.elapsed();
+
+        assert_eq!(serial.len(), 16);
+        assert_eq!(parallel.len(), 16);
+        assert!(
+            parallel
+                .iter()
+                .all(|(_, snapshot)| snapshot.stats.as_ref().is_ok_and(|s| s.has_uncommitted))
+        );
+        assert!(
+            parallel_elapsed < serial_elapsed,
+            "expected concurrent refresh ({parallel_elapsed:?}) to beat serial ({serial_elapsed:?})"
+        );
+    }
+
+    #[test]
+    fn git_stats_concurrency_is_clamped_to_at_least_one() {
+        let temp = TempDir::new().unwrap();
+        let db = Arc::new(Database::new(Some(temp.path().join("test.db"))).unwrap());
+        let tracker = ActivityTracker::new(db, MockEmitter::new()).with_git_stats_concurrency(0);
+        assert_eq!(tracker.git_stats_concurrency, 1);
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        changes: Arc<Mutex<Vec<HashSet<String>>>>,
+    }
+
+    impl AgentActivitySink for RecordingSink {
+        fn active_agents_changed(&self, active: HashSet<String>) {
+            self.changes.lock().unwrap().push(active);
+        }
+    }
+
+    fn names(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn agent_activity_reports_sessions_becoming_active_and_idle() {
+        let sink = RecordingSink::default();
+        let monitor = AgentActivityMonitor::new(Duration::from_secs(120), Box::new(sink.clone()));
+        let start = Instant::now();
+
+        monitor.record_output("alpha", start);
+        // Further output from an already active agent is not a transition
+        monitor.record_output("alpha", start + Duration::from_secs(30));
+        monitor.record_output("beta", start + Duration::from_secs(60));
+
+        monitor.expire_idle(start + Duration::from_secs(140));
+        monitor.expire_idle(start + Duration::from_secs(150));
+        monitor.expire_idle(start + Duration::from_secs(180));
+
+        assert_eq!(
+            *sink.changes.lock().unwrap(),
+            vec![
+                names(&["alpha"]),
+                names(&["alpha", "beta"]),
+                names(&["beta"]),
+                names(&[]),
+            ]
+        );
+    }
+    fn idle_changed(session_id: &str, idle: bool) -> SessionIdleChanged {
+        SessionIdleChanged {
+            session_id: session_id.to_string(),
+            idle,
+        }
+    }
+
+    #[test]
+    fn idle_and_resume_transitions_are_queued_once_each() {
+        let monitor =
+            AgentActivityMonitor::new(Duration::from_secs(120), Box::new(RecordingSink::default()));
+        let start = Instant::now();
+
+        monitor.record_output("alpha", start);
+        monitor.expire_idle(start + Duration::from_secs(100));
+        assert!(monitor.take_idle_transitions().is_empty());
+
+        monitor.expire_idle(start + Duration::from_secs(130));
+        monitor.expire_idle(start + Duration::from_secs(200));
+        assert_eq!(
+            monitor.take_idle_transitions(),
+            vec![idle_changed("alpha", true)]
+        );
+
+        monitor.record_output("alpha", start + Duration::from_secs(210));
+        monitor.record_output("alpha", start + Duration::from_secs(220));
+        // A session that was never idle does not resume
+        monitor.record_output("beta", start + Duration::from_secs(220));
+        assert_eq!(
+            monitor.take_idle_transitions(),
+            vec![idle_changed("alpha", false)]
+        );
+        assert!(monitor.take_idle_transitions().is_empty());
+    }
+
+    #[test]
+    fn tracker_emits_queued_idle_transitions() {
+        let temp = TempDir::new().unwrap();
+        let db = Arc::new(Database::new(Some(temp.path().join("test.db"))).unwrap());
+        let emitter = MockEmitter::new();
+        let tracker = ActivityTracker::new(db, emitter.clone());
+        let monitor =
+            AgentActivityMonitor::new(Duration::from_secs(60), Box::new(RecordingSink::default()));
+        let start = Instant::now();
+
+        monitor.record_output("alpha", start);
+        monitor.expire_idle(start + Duration::from_secs(90));
+        tracker.emit_idle_transitions(&monitor);
+        monitor.record_output("alpha", start + Duration::from_secs(95));
+        tracker.emit_idle_transitions(&monitor);
+        tracker.emit_idle_transitions(&monitor);
+
+        assert_eq!(
+            *emitter.idle_events.lock().unwrap(),
+            vec![idle_changed("alpha", true), idle_changed("alpha", false)]
+        );
+    }
 }
@@ -0,0 +1,340 @@
+use crate::domains::sessions::entity::{SessionState, SessionStatus};
+use crate::infrastructure::database::Database;
+use crate::infrastructure::database::timestamps::{
+    utc_from_epoch_seconds_lossy, utc_from_epoch_seconds_lossy_opt,
+};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+
+const UNKNOWN_AGENT: &str = "unknown";
+const MAX_LINEAGE_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentStatsPeriod {
+    Week,
+    Month,
+    Quarter,
+    #[default]
+    All,
+}
+
+impl AgentStatsPeriod {
+    pub fn cutoff(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            AgentStatsPeriod::Week => Some(now - Duration::days(7)),
+            AgentStatsPeriod::Month => Some(now - Duration::days(30)),
+            AgentStatsPeriod::Quarter => Some(now - Duration::days(90)),
+            AgentStatsPeriod::All => None,
+        }
+    }
+}
+
+impl FromStr for AgentStatsPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "week" => Ok(AgentStatsPeriod::Week),
+            "month" => Ok(AgentStatsPeriod::Month),
+            "quarter" => Ok(AgentStatsPeriod::Quarter),
+            "all" => Ok(AgentStatsPeriod::All),
+            other => Err(anyhow!("Unknown stats period: {other}")),
+        }
+    }
+}
+
+/// The subset of a session row that outcome analytics needs.
+#[derive(Debug, Clone)]
+pub struct SessionOutcomeRow {
+    pub id: String,
+    pub agent_type: Option<String>,
+    pub status: SessionStatus,
+    pub session_state: SessionState,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub retry_of: Option<String>,
+    pub files_changed: Option<u32>,
+    pub lines_changed: Option<u32>,
+}
+
+/// One row per agent type. Every count is per task lineage, so a cancelled session that was
+/// converted back to a spec and restarted counts once, under its latest attempt.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStatsRow {
+    pub agent_type: String,
+    pub started: u32,
+    pub reviewed: u32,
+    pub merged: u32,
+    pub cancelled_without_merge: u32,
+    pub retried: u32,
+    pub avg_seconds_to_review: Option<i64>,
+    pub avg_files_changed: Option<f64>,
+    pub avg_lines_changed: Option<f64>,
+}
+
+#[derive(Default)]
+struct AgentAccumulator {
+    started: u32,
+    reviewed: u32,
+    merged: u32,
+    cancelled_without_merge: u32,
+    retried: u32,
+    review_seconds: Vec<i64>,
+    files_changed: Vec<u32>,
+    lines_changed: Vec<u32>,
+}
+
+fn lineage_root<'a>(
+    row: &'a SessionOutcomeRow,
+    by_id: &HashMap<&str, &'a SessionOutcomeRow>,
+) -> &'a str {
+    let mut current = row;
+    for _ in 0..MAX_LINEAGE_DEPTH {
+        match current.retry_of.as_deref().and_then(|id| by_id.get(id)) {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+    current.id.as_str()
+}
+
+fn average<T: Copy + Into<f64>>(values: &[T]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().map(|v| (*v).into()).sum::<f64>() / values.len() as f64)
+}
+
+/// Aggregates session outcomes per agent type. `pending_retries` holds ids of cancelled
+/// sessions whose spec has not been restarted yet; those lineages are not failures.
+pub fn aggregate_agent_stats(
+    rows: &[SessionOutcomeRow],
+    pending_retries: &HashSet<String>,
+    cutoff: Option<DateTime<Utc>>,
+) -> Vec<AgentStatsRow> {
+    let by_id: HashMap<&str, &SessionOutcomeRow> =
+        rows.iter().map(|row| (row.id.as_str(), row)).collect();
+
+    let mut lineages: HashMap<&str, Vec<&SessionOutcomeRow>> = HashMap::new();
+    for row in rows {
+        if row.session_state == SessionState::Spec || row.status == SessionStatus::Spec {
+            continue;
+        }
+        lineages
+            .entry(lineage_root(row, &by_id))
+            .or_default()
+            .push(row);
+    }
+
+    let mut per_agent: BTreeMap<String, AgentAccumulator> = BTreeMap::new();
+    for attempts in lineages.values_mut() {
+        attempts.sort_by_key(|row| row.created_at);
+        let first = attempts[0];
+        let last = attempts[attempts.len() - 1];
+
+        if cutoff.is_some_and(|cutoff| first.created_at < cutoff) {
+            continue;
+        }
+
+        let agent = last
+            .agent_type
+            .clone()
+            .unwrap_or_else(|| UNKNOWN_AGENT.to_string());
+        let acc = per_agent.entry(agent).or_default();
+        acc.started += 1;
+        if attempts.len() > 1 {
+            acc.retried += 1;
+        }
+
+        let first_review = attempts.iter().filter_map(|row| row.reviewed_at).min();
+        let was_reviewed = first_review.is_some() || last.session_state == SessionState::Reviewed;
+        let was_merged = attempts.iter().any(|row| row.merged_at.is_some());
+
+        if was_reviewed {
+            acc.reviewed += 1;
+        }
+        if let Some(reviewed_at) = first_review {
+            acc.review_seconds
+                .push((reviewed_at - first.created_at).num_seconds().max(0));
+        }
+        if was_merged {
+            acc.merged += 1;
+        } else if last.status == SessionStatus::Cancelled && !pending_retries.contains(&last.id) {
+            acc.cancelled_without_merge += 1;
+        }
+        if let Some(files) = last.files_changed {
+            acc.files_changed.push(files);
+        }
+        if let Some(lines) = last.lines_changed {
+            acc.lines_changed.push(lines);
+        }
+    }
+
+    per_agent
+        .into_iter()
+        .map(|(agent_type, acc)| AgentStatsRow {
+            agent_type,
+            started: acc.started,
+            reviewed: acc.reviewed,
+            merged: acc.merged,
+            cancelled_without_merge: acc.cancelled_without_merge,
+            retried: acc.retried,
+            avg_seconds_to_review: (!acc.review_seconds.is_empty()).then(|| {
+                acc.review_seconds.iter().sum::<i64>() / acc.review_seconds.len() as i64
+            }),
+            avg_files_changed: average(&acc.files_changed),
+            avg_lines_changed: average(&acc.lines_changed),
+        })
+        .collect()
+}
+
+pub trait AgentStatsMethods {
+    fn list_session_outcomes(&self, repo_path: &Path) -> Result<Vec<SessionOutcomeRow>>;
+    fn list_pending_retries(&self, repo_path: &Path) -> Result<HashSet<String>>;
+}
+
+impl AgentStatsMethods for Database {
+    fn list_session_outcomes(&self, repo_path: &Path) -> Result<Vec<SessionOutcomeRow>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.original_agent_type, s.status, s.session_state, s.created_at,
+                    s.reviewed_at, s.merged_at, s.retry_of, g.files_changed,
+                    g.lines_added + g.lines_removed
+             FROM sessions s
+             LEFT JOIN git_stats g ON g.session_id = s.id
+             WHERE s.repository_path = ?1",
+        )?;
+        let rows = stmt.query_map(params![repo_path.to_string_lossy()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, Option<i64>>(5)?,
+                row.get::<_, Option<i64>>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<u32>>(8)?,
+                row.get::<_, Option<u32>>(9)?,
+            ))
+        })?;
+
+        let mut outcomes = Vec::new();
+        for row in rows {
+            let (
+                id,
+                agent_type,
+                status,
+                session_state,
+                created_at,
+                reviewed_at,
+                merged_at,
+                retry_of,
+                files_changed,
+                lines_changed,
+            ) = row?;
+            outcomes.push(SessionOutcomeRow {
+                id,
+                agent_type,
+                status: status.parse().unwrap_or(SessionStatus::Active),
+                session_state: session_state
+                    .as_deref()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(SessionState::Running),
+                created_at: utc_from_epoch_seconds_lossy(created_at),
+                reviewed_at: utc_from_epoch_seconds_lossy_opt(reviewed_at),
+                merged_at: utc_from_epoch_seconds_lossy_opt(merged_at),
+                retry_of,
+                files_changed,
+                lines_changed,
+            });
+        }
+        Ok(outcomes)
+    }
+
+    fn list_pending_retries(&self, repo_path: &Path) -> Result<HashSet<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT retry_of FROM specs WHERE repository_path = ?1 AND retry_of IS NOT NULL",
+        )?;
+        let ids = stmt
+            .query_map(params![repo_path.to_string_lossy()], |row| {
+                row.get::<_, String>(0)
+            })?
+            .collect::<rusqlite::Result<HashSet<_>>>()?;
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str, agent: &str, status: SessionStatus, minutes_ago: i64) -> SessionOutcomeRow {
+        SessionOutcomeRow {
+            id: id.to_string(),
+            agent_type: Some(agent.to_string()),
+            status,
+            session_state: SessionState::Running,
+            created_at: Utc::now() - Duration::minutes(minutes_ago),
+            reviewed_at: None,
+            merged_at: None,
+            retry_of: None,
+            files_changed: None,
+            lines_changed: None,
+        }
+    }
+
+    #[test]
+    fn retried_lineage_counts_once_under_latest_attempt() {
+        let failed = row("a1", "claude", SessionStatus::Cancelled, 120);
+        let mut retry = row("a2", "codex", SessionStatus::Cancelled, 60);
+        retry.retry_of = Some("a1".into());
+        retry.reviewed_at = Some(Utc::now() - Duration::minutes(30));
+        retry.merged_at = Some(Utc::now() - Duration::minutes(20));
+        retry.files_changed = Some(3);
+        retry.lines_changed = Some(40);
+
+        let stats = aggregate_agent_stats(&[failed, retry], &HashSet::new(), None);
+
+        assert_eq!(stats.len(), 1);
+        let codex = &stats[0];
+        assert_eq!(codex.agent_type, "codex");
+        assert_eq!(codex.started, 1);
+        assert_eq!(codex.retried, 1);
+        assert_eq!(codex.reviewed, 1);
+        assert_eq!(codex.merged, 1);
+        assert_eq!(codex.cancelled_without_merge, 0);
+        assert_eq!(codex.avg_seconds_to_review, Some(90 * 60));
+        assert_eq!(codex.avg_lines_changed, Some(40.0));
+    }
+
+    #[test]
+    fn cancelled_sessions_count_as_failures_unless_awaiting_retry() {
+        let abandoned = row("b1", "claude", SessionStatus::Cancelled, 10);
+        let awaiting = row("b2", "claude", SessionStatus::Cancelled, 10);
+        let running = row("b3", "claude", SessionStatus::Active, 10);
+        let old = row("b4", "claude", SessionStatus::Cancelled, 60 * 24 * 40);
+
+        let pending: HashSet<String> = ["b2".to_string()].into_iter().collect();
+        let stats = aggregate_agent_stats(
+            &[abandoned, awaiting, running, old],
+            &pending,
+            AgentStatsPeriod::Month.cutoff(Utc::now()),
+        );
+
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].started, 3);
+        assert_eq!(stats[0].cancelled_without_merge, 1);
+        assert_eq!(stats[0].reviewed, 0);
+        assert_eq!(stats[0].avg_seconds_to_review, None);
+    }
+}
@@ -1,15 +1,84 @@
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::domains::git::service as git;
 
 static PROMPTED_SESSIONS: OnceLock<StdMutex<HashSet<PathBuf>>> = OnceLock::new();
 static RESERVED_NAMES: OnceLock<StdMutex<HashMap<PathBuf, HashSet<String>>>> = OnceLock::new();
 
 static REPO_LOCKS: OnceLock<StdMutex<HashMap<PathBuf, Arc<StdMutex<()>>>>> = OnceLock::new();
 
-type SpecContentMap = HashMap<String, (Option<String>, Option<String>)>;
+/// Spec content and initial prompt of a session.
+pub type SpecContent = (Option<String>, Option<String>);
+
+type SpecContentMap = HashMap<String, EtaggedSpecContent>;
 static SPEC_CONTENT_CACHE: OnceLock<StdMutex<SpecContentMap>> = OnceLock::new();
 
+/// Spec content together with its etag, a hash of the content computed once when the
+/// content is loaded so repeated fetches can be answered without re-sending the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EtaggedSpecContent {
+    pub content: SpecContent,
+    pub etag: String,
+}
+
+impl EtaggedSpecContent {
+    pub fn new(content: SpecContent) -> Self {
+        let etag = spec_content_etag(&content);
+        Self { content, etag }
+    }
+}
+
+pub fn spec_content_etag((spec_content, initial_prompt): &SpecContent) -> String {
+    let mut hasher = Sha256::new();
+    for part in [spec_content, initial_prompt] {
+        match part {
+            Some(text) => {
+                hasher.update([1]);
+                hasher.update((text.len() as u64).to_le_bytes());
+                hasher.update(text.as_bytes());
+            }
+            None => hasher.update([0]),
+        }
+    }
+    let hash = format!("{:x}", hasher.finalize());
+    hash[..16].to_string()
+}
+
+/// Conflict status older than this is still served, but triggers a background refresh.
+pub const CONFLICT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedConflicts {
+    pub has_conflicts: bool,
+    pub checked_at: Instant,
+}
+
+impl CachedConflicts {
+    pub fn is_stale(&self) -> bool {
+        self.checked_at.elapsed() >= CONFLICT_CACHE_TTL
+    }
+}
+
+#[derive(Default)]
+struct ConflictCache {
+    conflicts_by_id: HashMap<String, CachedConflicts>,
+    refreshing: HashSet<String>,
+}
+
+static CONFLICT_CACHE: OnceLock<StdMutex<ConflictCache>> = OnceLock::new();
+
+fn conflict_cache() -> std::sync::MutexGuard<'static, ConflictCache> {
+    CONFLICT_CACHE
+        .get_or_init(|| StdMutex::new(ConflictCache::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 #[derive(Clone)]
 pub struct SessionCacheManager {
     repo_path: PathBuf,
@@ -91,6 +160,10 @@ impl SessionCacheManager {
             let mut cache = spec_cache.lock().unwrap();
             cache.clear();
         }
+
+        if CONFLICT_CACHE.get().is_some() {
+            *conflict_cache() = ConflictCache::default();
+        }
     }
 }
 
@@ -98,17 +171,18 @@ fn make_cache_key(repo_path: &Path, name: &str) -> String {
     format!("{}:{}", repo_path.display(), name)
 }
 
-pub fn get_cached_spec_content(
-    repo_path: &Path,
-    name: &str,
-) -> Option<(Option<String>, Option<String>)> {
+pub fn get_cached_spec_content(repo_path: &Path, name: &str) -> Option<EtaggedSpecContent> {
     let cache = SPEC_CONTENT_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
     let cache = cache.lock().unwrap();
     let key = make_cache_key(repo_path, name);
     cache.get(&key).cloned()
 }
 
-pub fn cache_spec_content(repo_path: &Path, name: &str, content: (Option<String>, Option<String>)) {
+pub fn cache_spec_content(repo_path: &Path, name: &str, content: SpecContent) {
+    cache_etagged_spec_content(repo_path, name, EtaggedSpecContent::new(content));
+}
+
+pub fn cache_etagged_spec_content(repo_path: &Path, name: &str, content: EtaggedSpecContent) {
     let cache = SPEC_CONTENT_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
     let mut cache = cache.lock().unwrap();
     let key = make_cache_key(repo_path, name);
@@ -127,3 +201,52 @@ pub fn clear_session_prompted_non_test(worktree_path: &Path) {
     let mut prompted = set.lock().unwrap();
     prompted.remove(worktree_path);
 }
+
+/// Last known conflict status of a session, keyed by session id.
+pub fn cached_conflicts(session_id: &str) -> Option<CachedConflicts> {
+    conflict_cache().conflicts_by_id.get(session_id).copied()
+}
+
+pub fn cache_conflicts(session_id: &str, has_conflicts: bool) {
+    conflict_cache().conflicts_by_id.insert(
+        session_id.to_string(),
+        CachedConflicts {
+            has_conflicts,
+            checked_at: Instant::now(),
+        },
+    );
+}
+
+pub fn invalidate_conflicts(session_id: &str) {
+    conflict_cache().conflicts_by_id.remove(session_id);
+}
+
+/// Runs conflict detection for `(session_id, worktree_path)` pairs on a background
+/// thread and stores the results. Sessions already being refreshed are skipped; returns
+/// `None` when nothing was left to do.
+pub fn refresh_conflicts_in_background(targets: Vec<(String, PathBuf)>) -> Option<JoinHandle<()>> {
+    let targets: Vec<(String, PathBuf)> = {
+        let mut cache = conflict_cache();
+        targets
+            .into_iter()
+            .filter(|(session_id, _)| cache.refreshing.insert(session_id.clone()))
+            .collect()
+    };
+    if targets.is_empty() {
+        return None;
+    }
+
+    Some(std::thread::spawn(move || {
+        for (session_id, worktree_path) in targets {
+            let has_conflicts = git::has_conflicts(&worktree_path).unwrap_or_else(|err| {
+                log::warn!(
+                    "Conflict detection failed for {}: {err}",
+                    worktree_path.display()
+                );
+                false
+            });
+            cache_conflicts(&session_id, has_conflicts);
+            conflict_cache().refreshing.remove(&session_id);
+        }
+    }))
+}
@@ -0,0 +1,425 @@
+use crate::infrastructure::database::Database;
+use crate::infrastructure::database::timestamps::utc_from_epoch_millis_lossy;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Create,
+    Cancel,
+    Merge,
+    SkipPermissionsLaunch,
+}
+
+impl OperationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationKind::Create => "create",
+            OperationKind::Cancel => "cancel",
+            OperationKind::Merge => "merge",
+            OperationKind::SkipPermissionsLaunch => "skip_permissions_launch",
+        }
+    }
+}
+
+impl FromStr for OperationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "create" => Ok(OperationKind::Create),
+            "cancel" => Ok(OperationKind::Cancel),
+            "merge" => Ok(OperationKind::Merge),
+            "skip_permissions_launch" => Ok(OperationKind::SkipPermissionsLaunch),
+            other => Err(anyhow!("Unknown journal operation kind: {other}")),
+        }
+    }
+}
+
+/// A begin record for a session operation. It carries enough information to find
+/// partial artifacts (worktree, branch, DB row) if the app dies before the end record.
+/// Audit-only kinds are written already finished and carry the actor and agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub id: String,
+    pub repository_path: PathBuf,
+    pub kind: OperationKind,
+    pub session_id: Option<String>,
+    pub session_name: String,
+    pub branch: Option<String>,
+    pub worktree_path: Option<PathBuf>,
+    pub owns_branch: bool,
+    pub base_commit: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub actor: Option<String>,
+    pub agent_type: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(repository_path: &Path, kind: OperationKind, session_name: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            repository_path: repository_path.to_path_buf(),
+            kind,
+            session_id: None,
+            session_name: session_name.to_string(),
+            branch: None,
+            worktree_path: None,
+            owns_branch: false,
+            base_commit: None,
+            started_at: Utc::now(),
+            actor: None,
+            agent_type: None,
+        }
+    }
+}
+
+pub trait OperationJournalMethods {
+    fn begin_operation(&self, entry: &JournalEntry) -> Result<()>;
+    fn end_operation(&self, id: &str) -> Result<()>;
+    fn list_unfinished_operations(&self, repo_path: &Path) -> Result<Vec<JournalEntry>>;
+    fn record_audit_entry(&self, entry: &JournalEntry) -> Result<()>;
+    fn list_operations_of_kind(
+        &self,
+        repo_path: &Path,
+        kind: OperationKind,
+    ) -> Result<Vec<JournalEntry>>;
+}
+
+const JOURNAL_COLUMNS: &str = "id, repository_path, kind, session_id, session_name, branch, worktree_path, owns_branch, base_commit, started_at, actor, agent_type";
+
+fn insert_entry(
+    conn: &rusqlite::Connection,
+    entry: &JournalEntry,
+    finished_at: Option<i64>,
+) -> Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT INTO operation_journal ({JOURNAL_COLUMNS}, finished_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+        ),
+        params![
+            entry.id,
+            entry.repository_path.to_string_lossy(),
+            entry.kind.as_str(),
+            entry.session_id,
+            entry.session_name,
+            entry.branch,
+            entry
+                .worktree_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            entry.owns_branch,
+            entry.base_commit,
+            entry.started_at.timestamp_millis(),
+            entry.actor,
+            entry.agent_type,
+            finished_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn query_entries(
+    conn: &rusqlite::Connection,
+    filter: &str,
+    args: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<JournalEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {JOURNAL_COLUMNS}
+         FROM operation_journal
+         WHERE {filter}
+         ORDER BY started_at ASC"
+    ))?;
+    let rows = stmt.query_map(args, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<String>>(5)?,
+            row.get::<_, Option<String>>(6)?,
+            row.get::<_, bool>(7)?,
+            row.get::<_, Option<String>>(8)?,
+            row.get::<_, i64>(9)?,
+            row.get::<_, Option<String>>(10)?,
+            row.get::<_, Option<String>>(11)?,
+        ))
+    })?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let (
+            id,
+            repository_path,
+            kind,
+            session_id,
+            session_name,
+            branch,
+            worktree_path,
+            owns_branch,
+            base_commit,
+            started_at,
+            actor,
+            agent_type,
+        ) = row?;
+        let kind = match OperationKind::from_str(&kind) {
+            Ok(kind) => kind,
+            Err(e) => {
+                log::warn!("Skipping journal entry {id}: {e}");
+                continue;
+            }
+        };
+        entries.push(JournalEntry {
+            id,
+            repository_path: PathBuf::from(repository_path),
+            kind,
+            session_id,
+            session_name,
+            branch,
+            worktree_path: worktree_path.map(PathBuf::from),
+            owns_branch,
+            base_commit,
+            started_at: utc_from_epoch_millis_lossy(started_at),
+            actor,
+            agent_type,
+        });
+    }
+    Ok(entries)
+}
+
+impl OperationJournalMethods for Database {
+    fn begin_operation(&self, entry: &JournalEntry) -> Result<()> {
+        let conn = self.get_conn()?;
+        insert_entry(&conn, entry, None)
+    }
+
+    fn end_operation(&self, id: &str) -> Result<()>;
+    fn list_unfinished_operations(&self, repo_path: &Path) -> Result<Vec<JournalEntry>>;
+    fn record_audit_entry(&self, entry: &JournalEntry) -> Result<()>;
+    fn list_operations_of_kind(
+        &self,
+        repo_path: &Path,
+        kind: OperationKind,
+    ) -> Result<Vec<JournalEntry>>;
+}
+
+const JOURNAL_COLUMNS: &str = "id, repository_path, kind, session_id, session_name, branch, worktree_path, owns_branch, base_commit, started_at, actor, agent_type";
+
+fn insert_entry(
+    conn: &rusqlite::Connection,
+    entry: &JournalEntry,
+    finished_at: Option<i64>,
+) -> Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT INTO operation_journal ({JOURNAL_COLUMNS}, finished_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+        ),
+        params![
+            entry.id,
+            entry.repository_path.to_string_lossy(),
+            entry.kind.as_str(),
+            entry.session_id,
+            entry.session_name,
+            entry.branch,
+            entry
+                .worktree_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            entry.owns_branch,
+            entry.base_commit,
+            entry.started_at.timestamp_millis(),
+            entry.actor,
+            entry.agent_type,
+            finished_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn query_entries(
+    conn: &rusqlite::Connection,
+    filter: &str,
+    args: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<JournalEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {JOURNAL_COLUMNS}
+         FROM operation_journal
+         WHERE {filter}
+         ORDER BY started_at ASC"
+    ))?;
+    let rows = stmt.query_map(args, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<String>>(5)?,
+            row.get::<_, Option<String>>(6)?,
+            row.get::<_, bool>(7)?,
+            row.get::<_, Option<String>>(8)?,
+            row.get::<_, i64>(9)?,
+            row.get::<_, Option<String>>(10)?,
+            row.get::<_, Option<String>>(11)?,
+        ))
+    })?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let (
+            id,
+            repository_path,
+            kind,
+            session_id,
+            session_name,
+            branch,
+            worktree_path,
+            owns_branch,
+            base_commit,
+            started_at,
+            actor,
+            agent_type,
+        ) = row?;
+        let kind = match OperationKind::from_str(&kind) {
+            Ok(kind) => kind,
+            Err(e) => {
+                log::warn!("Skipping journal entry {id}: {e}");
+                continue;
+            }
+        };
+        entries.push(JournalEntry {
+            id,
+            repository_path: PathBuf::from(repository_path),
+            kind,
+            session_id,
+            session_name,
+            branch,
+            worktree_path: worktree_path.map(PathBuf::from),
+            owns_branch,
+            base_commit,
+            started_at: utc_from_epoch_millis_lossy(started_at),
+            actor,
+            agent_type,
+        });
+    }
+    Ok(entries)
+}
+
+impl OperationJournalMethods for Database {
+    fn begin_operation(&self, entry: &JournalEntry) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO operation_journal (id, repository_path, kind, session_id, session_name, branch, worktree_path, owns_branch, base_commit, started_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                entry.id,
+                entry.repository_path.to_string_lossy(),
+                entry.kind.as_str(),
+                entry.session_id,
+                entry.session_name,
+                entry.branch,
+                entry
+                    .worktree_path
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string()),
+                entry.owns_branch,
+                entry.base_commit,
+                entry.started_at.timestamp_millis(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn end_operation(&self, id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE operation_journal SET finished_at = ?1 WHERE id = ?2",
+            params![Utc::now().timestamp_millis(), id],
+        )?;
+        Ok(())
+    }
+
+    fn list_unfinished_operations(&self, repo_path: &Path) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_conn()?;
+        query_entries(
+            &conn,
+            "repository_path = ?1 AND finished_at IS NULL",
+            &[&repo_path.to_string_lossy()],
+        )
+    }
+
+    fn record_audit_entry(&self, entry: &JournalEntry) -> Result<()> {
+        let conn = self.get_conn()?;
+        insert_entry(&conn, entry, Some(entry.started_at.timestamp_millis()))
+    }
+
+    fn list_operations_of_kind(
+        &self,
+        repo_path: &Path,
+        kind: OperationKind,
+    ) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_conn()?;
+        query_entries(
+            &conn,
+            "repository_path = ?1 AND kind = ?2",
+            &[&repo_path.to_string_lossy(), &kind.as_str()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn unfinished_operations_exclude_ended_entries() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        let repo = temp.path().join("repo");
+
+        let mut open = JournalEntry::new(&repo, OperationKind::Create, "alpha");
+        open.branch = Some("schaltwerk/alpha".into());
+        open.worktree_path = Some(repo.join(".schaltwerk/worktrees/alpha"));
+        open.owns_branch = true;
+        let closed = JournalEntry::new(&repo, OperationKind::Cancel, "beta");
+
+        db.begin_operation(&open).unwrap();
+        db.begin_operation(&closed).unwrap();
+        db.end_operation(&closed.id).unwrap();
+
+        let unfinished = db.list_unfinished_operations(&repo).unwrap();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].id, open.id);
+        assert_eq!(unfinished[0].kind, OperationKind::Create);
+        assert!(unfinished[0].owns_branch);
+        assert_eq!(unfinished[0].worktree_path, open.worktree_path);
+    }
+
+    #[test]
+    fn audit_entries_are_closed_and_keep_actor() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        let repo = temp.path().join("repo");
+
+        let mut audit = JournalEntry::new(&repo, OperationKind::SkipPermissionsLaunch, "gamma");
+        audit.actor = Some("jane".into());
+        audit.agent_type = Some("claude".into());
+        db.record_audit_entry(&audit).unwrap();
+
+        assert!(db.list_unfinished_operations(&repo).unwrap().is_empty());
+        let audits = db
+            .list_operations_of_kind(&repo, OperationKind::SkipPermissionsLaunch)
+            .unwrap();
+        assert_eq!(audits.len(), 1);
+        assert_eq!(audits[0].actor.as_deref(), Some("jane"));
+        assert_eq!(audits[0].agent_type.as_deref(), Some("claude"));
+    }
+}
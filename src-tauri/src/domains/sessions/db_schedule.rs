@@ -0,0 +1,239 @@
+use crate::infrastructure::database::Database;
+use crate::infrastructure::database::timestamps::{
+    utc_from_epoch_seconds_lossy, utc_from_epoch_seconds_lossy_opt,
+};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, params};
+use serde::Serialize;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledSpecStatus {
+    Pending,
+    Started,
+    Skipped,
+    Failed,
+    Cancelled,
+}
+
+impl ScheduledSpecStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledSpecStatus::Pending => "pending",
+            ScheduledSpecStatus::Started => "started",
+            ScheduledSpecStatus::Skipped => "skipped",
+            ScheduledSpecStatus::Failed => "failed",
+            ScheduledSpecStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl FromStr for ScheduledSpecStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(ScheduledSpecStatus::Pending),
+            "started" => Ok(ScheduledSpecStatus::Started),
+            "skipped" => Ok(ScheduledSpecStatus::Skipped),
+            "failed" => Ok(ScheduledSpecStatus::Failed),
+            "cancelled" => Ok(ScheduledSpecStatus::Cancelled),
+            other => Err(anyhow!("Unknown scheduled spec status: {other}")),
+        }
+    }
+}
+
+/// A spec queued to start no earlier than `not_before` (UTC), with the launch
+/// configuration to pass to `start_spec_session_with_config`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduledSpec {
+    pub id: String,
+    pub spec_name: String,
+    pub not_before: DateTime<Utc>,
+    pub base_branch: Option<String>,
+    pub agent_type: Option<String>,
+    pub skip_permissions: Option<bool>,
+    /// Drop the entry instead of starting it when its time passed while the project
+    /// was not open.
+    pub skip_if_late: bool,
+    pub status: ScheduledSpecStatus,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+pub trait ScheduledSpecMethods {
+    fn create_scheduled_spec(&self, repo_path: &Path, entry: &ScheduledSpec) -> Result<()>;
+    fn list_scheduled_specs(&self, repo_path: &Path) -> Result<Vec<ScheduledSpec>>;
+    fn get_scheduled_spec(&self, repo_path: &Path, id: &str) -> Result<Option<ScheduledSpec>>;
+    fn finish_scheduled_spec(
+        &self,
+        repo_path: &Path,
+        id: &str,
+        status: ScheduledSpecStatus,
+        error: Option<&str>,
+    ) -> Result<()>;
+}
+
+const SCHEDULE_COLUMNS: &str = "id, spec_name, not_before, base_branch, agent_type, skip_permissions, skip_if_late, status, created_at, finished_at, error";
+
+fn row_to_scheduled_spec(row: &rusqlite::Row<'_>) -> rusqlite::Result<ScheduledSpec> {
+    let status: String = row.get(7)?;
+    let status =
+        ScheduledSpecStatus::from_str(&status).map_err(|_e| rusqlite::Error::InvalidQuery)?;
+    Ok(ScheduledSpec {
+        id: row.get(0)?,
+        spec_name: row.get(1)?,
+        not_before: utc_from_epoch_seconds_lossy(row.get(2)?),
+        base_branch: row.get(3)?,
+        agent_type: row.get(4)?,
+        skip_permissions: row.get(5)?,
+        skip_if_late: row.get(6)?,
+        status,
+        created_at: utc_from_epoch_seconds_lossy(row.get(8)?),
+        finished_at: utc_from_epoch_seconds_lossy_opt(row.get(9)?),
+        error: row.get(10)?,
+    })
+}
+
+impl ScheduledSpecMethods for Database {
+    fn create_scheduled_spec(&self, repo_path: &Path, entry: &ScheduledSpec) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            &format!(
+                "INSERT INTO scheduled_specs (repository_path, {SCHEDULE_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"
+            ),
+            params![
+                repo_path.to_string_lossy(),
+                entry.id,
+                entry.spec_name,
+                entry.not_before.timestamp(),
+                entry.base_branch,
+                entry.agent_type,
+                entry.skip_permissions,
+                entry.skip_if_late,
+                entry.status.as_str(),
+                entry.created_at.timestamp(),
+                entry.finished_at.map(|t| t.timestamp()),
+                entry.error,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn list_scheduled_specs(&self, repo_path: &Path) -> Result<Vec<ScheduledSpec>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SCHEDULE_COLUMNS}
+             FROM scheduled_specs
+             WHERE repository_path = ?1
+             ORDER BY not_before ASC, created_at ASC"
+        ))?;
+        let rows = stmt.query_map(params![repo_path.to_string_lossy()], row_to_scheduled_spec)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+
+    fn get_scheduled_spec(&self, repo_path: &Path, id: &str) -> Result<Option<ScheduledSpec>> {
+        let conn = self.get_conn()?;
+        Ok(conn
+            .query_row(
+                &format!(
+                    "SELECT {SCHEDULE_COLUMNS}
+                     FROM scheduled_specs
+                     WHERE repository_path = ?1 AND id = ?2"
+                ),
+                params![repo_path.to_string_lossy(), id],
+                row_to_scheduled_spec,
+            )
+            .optional()?)
+    }
+
+    fn finish_scheduled_spec(
+        &self,
+        repo_path: &Path,
+        id: &str,
+        status: ScheduledSpecStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE scheduled_specs
+             SET status = ?1, error = ?2, finished_at = ?3
+             WHERE repository_path = ?4 AND id = ?5",
+            params![
+                status.as_str(),
+                error,
+                Utc::now().timestamp(),
+                repo_path.to_string_lossy(),
+                id,
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn entry(id: &str, spec_name: &str, not_before: DateTime<Utc>) -> ScheduledSpec {
+        ScheduledSpec {
+            id: id.to_string(),
+            spec_name: spec_name.to_string(),
+            not_before,
+            base_branch: None,
+            agent_type: Some("claude".to_string()),
+            skip_permissions: None,
+            skip_if_late: true,
+            status: ScheduledSpecStatus::Pending,
+            created_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            finished_at: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn scheduled_specs_are_per_project_and_ordered_by_time() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        let repo = temp.path().join("repo");
+        let other = temp.path().join("other");
+
+        let late = entry(
+            "b",
+            "late",
+            Utc.with_ymd_and_hms(2026, 1, 2, 18, 0, 0).unwrap(),
+        );
+        let early = entry(
+            "a",
+            "early",
+            Utc.with_ymd_and_hms(2026, 1, 2, 6, 0, 0).unwrap(),
+        );
+        db.create_scheduled_spec(&repo, &late).unwrap();
+        db.create_scheduled_spec(&repo, &early).unwrap();
+        db.create_scheduled_spec(&other, &entry("c", "elsewhere", early.not_before))
+            .unwrap();
+
+        let listed = db.list_scheduled_specs(&repo).unwrap();
+        assert_eq!(listed, vec![early.clone(), late.clone()]);
+
+        db.finish_scheduled_spec(&repo, "a", ScheduledSpecStatus::Failed, Some("boom"))
+            .unwrap();
+        let failed = db.get_scheduled_spec(&repo, "a").unwrap().unwrap();
+        assert_eq!(failed.status, ScheduledSpecStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("boom"));
+        assert!(failed.finished_at.is_some());
+        assert!(db.get_scheduled_spec(&other, "a").unwrap().is_none());
+    }
+}
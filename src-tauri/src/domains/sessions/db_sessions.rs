@@ -1,11 +1,16 @@
-use crate::domains::sessions::entity::{Session, SessionState, SessionStatus};
+use crate::domains::agents::EnvOverrides;
+use crate::domains::sessions::entity::{
+    GitStats, Session, SessionLaunchOverrides, SessionLaunchSnapshot, SessionState,
+    SessionStateTransition, SessionStatus, StateTransitionReason,
+};
+use crate::domains::sessions::utils::default_worktree_base_dir;
 use crate::infrastructure::database::timestamps::{
     utc_from_epoch_seconds_lossy, utc_from_epoch_seconds_lossy_opt,
 };
-use crate::infrastructure::database::Database;
+use crate::infrastructure::database::{Database, ProjectConfigMethods};
 use anyhow::Result;
 use chrono::Utc;
-use rusqlite::{Result as SqlResult, ToSql, params};
+use rusqlite::{OptionalExtension, Result as SqlResult, ToSql, params};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -35,6 +40,13 @@ pub trait SessionMethods {
     fn update_session_parent_branch(&self, id: &str, new_parent_branch: &str) -> Result<()>;
     fn update_session_ready_to_merge(&self, id: &str, ready: bool) -> Result<()>;
     fn update_session_state(&self, id: &str, state: SessionState) -> Result<()>;
+    fn update_session_state_with_reason(
+        &self,
+        id: &str,
+        state: SessionState,
+        reason: StateTransitionReason,
+    ) -> Result<()>;
+    fn get_state_history(&self, session_id: &str) -> Result<Vec<SessionStateTransition>>;
     fn update_spec_content(&self, id: &str, content: &str) -> Result<()>;
     fn append_spec_content(&self, id: &str, content: &str) -> Result<()>;
     fn update_session_initial_prompt(&self, id: &str, prompt: &str) -> Result<()>;
@@ -55,7 +67,13 @@ pub trait SessionMethods {
         group_id: Option<&str>,
         version_number: Option<i32>,
     ) -> Result<()>;
+    fn set_version_group_label(&self, group_id: &str, label: Option<&str>) -> Result<usize>;
+    fn get_version_group_label(&self, group_id: &str) -> Result<Option<String>>;
     fn update_session_epic_id(&self, id: &str, epic_id: Option<&str>) -> Result<()>;
+    fn set_session_group(&self, id: &str, group: Option<&str>) -> Result<()>;
+    fn list_session_groups(&self, repo_path: &Path) -> Result<Vec<String>>;
+    fn set_session_recording(&self, id: &str, enabled: bool) -> Result<()>;
+    fn list_recording_sessions(&self, repo_path: &Path) -> Result<Vec<String>>;
     fn delete_session(&self, id: &str) -> Result<()>;
     fn update_session_pr_info(
         &self,
@@ -63,6 +81,29 @@ pub trait SessionMethods {
         pr_number: Option<i64>,
         pr_url: Option<&str>,
     ) -> Result<()>;
+    fn save_git_stats(&self, stats: &GitStats) -> Result<()>;
+    fn mark_session_merged(&self, id: &str) -> Result<()>;
+    fn is_session_merged(&self, id: &str) -> Result<bool>;
+    fn set_session_retry_of(&self, id: &str, retry_of: Option<&str>) -> Result<()>;
+    fn set_session_launch_overrides(
+        &self,
+        id: &str,
+        overrides: &SessionLaunchOverrides,
+    ) -> Result<()>;
+    fn get_session_launch_overrides(&self, id: &str) -> Result<SessionLaunchOverrides>;
+    fn set_session_extra_cli_args(&self, id: &str, extra_cli_args: Option<&str>) -> Result<()>;
+    fn get_session_extra_cli_args(&self, id: &str) -> Result<Option<String>>;
+    fn set_session_env_overrides(&self, id: &str, overrides: &EnvOverrides) -> Result<()>;
+    fn get_session_env_overrides(&self, id: &str) -> Result<EnvOverrides>;
+    fn set_session_launch_snapshot(&self, id: &str, snapshot: &SessionLaunchSnapshot)
+    -> Result<()>;
+    fn get_session_launch_snapshot(&self, id: &str) -> Result<Option<SessionLaunchSnapshot>>;
+    fn set_session_stash(&self, id: &str, stash_oid: Option<&str>) -> Result<()>;
+    fn get_session_stash(&self, id: &str) -> Result<Option<String>>;
+    fn list_stashed_session_ids(&self, repo_path: &Path) -> Result<Vec<String>>;
+    fn set_session_setup_failed(&self, id: &str, failed: bool) -> Result<()>;
+    fn list_setup_failed_session_ids(&self, repo_path: &Path) -> Result<Vec<String>>;
+    fn get_git_stats(&self, session_id: &str) -> Result<Option<GitStats>>;
 }
 
 const SQLITE_MAX_VARIABLE_NUMBER: usize = 999;
@@ -74,6 +115,7 @@ struct SessionSummaryRow {
     display_name: Option<String>,
     version_group_id: Option<String>,
     version_number: Option<i32>,
+    version_group_label: Option<String>,
     epic_id: Option<String>,
     repository_path: PathBuf,
     repository_name: String,
@@ -95,6 +137,7 @@ struct SessionSummaryRow {
     amp_thread_id: Option<String>,
     pr_number: Option<i64>,
     pr_url: Option<String>,
+    group: Option<String>,
 }
 
 impl Database {
@@ -124,6 +167,7 @@ impl Database {
                     display_name: summary.display_name,
                     version_group_id: summary.version_group_id,
                     version_number: summary.version_number,
+                    version_group_label: summary.version_group_label,
                     epic_id: summary.epic_id,
                     repository_path: summary.repository_path,
                     repository_name: summary.repository_name,
@@ -147,6 +191,7 @@ impl Database {
                     amp_thread_id: summary.amp_thread_id,
                     pr_number: summary.pr_number,
                     pr_url: summary.pr_url,
+                    group: summary.group,
                 }
             })
             .collect())
@@ -196,8 +241,8 @@ impl SessionMethods for Database {
                 branch, parent_branch, original_parent_branch, worktree_path,
                 status, created_at, updated_at, last_activity, initial_prompt, ready_to_merge,
                 original_agent_type, original_skip_permissions, pending_name_generation, was_auto_generated,
-                spec_content, session_state, resume_allowed, amp_thread_id, pr_number, pr_url
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)",
+                spec_content, session_state, resume_allowed, amp_thread_id, pr_number, pr_url, session_group, version_group_label
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30)",
             params![
                 session.id,
                 session.name,
@@ -227,6 +272,8 @@ impl SessionMethods for Database {
                 session.amp_thread_id,
                 session.pr_number,
                 session.pr_url,
+                session.group,
+                session.version_group_label,
             ],
         )?;
 
@@ -241,7 +288,7 @@ impl SessionMethods for Database {
                     branch, parent_branch, original_parent_branch, worktree_path,
                     status, created_at, updated_at, last_activity, initial_prompt, ready_to_merge,
                     original_agent_type, original_skip_permissions, pending_name_generation, was_auto_generated,
-                    spec_content, session_state, resume_allowed, amp_thread_id, pr_number, pr_url
+                    spec_content, session_state, resume_allowed, amp_thread_id, pr_number, pr_url, session_group, version_group_label
              FROM sessions
              WHERE repository_path = ?1 AND name = ?2"
         )?;
@@ -253,6 +300,7 @@ impl SessionMethods for Database {
                 display_name: row.get(2).ok(),
                 version_group_id: row.get(3).ok(),
                 version_number: row.get(4).ok(),
+                version_group_label: row.get(29).ok(),
                 epic_id: row.get(5).ok(),
                 repository_path: PathBuf::from(row.get::<_, String>(6)?),
                 repository_name: row.get(7)?,
@@ -283,6 +331,7 @@ impl SessionMethods for Database {
                 amp_thread_id: row.get(25).ok(),
                 pr_number: row.get(26).ok(),
                 pr_url: row.get(27).ok(),
+                group: row.get(28).ok(),
             })
         })?;
 
@@ -297,7 +346,7 @@ impl SessionMethods for Database {
                     branch, parent_branch, original_parent_branch, worktree_path,
                     status, created_at, updated_at, last_activity, initial_prompt, ready_to_merge,
                     original_agent_type, original_skip_permissions, pending_name_generation, was_auto_generated,
-                    spec_content, session_state, resume_allowed, amp_thread_id, pr_number, pr_url
+                    spec_content, session_state, resume_allowed, amp_thread_id, pr_number, pr_url, session_group, version_group_label
              FROM sessions
              WHERE id = ?1"
         )?;
@@ -309,6 +358,7 @@ impl SessionMethods for Database {
                 display_name: row.get(2).ok(),
                 version_group_id: row.get(3).ok(),
                 version_number: row.get(4).ok(),
+                version_group_label: row.get(29).ok(),
                 epic_id: row.get(5).ok(),
                 repository_path: PathBuf::from(row.get::<_, String>(6)?),
                 repository_name: row.get(7)?,
@@ -339,6 +389,7 @@ impl SessionMethods for Database {
                 amp_thread_id: row.get(25).ok(),
                 pr_number: row.get(26).ok(),
                 pr_url: row.get(27).ok(),
+                group: row.get(28).ok(),
             })
         })?;
 
@@ -380,7 +431,7 @@ impl SessionMethods for Database {
                         branch, parent_branch, original_parent_branch, worktree_path,
                         status, created_at, updated_at, last_activity, ready_to_merge,
                         original_agent_type, original_skip_permissions, pending_name_generation, was_auto_generated,
-                        session_state, resume_allowed, amp_thread_id, pr_number, pr_url
+                        session_state, resume_allowed, amp_thread_id, pr_number, pr_url, session_group, version_group_label
                  FROM sessions
                  WHERE repository_path = ?1
                  ORDER BY ready_to_merge ASC, last_activity DESC",
@@ -393,6 +444,7 @@ impl SessionMethods for Database {
                     display_name: row.get(2).ok(),
                     version_group_id: row.get(3).ok(),
                     version_number: row.get(4).ok(),
+                    version_group_label: row.get(27).ok(),
                     epic_id: row.get(5).ok(),
                     repository_path: PathBuf::from(row.get::<_, String>(6)?),
                     repository_name: row.get(7)?,
@@ -421,6 +473,7 @@ impl SessionMethods for Database {
                     amp_thread_id: row.get(23).ok(),
                     pr_number: row.get(24).ok(),
                     pr_url: row.get(25).ok(),
+                    group: row.get(26).ok(),
                 })
             })?;
             rows.collect::<SqlResult<Vec<_>>>()?
@@ -450,7 +503,7 @@ impl SessionMethods for Database {
                         branch, parent_branch, original_parent_branch, worktree_path,
                         status, created_at, updated_at, last_activity, ready_to_merge,
                         original_agent_type, original_skip_permissions, pending_name_generation, was_auto_generated,
-                        session_state, resume_allowed, amp_thread_id, pr_number, pr_url
+                        session_state, resume_allowed, amp_thread_id, pr_number, pr_url, session_group, version_group_label
                  FROM sessions
                  WHERE status = 'active'
                  ORDER BY ready_to_merge ASC, last_activity DESC",
@@ -463,6 +516,7 @@ impl SessionMethods for Database {
                     display_name: row.get(2).ok(),
                     version_group_id: row.get(3).ok(),
                     version_number: row.get(4).ok(),
+                    version_group_label: row.get(27).ok(),
                     epic_id: row.get(5).ok(),
                     repository_path: PathBuf::from(row.get::<_, String>(6)?),
                     repository_name: row.get(7)?,
@@ -491,6 +545,7 @@ impl SessionMethods for Database {
                     amp_thread_id: row.get(23).ok(),
                     pr_number: row.get(24).ok(),
                     pr_url: row.get(25).ok(),
+                    group: row.get(26).ok(),
                 })
             })?;
             rows.collect::<SqlResult<Vec<_>>>()?
@@ -599,6 +654,60 @@ impl SessionMethods for Database {
         Ok(())
     }
 
+    fn set_session_group(&self, id: &str, group: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE sessions
+             SET session_group = ?1, updated_at = ?2
+             WHERE id = ?3",
+            params![group, Utc::now().timestamp(), id],
+        )?;
+
+        Ok(())
+    }
+
+    fn list_session_groups(&self, repo_path: &Path) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT session_group
+             FROM sessions
+             WHERE repository_path = ?1 AND status != 'cancelled' AND session_group IS NOT NULL
+             ORDER BY session_group COLLATE NOCASE",
+        )?;
+        let groups = stmt
+            .query_map(params![repo_path.to_string_lossy()], |row| row.get(0))?
+            .collect::<SqlResult<Vec<String>>>()?;
+        Ok(groups)
+    }
+
+    fn set_session_recording(&self, id: &str, enabled: bool) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE sessions
+             SET record_terminal_output = ?1, updated_at = ?2
+             WHERE id = ?3",
+            params![enabled, Utc::now().timestamp(), id],
+        )?;
+
+        Ok(())
+    }
+
+    fn list_recording_sessions(&self, repo_path: &Path) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name
+             FROM sessions
+             WHERE repository_path = ?1 AND status != 'cancelled' AND record_terminal_output = 1
+             ORDER BY name",
+        )?;
+        let names = stmt
+            .query_map(params![repo_path.to_string_lossy()], |row| row.get(0))?
+            .collect::<SqlResult<Vec<String>>>()?;
+        Ok(names)
+    }
+
     fn list_sessions_by_state(
         &self,
         repo_path: &Path,
@@ -617,7 +726,7 @@ impl SessionMethods for Database {
                         branch, parent_branch, original_parent_branch, worktree_path,
                         status, created_at, updated_at, last_activity, ready_to_merge,
                         original_agent_type, original_skip_permissions, pending_name_generation, was_auto_generated,
-                        session_state, resume_allowed, amp_thread_id, pr_number, pr_url
+                        session_state, resume_allowed, amp_thread_id, pr_number, pr_url, session_group, version_group_label
                  FROM sessions
                  WHERE repository_path = ?1 AND session_state = ?2
                  ORDER BY ready_to_merge ASC, last_activity DESC",
@@ -632,6 +741,7 @@ impl SessionMethods for Database {
                         display_name: row.get(2).ok(),
                         version_group_id: row.get(3).ok(),
                         version_number: row.get(4).ok(),
+                        version_group_label: row.get(27).ok(),
                         epic_id: row.get(5).ok(),
                         repository_path: PathBuf::from(row.get::<_, String>(6)?),
                         repository_name: row.get(7)?,
@@ -662,6 +772,7 @@ impl SessionMethods for Database {
                         amp_thread_id: row.get(23).ok(),
                         pr_number: row.get(24).ok(),
                         pr_url: row.get(25).ok(),
+                        group: row.get(26).ok(),
                     })
                 },
             )?;
@@ -685,18 +796,80 @@ impl SessionMethods for Database {
     }
 
     fn update_session_state(&self, id: &str, state: SessionState) -> Result<()> {
-        let conn = self.get_conn()?;
+        self.update_session_state_with_reason(id, state, StateTransitionReason::Manual)
+    }
 
-        conn.execute(
+    fn update_session_state_with_reason(
+        &self,
+        id: &str,
+        state: SessionState,
+        reason: StateTransitionReason,
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn.transaction()?;
+        let now = Utc::now().timestamp();
+
+        let from_state: Option<String> = tx
+            .query_row(
+                "SELECT session_state FROM sessions WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+
+        tx.execute(
             "UPDATE sessions
-             SET session_state = ?1, updated_at = ?2
+             SET session_state = ?1,
+                 updated_at = ?2,
+                 reviewed_at = CASE WHEN ?1 = 'reviewed' THEN COALESCE(reviewed_at, ?2) ELSE reviewed_at END
              WHERE id = ?3",
-            params![state.as_str(), Utc::now().timestamp(), id],
+            params![state.as_str(), now, id],
         )?;
 
+        if from_state.as_deref() != Some(state.as_str()) {
+            tx.execute(
+                "INSERT INTO session_state_history (session_id, from_state, to_state, timestamp, reason)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![id, from_state, state.as_str(), now, reason.as_str()],
+            )?;
+        }
+
+        tx.commit()?;
         Ok(())
     }
 
+    fn get_state_history(&self, session_id: &str) -> Result<Vec<SessionStateTransition>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT from_state, to_state, timestamp, reason
+             FROM session_state_history
+             WHERE session_id = ?1
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            let (from_state, to_state, timestamp, reason) = row?;
+            history.push(SessionStateTransition {
+                session_id: session_id.to_string(),
+                from_state: from_state.and_then(|s| s.parse().ok()),
+                to_state: to_state.parse().map_err(|e: String| anyhow::anyhow!(e))?,
+                timestamp: utc_from_epoch_seconds_lossy(timestamp),
+                reason: reason.parse().unwrap_or(StateTransitionReason::Manual),
+            });
+        }
+        Ok(history)
+    }
+
     fn update_spec_content(&self, id: &str, content: &str) -> Result<()> {
         let conn = self.get_conn()?;
 
@@ -768,6 +941,29 @@ impl SessionMethods for Database {
         Ok(())
     }
 
+    fn set_version_group_label(&self, group_id: &str, label: Option<&str>) -> Result<usize> {
+        let conn = self.get_conn()?;
+        let updated = conn.execute(
+            "UPDATE sessions SET version_group_label = ?1, updated_at = ?2 WHERE version_group_id = ?3",
+            params![label, Utc::now().timestamp(), group_id],
+        )?;
+        Ok(updated)
+    }
+
+    fn get_version_group_label(&self, group_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let label = conn
+            .query_row(
+                "SELECT version_group_label FROM sessions
+                 WHERE version_group_id = ?1 AND version_group_label IS NOT NULL
+                 LIMIT 1",
+                params![group_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(label)
+    }
+
     fn clear_session_run_state(&self, session_id: &str) -> Result<()> {
         let conn = self.get_conn()?;
         conn.execute(
@@ -817,9 +1013,9 @@ impl SessionMethods for Database {
         }
 
         // Calculate new worktree path based on new session name
-        let new_worktree_path = repo_path
-            .join(".schaltwerk")
-            .join("worktrees")
+        let new_worktree_path = self
+            .get_project_worktree_root(repo_path)?
+            .unwrap_or_else(|| default_worktree_base_dir(repo_path))
             .join(new_name);
 
         // Update the session name and worktree path
@@ -842,6 +1038,11 @@ impl SessionMethods for Database {
     fn delete_session(&self, id: &str) -> Result<()> {
         let conn = self.get_conn()?;
         conn.execute("DELETE FROM sessions WHERE id = ?1", params![id])?;
+        conn.execute("DELETE FROM git_stats WHERE session_id = ?1", params![id])?;
+        conn.execute(
+            "DELETE FROM session_state_history WHERE session_id = ?1",
+            params![id],
+        )?;
         Ok(())
     }
 
@@ -858,6 +1059,257 @@ impl SessionMethods for Database {
         )?;
         Ok(())
     }
+
+    fn save_git_stats(&self, stats: &GitStats) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO git_stats (session_id, files_changed, lines_added, lines_removed, has_uncommitted,
+                                    calculated_at, last_diff_change_ts, head_oid, parent_oid,
+                                    has_remote, remote_ahead, remote_behind, remote_oid)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(session_id) DO UPDATE SET
+                files_changed = excluded.files_changed,
+                lines_added = excluded.lines_added,
+                lines_removed = excluded.lines_removed,
+                has_uncommitted = excluded.has_uncommitted,
+                calculated_at = excluded.calculated_at,
+                last_diff_change_ts = excluded.last_diff_change_ts,
+                head_oid = excluded.head_oid,
+                parent_oid = excluded.parent_oid,
+                has_remote = excluded.has_remote,
+                remote_ahead = excluded.remote_ahead,
+                remote_behind = excluded.remote_behind,
+                remote_oid = excluded.remote_oid",
+            params![
+                stats.session_id,
+                stats.files_changed,
+                stats.lines_added,
+                stats.lines_removed,
+                stats.has_uncommitted,
+                stats.calculated_at.timestamp(),
+                stats.last_diff_change_ts,
+                stats.head_oid,
+                stats.parent_oid,
+                stats.has_remote,
+                stats.remote_ahead,
+                stats.remote_behind,
+                stats.remote_oid,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn mark_session_merged(&self, id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET merged_at = ?1 WHERE id = ?2",
+            params![Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    fn is_session_merged(&self, id: &str) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let merged_at: Option<i64> = conn.query_row(
+            "SELECT merged_at FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(merged_at.is_some())
+    }
+
+    fn set_session_retry_of(&self, id: &str, retry_of: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET retry_of = ?1 WHERE id = ?2",
+            params![retry_of, id],
+        )?;
+        Ok(())
+    }
+
+    fn set_session_launch_overrides(
+        &self,
+        id: &str,
+        overrides: &SessionLaunchOverrides,
+    ) -> Result<()> {
+        let json = if overrides.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(overrides)?)
+        };
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET launch_overrides = ?1, updated_at = ?2 WHERE id = ?3",
+            params![json, Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    fn get_session_launch_overrides(&self, id: &str) -> Result<SessionLaunchOverrides> {
+        let conn = self.get_conn()?;
+        let json: Option<String> = conn.query_row(
+            "SELECT launch_overrides FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        match json {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(SessionLaunchOverrides::default()),
+        }
+    }
+
+    fn set_session_extra_cli_args(&self, id: &str, extra_cli_args: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET extra_cli_args = ?1, updated_at = ?2 WHERE id = ?3",
+            params![extra_cli_args, Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    fn get_session_extra_cli_args(&self, id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let extra_cli_args = conn.query_row(
+            "SELECT extra_cli_args FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(extra_cli_args)
+    }
+
+    fn set_session_env_overrides(&self, id: &str, overrides: &EnvOverrides) -> Result<()> {
+        let json = if overrides.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(overrides)?)
+        };
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET env_overrides = ?1, updated_at = ?2 WHERE id = ?3",
+            params![json, Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    fn get_session_env_overrides(&self, id: &str) -> Result<EnvOverrides> {
+        let conn = self.get_conn()?;
+        let json: Option<String> = conn.query_row(
+            "SELECT env_overrides FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        match json {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(EnvOverrides::new()),
+        }
+    }
+
+    fn set_session_launch_snapshot(
+        &self,
+        id: &str,
+        snapshot: &SessionLaunchSnapshot,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET launch_snapshot = ?1 WHERE id = ?2",
+            params![serde_json::to_string(snapshot)?, id],
+        )?;
+        Ok(())
+    }
+
+    fn get_session_launch_snapshot(&self, id: &str) -> Result<Option<SessionLaunchSnapshot>> {
+        let conn = self.get_conn()?;
+        let json: Option<String> = conn.query_row(
+            "SELECT launch_snapshot FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        json.map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn set_session_stash(&self, id: &str, stash_oid: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET stash_oid = ?1, updated_at = ?2 WHERE id = ?3",
+            params![stash_oid, Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    fn get_session_stash(&self, id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let stash_oid = conn.query_row(
+            "SELECT stash_oid FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(stash_oid)
+    }
+
+    fn list_stashed_session_ids(&self, repo_path: &Path) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id FROM sessions WHERE repository_path = ?1 AND stash_oid IS NOT NULL",
+        )?;
+        let ids = stmt
+            .query_map(params![repo_path.to_string_lossy()], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(ids)
+    }
+
+    fn set_session_setup_failed(&self, id: &str, failed: bool) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE sessions SET setup_failed = ?1 WHERE id = ?2",
+            params![failed, id],
+        )?;
+        Ok(())
+    }
+
+    fn list_setup_failed_session_ids(&self, repo_path: &Path) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn
+            .prepare("SELECT id FROM sessions WHERE repository_path = ?1 AND setup_failed = 1")?;
+        let ids = stmt
+            .query_map(params![repo_path.to_string_lossy()], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(ids)
+    }
+
+    fn get_git_stats(&self, session_id: &str) -> Result<Option<GitStats>> {
+        let conn = self.get_conn()?;
+        let result = conn.query_row(
+            "SELECT session_id, files_changed, lines_added, lines_removed, has_uncommitted,
+                    calculated_at, last_diff_change_ts, head_oid, parent_oid,
+                    has_remote, remote_ahead, remote_behind, remote_oid
+             FROM git_stats WHERE session_id = ?1",
+            params![session_id],
+            |row| {
+                Ok(GitStats {
+                    session_id: row.get(0)?,
+                    files_changed: row.get(1)?,
+                    lines_added: row.get(2)?,
+                    lines_removed: row.get(3)?,
+                    has_uncommitted: row.get(4)?,
+                    calculated_at: utc_from_epoch_seconds_lossy(row.get(5)?),
+                    last_diff_change_ts: row.get(6)?,
+                    head_oid: row.get(7)?,
+                    parent_oid: row.get(8)?,
+                    has_remote: row.get(9)?,
+                    remote_ahead: row.get(10)?,
+                    remote_behind: row.get(11)?,
+                    remote_oid: row.get(12)?,
+                })
+            },
+        );
+        match result {
+            Ok(stats) => Ok(Some(stats)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -880,6 +1332,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.clone(),
             repository_name: "repo".to_string(),
@@ -903,6 +1356,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
 
         db.create_session(&session).expect("failed to create session");
@@ -930,6 +1384,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: PathBuf::from("/tmp/repo"),
             repository_name: "repo".to_string(),
@@ -953,6 +1408,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: Some(142),
             pr_url: Some("https://github.com/owner/repo/pull/142".to_string()),
+            group: None,
         };
 
         db.create_session(&session).expect("failed to create session");
@@ -978,6 +1434,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: PathBuf::from("/tmp/repo"),
             repository_name: "repo".to_string(),
@@ -1001,6 +1458,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
 
         db.create_session(&session).expect("failed to create session");
@@ -1019,6 +1477,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_session_group_assignment_and_listing() {
+        let db = Database::new_in_memory().expect("failed to build in-memory database");
+        let repo_path = PathBuf::from("/tmp/repo");
+
+        for name in ["alpha", "beta", "gamma"] {
+            let session = Session {
+                id: format!("{name}-id"),
+                name: name.to_string(),
+                display_name: None,
+                version_group_id: None,
+                version_number: None,
+                version_group_label: None,
+                epic_id: None,
+                repository_path: repo_path.clone(),
+                repository_name: "repo".to_string(),
+                branch: format!("schaltwerk/{name}"),
+                parent_branch: "main".to_string(),
+                original_parent_branch: Some("main".to_string()),
+                worktree_path: repo_path.join(format!(".schaltwerk/worktrees/{name}")),
+                status: SessionStatus::Active,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                last_activity: None,
+                initial_prompt: None,
+                ready_to_merge: false,
+                original_agent_type: None,
+                original_skip_permissions: None,
+                pending_name_generation: false,
+                was_auto_generated: false,
+                spec_content: None,
+                session_state: SessionState::Running,
+                resume_allowed: true,
+                amp_thread_id: None,
+                pr_number: None,
+                pr_url: None,
+                group: None,
+            };
+            db.create_session(&session).expect("failed to create session");
+        }
+
+        db.set_session_group("alpha-id", Some("frontend"))
+            .expect("failed to set group");
+        db.set_session_group("beta-id", Some("backend"))
+            .expect("failed to set group");
+        db.set_session_group("gamma-id", Some("frontend"))
+            .expect("failed to set group");
+
+        let loaded = db
+            .get_session_by_id("alpha-id")
+            .expect("failed to load session");
+        assert_eq!(loaded.group.as_deref(), Some("frontend"));
+
+        let groups = db
+            .list_session_groups(&repo_path)
+            .expect("failed to list groups");
+        assert_eq!(groups, vec!["backend".to_string(), "frontend".to_string()]);
+
+        db.set_session_group("beta-id", None)
+            .expect("failed to clear group");
+        let listed = db.list_sessions(&repo_path).expect("failed to list sessions");
+        let beta = listed.iter().find(|s| s.id == "beta-id").unwrap();
+        assert_eq!(beta.group, None);
+        assert_eq!(
+            db.list_session_groups(&repo_path).unwrap(),
+            vec!["frontend".to_string()]
+        );
+    }
+
     #[test]
     fn test_repo_order_index_structure_and_plan() {
         let db = Database::new_in_memory().expect("failed to build in-memory database");
@@ -1102,6 +1629,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.clone(),
             repository_name: "repo".to_string(),
@@ -1125,6 +1653,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
 
         db.create_session(&session).expect("failed to create session");
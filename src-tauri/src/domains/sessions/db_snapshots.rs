@@ -0,0 +1,172 @@
+use crate::infrastructure::database::Database;
+use crate::infrastructure::database::timestamps::utc_from_epoch_seconds_lossy;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, params};
+use serde::Serialize;
+use std::path::Path;
+
+/// Metadata of a worktree snapshot. The captured branch tip and uncommitted changes
+/// live in the git ref `ref_name`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionSnapshot {
+    pub id: String,
+    pub session_id: String,
+    pub label: String,
+    pub ref_name: String,
+    pub head_oid: String,
+    pub dirty_files: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+pub trait SessionSnapshotMethods {
+    fn create_session_snapshot(&self, repo_path: &Path, snapshot: &SessionSnapshot) -> Result<()>;
+    fn list_session_snapshots(
+        &self,
+        repo_path: &Path,
+        session_id: &str,
+    ) -> Result<Vec<SessionSnapshot>>;
+    fn get_session_snapshot(
+        &self,
+        repo_path: &Path,
+        session_id: &str,
+        id: &str,
+    ) -> Result<Option<SessionSnapshot>>;
+    fn delete_session_snapshots(&self, repo_path: &Path, session_id: &str) -> Result<usize>;
+}
+
+const SNAPSHOT_COLUMNS: &str = "id, session_id, label, ref_name, head_oid, dirty_files, created_at";
+
+fn row_to_snapshot(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionSnapshot> {
+    Ok(SessionSnapshot {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        label: row.get(2)?,
+        ref_name: row.get(3)?,
+        head_oid: row.get(4)?,
+        dirty_files: row.get(5)?,
+        created_at: utc_from_epoch_seconds_lossy(row.get(6)?),
+    })
+}
+
+impl SessionSnapshotMethods for Database {
+    fn create_session_snapshot(&self, repo_path: &Path, snapshot: &SessionSnapshot) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            &format!(
+                "INSERT INTO session_snapshots (repository_path, {SNAPSHOT_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            ),
+            params![
+                repo_path.to_string_lossy(),
+                snapshot.id,
+                snapshot.session_id,
+                snapshot.label,
+                snapshot.ref_name,
+                snapshot.head_oid,
+                snapshot.dirty_files,
+                snapshot.created_at.timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn list_session_snapshots(
+        &self,
+        repo_path: &Path,
+        session_id: &str,
+    ) -> Result<Vec<SessionSnapshot>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SNAPSHOT_COLUMNS}
+             FROM session_snapshots
+             WHERE repository_path = ?1 AND session_id = ?2
+             ORDER BY created_at DESC, rowid DESC"
+        ))?;
+        let rows = stmt.query_map(
+            params![repo_path.to_string_lossy(), session_id],
+            row_to_snapshot,
+        )?;
+
+        let mut snapshots = Vec::new();
+        for row in rows {
+            snapshots.push(row?);
+        }
+        Ok(snapshots)
+    }
+
+    fn get_session_snapshot(
+        &self,
+        repo_path: &Path,
+        session_id: &str,
+        id: &str,
+    ) -> Result<Option<SessionSnapshot>> {
+        let conn = self.get_conn()?;
+        Ok(conn
+            .query_row(
+                &format!(
+                    "SELECT {SNAPSHOT_COLUMNS}
+                     FROM session_snapshots
+                     WHERE repository_path = ?1 AND session_id = ?2 AND id = ?3"
+                ),
+                params![repo_path.to_string_lossy(), session_id, id],
+                row_to_snapshot,
+            )
+            .optional()?)
+    }
+
+    fn delete_session_snapshots(&self, repo_path: &Path, session_id: &str) -> Result<usize> {
+        let conn = self.get_conn()?;
+        Ok(conn.execute(
+            "DELETE FROM session_snapshots WHERE repository_path = ?1 AND session_id = ?2",
+            params![repo_path.to_string_lossy(), session_id],
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn snapshot(id: &str, session_id: &str, created_at: i64) -> SessionSnapshot {
+        SessionSnapshot {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            label: format!("Snapshot {id}"),
+            ref_name: format!("refs/schaltwerk/snapshots/session/{id}"),
+            head_oid: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            dirty_files: 3,
+            created_at: Utc.timestamp_opt(created_at, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn snapshots_are_listed_newest_first_and_deleted_per_session() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        let repo = temp.path().join("repo");
+
+        let older = snapshot("a", "s1", 100);
+        let newer = snapshot("b", "s1", 200);
+        db.create_session_snapshot(&repo, &older).unwrap();
+        db.create_session_snapshot(&repo, &newer).unwrap();
+        db.create_session_snapshot(&repo, &snapshot("c", "s2", 300))
+            .unwrap();
+
+        assert_eq!(
+            db.list_session_snapshots(&repo, "s1").unwrap(),
+            vec![newer, older.clone()]
+        );
+        assert_eq!(
+            db.get_session_snapshot(&repo, "s1", "a").unwrap(),
+            Some(older)
+        );
+        assert!(db.get_session_snapshot(&repo, "s2", "a").unwrap().is_none());
+
+        assert_eq!(db.delete_session_snapshots(&repo, "s1").unwrap(), 2);
+        assert!(db.list_session_snapshots(&repo, "s1").unwrap().is_empty());
+        assert_eq!(db.list_session_snapshots(&repo, "s2").unwrap().len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domains::agents::AgentOutputStatus;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -8,6 +9,12 @@ pub enum SortMode {
     Name,
     Created,
     LastEdited,
+    /// Sessions of the same group next to each other, ungrouped ones last.
+    Group,
+    /// Largest diff (additions + deletions) first; specs and sessions without stats last.
+    DiffSize,
+    /// Most recent of the stored activity and live terminal output first.
+    TerminalActivity,
 }
 
 impl FromStr for SortMode {
@@ -18,6 +25,9 @@ impl FromStr for SortMode {
             "name" => Ok(SortMode::Name),
             "created" => Ok(SortMode::Created),
             "last-edited" => Ok(SortMode::LastEdited),
+            "group" => Ok(SortMode::Group),
+            "diff-size" => Ok(SortMode::DiffSize),
+            "terminal-activity" => Ok(SortMode::TerminalActivity),
             _ => Err(format!("Invalid sort mode: {s}")),
         }
     }
@@ -28,6 +38,8 @@ pub enum FilterMode {
     Spec,
     Running,
     Reviewed,
+    /// Sessions in any state that carry this group, parsed from `group:<name>`.
+    Group(String),
 }
 
 impl FromStr for FilterMode {
@@ -38,7 +50,10 @@ impl FromStr for FilterMode {
             "all" | "running" => Ok(FilterMode::Running),
             "spec" => Ok(FilterMode::Spec),
             "reviewed" => Ok(FilterMode::Reviewed),
-            _ => Err(format!("Invalid filter mode: {s}")),
+            _ => match s.strip_prefix("group:").map(str::trim) {
+                Some(group) if !group.is_empty() => Ok(FilterMode::Group(group.to_string())),
+                _ => Err(format!("Invalid filter mode: {s}")),
+            },
         }
     }
 }
@@ -51,6 +66,10 @@ pub struct ChangedFile {
     pub deletions: u32,
     pub changes: u32,
     pub is_binary: Option<bool>,
+    /// Whether the change is (partly) in the index. Only set for orchestrator working
+    /// changes.
+    #[serde(default)]
+    pub staged: bool,
 }
 
 impl ChangedFile {
@@ -62,6 +81,7 @@ impl ChangedFile {
             deletions: 0,
             changes: 0,
             is_binary: None,
+            staged: false,
         }
     }
 }
@@ -75,6 +95,8 @@ pub struct Session {
     // DB-backed version grouping for parallel versions
     pub version_group_id: Option<String>,
     pub version_number: Option<i32>,
+    /// Human-readable name shared by every version in the group.
+    pub version_group_label: Option<String>,
     // Optional epic association for grouping sessions/specs
     pub epic_id: Option<String>,
     pub repository_path: PathBuf,
@@ -109,6 +131,9 @@ pub struct Session {
     pub pr_number: Option<i64>,
     // GitHub PR URL linked to this session
     pub pr_url: Option<String>,
+    // Freeform label the user assigned to organize sessions in the sidebar
+    #[serde(default)]
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,6 +147,9 @@ pub struct Spec {
     pub content: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Session that has to merge before this spec may start.
+    #[serde(default)]
+    pub depends_on_session: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,6 +224,48 @@ impl FromStr for SessionState {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StateTransitionReason {
+    Manual,
+    MarkReady,
+    UnmarkReady,
+    FollowUp,
+}
+
+impl StateTransitionReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StateTransitionReason::Manual => "Manual",
+            StateTransitionReason::MarkReady => "MarkReady",
+            StateTransitionReason::UnmarkReady => "UnmarkReady",
+            StateTransitionReason::FollowUp => "FollowUp",
+        }
+    }
+}
+
+impl FromStr for StateTransitionReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Manual" => Ok(StateTransitionReason::Manual),
+            "MarkReady" => Ok(StateTransitionReason::MarkReady),
+            "UnmarkReady" => Ok(StateTransitionReason::UnmarkReady),
+            "FollowUp" => Ok(StateTransitionReason::FollowUp),
+            _ => Err(format!("Invalid state transition reason: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionStateTransition {
+    pub session_id: String,
+    pub from_state: Option<SessionState>,
+    pub to_state: SessionState,
+    pub timestamp: DateTime<Utc>,
+    pub reason: StateTransitionReason,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitStats {
     pub session_id: String,
@@ -207,6 +277,60 @@ pub struct GitStats {
     // Timestamp (unix seconds) of the most recent meaningful diff change:
     // max(latest commit ahead of base, latest mtime among uncommitted changed files)
     pub last_diff_change_ts: Option<i64>,
+    // Worktree HEAD and parent tip the stats were computed against
+    #[serde(default)]
+    pub head_oid: Option<String>,
+    #[serde(default)]
+    pub parent_oid: Option<String>,
+    // Comparison against origin/<branch>; ahead/behind stay None without a remote branch
+    #[serde(default)]
+    pub has_remote: bool,
+    #[serde(default)]
+    pub remote_ahead: Option<u32>,
+    #[serde(default)]
+    pub remote_behind: Option<u32>,
+    #[serde(default)]
+    pub remote_oid: Option<String>,
+}
+
+/// Per-session adjustments applied on top of the agent's configured CLI args.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLaunchOverrides {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub extra_flags: Option<String>,
+}
+
+impl SessionLaunchOverrides {
+    /// Trims both fields and drops the ones left empty.
+    pub fn normalized(self) -> Self {
+        let clean = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            model: clean(self.model),
+            extra_flags: clean(self.extra_flags),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none() && self.extra_flags.is_none()
+    }
+}
+
+/// What the last agent launch of a session actually ran.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLaunchSnapshot {
+    pub agent_type: String,
+    pub command: String,
+    pub overrides: SessionLaunchOverrides,
+    pub force_restart: bool,
+    pub launched_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -260,6 +384,15 @@ pub struct DiffStats {
     pub deletions: usize,
     #[serde(default)]
     pub insertions: usize,
+    /// Whether `origin/<branch>` exists for the session branch.
+    #[serde(default)]
+    pub has_remote: bool,
+    /// Commits on the session branch that are not on `origin/<branch>` yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_ahead: Option<u32>,
+    /// Commits on `origin/<branch>` that the session branch does not have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_behind: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -272,6 +405,8 @@ pub struct SessionInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_number: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_group_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub epic: Option<Epic>,
     pub branch: String,
     pub worktree_path: String,
@@ -304,17 +439,61 @@ pub struct SessionInfo {
     pub pr_number: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pr_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// True while the session's agent was launched with permission prompts disabled.
+    #[serde(default)]
+    pub skip_permissions_active: bool,
+    /// True while a stash taken from the session worktree is waiting to be restored.
+    #[serde(default)]
+    pub has_stashed_changes: bool,
+    /// True when the last setup script run in the session worktree exited with an error.
+    #[serde(default)]
+    pub setup_failed: bool,
+    /// True while the project setup script is still running in the session worktree.
+    #[serde(default)]
+    pub setup_running: bool,
+    /// True when the worktree exists but its branch was deleted outside Schaltwerk.
+    #[serde(default)]
+    pub branch_missing: bool,
+    /// Name of the member repository in a multi-root workspace listing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_label: Option<String>,
+    /// Session a spec waits on before it may start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on_session: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct EnrichedSession {
     pub info: SessionInfo,
     pub status: Option<SessionMonitorStatus>,
+    /// Canonical top/bottom terminal ids. Superseded by `terminal_details`.
     pub terminals: Vec<String>,
+    /// Terminals that belong to the session, in display order, with their liveness.
+    pub terminal_details: Vec<SessionTerminal>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub attention_required: Option<bool>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionTerminalKind {
+    Agent,
+    Shell,
+    Run,
+    Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionTerminal {
+    pub id: String,
+    pub kind: SessionTerminalKind,
+    pub exists: bool,
+    pub suspended: bool,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMonitorStatus {
     pub session_name: String,
@@ -322,6 +501,9 @@ pub struct SessionMonitorStatus {
     pub test_status: TestStatus,
     pub diff_stats: Option<DiffStats>,
     pub last_update: DateTime<Utc>,
+    /// Parsed from the agent's terminal output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_status: Option<AgentOutputStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -332,6 +514,24 @@ pub enum TestStatus {
     Unknown,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WorktreeClass {
+    /// Backs a live session.
+    Tracked,
+    /// No session row points at this worktree.
+    Orphaned,
+    /// The owning session was cancelled or turned back into a spec.
+    Stale,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeClassification {
+    pub path: PathBuf,
+    pub class: WorktreeClass,
+    pub session_name: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchivedSpec {
     pub id: String,
@@ -341,3 +541,26 @@ pub struct ArchivedSpec {
     pub content: String,
     pub archived_at: DateTime<Utc>,
 }
+
+/// Answer to a session agent content request. `NotModified` is returned instead of the
+/// body when the caller already holds the content with the current etag.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SessionAgentContent {
+    Modified {
+        spec_content: Option<String>,
+        initial_prompt: Option<String>,
+        etag: String,
+    },
+    NotModified {
+        etag: String,
+    },
+}
+
+impl SessionAgentContent {
+    pub fn etag(&self) -> &str {
+        match self {
+            Self::Modified { etag, .. } | Self::NotModified { etag } => etag,
+        }
+    }
+}
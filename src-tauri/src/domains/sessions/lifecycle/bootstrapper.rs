@@ -1,8 +1,17 @@
 use crate::domains::git::service as git;
-use crate::domains::sessions::utils::SessionUtils;
+use crate::domains::sessions::lifecycle::worktree_clone::{CloneMethod, clone_dir_into_worktree};
+use crate::domains::sessions::utils::{SessionUtils, ensure_worktree_base_writable};
 use anyhow::{Context, Result, anyhow};
+use git2::Repository;
+use ignore::overrides::OverrideBuilder;
 use log::{info, warn};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Upper bound on the bytes copied for the project's worktree copy patterns, so a
+/// pattern that accidentally matches build output cannot fill the disk.
+const MAX_CONFIGURED_COPY_BYTES: u64 = 50 * 1024 * 1024;
 
 pub struct WorktreeBootstrapper<'a> {
     repo_path: &'a Path,
@@ -18,6 +27,11 @@ pub struct BootstrapConfig<'a> {
     pub use_existing_branch: bool,
     pub sync_with_origin: bool,
     pub should_copy_claude_locals: bool,
+    /// Project globs of untracked files to copy into the worktree, whatever the agent.
+    pub copy_patterns: &'a [String],
+    /// Untracked directories, relative to the repository root, cloned into the worktree
+    /// so dependencies such as `node_modules` need no reinstall.
+    pub clone_dirs: &'a [String],
     /// When set, fetch the PR's changes and create the session from those changes.
     pub pr_number: Option<i64>,
 }
@@ -40,6 +54,9 @@ impl<'a> WorktreeBootstrapper<'a> {
             config.session_name, config.branch_name
         );
 
+        if let Some(base_dir) = config.worktree_path.parent() {
+            ensure_worktree_base_writable(base_dir)?;
+        }
         self.utils.cleanup_existing_worktree(config.worktree_path)?;
 
         // If pr_number is set, fetch the PR and create worktree from it
@@ -63,6 +80,8 @@ impl<'a> WorktreeBootstrapper<'a> {
             if config.should_copy_claude_locals {
                 self.copy_claude_locals(config.worktree_path);
             }
+            self.copy_configured_files(config.worktree_path, config.copy_patterns);
+            self.clone_heavy_dirs(config.worktree_path, config.clone_dirs);
 
             info!(
                 "Successfully bootstrapped worktree from PR #{} at: {}",
@@ -103,6 +122,8 @@ impl<'a> WorktreeBootstrapper<'a> {
         if config.should_copy_claude_locals {
             self.copy_claude_locals(config.worktree_path);
         }
+        self.copy_configured_files(config.worktree_path, config.copy_patterns);
+        self.clone_heavy_dirs(config.worktree_path, config.clone_dirs);
 
         info!(
             "Successfully bootstrapped worktree at: {}",
@@ -116,6 +137,26 @@ impl<'a> WorktreeBootstrapper<'a> {
         })
     }
 
+    /// Creates the worktree and fresh branch of a session whose worktree base was
+    /// already checked and whose stale worktrees were already pruned. Skipping that
+    /// shared preparation makes it safe to create several worktrees in parallel.
+    pub fn create_prepared_worktree(&self, config: &BootstrapConfig) -> Result<BootstrapResult> {
+        self.create_worktree_directory(config, config.branch_name)?;
+        self.verify_worktree(config.worktree_path)?;
+
+        if config.should_copy_claude_locals {
+            self.copy_claude_locals(config.worktree_path);
+        }
+        self.copy_configured_files(config.worktree_path, config.copy_patterns);
+        self.clone_heavy_dirs(config.worktree_path, config.clone_dirs);
+
+        Ok(BootstrapResult {
+            branch: config.branch_name.to_string(),
+            worktree_path: config.worktree_path.to_path_buf(),
+            parent_branch: config.parent_branch.to_string(),
+        })
+    }
+
     pub fn resolve_parent_branch(&self, requested: Option<&str>) -> Result<String> {
         if let Some(branch) = requested {
             let trimmed = branch.trim();
@@ -147,16 +188,13 @@ impl<'a> WorktreeBootstrapper<'a> {
             ));
         }
 
-        let branch_exists = git::branch_exists(self.repo_path, custom_branch)?;
-        if branch_exists {
-            let suffix = SessionUtils::generate_random_suffix(2);
-            let unique_branch = format!("{custom_branch}-{suffix}");
-            info!("Custom branch '{custom_branch}' exists, using '{unique_branch}' instead");
-            Ok(unique_branch)
-        } else {
+        let final_branch = self.utils.resolve_branch_collision(custom_branch)?;
+        if final_branch == custom_branch {
             info!("Using custom branch '{custom_branch}'");
-            Ok(custom_branch.to_string())
+        } else {
+            info!("Custom branch '{custom_branch}' exists, using '{final_branch}' instead");
         }
+        Ok(final_branch)
     }
 
     fn create_worktree_directory(
@@ -247,7 +285,7 @@ impl<'a> WorktreeBootstrapper<'a> {
         Ok(())
     }
 
-    fn copy_claude_locals(&self, worktree_path: &Path) {
+    pub fn copy_claude_locals(&self, worktree_path: &Path) {
         let mut copy_plan: Vec<(std::path::PathBuf, std::path::PathBuf)> = Vec::new();
 
         if let Ok(entries) = std::fs::read_dir(self.repo_path) {
@@ -305,6 +343,146 @@ impl<'a> WorktreeBootstrapper<'a> {
             }
         }
     }
+
+    /// Copies the untracked files matching `patterns` from the repository into the
+    /// worktree. Gitignored files are included; tracked files are skipped because the
+    /// checkout already carries them.
+    pub fn copy_configured_files(&self, worktree_path: &Path, patterns: &[String]) {
+        self.copy_matching_files(worktree_path, patterns, MAX_CONFIGURED_COPY_BYTES);
+    }
+
+    /// Clones the project's heavy directories into the worktree, using copy-on-write
+    /// clones where the filesystem supports them. Failures are logged and skipped.
+    pub fn clone_heavy_dirs(&self, worktree_path: &Path, dirs: &[String]) {
+        for dir in dirs
+            .iter()
+            .map(|dir| dir.trim())
+            .filter(|dir| !dir.is_empty())
+        {
+            match clone_dir_into_worktree(self.repo_path, worktree_path, dir) {
+                Ok(Some(CloneMethod::CopyOnWrite)) => {
+                    info!("Cloned '{dir}' into worktree using copy-on-write clones")
+                }
+                Ok(Some(CloneMethod::Copy)) => info!(
+                    "Copied '{dir}' into worktree; the filesystem does not support copy-on-write clones"
+                ),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to clone '{dir}' into worktree: {e:#}"),
+            }
+        }
+    }
+
+    fn copy_matching_files(&self, worktree_path: &Path, patterns: &[String], max_bytes: u64) {
+        if patterns.is_empty() {
+            return;
+        }
+        let matches = match untracked_files_matching(self.repo_path, patterns) {
+            Ok(matches) => matches,
+            Err(e) => {
+                warn!("Failed to resolve worktree copy patterns: {e}");
+                return;
+            }
+        };
+
+        let mut copied_bytes = 0;
+        for (relative, size) in matches {
+            if copied_bytes + size > max_bytes {
+                warn!(
+                    "Skipping {}: copying it would exceed the {max_bytes} byte limit for configured files",
+                    relative.display()
+                );
+                continue;
+            }
+
+            let dest = worktree_path.join(&relative);
+            if dest.exists() {
+                info!(
+                    "Skipping configured file copy; destination already exists: {}",
+                    dest.display()
+                );
+                continue;
+            }
+            if let Some(parent) = dest.parent()
+                && let Err(e) = std::fs::create_dir_all(parent)
+            {
+                warn!("Failed to create directory for configured file: {e}");
+                continue;
+            }
+
+            match std::fs::copy(self.repo_path.join(&relative), &dest) {
+                Ok(_) => {
+                    copied_bytes += size;
+                    info!("Copied configured file: {}", dest.display());
+                }
+                Err(e) => warn!("Failed to copy configured file {}: {e}", relative.display()),
+            }
+        }
+    }
+}
+
+/// Untracked files of the repository matching the gitignore-style `patterns`, relative
+/// to `repo_path` and with their sizes. Patterns are anchored at the repository root and
+/// only match deeper than they spell out through `**`.
+fn untracked_files_matching(
+    repo_path: &Path,
+    patterns: &[String],
+) -> Result<BTreeMap<PathBuf, u64>> {
+    let mut builder = OverrideBuilder::new(repo_path);
+    let mut walk_roots = Vec::new();
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        if let Err(e) = builder.add(pattern) {
+            warn!("Ignoring invalid worktree copy pattern '{pattern}': {e}");
+            continue;
+        }
+        if !pattern.starts_with('!') {
+            walk_roots.push(pattern_walk_root(pattern));
+        }
+    }
+    let matcher = builder.build()?;
+    let index = Repository::open(repo_path)?.index()?;
+
+    let mut matches = BTreeMap::new();
+    for (root, max_depth) in walk_roots {
+        let mut walker = WalkDir::new(repo_path.join(root));
+        if let Some(depth) = max_depth {
+            walker = walker.max_depth(depth);
+        }
+        // Skip git metadata and the session worktrees themselves
+        let entries = walker.into_iter().filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && matches!(entry.file_name().to_str(), Some(".git" | ".schaltwerk")))
+        });
+        for entry in entries.filter_map(Result::ok) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(repo_path) else {
+                continue;
+            };
+            if !matcher.matched(relative, false).is_whitelist()
+                || index.get_path(relative, 0).is_some()
+            {
+                continue;
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            matches.insert(relative.to_path_buf(), size);
+        }
+    }
+    Ok(matches)
+}
+
+/// The directory `pattern` can only match below, made of its literal leading
+/// components, and how deep below it to look; unbounded when the rest contains `**`.
+fn pattern_walk_root(pattern: &str) -> (PathBuf, Option<usize>) {
+    let components: Vec<&str> = pattern.trim_matches('/').split('/').collect();
+    let literal = components
+        .iter()
+        .take_while(|component| !component.contains(['*', '?', '[', '{']))
+        .count()
+        .min(components.len() - 1);
+    let rest = &components[literal..];
+    let max_depth = (!rest.contains(&"**")).then_some(rest.len());
+    (components[..literal].iter().collect(), max_depth)
 }
 
 #[cfg(test)]
@@ -312,7 +490,9 @@ mod tests {
     use super::*;
     use crate::domains::sessions::cache::SessionCacheManager;
     use crate::domains::sessions::repository::SessionDbManager;
-    use crate::infrastructure::database::Database;
+    use crate::infrastructure::database::{
+        BranchCollisionStrategy, Database, ProjectConfigMethods,
+    };
     use serial_test::serial;
     use std::process::Command;
     use tempfile::TempDir;
@@ -375,6 +555,8 @@ mod tests {
             use_existing_branch: false,
             sync_with_origin: false,
             should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: None,
         };
 
@@ -411,6 +593,8 @@ mod tests {
             use_existing_branch: false,
             sync_with_origin: false,
             should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: None,
         };
 
@@ -419,6 +603,84 @@ mod tests {
         assert_ne!(result.branch, "custom-branch");
     }
 
+    fn bootstrap_custom_branch(
+        repo_path: &Path,
+        strategy: BranchCollisionStrategy,
+        session_name: &str,
+    ) -> Result<BootstrapResult> {
+        let db = Database::new(Some(repo_path.join("test.db"))).unwrap();
+        db.set_project_branch_collision_strategy(repo_path, strategy)
+            .unwrap();
+        let db_manager = SessionDbManager::new(db, repo_path.to_path_buf());
+        let cache_manager = SessionCacheManager::new(repo_path.to_path_buf());
+        let utils = SessionUtils::new(repo_path.to_path_buf(), cache_manager, db_manager);
+        let bootstrapper = WorktreeBootstrapper::new(repo_path, &utils);
+
+        let worktree_path = repo_path.join(".schaltwerk/worktrees").join(session_name);
+        bootstrapper.bootstrap_worktree(BootstrapConfig {
+            session_name,
+            branch_name: "custom-branch",
+            worktree_path: &worktree_path,
+            parent_branch: "master",
+            custom_branch: Some("custom-branch"),
+            use_existing_branch: false,
+            sync_with_origin: false,
+            should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
+            pr_number: None,
+        })
+    }
+
+    #[test]
+    #[serial]
+    fn test_custom_branch_conflict_with_increment_strategy_is_deterministic() {
+        let (_temp, repo_path) = setup_test_repo();
+        for branch in ["custom-branch", "custom-branch-2"] {
+            Command::new("git")
+                .args(["branch", branch])
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
+
+        let first =
+            bootstrap_custom_branch(&repo_path, BranchCollisionStrategy::Increment, "first")
+                .unwrap();
+        assert_eq!(first.branch, "custom-branch-3");
+
+        let second =
+            bootstrap_custom_branch(&repo_path, BranchCollisionStrategy::Increment, "second")
+                .unwrap();
+        assert_eq!(second.branch, "custom-branch-4");
+    }
+
+    #[test]
+    #[serial]
+    fn test_custom_branch_conflict_with_error_strategy_is_refused() {
+        let (_temp, repo_path) = setup_test_repo();
+        Command::new("git")
+            .args(["branch", "custom-branch"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let err = bootstrap_custom_branch(&repo_path, BranchCollisionStrategy::Error, "refused")
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert!(!repo_path.join(".schaltwerk/worktrees/refused").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_custom_branch_without_conflict_ignores_strategy() {
+        let (_temp, repo_path) = setup_test_repo();
+
+        let result = bootstrap_custom_branch(&repo_path, BranchCollisionStrategy::Error, "free")
+            .unwrap();
+        assert_eq!(result.branch, "custom-branch");
+    }
+
     #[test]
     #[serial]
     fn test_resolve_parent_branch_uses_explicit() {
@@ -481,6 +743,8 @@ mod tests {
             use_existing_branch: false,
             sync_with_origin: false,
             should_copy_claude_locals: true,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: None,
         };
 
@@ -497,6 +761,146 @@ mod tests {
         assert_eq!(settings_content, "{\"key\":\"value\"}");
     }
 
+    #[test]
+    #[serial]
+    fn test_configured_files_are_copied_including_gitignored_but_not_tracked() {
+        let (_temp, repo_path) = setup_test_repo();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap()
+        };
+        std::fs::write(repo_path.join(".gitignore"), ".env.local\n").unwrap();
+        std::fs::create_dir_all(repo_path.join("config/nested")).unwrap();
+        std::fs::write(repo_path.join("config/tracked.local.json"), "committed").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "Add config"]);
+
+        std::fs::write(repo_path.join(".env.local"), "SECRET=1").unwrap();
+        std::fs::create_dir_all(repo_path.join(".vscode")).unwrap();
+        std::fs::write(repo_path.join(".vscode/settings.json"), "{}").unwrap();
+        std::fs::write(repo_path.join("config/dev.local.json"), "dev").unwrap();
+        std::fs::write(repo_path.join("config/nested/deep.local.json"), "deep").unwrap();
+        std::fs::write(repo_path.join("config/tracked.local.json"), "dirty").unwrap();
+        std::fs::write(repo_path.join("notes.txt"), "unrelated").unwrap();
+
+        let db = Database::new(Some(repo_path.join("test.db"))).unwrap();
+        let db_manager = SessionDbManager::new(db.clone(), repo_path.clone());
+        let cache_manager = SessionCacheManager::new(repo_path.clone());
+        let utils = SessionUtils::new(repo_path.clone(), cache_manager, db_manager);
+        let bootstrapper = WorktreeBootstrapper::new(&repo_path, &utils);
+
+        let patterns = [
+            ".env.local".to_string(),
+            ".vscode/settings.json".to_string(),
+            "config/*.local.json".to_string(),
+        ];
+        let worktree_path = repo_path.join(".schaltwerk/worktrees/test-session");
+        bootstrapper
+            .bootstrap_worktree(BootstrapConfig {
+                session_name: "test-session",
+                branch_name: "schaltwerk/test-session",
+                worktree_path: &worktree_path,
+                parent_branch: "master",
+                custom_branch: None,
+                use_existing_branch: false,
+                sync_with_origin: false,
+                should_copy_claude_locals: false,
+                copy_patterns: &patterns,
+                clone_dirs: &[],
+                pr_number: None,
+            })
+            .unwrap();
+
+        let read = |path: &str| std::fs::read_to_string(worktree_path.join(path)).unwrap();
+        assert_eq!(read(".env.local"), "SECRET=1");
+        assert_eq!(read(".vscode/settings.json"), "{}");
+        assert_eq!(read("config/dev.local.json"), "dev");
+        assert_eq!(read("config/tracked.local.json"), "committed");
+        assert!(!worktree_path.join("config/nested/deep.local.json").exists());
+        assert!(!worktree_path.join("notes.txt").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_configured_file_copy_stops_at_size_limit() {
+        let (_temp, repo_path) = setup_test_repo();
+        std::fs::write(repo_path.join("a.local"), "12345").unwrap();
+        std::fs::write(repo_path.join("b.local"), "67890").unwrap();
+
+        let db = Database::new(Some(repo_path.join("test.db"))).unwrap();
+        let db_manager = SessionDbManager::new(db.clone(), repo_path.clone());
+        let cache_manager = SessionCacheManager::new(repo_path.clone());
+        let utils = SessionUtils::new(repo_path.clone(), cache_manager, db_manager);
+        let bootstrapper = WorktreeBootstrapper::new(&repo_path, &utils);
+
+        let target = TempDir::new().unwrap();
+        bootstrapper.copy_matching_files(target.path(), &["*.local".to_string()], 8);
+
+        assert!(target.path().join("a.local").exists());
+        assert!(!target.path().join("b.local").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_clone_dirs_are_copied_only_when_configured() {
+        let (_temp, repo_path) = setup_test_repo();
+        std::fs::write(repo_path.join(".gitignore"), "node_modules/\n").unwrap();
+        std::fs::create_dir_all(repo_path.join("node_modules/pkg")).unwrap();
+        std::fs::write(repo_path.join("node_modules/pkg/index.js"), "pkg").unwrap();
+
+        let db = Database::new(Some(repo_path.join("test.db"))).unwrap();
+        let db_manager = SessionDbManager::new(db.clone(), repo_path.clone());
+        let cache_manager = SessionCacheManager::new(repo_path.clone());
+        let utils = SessionUtils::new(repo_path.clone(), cache_manager, db_manager);
+        let bootstrapper = WorktreeBootstrapper::new(&repo_path, &utils);
+
+        let clone_dirs = ["node_modules".to_string(), "vendor".to_string()];
+        for (session, clone_dirs) in [("disabled", &[][..]), ("enabled", &clone_dirs[..])] {
+            let worktree_path = repo_path.join(".schaltwerk/worktrees").join(session);
+            bootstrapper
+                .bootstrap_worktree(BootstrapConfig {
+                    session_name: session,
+                    branch_name: &format!("schaltwerk/{session}"),
+                    worktree_path: &worktree_path,
+                    parent_branch: "master",
+                    custom_branch: None,
+                    use_existing_branch: false,
+                    sync_with_origin: false,
+                    should_copy_claude_locals: false,
+                    copy_patterns: &[],
+                    clone_dirs,
+                    pr_number: None,
+                })
+                .unwrap();
+        }
+
+        let disabled = repo_path.join(".schaltwerk/worktrees/disabled");
+        assert!(!disabled.join("node_modules").exists());
+        let enabled = repo_path.join(".schaltwerk/worktrees/enabled");
+        assert_eq!(
+            std::fs::read_to_string(enabled.join("node_modules/pkg/index.js")).unwrap(),
+            "pkg"
+        );
+        assert!(!enabled.join("vendor").exists());
+    }
+
+    #[test]
+    fn test_pattern_walk_root_limits_the_search() {
+        assert_eq!(pattern_walk_root(".env.local"), (PathBuf::new(), Some(1)));
+        assert_eq!(
+            pattern_walk_root("/.vscode/settings.json"),
+            (PathBuf::from(".vscode"), Some(1))
+        );
+        assert_eq!(
+            pattern_walk_root("config/*/app.yml"),
+            (PathBuf::from("config"), Some(2))
+        );
+        assert_eq!(pattern_walk_root("**/.env"), (PathBuf::new(), None));
+    }
+
     #[test]
     #[serial]
     fn test_verify_worktree_fails_if_not_created() {
@@ -539,6 +943,8 @@ mod tests {
             use_existing_branch: true,
             sync_with_origin: false,
             should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: None,
         };
 
@@ -569,6 +975,8 @@ mod tests {
             use_existing_branch: true,
             sync_with_origin: false,
             should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: None,
         };
 
@@ -599,6 +1007,8 @@ mod tests {
             use_existing_branch: true,
             sync_with_origin: false,
             should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: None,
         };
 
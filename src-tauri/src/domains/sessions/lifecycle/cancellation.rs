@@ -2,6 +2,7 @@ use crate::domains::git::service as git;
 use crate::domains::sessions::entity::{Session, SessionState, SessionStatus};
 use crate::domains::sessions::process_cleanup::terminate_processes_with_cwd;
 use crate::domains::sessions::repository::SessionDbManager;
+use crate::domains::terminal::recording::session_recordings_dir;
 use anyhow::{Context, Result, anyhow};
 use log::{info, warn};
 use std::path::{Path, PathBuf};
@@ -25,6 +26,40 @@ pub struct CancellationConfig {
     pub skip_branch_deletion: bool,
 }
 
+/// Removes the terminal recordings of a cancelled session unless the project keeps them.
+/// Returns whether a recordings directory was removed.
+pub fn discard_session_recordings(db_manager: &SessionDbManager, session_name: &str) -> Result<bool> {
+    if db_manager.get_project_keep_recordings()? {
+        return Ok(false);
+    }
+    let dir = session_recordings_dir(&db_manager.repo_path, session_name);
+    if !dir.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(&dir)
+        .with_context(|| format!("Failed to remove recordings at {}", dir.display()))?;
+    info!("Removed terminal recordings of session '{session_name}'");
+    Ok(true)
+}
+
+/// Deletes the snapshot refs and records of a cancelled session so the captured commits
+/// can be garbage collected. Returns how many snapshot records were removed.
+pub fn discard_session_snapshots(
+    db_manager: &SessionDbManager,
+    session: &Session,
+) -> Result<usize> {
+    let refs_removed = git::delete_session_snapshot_refs(&db_manager.repo_path, &session.name)?;
+    let removed = db_manager.delete_session_snapshots(&session.id)?;
+    if refs_removed > 0 || removed > 0 {
+        info!(
+            "Removed {} snapshot(s) of session '{}'",
+            removed.max(refs_removed),
+            session.name
+        );
+    }
+    Ok(removed)
+}
+
 #[derive(Debug, Clone)]
 pub struct CancellationResult {
     pub terminated_processes: Vec<i32>,
@@ -219,6 +254,8 @@ impl<'a> CancellationCoordinator<'a> {
         }
 
         self.finalize_cancellation(&session.id, &mut result.errors)?;
+        self.discard_recordings(&session.name, &mut result.errors);
+        self.discard_snapshots(session, &mut result.errors);
 
         if !result.errors.is_empty() {
             warn!(
@@ -278,6 +315,8 @@ impl<'a> CancellationCoordinator<'a> {
         }
 
         self.finalize_cancellation(&session.id, &mut result.errors)?;
+        self.discard_recordings(&session.name, &mut result.errors);
+        self.discard_snapshots(session, &mut result.errors);
 
         if !result.errors.is_empty() {
             warn!(
@@ -448,6 +487,22 @@ impl<'a> CancellationCoordinator<'a> {
         Ok(())
     }
 
+    fn discard_recordings(&self, session_name: &str, errors: &mut Vec<String>) {
+        if let Err(e) = discard_session_recordings(self.db_manager, session_name) {
+            let msg = format!("Recording cleanup failed: {e}");
+            warn!("{msg}");
+            errors.push(msg);
+        }
+    }
+
+    fn discard_snapshots(&self, session: &Session, errors: &mut Vec<String>) {
+        if let Err(e) = discard_session_snapshots(self.db_manager, session) {
+            let msg = format!("Snapshot cleanup failed: {e}");
+            warn!("{msg}");
+            errors.push(msg);
+        }
+    }
+
     async fn remove_worktree_async(
         repo_path: &Path,
         worktree_path: &Path,
@@ -505,7 +560,7 @@ impl<'a> CancellationCoordinator<'a> {
 mod tests {
     use super::*;
     use crate::domains::sessions::entity::{Session, SessionState, SessionStatus};
-    use crate::infrastructure::database::Database;
+    use crate::infrastructure::database::{Database, ProjectConfigMethods};
     use chrono::Utc;
     use serial_test::serial;
     use std::path::PathBuf;
@@ -558,6 +613,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.to_path_buf(),
             repository_name: "test-repo".to_string(),
@@ -581,6 +637,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         }
     }
 
@@ -650,6 +707,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.clone(),
             repository_name: "test-repo".to_string(),
@@ -673,6 +731,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
 
         let coordinator = CancellationCoordinator::new(&repo_path, &db_manager);
@@ -734,6 +793,44 @@ mod tests {
         assert!(!updated.resume_allowed);
     }
 
+    #[test]
+    #[serial]
+    fn test_cancel_session_removes_recordings_unless_kept() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let db = Database::new(Some(repo_path.join("test.db"))).unwrap();
+        let db_manager = SessionDbManager::new(db, repo_path.clone());
+
+        let session = create_test_session(&repo_path, repo_path.join(".schaltwerk/worktrees/test"));
+        db_manager.create_session(&session).unwrap();
+
+        let recordings = session_recordings_dir(&repo_path, &session.name);
+        std::fs::create_dir_all(&recordings).unwrap();
+        std::fs::write(recordings.join("session-test-top.log"), b"output").unwrap();
+
+        db_manager
+            .db
+            .set_project_keep_recordings(&repo_path, true)
+            .unwrap();
+        assert!(!discard_session_recordings(&db_manager, &session.name).unwrap());
+        assert!(recordings.exists());
+
+        db_manager
+            .db
+            .set_project_keep_recordings(&repo_path, false)
+            .unwrap();
+        let coordinator = CancellationCoordinator::new(&repo_path, &db_manager);
+        let result = coordinator
+            .cancel_session(&session, CancellationConfig::default())
+            .unwrap();
+
+        assert!(!recordings.exists());
+        assert!(
+            !result.errors.iter().any(|e| e.contains("Recording")),
+            "unexpected errors: {:?}",
+            result.errors
+        );
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_async_cancel_session() {
@@ -159,6 +159,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: PathBuf::from("/tmp/repo"),
             repository_name: "test-repo".to_string(),
@@ -182,6 +183,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         }
     }
 
@@ -1,3 +1,5 @@
 pub mod bootstrapper;
 pub mod cancellation;
 pub mod finalizer;
+pub mod recovery;
+pub mod worktree_clone;
@@ -0,0 +1,416 @@
+use crate::domains::git::service as git;
+use crate::domains::sessions::db_journal::{JournalEntry, OperationJournalMethods, OperationKind};
+use crate::domains::sessions::db_sessions::SessionMethods;
+use crate::domains::sessions::entity::{Session, SessionStatus};
+use crate::infrastructure::database::Database;
+use anyhow::Result;
+use git2::{Repository, RepositoryState, ResetType};
+use log::{info, warn};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static IN_FLIGHT_OPERATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps a journal entry open for the lifetime of an operation.
+///
+/// The end record is written on drop, so both success and regular error paths (which
+/// already roll back on their own) close the entry. Only a hard exit leaves it open.
+pub struct JournalGuard {
+    db: Database,
+    id: String,
+}
+
+impl JournalGuard {
+    /// Journal failures never block the operation itself; they only cost crash recovery.
+    pub fn begin(db: &Database, entry: JournalEntry) -> Option<Self> {
+        if let Err(e) = db.begin_operation(&entry) {
+            warn!(
+                "Failed to journal {} operation for '{}': {e}",
+                entry.kind.as_str(),
+                entry.session_name
+            );
+            return None;
+        }
+        IN_FLIGHT_OPERATIONS.fetch_add(1, Ordering::SeqCst);
+        Some(Self {
+            db: db.clone(),
+            id: entry.id,
+        })
+    }
+}
+
+impl Drop for JournalGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.db.end_operation(&self.id) {
+            warn!("Failed to close journal entry {}: {e}", self.id);
+        }
+        IN_FLIGHT_OPERATIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn cancel_journal_entry(session: &Session) -> JournalEntry {
+    let mut entry = JournalEntry::new(
+        &session.repository_path,
+        OperationKind::Cancel,
+        &session.name,
+    );
+    entry.session_id = Some(session.id.clone());
+    entry.branch = Some(session.branch.clone());
+    entry.worktree_path = Some(session.worktree_path.clone());
+    entry.owns_branch = true;
+    entry
+}
+
+pub fn in_flight_operations() -> usize {
+    IN_FLIGHT_OPERATIONS.load(Ordering::SeqCst)
+}
+
+/// Blocks until every journaled operation finished or the timeout elapsed.
+/// Returns `true` when nothing is left in flight.
+pub fn wait_for_in_flight_operations(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while in_flight_operations() > 0 {
+        if Instant::now() >= deadline {
+            warn!(
+                "Giving up on {} in-flight session operation(s) after {timeout:?}",
+                in_flight_operations()
+            );
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// Partial artifacts were removed and the operation is undone.
+    RolledBack,
+    /// The interrupted operation was finished on its behalf.
+    Completed,
+    /// Nothing partial was found.
+    NoChanges,
+    /// State could not be fixed automatically; the session needs manual attention.
+    NeedsRepair,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveredOperation {
+    pub kind: OperationKind,
+    pub session_name: String,
+    pub action: RecoveryAction,
+    pub detail: String,
+}
+
+/// Scans the journal for operations that began but never ended and repairs what they left behind.
+pub fn recover_interrupted_operations(
+    db: &Database,
+    repo_path: &Path,
+) -> Result<Vec<RecoveredOperation>> {
+    let entries = db.list_unfinished_operations(repo_path)?;
+    let mut recovered = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let (action, detail) = match entry.kind {
+            OperationKind::Create => recover_create(db, repo_path, &entry),
+            OperationKind::Cancel => recover_cancel(db, repo_path, &entry),
+            OperationKind::Merge => recover_merge(&entry),
+            OperationKind::SkipPermissionsLaunch => (
+                RecoveryAction::NoChanges,
+                "audit entries need no recovery".to_string(),
+            ),
+        };
+
+        info!(
+            "Recovered interrupted {} of session '{}': {action:?} ({detail})",
+            entry.kind.as_str(),
+            entry.session_name
+        );
+
+        if let Err(e) = db.end_operation(&entry.id) {
+            warn!("Failed to close recovered journal entry {}: {e}", entry.id);
+        }
+
+        recovered.push(RecoveredOperation {
+            kind: entry.kind,
+            session_name: entry.session_name,
+            action,
+            detail,
+        });
+    }
+
+    Ok(recovered)
+}
+
+fn recover_create(db: &Database, repo_path: &Path, entry: &JournalEntry) -> (RecoveryAction, String) {
+    let session = entry
+        .session_id
+        .as_deref()
+        .and_then(|id| db.get_session_by_id(id).ok());
+
+    if let Some(session) = session {
+        if session.worktree_path.exists() {
+            return (
+                RecoveryAction::NoChanges,
+                "creation had already finished".to_string(),
+            );
+        }
+        return (
+            RecoveryAction::NeedsRepair,
+            format!(
+                "session row exists but worktree {} is missing",
+                session.worktree_path.display()
+            ),
+        );
+    }
+
+    let mut removed = Vec::new();
+    if let Some(worktree) = entry.worktree_path.as_deref()
+        && worktree.exists()
+    {
+        match git::remove_worktree(repo_path, worktree) {
+            Ok(()) => removed.push(format!("worktree {}", worktree.display())),
+            Err(e) => warn!("Recovery: failed to remove partial worktree {}: {e}", worktree.display()),
+        }
+    }
+    if let Err(e) = git::prune_worktrees(repo_path) {
+        warn!("Recovery: failed to prune worktrees: {e}");
+    }
+
+    if entry.owns_branch
+        && let Some(branch) = entry.branch.as_deref()
+        && git::branch_exists(repo_path, branch).unwrap_or(false)
+    {
+        match git::delete_branch(repo_path, branch) {
+            Ok(()) => removed.push(format!("branch {branch}")),
+            Err(e) => warn!("Recovery: failed to delete partial branch {branch}: {e}"),
+        }
+    }
+
+    if removed.is_empty() {
+        (
+            RecoveryAction::NoChanges,
+            "no partial artifacts found".to_string(),
+        )
+    } else {
+        (
+            RecoveryAction::RolledBack,
+            format!("removed {}", removed.join(", ")),
+        )
+    }
+}
+
+fn recover_cancel(db: &Database, repo_path: &Path, entry: &JournalEntry) -> (RecoveryAction, String) {
+    let Some(session) = entry
+        .session_id
+        .as_deref()
+        .and_then(|id| db.get_session_by_id(id).ok())
+    else {
+        return (
+            RecoveryAction::NoChanges,
+            "session record no longer exists".to_string(),
+        );
+    };
+
+    if session.status == SessionStatus::Cancelled {
+        return (
+            RecoveryAction::NoChanges,
+            "cancellation had already finished".to_string(),
+        );
+    }
+
+    if session.worktree_path.exists() {
+        return (
+            RecoveryAction::NeedsRepair,
+            format!(
+                "worktree {} is still present; cancel the session again to finish cleanup",
+                session.worktree_path.display()
+            ),
+        );
+    }
+
+    if entry.owns_branch
+        && git::branch_exists(repo_path, &session.branch).unwrap_or(false)
+        && let Err(e) = git::delete_branch(repo_path, &session.branch)
+    {
+        warn!(
+            "Recovery: failed to delete branch {} of cancelled session: {e}",
+            session.branch
+        );
+    }
+
+    if let Err(e) = db.update_session_status(&session.id, SessionStatus::Cancelled) {
+        return (
+            RecoveryAction::NeedsRepair,
+            format!("failed to mark session cancelled: {e}"),
+        );
+    }
+    if let Err(e) = db.set_session_resume_allowed(&session.id, false) {
+        warn!("Recovery: failed to gate resume for {}: {e}", session.name);
+    }
+
+    (
+        RecoveryAction::Completed,
+        "worktree was already removed; marked session cancelled".to_string(),
+    )
+}
+
+fn recover_merge(entry: &JournalEntry) -> (RecoveryAction, String) {
+    let Some(worktree) = entry.worktree_path.as_deref().filter(|p| p.exists()) else {
+        return (
+            RecoveryAction::NoChanges,
+            "session worktree no longer exists".to_string(),
+        );
+    };
+
+    let repo = match Repository::open(worktree) {
+        Ok(repo) => repo,
+        Err(e) => {
+            return (
+                RecoveryAction::NeedsRepair,
+                format!("failed to open worktree {}: {e}", worktree.display()),
+            );
+        }
+    };
+
+    if repo.state() == RepositoryState::Clean {
+        return (
+            RecoveryAction::NoChanges,
+            "worktree has no rebase in progress".to_string(),
+        );
+    }
+
+    if let Ok(mut rebase) = repo.open_rebase(None) {
+        let _ = rebase.abort();
+    }
+    let _ = repo.cleanup_state();
+
+    if let Some(base) = entry.base_commit.as_deref()
+        && let Ok(oid) = git2::Oid::from_str(base)
+        && let Ok(commit) = repo.find_commit(oid)
+        && let Err(e) = repo.reset(commit.as_object(), ResetType::Hard, None)
+    {
+        return (
+            RecoveryAction::NeedsRepair,
+            format!("aborted rebase but failed to restore {base}: {e}"),
+        );
+    }
+
+    (
+        RecoveryAction::RolledBack,
+        "aborted interrupted rebase and restored the session head".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn init_repo(temp: &TempDir) -> std::path::PathBuf {
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "initial"]);
+        repo
+    }
+
+    #[test]
+    fn interrupted_create_removes_partial_worktree_and_branch() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp);
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+
+        let worktree = repo.join(".schaltwerk/worktrees/half-built");
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                "schaltwerk/half-built",
+                worktree.to_str().unwrap(),
+            ],
+        );
+
+        let mut entry = JournalEntry::new(&repo, OperationKind::Create, "half-built");
+        entry.session_id = Some("missing-session-id".into());
+        entry.branch = Some("schaltwerk/half-built".into());
+        entry.worktree_path = Some(worktree.clone());
+        entry.owns_branch = true;
+        db.begin_operation(&entry).unwrap();
+
+        let recovered = recover_interrupted_operations(&db, &repo).unwrap();
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].action, RecoveryAction::RolledBack);
+        assert!(!worktree.exists());
+        assert!(!git::branch_exists(&repo, "schaltwerk/half-built").unwrap());
+
+        assert!(db.list_unfinished_operations(&repo).unwrap().is_empty());
+    }
+
+    #[test]
+    fn interrupted_create_keeps_foreign_branch() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp);
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        git(&repo, &["branch", "feature/existing"]);
+
+        let mut entry = JournalEntry::new(&repo, OperationKind::Create, "existing");
+        entry.branch = Some("feature/existing".into());
+        entry.worktree_path = Some(repo.join(".schaltwerk/worktrees/existing"));
+        entry.owns_branch = false;
+        db.begin_operation(&entry).unwrap();
+
+        let recovered = recover_interrupted_operations(&db, &repo).unwrap();
+        assert_eq!(recovered[0].action, RecoveryAction::NoChanges);
+        assert!(git::branch_exists(&repo, "feature/existing").unwrap());
+    }
+
+    #[test]
+    fn interrupted_cancel_without_session_row_is_a_no_op() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp);
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+
+        let mut entry = JournalEntry::new(&repo, OperationKind::Cancel, "gone");
+        entry.session_id = Some("gone-id".into());
+        db.begin_operation(&entry).unwrap();
+
+        let recovered = recover_interrupted_operations(&db, &repo).unwrap();
+        assert_eq!(recovered[0].kind, OperationKind::Cancel);
+        assert_eq!(recovered[0].action, RecoveryAction::NoChanges);
+    }
+
+    #[test]
+    fn guard_closes_entry_and_tracks_in_flight_count() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+
+        let entry = JournalEntry::new(&repo, OperationKind::Merge, "merging");
+        {
+            let _guard = JournalGuard::begin(&db, entry).expect("journal entry");
+            assert!(in_flight_operations() >= 1);
+            assert_eq!(db.list_unfinished_operations(&repo).unwrap().len(), 1);
+        }
+        assert!(db.list_unfinished_operations(&repo).unwrap().is_empty());
+    }
+}
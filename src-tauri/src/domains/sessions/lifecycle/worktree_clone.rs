@@ -0,0 +1,300 @@
+use anyhow::{Context, Result, anyhow};
+use log::{debug, info, warn};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// How the contents of a directory ended up in the worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneMethod {
+    /// Files share their blocks with the repository until either copy changes.
+    CopyOnWrite,
+    /// The filesystem cannot clone files, so their contents were copied.
+    Copy,
+}
+
+/// Clones the directory `dir`, relative to the repository root, to the same place in
+/// the worktree. Symlinks are recreated as symlinks and never followed. Returns `None`
+/// without copying when the directory does not exist, is a symlink, resolves outside
+/// the repository or is already present in the worktree.
+pub fn clone_dir_into_worktree(
+    repo_path: &Path,
+    worktree_path: &Path,
+    dir: &str,
+) -> Result<Option<CloneMethod>> {
+    let relative = clone_dir_relative_path(dir)?;
+    let source = repo_path.join(&relative);
+
+    let Ok(metadata) = std::fs::symlink_metadata(&source) else {
+        debug!("Skipping clone of '{dir}': it does not exist in the repository");
+        return Ok(None);
+    };
+    if metadata.file_type().is_symlink() {
+        warn!("Skipping clone of '{dir}': it is a symlink");
+        return Ok(None);
+    }
+    if !metadata.is_dir() {
+        warn!("Skipping clone of '{dir}': it is not a directory");
+        return Ok(None);
+    }
+    if !source
+        .canonicalize()?
+        .starts_with(repo_path.canonicalize()?)
+    {
+        warn!("Skipping clone of '{dir}': it resolves outside the repository");
+        return Ok(None);
+    }
+
+    let dest = worktree_path.join(&relative);
+    if std::fs::symlink_metadata(&dest).is_ok() {
+        info!("Skipping clone of '{dir}': it already exists in the worktree");
+        return Ok(None);
+    }
+    let parent = dest
+        .parent()
+        .ok_or_else(|| anyhow!("Clone destination has no parent: {}", dest.display()))?;
+    std::fs::create_dir_all(parent)?;
+    if !parent
+        .canonicalize()?
+        .starts_with(worktree_path.canonicalize()?)
+    {
+        warn!("Skipping clone of '{dir}': its parent resolves outside the worktree");
+        return Ok(None);
+    }
+
+    clone_tree(&source, &dest)
+        .map(Some)
+        .with_context(|| format!("Failed to clone '{dir}' into {}", worktree_path.display()))
+}
+
+/// `dir` as a plain relative path; parent references, absolute paths and git or
+/// Schaltwerk metadata are refused.
+fn clone_dir_relative_path(dir: &str) -> Result<PathBuf> {
+    let relative = Path::new(dir.trim().trim_end_matches('/'));
+    let mut components = relative.components().peekable();
+    if components.peek().is_none() {
+        return Err(anyhow!("Clone directory must not be empty"));
+    }
+    if let Some(Component::Normal(first)) = components.peek()
+        && matches!(first.to_str(), Some(".git" | ".schaltwerk"))
+    {
+        return Err(anyhow!("Clone directory '{dir}' is reserved"));
+    }
+    if !components.all(|component| matches!(component, Component::Normal(_))) {
+        return Err(anyhow!(
+            "Clone directory '{dir}' must be a relative path inside the repository"
+        ));
+    }
+    Ok(relative.to_path_buf())
+}
+
+#[cfg(target_os = "macos")]
+fn clone_tree(source: &Path, dest: &Path) -> Result<CloneMethod> {
+    // clonefile copies a whole directory in one call on APFS
+    match clonefile(source, dest) {
+        Ok(()) => Ok(CloneMethod::CopyOnWrite),
+        Err(e) => {
+            debug!(
+                "clonefile of {} failed, copying instead: {e}",
+                source.display()
+            );
+            copy_tree(source, dest, false)
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn clone_tree(source: &Path, dest: &Path) -> Result<CloneMethod> {
+    copy_tree(source, dest, cfg!(target_os = "linux"))
+}
+
+/// Recreates the tree below `source` at `dest`. File contents are cloned while
+/// `reflink` holds and the filesystem supports it, and copied from the first failed
+/// clone on. A partial copy is removed on error.
+fn copy_tree(source: &Path, dest: &Path, mut reflink: bool) -> Result<CloneMethod> {
+    let result = (|| -> Result<()> {
+        for entry in WalkDir::new(source).follow_links(false) {
+            let entry = entry?;
+            let target = dest.join(entry.path().strip_prefix(source)?);
+            let file_type = entry.file_type();
+            if file_type.is_dir() {
+                std::fs::create_dir_all(&target)?;
+            } else if file_type.is_symlink() {
+                copy_symlink(entry.path(), &target)?;
+            } else if file_type.is_file() {
+                if reflink && let Err(e) = reflink_file(entry.path(), &target) {
+                    debug!("Copy-on-write clone unsupported, copying instead: {e}");
+                    reflink = false;
+                }
+                if !reflink {
+                    std::fs::copy(entry.path(), &target)?;
+                }
+            }
+        }
+        Ok(())
+    })();
+
+    match result {
+        Ok(()) if reflink => Ok(CloneMethod::CopyOnWrite),
+        Ok(()) => Ok(CloneMethod::Copy),
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(dest);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(source)?, target)
+}
+
+#[cfg(windows)]
+fn copy_symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    let link = std::fs::read_link(source)?;
+    if source.is_dir() {
+        std::os::windows::fs::symlink_dir(link, target)
+    } else {
+        std::os::windows::fs::symlink_file(link, target)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn reflink_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let src = std::fs::File::open(source)?;
+    let dst = std::fs::File::create(target)?;
+    // SAFETY: both descriptors stay open for the duration of the call.
+    if unsafe { libc::ioctl(dst.as_raw_fd(), libc::FICLONE, src.as_raw_fd()) } != 0 {
+        let err = std::io::Error::last_os_error();
+        drop(dst);
+        let _ = std::fs::remove_file(target);
+        return Err(err);
+    }
+    dst.set_permissions(src.metadata()?.permissions())
+}
+
+#[cfg(target_os = "macos")]
+fn reflink_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    clonefile(source, target)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn reflink_file(_source: &Path, _target: &Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(target_os = "macos")]
+fn clonefile(source: &Path, target: &Path) -> std::io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    // From <sys/clonefile.h>: clone a symlink itself rather than its target
+    const CLONE_NOFOLLOW: u32 = 0x0001;
+
+    let src = CString::new(source.as_os_str().as_bytes())?;
+    let dst = CString::new(target.as_os_str().as_bytes())?;
+    // SAFETY: both pointers are NUL-terminated strings that outlive the call.
+    if unsafe { libc::clonefile(src.as_ptr(), dst.as_ptr(), CLONE_NOFOLLOW) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn fallback_copy_recreates_the_tree() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("node_modules");
+        std::fs::create_dir_all(source.join("pkg/lib")).unwrap();
+        std::fs::create_dir_all(source.join("empty")).unwrap();
+        std::fs::write(source.join("pkg/index.js"), "module.exports = 1").unwrap();
+        std::fs::write(source.join("pkg/lib/util.js"), "util").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::write(source.join("pkg/cli"), "#!/bin/sh").unwrap();
+            std::fs::set_permissions(source.join("pkg/cli"), PermissionsExt::from_mode(0o755))
+                .unwrap();
+        }
+
+        let dest = temp.path().join("copy");
+        assert_eq!(copy_tree(&source, &dest, false).unwrap(), CloneMethod::Copy);
+
+        let read = |path: &str| std::fs::read_to_string(dest.join(path)).unwrap();
+        assert_eq!(read("pkg/index.js"), "module.exports = 1");
+        assert_eq!(read("pkg/lib/util.js"), "util");
+        assert!(dest.join("empty").is_dir());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dest.join("pkg/cli"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn missing_dirs_are_skipped() {
+        let repo = TempDir::new().unwrap();
+        let worktree = TempDir::new().unwrap();
+
+        let result = clone_dir_into_worktree(repo.path(), worktree.path(), "vendor").unwrap();
+        assert_eq!(result, None);
+        assert!(!worktree.path().join("vendor").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_not_followed_out_of_the_repository() {
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        let repo = TempDir::new().unwrap();
+        let worktree = TempDir::new().unwrap();
+
+        std::fs::create_dir_all(repo.path().join("node_modules/pkg")).unwrap();
+        std::fs::write(repo.path().join("node_modules/pkg/index.js"), "pkg").unwrap();
+        std::os::unix::fs::symlink(outside.path(), repo.path().join("node_modules/escape"))
+            .unwrap();
+        std::os::unix::fs::symlink(outside.path(), repo.path().join("vendor")).unwrap();
+
+        let cloned = clone_dir_into_worktree(repo.path(), worktree.path(), "node_modules").unwrap();
+        assert!(cloned.is_some());
+        assert_eq!(
+            std::fs::read_to_string(worktree.path().join("node_modules/pkg/index.js")).unwrap(),
+            "pkg"
+        );
+        let escape = worktree.path().join("node_modules/escape");
+        assert!(
+            std::fs::symlink_metadata(&escape)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(std::fs::read_link(&escape).unwrap(), outside.path());
+
+        let skipped = clone_dir_into_worktree(repo.path(), worktree.path(), "vendor").unwrap();
+        assert_eq!(skipped, None);
+        assert!(std::fs::symlink_metadata(worktree.path().join("vendor")).is_err());
+    }
+
+    #[test]
+    fn paths_outside_the_repository_are_refused() {
+        let repo = TempDir::new().unwrap();
+        let worktree = TempDir::new().unwrap();
+
+        for dir in ["../outside", "/etc", "", ".git", ".schaltwerk/worktrees"] {
+            assert!(
+                clone_dir_into_worktree(repo.path(), worktree.path(), dir).is_err(),
+                "{dir} should be refused"
+            );
+        }
+    }
+}
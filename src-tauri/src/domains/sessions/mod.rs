@@ -1,11 +1,21 @@
 pub mod activity;
+pub mod agent_stats;
 pub mod cache;
+pub mod db_journal;
+pub mod db_schedule;
 pub mod db_sessions;
+pub mod db_snapshots;
 pub mod entity;
 pub mod lifecycle;
+pub mod name_retry;
 pub mod process_cleanup;
+pub mod relocation;
 pub mod repository;
+pub mod schedule;
 pub mod service;
+pub mod skip_permissions;
+pub mod templates;
+pub mod terminals;
 pub mod utils;
 
 #[cfg(test)]
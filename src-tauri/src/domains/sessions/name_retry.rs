@@ -0,0 +1,109 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// How long a session may wait for its generated name before the first background retry.
+pub const NAME_RETRY_INITIAL_DELAY: Duration = Duration::minutes(2);
+
+/// Background naming attempts per session before it keeps its original name.
+pub const MAX_NAME_RETRY_ATTEMPTS: u32 = 3;
+
+/// Background naming attempts made so far for one session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NameRetryState {
+    pub attempts: u32,
+    pub last_attempt: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameRetryDecision {
+    Wait,
+    Retry,
+    GiveUp,
+}
+
+/// Delay between a failed retry and the next one, doubling with every attempt.
+pub fn name_retry_delay(attempts: u32) -> Duration {
+    NAME_RETRY_INITIAL_DELAY * 2i32.pow(attempts.min(16))
+}
+
+/// Decides whether a session still pending name generation since `started_at` should
+/// be retried now. The first retry happens [`NAME_RETRY_INITIAL_DELAY`] after start;
+/// after [`MAX_NAME_RETRY_ATTEMPTS`] failed retries the session is given up on.
+pub fn decide_name_retry(
+    started_at: DateTime<Utc>,
+    state: &NameRetryState,
+    now: DateTime<Utc>,
+) -> NameRetryDecision {
+    if state.attempts >= MAX_NAME_RETRY_ATTEMPTS {
+        return NameRetryDecision::GiveUp;
+    }
+
+    let due = match state.last_attempt {
+        Some(last) => last + name_retry_delay(state.attempts),
+        None => started_at + NAME_RETRY_INITIAL_DELAY,
+    };
+    if now >= due {
+        NameRetryDecision::Retry
+    } else {
+        NameRetryDecision::Wait
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 4, 10, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn retries_back_off_exponentially_and_give_up_after_max_attempts() {
+        let started = at(0);
+        let mut state = NameRetryState::default();
+
+        assert_eq!(
+            decide_name_retry(started, &state, at(1)),
+            NameRetryDecision::Wait
+        );
+        assert_eq!(
+            decide_name_retry(started, &state, at(2)),
+            NameRetryDecision::Retry
+        );
+
+        state = NameRetryState {
+            attempts: 1,
+            last_attempt: Some(at(2)),
+        };
+        assert_eq!(
+            decide_name_retry(started, &state, at(5)),
+            NameRetryDecision::Wait
+        );
+        assert_eq!(
+            decide_name_retry(started, &state, at(6)),
+            NameRetryDecision::Retry
+        );
+
+        state = NameRetryState {
+            attempts: 2,
+            last_attempt: Some(at(6)),
+        };
+        assert_eq!(
+            decide_name_retry(started, &state, at(13)),
+            NameRetryDecision::Wait
+        );
+        assert_eq!(
+            decide_name_retry(started, &state, at(14)),
+            NameRetryDecision::Retry
+        );
+
+        state = NameRetryState {
+            attempts: MAX_NAME_RETRY_ATTEMPTS,
+            last_attempt: Some(at(14)),
+        };
+        assert_eq!(
+            decide_name_retry(started, &state, at(59)),
+            NameRetryDecision::GiveUp
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use crate::infrastructure::database::Database;
+use anyhow::{Context, Result, anyhow};
+use git2::Repository;
+use rusqlite::{Transaction, params};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Tables whose rows are scoped to a project by `repository_path`.
+const REPOSITORY_SCOPED_TABLES: &[&str] = &[
+    "sessions",
+    "specs",
+    "epics",
+    "archived_specs",
+    "operation_journal",
+];
+
+/// Tables that also record the repository's folder name next to its path.
+const REPOSITORY_NAMED_TABLES: &[&str] = &["sessions", "specs", "archived_specs"];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationFailure {
+    pub session_name: String,
+    pub worktree_path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationReport {
+    pub old_path: String,
+    pub new_path: String,
+    pub sessions_updated: usize,
+    pub specs_updated: usize,
+    pub repaired: Vec<String>,
+    pub failed: Vec<RelocationFailure>,
+}
+
+/// Looks for session worktrees under `.schaltwerk/worktrees` whose gitdir link points into
+/// a repository that no longer exists, and returns that repository's former location.
+pub fn detect_relocated_repository(repo_path: &Path) -> Option<PathBuf> {
+    let worktrees_dir = repo_path.join(".schaltwerk").join("worktrees");
+    let entries = std::fs::read_dir(&worktrees_dir).ok()?;
+
+    for entry in entries.flatten() {
+        let Some(gitdir) = read_gitdir_link(&entry.path()) else {
+            continue;
+        };
+        if gitdir.exists() {
+            continue;
+        }
+        let Some(old_repo) = gitdir
+            .ancestors()
+            .find(|ancestor| ancestor.file_name().is_some_and(|name| name == ".git"))
+            .and_then(Path::parent)
+        else {
+            continue;
+        };
+        if old_repo != repo_path && !old_repo.exists() {
+            return Some(old_repo.to_path_buf());
+        }
+    }
+
+    None
+}
+
+/// True when the database holds any session or spec, for any repository.
+pub fn database_has_records(db: &Database) -> Result<bool> {
+    let conn = db.get_conn()?;
+    let count: i64 = conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM sessions) + (SELECT COUNT(*) FROM specs)",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+fn read_gitdir_link(worktree_path: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string(worktree_path.join(".git")).ok()?;
+    let target = contents.trim().strip_prefix("gitdir:")?.trim();
+    Some(PathBuf::from(target))
+}
+
+fn relocated_path(path: &str, old_root: &Path, new_root: &Path) -> Option<PathBuf> {
+    Path::new(path)
+        .strip_prefix(old_root)
+        .ok()
+        .map(|rest| new_root.join(rest))
+}
+
+fn relocate_worktree_paths(
+    tx: &Transaction<'_>,
+    table: &str,
+    new_path: &str,
+    old_root: &Path,
+    new_root: &Path,
+) -> Result<()> {
+    let rows = {
+        let mut stmt = tx.prepare(&format!(
+            "SELECT id, worktree_path FROM {table}
+             WHERE repository_path = ?1 AND worktree_path IS NOT NULL"
+        ))?;
+        stmt.query_map(params![new_path], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    for (id, worktree_path) in rows {
+        if let Some(moved) = relocated_path(&worktree_path, old_root, new_root) {
+            tx.execute(
+                &format!("UPDATE {table} SET worktree_path = ?1 WHERE id = ?2"),
+                params![moved.to_string_lossy(), id],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites every row recorded under `old_root` so it belongs to `new_root`, including
+/// worktree paths that lived inside the old repository. Returns the number of session
+/// and spec rows that moved.
+pub fn relocate_repository_rows(
+    db: &Database,
+    old_root: &Path,
+    new_root: &Path,
+) -> Result<(usize, usize)> {
+    let old_path = old_root.to_string_lossy().to_string();
+    let new_path = new_root.to_string_lossy().to_string();
+    let new_name = new_root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| new_path.clone());
+
+    let mut conn = db.get_conn()?;
+    let tx = conn.transaction()?;
+
+    let mut sessions_updated = 0;
+    let mut specs_updated = 0;
+    for table in REPOSITORY_SCOPED_TABLES {
+        let updated = tx
+            .execute(
+                &format!("UPDATE {table} SET repository_path = ?1 WHERE repository_path = ?2"),
+                params![new_path, old_path],
+            )
+            .with_context(|| format!("Failed to relocate rows in {table}"))?;
+        match *table {
+            "sessions" => sessions_updated = updated,
+            "specs" => specs_updated = updated,
+            _ => {}
+        }
+    }
+    for table in REPOSITORY_NAMED_TABLES {
+        tx.execute(
+            &format!("UPDATE {table} SET repository_name = ?1 WHERE repository_path = ?2"),
+            params![new_name, new_path],
+        )?;
+    }
+
+    // The relocated project's config replaces whatever was recorded for the new path.
+    tx.execute(
+        "UPDATE OR REPLACE project_config SET repository_path = ?1 WHERE repository_path = ?2",
+        params![new_path, old_path],
+    )?;
+
+    relocate_worktree_paths(&tx, "sessions", &new_path, old_root, new_root)?;
+    relocate_worktree_paths(&tx, "operation_journal", &new_path, old_root, new_root)?;
+
+    tx.commit()?;
+    Ok((sessions_updated, specs_updated))
+}
+
+fn repair_worktree(repo_path: &Path, worktree_path: &Path) -> Result<()> {
+    if !worktree_path.exists() {
+        return Err(anyhow!("Worktree directory does not exist"));
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["worktree", "repair"])
+        .arg(worktree_path)
+        .output()
+        .map_err(|e| anyhow!("Failed to execute git worktree repair: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git worktree repair failed: {}", stderr.trim()));
+    }
+
+    let worktree = Repository::open(worktree_path)
+        .map_err(|e| anyhow!("Worktree does not open after repair: {e}"))?;
+    let expected = std::fs::canonicalize(repo_path.join(".git"))?;
+    let actual = std::fs::canonicalize(worktree.commondir())?;
+    if actual != expected {
+        return Err(anyhow!("Worktree still belongs to {}", actual.display()));
+    }
+    Ok(())
+}
+
+/// Moves a project's records from `old_root` to `new_root`, repairs the gitdir links of
+/// every active session worktree, and reports the sessions that are still broken.
+pub fn relocate_project(
+    db: &Database,
+    old_root: &Path,
+    new_root: &Path,
+) -> Result<RelocationReport> {
+    if !new_root.join(".git").exists() {
+        return Err(anyhow!("{} is not a git repository", new_root.display()));
+    }
+
+    let (sessions_updated, specs_updated) = relocate_repository_rows(db, old_root, new_root)?;
+
+    let active_sessions = {
+        let conn = db.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name, worktree_path FROM sessions
+             WHERE repository_path = ?1 AND status = 'active'
+             ORDER BY name",
+        )?;
+        stmt.query_map(params![new_root.to_string_lossy()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let mut report = RelocationReport {
+        old_path: old_root.to_string_lossy().to_string(),
+        new_path: new_root.to_string_lossy().to_string(),
+        sessions_updated,
+        specs_updated,
+        ..RelocationReport::default()
+    };
+
+    for (session_name, worktree_path) in active_sessions {
+        match repair_worktree(new_root, Path::new(&worktree_path)) {
+            Ok(()) => report.repaired.push(session_name),
+            Err(e) => {
+                log::warn!("Could not repair worktree of session '{session_name}': {e}");
+                report.failed.push(RelocationFailure {
+                    session_name,
+                    worktree_path,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::sessions::db_sessions::SessionMethods;
+    use crate::domains::sessions::entity::{Session, SessionState, SessionStatus};
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn session(name: &str, repo: &Path) -> Session {
+        Session {
+            id: format!("{name}-id"),
+            name: name.to_string(),
+            display_name: None,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            repository_path: repo.to_path_buf(),
+            repository_name: "old".to_string(),
+            branch: format!("schaltwerk/{name}"),
+            parent_branch: "main".to_string(),
+            original_parent_branch: Some("main".to_string()),
+            worktree_path: repo.join(".schaltwerk").join("worktrees").join(name),
+            status: SessionStatus::Active,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_activity: None,
+            initial_prompt: None,
+            ready_to_merge: false,
+            original_agent_type: None,
+            original_skip_permissions: None,
+            pending_name_generation: false,
+            was_auto_generated: false,
+            spec_content: None,
+            session_state: SessionState::Running,
+            resume_allowed: true,
+            amp_thread_id: None,
+            pr_number: None,
+            pr_url: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn relocating_a_moved_repository_repairs_session_worktrees() {
+        let temp = TempDir::new().unwrap();
+        let old_root = temp.path().join("old");
+        let new_root = temp.path().join("new");
+        std::fs::create_dir_all(&old_root).unwrap();
+        git(&old_root, &["init", "-q", "-b", "main"]);
+        git(
+            &old_root,
+            &[
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "init",
+            ],
+        );
+        let worktree = old_root.join(".schaltwerk").join("worktrees").join("alpha");
+        git(
+            &old_root,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "schaltwerk/alpha",
+                worktree.to_str().unwrap(),
+            ],
+        );
+
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        db.create_session(&session("alpha", &old_root)).unwrap();
+        let mut missing = session("beta", &old_root);
+        missing.worktree_path = temp.path().join("elsewhere");
+        db.create_session(&missing).unwrap();
+
+        std::fs::rename(&old_root, &new_root).unwrap();
+        assert_eq!(
+            detect_relocated_repository(&new_root),
+            Some(old_root.clone())
+        );
+
+        let report = relocate_project(&db, &old_root, &new_root).unwrap();
+
+        assert_eq!(report.sessions_updated, 2);
+        assert_eq!(report.repaired, vec!["alpha".to_string()]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].session_name, "beta");
+        assert_eq!(detect_relocated_repository(&new_root), None);
+
+        let alpha = db.get_session_by_name(&new_root, "alpha").unwrap();
+        assert_eq!(
+            alpha.worktree_path,
+            new_root.join(".schaltwerk").join("worktrees").join("alpha")
+        );
+        assert_eq!(alpha.repository_name, "new");
+        assert!(db.get_session_by_name(&old_root, "alpha").is_err());
+    }
+}
@@ -1,7 +1,14 @@
 use crate::{
+    domains::agents::EnvOverrides,
     domains::git::service as git,
+    domains::sessions::cache::EtaggedSpecContent,
+    domains::sessions::db_schedule::{ScheduledSpec, ScheduledSpecMethods, ScheduledSpecStatus},
     domains::sessions::db_sessions::SessionMethods,
-    domains::sessions::entity::{Epic, Session, SessionState, SessionStatus, Spec},
+    domains::sessions::db_snapshots::{SessionSnapshot, SessionSnapshotMethods},
+    domains::sessions::entity::{
+        Epic, Session, SessionLaunchOverrides, SessionLaunchSnapshot, SessionState,
+        SessionStateTransition, SessionStatus, Spec, StateTransitionReason,
+    },
     infrastructure::database::{AppConfigMethods, Database, EpicMethods, ProjectConfigMethods, SpecMethods},
 };
 use anyhow::{Context, Result, anyhow};
@@ -210,6 +217,63 @@ impl SessionDbManager {
             .map_err(|e| anyhow!("Failed to delete epic '{id}': {e}"))
     }
 
+    pub fn create_scheduled_spec(&self, entry: &ScheduledSpec) -> Result<()> {
+        self.db
+            .create_scheduled_spec(&self.repo_path, entry)
+            .map_err(|e| anyhow!("Failed to schedule spec '{}': {e}", entry.spec_name))
+    }
+
+    pub fn list_scheduled_specs(&self) -> Result<Vec<ScheduledSpec>> {
+        self.db
+            .list_scheduled_specs(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to list scheduled specs: {e}"))
+    }
+
+    pub fn get_scheduled_spec(&self, id: &str) -> Result<Option<ScheduledSpec>> {
+        self.db
+            .get_scheduled_spec(&self.repo_path, id)
+            .map_err(|e| anyhow!("Failed to get scheduled spec '{id}': {e}"))
+    }
+
+    pub fn finish_scheduled_spec(
+        &self,
+        id: &str,
+        status: ScheduledSpecStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.db
+            .finish_scheduled_spec(&self.repo_path, id, status, error)
+            .map_err(|e| anyhow!("Failed to update scheduled spec '{id}': {e}"))
+    }
+
+    pub fn create_session_snapshot(&self, snapshot: &SessionSnapshot) -> Result<()> {
+        self.db
+            .create_session_snapshot(&self.repo_path, snapshot)
+            .map_err(|e| anyhow!("Failed to record snapshot '{}': {e}", snapshot.label))
+    }
+
+    pub fn list_session_snapshots(&self, session_id: &str) -> Result<Vec<SessionSnapshot>> {
+        self.db
+            .list_session_snapshots(&self.repo_path, session_id)
+            .map_err(|e| anyhow!("Failed to list session snapshots: {e}"))
+    }
+
+    pub fn get_session_snapshot(
+        &self,
+        session_id: &str,
+        id: &str,
+    ) -> Result<Option<SessionSnapshot>> {
+        self.db
+            .get_session_snapshot(&self.repo_path, session_id, id)
+            .map_err(|e| anyhow!("Failed to get session snapshot '{id}': {e}"))
+    }
+
+    pub fn delete_session_snapshots(&self, session_id: &str) -> Result<usize> {
+        self.db
+            .delete_session_snapshots(&self.repo_path, session_id)
+            .map_err(|e| anyhow!("Failed to delete session snapshots: {e}"))
+    }
+
     pub fn get_spec_by_name(&self, name: &str) -> Result<Spec> {
         self.db
             .get_spec_by_name(&self.repo_path, name)
@@ -227,6 +291,11 @@ impl SessionDbManager {
             .map_err(|e| anyhow!("Failed to update spec content: {e}"))
     }
 
+    pub fn append_spec_content_by_id(&self, id: &str, content: &str) -> Result<()> {
+        SpecMethods::append_spec_content(&self.db, id, content)
+            .map_err(|e| anyhow!("Failed to append spec content: {e}"))
+    }
+
     pub fn update_spec_display_name(&self, id: &str, display_name: &str) -> Result<()> {
         SpecMethods::update_spec_display_name(&self.db, id, display_name)
             .map_err(|e| anyhow!("Failed to update spec display name: {e}"))
@@ -250,8 +319,17 @@ impl SessionDbManager {
     }
 
     pub fn update_session_state(&self, session_id: &str, state: SessionState) -> Result<()> {
+        self.update_session_state_with_reason(session_id, state, StateTransitionReason::Manual)
+    }
+
+    pub fn update_session_state_with_reason(
+        &self,
+        session_id: &str,
+        state: SessionState,
+        reason: StateTransitionReason,
+    ) -> Result<()> {
         self.db
-            .update_session_state(session_id, state)
+            .update_session_state_with_reason(session_id, state, reason)
             .map_err(|e| anyhow!("Failed to update session state: {e}"))?;
 
         if let Ok(session) = self.db.get_session_by_id(session_id) {
@@ -264,6 +342,12 @@ impl SessionDbManager {
         Ok(())
     }
 
+    pub fn get_state_history(&self, session_id: &str) -> Result<Vec<SessionStateTransition>> {
+        self.db
+            .get_state_history(session_id)
+            .map_err(|e| anyhow!("Failed to load session state history: {e}"))
+    }
+
     pub fn update_session_ready_to_merge(&self, session_id: &str, ready: bool) -> Result<()> {
         self.db
             .update_session_ready_to_merge(session_id, ready)
@@ -276,6 +360,30 @@ impl SessionDbManager {
             .map_err(|e| anyhow!("Failed to update session epic: {e}"))
     }
 
+    pub fn set_session_group(&self, session_id: &str, group: Option<&str>) -> Result<()> {
+        self.db
+            .set_session_group(session_id, group)
+            .map_err(|e| anyhow!("Failed to update session group: {e}"))
+    }
+
+    pub fn list_session_groups(&self) -> Result<Vec<String>> {
+        self.db
+            .list_session_groups(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to list session groups: {e}"))
+    }
+
+    pub fn set_session_recording(&self, session_id: &str, enabled: bool) -> Result<()> {
+        self.db
+            .set_session_recording(session_id, enabled)
+            .map_err(|e| anyhow!("Failed to update terminal recording: {e}"))
+    }
+
+    pub fn list_recording_sessions(&self) -> Result<Vec<String>> {
+        self.db
+            .list_recording_sessions(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to list recording sessions: {e}"))
+    }
+
     pub fn update_session_pr_info(
         &self,
         session_id: &str,
@@ -305,20 +413,14 @@ impl SessionDbManager {
     }
 
     pub fn append_spec_content(&self, session_id: &str, content: &str) -> Result<()> {
-        // Specs: replace with append semantics on specs table
+        // Specs: append on the specs table
         if let Ok(spec) = self.db.get_spec_by_id(session_id) {
-            let combined = if spec.content.is_empty() {
-                content.to_string()
-            } else {
-                format!("{}\n{}", spec.content, content)
-            };
-            self.update_spec_content_by_id(&spec.id, &combined)?;
+            self.append_spec_content_by_id(&spec.id, content)?;
             crate::domains::sessions::cache::invalidate_spec_content(&self.repo_path, &spec.name);
             return Ok(());
         }
 
-        self.db
-            .append_spec_content(session_id, content)
+        SessionMethods::append_spec_content(&self.db, session_id, content)
             .map_err(|e| anyhow!("Failed to append spec content: {e}"))?;
 
         if let Ok(session) = self.db.get_session_by_id(session_id) {
@@ -332,6 +434,13 @@ impl SessionDbManager {
     }
 
     pub fn get_session_task_content(&self, name: &str) -> Result<(Option<String>, Option<String>)> {
+        self.get_etagged_session_task_content(name)
+            .map(|tagged| tagged.content)
+    }
+
+    /// Spec content and initial prompt of the session together with their etag. Cached
+    /// content reuses the etag computed when it was cached, so no extra DB read happens.
+    pub fn get_etagged_session_task_content(&self, name: &str) -> Result<EtaggedSpecContent> {
         if let Some(cached) =
             crate::domains::sessions::cache::get_cached_spec_content(&self.repo_path, name)
         {
@@ -353,27 +462,27 @@ impl SessionDbManager {
                 {
                     // Try the specs table as a fallback
                     if let Ok(spec) = self.db.get_spec_by_name(&self.repo_path, name) {
-                        let result = (Some(spec.content.clone()), None, SessionState::Spec);
-                        crate::domains::sessions::cache::cache_spec_content(
+                        let result = EtaggedSpecContent::new((Some(spec.content), None));
+                        crate::domains::sessions::cache::cache_etagged_spec_content(
                             &self.repo_path,
                             name,
-                            (Some(spec.content), None),
+                            result.clone(),
                         );
-                        return Ok((result.0.clone(), result.1.clone()));
+                        return Ok(result);
                     }
 
                     warn!(
                         "Spec content requested for missing session '{name}', returning empty payload"
                     );
                     crate::domains::sessions::cache::invalidate_spec_content(&self.repo_path, name);
-                    return Ok((None, None));
+                    return Ok(EtaggedSpecContent::new((None, None)));
                 }
 
                 return Err(anyhow!("Failed to get session agent content: {error}"));
             }
         };
 
-        let result = (spec_content, initial_prompt);
+        let result = EtaggedSpecContent::new((spec_content, initial_prompt));
 
         if matches!(
             session_state,
@@ -381,7 +490,7 @@ impl SessionDbManager {
                 | crate::domains::sessions::entity::SessionState::Reviewed
         ) {
             log::debug!("Caching spec content for running/reviewed session: {name}");
-            crate::domains::sessions::cache::cache_spec_content(
+            crate::domains::sessions::cache::cache_etagged_spec_content(
                 &self.repo_path,
                 name,
                 result.clone(),
@@ -423,6 +532,18 @@ impl SessionDbManager {
             .map_err(|e| anyhow!("Failed to set session version info: {e}"))
     }
 
+    pub fn set_version_group_label(&self, group_id: &str, label: Option<&str>) -> Result<usize> {
+        self.db
+            .set_version_group_label(group_id, label)
+            .map_err(|e| anyhow!("Failed to set version group label: {e}"))
+    }
+
+    pub fn get_version_group_label(&self, group_id: &str) -> Result<Option<String>> {
+        self.db
+            .get_version_group_label(group_id)
+            .map_err(|e| anyhow!("Failed to get version group label: {e}"))
+    }
+
     pub fn clear_session_run_state(&self, session_id: &str) -> Result<()> {
         self.db
             .clear_session_run_state(session_id)
@@ -441,6 +562,103 @@ impl SessionDbManager {
             .map_err(|e| anyhow!("Failed to set amp_thread_id: {e}"))
     }
 
+    pub fn set_session_launch_overrides(
+        &self,
+        session_id: &str,
+        overrides: &SessionLaunchOverrides,
+    ) -> Result<()> {
+        self.db
+            .set_session_launch_overrides(session_id, overrides)
+            .map_err(|e| anyhow!("Failed to set session launch overrides: {e}"))
+    }
+
+    pub fn get_session_launch_overrides(&self, session_id: &str) -> Result<SessionLaunchOverrides> {
+        self.db
+            .get_session_launch_overrides(session_id)
+            .map_err(|e| anyhow!("Failed to load session launch overrides: {e}"))
+    }
+
+    pub fn set_session_extra_cli_args(
+        &self,
+        session_id: &str,
+        extra_cli_args: Option<&str>,
+    ) -> Result<()> {
+        self.db
+            .set_session_extra_cli_args(session_id, extra_cli_args)
+            .map_err(|e| anyhow!("Failed to set session extra CLI args: {e}"))
+    }
+
+    pub fn get_session_extra_cli_args(&self, session_id: &str) -> Result<Option<String>> {
+        self.db
+            .get_session_extra_cli_args(session_id)
+            .map_err(|e| anyhow!("Failed to load session extra CLI args: {e}"))
+    }
+
+    pub fn set_session_env_overrides(
+        &self,
+        session_id: &str,
+        overrides: &EnvOverrides,
+    ) -> Result<()> {
+        self.db
+            .set_session_env_overrides(session_id, overrides)
+            .map_err(|e| anyhow!("Failed to set session env overrides: {e}"))
+    }
+
+    pub fn get_session_env_overrides(&self, session_id: &str) -> Result<EnvOverrides> {
+        self.db
+            .get_session_env_overrides(session_id)
+            .map_err(|e| anyhow!("Failed to load session env overrides: {e}"))
+    }
+
+    pub fn set_session_launch_snapshot(
+        &self,
+        session_id: &str,
+        snapshot: &SessionLaunchSnapshot,
+    ) -> Result<()> {
+        self.db
+            .set_session_launch_snapshot(session_id, snapshot)
+            .map_err(|e| anyhow!("Failed to record session launch: {e}"))
+    }
+
+    pub fn get_session_launch_snapshot(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<SessionLaunchSnapshot>> {
+        self.db
+            .get_session_launch_snapshot(session_id)
+            .map_err(|e| anyhow!("Failed to load session launch record: {e}"))
+    }
+
+    pub fn set_session_stash(&self, session_id: &str, stash_oid: Option<&str>) -> Result<()> {
+        self.db
+            .set_session_stash(session_id, stash_oid)
+            .map_err(|e| anyhow!("Failed to record session stash: {e}"))
+    }
+
+    pub fn get_session_stash(&self, session_id: &str) -> Result<Option<String>> {
+        self.db
+            .get_session_stash(session_id)
+            .map_err(|e| anyhow!("Failed to load session stash: {e}"))
+    }
+
+    pub fn list_stashed_session_ids(&self) -> Result<Vec<String>> {
+        self.db
+            .list_stashed_session_ids(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to list stashed sessions: {e}"))
+    }
+
+    pub fn set_session_setup_failed(&self, session_id: &str, failed: bool) -> Result<()> {
+        self.db
+            .set_session_setup_failed(session_id, failed)
+            .map_err(|e| anyhow!("Failed to record setup result: {e}"))
+    }
+
+    pub fn list_setup_failed_session_ids(&self) -> Result<Vec<String>> {
+        self.db
+            .list_setup_failed_session_ids(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to list sessions with failed setup: {e}"))
+    }
+
     pub fn rename_draft_session(&self, old_name: &str, new_name: &str) -> Result<()> {
         self.db
             .rename_draft_session(&self.repo_path, old_name, new_name)
@@ -455,8 +673,31 @@ impl SessionDbManager {
 
     pub fn update_git_stats(&self, session_id: &str) -> Result<()> {
         let session = self.get_session_by_id(session_id)?;
-        git::calculate_git_stats_fast(&session.worktree_path, &session.parent_branch)?;
-        Ok(())
+        let mut stats =
+            git::calculate_git_stats_fast(&session.worktree_path, &session.parent_branch)?;
+        stats.session_id = session.id.clone();
+        self.save_git_stats(&stats)
+    }
+
+    /// Fetches `origin` for the session worktree and stores stats with fresh remote counts.
+    pub fn fetch_remote_status(&self, session_id: &str) -> Result<GitStats> {
+        let session = self.get_session_by_id(session_id)?;
+        let mut stats = git::fetch_remote_status(&session.worktree_path, &session.parent_branch)?;
+        stats.session_id = session.id.clone();
+        self.save_git_stats(&stats)?;
+        Ok(stats)
+    }
+
+    pub fn get_git_stats(&self, session_id: &str) -> Result<Option<GitStats>> {
+        self.db
+            .get_git_stats(session_id)
+            .map_err(|e| anyhow!("Failed to load git stats for '{session_id}': {e}"))
+    }
+
+    pub fn save_git_stats(&self, stats: &GitStats) -> Result<()> {
+        self.db
+            .save_git_stats(stats)
+            .map_err(|e| anyhow!("Failed to save git stats for '{}': {e}", stats.session_id))
     }
 
     pub fn get_project_setup_script(&self) -> Result<Option<String>> {
@@ -465,6 +706,18 @@ impl SessionDbManager {
             .map_err(|e| anyhow!("Failed to get project setup script: {e}"))
     }
 
+    pub fn get_project_keep_recordings(&self) -> Result<bool> {
+        self.db
+            .get_project_keep_recordings(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to get project keep-recordings setting: {e}"))
+    }
+
+    pub fn get_project_spec_file_mirror(&self) -> Result<bool> {
+        self.db
+            .get_project_spec_file_mirror(&self.repo_path)
+            .map_err(|e| anyhow!("Failed to get project spec-file mirror setting: {e}"))
+    }
+
     pub fn get_agent_type(&self) -> Result<String> {
         self.db
             .get_agent_type()
@@ -0,0 +1,137 @@
+use crate::domains::sessions::db_schedule::{ScheduledSpec, ScheduledSpecStatus};
+use chrono::{DateTime, Duration, Utc};
+
+/// Running sessions allowed before the scheduler holds back due entries.
+pub const DEFAULT_SCHEDULED_MAX_RUNNING: usize = 3;
+
+/// How far past its time an entry may be when the project opens and still count as
+/// on time rather than missed.
+pub const SCHEDULE_LATE_GRACE: Duration = Duration::minutes(5);
+
+/// Reads `SCHALTWERK_SCHEDULED_MAX_RUNNING`, falling back to
+/// [`DEFAULT_SCHEDULED_MAX_RUNNING`] when unset or invalid.
+pub fn scheduled_max_running_from_env() -> usize {
+    std::env::var("SCHALTWERK_SCHEDULED_MAX_RUNNING")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|cap| *cap > 0)
+        .unwrap_or(DEFAULT_SCHEDULED_MAX_RUNNING)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleAction {
+    Start(ScheduledSpec),
+    SkipLate(ScheduledSpec),
+}
+
+/// Decides what to do with the due entries of a project that has been open since
+/// `active_since`.
+///
+/// An entry whose time passed more than [`SCHEDULE_LATE_GRACE`] before the project
+/// became active was missed; it is skipped when marked `skip_if_late` and otherwise
+/// started like any other due entry. Starts are limited to `free_slots`, earliest
+/// first; entries waiting for a slot are never treated as missed.
+pub fn plan_due_schedules(
+    entries: &[ScheduledSpec],
+    now: DateTime<Utc>,
+    active_since: DateTime<Utc>,
+    free_slots: usize,
+) -> Vec<ScheduleAction> {
+    let mut due: Vec<&ScheduledSpec> = entries
+        .iter()
+        .filter(|e| e.status == ScheduledSpecStatus::Pending && e.not_before <= now)
+        .collect();
+    due.sort_by_key(|e| (e.not_before, e.created_at));
+
+    let mut slots = free_slots;
+    let mut actions = Vec::new();
+    for entry in due {
+        let missed = entry.not_before + SCHEDULE_LATE_GRACE < active_since;
+        if missed && entry.skip_if_late {
+            actions.push(ScheduleAction::SkipLate(entry.clone()));
+        } else if slots > 0 {
+            slots -= 1;
+            actions.push(ScheduleAction::Start(entry.clone()));
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 4, hour, minute, 0).unwrap()
+    }
+
+    fn entry(id: &str, not_before: DateTime<Utc>, skip_if_late: bool) -> ScheduledSpec {
+        ScheduledSpec {
+            id: id.to_string(),
+            spec_name: format!("spec-{id}"),
+            not_before,
+            base_branch: None,
+            agent_type: None,
+            skip_permissions: None,
+            skip_if_late,
+            status: ScheduledSpecStatus::Pending,
+            created_at: at(0, 0),
+            finished_at: None,
+            error: None,
+        }
+    }
+
+    fn ids(actions: &[ScheduleAction]) -> Vec<String> {
+        actions
+            .iter()
+            .map(|a| match a {
+                ScheduleAction::Start(e) => format!("start:{}", e.id),
+                ScheduleAction::SkipLate(e) => format!("skip:{}", e.id),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn starts_due_entries_in_time_order_up_to_free_slots() {
+        let mut cancelled = entry("x", at(17, 0), false);
+        cancelled.status = ScheduledSpecStatus::Cancelled;
+        let entries = vec![
+            entry("later", at(19, 0), false),
+            entry("b", at(18, 30), false),
+            entry("a", at(18, 0), false),
+            entry("c", at(18, 45), false),
+            cancelled,
+        ];
+
+        let actions = plan_due_schedules(&entries, at(18, 50), at(9, 0), 2);
+        assert_eq!(ids(&actions), vec!["start:a", "start:b"]);
+
+        assert!(plan_due_schedules(&entries, at(18, 50), at(9, 0), 0).is_empty());
+    }
+
+    #[test]
+    fn missed_entries_fire_on_open_unless_skip_if_late() {
+        let entries = vec![
+            entry("missed-skip", at(18, 0), true),
+            entry("missed-run", at(18, 5), false),
+            entry("just-now", at(21, 58), true),
+        ];
+
+        // Project opened at 22:00, long after the first two were due
+        let actions = plan_due_schedules(&entries, at(22, 0), at(22, 0), 5);
+        assert_eq!(
+            ids(&actions),
+            vec!["skip:missed-skip", "start:missed-run", "start:just-now"]
+        );
+    }
+
+    #[test]
+    fn waiting_for_a_slot_does_not_make_an_entry_late() {
+        let entries = vec![entry("queued", at(18, 0), true)];
+
+        // Open since morning; the entry waited hours for a free slot
+        let actions = plan_due_schedules(&entries, at(23, 0), at(9, 0), 1);
+        assert_eq!(ids(&actions), vec!["start:queued"]);
+    }
+}
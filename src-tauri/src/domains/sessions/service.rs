@@ -1,9 +1,12 @@
-use crate::domains::agents::{AgentLaunchSpec, naming::sanitize_name};
+use crate::domains::agents::{
+    AgentLaunchSpec, CliArgPlaceholders, EnvOverrides, expand_cli_placeholders,
+    naming::sanitize_name,
+};
 use crate::shared::terminal_id::{terminal_id_for_session_bottom, terminal_id_for_session_top};
 use anyhow::{Context, Result, anyhow};
 use chrono::Utc;
 use log::{info, warn};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use which::which;
 
@@ -53,6 +56,29 @@ fn agent_binary_available(agent: &str, binary_paths: &HashMap<String, String>) -
         .unwrap_or(false)
 }
 
+/// Monitor status of a running session, present once its agent output was recognised.
+fn agent_monitor_status(
+    session: &Session,
+    diff_stats: Option<DiffStats>,
+) -> Option<SessionMonitorStatus> {
+    let agent = agent_status_for_session(&session.name)?;
+    Some(SessionMonitorStatus {
+        session_name: agent.session_name,
+        current_task: session.initial_prompt.clone().unwrap_or_default(),
+        test_status: TestStatus::Unknown,
+        diff_stats,
+        last_update: agent.updated_at,
+        agent_status: Some(agent.status),
+    })
+}
+
+/// Whether the project setup script of `session_name` is queued or still running.
+fn is_setup_running(session_name: &str) -> bool {
+    SetupRunsRegistry::global()
+        .status(session_name)
+        .is_some_and(|status| !status.is_finished())
+}
+
 fn normalize_agent_name(name: &str) -> &str {
     match name {
         "kilocode" => "kilo",
@@ -107,6 +133,8 @@ pub struct SessionCreationParams<'a> {
     pub was_auto_generated: bool,
     pub version_group_id: Option<&'a str>,
     pub version_number: Option<i32>,
+    /// Label for the version group; ignored when the group already has one.
+    pub version_group_label: Option<&'a str>,
     pub epic_id: Option<&'a str>,
     pub agent_type: Option<&'a str>,
     pub skip_permissions: Option<bool>,
@@ -115,6 +143,28 @@ pub struct SessionCreationParams<'a> {
     pub pr_number: Option<i64>,
 }
 
+/// Slow steps of session creation, reported in this order. `Ready` or `Failed` always
+/// comes last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionCreationStep {
+    ReservingName,
+    CreatingWorktree,
+    CopyingOverrides,
+    ComputingStats,
+    Ready,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCreationProgress {
+    pub session_name: String,
+    pub step: SessionCreationStep,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 pub struct AgentLaunchParams<'a> {
     pub session_name: &'a str,
     pub force_restart: bool,
@@ -127,22 +177,63 @@ pub struct AgentLaunchParams<'a> {
 
 use crate::{
     domains::git::service as git,
-    domains::sessions::cache::SessionCacheManager,
+    domains::sessions::agent_stats::{
+        AgentStatsMethods, AgentStatsPeriod, AgentStatsRow, aggregate_agent_stats,
+    },
+    domains::sessions::cache::{self, SessionCacheManager},
+    domains::sessions::db_journal::{JournalEntry, OperationKind},
     domains::sessions::db_sessions::SessionMethods,
     domains::sessions::entity::ArchivedSpec,
     domains::sessions::entity::{
-        DiffStats, EnrichedSession, Epic, FilterMode, Session, SessionInfo, SessionState,
-        SessionStatus, SessionStatusType, SessionType, SortMode, Spec,
+        DiffStats, EnrichedSession, Epic, FilterMode, GitStats, Session, SessionAgentContent,
+        SessionInfo, SessionLaunchOverrides, SessionLaunchSnapshot, SessionMonitorStatus,
+        SessionState, SessionStateTransition, SessionStatus, SessionStatusType, SessionType,
+        SortMode, Spec, StateTransitionReason, TestStatus, WorktreeClass, WorktreeClassification,
     },
+    domains::sessions::lifecycle::cancellation::{
+        discard_session_recordings, discard_session_snapshots,
+    },
+    domains::sessions::lifecycle::recovery::{JournalGuard, cancel_journal_entry},
     domains::sessions::repository::SessionDbManager,
+    domains::sessions::skip_permissions::{authorize_launch, is_running_with_skipped_permissions},
+    domains::sessions::templates::render_prompt_template,
+    domains::sessions::terminals::attach_terminal_details,
+    domains::terminal::TerminalLiveness,
     domains::sessions::utils::SessionUtils,
     shared::format_branch_name,
+    infrastructure::agent_status_bridge::agent_status_for_session,
     infrastructure::database::db_project_config::{DEFAULT_BRANCH_PREFIX, ProjectConfigMethods},
-    infrastructure::database::{Database, db_archived_specs::ArchivedSpecMethods as _},
+    infrastructure::database::{
+        Database, SpecMethods as _, db_archived_specs::ArchivedSpecMethods as _,
+    },
+    services::setup_scripts::SetupRunsRegistry,
 };
 use uuid::Uuid;
 
 mod epics;
+mod git_refresh;
+mod issue_sessions;
+mod parent_sync;
+mod scheduled_specs;
+mod session_fork;
+mod session_groups;
+mod session_naming;
+mod session_snapshots;
+mod spec_dependencies;
+mod spec_files;
+mod terminal_recording;
+mod version_groups;
+
+pub use git_refresh::{RemoteDivergence, SessionGitRefresh, WorktreeGitState, WorktreeOperation};
+pub use issue_sessions::{IssueSessionOptions, issue_session_name, render_issue_prompt};
+pub use scheduled_specs::ScheduleSpecOptions;
+pub use spec_files::{
+    SkippedSpecFile, SpecImportReport, SpecSyncReport, SpecSyncState, spec_files_in_directory,
+};
+pub use version_groups::{
+    MAX_GROUP_VERSIONS, VersionAgentConfig, VersionComparisonEntry, VersionGroupCreation,
+    VersionGroupDiff, VersionGroupParams, VersionPairSummary, VersionWinnerSelection,
+};
 
 #[cfg(test)]
 mod service_unified_tests {
@@ -228,6 +319,7 @@ mod service_unified_tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path,
             repository_name: "test-repo".to_string(),
@@ -251,6 +343,7 @@ mod service_unified_tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         }
     }
 
@@ -299,7 +392,7 @@ mod service_unified_tests {
             .create_spec_session(spec_name, "Build feature A")
             .unwrap();
         let session = manager
-            .start_spec_session(spec_name, None, None, None)
+            .start_spec_session(spec_name, None, None, None, false)
             .unwrap();
 
         // Simulate Claude session files existing for this worktree so resume would normally happen
@@ -764,6 +857,67 @@ mod service_unified_tests {
         assert_eq!(enriched[0].info.session_id, session.name);
     }
 
+    #[test]
+    fn list_enriched_sessions_serves_conflicts_from_cache() {
+        let (manager, temp_dir) = create_test_session_manager();
+        let session = create_test_session(&temp_dir, "claude", "cached-conflicts");
+        manager.db_manager.create_session(&session).unwrap();
+        // The worktree is no git repository, so fresh detection would report no conflicts
+        cache::cache_conflicts(&session.id, true);
+
+        let enriched = manager.list_enriched_sessions().unwrap();
+
+        assert_eq!(enriched[0].info.has_conflicts, Some(true));
+        cache::invalidate_conflicts(&session.id);
+    }
+
+    #[test]
+    fn list_enriched_sessions_refreshes_conflicts_in_background() {
+        use std::process::Command;
+        let (manager, temp_dir) = create_test_session_manager();
+        let session = create_test_session(&temp_dir, "claude", "background-conflicts");
+        manager.db_manager.create_session(&session).unwrap();
+
+        let worktree = &session.worktree_path;
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(worktree)
+                .output()
+                .unwrap()
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        std::fs::write(worktree.join("file.txt"), "base\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "base"]);
+        git(&["branch", "-M", "main"]);
+        git(&["checkout", "-b", "feature"]);
+        std::fs::write(worktree.join("file.txt"), "feature\n").unwrap();
+        git(&["commit", "-am", "feature"]);
+        git(&["checkout", "main"]);
+        std::fs::write(worktree.join("file.txt"), "main\n").unwrap();
+        git(&["commit", "-am", "main"]);
+        assert!(!git(&["merge", "feature"]).status.success());
+
+        let first = manager.list_enriched_sessions().unwrap();
+        assert_eq!(first[0].info.has_conflicts, None, "unknown until refreshed");
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while cache::cached_conflicts(&session.id).is_none() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "background conflict refresh did not finish"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        let second = manager.list_enriched_sessions().unwrap();
+        assert_eq!(second[0].info.has_conflicts, Some(true));
+        cache::invalidate_conflicts(&session.id);
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_start_spec_with_config_uses_codex_and_prompt_without_resume() {
@@ -807,7 +961,15 @@ mod service_unified_tests {
             .unwrap();
 
         let running = manager
-            .start_spec_session_with_config(spec_name, None, None, None, Some("codex"), Some(true))
+            .start_spec_session_with_config(
+                spec_name,
+                None,
+                None,
+                None,
+                Some("codex"),
+                Some(true),
+                false,
+            )
             .unwrap();
 
         // Build the start command (unified start handles correct agent based on original settings)
@@ -925,7 +1087,15 @@ mod service_unified_tests {
             .unwrap();
 
         let running = manager
-            .start_spec_session_with_config(spec_name, None, None, None, Some("codex"), Some(true))
+            .start_spec_session_with_config(
+                spec_name,
+                None,
+                None,
+                None,
+                Some("codex"),
+                Some(true),
+                false,
+            )
             .unwrap();
 
         let stored = manager
@@ -939,6 +1109,95 @@ mod service_unified_tests {
         assert!(!manager.db_manager.get_skip_permissions().unwrap());
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn scheduled_spec_starts_once_due_and_cancelled_entries_stay_put() {
+        use crate::domains::sessions::db_schedule::ScheduledSpecStatus;
+        use std::process::Command;
+        let (manager, temp_dir) = create_test_session_manager();
+
+        let repo = temp_dir.path().join("repo");
+        for args in [
+            vec!["init"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test User"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(&repo)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "init"])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+
+        manager
+            .create_spec_session("nightly", "Run the heavy refactor")
+            .unwrap();
+        manager
+            .create_spec_session("dropped", "Never mind")
+            .unwrap();
+        assert!(
+            manager
+                .schedule_spec("missing", Utc::now(), ScheduleSpecOptions::default())
+                .is_err()
+        );
+
+        let trigger = Utc::now() + chrono::Duration::hours(1);
+        let options = ScheduleSpecOptions {
+            agent_type: Some("codex".to_string()),
+            ..Default::default()
+        };
+        let nightly = manager
+            .schedule_spec("nightly", trigger, options.clone())
+            .unwrap();
+        let dropped = manager
+            .schedule_spec("dropped", trigger + chrono::Duration::minutes(1), options)
+            .unwrap();
+        manager.cancel_scheduled_spec(&dropped.id).unwrap();
+        assert!(manager.cancel_scheduled_spec(&dropped.id).is_err());
+
+        let opened = Utc::now();
+        assert!(
+            manager
+                .run_due_scheduled_specs(Utc::now(), opened, 3)
+                .unwrap()
+                .is_empty()
+        );
+
+        let changed = manager
+            .run_due_scheduled_specs(trigger + chrono::Duration::minutes(1), opened, 3)
+            .unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].id, nightly.id);
+        assert_eq!(changed[0].status, ScheduledSpecStatus::Started);
+
+        let session = manager.db_manager.get_session_by_name("nightly").unwrap();
+        assert_eq!(session.session_state, SessionState::Running);
+        assert_eq!(session.original_agent_type.as_deref(), Some("codex"));
+        assert!(manager.db_manager.get_spec_by_name("dropped").is_ok());
+
+        let statuses: Vec<ScheduledSpecStatus> = manager
+            .list_scheduled_specs()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![ScheduledSpecStatus::Started, ScheduledSpecStatus::Cancelled]
+        );
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_droid_receives_initial_prompt_on_fresh_start() {
@@ -980,7 +1239,7 @@ mod service_unified_tests {
             .unwrap();
 
         let running = manager
-            .start_spec_session_with_config(spec_name, None, None, None, Some("droid"), None)
+            .start_spec_session_with_config(spec_name, None, None, None, Some("droid"), None, false)
             .unwrap();
 
         let cmd = manager
@@ -1046,7 +1305,7 @@ mod service_unified_tests {
             .unwrap();
 
         let running = manager
-            .start_spec_session_with_config(spec_name, None, None, None, Some("droid"), None)
+            .start_spec_session_with_config(spec_name, None, None, None, Some("droid"), None, false)
             .unwrap();
 
         let home_dir = tempfile::TempDir::new().unwrap();
@@ -1153,6 +1412,7 @@ mod service_unified_tests {
                 Some(1),
                 Some("codex"),
                 Some(false),
+                false,
             )
             .unwrap();
         assert_eq!(running.version_group_id.as_deref(), Some(group_id));
@@ -1226,7 +1486,7 @@ mod service_unified_tests {
             .unwrap();
 
         let session = manager
-            .start_spec_session("feature_spec", Some("feature/login"), None, None)
+            .start_spec_session("feature_spec", Some("feature/login"), None, None, false)
             .unwrap();
         assert_eq!(session.parent_branch, "feature/login");
 
@@ -1314,7 +1574,7 @@ mod service_unified_tests {
             .unwrap();
 
         let session = manager
-            .start_spec_session("stored_spec", None, None, None)
+            .start_spec_session("stored_spec", None, None, None, false)
             .unwrap();
         assert_eq!(session.parent_branch, "main");
 
@@ -1374,7 +1634,7 @@ mod service_unified_tests {
             .unwrap();
 
         let session = manager
-            .start_spec_session("spec-pending", None, None, None)
+            .start_spec_session("spec-pending", None, None, None, false)
             .unwrap();
 
         assert!(session.pending_name_generation);
@@ -1431,7 +1691,7 @@ mod service_unified_tests {
             .unwrap();
 
         let session = manager
-            .start_spec_session("spec-friendly", None, None, None)
+            .start_spec_session("spec-friendly", None, None, None, false)
             .unwrap();
 
         assert_eq!(session.display_name.as_deref(), Some("friendly-name"));
@@ -1522,6 +1782,7 @@ mod service_unified_tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: Some("claude"),
             skip_permissions: Some(true),
@@ -1551,6 +1812,84 @@ mod service_unified_tests {
         );
     }
 
+    #[test]
+    fn session_creation_reports_progress_steps_and_failure() {
+        use std::cell::RefCell;
+        let (manager, temp_dir) = create_test_session_manager();
+        let repo_root = temp_dir.path().join("repo");
+        for args in [
+            &["init"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test User"],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo_root)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(repo_root.join("README.md"), "Initial").unwrap();
+        for args in [&["add", "."][..], &["commit", "-m", "init"]] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo_root)
+                .output()
+                .unwrap();
+        }
+
+        let params = |name| SessionCreationParams {
+            name,
+            prompt: None,
+            base_branch: None,
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: Some("claude"),
+            skip_permissions: None,
+            pr_number: None,
+        };
+        let events = RefCell::new(Vec::new());
+        let record = |progress: SessionCreationProgress| events.borrow_mut().push(progress);
+
+        let session = manager
+            .create_session_with_progress(params("progress"), &record)
+            .unwrap();
+
+        let steps: Vec<_> = events.borrow().iter().map(|e| e.step).collect();
+        assert_eq!(
+            steps,
+            vec![
+                SessionCreationStep::ReservingName,
+                SessionCreationStep::CreatingWorktree,
+                SessionCreationStep::CopyingOverrides,
+                SessionCreationStep::ComputingStats,
+                SessionCreationStep::Ready,
+            ]
+        );
+        assert!(
+            events
+                .borrow()
+                .iter()
+                .all(|e| e.session_name == session.name)
+        );
+
+        events.borrow_mut().clear();
+        let err = manager
+            .create_session_with_progress(params("bad name!"), &record)
+            .unwrap_err();
+
+        let events = events.into_inner();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].step, SessionCreationStep::Failed);
+        assert_eq!(events[0].session_name, "bad name!");
+        assert_eq!(events[0].error.as_deref(), Some(err.to_string().as_str()));
+    }
+
     #[test]
     fn non_claude_session_does_not_copy_local_overrides() {
         let (manager, temp_dir) = create_test_session_manager();
@@ -1598,6 +1937,7 @@ mod service_unified_tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: Some("opencode"),
             skip_permissions: Some(false),
@@ -1660,6 +2000,7 @@ mod service_unified_tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1726,6 +2067,7 @@ mod service_unified_tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -1781,6 +2123,7 @@ mod service_unified_tests {
             was_auto_generated: false,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             agent_type: Some("gemini"),
             skip_permissions: Some(true),
@@ -1987,11 +2330,6 @@ impl SessionManager {
             return Ok(false);
         }
 
-        self.db_manager
-            .db
-            .update_session_display_name(&session.id, &sanitized)?;
-        session.display_name = Some(sanitized.clone());
-
         let branch_prefix = self
             .db_manager
             .db
@@ -2003,25 +2341,74 @@ impl SessionManager {
                 DEFAULT_BRANCH_PREFIX.to_string()
             });
 
-        let target_branch = format_branch_name(&branch_prefix, &sanitized);
-        if target_branch == session.branch {
-            return Ok(true);
+        let mut target_branch = format_branch_name(&branch_prefix, &sanitized);
+
+        // Another session or a user branch may already carry the name; suffix the branch
+        // like session creation does instead of failing the rename.
+        if target_branch != session.branch && git::branch_exists(&self.repo_path, &target_branch)? {
+            let base = target_branch;
+            target_branch = (2..=100)
+                .map(|n| format!("{base}-{n}"))
+                .find(|candidate| {
+                    *candidate == session.branch
+                        || !git::branch_exists(&self.repo_path, candidate).unwrap_or(true)
+                })
+                .ok_or_else(|| anyhow!("No free branch name derived from '{base}'"))?;
         }
 
-        git::rename_branch(&self.repo_path, &session.branch, &target_branch)?;
-
-        if let Err(e) = git::update_worktree_branch(&session.worktree_path, &target_branch) {
-            let _ = git::rename_branch(&self.repo_path, &target_branch, &session.branch);
-            return Err(e);
+        if target_branch != session.branch {
+            self.rename_session_branch(session, &target_branch)?;
+            session.branch = target_branch;
         }
 
         self.db_manager
             .db
-            .update_session_branch(&session.id, &target_branch)?;
-        session.branch = target_branch;
+            .update_session_display_name(&session.id, &sanitized)?;
+        session.display_name = Some(sanitized);
         Ok(true)
     }
 
+    /// Renames the session branch, checks it out in the worktree and records it, undoing
+    /// the completed steps when a later one fails.
+    fn rename_session_branch(&self, session: &Session, target_branch: &str) -> Result<()> {
+        git::rename_branch(&self.repo_path, &session.branch, target_branch)?;
+
+        if let Err(e) = git::update_worktree_branch(&session.worktree_path, target_branch) {
+            self.roll_back_branch_rename(session, target_branch, false);
+            return Err(e);
+        }
+
+        if let Err(e) = self
+            .db_manager
+            .db
+            .update_session_branch(&session.id, target_branch)
+        {
+            self.roll_back_branch_rename(session, target_branch, true);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn roll_back_branch_rename(&self, session: &Session, renamed_to: &str, worktree_updated: bool) {
+        if let Err(e) = git::rename_branch(&self.repo_path, renamed_to, &session.branch) {
+            log::warn!(
+                "Failed to restore branch '{}' of session '{}': {e}",
+                session.branch,
+                session.name
+            );
+            return;
+        }
+        if worktree_updated
+            && let Err(e) = git::update_worktree_branch(&session.worktree_path, &session.branch)
+        {
+            log::warn!(
+                "Failed to check out restored branch '{}' in session '{}': {e}",
+                session.branch,
+                session.name
+            );
+        }
+    }
+
     pub fn new(db: Database, repo_path: PathBuf) -> Self {
         log::trace!(
             "Creating SessionManager with repo path: {}",
@@ -2069,6 +2456,7 @@ impl SessionManager {
             was_auto_generated,
             version_group_id,
             version_number,
+            version_group_label: None,
             epic_id: None,
             agent_type: None,
             skip_permissions: None,
@@ -2077,7 +2465,111 @@ impl SessionManager {
         self.create_session_with_agent(params)
     }
 
+    /// Renders the named project prompt template into the initial prompt and creates the
+    /// session with it. `name`, `branch`, `base_branch` and `date` are always available as
+    /// placeholders; `vars` override them and the template's defaults fill any gaps.
+    pub fn create_session_from_template(
+        &self,
+        template_name: &str,
+        vars: &HashMap<String, String>,
+        params: SessionCreationParams,
+    ) -> Result<Session> {
+        let template = self
+            .db_manager
+            .db
+            .get_project_prompt_templates(&self.repo_path)?
+            .into_iter()
+            .find(|template| template.name == template_name)
+            .ok_or_else(|| anyhow!("Prompt template '{template_name}' not found"))?;
+
+        let branch = match params.custom_branch {
+            Some(branch) => branch.to_string(),
+            None => format_branch_name(&self.utils.branch_prefix(), params.name),
+        };
+        let base_branch = self.resolve_parent_branch(params.base_branch)?;
+        let date = Utc::now().format("%Y-%m-%d").to_string();
+        let mut values = HashMap::from([
+            ("name".to_string(), params.name.to_string()),
+            ("branch".to_string(), branch),
+            ("base_branch".to_string(), base_branch),
+            ("date".to_string(), date),
+        ]);
+        values.extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let prompt = render_prompt_template(&template.content, &values, &template.defaults)
+            .with_context(|| format!("Failed to render prompt template '{template_name}'"))?;
+
+        self.create_session_with_agent(SessionCreationParams {
+            prompt: Some(&prompt),
+            ..params
+        })
+    }
+
     pub fn create_session_with_agent(&self, params: SessionCreationParams) -> Result<Session> {
+        self.create_session_with_progress(params, &|_| {})
+    }
+
+    /// Creates the session like `create_session_with_agent` and reports each slow step to
+    /// `on_progress`. Errors are reported as `SessionCreationStep::Failed` before they are
+    /// returned, so listeners can always clear their progress.
+    pub fn create_session_with_progress(
+        &self,
+        params: SessionCreationParams,
+        on_progress: &dyn Fn(SessionCreationProgress),
+    ) -> Result<Session> {
+        let last_name = std::cell::RefCell::new(params.name.to_string());
+        let report = |session_name: &str, step: SessionCreationStep| {
+            last_name.replace(session_name.to_string());
+            on_progress(SessionCreationProgress {
+                session_name: session_name.to_string(),
+                step,
+                error: None,
+            });
+        };
+
+        match self.create_session_reporting(params, &report) {
+            Ok(session) => {
+                report(&session.name, SessionCreationStep::Ready);
+                Ok(session)
+            }
+            Err(e) => {
+                on_progress(SessionCreationProgress {
+                    session_name: last_name.take(),
+                    step: SessionCreationStep::Failed,
+                    error: Some(e.to_string()),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// The project's globs of untracked files to copy into every new worktree.
+    fn worktree_copy_patterns(&self) -> Vec<String> {
+        self.db_manager
+            .db
+            .get_project_worktree_copy_patterns(&self.repo_path)
+            .unwrap_or_else(|err| {
+                log::warn!("Failed to load worktree copy patterns: {err}");
+                Vec::new()
+            })
+    }
+
+    /// The project's untracked directories to clone into every new worktree.
+    fn worktree_clone_dirs(&self) -> Vec<String> {
+        self.db_manager
+            .db
+            .get_project_worktree_clone_dirs(&self.repo_path)
+            .unwrap_or_else(|err| {
+                log::warn!("Failed to load worktree clone directories: {err}");
+                Vec::new()
+            })
+    }
+
+    fn create_session_reporting(
+        &self,
+        params: SessionCreationParams,
+        report: &dyn Fn(&str, SessionCreationStep),
+    ) -> Result<Session> {
         use crate::domains::sessions::lifecycle::bootstrapper::{
             BootstrapConfig, WorktreeBootstrapper,
         };
@@ -2104,6 +2596,20 @@ impl SessionManager {
             let _ = self.db_manager.get_epic_by_id(epic_id)?;
         }
 
+        // Later versions keep the label the group already has.
+        let existing_group_label = match params.version_group_id {
+            Some(group_id) => self.db_manager.get_version_group_label(group_id)?,
+            None => None,
+        };
+        let version_group_label = existing_group_label.clone().or_else(|| {
+            params
+                .version_group_id
+                .and(params.version_group_label)
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(String::from)
+        });
+
         if params.use_existing_branch && params.pr_number.is_none() {
             let custom_branch = params.custom_branch.ok_or_else(|| {
                 anyhow!("use_existing_branch requires custom_branch to be specified")
@@ -2131,6 +2637,7 @@ impl SessionManager {
             }
         }
 
+        report(params.name, SessionCreationStep::ReservingName);
         let (unique_name, branch, worktree_path) = if let Some(custom_branch) = params.custom_branch
         {
             if !git::is_valid_branch_name(custom_branch) {
@@ -2139,19 +2646,9 @@ impl SessionManager {
                 ));
             }
 
-            let branch_exists = git::branch_exists(&self.repo_path, custom_branch)?;
-            let final_branch = if branch_exists {
-                let suffix = SessionUtils::generate_random_suffix(2);
-                format!("{custom_branch}-{suffix}")
-            } else {
-                custom_branch.to_string()
-            };
+            let final_branch = self.utils.resolve_branch_collision(custom_branch)?;
 
-            let worktree_path = self
-                .repo_path
-                .join(".schaltwerk")
-                .join("worktrees")
-                .join(params.name);
+            let worktree_path = self.utils.worktree_path_for(params.name);
 
             (params.name.to_string(), final_branch, worktree_path)
         } else {
@@ -2159,6 +2656,15 @@ impl SessionManager {
         };
 
         let session_id = SessionUtils::generate_session_id();
+
+        let mut journal_entry =
+            JournalEntry::new(&self.repo_path, OperationKind::Create, &unique_name);
+        journal_entry.session_id = Some(session_id.clone());
+        journal_entry.branch = Some(branch.clone());
+        journal_entry.worktree_path = Some(worktree_path.clone());
+        journal_entry.owns_branch = !params.use_existing_branch;
+        let _journal = JournalGuard::begin(&self.db_manager.db, journal_entry);
+
         self.utils.cleanup_existing_worktree(&worktree_path)?;
 
         // When using an existing branch, the parent_branch should be the default branch
@@ -2194,6 +2700,8 @@ impl SessionManager {
             .unwrap_or_else(|| default_agent_type.clone());
         let effective_skip_permissions = params.skip_permissions.unwrap_or(global_skip_default);
         let should_copy_claude_locals = effective_agent_type.eq_ignore_ascii_case("claude");
+        let copy_patterns = self.worktree_copy_patterns();
+        let clone_dirs = self.worktree_clone_dirs();
 
         self.ensure_repository_initialized(&parent_branch)?;
 
@@ -2206,10 +2714,14 @@ impl SessionManager {
             custom_branch: params.custom_branch,
             use_existing_branch: params.use_existing_branch,
             sync_with_origin: params.sync_with_origin,
-            should_copy_claude_locals,
+            // Copied below so the step can be reported on its own
+            should_copy_claude_locals: false,
+            copy_patterns: &[],
+            clone_dirs: &[],
             pr_number: params.pr_number,
         };
 
+        report(&unique_name, SessionCreationStep::CreatingWorktree);
         let bootstrap_result = match bootstrapper.bootstrap_worktree(bootstrap_config) {
             Ok(result) => result,
             Err(e) => {
@@ -2218,6 +2730,15 @@ impl SessionManager {
             }
         };
 
+        if should_copy_claude_locals || !copy_patterns.is_empty() || !clone_dirs.is_empty() {
+            report(&unique_name, SessionCreationStep::CopyingOverrides);
+            if should_copy_claude_locals {
+                bootstrapper.copy_claude_locals(&bootstrap_result.worktree_path);
+            }
+            bootstrapper.copy_configured_files(&bootstrap_result.worktree_path, &copy_patterns);
+            bootstrapper.clone_heavy_dirs(&bootstrap_result.worktree_path, &clone_dirs);
+        }
+
         let repo_name = self.utils.get_repo_name()?;
         let now = Utc::now();
 
@@ -2227,6 +2748,7 @@ impl SessionManager {
             display_name: None,
             version_group_id: params.version_group_id.map(|s| s.to_string()),
             version_number: params.version_number,
+            version_group_label,
             epic_id: params.epic_id.map(|id| id.to_string()),
             repository_path: self.repo_path.clone(),
             repository_name: repo_name,
@@ -2250,6 +2772,7 @@ impl SessionManager {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         };
 
         let finalizer = SessionFinalizer::new(&self.db_manager, &self.cache_manager);
@@ -2259,6 +2782,7 @@ impl SessionManager {
             update_activity: true,
         };
 
+        report(&unique_name, SessionCreationStep::ComputingStats);
         let finalization_result = match finalizer.finalize_creation(finalization_config) {
             Ok(result) => result,
             Err(e) => {
@@ -2277,11 +2801,36 @@ impl SessionManager {
             log::warn!("Failed to set original agent settings: {e}");
         }
 
+        if existing_group_label.is_none()
+            && let Some(group_id) = params.version_group_id
+            && let Some(label) = session.version_group_label.as_deref()
+            && let Err(e) = self
+                .db_manager
+                .set_version_group_label(group_id, Some(label))
+        {
+            log::warn!("Failed to label version group '{group_id}': {e}");
+        }
+
         self.cache_manager.unreserve_name(&unique_name);
         log::info!("Successfully created session '{unique_name}'");
         Ok(finalization_result.session)
     }
 
+    /// Renames the label shown for every session in the version group.
+    pub fn set_version_group_label(&self, group_id: &str, label: &str) -> Result<()> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(anyhow!("Version group label cannot be empty"));
+        }
+        let updated = self
+            .db_manager
+            .set_version_group_label(group_id, Some(label))?;
+        if updated == 0 {
+            return Err(anyhow!("No sessions found in version group '{group_id}'"));
+        }
+        Ok(())
+    }
+
     pub fn cancel_session(&self, name: &str) -> Result<()> {
         use crate::domains::sessions::lifecycle::cancellation::{
             CancellationConfig, CancellationCoordinator,
@@ -2307,6 +2856,8 @@ impl SessionManager {
             return Ok(());
         }
 
+        let _journal = JournalGuard::begin(&self.db_manager.db, cancel_journal_entry(&session));
+
         let coordinator = CancellationCoordinator::new(&self.repo_path, &self.db_manager);
         let config = CancellationConfig {
             force: false,
@@ -2366,6 +2917,22 @@ impl SessionManager {
             log::warn!("Failed to gate resume for {session_id}: {e}");
         }
 
+        if let Err(e) = self
+            .db_manager
+            .get_session_by_id(session_id)
+            .and_then(|session| discard_session_recordings(&self.db_manager, &session.name))
+        {
+            log::warn!("Failed to clean up recordings for {session_id}: {e}");
+        }
+
+        if let Err(e) = self
+            .db_manager
+            .get_session_by_id(session_id)
+            .and_then(|session| discard_session_snapshots(&self.db_manager, &session))
+        {
+            log::warn!("Failed to clean up snapshots for {session_id}: {e}");
+        }
+
         if !fs_result.errors.is_empty() {
             log::warn!(
                 "Session cancellation completed with {} error(s): {:?}",
@@ -2410,6 +2977,7 @@ impl SessionManager {
             session.display_name.as_deref(),
             session.epic_id.as_deref(),
         )?;
+        self.link_spec_to_retried_session(&spec, &session);
 
         log::info!(
             "Successfully converted session '{name}' to new spec '{}'",
@@ -2419,6 +2987,20 @@ impl SessionManager {
         Ok(spec.name)
     }
 
+    fn link_spec_to_retried_session(&self, spec: &Spec, session: &Session) {
+        if let Err(e) = self
+            .db_manager
+            .db
+            .set_spec_retry_of(&spec.id, Some(&session.id))
+        {
+            log::warn!(
+                "Failed to link spec '{}' to cancelled session '{}': {e}",
+                spec.name,
+                session.name
+            );
+        }
+    }
+
     /// Async-safe version of convert_session_to_draft that avoids blocking the Tokio runtime.
     pub async fn convert_session_to_draft_async(&self, name: &str) -> Result<String> {
         let session = self.db_manager.get_session_by_name(name)?;
@@ -2453,6 +3035,7 @@ impl SessionManager {
             session.display_name.as_deref(),
             session.epic_id.as_deref(),
         )?;
+        self.link_spec_to_retried_session(&spec, &session);
 
         log::info!(
             "Successfully converted session '{name}' to new spec '{}' (async flow)",
@@ -2483,6 +3066,25 @@ impl SessionManager {
         self.db_manager.get_session_task_content(name)
     }
 
+    /// Spec content and initial prompt of the session, or just the etag when it equals
+    /// `if_none_match`.
+    pub fn get_session_agent_content(
+        &self,
+        name: &str,
+        if_none_match: Option<&str>,
+    ) -> Result<SessionAgentContent> {
+        let tagged = self.db_manager.get_etagged_session_task_content(name)?;
+        if if_none_match == Some(tagged.etag.as_str()) {
+            return Ok(SessionAgentContent::NotModified { etag: tagged.etag });
+        }
+        let (spec_content, initial_prompt) = tagged.content;
+        Ok(SessionAgentContent::Modified {
+            spec_content,
+            initial_prompt,
+            etag: tagged.etag,
+        })
+    }
+
     pub fn list_sessions(&self) -> Result<Vec<Session>> {
         self.db_manager.list_sessions()
     }
@@ -2491,6 +3093,17 @@ impl SessionManager {
         self.db_manager.list_specs()
     }
 
+    pub fn get_agent_stats(&self, period: AgentStatsPeriod) -> Result<Vec<AgentStatsRow>> {
+        let db = &self.db_manager.db;
+        let outcomes = db.list_session_outcomes(&self.repo_path)?;
+        let pending_retries = db.list_pending_retries(&self.repo_path)?;
+        Ok(aggregate_agent_stats(
+            &outcomes,
+            &pending_retries,
+            period.cutoff(Utc::now()),
+        ))
+    }
+
     pub fn link_session_to_pr(
         &self,
         name: &str,
@@ -2512,8 +3125,28 @@ impl SessionManager {
         self.db_manager.update_git_stats(session_id)
     }
 
-    pub fn cleanup_orphaned_worktrees(&self) -> Result<()> {
-        self.utils.cleanup_orphaned_worktrees()
+    pub fn fetch_remote_status(&self, name: &str) -> Result<GitStats> {
+        let session = self.get_session(name)?;
+        self.db_manager.fetch_remote_status(&session.id)
+    }
+
+    pub fn classify_worktrees(&self) -> Result<Vec<WorktreeClassification>> {
+        self.utils.classify_worktrees()
+    }
+
+    pub fn cleanup_orphaned_worktrees(&self, paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        self.utils.cleanup_orphaned_worktrees(paths)
+    }
+
+    /// Removes every worktree that is currently orphaned or stale.
+    pub fn cleanup_all_orphaned_worktrees(&self) -> Result<Vec<PathBuf>> {
+        let paths: Vec<PathBuf> = self
+            .classify_worktrees()?
+            .into_iter()
+            .filter(|entry| entry.class != WorktreeClass::Tracked)
+            .map(|entry| entry.path)
+            .collect();
+        self.cleanup_orphaned_worktrees(&paths)
     }
 
     pub fn list_enriched_sessions(&self) -> Result<Vec<EnrichedSession>> {
@@ -2568,8 +3201,19 @@ impl SessionManager {
 
         // Fetch global defaults once to avoid per-row DB hits
         let default_agent_type = self.db_manager.get_agent_type().ok();
+        let stashed_session_ids: HashSet<String> = self
+            .db_manager
+            .list_stashed_session_ids()
+            .map(|ids| ids.into_iter().collect())
+            .unwrap_or_default();
+        let setup_failed_session_ids: HashSet<String> = self
+            .db_manager
+            .list_setup_failed_session_ids()
+            .map(|ids| ids.into_iter().collect())
+            .unwrap_or_default();
 
         let mut enriched = Vec::new();
+        let mut stale_conflicts = Vec::new();
         let mut git_stats_total_time = std::time::Duration::ZERO;
         let mut worktree_check_time = std::time::Duration::ZERO;
         let mut session_count = 0;
@@ -2590,6 +3234,7 @@ impl SessionManager {
                 display_name: spec.display_name.clone(),
                 version_group_id: None,
                 version_number: None,
+                version_group_label: None,
                 epic: spec
                     .epic_id
                     .as_deref()
@@ -2614,12 +3259,21 @@ impl SessionManager {
                 session_state: SessionState::Spec,
                 pr_number: None,
                 pr_url: None,
+                group: None,
+                skip_permissions_active: false,
+                has_stashed_changes: false,
+                setup_failed: false,
+                setup_running: false,
+                branch_missing: false,
+                repo_label: None,
+                depends_on_session: spec.depends_on_session.clone(),
             };
 
             enriched.push(EnrichedSession {
                 info,
                 status: None,
                 terminals: Vec::new(),
+                terminal_details: Vec::new(),
                 attention_required: None,
             });
         }
@@ -2650,6 +3304,7 @@ impl SessionManager {
                     display_name: session.display_name.clone(),
                     version_group_id: session.version_group_id.clone(),
                     version_number: session.version_number,
+                    version_group_label: session.version_group_label.clone(),
                     epic: session
                         .epic_id
                         .as_deref()
@@ -2677,12 +3332,24 @@ impl SessionManager {
                     session_state: session.session_state.clone(),
                     pr_number: session.pr_number,
                     pr_url: session.pr_url.clone(),
+                    group: session.group.clone(),
+                    skip_permissions_active: is_running_with_skipped_permissions(
+                        &self.repo_path,
+                        &session.name,
+                    ),
+                    has_stashed_changes: stashed_session_ids.contains(&session.id),
+                    setup_failed: setup_failed_session_ids.contains(&session.id),
+                    setup_running: is_setup_running(&session.name),
+                    branch_missing: false,
+                    repo_label: None,
+                    depends_on_session: None,
                 };
 
                 enriched.push(EnrichedSession {
                     info,
                     status: None,
                     terminals: Vec::new(),
+                    terminal_details: Vec::new(),
                     attention_required: None,
                 });
 
@@ -2723,31 +3390,47 @@ impl SessionManager {
                 );
             }
 
+            let branch_missing = worktree_exists
+                && !git::branch_exists(&self.repo_path, &session.branch).unwrap_or(true);
+            if branch_missing {
+                log::warn!(
+                    "list_enriched_sessions: branch '{}' of session '{}' is missing while its worktree remains",
+                    session.branch,
+                    session.name
+                );
+            }
+
             let (git_stats, has_conflicts) = if worktree_exists {
                 let git_stats_start = std::time::Instant::now();
-                let computed_stats = git::calculate_git_stats_fast(
+                let previous_stats = self.db_manager.get_git_stats(&session.id).ok().flatten();
+                let computed_stats = git::calculate_git_stats_incremental(
                     &session.worktree_path,
                     &session.parent_branch,
+                    previous_stats.as_ref(),
                 )
                 .ok()
                 .map(|mut s| {
                     s.session_id = session.id.clone();
                     s
                 });
+                if let Some(stats) = computed_stats.as_ref()
+                    && previous_stats
+                        .as_ref()
+                        .is_none_or(|prev| prev.calculated_at != stats.calculated_at)
+                    && let Err(e) = self.db_manager.save_git_stats(stats)
+                {
+                    log::debug!("Failed to persist git stats for '{}': {e}", session.name);
+                }
                 git_stats_total_time += git_stats_start.elapsed();
 
-                let has_conflicts = match git::has_conflicts(&session.worktree_path) {
-                    Ok(value) => value,
-                    Err(err) => {
-                        log::warn!(
-                            "Conflict detection failed for session '{}': {err}",
-                            session.name
-                        );
-                        false
-                    }
-                };
+                // Conflict detection walks the whole worktree status, so serve the cached
+                // value and let a background refresh catch up.
+                let cached = cache::cached_conflicts(&session.id);
+                if cached.is_none_or(|cached| cached.is_stale()) {
+                    stale_conflicts.push((session.id.clone(), session.worktree_path.clone()));
+                }
 
-                (computed_stats, Some(has_conflicts))
+                (computed_stats, cached.map(|cached| cached.has_conflicts))
             } else {
                 (None, None)
             };
@@ -2762,6 +3445,9 @@ impl SessionManager {
                 additions: stats.lines_added as usize,
                 deletions: stats.lines_removed as usize,
                 insertions: stats.lines_added as usize,
+                has_remote: stats.has_remote,
+                remote_ahead: stats.remote_ahead,
+                remote_behind: stats.remote_behind,
             });
 
             let status_type = if !worktree_exists && !cfg!(test) {
@@ -2795,6 +3481,7 @@ impl SessionManager {
                 display_name: session.display_name.clone(),
                 version_group_id: session.version_group_id.clone(),
                 version_number: session.version_number,
+                version_group_label: session.version_group_label.clone(),
                 epic: session
                     .epic_id
                     .as_deref()
@@ -2819,6 +3506,17 @@ impl SessionManager {
                 session_state,
                 pr_number: session.pr_number,
                 pr_url: session.pr_url.clone(),
+                group: session.group.clone(),
+                skip_permissions_active: is_running_with_skipped_permissions(
+                    &self.repo_path,
+                    &session.name,
+                ),
+                has_stashed_changes: stashed_session_ids.contains(&session.id),
+                setup_failed: setup_failed_session_ids.contains(&session.id),
+                setup_running: is_setup_running(&session.name),
+                branch_missing,
+                repo_label: None,
+                depends_on_session: None,
             };
 
             let terminals = vec![
@@ -2828,8 +3526,9 @@ impl SessionManager {
 
             enriched.push(EnrichedSession {
                 info,
-                status: None,
+                status: agent_monitor_status(&session, diff_stats),
                 terminals,
+                terminal_details: Vec::new(),
                 attention_required: None,
             });
 
@@ -2843,6 +3542,10 @@ impl SessionManager {
             }
         }
 
+        if !stale_conflicts.is_empty() {
+            cache::refresh_conflicts_in_background(stale_conflicts);
+        }
+
         let total_elapsed = start_time.elapsed();
         log::info!(
             "list_enriched_sessions: Returning {} enriched sessions (total: {}ms, db: {}ms, git_stats: {}ms, worktree_checks: {}ms, avg per session: {}ms)",
@@ -2882,6 +3585,22 @@ impl SessionManager {
         Ok(sorted_sessions)
     }
 
+    /// Like [`Self::list_enriched_sessions_sorted`], but fills in terminal details from
+    /// `live` before sorting so [`SortMode::TerminalActivity`] sees live output.
+    pub fn list_enriched_sessions_sorted_with_terminals(
+        &self,
+        sort_mode: SortMode,
+        filter_mode: FilterMode,
+        live: &HashMap<String, TerminalLiveness>,
+    ) -> Result<Vec<EnrichedSession>> {
+        log::debug!("Computing sorted sessions with terminals: {sort_mode:?}/{filter_mode:?}");
+        let mut all_sessions = self.list_enriched_sessions()?;
+        attach_terminal_details(&mut all_sessions, live);
+
+        let filtered_sessions = self.utils.apply_session_filter(all_sessions, &filter_mode);
+        Ok(self.utils.apply_session_sort(filtered_sessions, &sort_mode))
+    }
+
     pub fn start_claude_in_session(&self, session_name: &str) -> Result<AgentLaunchSpec> {
         self.start_claude_in_session_with_restart(session_name, false)
     }
@@ -2921,18 +3640,18 @@ impl SessionManager {
     pub fn start_claude_in_session_with_args(
         &self,
         session_name: &str,
-        _cli_args: Option<&str>,
+        cli_args: Option<&str>,
     ) -> Result<AgentLaunchSpec> {
-        self.start_claude_in_session_with_args_and_binary(session_name, _cli_args, &HashMap::new())
+        self.start_claude_in_session_with_args_and_binary(session_name, cli_args, &HashMap::new())
     }
 
     pub fn start_claude_in_session_with_args_and_binary(
         &self,
         session_name: &str,
-        _cli_args: Option<&str>,
+        cli_args: Option<&str>,
         binary_paths: &HashMap<String, String>,
     ) -> Result<AgentLaunchSpec> {
-        self.start_claude_in_session_with_restart_and_binary(AgentLaunchParams {
+        let spec = self.start_claude_in_session_with_restart_and_binary(AgentLaunchParams {
             session_name,
             force_restart: false,
             binary_paths,
@@ -2940,7 +3659,14 @@ impl SessionManager {
             agent_type_override: None,
             skip_prompt: false,
             skip_permissions_override: None,
-        })
+        })?;
+        match cli_args {
+            Some(cli_args) => {
+                let session = self.db_manager.get_session_by_name(session_name)?;
+                spec.with_cli_args(cli_args, session.initial_prompt.as_deref())
+            }
+            None => Ok(spec),
+        }
     }
 
     pub fn start_claude_in_session_with_restart_and_binary(
@@ -2971,6 +3697,14 @@ impl SessionManager {
                     .unwrap_or_else(|| self.db_manager.get_agent_type().unwrap_or("claude".to_string()))
             });
         let agent_type = resolve_launch_agent(&requested_agent_type, binary_paths)?;
+        authorize_launch(
+            &self.db_manager.db,
+            &self.repo_path,
+            &session.id,
+            session_name,
+            &agent_type,
+            skip_permissions,
+        )?;
 
         // When skip_prompt is true (e.g., secondary agent tabs), don't use the initial prompt
         let effective_initial_prompt = if skip_prompt {
@@ -3385,17 +4119,21 @@ impl SessionManager {
 
     pub fn start_claude_in_orchestrator_with_args(
         &self,
-        _cli_args: Option<&str>,
+        cli_args: Option<&str>,
     ) -> Result<AgentLaunchSpec> {
-        self.start_claude_in_orchestrator_with_args_and_binary(_cli_args, &HashMap::new())
+        self.start_claude_in_orchestrator_with_args_and_binary(cli_args, &HashMap::new())
     }
 
     pub fn start_claude_in_orchestrator_with_args_and_binary(
         &self,
-        _cli_args: Option<&str>,
+        cli_args: Option<&str>,
         binary_paths: &HashMap<String, String>,
     ) -> Result<AgentLaunchSpec> {
-        self.start_orchestrator_internal(binary_paths, true, None, None)
+        let spec = self.start_orchestrator_internal(binary_paths, true, None, None)?;
+        match cli_args {
+            Some(cli_args) => spec.with_cli_args(cli_args, None),
+            None => Ok(spec),
+        }
     }
 
     pub fn start_agent_in_orchestrator(
@@ -3577,6 +4315,7 @@ impl SessionManager {
         self.convert_session_to_draft(session_name)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn start_spec_session_with_config(
         &self,
         session_name: &str,
@@ -3585,10 +4324,16 @@ impl SessionManager {
         version_number: Option<i32>,
         agent_type: Option<&str>,
         skip_permissions: Option<bool>,
+        force: bool,
     ) -> Result<Session> {
         // Start the draft session first
-        let mut session =
-            self.start_spec_session(session_name, base_branch, version_group_id, version_number)?;
+        let mut session = self.start_spec_session(
+            session_name,
+            base_branch,
+            version_group_id,
+            version_number,
+            force,
+        )?;
 
         // Apply session-scoped original settings without mutating globals
         if agent_type.is_some() || skip_permissions.is_some() {
@@ -3640,8 +4385,11 @@ impl SessionManager {
 
         self.db_manager
             .update_session_ready_to_merge(&session.id, ready_to_merge)?;
-        self.db_manager
-            .update_session_state(&session.id, SessionState::Reviewed)?;
+        self.db_manager.update_session_state_with_reason(
+            &session.id,
+            SessionState::Reviewed,
+            StateTransitionReason::MarkReady,
+        )?;
 
         if let Err(e) = self.db_manager.update_git_stats(&session.id) {
             log::warn!("mark_session_ready: failed to refresh git stats for '{session_name}': {e}");
@@ -3655,8 +4403,11 @@ impl SessionManager {
         self.db_manager
             .update_session_ready_to_merge(&session.id, false)?;
         if session.session_state != SessionState::Spec {
-            self.db_manager
-                .update_session_state(&session.id, SessionState::Running)?;
+            self.db_manager.update_session_state_with_reason(
+                &session.id,
+                SessionState::Running,
+                StateTransitionReason::UnmarkReady,
+            )?;
         }
         Ok(())
     }
@@ -3692,8 +4443,11 @@ impl SessionManager {
             // Clear review flag/state and ensure state is Running for UI consistency
             self.db_manager
                 .update_session_ready_to_merge(&session.id, false)?;
-            self.db_manager
-                .update_session_state(&session.id, SessionState::Running)?;
+            self.db_manager.update_session_state_with_reason(
+                &session.id,
+                SessionState::Running,
+                StateTransitionReason::FollowUp,
+            )?;
 
             // Touch last_activity to surface recency deterministically
             let _ = self
@@ -3754,6 +4508,7 @@ impl SessionManager {
             content: spec_content.to_string(),
             created_at: now,
             updated_at: now,
+            depends_on_session: None,
         };
 
         self.db_manager.create_spec(&spec)?;
@@ -3784,6 +4539,7 @@ impl SessionManager {
             display_name: spec.display_name,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: spec.epic_id,
             repository_path: spec.repository_path.clone(),
             repository_name: spec.repository_name,
@@ -3808,6 +4564,7 @@ impl SessionManager {
             resume_allowed: false,
             pr_number: None,
             pr_url: None,
+            group: None,
             amp_thread_id: None,
         }
     }
@@ -3827,8 +4584,13 @@ impl SessionManager {
         );
 
         let spec = self.create_spec_session_with_agent(name, spec_content, None, None, None)?;
-        let session =
-            self.start_spec_session(&spec.name, base_branch, version_group_id, version_number)?;
+        let session = self.start_spec_session(
+            &spec.name,
+            base_branch,
+            version_group_id,
+            version_number,
+            false,
+        )?;
         Ok(session)
     }
 
@@ -3872,12 +4634,15 @@ impl SessionManager {
         Ok(session)
     }
 
+    /// Turns a spec into a running session. A spec whose dependency has not merged yet
+    /// is refused unless `force` is set.
     pub fn start_spec_session(
         &self,
         spec_name: &str,
         base_branch: Option<&str>,
         version_group_id: Option<&str>,
         version_number: Option<i32>,
+        force: bool,
     ) -> Result<Session> {
         log::info!(
             "Starting spec '{}' in repository: {}",
@@ -3889,10 +4654,12 @@ impl SessionManager {
             .db_manager
             .get_spec_by_name(spec_name)
             .map_err(|e| anyhow!("Spec '{spec_name}' not found: {e}"))?;
+        let dependency_base = self.check_spec_dependency(&spec, force)?;
 
         let parent_branch = base_branch
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
+            .or(dependency_base)
             .or_else(|| self.resolve_parent_branch(None).ok())
             .ok_or_else(|| anyhow!("Failed to resolve base branch for spec '{spec_name}'"))?;
 
@@ -3945,10 +4712,24 @@ impl SessionManager {
             .set_session_resume_allowed(&session.id, false);
         session.resume_allowed = false;
 
+        if let Ok(Some(retry_of)) = self.db_manager.db.get_spec_retry_of(&spec.id)
+            && let Err(e) = self
+                .db_manager
+                .db
+                .set_session_retry_of(&session.id, Some(&retry_of))
+        {
+            log::warn!(
+                "Failed to record retry lineage for session '{}': {e}",
+                session.name
+            );
+        }
+
         // spec fulfilled -> delete
         self.db_manager.delete_spec(&spec.id)?;
         crate::domains::sessions::cache::invalidate_spec_content(&self.repo_path, &spec.name);
 
+        self.update_started_session_from_parent(&session);
+
         Ok(session)
     }
 
@@ -3958,6 +4739,14 @@ impl SessionManager {
         Ok(())
     }
 
+    pub fn get_session_state_history(
+        &self,
+        session_name: &str,
+    ) -> Result<Vec<SessionStateTransition>> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager.get_state_history(&session.id)
+    }
+
     pub fn spawn_amp_thread_watcher(&self, session_name: &str) -> Result<()> {
         let session = self.db_manager.get_session_by_name(session_name)?;
 
@@ -4032,9 +4821,13 @@ impl SessionManager {
 
         self.db_manager
             .update_spec_content_by_id(&spec.id, content)?;
+        crate::domains::sessions::cache::invalidate_spec_content(&self.repo_path, session_name);
         info!(
             "SessionCore: Successfully updated spec content in database for session '{session_name}'"
         );
+        if let Err(e) = self.mirror_spec_file(session_name) {
+            warn!("Failed to mirror spec '{session_name}' to its file: {e}");
+        }
         Ok(())
     }
 
@@ -4049,17 +4842,15 @@ impl SessionManager {
             .get_spec_by_name(session_name)
             .map_err(|e| anyhow::anyhow!("Cannot append content for spec '{session_name}': {e}"))?;
 
-        let combined = if spec.content.is_empty() {
-            content.to_string()
-        } else {
-            format!("{}\n{}", spec.content, content)
-        };
-
         self.db_manager
-            .update_spec_content_by_id(&spec.id, &combined)?;
+            .append_spec_content_by_id(&spec.id, content)?;
+        crate::domains::sessions::cache::invalidate_spec_content(&self.repo_path, session_name);
         info!(
             "SessionCore: Successfully appended spec content in database for session '{session_name}'"
         );
+        if let Err(e) = self.mirror_spec_file(session_name) {
+            warn!("Failed to mirror spec '{session_name}' to its file: {e}");
+        }
         Ok(())
     }
 
@@ -4082,6 +4873,7 @@ impl SessionManager {
                 "Invalid session name: use only letters, numbers, hyphens, and underscores"
             ));
         }
+        self.utils.ensure_rename_target_free(new_name)?;
 
         self.db_manager.rename_draft_session(old_name, new_name)?;
         Ok(())
@@ -4189,7 +4981,9 @@ impl SessionManager {
 
     // Reset a session's worktree to the base branch in a defensive manner.
     // Verifies the worktree belongs to this project and that HEAD matches the session branch.
-    pub fn reset_session_worktree(&self, name: &str) -> Result<()> {
+    // A session with stashed changes is only reset when `discard_stash` is set, which also
+    // drops the stash.
+    pub fn reset_session_worktree(&self, name: &str, discard_stash: bool) -> Result<()> {
         let session = self.db_manager.get_session_by_name(name)?;
 
         // Ensure worktree path is inside this repository for safety
@@ -4197,7 +4991,57 @@ impl SessionManager {
             return Err(anyhow!("Invalid worktree path for this project"));
         }
 
-        // Open the worktree repo and confirm it's a worktree and on the session branch
+        let stash = self.db_manager.get_session_stash(&session.id)?;
+        if stash.is_some() && !discard_stash {
+            return Err(anyhow!(
+                "Session '{name}' has stashed changes; restore them or discard the stash before resetting"
+            ));
+        }
+
+        self.open_session_worktree_for_reset(&session)?;
+
+        if let Some(stash_oid) = stash {
+            git::drop_worktree_stash(&session.worktree_path, git2::Oid::from_str(&stash_oid)?)?;
+            self.db_manager.set_session_stash(&session.id, None)?;
+        }
+
+        // Delegate to git domain code (already constrained to this repo)
+        crate::domains::git::worktrees::reset_worktree_to_base(
+            &session.worktree_path,
+            &session.parent_branch,
+        )
+    }
+
+    // Reset a session's branch and worktree to `oid`, a commit from the session's own
+    // history. Runs the same checks as `reset_session_worktree` and refuses commits that
+    // are not ancestors of the session branch, so the branch never jumps to unrelated work.
+    pub fn reset_session_to_commit(&self, name: &str, oid: &str) -> Result<()> {
+        let session = self.db_manager.get_session_by_name(name)?;
+
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+
+        let repo = self.open_session_worktree_for_reset(&session)?;
+        let target = repo
+            .revparse_single(oid)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|e| anyhow!("Commit '{oid}' not found: {e}"))?
+            .id();
+        let head = repo.head()?.peel_to_commit()?.id();
+        if target != head && !repo.graph_descendant_of(head, target)? {
+            return Err(anyhow!(
+                "Commit {target} is not an ancestor of session branch '{}'",
+                session.branch
+            ));
+        }
+
+        crate::domains::git::worktrees::reset_worktree_to_commit(&session.worktree_path, target)
+    }
+
+    // Open the session worktree and confirm it is a worktree checked out on the session
+    // branch, so a reset can never move some other branch.
+    fn open_session_worktree_for_reset(&self, session: &Session) -> Result<git2::Repository> {
         let repo = git2::Repository::open(&session.worktree_path)
             .map_err(|e| anyhow!("Failed to open worktree repository: {e}"))?;
 
@@ -4205,24 +5049,62 @@ impl SessionManager {
             return Err(anyhow!("Target repository is not a git worktree"));
         }
 
-        // Confirm HEAD matches the session branch to avoid resetting the wrong branch
-        let head = repo
-            .head()
-            .map_err(|e| anyhow!("Failed to read HEAD: {e}"))?;
         let expected_ref = format!("refs/heads/{}", session.branch);
-        if head.name() != Some(expected_ref.as_str()) {
+        {
+            let head = repo
+                .head()
+                .map_err(|e| anyhow!("Failed to read HEAD: {e}"))?;
+            if head.name() != Some(expected_ref.as_str()) {
+                return Err(anyhow!(
+                    "HEAD does not point to the session branch (expected {}, got {:?})",
+                    expected_ref,
+                    head.name()
+                ));
+            }
+        }
+        Ok(repo)
+    }
+
+    /// Stash all local changes in a session's worktree and remember the stash on the session.
+    pub fn stash_session_changes(&self, name: &str, message: Option<&str>) -> Result<String> {
+        let session = self.db_manager.get_session_by_name(name)?;
+        if session.session_state == SessionState::Spec {
+            return Err(anyhow!("Cannot stash changes of spec session '{name}'"));
+        }
+        if self.db_manager.get_session_stash(&session.id)?.is_some() {
             return Err(anyhow!(
-                "HEAD does not point to the session branch (expected {}, got {:?})",
-                expected_ref,
-                head.name()
+                "Session '{name}' already has stashed changes; restore them first"
             ));
         }
 
-        // Delegate to git domain code (already constrained to this repo)
-        crate::domains::git::worktrees::reset_worktree_to_base(
+        let message = message
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Schaltwerk stash [session:{name}]"));
+        let stash_oid = git::stash_worktree_changes(&session.worktree_path, &message)?.to_string();
+        self.db_manager
+            .set_session_stash(&session.id, Some(&stash_oid))?;
+        Ok(stash_oid)
+    }
+
+    /// Restore the session's stash. Nothing is applied when it would conflict; the
+    /// conflicting paths are returned and the stash stays recorded.
+    pub fn unstash_session_changes(&self, name: &str) -> Result<git::UnstashOutcome> {
+        let session = self.db_manager.get_session_by_name(name)?;
+        let stash_oid = self
+            .db_manager
+            .get_session_stash(&session.id)?
+            .ok_or_else(|| anyhow!("Session '{name}' has no stashed changes"))?;
+
+        let outcome = git::unstash_worktree_changes(
             &session.worktree_path,
-            &session.parent_branch,
-        )
+            git2::Oid::from_str(&stash_oid)?,
+        )?;
+        if outcome.applied {
+            self.db_manager.set_session_stash(&session.id, None)?;
+        }
+        Ok(outcome)
     }
 
     /// Discard changes for a single file in a session's worktree (defensive checks included).
@@ -4263,6 +5145,148 @@ impl SessionManager {
         )
     }
 
+    /// Discard changes for several files of a session's worktree in one repository pass.
+    /// Each path gets its own result, in order; paths under `.schaltwerk/` or outside
+    /// the worktree are refused without affecting the others.
+    pub fn discard_files_in_session(
+        &self,
+        name: &str,
+        rel_paths: &[String],
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let session = self.db_manager.get_session_by_name(name)?;
+
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+
+        // Prevent touching our internal control area
+        let is_internal = |path: &str| path.starts_with(".schaltwerk/");
+        let allowed: Vec<&Path> = rel_paths
+            .iter()
+            .filter(|path| !is_internal(path))
+            .map(Path::new)
+            .collect();
+        let mut outcomes = crate::domains::git::worktrees::discard_paths_in_worktree(
+            &session.worktree_path,
+            &allowed,
+            Some(&session.parent_branch),
+        )?
+        .into_iter();
+
+        Ok(rel_paths
+            .iter()
+            .map(|path| {
+                let result = if is_internal(path) {
+                    Err(anyhow!("Refusing to discard changes under .schaltwerk"))
+                } else {
+                    outcomes
+                        .next()
+                        .unwrap_or_else(|| Err(anyhow!("No discard result for {path}")))
+                };
+                (path.clone(), result)
+            })
+            .collect())
+    }
+
+    /// Unified diff of the changes [`Self::discard_file_in_session`] would drop, so they
+    /// can be reviewed before discarding. Empty when the file has no changes.
+    pub fn preview_discard_file(&self, name: &str, rel_file_path: &str) -> Result<String> {
+        let session = self.db_manager.get_session_by_name(name)?;
+
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+
+        // Prevent touching our internal control area
+        if rel_file_path.starts_with(".schaltwerk/") {
+            return Err(anyhow!("Refusing to discard changes under .schaltwerk"));
+        }
+
+        git::preview_discard_path_in_worktree(
+            &session.worktree_path,
+            std::path::Path::new(rel_file_path),
+            Some(&session.parent_branch),
+        )
+    }
+
+    /// Discard one hunk of a file's session diff (merge-base with the parent branch versus
+    /// the worktree). `content_hash` is the file's hash reported with the diff; a mismatch
+    /// fails with [`git::StaleHunkError`].
+    pub fn discard_hunk_in_session(
+        &self,
+        name: &str,
+        rel_file_path: &str,
+        hunk_header: &str,
+        content_hash: &str,
+    ) -> Result<()> {
+        let session = self.db_manager.get_session_by_name(name)?;
+
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+
+        // Prevent touching our internal control area
+        if rel_file_path.starts_with(".schaltwerk/") {
+            return Err(anyhow!("Refusing to discard changes under .schaltwerk"));
+        }
+
+        git::discard_hunk_in_worktree(
+            &session.worktree_path,
+            std::path::Path::new(rel_file_path),
+            Some(&session.parent_branch),
+            hunk_header,
+            content_hash,
+        )
+    }
+
+    /// Recreate the branch of a session whose worktree survived an external branch
+    /// deletion, then refresh its git stats.
+    pub fn recreate_session_branch(&self, name: &str) -> Result<()> {
+        let session = self.db_manager.get_session_by_name(name)?;
+        if session.session_state == SessionState::Spec {
+            return Err(anyhow!("Spec session '{name}' has no branch to recreate"));
+        }
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+        if !session.worktree_path.exists() {
+            return Err(anyhow!(
+                "Worktree for session '{name}' is missing: {}",
+                session.worktree_path.display()
+            ));
+        }
+
+        git::recreate_worktree_branch(&session.worktree_path, &session.branch)?;
+        self.db_manager.update_git_stats(&session.id)
+    }
+
+    /// Revert a single file in a session's worktree to its content at `commit_oid`,
+    /// which must be part of the session branch. The change is left unstaged.
+    pub fn revert_file_to_commit(
+        &self,
+        name: &str,
+        rel_file_path: &str,
+        commit_oid: &str,
+    ) -> Result<git::FileRevertOutcome> {
+        let session = self.db_manager.get_session_by_name(name)?;
+
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+
+        // Prevent touching our internal control area
+        if rel_file_path.starts_with(".schaltwerk/") {
+            return Err(anyhow!("Refusing to revert files under .schaltwerk"));
+        }
+
+        git::revert_path_to_commit(
+            &session.worktree_path,
+            std::path::Path::new(rel_file_path),
+            &session.branch,
+            commit_oid,
+        )
+    }
+
     pub fn mark_session_prompted(&self, worktree_path: &std::path::Path) {
         self.cache_manager.mark_session_prompted(worktree_path);
     }
@@ -4284,4 +5308,138 @@ impl SessionManager {
         crate::domains::sessions::cache::invalidate_spec_content(&self.repo_path, session_name);
         Ok(())
     }
+
+    /// Stores the session's launch overrides; empty overrides clear them so the next
+    /// launch uses the agent defaults again.
+    pub fn set_session_launch_overrides(
+        &self,
+        session_name: &str,
+        overrides: SessionLaunchOverrides,
+    ) -> Result<SessionLaunchOverrides> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        let overrides = overrides.normalized();
+        self.db_manager
+            .set_session_launch_overrides(&session.id, &overrides)?;
+        Ok(overrides)
+    }
+
+    pub fn get_session_launch_overrides(
+        &self,
+        session_name: &str,
+    ) -> Result<SessionLaunchOverrides> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager.get_session_launch_overrides(&session.id)
+    }
+
+    /// Stores CLI args passed to this session's agent after the configured ones. Blank
+    /// args clear them; args that would fail to expand or parse at launch are rejected.
+    pub fn set_session_extra_cli_args(
+        &self,
+        session_name: &str,
+        extra_cli_args: Option<&str>,
+    ) -> Result<Option<String>> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        let extra_cli_args = extra_cli_args
+            .map(str::trim)
+            .filter(|args| !args.is_empty());
+        if let Some(args) = extra_cli_args {
+            let expanded = expand_cli_placeholders(
+                args,
+                &CliArgPlaceholders {
+                    worktree: &session.worktree_path,
+                    session_name: &session.name,
+                    parent_branch: &session.parent_branch,
+                    repo_root: &self.repo_path,
+                },
+            )?;
+            shell_words::split(&expanded)
+                .map_err(|e| anyhow!("Failed to parse CLI args '{args}': {e}"))?;
+        }
+        self.db_manager
+            .set_session_extra_cli_args(&session.id, extra_cli_args)?;
+        Ok(extra_cli_args.map(str::to_string))
+    }
+
+    pub fn get_session_extra_cli_args(&self, session_name: &str) -> Result<Option<String>> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager.get_session_extra_cli_args(&session.id)
+    }
+
+    /// Replaces the environment variables set (or, with `None`, unset) for this
+    /// session's agents. Names are trimmed; an empty map clears the overrides.
+    pub fn set_session_env_overrides(
+        &self,
+        session_name: &str,
+        overrides: EnvOverrides,
+    ) -> Result<EnvOverrides> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        let mut normalized = EnvOverrides::new();
+        for (name, value) in overrides {
+            let name = name.trim();
+            if name.is_empty() || name.contains('=') || name.contains('\0') {
+                return Err(anyhow!("Invalid environment variable name '{name}'"));
+            }
+            normalized.insert(name.to_string(), value);
+        }
+        self.db_manager
+            .set_session_env_overrides(&session.id, &normalized)?;
+        Ok(normalized)
+    }
+
+    pub fn get_session_env_overrides(&self, session_name: &str) -> Result<EnvOverrides> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager.get_session_env_overrides(&session.id)
+    }
+
+    /// Builds the command a fresh launch of the session's agent would start with, without
+    /// touching resume state, the initial prompt, or the skip-permissions gate.
+    pub fn preview_session_agent_launch(
+        &self,
+        session_name: &str,
+        binary_paths: &HashMap<String, String>,
+    ) -> Result<AgentLaunchSpec> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        let skip_permissions = session
+            .original_skip_permissions
+            .unwrap_or(self.db_manager.get_skip_permissions().unwrap_or(false));
+        let requested_agent_type = session.original_agent_type.clone().unwrap_or_else(|| {
+            self.db_manager
+                .get_agent_type()
+                .unwrap_or("claude".to_string())
+        });
+        let agent_type = resolve_launch_agent(&requested_agent_type, binary_paths)?;
+        let binary_path = self.utils.get_effective_binary_path_with_override(
+            &agent_type,
+            binary_paths.get(&agent_type).map(|s| s.as_str()),
+        );
+
+        crate::domains::agents::unified::AgentRegistry::new()
+            .build_launch_spec(
+                &agent_type,
+                &session.worktree_path,
+                None,
+                None,
+                skip_permissions,
+                Some(&binary_path),
+            )
+            .ok_or_else(|| anyhow!("Unsupported agent type: {agent_type}"))
+    }
+
+    pub fn record_session_launch(
+        &self,
+        session_name: &str,
+        snapshot: &SessionLaunchSnapshot,
+    ) -> Result<()> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager
+            .set_session_launch_snapshot(&session.id, snapshot)
+    }
+
+    pub fn get_session_launch_snapshot(
+        &self,
+        session_name: &str,
+    ) -> Result<Option<SessionLaunchSnapshot>> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager.get_session_launch_snapshot(&session.id)
+    }
 }
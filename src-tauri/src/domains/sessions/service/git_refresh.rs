@@ -0,0 +1,235 @@
+use super::SessionManager;
+use crate::domains::git::service as git;
+use crate::domains::sessions::activity::SessionGitStatsUpdated;
+use crate::domains::sessions::entity::{ChangedFile, GitStats, Session};
+use crate::shared::merge_snapshot_gateway::{MergeSnapshotGateway, MergeStateSnapshot};
+use anyhow::{Result, anyhow};
+use git2::{Repository, RepositoryState};
+use serde::Serialize;
+use std::path::Path;
+use std::thread::ScopedJoinHandle;
+
+const UNCOMMITTED_SAMPLE_LIMIT: usize = 5;
+
+/// A git operation left in progress in a worktree, e.g. by a manual `git rebase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    Bisect,
+    ApplyMailbox,
+}
+
+impl WorktreeOperation {
+    fn from_state(state: RepositoryState) -> Option<Self> {
+        match state {
+            RepositoryState::Clean => None,
+            RepositoryState::Merge => Some(Self::Merge),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some(Self::Rebase),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some(Self::CherryPick)
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some(Self::Revert),
+            RepositoryState::Bisect => Some(Self::Bisect),
+            RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+                Some(Self::ApplyMailbox)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorktreeGitState {
+    pub operation: Option<WorktreeOperation>,
+    pub head_detached: bool,
+    /// Branch checked out in the worktree; `None` while HEAD is detached.
+    pub current_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemoteDivergence {
+    pub has_remote: bool,
+    pub ahead: Option<u32>,
+    pub behind: Option<u32>,
+}
+
+impl RemoteDivergence {
+    fn from_stats(stats: &GitStats) -> Self {
+        Self {
+            has_remote: stats.has_remote,
+            ahead: stats.remote_ahead,
+            behind: stats.remote_behind,
+        }
+    }
+}
+
+/// Complete git state of one session, recomputed in a single pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionGitRefresh {
+    pub stats: SessionGitStatsUpdated,
+    pub remote: RemoteDivergence,
+    pub worktree: WorktreeGitState,
+    pub changed_files: Vec<ChangedFile>,
+}
+
+impl SessionManager {
+    /// Recomputes everything the UI shows about a session's git state, bypassing the
+    /// stats cache, and persists the fresh stats. The checks run in parallel and only
+    /// read the worktree, so this is safe while an agent is writing files.
+    pub fn refresh_session_git_state(&self, session_name: &str) -> Result<SessionGitRefresh> {
+        let session = self.get_session(session_name)?;
+        if !session.worktree_path.exists() {
+            return Err(anyhow!(
+                "Worktree for session '{session_name}' does not exist: {}",
+                session.worktree_path.display()
+            ));
+        }
+
+        let start = std::time::Instant::now();
+        let worktree_path = session.worktree_path.as_path();
+        let parent_branch = session.parent_branch.as_str();
+        crate::domains::git::stats::invalidate_stats_cache_for(worktree_path, parent_branch);
+
+        let (mut stats, has_conflicts, worktree, merge_snapshot, changed_files, sample) =
+            std::thread::scope(|scope| {
+                let stats =
+                    scope.spawn(|| git::calculate_git_stats_fast(worktree_path, parent_branch));
+                let conflicts = scope.spawn(|| git::has_conflicts(worktree_path));
+                let worktree = scope.spawn(|| read_worktree_state(worktree_path));
+                let merge = scope.spawn(|| assess_merge_state(&session));
+                let changed = scope.spawn(|| git::get_changed_files(worktree_path, parent_branch));
+                let sample = scope.spawn(|| {
+                    crate::domains::git::operations::uncommitted_sample_paths(
+                        worktree_path,
+                        UNCOMMITTED_SAMPLE_LIMIT,
+                    )
+                });
+
+                Ok::<_, anyhow::Error>((
+                    join_check(stats, "git stats")?,
+                    join_check(conflicts, "conflict detection")?,
+                    join_check(worktree, "worktree state")?,
+                    merge
+                        .join()
+                        .map_err(|_| anyhow!("Merge assessment panicked"))?,
+                    join_check(changed, "changed files")?,
+                    join_check(sample, "uncommitted paths")?,
+                ))
+            })?;
+
+        stats.session_id = session.id.clone();
+        if let Err(e) = self.db_manager.save_git_stats(&stats) {
+            log::warn!("Failed to persist refreshed git stats for {session_name}: {e}");
+        }
+
+        log::debug!(
+            "Refreshed git state of session '{session_name}' in {}ms",
+            start.elapsed().as_millis()
+        );
+
+        Ok(SessionGitRefresh {
+            stats: SessionGitStatsUpdated {
+                session_id: session.id.clone(),
+                session_name: session.name.clone(),
+                files_changed: stats.files_changed,
+                lines_added: stats.lines_added,
+                lines_removed: stats.lines_removed,
+                has_uncommitted: stats.has_uncommitted,
+                has_conflicts,
+                top_uncommitted_paths: (!sample.is_empty()).then_some(sample),
+                merge_has_conflicts: merge_snapshot.merge_has_conflicts,
+                merge_conflicting_paths: merge_snapshot.merge_conflicting_paths,
+                merge_is_up_to_date: merge_snapshot.merge_is_up_to_date,
+            },
+            remote: RemoteDivergence::from_stats(&stats),
+            worktree,
+            changed_files,
+        })
+    }
+}
+
+fn join_check<T>(handle: ScopedJoinHandle<'_, Result<T>>, check: &str) -> Result<T> {
+    handle
+        .join()
+        .map_err(|_| anyhow!("Session refresh check '{check}' panicked"))?
+        .map_err(|e| anyhow!("Session refresh check '{check}' failed: {e}"))
+}
+
+fn read_worktree_state(worktree_path: &Path) -> Result<WorktreeGitState> {
+    let repo = Repository::open(worktree_path)?;
+    let head_detached = repo.head_detached().unwrap_or(false);
+    let current_branch = if head_detached {
+        None
+    } else {
+        repo.head()
+            .ok()
+            .and_then(|head| head.shorthand().map(str::to_string))
+    };
+
+    Ok(WorktreeGitState {
+        operation: WorktreeOperation::from_state(repo.state()),
+        head_detached,
+        current_branch,
+    })
+}
+
+fn assess_merge_state(session: &Session) -> MergeStateSnapshot {
+    Repository::open(&session.repository_path)
+        .ok()
+        .and_then(|repo| {
+            let session_oid =
+                MergeSnapshotGateway::resolve_branch_oid(&repo, &session.branch).ok()?;
+            let parent_oid =
+                MergeSnapshotGateway::resolve_branch_oid(&repo, &session.parent_branch).ok()?;
+            MergeSnapshotGateway::compute(
+                &repo,
+                session_oid,
+                parent_oid,
+                &session.branch,
+                &session.parent_branch,
+            )
+            .map_err(|err| {
+                log::warn!(
+                    "Merge assessment failed for session '{}': {}",
+                    session.name,
+                    err
+                );
+            })
+            .ok()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use tempfile::TempDir;
+
+    #[test]
+    fn worktree_state_reports_branch_and_detached_head() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let sig = Signature::now("Tester", "tester@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+
+        let state = read_worktree_state(temp.path()).unwrap();
+        assert_eq!(state.operation, None);
+        assert!(!state.head_detached);
+        assert!(state.current_branch.is_some());
+
+        repo.set_head_detached(commit).unwrap();
+        let state = read_worktree_state(temp.path()).unwrap();
+        assert!(state.head_detached);
+        assert_eq!(state.current_branch, None);
+    }
+}
@@ -0,0 +1,192 @@
+use super::{SessionCreationParams, SessionManager};
+use crate::domains::git::github_cli::{GitHubIssueDetails, sanitize_branch_component};
+use crate::domains::sessions::entity::Session;
+use anyhow::Result;
+
+const ISSUE_SLUG_MAX_LEN: usize = 40;
+
+/// Launch configuration for a session started from a GitHub issue.
+#[derive(Debug, Clone, Default)]
+pub struct IssueSessionOptions {
+    pub base_branch: Option<String>,
+    pub agent_type: Option<String>,
+    pub skip_permissions: Option<bool>,
+    pub include_comments: bool,
+}
+
+/// Builds a name like `issue-1234-fix-login` from the issue number and title. Used for
+/// both specs and sessions, so the session branch becomes `<prefix>/issue-1234-fix-login`.
+pub fn issue_session_name(number: u64, title: &str) -> String {
+    let prefix = format!("issue-{number}");
+    if !title.chars().any(|c| c.is_ascii_alphanumeric()) {
+        return prefix;
+    }
+
+    let slug = sanitize_branch_component(title);
+    let mut truncated = String::new();
+    for part in slug.split('-') {
+        let extra = if truncated.is_empty() {
+            part.len()
+        } else {
+            part.len() + 1
+        };
+        if !truncated.is_empty() && truncated.len() + extra > ISSUE_SLUG_MAX_LEN {
+            break;
+        }
+        if !truncated.is_empty() {
+            truncated.push('-');
+        }
+        truncated.push_str(part);
+    }
+    truncated.truncate(ISSUE_SLUG_MAX_LEN);
+    format!("{prefix}-{}", truncated.trim_end_matches('-'))
+}
+
+/// Renders the issue as markdown with a backlink, labels and optionally its comments.
+pub fn render_issue_prompt(details: &GitHubIssueDetails, include_comments: bool) -> String {
+    let mut out = format!("# {}\n\n", details.title.trim());
+    out.push_str(&format!(
+        "GitHub issue: [#{}]({})\n\n",
+        details.number, details.url
+    ));
+
+    if !details.labels.is_empty() {
+        let labels = details
+            .labels
+            .iter()
+            .map(|label| format!("`{}`", label.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push_str(&format!("Labels: {labels}\n\n"));
+    }
+
+    let body = details.body.trim();
+    if body.is_empty() {
+        out.push_str("_No description provided._\n");
+    } else {
+        out.push_str(body);
+        out.push('\n');
+    }
+
+    if include_comments && !details.comments.is_empty() {
+        out.push_str("\n## Comments\n");
+        for comment in &details.comments {
+            let author = comment.author_login.as_deref().unwrap_or("unknown");
+            if comment.created_at.is_empty() {
+                out.push_str(&format!("\n### @{author}\n\n"));
+            } else {
+                out.push_str(&format!("\n### @{author} ({})\n\n", comment.created_at));
+            }
+            out.push_str(comment.body.trim());
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+impl SessionManager {
+    /// Creates a session for a GitHub issue, named and branched after the issue and
+    /// seeded with its title and body as the initial prompt.
+    pub fn create_session_from_issue(
+        &self,
+        details: &GitHubIssueDetails,
+        options: &IssueSessionOptions,
+    ) -> Result<Session> {
+        let name = issue_session_name(details.number, &details.title);
+        let prompt = render_issue_prompt(details, options.include_comments);
+
+        self.create_session_with_agent(SessionCreationParams {
+            name: &name,
+            prompt: Some(&prompt),
+            base_branch: options.base_branch.as_deref(),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: options.agent_type.as_deref(),
+            skip_permissions: options.skip_permissions,
+            pr_number: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::git::github_cli::{GitHubIssueComment, GitHubIssueLabel};
+    use crate::infrastructure::database::Database;
+    use crate::infrastructure::database::db_project_config::ProjectConfigMethods;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn issue() -> GitHubIssueDetails {
+        GitHubIssueDetails {
+            number: 123,
+            title: "Fix login: redirect loop on Safari!".to_string(),
+            url: "https://github.com/example/repo/issues/123".to_string(),
+            body: "Users get stuck after signing in.\n".to_string(),
+            labels: vec![GitHubIssueLabel {
+                name: "bug".to_string(),
+                color: None,
+            }],
+            comments: vec![GitHubIssueComment {
+                author_login: Some("octocat".to_string()),
+                created_at: "2024-01-02T00:00:00Z".to_string(),
+                body: "Reproduced on 17.2".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn issue_session_uses_issue_prompt_and_branch() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        db.set_project_branch_prefix(&repo, "feature").unwrap();
+        let manager = SessionManager::new(db, repo);
+
+        let session = manager
+            .create_session_from_issue(
+                &issue(),
+                &IssueSessionOptions {
+                    base_branch: Some("main".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(session.name, "issue-123-fix-login-redirect-loop-on-safari");
+        assert_eq!(
+            session.branch,
+            "feature/issue-123-fix-login-redirect-loop-on-safari"
+        );
+        let prompt = session.initial_prompt.unwrap();
+        assert!(prompt.starts_with("# Fix login: redirect loop on Safari!\n"));
+        assert!(prompt.contains("[#123](https://github.com/example/repo/issues/123)"));
+        assert!(prompt.contains("Users get stuck after signing in."));
+        assert!(!prompt.contains("## Comments"));
+    }
+}
@@ -0,0 +1,155 @@
+use super::SessionManager;
+use crate::domains::merge::{
+    UpdateFromParentStatus, UpdateSessionFromParentResult, update_session_from_parent,
+};
+use crate::domains::sessions::entity::Session;
+use crate::infrastructure::database::ProjectConfigMethods;
+
+impl SessionManager {
+    /// Merges the latest parent branch into a freshly started session when the project
+    /// opted in. Returns `None` when the setting is off. A conflicting or failed update
+    /// is skipped with a warning so the session still starts from its original base.
+    pub(super) fn update_started_session_from_parent(
+        &self,
+        session: &Session,
+    ) -> Option<UpdateSessionFromParentResult> {
+        let enabled = self
+            .db_manager
+            .db
+            .get_project_update_from_parent_on_start(&self.repo_path)
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let result = update_session_from_parent(
+            &session.name,
+            &session.worktree_path,
+            &self.repo_path,
+            &session.parent_branch,
+        );
+        match result.status {
+            UpdateFromParentStatus::Success | UpdateFromParentStatus::AlreadyUpToDate => {
+                log::info!(
+                    "Session '{}' started on latest '{}': {}",
+                    session.name,
+                    result.parent_branch,
+                    result.message
+                );
+            }
+            UpdateFromParentStatus::HasConflicts => {
+                log::warn!(
+                    "Skipped updating session '{}' from '{}' on start; conflicting paths: {}",
+                    session.name,
+                    result.parent_branch,
+                    result.conflicting_paths.join(", ")
+                );
+            }
+            _ => {
+                log::warn!(
+                    "Skipped updating session '{}' from '{}' on start: {}",
+                    session.name,
+                    result.parent_branch,
+                    result.message
+                );
+            }
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::Database;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn manager_with_repo() -> (SessionManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        db.set_project_update_from_parent_on_start(&repo, true)
+            .unwrap();
+        (SessionManager::new(db, repo), temp)
+    }
+
+    #[test]
+    fn started_spec_picks_up_parent_commits_from_origin() {
+        let (manager, temp) = manager_with_repo();
+        let repo = manager.repo_path.clone();
+        let origin = temp.path().join("origin.git");
+        git(temp.path(), &["init", "--bare", origin.to_str().unwrap()]);
+        git(&repo, &["branch", "release"]);
+        git(
+            &repo,
+            &["remote", "add", "origin", origin.to_str().unwrap()],
+        );
+        git(&repo, &["push", "-u", "origin", "release"]);
+
+        let upstream = temp.path().join("upstream");
+        git(
+            temp.path(),
+            &[
+                "clone",
+                "-b",
+                "release",
+                origin.to_str().unwrap(),
+                upstream.to_str().unwrap(),
+            ],
+        );
+        git(&upstream, &["config", "user.email", "test@example.com"]);
+        git(&upstream, &["config", "user.name", "Test User"]);
+        std::fs::write(upstream.join("upstream.txt"), "upstream work").unwrap();
+        git(&upstream, &["add", "."]);
+        git(&upstream, &["commit", "-m", "upstream change"]);
+        git(&upstream, &["push", "origin", "release"]);
+
+        let spec = manager.create_spec_session("late", "Old idea").unwrap();
+        let session = manager
+            .start_spec_session(&spec.name, Some("release"), None, None, false)
+            .unwrap();
+
+        assert!(session.worktree_path.join("upstream.txt").exists());
+    }
+
+    #[test]
+    fn conflicting_parent_update_is_skipped() {
+        let (manager, _temp) = manager_with_repo();
+        let repo = manager.repo_path.clone();
+        let session = manager
+            .create_session_with_auto_flag("clash", None, Some("main"), false, None, None)
+            .unwrap();
+
+        std::fs::write(session.worktree_path.join("README.md"), "Session").unwrap();
+        git(&session.worktree_path, &["commit", "-am", "session edit"]);
+        std::fs::write(repo.join("README.md"), "Parent").unwrap();
+        git(&repo, &["commit", "-am", "parent edit"]);
+
+        let result = manager
+            .update_started_session_from_parent(&session)
+            .expect("setting is enabled");
+        assert_eq!(result.status, UpdateFromParentStatus::HasConflicts);
+        assert_eq!(result.conflicting_paths, vec!["README.md".to_string()]);
+        let contents = std::fs::read_to_string(session.worktree_path.join("README.md")).unwrap();
+        assert_eq!(contents, "Session");
+    }
+}
@@ -0,0 +1,142 @@
+use super::SessionManager;
+use crate::domains::sessions::db_schedule::{ScheduledSpec, ScheduledSpecStatus};
+use crate::domains::sessions::entity::SessionState;
+use crate::domains::sessions::schedule::{ScheduleAction, plan_due_schedules};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Launch configuration stored with a scheduled spec.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleSpecOptions {
+    pub base_branch: Option<String>,
+    pub agent_type: Option<String>,
+    pub skip_permissions: Option<bool>,
+    pub skip_if_late: bool,
+}
+
+impl SessionManager {
+    pub fn schedule_spec(
+        &self,
+        spec_name: &str,
+        not_before: DateTime<Utc>,
+        options: ScheduleSpecOptions,
+    ) -> Result<ScheduledSpec> {
+        let spec = self
+            .db_manager
+            .get_spec_by_name(spec_name)
+            .map_err(|e| anyhow!("Spec '{spec_name}' not found: {e}"))?;
+
+        let entry = ScheduledSpec {
+            id: Uuid::new_v4().to_string(),
+            spec_name: spec.name,
+            not_before,
+            base_branch: options.base_branch,
+            agent_type: options.agent_type,
+            skip_permissions: options.skip_permissions,
+            skip_if_late: options.skip_if_late,
+            status: ScheduledSpecStatus::Pending,
+            created_at: Utc::now(),
+            finished_at: None,
+            error: None,
+        };
+        self.db_manager.create_scheduled_spec(&entry)?;
+        log::info!(
+            "Scheduled spec '{spec_name}' to start at {} ({})",
+            entry.not_before.to_rfc3339(),
+            entry.id
+        );
+        Ok(entry)
+    }
+
+    pub fn list_scheduled_specs(&self) -> Result<Vec<ScheduledSpec>> {
+        self.db_manager.list_scheduled_specs()
+    }
+
+    pub fn cancel_scheduled_spec(&self, id: &str) -> Result<ScheduledSpec> {
+        let entry = self
+            .db_manager
+            .get_scheduled_spec(id)?
+            .ok_or_else(|| anyhow!("Scheduled spec '{id}' not found"))?;
+        if entry.status != ScheduledSpecStatus::Pending {
+            return Err(anyhow!(
+                "Scheduled spec '{id}' is already {}",
+                entry.status.as_str()
+            ));
+        }
+
+        self.db_manager
+            .finish_scheduled_spec(id, ScheduledSpecStatus::Cancelled, None)?;
+        self.db_manager
+            .get_scheduled_spec(id)?
+            .ok_or_else(|| anyhow!("Scheduled spec '{id}' not found"))
+    }
+
+    /// Starts the scheduled specs that are due, keeping at most `max_running` running
+    /// sessions, and skips missed `skip_if_late` entries. Returns the entries that
+    /// changed status.
+    pub fn run_due_scheduled_specs(
+        &self,
+        now: DateTime<Utc>,
+        active_since: DateTime<Utc>,
+        max_running: usize,
+    ) -> Result<Vec<ScheduledSpec>> {
+        let entries = self.db_manager.list_scheduled_specs()?;
+        if !entries
+            .iter()
+            .any(|e| e.status == ScheduledSpecStatus::Pending && e.not_before <= now)
+        {
+            return Ok(Vec::new());
+        }
+
+        let running = self
+            .db_manager
+            .list_sessions_by_state(SessionState::Running)?
+            .iter()
+            .filter(|s| !s.ready_to_merge)
+            .count();
+        let free_slots = max_running.saturating_sub(running);
+
+        let mut changed = Vec::new();
+        for action in plan_due_schedules(&entries, now, active_since, free_slots) {
+            let (entry, status, error) = match action {
+                ScheduleAction::SkipLate(entry) => {
+                    log::info!(
+                        "Skipping scheduled spec '{}': missed its start time {}",
+                        entry.spec_name,
+                        entry.not_before.to_rfc3339()
+                    );
+                    (entry, ScheduledSpecStatus::Skipped, None)
+                }
+                ScheduleAction::Start(entry) => match self.start_spec_session_with_config(
+                    &entry.spec_name,
+                    entry.base_branch.as_deref(),
+                    None,
+                    None,
+                    entry.agent_type.as_deref(),
+                    entry.skip_permissions,
+                    false,
+                ) {
+                    Ok(_) => {
+                        log::info!("Started scheduled spec '{}'", entry.spec_name);
+                        (entry, ScheduledSpecStatus::Started, None)
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to start scheduled spec '{}': {e}", entry.spec_name);
+                        (entry, ScheduledSpecStatus::Failed, Some(e.to_string()))
+                    }
+                },
+            };
+
+            self.db_manager
+                .finish_scheduled_spec(&entry.id, status, error.as_deref())?;
+            changed.push(ScheduledSpec {
+                status,
+                error,
+                finished_at: Some(Utc::now()),
+                ..entry
+            });
+        }
+        Ok(changed)
+    }
+}
@@ -0,0 +1,132 @@
+use super::{SessionCreationParams, SessionManager};
+use crate::domains::sessions::db_sessions::SessionMethods;
+use crate::domains::sessions::entity::Session;
+use anyhow::{Context, Result};
+
+impl SessionManager {
+    /// Creates `new_name` on its own branch and worktree starting at the committed HEAD of
+    /// `source_name`, with the same initial prompt and agent settings. The fork keeps the
+    /// source's parent branch as merge target and never resumes the source conversation.
+    pub fn fork_session(&self, source_name: &str, new_name: &str) -> Result<Session> {
+        let source = self
+            .get_session(source_name)
+            .with_context(|| format!("Cannot fork session '{source_name}'"))?;
+
+        let mut fork = self.create_session_with_agent(SessionCreationParams {
+            name: new_name,
+            prompt: source.initial_prompt.as_deref(),
+            base_branch: Some(&source.branch),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            agent_type: source.original_agent_type.as_deref(),
+            skip_permissions: source.original_skip_permissions,
+            pr_number: None,
+        })?;
+
+        self.db_manager
+            .db
+            .update_session_parent_branch(&fork.id, &source.parent_branch)?;
+        fork.parent_branch = source.parent_branch.clone();
+
+        self.db_manager
+            .set_session_resume_allowed(&fork.id, false)?;
+        fork.resume_allowed = false;
+        Ok(fork)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::Database;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn manager_with_repo() -> (SessionManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        (SessionManager::new(db, repo), temp)
+    }
+
+    #[test]
+    fn fork_starts_from_source_head_with_copied_settings() {
+        let (manager, _temp) = manager_with_repo();
+        let source = manager
+            .create_session_with_agent(SessionCreationParams {
+                name: "approach-a",
+                prompt: Some("Speed up the search index"),
+                base_branch: Some("main"),
+                custom_branch: None,
+                use_existing_branch: false,
+                sync_with_origin: false,
+                was_auto_generated: false,
+                version_group_id: None,
+                version_number: None,
+                version_group_label: None,
+                epic_id: None,
+                agent_type: Some("codex"),
+                skip_permissions: Some(true),
+                pr_number: None,
+            })
+            .unwrap();
+        std::fs::write(source.worktree_path.join("index.rs"), "// cache").unwrap();
+        git(&source.worktree_path, &["add", "."]);
+        git(&source.worktree_path, &["commit", "-m", "add cache"]);
+        manager
+            .db_manager
+            .set_session_resume_allowed(&source.id, true)
+            .unwrap();
+
+        let fork = manager.fork_session("approach-a", "approach-b").unwrap();
+
+        let source_head = git(&source.worktree_path, &["rev-parse", "HEAD"]);
+        assert_eq!(
+            git(&fork.worktree_path, &["rev-parse", "HEAD"]),
+            source_head
+        );
+        assert_ne!(fork.branch, source.branch);
+        assert_ne!(fork.worktree_path, source.worktree_path);
+
+        let stored = manager.get_session("approach-b").unwrap();
+        assert_eq!(stored.parent_branch, "main");
+        assert_eq!(
+            stored.initial_prompt.as_deref(),
+            Some("Speed up the search index")
+        );
+        assert_eq!(stored.original_agent_type.as_deref(), Some("codex"));
+        assert_eq!(stored.original_skip_permissions, Some(true));
+        assert!(!stored.resume_allowed);
+    }
+
+    #[test]
+    fn fork_of_unknown_session_fails() {
+        let (manager, _temp) = manager_with_repo();
+        assert!(manager.fork_session("missing", "copy").is_err());
+    }
+}
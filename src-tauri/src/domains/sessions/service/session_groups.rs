@@ -0,0 +1,20 @@
+use super::SessionManager;
+use crate::domains::sessions::entity::Session;
+use anyhow::Result;
+
+impl SessionManager {
+    /// Assigns `session_name` to a group. Blank or missing names clear the group.
+    pub fn set_session_group(&self, session_name: &str, group: Option<&str>) -> Result<Session> {
+        let mut session = self.db_manager.get_session_by_name(session_name)?;
+        let group = group.map(str::trim).filter(|g| !g.is_empty());
+
+        self.db_manager.set_session_group(&session.id, group)?;
+        session.group = group.map(str::to_string);
+        Ok(session)
+    }
+
+    /// Distinct group names used by non-cancelled sessions of this project.
+    pub fn list_session_groups(&self) -> Result<Vec<String>> {
+        self.db_manager.list_session_groups()
+    }
+}
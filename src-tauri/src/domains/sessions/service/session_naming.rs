@@ -0,0 +1,200 @@
+use super::SessionManager;
+use crate::domains::sessions::db_sessions::SessionMethods;
+use crate::domains::sessions::entity::Session;
+use anyhow::{Result, anyhow};
+
+impl SessionManager {
+    /// Applies an agent-generated name to a session: updates the display name, renames
+    /// the branch (suffixed when the name is taken) and clears `pending_name_generation`.
+    pub fn apply_generated_session_name(
+        &self,
+        session_name: &str,
+        display_name: &str,
+    ) -> Result<Session> {
+        let mut session = self.get_session(session_name)?;
+        if !self.apply_display_name_to_session(&mut session, display_name)? {
+            return Err(anyhow!(
+                "Generated name '{display_name}' is not usable for session '{session_name}'"
+            ));
+        }
+
+        self.clear_pending_name_generation(&mut session)?;
+        Ok(session)
+    }
+
+    /// Stops automatic naming for a session, leaving its current name in place.
+    pub fn abandon_name_generation(&self, session_name: &str) -> Result<()> {
+        let mut session = self.get_session(session_name)?;
+        self.clear_pending_name_generation(&mut session)
+    }
+
+    fn clear_pending_name_generation(&self, session: &mut Session) -> Result<()> {
+        self.db_manager
+            .db
+            .set_pending_name_generation(&session.id, false)?;
+        session.pending_name_generation = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SessionCreationParams;
+    use super::*;
+    use crate::infrastructure::database::Database;
+    use crate::infrastructure::database::db_project_config::ProjectConfigMethods;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn git_output(repo: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn manager_with_pending_session() -> (SessionManager, Session, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        db.set_project_branch_prefix(&repo, "feature").unwrap();
+        let manager = SessionManager::new(db, repo);
+        let session = manager
+            .create_session_with_agent(SessionCreationParams {
+                name: "eager-otter",
+                prompt: Some("Fix the login redirect loop"),
+                base_branch: Some("main"),
+                custom_branch: None,
+                use_existing_branch: false,
+                sync_with_origin: false,
+                was_auto_generated: true,
+                version_group_id: None,
+                version_number: None,
+                version_group_label: None,
+                epic_id: None,
+                agent_type: None,
+                skip_permissions: None,
+                pr_number: None,
+            })
+            .unwrap();
+        assert!(session.pending_name_generation);
+
+        (manager, session, temp)
+    }
+
+    #[test]
+    fn generated_name_renames_branch_and_clears_pending_flag() {
+        let (manager, session, _temp) = manager_with_pending_session();
+
+        let renamed = manager
+            .apply_generated_session_name(&session.name, "Fix Login Loop")
+            .unwrap();
+
+        assert_eq!(renamed.display_name.as_deref(), Some("fix-login-loop"));
+        assert_eq!(renamed.branch, "feature/fix-login-loop");
+        assert!(!renamed.pending_name_generation);
+
+        let stored = manager.get_session(&session.name).unwrap();
+        assert_eq!(stored.branch, "feature/fix-login-loop");
+        assert!(!stored.pending_name_generation);
+    }
+
+    #[test]
+    fn generated_name_suffixes_branch_when_name_is_taken() {
+        let (manager, session, _temp) = manager_with_pending_session();
+        git(&manager.repo_path, &["branch", "feature/fix-login-loop"]);
+
+        let renamed = manager
+            .apply_generated_session_name(&session.name, "fix-login-loop")
+            .unwrap();
+
+        assert_eq!(renamed.display_name.as_deref(), Some("fix-login-loop"));
+        assert_eq!(renamed.branch, "feature/fix-login-loop-2");
+        assert!(!renamed.pending_name_generation);
+    }
+
+    #[test]
+    fn failed_branch_write_restores_branch_and_worktree() {
+        let (manager, session, _temp) = manager_with_pending_session();
+        manager
+            .db_manager
+            .db
+            .get_conn()
+            .unwrap()
+            .execute_batch(
+                "CREATE TRIGGER reject_branch_update BEFORE UPDATE OF branch ON sessions
+                 BEGIN SELECT RAISE(ABORT, 'branch update rejected'); END;",
+            )
+            .unwrap();
+
+        assert!(
+            manager
+                .apply_generated_session_name(&session.name, "Fix Login Loop")
+                .is_err()
+        );
+
+        let stored = manager.get_session(&session.name).unwrap();
+        assert_eq!(stored.branch, session.branch);
+        assert_eq!(stored.display_name, session.display_name);
+        assert!(stored.pending_name_generation);
+        let branches = git_output(&manager.repo_path, &["branch", "--list", "feature/*"]);
+        assert_eq!(branches.trim_start_matches(['*', '+', ' ']), session.branch);
+        let head = git_output(
+            &session.worktree_path,
+            &["rev-parse", "--abbrev-ref", "HEAD"],
+        );
+        assert_eq!(head, session.branch);
+    }
+
+    #[test]
+    fn rename_rejects_taken_branch_or_worktree_path() {
+        let (manager, _session, _temp) = manager_with_pending_session();
+
+        git(&manager.repo_path, &["branch", "feature/taken-branch"]);
+        let err = manager
+            .rename_draft_session("draft", "taken-branch")
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("branch 'feature/taken-branch' already exists"),
+            "{err}"
+        );
+
+        std::fs::create_dir_all(manager.utils.worktree_path_for("taken-path")).unwrap();
+        let err = manager
+            .rename_draft_session("draft", "taken-path")
+            .unwrap_err();
+        assert!(err.to_string().contains("worktree path"), "{err}");
+    }
+
+    #[test]
+    fn abandoning_name_generation_keeps_original_name() {
+        let (manager, session, _temp) = manager_with_pending_session();
+
+        manager.abandon_name_generation(&session.name).unwrap();
+
+        let stored = manager.get_session(&session.name).unwrap();
+        assert_eq!(stored.branch, session.branch);
+        assert!(!stored.pending_name_generation);
+    }
+}
@@ -0,0 +1,258 @@
+use super::SessionManager;
+use crate::domains::git::service as git;
+use crate::domains::merge::lock as merge_lock;
+use crate::domains::sessions::db_snapshots::SessionSnapshot;
+use crate::domains::sessions::entity::SessionState;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use log::warn;
+use uuid::Uuid;
+
+impl SessionManager {
+    /// Capture the session's branch tip and uncommitted changes so they can be restored
+    /// with `restore_session_snapshot`.
+    pub fn create_session_snapshot(
+        &self,
+        name: &str,
+        label: Option<&str>,
+    ) -> Result<SessionSnapshot> {
+        let session = self.db_manager.get_session_by_name(name)?;
+        if session.session_state == SessionState::Spec {
+            return Err(anyhow!("Cannot snapshot spec session '{name}'"));
+        }
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let created_at = Utc::now();
+        let label = label
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Snapshot {}", created_at.format("%Y-%m-%d %H:%M:%S")));
+        let ref_name = git::snapshot_ref_name(&session.name, &id);
+        let captured = git::create_worktree_snapshot(
+            &session.worktree_path,
+            &ref_name,
+            &format!("Schaltwerk snapshot: {label} [session:{name}]"),
+        )?;
+
+        let snapshot = SessionSnapshot {
+            id,
+            session_id: session.id.clone(),
+            label,
+            ref_name,
+            head_oid: captured.head_oid.to_string(),
+            dirty_files: u32::try_from(captured.dirty_files).unwrap_or(u32::MAX),
+            created_at,
+        };
+        if let Err(e) = self.db_manager.create_session_snapshot(&snapshot) {
+            if let Err(cleanup) = git::delete_snapshot_ref(&self.repo_path, &snapshot.ref_name) {
+                warn!(
+                    "Failed to remove unrecorded snapshot ref {}: {cleanup}",
+                    snapshot.ref_name
+                );
+            }
+            return Err(e);
+        }
+        Ok(snapshot)
+    }
+
+    pub fn list_session_snapshots(&self, name: &str) -> Result<Vec<SessionSnapshot>> {
+        let session = self.db_manager.get_session_by_name(name)?;
+        self.db_manager.list_session_snapshots(&session.id)
+    }
+
+    /// Reset the session's worktree to a snapshot: the branch moves back to the recorded
+    /// tip and the captured uncommitted changes replace the current ones. Refused while
+    /// the session is being merged.
+    pub fn restore_session_snapshot(
+        &self,
+        name: &str,
+        snapshot_id: &str,
+    ) -> Result<SessionSnapshot> {
+        let session = self.db_manager.get_session_by_name(name)?;
+        if !session.worktree_path.starts_with(&self.repo_path) {
+            return Err(anyhow!("Invalid worktree path for this project"));
+        }
+        let snapshot = self
+            .db_manager
+            .get_session_snapshot(&session.id, snapshot_id)?
+            .ok_or_else(|| anyhow!("Snapshot '{snapshot_id}' not found for session '{name}'"))?;
+
+        let _merge_lock = merge_lock::try_acquire(name).ok_or_else(|| {
+            anyhow!("Cannot restore a snapshot while session '{name}' is being merged")
+        })?;
+
+        let repo = git2::Repository::open(&session.worktree_path)
+            .map_err(|e| anyhow!("Failed to open worktree repository: {e}"))?;
+        let head = repo
+            .head()
+            .map_err(|e| anyhow!("Failed to read HEAD: {e}"))?;
+        let expected_ref = format!("refs/heads/{}", session.branch);
+        if head.name() != Some(expected_ref.as_str()) {
+            return Err(anyhow!(
+                "HEAD does not point to the session branch (expected {}, got {:?})",
+                expected_ref,
+                head.name()
+            ));
+        }
+
+        git::restore_worktree_snapshot(&session.worktree_path, &snapshot.ref_name)?;
+        self.db_manager.update_git_stats(&session.id)?;
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::sessions::service::SessionCreationParams;
+    use crate::infrastructure::database::Database;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn manager_with_session(name: &str) -> (SessionManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        let manager = SessionManager::new(db, repo);
+        manager
+            .create_session_with_agent(SessionCreationParams {
+                name,
+                prompt: None,
+                base_branch: Some("main"),
+                custom_branch: None,
+                use_existing_branch: false,
+                sync_with_origin: false,
+                was_auto_generated: false,
+                version_group_id: None,
+                version_number: None,
+                version_group_label: None,
+                epic_id: None,
+                agent_type: None,
+                skip_permissions: None,
+                pr_number: None,
+            })
+            .unwrap();
+        (manager, temp)
+    }
+
+    fn snapshot_refs(repo: &Path) -> String {
+        git(
+            repo,
+            &[
+                "for-each-ref",
+                "--format=%(refname)",
+                "refs/schaltwerk/snapshots",
+            ],
+        )
+    }
+
+    #[test]
+    fn snapshot_with_dirty_files_restores_after_agent_run() {
+        let (manager, _temp) = manager_with_session("refactor");
+        let session = manager.get_session("refactor").unwrap();
+        let worktree = &session.worktree_path;
+        let tip = git(worktree, &["rev-parse", "HEAD"]);
+        std::fs::write(worktree.join("README.md"), "Work in progress").unwrap();
+        std::fs::write(worktree.join("notes.md"), "plan").unwrap();
+
+        let snapshot = manager
+            .create_session_snapshot("refactor", Some("  before refactor "))
+            .unwrap();
+        assert_eq!(snapshot.label, "before refactor");
+        assert_eq!(snapshot.dirty_files, 2);
+        assert_eq!(snapshot.head_oid, tip);
+        assert_eq!(
+            manager.list_session_snapshots("refactor").unwrap(),
+            vec![snapshot.clone()]
+        );
+
+        git(worktree, &["add", "."]);
+        git(worktree, &["commit", "-m", "agent rewrite"]);
+        std::fs::write(worktree.join("README.md"), "Broken").unwrap();
+        std::fs::write(worktree.join("scratch.rs"), "junk").unwrap();
+
+        let restored = manager
+            .restore_session_snapshot("refactor", &snapshot.id)
+            .unwrap();
+        assert_eq!(restored, snapshot);
+        assert_eq!(git(worktree, &["rev-parse", "HEAD"]), tip);
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("README.md")).unwrap(),
+            "Work in progress"
+        );
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("notes.md")).unwrap(),
+            "plan"
+        );
+        assert!(!worktree.join("scratch.rs").exists());
+    }
+
+    #[test]
+    fn restore_is_refused_while_the_session_is_being_merged() {
+        let (manager, _temp) = manager_with_session("merging");
+        let session = manager.get_session("merging").unwrap();
+        let snapshot = manager.create_session_snapshot("merging", None).unwrap();
+        assert!(snapshot.label.starts_with("Snapshot "));
+        std::fs::write(session.worktree_path.join("late.txt"), "keep").unwrap();
+
+        let guard = merge_lock::try_acquire("merging").unwrap();
+        let err = manager
+            .restore_session_snapshot("merging", &snapshot.id)
+            .unwrap_err();
+        assert!(err.to_string().contains("being merged"), "{err}");
+        assert!(session.worktree_path.join("late.txt").exists());
+        drop(guard);
+
+        manager
+            .restore_session_snapshot("merging", &snapshot.id)
+            .unwrap();
+        assert!(!session.worktree_path.join("late.txt").exists());
+    }
+
+    #[test]
+    fn cancelling_a_session_deletes_its_snapshots() {
+        let (manager, _temp) = manager_with_session("doomed");
+        manager
+            .create_session_snapshot("doomed", Some("one"))
+            .unwrap();
+        manager
+            .create_session_snapshot("doomed", Some("two"))
+            .unwrap();
+        let session = manager.get_session("doomed").unwrap();
+        assert_eq!(snapshot_refs(&manager.repo_path).lines().count(), 2);
+
+        manager.cancel_session("doomed").unwrap();
+
+        assert_eq!(snapshot_refs(&manager.repo_path), "");
+        assert!(
+            manager
+                .db_manager
+                .list_session_snapshots(&session.id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use super::SessionManager;
+use crate::domains::sessions::db_sessions::SessionMethods;
+use crate::domains::sessions::entity::{SessionStatus, Spec};
+use crate::infrastructure::database::SpecMethods;
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+
+impl SessionManager {
+    /// Records that `spec_name` may only start once `depends_on` has merged; `None`
+    /// clears the dependency. Dependencies that would form a cycle are rejected.
+    pub fn set_spec_dependency(&self, spec_name: &str, depends_on: Option<&str>) -> Result<Spec> {
+        let spec = self
+            .db_manager
+            .get_spec_by_name(spec_name)
+            .map_err(|e| anyhow!("Spec '{spec_name}' not found: {e}"))?;
+        let depends_on = depends_on.map(str::trim).filter(|name| !name.is_empty());
+
+        if let Some(dependency) = depends_on {
+            if dependency == spec.name {
+                return Err(anyhow!("Spec '{spec_name}' cannot depend on itself"));
+            }
+
+            let dependency_spec = self.db_manager.get_spec_by_name(dependency).ok();
+            if dependency_spec.is_none() && self.db_manager.get_session_by_name(dependency).is_err()
+            {
+                return Err(anyhow!("Session '{dependency}' not found"));
+            }
+
+            let mut visited = HashSet::new();
+            let mut next = dependency_spec.and_then(|s| s.depends_on_session);
+            while let Some(name) = next {
+                if name == spec.name {
+                    return Err(anyhow!(
+                        "Circular dependency: '{dependency}' already depends on spec '{spec_name}'"
+                    ));
+                }
+                if !visited.insert(name.clone()) {
+                    break;
+                }
+                next = self
+                    .db_manager
+                    .get_spec_by_name(&name)
+                    .ok()
+                    .and_then(|s| s.depends_on_session);
+            }
+        }
+
+        self.db_manager
+            .db
+            .set_spec_depends_on_session(&spec.id, depends_on)?;
+        self.db_manager.get_spec_by_name(&spec.name)
+    }
+
+    /// Refuses to start `spec` while the session it depends on is still open, unless
+    /// `force` is set. Returns the branch a merged dependency landed on, which the
+    /// spec should start from when no base branch was requested.
+    pub(super) fn check_spec_dependency(&self, spec: &Spec, force: bool) -> Result<Option<String>> {
+        let Some(dependency) = spec.depends_on_session.as_deref() else {
+            return Ok(None);
+        };
+
+        let blocker = match self.db_manager.get_session_by_name(dependency) {
+            Ok(session) => {
+                if self.db_manager.db.is_session_merged(&session.id)? {
+                    return Ok(Some(session.parent_branch));
+                }
+                if session.status == SessionStatus::Cancelled {
+                    None
+                } else {
+                    Some(format!("session '{dependency}', which has not merged yet"))
+                }
+            }
+            Err(_) if self.db_manager.get_spec_by_name(dependency).is_ok() => Some(format!(
+                "spec '{dependency}', which has not been started yet"
+            )),
+            Err(_) => None,
+        };
+
+        match blocker {
+            Some(reason) if !force => Err(anyhow!(
+                "Spec '{}' depends on {reason}. Merge it first or start with force.",
+                spec.name
+            )),
+            Some(reason) => {
+                log::warn!(
+                    "Force-starting spec '{}' that depends on {reason}",
+                    spec.name
+                );
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::Database;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn manager_with_repo() -> (SessionManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        (SessionManager::new(db, repo), temp)
+    }
+
+    fn dependent_spec(manager: &SessionManager) -> String {
+        manager
+            .create_session_with_auto_flag("api", None, Some("main"), false, None, None)
+            .unwrap();
+        let spec = manager
+            .create_spec_session("client", "Use the new API")
+            .unwrap();
+        manager
+            .set_spec_dependency(&spec.name, Some("api"))
+            .unwrap();
+        spec.name
+    }
+
+    #[test]
+    fn start_is_blocked_until_dependency_merges() {
+        let (manager, _temp) = manager_with_repo();
+        let spec = dependent_spec(&manager);
+
+        let err = manager
+            .start_spec_session(&spec, None, None, None, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("session 'api'"), "unexpected error: {err}");
+        assert!(manager.get_spec(&spec).is_ok(), "blocked spec must remain");
+    }
+
+    #[test]
+    fn start_uses_dependency_parent_after_merge() {
+        let (manager, _temp) = manager_with_repo();
+        let spec = dependent_spec(&manager);
+        let api = manager.get_session("api").unwrap();
+        manager.db_manager.db.mark_session_merged(&api.id).unwrap();
+
+        let session = manager
+            .start_spec_session(&spec, None, None, None, false)
+            .unwrap();
+        assert_eq!(session.parent_branch, api.parent_branch);
+    }
+
+    #[test]
+    fn forced_start_ignores_open_dependency() {
+        let (manager, _temp) = manager_with_repo();
+        let spec = dependent_spec(&manager);
+
+        let session = manager
+            .start_spec_session(&spec, None, None, None, true)
+            .unwrap();
+        assert_eq!(session.name, spec);
+    }
+
+    #[test]
+    fn circular_spec_dependencies_are_rejected() {
+        let (manager, _temp) = manager_with_repo();
+        let first = manager.create_spec_session("first", "one").unwrap();
+        let second = manager.create_spec_session("second", "two").unwrap();
+
+        manager
+            .set_spec_dependency(&first.name, Some(&second.name))
+            .unwrap();
+        let err = manager
+            .set_spec_dependency(&second.name, Some(&first.name))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Circular dependency"),
+            "unexpected error: {err}"
+        );
+        assert!(
+            manager
+                .set_spec_dependency(&first.name, Some("first"))
+                .is_err()
+        );
+
+        let unchanged = manager.get_spec(&second.name).unwrap();
+        assert_eq!(unchanged.depends_on_session, None);
+    }
+}
@@ -0,0 +1,807 @@
+use super::SessionManager;
+use crate::domains::agents::naming::sanitize_name;
+use crate::domains::git::service as git;
+use crate::domains::sessions::entity::{ArchivedSpec, Spec};
+use crate::infrastructure::database::db_archived_specs::ArchivedSpecMethods as _;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const FRONTMATTER_DELIMITER: &str = "---";
+const SPEC_FILE_EXTENSION: &str = "md";
+
+/// Directory the spec-file mirror keeps one `<name>.md` per spec in.
+pub fn spec_mirror_dir(repo_path: &Path) -> PathBuf {
+    repo_path.join(".schaltwerk").join("specs")
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecFrontmatter {
+    pub name: Option<String>,
+    pub display_name: Option<String>,
+    pub epic: Option<String>,
+    pub agent_type: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecDocument {
+    pub frontmatter: SpecFrontmatter,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedSpecFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecImportReport {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<SkippedSpecFile>,
+}
+
+fn push_field(out: &mut String, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        let quoted = serde_json::to_string(value).unwrap_or_else(|_| format!("\"{value}\""));
+        out.push_str(&format!("{key}: {quoted}\n"));
+    }
+}
+
+/// Renders a spec as markdown with a YAML frontmatter block. The body after the
+/// closing delimiter is the spec content verbatim.
+pub fn render_spec_markdown(spec: &Spec, epic_name: Option<&str>) -> String {
+    let mut out = String::new();
+    out.push_str(FRONTMATTER_DELIMITER);
+    out.push('\n');
+    push_field(&mut out, "name", Some(&spec.name));
+    push_field(&mut out, "display_name", spec.display_name.as_deref());
+    push_field(&mut out, "epic", epic_name);
+    push_field(&mut out, "created_at", Some(&spec.created_at.to_rfc3339()));
+    push_field(&mut out, "updated_at", Some(&spec.updated_at.to_rfc3339()));
+    out.push_str(FRONTMATTER_DELIMITER);
+    out.push('\n');
+    out.push_str(&spec.content);
+    out
+}
+
+fn parse_frontmatter_value(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if trimmed.starts_with('"')
+        && let Ok(value) = serde_json::from_str::<String>(trimmed)
+    {
+        return value;
+    }
+    if trimmed.len() >= 2 && trimmed.starts_with('\'') && trimmed.ends_with('\'') {
+        return trimmed[1..trimmed.len() - 1].replace("''", "'");
+    }
+    trimmed.to_string()
+}
+
+/// Splits an optional frontmatter block from the body. Files without a leading
+/// `---` line are treated as plain content.
+pub fn parse_spec_markdown(source: &str) -> SpecDocument {
+    let opening = format!("{FRONTMATTER_DELIMITER}\n");
+    let Some(rest) = source.strip_prefix(&opening) else {
+        return SpecDocument {
+            frontmatter: SpecFrontmatter::default(),
+            content: source.to_string(),
+        };
+    };
+
+    let closing = format!("\n{FRONTMATTER_DELIMITER}\n");
+    let (header, content) = if let Some(stripped) = rest.strip_prefix(&opening) {
+        ("", stripped)
+    } else if let Some(end) = rest.find(&closing) {
+        (&rest[..end], &rest[end + closing.len()..])
+    } else if let Some(header) = rest.strip_suffix(&format!("\n{FRONTMATTER_DELIMITER}")) {
+        (header, "")
+    } else {
+        return SpecDocument {
+            frontmatter: SpecFrontmatter::default(),
+            content: source.to_string(),
+        };
+    };
+
+    let fields: BTreeMap<&str, String> = header
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), parse_frontmatter_value(value)))
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+
+    let field = |key: &str| fields.get(key).cloned();
+    SpecDocument {
+        frontmatter: SpecFrontmatter {
+            name: field("name"),
+            display_name: field("display_name"),
+            epic: field("epic"),
+            agent_type: field("agent_type"),
+            created_at: field("created_at"),
+            updated_at: field("updated_at"),
+        },
+        content: content.to_string(),
+    }
+}
+
+/// Picks the spec name for an imported file: the frontmatter name, else the file stem.
+/// Names that are already valid are kept as-is so round-trips are stable; anything
+/// else goes through `sanitize_name`.
+fn resolve_import_name(frontmatter: &SpecFrontmatter, path: &Path) -> Option<String> {
+    let candidate = frontmatter.name.clone().or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+    })?;
+
+    if git::is_valid_session_name(&candidate) {
+        return Some(candidate);
+    }
+
+    let sanitized = sanitize_name(&candidate);
+    if sanitized.is_empty() || !git::is_valid_session_name(&sanitized) {
+        None
+    } else {
+        Some(sanitized)
+    }
+}
+
+fn is_spec_file_name(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(SPEC_FILE_EXTENSION))
+}
+
+/// Lists the markdown spec files directly inside `dir`, sorted by path.
+pub fn spec_files_in_directory(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read spec directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_spec_file_name(path))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn file_modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(DateTime::<Utc>::from(modified))
+}
+
+#[derive(Debug, Clone)]
+struct SyncedSpecFile {
+    name: String,
+    content: String,
+}
+
+/// Remembers which spec each watched file maps to and the content last synced from it,
+/// so deletions can be resolved and concurrent in-app edits detected.
+#[derive(Debug, Default)]
+pub struct SpecSyncState {
+    synced: HashMap<PathBuf, SyncedSpecFile>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecSyncReport {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub archived: Vec<String>,
+    pub conflicts: Vec<String>,
+}
+
+impl SpecSyncReport {
+    pub fn has_changes(&self) -> bool {
+        !self.created.is_empty() || !self.updated.is_empty() || !self.archived.is_empty()
+    }
+}
+
+impl SessionManager {
+    fn render_spec_file(&self, spec: &Spec) -> String {
+        let epic_name = spec
+            .epic_id
+            .as_deref()
+            .and_then(|id| self.db_manager.get_epic_by_id(id).ok())
+            .map(|epic| epic.name);
+        render_spec_markdown(spec, epic_name.as_deref())
+    }
+
+    pub fn export_specs_to_directory(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create export directory {}", dir.display()))?;
+
+        let mut written = Vec::new();
+        for spec in self.list_specs()? {
+            let path = dir.join(format!("{}.{SPEC_FILE_EXTENSION}", spec.name));
+            std::fs::write(&path, self.render_spec_file(&spec))
+                .with_context(|| format!("Failed to write spec file {}", path.display()))?;
+            written.push(path);
+        }
+
+        log::info!(
+            "Exported {} specs from {} to {}",
+            written.len(),
+            self.repo_path.display(),
+            dir.display()
+        );
+        Ok(written)
+    }
+
+    pub fn export_spec_to_file(&self, name: &str, path: &Path) -> Result<PathBuf> {
+        let spec = self.get_spec(name)?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        std::fs::write(path, self.render_spec_file(&spec))
+            .with_context(|| format!("Failed to write spec file {}", path.display()))?;
+        Ok(path.to_path_buf())
+    }
+
+    pub fn import_specs_from_directory(
+        &self,
+        dir: &Path,
+        overwrite: bool,
+    ) -> Result<SpecImportReport> {
+        if !dir.is_dir() {
+            return Err(anyhow!("Spec directory {} does not exist", dir.display()));
+        }
+
+        let paths = spec_files_in_directory(dir)?;
+
+        let mut report = SpecImportReport::default();
+        for path in paths {
+            let skip = |reason: String| SkippedSpecFile {
+                path: path.clone(),
+                reason,
+            };
+
+            let source = match std::fs::read_to_string(&path) {
+                Ok(source) => source,
+                Err(e) => {
+                    report.skipped.push(skip(format!("Failed to read file: {e}")));
+                    continue;
+                }
+            };
+
+            let document = parse_spec_markdown(&source);
+            let Some(name) = resolve_import_name(&document.frontmatter, &path) else {
+                report
+                    .skipped
+                    .push(skip("Invalid spec name".to_string()));
+                continue;
+            };
+
+            if self.db_manager.get_spec_by_name(&name).is_ok() {
+                if !overwrite {
+                    report
+                        .skipped
+                        .push(skip(format!("Spec '{name}' already exists")));
+                    continue;
+                }
+                if let Err(e) = self.update_spec_content(&name, &document.content) {
+                    report.skipped.push(skip(e.to_string()));
+                    continue;
+                }
+                report.updated.push(name);
+                continue;
+            }
+
+            let epic_id = document
+                .frontmatter
+                .epic
+                .as_deref()
+                .and_then(|epic| self.db_manager.get_epic_by_name(epic).ok())
+                .map(|epic| epic.id);
+
+            match self.create_spec_session_with_agent(
+                &name,
+                &document.content,
+                document.frontmatter.agent_type.as_deref(),
+                document.frontmatter.display_name.as_deref(),
+                epic_id.as_deref(),
+            ) {
+                Ok(spec) => report.created.push(spec.name),
+                Err(e) => report.skipped.push(skip(e.to_string())),
+            }
+        }
+
+        log::info!(
+            "Imported specs from {}: created={} updated={} skipped={}",
+            dir.display(),
+            report.created.len(),
+            report.updated.len(),
+            report.skipped.len()
+        );
+        Ok(report)
+    }
+
+    /// Copies a spec's current content into the archive without removing the spec.
+    fn archive_spec_snapshot(&self, spec: &Spec) -> Result<()> {
+        let archived = ArchivedSpec {
+            id: Uuid::new_v4().to_string(),
+            session_name: spec.name.clone(),
+            repository_path: self.repo_path.clone(),
+            repository_name: spec.repository_name.clone(),
+            content: spec.content.clone(),
+            archived_at: Utc::now(),
+        };
+        self.db_manager.db.insert_archived_spec(&archived)?;
+        self.db_manager.db.enforce_archive_limit(&self.repo_path)?;
+        Ok(())
+    }
+
+    /// Applies changed spec files from a watched directory. Existing files create or update
+    /// specs; missing files archive the spec they were last synced to. When the spec was
+    /// edited in the app since the last sync the file still wins, but the in-app content is
+    /// archived first.
+    pub fn sync_spec_files(
+        &self,
+        state: &mut SpecSyncState,
+        changed: &[PathBuf],
+    ) -> Result<SpecSyncReport> {
+        let mut report = SpecSyncReport::default();
+        let mut seen = HashSet::new();
+
+        for path in changed {
+            if !is_spec_file_name(path) || !seen.insert(path.clone()) {
+                continue;
+            }
+
+            if !path.exists() {
+                let name = state
+                    .synced
+                    .remove(path)
+                    .map(|synced| synced.name)
+                    .or_else(|| resolve_import_name(&SpecFrontmatter::default(), path));
+                if let Some(name) = name
+                    && self.db_manager.get_spec_by_name(&name).is_ok()
+                {
+                    self.archive_spec_session(&name)?;
+                    report.archived.push(name);
+                }
+                continue;
+            }
+
+            let source = match std::fs::read_to_string(path) {
+                Ok(source) => source,
+                Err(e) => {
+                    log::warn!("Failed to read spec file {}: {e}", path.display());
+                    continue;
+                }
+            };
+            let document = parse_spec_markdown(&source);
+            let Some(name) = resolve_import_name(&document.frontmatter, path) else {
+                log::warn!("Skipping spec file with invalid name: {}", path.display());
+                continue;
+            };
+
+            match self.db_manager.get_spec_by_name(&name) {
+                Ok(spec) if spec.content == document.content => {}
+                Ok(spec) => {
+                    let baseline = state
+                        .synced
+                        .get(path)
+                        .filter(|synced| synced.name == name)
+                        .map(|synced| synced.content.as_str());
+                    if baseline != Some(spec.content.as_str()) {
+                        log::warn!(
+                            "Spec '{name}' changed both in the app and in {}; keeping the file and archiving the previous content",
+                            path.display()
+                        );
+                        self.archive_spec_snapshot(&spec)?;
+                        report.conflicts.push(name.clone());
+                    }
+                    self.update_spec_content(&name, &document.content)?;
+                    report.updated.push(name.clone());
+                }
+                Err(_) => {
+                    let epic_id = document
+                        .frontmatter
+                        .epic
+                        .as_deref()
+                        .and_then(|epic| self.db_manager.get_epic_by_name(epic).ok())
+                        .map(|epic| epic.id);
+                    self.create_spec_session_with_agent(
+                        &name,
+                        &document.content,
+                        document.frontmatter.agent_type.as_deref(),
+                        document.frontmatter.display_name.as_deref(),
+                        epic_id.as_deref(),
+                    )?;
+                    report.created.push(name.clone());
+                }
+            }
+
+            state.synced.insert(
+                path.clone(),
+                SyncedSpecFile {
+                    name,
+                    content: document.content,
+                },
+            );
+        }
+
+        if report.has_changes() {
+            log::info!(
+                "Synced spec files for {}: created={} updated={} archived={} conflicts={}",
+                self.repo_path.display(),
+                report.created.len(),
+                report.updated.len(),
+                report.archived.len(),
+                report.conflicts.len()
+            );
+        }
+        Ok(report)
+    }
+
+    pub fn spec_mirror_dir(&self) -> PathBuf {
+        spec_mirror_dir(&self.repo_path)
+    }
+
+    /// Writes the mirror file of `name` if the project mirrors specs to files.
+    pub fn mirror_spec_file(&self, name: &str) -> Result<Option<PathBuf>> {
+        if !self.db_manager.get_project_spec_file_mirror()? {
+            return Ok(None);
+        }
+        let spec = self.get_spec(name)?;
+        self.write_spec_mirror_file(&spec).map(Some)
+    }
+
+    fn write_spec_mirror_file(&self, spec: &Spec) -> Result<PathBuf> {
+        let dir = self.spec_mirror_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create spec mirror directory {}", dir.display()))?;
+
+        let path = dir.join(format!("{}.{SPEC_FILE_EXTENSION}", spec.name));
+        let rendered = self.render_spec_file(spec);
+        // Unchanged files are left alone so the watcher does not pick up our own writes
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(rendered.as_str()) {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("Failed to write spec file {}", path.display()))?;
+        }
+        Ok(path)
+    }
+
+    /// Applies external edits to mirrored spec files. The newer side wins: a file modified
+    /// after the spec's last update replaces its content, an older one is rewritten from
+    /// the database and reported as a conflict. Files of unknown specs are ignored.
+    pub fn sync_spec_mirror_files(&self, changed: &[PathBuf]) -> Result<SpecSyncReport> {
+        let mut report = SpecSyncReport::default();
+        let mut seen = HashSet::new();
+
+        for path in changed {
+            if !is_spec_file_name(path) || !path.is_file() || !seen.insert(path.clone()) {
+                continue;
+            }
+
+            let source = match std::fs::read_to_string(path) {
+                Ok(source) => source,
+                Err(e) => {
+                    log::warn!("Failed to read mirrored spec file {}: {e}", path.display());
+                    continue;
+                }
+            };
+            let document = parse_spec_markdown(&source);
+            let Some(name) = resolve_import_name(&document.frontmatter, path) else {
+                continue;
+            };
+            let Ok(spec) = self.db_manager.get_spec_by_name(&name) else {
+                log::debug!("Ignoring mirrored file of unknown spec: {}", path.display());
+                continue;
+            };
+            if spec.content == document.content {
+                continue;
+            }
+
+            if file_modified_at(path).is_some_and(|modified| modified > spec.updated_at) {
+                self.update_spec_content(&name, &document.content)?;
+                report.updated.push(name);
+            } else {
+                log::warn!(
+                    "Spec '{name}' is newer in the app than in {}; rewriting the file",
+                    path.display()
+                );
+                self.write_spec_mirror_file(&spec)?;
+                report.conflicts.push(name);
+            }
+        }
+
+        if !report.updated.is_empty() || !report.conflicts.is_empty() {
+            log::info!(
+                "Synced spec mirror for {}: updated={} conflicts={}",
+                self.repo_path.display(),
+                report.updated.len(),
+                report.conflicts.len()
+            );
+        }
+        Ok(report)
+    }
+
+    /// Brings the mirror in line with the database, e.g. when it is enabled or the project
+    /// opens: files edited in the meantime are synced, then missing files are written.
+    pub fn reconcile_spec_mirror(&self) -> Result<SpecSyncReport> {
+        let dir = self.spec_mirror_dir();
+        let existing = if dir.is_dir() {
+            spec_files_in_directory(&dir)?
+        } else {
+            Vec::new()
+        };
+        let report = self.sync_spec_mirror_files(&existing)?;
+
+        for spec in self.list_specs()? {
+            let path = dir.join(format!("{}.{SPEC_FILE_EXTENSION}", spec.name));
+            if !path.exists() {
+                self.write_spec_mirror_file(&spec)?;
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::{Database, ProjectConfigMethods};
+    use filetime::{FileTime, set_file_mtime};
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn manager_with_repo() -> (SessionManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        for args in [
+            vec!["init"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test User"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(&repo)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "init"])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        (SessionManager::new(db, repo), temp)
+    }
+
+    #[test]
+    fn export_import_round_trip_preserves_content() {
+        let (source, temp) = manager_with_repo();
+        let tricky = "# Title\n\n---\nnot frontmatter\n---\n\ttabs and trailing spaces   \n\nno newline at end";
+        source
+            .create_spec_session_with_agent("round_trip", tricky, None, Some("Round \"trip\""), None)
+            .unwrap();
+        source.create_spec_session("empty-spec", "").unwrap();
+
+        let export_dir = temp.path().join("export");
+        let written = source.export_specs_to_directory(&export_dir).unwrap();
+        assert_eq!(written.len(), 2);
+        assert!(export_dir.join("round_trip.md").exists());
+
+        let (target, _target_temp) = manager_with_repo();
+        let report = target
+            .import_specs_from_directory(&export_dir, false)
+            .unwrap();
+        assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+        assert_eq!(report.created.len(), 2);
+
+        let imported = target.get_spec("round_trip").unwrap();
+        assert_eq!(imported.content, tricky);
+        assert_eq!(imported.display_name.as_deref(), Some("Round \"trip\""));
+        assert_eq!(target.get_spec("empty-spec").unwrap().content, "");
+    }
+
+    #[test]
+    fn import_sanitizes_names_and_reports_invalid_files() {
+        let (manager, temp) = manager_with_repo();
+        let dir = temp.path().join("specs");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("My Fancy Spec.md"), "plain body").unwrap();
+        std::fs::write(dir.join("!!!.md"), "no usable name").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let report = manager.import_specs_from_directory(&dir, false).unwrap();
+
+        assert_eq!(report.created, vec![sanitize_name("My Fancy Spec")]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].path, dir.join("!!!.md"));
+        assert_eq!(
+            manager.get_spec("my-fancy-spec").unwrap().content,
+            "plain body"
+        );
+    }
+
+    #[test]
+    fn import_respects_overwrite_flag() {
+        let (manager, temp) = manager_with_repo();
+        manager.create_spec_session("shared", "original").unwrap();
+
+        let dir = temp.path().join("specs");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("shared.md"), "---\nname: \"shared\"\n---\nreplacement").unwrap();
+
+        let report = manager.import_specs_from_directory(&dir, false).unwrap();
+        assert!(report.updated.is_empty());
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(manager.get_spec("shared").unwrap().content, "original");
+
+        let report = manager.import_specs_from_directory(&dir, true).unwrap();
+        assert_eq!(report.updated, vec!["shared".to_string()]);
+        assert_eq!(manager.get_spec("shared").unwrap().content, "replacement");
+    }
+
+    #[test]
+    fn sync_creates_updates_and_archives_from_file_events() {
+        let (manager, temp) = manager_with_repo();
+        let dir = temp.path().join("specs");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("watched.md");
+        let mut state = SpecSyncState::default();
+
+        std::fs::write(&path, "first draft").unwrap();
+        let report = manager.sync_spec_files(&mut state, &[path.clone()]).unwrap();
+        assert_eq!(report.created, vec!["watched".to_string()]);
+        assert_eq!(manager.get_spec("watched").unwrap().content, "first draft");
+
+        std::fs::write(&path, "second draft").unwrap();
+        let report = manager
+            .sync_spec_files(&mut state, &[path.clone(), path.clone()])
+            .unwrap();
+        assert_eq!(report.updated, vec!["watched".to_string()]);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(manager.get_spec("watched").unwrap().content, "second draft");
+
+        std::fs::remove_file(&path).unwrap();
+        let report = manager.sync_spec_files(&mut state, &[path]).unwrap();
+        assert_eq!(report.archived, vec!["watched".to_string()]);
+        assert!(manager.get_spec("watched").is_err());
+        assert!(
+            manager
+                .list_archived_specs()
+                .unwrap()
+                .iter()
+                .any(|spec| spec.session_name == "watched" && spec.content == "second draft")
+        );
+    }
+
+    #[test]
+    fn sync_conflict_prefers_file_and_archives_app_content() {
+        let (manager, temp) = manager_with_repo();
+        let dir = temp.path().join("specs");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("contested.md");
+        let mut state = SpecSyncState::default();
+
+        std::fs::write(&path, "from file").unwrap();
+        manager.sync_spec_files(&mut state, &[path.clone()]).unwrap();
+        manager
+            .update_spec_content("contested", "edited in app")
+            .unwrap();
+
+        std::fs::write(&path, "edited on disk").unwrap();
+        let report = manager.sync_spec_files(&mut state, &[path]).unwrap();
+
+        assert_eq!(report.conflicts, vec!["contested".to_string()]);
+        assert_eq!(
+            manager.get_spec("contested").unwrap().content,
+            "edited on disk"
+        );
+        assert!(
+            manager
+                .list_archived_specs()
+                .unwrap()
+                .iter()
+                .any(|spec| spec.content == "edited in app")
+        );
+    }
+
+    #[test]
+    fn single_spec_export_parses_back() {
+        let (manager, temp) = manager_with_repo();
+        manager.create_spec_session("share-me", "body\n").unwrap();
+
+        let path = temp.path().join("out").join("share-me.md");
+        manager.export_spec_to_file("share-me", &path).unwrap();
+
+        let document = parse_spec_markdown(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(document.frontmatter.name.as_deref(), Some("share-me"));
+        assert!(document.frontmatter.created_at.is_some());
+        assert_eq!(document.content, "body\n");
+    }
+
+    fn enable_mirror(manager: &SessionManager) {
+        manager
+            .db_manager
+            .db
+            .set_project_spec_file_mirror(&manager.repo_path, true)
+            .unwrap();
+    }
+
+    #[test]
+    fn mirror_writes_spec_file_on_update_and_append_when_enabled() {
+        let (manager, _temp) = manager_with_repo();
+        manager.create_spec_session("mirrored", "draft").unwrap();
+        let path = manager.spec_mirror_dir().join("mirrored.md");
+
+        manager.update_spec_content("mirrored", "not yet").unwrap();
+        assert!(!path.exists());
+
+        enable_mirror(&manager);
+        manager.update_spec_content("mirrored", "# Plan\n").unwrap();
+        let document = parse_spec_markdown(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(document.frontmatter.name.as_deref(), Some("mirrored"));
+        assert_eq!(document.content, "# Plan\n");
+
+        manager.append_spec_content("mirrored", "- step").unwrap();
+        let document = parse_spec_markdown(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(document.content, "# Plan\n\n- step");
+    }
+
+    #[test]
+    fn mirror_sync_keeps_the_newer_side() {
+        let (manager, _temp) = manager_with_repo();
+        enable_mirror(&manager);
+        manager.create_spec_session("contested", "in app").unwrap();
+        let path = manager.mirror_spec_file("contested").unwrap().unwrap();
+        let updated_at = manager.get_spec("contested").unwrap().updated_at;
+
+        std::fs::write(&path, "edited in editor").unwrap();
+        let newer = FileTime::from_unix_time(updated_at.timestamp() + 60, 0);
+        set_file_mtime(&path, newer).unwrap();
+        let report = manager.sync_spec_mirror_files(&[path.clone()]).unwrap();
+        assert_eq!(report.updated, vec!["contested".to_string()]);
+        assert_eq!(
+            manager.get_spec("contested").unwrap().content,
+            "edited in editor"
+        );
+
+        std::fs::write(&path, "stale copy").unwrap();
+        set_file_mtime(&path, FileTime::from_unix_time(0, 0)).unwrap();
+        let report = manager.sync_spec_mirror_files(&[path.clone()]).unwrap();
+        assert_eq!(report.conflicts, vec!["contested".to_string()]);
+        assert_eq!(
+            manager.get_spec("contested").unwrap().content,
+            "edited in editor"
+        );
+        let document = parse_spec_markdown(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(document.content, "edited in editor");
+    }
+
+    #[test]
+    fn reconcile_writes_missing_mirror_files() {
+        let (manager, _temp) = manager_with_repo();
+        manager.create_spec_session("alpha", "a").unwrap();
+        manager.create_spec_session("beta", "b").unwrap();
+        enable_mirror(&manager);
+
+        manager.reconcile_spec_mirror().unwrap();
+
+        let files = spec_files_in_directory(&manager.spec_mirror_dir()).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                manager.spec_mirror_dir().join("alpha.md"),
+                manager.spec_mirror_dir().join("beta.md"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use super::SessionManager;
+use crate::domains::sessions::entity::Session;
+use crate::domains::terminal::recording::{
+    TerminalRecordingFile, list_recordings, session_recordings_dir,
+};
+use anyhow::{Result, anyhow};
+use std::path::PathBuf;
+
+impl SessionManager {
+    /// Turns terminal output recording for `session_name` on or off.
+    pub fn set_session_recording(&self, session_name: &str, enabled: bool) -> Result<Session> {
+        let session = self.db_manager.get_session_by_name(session_name)?;
+        self.db_manager
+            .set_session_recording(&session.id, enabled)?;
+        Ok(session)
+    }
+
+    /// Names of the sessions that record their terminal output.
+    pub fn list_recording_sessions(&self) -> Result<Vec<String>> {
+        self.db_manager.list_recording_sessions()
+    }
+
+    pub fn session_recordings_dir(&self, session_name: &str) -> PathBuf {
+        session_recordings_dir(&self.repo_path, session_name)
+    }
+
+    /// Recording files of `session_name`, including rotated ones.
+    pub fn get_terminal_recordings(
+        &self,
+        session_name: &str,
+    ) -> Result<Vec<TerminalRecordingFile>> {
+        list_recordings(&self.session_recordings_dir(session_name))
+            .map_err(|e| anyhow!("Failed to list recordings for '{session_name}': {e}"))
+    }
+}
@@ -0,0 +1,897 @@
+use super::SessionManager;
+use crate::domains::git::service as git;
+use crate::domains::sessions::db_journal::{JournalEntry, OperationKind};
+use crate::domains::sessions::entity::{GitStats, Session, SessionState, SessionStatus};
+use crate::domains::sessions::lifecycle::bootstrapper::{BootstrapConfig, WorktreeBootstrapper};
+use crate::domains::sessions::lifecycle::finalizer::{FinalizationConfig, SessionFinalizer};
+use crate::domains::sessions::lifecycle::recovery::JournalGuard;
+use crate::domains::sessions::utils::ensure_worktree_base_writable;
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Most versions a single group can be created with.
+pub const MAX_GROUP_VERSIONS: usize = 4;
+
+/// Which changed files one version shares with a sibling and which it doesn't.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionPairSummary {
+    pub other_session: String,
+    pub shared_files: Vec<String>,
+    pub only_this: Vec<String>,
+    pub only_other: Vec<String>,
+}
+
+/// Changes of one version relative to the base the group started from.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionGroupDiff {
+    pub session_name: String,
+    pub version_number: Option<i32>,
+    pub branch: String,
+    pub stats: GitStats,
+    pub commit_count: u32,
+    pub changed_files: Vec<String>,
+    pub pairs: Vec<VersionPairSummary>,
+}
+
+/// Stored diff stats of one version, for comparing versions before picking a winner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionComparisonEntry {
+    pub session_name: String,
+    pub display_name: Option<String>,
+    pub version_number: Option<i32>,
+    pub agent_type: Option<String>,
+    pub files_changed: u32,
+    pub additions: u32,
+    pub deletions: u32,
+    pub has_uncommitted: bool,
+}
+
+/// Result of [`SessionManager::select_version_winner`].
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionWinnerSelection {
+    pub winner: Session,
+    /// Sibling versions that were cancelled, in version order.
+    pub cancelled: Vec<String>,
+}
+
+/// Agent settings for one version of a group.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VersionAgentConfig {
+    #[serde(default)]
+    pub agent_type: Option<String>,
+    #[serde(default)]
+    pub skip_permissions: Option<bool>,
+}
+
+pub struct VersionGroupParams<'a> {
+    pub base_name: &'a str,
+    pub prompt: Option<&'a str>,
+    pub count: usize,
+    /// Empty for the project defaults, one entry shared by every version, or one
+    /// entry per version.
+    pub agent_configs: &'a [VersionAgentConfig],
+    pub base_branch: Option<&'a str>,
+    pub version_group_label: Option<&'a str>,
+}
+
+/// Sessions created by [`SessionManager::create_version_group`], ordered by version
+/// number. Creation is all-or-nothing: when any version fails, the versions created
+/// so far are removed again and the error is returned instead.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionGroupCreation {
+    pub version_group_id: String,
+    pub sessions: Vec<Session>,
+}
+
+struct PlannedVersion {
+    session_id: String,
+    name: String,
+    branch: String,
+    worktree_path: PathBuf,
+    version_number: i32,
+    agent_type: String,
+    skip_permissions: bool,
+}
+
+impl SessionManager {
+    /// Creates `count` running sessions for the same prompt in a new version group.
+    /// Names, branches and the base branch are resolved once under the repository
+    /// lock; the worktrees are then created in parallel. The first version keeps
+    /// `base_name`, later ones get a `_v{n}` suffix.
+    pub fn create_version_group(&self, params: VersionGroupParams) -> Result<VersionGroupCreation> {
+        let VersionGroupParams {
+            base_name,
+            prompt,
+            count,
+            agent_configs,
+            base_branch,
+            version_group_label,
+        } = params;
+
+        if !(1..=MAX_GROUP_VERSIONS).contains(&count) {
+            return Err(anyhow!(
+                "A version group needs between 1 and {MAX_GROUP_VERSIONS} versions, got {count}"
+            ));
+        }
+        if !matches!(agent_configs.len(), 0 | 1) && agent_configs.len() != count {
+            return Err(anyhow!(
+                "Expected 1 or {count} agent configs for {count} versions, got {}",
+                agent_configs.len()
+            ));
+        }
+        if !git::is_valid_session_name(base_name) {
+            return Err(anyhow!(
+                "Invalid session name: use only letters, numbers, hyphens, and underscores"
+            ));
+        }
+
+        let repo_lock = self.cache_manager.get_repo_lock();
+        let _guard = repo_lock.lock().unwrap();
+
+        let parent_branch = self.resolve_parent_branch(base_branch)?;
+        self.ensure_repository_initialized(&parent_branch)?;
+        let repo_name = self.utils.get_repo_name()?;
+        let default_agent_type = self
+            .db_manager
+            .get_agent_type()
+            .unwrap_or_else(|_| "claude".to_string());
+        let global_skip_default = self.db_manager.get_skip_permissions().unwrap_or(false);
+
+        let mut planned: Vec<PlannedVersion> = Vec::with_capacity(count);
+        for index in 0..count {
+            let version_number = index + 1;
+            let requested_name = if version_number == 1 {
+                base_name.to_string()
+            } else {
+                format!("{base_name}_v{version_number}")
+            };
+            let (name, branch, worktree_path) =
+                match self.utils.find_unique_session_paths(&requested_name) {
+                    Ok(paths) => paths,
+                    Err(e) => {
+                        self.discard_planned_versions(&planned, &[]);
+                        return Err(e);
+                    }
+                };
+            let config = agent_configs
+                .get(index)
+                .or(agent_configs.first())
+                .cloned()
+                .unwrap_or_default();
+            planned.push(PlannedVersion {
+                session_id: Uuid::new_v4().to_string(),
+                name,
+                branch,
+                worktree_path,
+                version_number: version_number as i32,
+                agent_type: config
+                    .agent_type
+                    .unwrap_or_else(|| default_agent_type.clone()),
+                skip_permissions: config.skip_permissions.unwrap_or(global_skip_default),
+            });
+        }
+
+        let _journals: Vec<_> = planned
+            .iter()
+            .map(|version| {
+                let mut entry =
+                    JournalEntry::new(&self.repo_path, OperationKind::Create, &version.name);
+                entry.session_id = Some(version.session_id.clone());
+                entry.branch = Some(version.branch.clone());
+                entry.worktree_path = Some(version.worktree_path.clone());
+                entry.owns_branch = true;
+                JournalGuard::begin(&self.db_manager.db, entry)
+            })
+            .collect();
+
+        // Checking the worktree base and pruning touch shared state, so they run once
+        // per version before the parallel part.
+        for version in &planned {
+            let prepared = version
+                .worktree_path
+                .parent()
+                .map_or(Ok(()), ensure_worktree_base_writable)
+                .and_then(|_| self.utils.cleanup_existing_worktree(&version.worktree_path));
+            if let Err(e) = prepared {
+                self.discard_planned_versions(&planned, &[]);
+                return Err(e);
+            }
+        }
+
+        let copy_patterns = self.worktree_copy_patterns();
+        let clone_dirs = self.worktree_clone_dirs();
+        let bootstrapper = WorktreeBootstrapper::new(&self.repo_path, &self.utils);
+        let bootstrapped: Vec<Result<()>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = planned
+                .iter()
+                .map(|version| {
+                    let bootstrapper = &bootstrapper;
+                    let parent_branch = parent_branch.as_str();
+                    let copy_patterns = copy_patterns.as_slice();
+                    let clone_dirs = clone_dirs.as_slice();
+                    scope.spawn(move || {
+                        bootstrapper
+                            .create_prepared_worktree(&BootstrapConfig {
+                                session_name: &version.name,
+                                branch_name: &version.branch,
+                                worktree_path: &version.worktree_path,
+                                parent_branch,
+                                custom_branch: None,
+                                use_existing_branch: false,
+                                sync_with_origin: false,
+                                should_copy_claude_locals: version
+                                    .agent_type
+                                    .eq_ignore_ascii_case("claude"),
+                                copy_patterns,
+                                clone_dirs,
+                                pr_number: None,
+                            })
+                            .map(|_| ())
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(anyhow!("Worktree creation thread panicked")))
+                })
+                .collect()
+        });
+        for (version, result) in planned.iter().zip(bootstrapped) {
+            if let Err(e) = result {
+                self.discard_planned_versions(&planned, &[]);
+                return Err(e).with_context(|| {
+                    format!("Failed to create worktree for version '{}'", version.name)
+                });
+            }
+        }
+
+        let version_group_id = Uuid::new_v4().to_string();
+        let label = version_group_label
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(String::from);
+        let finalizer = SessionFinalizer::new(&self.db_manager, &self.cache_manager);
+        let mut sessions = Vec::with_capacity(count);
+        for version in &planned {
+            let now = Utc::now();
+            let session = Session {
+                id: version.session_id.clone(),
+                name: version.name.clone(),
+                display_name: None,
+                version_group_id: Some(version_group_id.clone()),
+                version_number: Some(version.version_number),
+                version_group_label: label.clone(),
+                epic_id: None,
+                repository_path: self.repo_path.clone(),
+                repository_name: repo_name.clone(),
+                branch: version.branch.clone(),
+                parent_branch: parent_branch.clone(),
+                original_parent_branch: Some(parent_branch.clone()),
+                worktree_path: version.worktree_path.clone(),
+                status: SessionStatus::Active,
+                created_at: now,
+                updated_at: now,
+                last_activity: None,
+                initial_prompt: prompt.map(String::from),
+                ready_to_merge: false,
+                original_agent_type: Some(version.agent_type.clone()),
+                original_skip_permissions: Some(version.skip_permissions),
+                pending_name_generation: false,
+                was_auto_generated: false,
+                spec_content: None,
+                session_state: SessionState::Running,
+                resume_allowed: false,
+                amp_thread_id: None,
+                pr_number: None,
+                pr_url: None,
+                group: None,
+            };
+            let finalized = finalizer.finalize_creation(FinalizationConfig {
+                session,
+                compute_git_stats: true,
+                update_activity: true,
+            });
+            match finalized {
+                Ok(result) => sessions.push(result.session),
+                Err(e) => {
+                    self.discard_planned_versions(&planned, &sessions);
+                    return Err(e);
+                }
+            }
+            if let Err(e) = self.db_manager.set_session_original_settings(
+                &version.session_id,
+                &version.agent_type,
+                version.skip_permissions,
+            ) {
+                log::warn!("Failed to set original agent settings: {e}");
+            }
+        }
+
+        if let Some(label) = label.as_deref()
+            && let Err(e) = self
+                .db_manager
+                .set_version_group_label(&version_group_id, Some(label))
+        {
+            log::warn!("Failed to label version group '{version_group_id}': {e}");
+        }
+
+        for version in &planned {
+            self.cache_manager.unreserve_name(&version.name);
+        }
+        log::info!(
+            "Created version group '{version_group_id}' with {} versions of '{base_name}'",
+            sessions.len()
+        );
+        Ok(VersionGroupCreation {
+            version_group_id,
+            sessions,
+        })
+    }
+
+    /// Rolls back a partially created version group: drops the persisted sessions,
+    /// removes every worktree and branch the group created, and releases the names.
+    fn discard_planned_versions(&self, planned: &[PlannedVersion], persisted: &[Session]) {
+        for session in persisted {
+            if let Err(e) = self.db_manager.delete_session(&session.id) {
+                log::warn!("Failed to remove version '{}': {e}", session.name);
+            }
+        }
+        for version in planned {
+            if version.worktree_path.exists()
+                && let Err(e) = git::remove_worktree(&self.repo_path, &version.worktree_path)
+            {
+                log::warn!(
+                    "Failed to remove worktree of version '{}': {e}",
+                    version.name
+                );
+            }
+            if git::branch_exists(&self.repo_path, &version.branch).unwrap_or(false)
+                && let Err(e) = git::delete_branch(&self.repo_path, &version.branch)
+            {
+                log::warn!("Failed to delete branch of version '{}': {e}", version.name);
+            }
+            self.cache_manager.unreserve_name(&version.name);
+        }
+    }
+
+    /// Diffs every running version of `group_id` against its parent branch and against
+    /// each sibling, ordered by version number.
+    pub fn diff_version_group(&self, group_id: &str) -> Result<Vec<VersionGroupDiff>> {
+        let mut versions = self.version_group_sessions(group_id)?;
+        versions.retain(|s| s.worktree_path.exists());
+        if versions.is_empty() {
+            return Err(anyhow!("No sessions found in version group '{group_id}'"));
+        }
+
+        let mut diffs = Vec::with_capacity(versions.len());
+        let mut file_sets = Vec::with_capacity(versions.len());
+        for session in versions {
+            let mut stats =
+                git::calculate_git_stats_fast(&session.worktree_path, &session.parent_branch)?;
+            stats.session_id = session.id.clone();
+            let commit_count =
+                git::count_commits_ahead_of_parent(&session.worktree_path, &session.parent_branch)?;
+            let files: BTreeSet<String> =
+                git::get_changed_files(&session.worktree_path, &session.parent_branch)?
+                    .into_iter()
+                    .map(|file| file.path)
+                    .collect();
+
+            diffs.push(VersionGroupDiff {
+                session_name: session.name,
+                version_number: session.version_number,
+                branch: session.branch,
+                stats,
+                commit_count,
+                changed_files: files.iter().cloned().collect(),
+                pairs: Vec::new(),
+            });
+            file_sets.push(files);
+        }
+
+        let names: Vec<String> = diffs.iter().map(|d| d.session_name.clone()).collect();
+        for (i, diff) in diffs.iter_mut().enumerate() {
+            diff.pairs = file_sets
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, other)| VersionPairSummary {
+                    other_session: names[j].clone(),
+                    shared_files: file_sets[i].intersection(other).cloned().collect(),
+                    only_this: file_sets[i].difference(other).cloned().collect(),
+                    only_other: other.difference(&file_sets[i]).cloned().collect(),
+                })
+                .collect();
+        }
+        Ok(diffs)
+    }
+
+    /// Stored diff stats of every running version of `group_id`, ordered by version
+    /// number. Versions without stored stats get them calculated first.
+    pub fn get_version_group_comparison(
+        &self,
+        group_id: &str,
+    ) -> Result<Vec<VersionComparisonEntry>> {
+        self.version_group_sessions(group_id)?
+            .into_iter()
+            .map(|session| {
+                let stats = match self.db_manager.get_git_stats(&session.id)? {
+                    Some(stats) => stats,
+                    None => {
+                        self.db_manager.update_git_stats(&session.id)?;
+                        self.db_manager
+                            .get_git_stats(&session.id)?
+                            .ok_or_else(|| anyhow!("No diff stats for '{}'", session.name))?
+                    }
+                };
+                Ok(VersionComparisonEntry {
+                    session_name: session.name,
+                    display_name: session.display_name,
+                    version_number: session.version_number,
+                    agent_type: session.original_agent_type,
+                    files_changed: stats.files_changed,
+                    additions: stats.lines_added,
+                    deletions: stats.lines_removed,
+                    has_uncommitted: stats.has_uncommitted,
+                })
+            })
+            .collect()
+    }
+
+    /// Keeps `winning_session` as the result of its version group: the other versions
+    /// have their prompts archived and are cancelled, then the winner's display name
+    /// and branch drop the `_vN` suffix.
+    pub async fn select_version_winner(
+        &self,
+        group_id: &str,
+        winning_session: &str,
+    ) -> Result<VersionWinnerSelection> {
+        let versions = self.version_group_sessions(group_id)?;
+        let mut winner = versions
+            .iter()
+            .find(|s| s.name == winning_session)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!("Session '{winning_session}' is not a version in group '{group_id}'")
+            })?;
+
+        let mut cancelled = Vec::new();
+        for sibling in versions.iter().filter(|s| s.name != winning_session) {
+            if let Err(e) = self.archive_prompt_for_session(&sibling.name) {
+                log::warn!(
+                    "Failed to archive prompt of version '{}': {e}",
+                    sibling.name
+                );
+            }
+            self.fast_cancel_session(&sibling.name)
+                .await
+                .with_context(|| format!("Failed to cancel version '{}'", sibling.name))?;
+            cancelled.push(sibling.name.clone());
+        }
+
+        // Renamed only after the siblings are gone so their branches don't block the name.
+        let current_name = winner
+            .display_name
+            .clone()
+            .unwrap_or_else(|| winner.name.clone());
+        let base_name = strip_version_suffix(&current_name);
+        if base_name != current_name {
+            self.apply_display_name_to_session(&mut winner, base_name)?;
+        }
+
+        Ok(VersionWinnerSelection { winner, cancelled })
+    }
+
+    /// Non-spec sessions of `group_id`, ordered by version number.
+    fn version_group_sessions(&self, group_id: &str) -> Result<Vec<Session>> {
+        let mut versions: Vec<_> = self
+            .list_sessions()?
+            .into_iter()
+            .filter(|s| {
+                s.version_group_id.as_deref() == Some(group_id)
+                    && s.session_state != SessionState::Spec
+            })
+            .collect();
+        if versions.is_empty() {
+            return Err(anyhow!("No sessions found in version group '{group_id}'"));
+        }
+        versions.sort_by(|a, b| {
+            a.version_number
+                .cmp(&b.version_number)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(versions)
+    }
+}
+
+/// `auth_v2` becomes `auth`; names without a numeric `_vN` suffix are kept.
+fn strip_version_suffix(name: &str) -> &str {
+    match name.rsplit_once("_v") {
+        Some((base, number))
+            if !base.is_empty()
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::sessions::service::SessionCreationParams;
+    use crate::infrastructure::database::Database;
+    use serial_test::serial;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn commit_file(worktree: &Path, file: &str, content: &str) {
+        std::fs::write(worktree.join(file), content).unwrap();
+        git(worktree, &["add", file]);
+        git(worktree, &["commit", "-m", &format!("update {file}")]);
+    }
+
+    fn manager_with_repo() -> (SessionManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        (SessionManager::new(db, repo), temp)
+    }
+
+    #[test]
+    fn diff_version_group_distinguishes_versions() {
+        let (manager, _temp) = manager_with_repo();
+        let first = manager
+            .create_session_with_auto_flag(
+                "cache_v1",
+                None,
+                Some("main"),
+                false,
+                Some("cache"),
+                Some(1),
+            )
+            .unwrap();
+        let second = manager
+            .create_session_with_auto_flag(
+                "cache_v2",
+                None,
+                Some("main"),
+                false,
+                Some("cache"),
+                Some(2),
+            )
+            .unwrap();
+        manager
+            .create_session("unrelated", None, Some("main"))
+            .unwrap();
+
+        commit_file(&first.worktree_path, "cache.rs", "fn get() {}\n");
+        commit_file(
+            &second.worktree_path,
+            "cache.rs",
+            "fn get() {}\nfn put() {}\n",
+        );
+        commit_file(&second.worktree_path, "eviction.rs", "fn evict() {}\n");
+
+        let diffs = manager.diff_version_group("cache").unwrap();
+        assert_eq!(diffs.len(), 2);
+        let (v1, v2) = (&diffs[0], &diffs[1]);
+
+        assert_eq!(v1.session_name, "cache_v1");
+        assert_eq!(v1.commit_count, 1);
+        assert_eq!(v1.changed_files, vec!["cache.rs"]);
+        assert_eq!((v1.stats.files_changed, v1.stats.lines_added), (1, 1));
+
+        assert_eq!(v2.session_name, "cache_v2");
+        assert_eq!(v2.commit_count, 2);
+        assert_eq!(v2.changed_files, vec!["cache.rs", "eviction.rs"]);
+        assert_eq!((v2.stats.files_changed, v2.stats.lines_added), (2, 3));
+
+        assert_eq!(
+            v1.pairs,
+            vec![VersionPairSummary {
+                other_session: "cache_v2".to_string(),
+                shared_files: vec!["cache.rs".to_string()],
+                only_this: Vec::new(),
+                only_other: vec!["eviction.rs".to_string()],
+            }]
+        );
+        assert_eq!(v2.pairs[0].only_this, vec!["eviction.rs"]);
+
+        assert!(manager.diff_version_group("missing").is_err());
+    }
+
+    fn group_params(count: usize, agent_configs: &[VersionAgentConfig]) -> VersionGroupParams<'_> {
+        VersionGroupParams {
+            base_name: "search",
+            prompt: Some("Add fuzzy search"),
+            count,
+            agent_configs,
+            base_branch: Some("main"),
+            version_group_label: Some(" Fuzzy search "),
+        }
+    }
+
+    #[test]
+    fn create_version_group_creates_distinct_versions_in_one_group() {
+        let (manager, _temp) = manager_with_repo();
+        let configs = [
+            VersionAgentConfig {
+                agent_type: Some("claude".to_string()),
+                skip_permissions: Some(true),
+            },
+            VersionAgentConfig::default(),
+            VersionAgentConfig {
+                agent_type: Some("codex".to_string()),
+                skip_permissions: None,
+            },
+        ];
+
+        let created = manager
+            .create_version_group(group_params(3, &configs))
+            .unwrap();
+
+        let names: Vec<_> = created.sessions.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["search", "search_v2", "search_v3"]);
+        let branches: BTreeSet<_> = created.sessions.iter().map(|s| &s.branch).collect();
+        let worktrees: BTreeSet<_> = created.sessions.iter().map(|s| &s.worktree_path).collect();
+        assert_eq!((branches.len(), worktrees.len()), (3, 3));
+        for (index, session) in created.sessions.iter().enumerate() {
+            assert!(session.worktree_path.join("README.md").exists());
+            assert_eq!(session.version_number, Some(index as i32 + 1));
+            assert_eq!(session.parent_branch, "main");
+        }
+
+        let stored: Vec<_> = manager
+            .list_sessions()
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.version_group_id.as_deref() == Some(created.version_group_id.as_str()))
+            .collect();
+        assert_eq!(stored.len(), 3);
+        for session in &stored {
+            assert_eq!(session.version_group_label.as_deref(), Some("Fuzzy search"));
+            assert_eq!(session.initial_prompt.as_deref(), Some("Add fuzzy search"));
+        }
+        let agents = |name: &str| {
+            let session = stored.iter().find(|s| s.name == name).unwrap();
+            (
+                session.original_agent_type.clone(),
+                session.original_skip_permissions,
+            )
+        };
+        assert_eq!(agents("search"), (Some("claude".to_string()), Some(true)));
+        assert_eq!(
+            agents("search_v3"),
+            (Some("codex".to_string()), Some(false))
+        );
+    }
+
+    #[test]
+    fn create_version_group_rejects_bad_counts_without_creating_anything() {
+        let (manager, _temp) = manager_with_repo();
+        let two_configs = [VersionAgentConfig::default(), VersionAgentConfig::default()];
+
+        assert!(manager.create_version_group(group_params(0, &[])).is_err());
+        assert!(
+            manager
+                .create_version_group(group_params(MAX_GROUP_VERSIONS + 1, &[]))
+                .is_err()
+        );
+        assert!(
+            manager
+                .create_version_group(group_params(3, &two_configs))
+                .is_err()
+        );
+        assert!(manager.list_sessions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn discarding_planned_versions_removes_sessions_worktrees_and_branches() {
+        let (manager, _temp) = manager_with_repo();
+        let created = manager.create_version_group(group_params(2, &[])).unwrap();
+        let planned: Vec<_> = created
+            .sessions
+            .iter()
+            .map(|session| PlannedVersion {
+                session_id: session.id.clone(),
+                name: session.name.clone(),
+                branch: session.branch.clone(),
+                worktree_path: session.worktree_path.clone(),
+                version_number: session.version_number.unwrap(),
+                agent_type: "claude".to_string(),
+                skip_permissions: false,
+            })
+            .collect();
+
+        manager.discard_planned_versions(&planned, &created.sessions);
+
+        assert!(manager.list_sessions().unwrap().is_empty());
+        for session in &created.sessions {
+            assert!(!session.worktree_path.exists());
+            assert!(!git::branch_exists(&manager.repo_path, &session.branch).unwrap());
+        }
+    }
+
+    #[test]
+    fn version_group_comparison_reports_diff_stats_per_version() {
+        let (manager, _temp) = manager_with_repo();
+        let created = manager.create_version_group(group_params(2, &[])).unwrap();
+        commit_file(
+            &created.sessions[1].worktree_path,
+            "search.rs",
+            "fn search() {}\nfn rank() {}\n",
+        );
+
+        let comparison = manager
+            .get_version_group_comparison(&created.version_group_id)
+            .unwrap();
+
+        let rows: Vec<_> = comparison
+            .iter()
+            .map(|entry| {
+                (
+                    entry.session_name.as_str(),
+                    entry.version_number,
+                    entry.files_changed,
+                    entry.additions,
+                    entry.deletions,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("search", Some(1), 0, 0, 0),
+                ("search_v2", Some(2), 1, 2, 0)
+            ]
+        );
+        assert!(manager.get_version_group_comparison("missing").is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn selecting_winner_cancels_siblings_and_drops_version_suffix() {
+        let (manager, _temp) = manager_with_repo();
+        let created = manager.create_version_group(group_params(3, &[])).unwrap();
+        let group_id = created.version_group_id.as_str();
+
+        assert!(
+            manager
+                .select_version_winner(group_id, "unrelated")
+                .await
+                .is_err()
+        );
+
+        let selection = manager
+            .select_version_winner(group_id, "search_v2")
+            .await
+            .unwrap();
+
+        assert_eq!(selection.cancelled, vec!["search", "search_v3"]);
+        assert_eq!(selection.winner.display_name.as_deref(), Some("search"));
+        assert!(selection.winner.branch.ends_with("/search"));
+
+        let remaining = manager.list_sessions().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, "search_v2");
+        assert_eq!(remaining[0].display_name.as_deref(), Some("search"));
+        assert_eq!(remaining[0].branch, selection.winner.branch);
+        for loser in [&created.sessions[0], &created.sessions[2]] {
+            assert!(!loser.worktree_path.exists());
+        }
+
+        let archived: BTreeSet<_> = manager
+            .list_archived_specs()
+            .unwrap()
+            .into_iter()
+            .map(|spec| (spec.session_name, spec.content))
+            .collect();
+        assert_eq!(
+            archived,
+            BTreeSet::from([
+                ("search".to_string(), "Add fuzzy search".to_string()),
+                ("search_v3".to_string(), "Add fuzzy search".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn version_suffix_is_stripped_only_when_numeric() {
+        assert_eq!(strip_version_suffix("auth_v2"), "auth");
+        assert_eq!(strip_version_suffix("auth_v12"), "auth");
+        assert_eq!(strip_version_suffix("auth"), "auth");
+        assert_eq!(strip_version_suffix("auth_vx"), "auth_vx");
+        assert_eq!(strip_version_suffix("_v2"), "_v2");
+    }
+
+    fn create_version(
+        manager: &SessionManager,
+        name: &str,
+        version_number: i32,
+        label: Option<&str>,
+    ) -> crate::domains::sessions::entity::Session {
+        manager
+            .create_session_with_agent(SessionCreationParams {
+                name,
+                prompt: None,
+                base_branch: Some("main"),
+                custom_branch: None,
+                use_existing_branch: false,
+                sync_with_origin: false,
+                was_auto_generated: false,
+                version_group_id: Some("auth"),
+                version_number: Some(version_number),
+                version_group_label: label,
+                epic_id: None,
+                agent_type: None,
+                skip_permissions: None,
+                pr_number: None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn versions_share_group_label_and_rename_updates_all() {
+        let (manager, _temp) = manager_with_repo();
+        let v1 = create_version(&manager, "auth_v1", 1, Some("Auth refactor"));
+        let v2 = create_version(&manager, "auth_v2", 2, Some("Something else"));
+        let v3 = create_version(&manager, "auth_v3", 3, None);
+        manager
+            .create_session("unrelated", None, Some("main"))
+            .unwrap();
+
+        for session in [&v1, &v2, &v3] {
+            assert_eq!(
+                session.version_group_label.as_deref(),
+                Some("Auth refactor")
+            );
+        }
+
+        manager
+            .set_version_group_label("auth", "  Auth rewrite ")
+            .unwrap();
+        let labels: Vec<_> = manager
+            .list_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.name, s.version_group_label))
+            .collect();
+        for (name, label) in labels {
+            let expected = name
+                .starts_with("auth_")
+                .then(|| "Auth rewrite".to_string());
+            assert_eq!(label, expected, "label of {name}");
+        }
+
+        assert!(manager.set_version_group_label("auth", " ").is_err());
+        assert!(manager.set_version_group_label("missing", "Label").is_err());
+    }
+}
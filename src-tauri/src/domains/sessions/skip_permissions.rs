@@ -0,0 +1,310 @@
+use crate::domains::sessions::db_journal::{JournalEntry, OperationJournalMethods, OperationKind};
+use crate::infrastructure::database::{Database, ProjectConfigMethods};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a confirmation token stays redeemable, and how long a redeemed token
+/// keeps the approval alive for the launch it was issued for.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipPermissionsPolicy {
+    #[default]
+    Allowed,
+    RequireConfirmation,
+    Forbidden,
+}
+
+impl SkipPermissionsPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipPermissionsPolicy::Allowed => "allowed",
+            SkipPermissionsPolicy::RequireConfirmation => "require-confirmation",
+            SkipPermissionsPolicy::Forbidden => "forbidden",
+        }
+    }
+
+    pub fn load(db: &Database, repo_path: &Path) -> Result<Self> {
+        match db.get_project_skip_permissions_policy(repo_path)? {
+            Some(value) => value.parse(),
+            None => Ok(SkipPermissionsPolicy::default()),
+        }
+    }
+}
+
+impl FromStr for SkipPermissionsPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "allowed" => Ok(SkipPermissionsPolicy::Allowed),
+            "require-confirmation" => Ok(SkipPermissionsPolicy::RequireConfirmation),
+            "forbidden" => Ok(SkipPermissionsPolicy::Forbidden),
+            other => Err(anyhow!("Unknown skip-permissions policy: {other}")),
+        }
+    }
+}
+
+/// Why a launch with skipped permissions did not go ahead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipPermissionsError {
+    NeedsConfirmation {
+        session_name: String,
+        agent_type: String,
+        token: String,
+    },
+    Forbidden {
+        session_name: String,
+        agent_type: String,
+    },
+}
+
+impl fmt::Display for SkipPermissionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipPermissionsError::NeedsConfirmation {
+                session_name,
+                agent_type,
+                ..
+            } => write!(
+                f,
+                "Starting {agent_type} in session '{session_name}' with skipped permissions needs confirmation"
+            ),
+            SkipPermissionsError::Forbidden {
+                session_name,
+                agent_type,
+            } => write!(
+                f,
+                "This project forbids starting {agent_type} in session '{session_name}' with skipped permissions"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SkipPermissionsError {}
+
+type SessionKey = (PathBuf, String);
+
+struct PendingConfirmation {
+    key: SessionKey,
+    issued_at: Instant,
+}
+
+#[derive(Default)]
+struct GateState {
+    pending: HashMap<String, PendingConfirmation>,
+    approved: HashMap<SessionKey, Instant>,
+    running_skipped: HashSet<SessionKey>,
+}
+
+static GATE: LazyLock<Mutex<GateState>> = LazyLock::new(|| Mutex::new(GateState::default()));
+
+fn gate() -> std::sync::MutexGuard<'static, GateState> {
+    GATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn session_key(repo_path: &Path, session_name: &str) -> SessionKey {
+    (repo_path.to_path_buf(), session_name.to_string())
+}
+
+fn issue_confirmation(repo_path: &Path, session_name: &str) -> String {
+    let token = uuid::Uuid::new_v4().to_string();
+    let mut state = gate();
+    state
+        .pending
+        .retain(|_, pending| pending.issued_at.elapsed() < CONFIRMATION_TTL);
+    state.pending.insert(
+        token.clone(),
+        PendingConfirmation {
+            key: session_key(repo_path, session_name),
+            issued_at: Instant::now(),
+        },
+    );
+    token
+}
+
+fn take_approval(repo_path: &Path, session_name: &str) -> bool {
+    gate()
+        .approved
+        .remove(&session_key(repo_path, session_name))
+        .is_some_and(|approved_at| approved_at.elapsed() < CONFIRMATION_TTL)
+}
+
+/// Redeems a one-time confirmation token and returns the session it approves.
+/// The approval covers the next launch of that session only.
+pub fn confirm_skip_permissions(token: &str) -> Result<String> {
+    let mut state = gate();
+    let pending = state
+        .pending
+        .remove(token)
+        .filter(|pending| pending.issued_at.elapsed() < CONFIRMATION_TTL)
+        .ok_or_else(|| anyhow!("Confirmation token is unknown or expired"))?;
+    let session_name = pending.key.1.clone();
+    state.approved.insert(pending.key, Instant::now());
+    Ok(session_name)
+}
+
+pub fn is_running_with_skipped_permissions(repo_path: &Path, session_name: &str) -> bool {
+    gate()
+        .running_skipped
+        .contains(&session_key(repo_path, session_name))
+}
+
+fn record_launch_permissions(repo_path: &Path, session_name: &str, skipped: bool) {
+    let key = session_key(repo_path, session_name);
+    let mut state = gate();
+    if skipped {
+        state.running_skipped.insert(key);
+    } else {
+        state.running_skipped.remove(&key);
+    }
+}
+
+fn current_actor() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|user| !user.is_empty())
+}
+
+fn audit_skipped_launch(
+    db: &Database,
+    repo_path: &Path,
+    session_id: &str,
+    session_name: &str,
+    agent_type: &str,
+) {
+    let mut entry = JournalEntry::new(repo_path, OperationKind::SkipPermissionsLaunch, session_name);
+    entry.session_id = Some(session_id.to_string());
+    entry.actor = current_actor();
+    entry.agent_type = Some(agent_type.to_string());
+    if let Err(e) = db.record_audit_entry(&entry) {
+        log::warn!("Failed to audit skip-permissions launch of session '{session_name}': {e}");
+    }
+}
+
+/// Applies the project's skip-permissions policy to a launch. Allowed launches with
+/// skipped permissions are written to the operation journal before they proceed.
+pub fn authorize_launch(
+    db: &Database,
+    repo_path: &Path,
+    session_id: &str,
+    session_name: &str,
+    agent_type: &str,
+    skip_permissions: bool,
+) -> Result<()> {
+    if !skip_permissions {
+        record_launch_permissions(repo_path, session_name, false);
+        return Ok(());
+    }
+
+    match SkipPermissionsPolicy::load(db, repo_path)? {
+        SkipPermissionsPolicy::Allowed => {}
+        SkipPermissionsPolicy::Forbidden => {
+            return Err(SkipPermissionsError::Forbidden {
+                session_name: session_name.to_string(),
+                agent_type: agent_type.to_string(),
+            }
+            .into());
+        }
+        SkipPermissionsPolicy::RequireConfirmation => {
+            if !take_approval(repo_path, session_name) {
+                return Err(SkipPermissionsError::NeedsConfirmation {
+                    session_name: session_name.to_string(),
+                    agent_type: agent_type.to_string(),
+                    token: issue_confirmation(repo_path, session_name),
+                }
+                .into());
+            }
+        }
+    }
+
+    audit_skipped_launch(db, repo_path, session_id, session_name, agent_type);
+    record_launch_permissions(repo_path, session_name, true);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Database, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(Some(temp.path().join("db.sqlite"))).unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        (temp, db, repo)
+    }
+
+    fn audits(db: &Database, repo: &Path) -> Vec<JournalEntry> {
+        db.list_operations_of_kind(repo, OperationKind::SkipPermissionsLaunch)
+            .unwrap()
+    }
+
+    #[test]
+    fn require_confirmation_allows_exactly_one_launch_per_token() {
+        let (_temp, db, repo) = setup();
+        db.set_project_skip_permissions_policy(&repo, "require-confirmation")
+            .unwrap();
+
+        let err = authorize_launch(&db, &repo, "id-1", "alpha", "claude", true).unwrap_err();
+        let token = match err.downcast_ref::<SkipPermissionsError>() {
+            Some(SkipPermissionsError::NeedsConfirmation { token, .. }) => token.clone(),
+            other => panic!("expected confirmation request, got {other:?}"),
+        };
+        assert!(audits(&db, &repo).is_empty());
+        assert!(!is_running_with_skipped_permissions(&repo, "alpha"));
+
+        assert_eq!(confirm_skip_permissions(&token).unwrap(), "alpha");
+        assert!(confirm_skip_permissions(&token).is_err());
+
+        authorize_launch(&db, &repo, "id-1", "alpha", "claude", true).unwrap();
+        assert!(is_running_with_skipped_permissions(&repo, "alpha"));
+        let entries = audits(&db, &repo);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].session_id.as_deref(), Some("id-1"));
+        assert_eq!(entries[0].agent_type.as_deref(), Some("claude"));
+
+        let again = authorize_launch(&db, &repo, "id-1", "alpha", "claude", true).unwrap_err();
+        assert!(matches!(
+            again.downcast_ref::<SkipPermissionsError>(),
+            Some(SkipPermissionsError::NeedsConfirmation { .. })
+        ));
+    }
+
+    #[test]
+    fn forbidden_blocks_and_safe_launches_skip_the_gate() {
+        let (_temp, db, repo) = setup();
+        db.set_project_skip_permissions_policy(&repo, "forbidden")
+            .unwrap();
+
+        let err = authorize_launch(&db, &repo, "id-2", "beta", "codex", true).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SkipPermissionsError>(),
+            Some(SkipPermissionsError::Forbidden { .. })
+        ));
+
+        authorize_launch(&db, &repo, "id-2", "beta", "codex", false).unwrap();
+        assert!(!is_running_with_skipped_permissions(&repo, "beta"));
+        assert!(audits(&db, &repo).is_empty());
+    }
+
+    #[test]
+    fn allowed_policy_audits_every_skipped_launch() {
+        let (_temp, db, repo) = setup();
+
+        authorize_launch(&db, &repo, "id-3", "gamma", "claude", true).unwrap();
+        authorize_launch(&db, &repo, "id-3", "gamma", "claude", true).unwrap();
+
+        assert_eq!(audits(&db, &repo).len(), 2);
+        assert!(is_running_with_skipped_permissions(&repo, "gamma"));
+    }
+}
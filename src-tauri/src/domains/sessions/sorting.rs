@@ -1,12 +1,21 @@
 #[cfg(test)]
 mod session_sorting_tests {
     use crate::{
+        domains::sessions::cache::SessionCacheManager,
         domains::sessions::db_sessions::SessionMethods,
-        domains::sessions::entity::{FilterMode, Session, SessionState, SessionStatus, SortMode},
+        domains::sessions::entity::{
+            DiffStats, EnrichedSession, FilterMode, Session, SessionInfo, SessionState,
+            SessionStatus, SessionStatusType, SessionType, SortMode,
+        },
+        domains::sessions::repository::SessionDbManager,
         domains::sessions::service::SessionManager,
+        domains::sessions::utils::SessionUtils,
+        domains::terminal::TerminalLiveness,
         infrastructure::database::{Database, initialize_schema},
+        shared::terminal_id::{terminal_id_for_session_bottom, terminal_id_for_session_top},
     };
     use chrono::{Duration, Utc};
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -26,6 +35,7 @@ mod session_sorting_tests {
             display_name: Some(format!("Display {}", name)),
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.clone(),
             repository_name: "test-repo".to_string(),
@@ -54,6 +64,7 @@ mod session_sorting_tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         }
     }
 
@@ -332,4 +343,216 @@ mod session_sorting_tests {
             .collect();
         assert_eq!(names, vec!["spec-beta", "spec-alpha"]);
     }
+
+    fn live_terminal(minutes_ago: i64) -> TerminalLiveness {
+        TerminalLiveness {
+            suspended: false,
+            last_activity: Utc::now() - Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sort_by_terminal_activity_prefers_most_recent_source() {
+        let (_temp_dir, manager, _sessions) = setup_test_sessions();
+
+        // echo's agent just printed output; delta's shell output predates its stored activity
+        let live = HashMap::from([
+            (terminal_id_for_session_top("running-echo"), live_terminal(1)),
+            (terminal_id_for_session_bottom("running-delta"), live_terminal(30)),
+        ]);
+
+        let sorted_sessions = manager
+            .list_enriched_sessions_sorted_with_terminals(
+                "terminal-activity".parse().unwrap(),
+                FilterMode::Running,
+                &live,
+            )
+            .unwrap();
+
+        let session_names: Vec<&str> = sorted_sessions
+            .iter()
+            .map(|s| s.info.session_id.as_str())
+            .collect();
+        assert_eq!(
+            session_names,
+            vec!["running-echo", "running-charlie", "running-delta"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sort_by_terminal_activity_without_live_data() {
+        let (_temp_dir, manager, _sessions) = setup_test_sessions();
+
+        let sorted_sessions = manager
+            .list_enriched_sessions_sorted_with_terminals(
+                SortMode::TerminalActivity,
+                FilterMode::Running,
+                &HashMap::new(),
+            )
+            .unwrap();
+
+        // Falls back to the stored last activity
+        let session_names: Vec<&str> = sorted_sessions
+            .iter()
+            .map(|s| s.info.session_id.as_str())
+            .collect();
+        assert_eq!(
+            session_names,
+            vec!["running-charlie", "running-delta", "running-echo"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_group() {
+        let (_temp_dir, manager, _sessions) = setup_test_sessions();
+        manager
+            .set_session_group("running-echo", Some("api"))
+            .unwrap();
+        manager
+            .set_session_group("reviewed-golf", Some(" api "))
+            .unwrap();
+        manager
+            .set_session_group("running-charlie", Some("ui"))
+            .unwrap();
+
+        let filtered_sessions = manager
+            .list_enriched_sessions_sorted(SortMode::Name, "group:api".parse().unwrap())
+            .unwrap();
+
+        // Group filter spans states; reviewed sessions still sort last
+        let session_names: Vec<&str> = filtered_sessions
+            .iter()
+            .map(|s| s.info.session_id.as_str())
+            .collect();
+        assert_eq!(session_names, vec!["running-echo", "reviewed-golf"]);
+        assert_eq!(
+            manager.list_session_groups().unwrap(),
+            vec!["api".to_string(), "ui".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sort_by_group_keeps_groups_together() {
+        let (_temp_dir, manager, _sessions) = setup_test_sessions();
+        manager
+            .set_session_group("running-echo", Some("api"))
+            .unwrap();
+        manager
+            .set_session_group("running-charlie", Some("ui"))
+            .unwrap();
+        manager
+            .set_session_group("running-delta", Some("api"))
+            .unwrap();
+        manager.set_session_group("running-delta", None).unwrap();
+
+        let sorted_sessions = manager
+            .list_enriched_sessions_sorted(SortMode::Group, FilterMode::Running)
+            .unwrap();
+
+        // Grouped sessions first by group name, ungrouped ones last
+        let session_names: Vec<&str> = sorted_sessions
+            .iter()
+            .map(|s| s.info.session_id.as_str())
+            .collect();
+        assert_eq!(
+            session_names,
+            vec!["running-echo", "running-charlie", "running-delta"]
+        );
+        assert_eq!(sorted_sessions[2].info.group, None);
+    }
+
+    fn enriched_with_diff(
+        name: &str,
+        state: SessionState,
+        diff_size: Option<(usize, usize)>,
+        last_modified_minutes_ago: i64,
+    ) -> EnrichedSession {
+        EnrichedSession {
+            info: SessionInfo {
+                session_id: name.to_string(),
+                display_name: None,
+                version_group_id: None,
+                version_number: None,
+                version_group_label: None,
+                epic: None,
+                branch: format!("schaltwerk/{name}"),
+                worktree_path: format!("/tmp/{name}"),
+                base_branch: "main".to_string(),
+                original_base_branch: Some("main".to_string()),
+                status: SessionStatusType::Active,
+                created_at: Some(Utc::now() - Duration::hours(1)),
+                last_modified: Some(Utc::now() - Duration::minutes(last_modified_minutes_ago)),
+                has_uncommitted_changes: Some(diff_size.is_some()),
+                has_conflicts: Some(false),
+                is_current: false,
+                session_type: SessionType::Worktree,
+                container_status: None,
+                original_agent_type: None,
+                current_task: None,
+                diff_stats: diff_size.map(|(additions, deletions)| DiffStats {
+                    files_changed: 1,
+                    additions,
+                    deletions,
+                    insertions: additions,
+                    has_remote: false,
+                    remote_ahead: None,
+                    remote_behind: None,
+                }),
+                ready_to_merge: false,
+                spec_content: None,
+                session_state: state,
+                pr_number: None,
+                pr_url: None,
+                group: None,
+                skip_permissions_active: false,
+                has_stashed_changes: false,
+                setup_failed: false,
+                setup_running: false,
+                branch_missing: false,
+                repo_label: None,
+                depends_on_session: None,
+            },
+            status: None,
+            terminals: vec![],
+            terminal_details: vec![],
+            attention_required: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_by_diff_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let utils = SessionUtils::new(
+            repo_path.clone(),
+            SessionCacheManager::new(repo_path.clone()),
+            SessionDbManager::new(db, repo_path),
+        );
+
+        let sessions = vec![
+            enriched_with_diff("spec-large", SessionState::Spec, Some((900, 0)), 1),
+            enriched_with_diff("no-stats", SessionState::Running, None, 1),
+            enriched_with_diff("small", SessionState::Running, Some((3, 2)), 1),
+            enriched_with_diff("large", SessionState::Running, Some((120, 80)), 30),
+            enriched_with_diff("tie-older", SessionState::Running, Some((10, 10)), 20),
+            enriched_with_diff("tie-newer", SessionState::Running, Some((15, 5)), 5),
+        ];
+
+        let sorted = utils.apply_session_sort(sessions, &"diff-size".parse().unwrap());
+        let names: Vec<&str> = sorted.iter().map(|s| s.info.session_id.as_str()).collect();
+
+        // Biggest diffs first, equal diffs by most recent edit, specs and missing stats last
+        assert_eq!(
+            names,
+            vec![
+                "large",
+                "tie-newer",
+                "tie-older",
+                "small",
+                "no-stats",
+                "spec-large"
+            ]
+        );
+    }
 }
@@ -0,0 +1,124 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn is_valid_placeholder(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse(content: &str) -> Result<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find(OPEN) {
+        segments.push(Segment::Text(&rest[..start]));
+        let after_open = &rest[start + OPEN.len()..];
+        let end = after_open
+            .find(CLOSE)
+            .ok_or_else(|| anyhow!("Unclosed placeholder in template: {{{{{after_open}"))?;
+        let name = after_open[..end].trim();
+        if !is_valid_placeholder(name) {
+            return Err(anyhow!(
+                "Invalid placeholder '{{{{{}}}}}': use letters, numbers, and underscores",
+                &after_open[..end]
+            ));
+        }
+        segments.push(Segment::Placeholder(name));
+        rest = &after_open[end + CLOSE.len()..];
+    }
+    segments.push(Segment::Text(rest));
+
+    Ok(segments)
+}
+
+/// Checks that every placeholder in `content` is well formed.
+pub fn validate_prompt_template(content: &str) -> Result<()> {
+    parse(content).map(|_| ())
+}
+
+/// Replaces each `{{name}}` in `content` with the value from `vars`, falling back to
+/// `defaults`. A placeholder found in neither map is an error.
+pub fn render_prompt_template(
+    content: &str,
+    vars: &HashMap<String, String>,
+    defaults: &HashMap<String, String>,
+) -> Result<String> {
+    let mut rendered = String::with_capacity(content.len());
+    let mut missing = Vec::new();
+
+    for segment in parse(content)? {
+        match segment {
+            Segment::Text(text) => rendered.push_str(text),
+            Segment::Placeholder(name) => match vars.get(name).or_else(|| defaults.get(name)) {
+                Some(value) => rendered.push_str(value),
+                None if !missing.contains(&name) => missing.push(name),
+                None => {}
+            },
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "No value for template placeholder(s): {}",
+            missing.join(", ")
+        ));
+    }
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_vars_and_falls_back_to_defaults() {
+        let rendered = render_prompt_template(
+            "Work on {{branch}} from {{ base_branch }} ({{date}}): {{task}}",
+            &map(&[
+                ("branch", "schaltwerk/alpha"),
+                ("base_branch", "main"),
+                ("date", "2026-10-17"),
+            ]),
+            &map(&[("task", "fix the build"), ("branch", "ignored")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "Work on schaltwerk/alpha from main (2026-10-17): fix the build"
+        );
+    }
+
+    #[test]
+    fn missing_variables_are_reported() {
+        let err = render_prompt_template(
+            "{{ticket}} {{owner}} {{ticket}}",
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No value for template placeholder(s): ticket, owner"
+        );
+
+        assert!(validate_prompt_template("Broken {{ticket").is_err());
+        assert!(validate_prompt_template("Bad {{two words}}").is_err());
+        assert!(validate_prompt_template("Plain text").is_ok());
+    }
+}
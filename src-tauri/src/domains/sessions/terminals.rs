@@ -0,0 +1,157 @@
+use crate::domains::sessions::entity::{EnrichedSession, SessionTerminal, SessionTerminalKind};
+use crate::domains::terminal::TerminalLiveness;
+use crate::shared::terminal_id::{
+    session_terminal_base, terminal_id_for_session_bottom, terminal_id_for_session_top,
+};
+use std::collections::{BTreeSet, HashMap};
+
+fn run_terminal_id(session_name: &str) -> String {
+    format!("run-terminal-{session_name}")
+}
+
+fn classify(session_name: &str, id: &str) -> SessionTerminalKind {
+    if id == terminal_id_for_session_top(session_name) {
+        SessionTerminalKind::Agent
+    } else if id == terminal_id_for_session_bottom(session_name) {
+        SessionTerminalKind::Shell
+    } else if id == run_terminal_id(session_name) {
+        SessionTerminalKind::Run
+    } else {
+        SessionTerminalKind::Extra
+    }
+}
+
+// Splits a trailing `-N` tab index so `-top-10` sorts after `-top-2`.
+fn sort_key(terminal: &SessionTerminal) -> (SessionTerminalKind, &str, u32) {
+    match terminal.id.rsplit_once('-') {
+        Some((stem, index)) if index.chars().all(|c| c.is_ascii_digit()) => {
+            (terminal.kind, stem, index.parse().unwrap_or(u32::MAX))
+        }
+        _ => (terminal.kind, terminal.id.as_str(), 0),
+    }
+}
+
+/// Describes a session's terminals: the `canonical` ids whether or not they are open,
+/// plus every open terminal under the session's id prefix or its run terminal id.
+/// Ordered agent, shell, run, then extra tabs by index.
+pub fn describe_session_terminals(
+    session_name: &str,
+    canonical: &[String],
+    live: &HashMap<String, TerminalLiveness>,
+) -> Vec<SessionTerminal> {
+    let prefix = format!("{}-", session_terminal_base(session_name));
+    let run_id = run_terminal_id(session_name);
+
+    let mut ids: BTreeSet<&str> = canonical.iter().map(String::as_str).collect();
+    ids.extend(
+        live.keys()
+            .map(String::as_str)
+            .filter(|id| id.starts_with(&prefix) || *id == run_id),
+    );
+
+    let mut terminals: Vec<SessionTerminal> = ids
+        .into_iter()
+        .map(|id| {
+            let liveness = live.get(id);
+            SessionTerminal {
+                id: id.to_string(),
+                kind: classify(session_name, id),
+                exists: liveness.is_some(),
+                suspended: liveness.is_some_and(|l| l.suspended),
+                last_activity: liveness.map(|l| l.last_activity),
+            }
+        })
+        .collect();
+    terminals.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+    terminals
+}
+
+/// Fills `terminal_details` on every session from a single liveness snapshot.
+pub fn attach_terminal_details(
+    sessions: &mut [EnrichedSession],
+    live: &HashMap<String, TerminalLiveness>,
+) {
+    for session in sessions {
+        session.terminal_details =
+            describe_session_terminals(&session.info.session_id, &session.terminals, live);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn live(ids: &[String]) -> HashMap<String, TerminalLiveness> {
+        ids.iter()
+            .map(|id| {
+                (
+                    id.clone(),
+                    TerminalLiveness {
+                        suspended: false,
+                        last_activity: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn canonical(name: &str) -> Vec<String> {
+        vec![
+            terminal_id_for_session_top(name),
+            terminal_id_for_session_bottom(name),
+        ]
+    }
+
+    #[test]
+    fn spec_without_terminals_has_no_details() {
+        let other = canonical("other");
+        let details = describe_session_terminals("draft", &[], &live(&other));
+        assert!(details.is_empty());
+    }
+
+    #[test]
+    fn canonical_pair_reports_existence() {
+        let ids = canonical("alpha");
+        let details = describe_session_terminals("alpha", &ids, &live(&ids[..1]));
+
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].id, ids[0]);
+        assert_eq!(details[0].kind, SessionTerminalKind::Agent);
+        assert!(details[0].exists);
+        assert!(details[0].last_activity.is_some());
+        assert_eq!(details[1].id, ids[1]);
+        assert_eq!(details[1].kind, SessionTerminalKind::Shell);
+        assert!(!details[1].exists);
+        assert_eq!(details[1].last_activity, None);
+    }
+
+    #[test]
+    fn extra_and_run_terminals_are_ordered_and_deduplicated() {
+        let ids = canonical("alpha");
+        let top = &ids[0];
+        let open = vec![
+            format!("{top}-10"),
+            ids[1].clone(),
+            "run-terminal-alpha".to_string(),
+            format!("{top}-2"),
+            ids[0].clone(),
+            terminal_id_for_session_top("beta"),
+        ];
+        let details = describe_session_terminals("alpha", &ids, &live(&open));
+
+        let order: Vec<(&str, SessionTerminalKind)> =
+            details.iter().map(|t| (t.id.as_str(), t.kind)).collect();
+        assert_eq!(
+            order,
+            vec![
+                (ids[0].as_str(), SessionTerminalKind::Agent),
+                (ids[1].as_str(), SessionTerminalKind::Shell),
+                ("run-terminal-alpha", SessionTerminalKind::Run),
+                (open[3].as_str(), SessionTerminalKind::Extra),
+                (open[0].as_str(), SessionTerminalKind::Extra),
+            ]
+        );
+        assert!(details.iter().all(|t| t.exists));
+    }
+}
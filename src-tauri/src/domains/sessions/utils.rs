@@ -1,18 +1,50 @@
 use crate::{
     domains::git::service as git,
     domains::sessions::cache::SessionCacheManager,
-    domains::sessions::entity::{EnrichedSession, FilterMode, SessionState, SortMode},
+    domains::sessions::entity::{
+        EnrichedSession, FilterMode, SessionState, SessionStatus, SortMode, WorktreeClass,
+        WorktreeClassification,
+    },
     domains::sessions::repository::SessionDbManager,
     domains::terminal::{build_login_shell_invocation, sh_quote_string},
-    infrastructure::database::{DEFAULT_BRANCH_PREFIX, ProjectConfigMethods},
+    infrastructure::database::{
+        BranchCollisionStrategy, DEFAULT_BRANCH_PREFIX, ProjectConfigMethods,
+    },
     shared::format_branch_name,
 };
 use anyhow::{Result, anyhow};
-use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+pub fn default_worktree_base_dir(repo_path: &Path) -> PathBuf {
+    repo_path.join(".schaltwerk").join("worktrees")
+}
+
+/// Creates the worktree directory if needed and checks that files can be written
+/// there, so a bad `worktree_root` fails before git starts creating a worktree.
+pub fn ensure_worktree_base_writable(base_dir: &Path) -> Result<()> {
+    fs::create_dir_all(base_dir).map_err(|e| {
+        anyhow!(
+            "Worktree directory {} cannot be created: {e}",
+            base_dir.display()
+        )
+    })?;
+    let probe = base_dir.join(format!(".schaltwerk-write-check-{}", Uuid::new_v4()));
+    fs::write(&probe, b"").map_err(|e| {
+        anyhow!(
+            "Worktree directory {} is not writable: {e}",
+            base_dir.display()
+        )
+    })?;
+    if let Err(e) = fs::remove_file(&probe) {
+        log::warn!("Failed to remove write check file {}: {e}", probe.display());
+    }
+    Ok(())
+}
+
 pub struct SessionUtils {
     repo_path: PathBuf,
     cache_manager: SessionCacheManager,
@@ -20,7 +52,7 @@ pub struct SessionUtils {
 }
 
 impl SessionUtils {
-    fn branch_prefix(&self) -> String {
+    pub fn branch_prefix(&self) -> String {
         self.db_manager
             .db
             .get_project_branch_prefix(&self.repo_path)
@@ -30,13 +62,85 @@ impl SessionUtils {
             })
     }
 
+    pub fn branch_collision_strategy(&self) -> BranchCollisionStrategy {
+        self.db_manager
+            .db
+            .get_project_branch_collision_strategy(&self.repo_path)
+            .unwrap_or_else(|err| {
+                log::warn!("Falling back to default branch collision strategy: {err}");
+                BranchCollisionStrategy::default()
+            })
+    }
+
+    /// Returns `branch` if it is free, otherwise the name picked by the project's
+    /// [`BranchCollisionStrategy`].
+    pub fn resolve_branch_collision(&self, branch: &str) -> Result<String> {
+        if !git::branch_exists(&self.repo_path, branch)? {
+            return Ok(branch.to_string());
+        }
+
+        match self.branch_collision_strategy() {
+            BranchCollisionStrategy::Suffix => {
+                let suffix = Self::generate_random_suffix(2);
+                Ok(format!("{branch}-{suffix}"))
+            }
+            BranchCollisionStrategy::Increment => {
+                for n in 2..=100 {
+                    let candidate = format!("{branch}-{n}");
+                    if !git::branch_exists(&self.repo_path, &candidate)? {
+                        return Ok(candidate);
+                    }
+                }
+                Err(anyhow!("No free branch name derived from '{branch}'"))
+            }
+            BranchCollisionStrategy::Error => Err(anyhow!("Branch '{branch}' already exists")),
+        }
+    }
+
+    /// Directory holding this project's session worktrees: the project's configured
+    /// `worktree_root`, or `.schaltwerk/worktrees` inside the repository.
+    pub fn worktree_base_dir(&self) -> PathBuf {
+        match self
+            .db_manager
+            .db
+            .get_project_worktree_root(&self.repo_path)
+        {
+            Ok(Some(root)) => root,
+            Ok(None) => default_worktree_base_dir(&self.repo_path),
+            Err(err) => {
+                log::warn!("Falling back to default worktree directory due to error: {err}");
+                default_worktree_base_dir(&self.repo_path)
+            }
+        }
+    }
+
+    pub fn worktree_path_for(&self, name: &str) -> PathBuf {
+        self.worktree_base_dir().join(name)
+    }
+
+    /// Fails when the branch or worktree path `name` would use is already taken, so a
+    /// rename can be rejected before anything is changed.
+    pub fn ensure_rename_target_free(&self, name: &str) -> Result<()> {
+        let branch = format_branch_name(&self.branch_prefix(), name);
+        if git::branch_exists(&self.repo_path, &branch)? {
+            return Err(anyhow!(
+                "Cannot rename to '{name}': branch '{branch}' already exists"
+            ));
+        }
+
+        let worktree_path = self.worktree_path_for(name);
+        if worktree_path.exists() {
+            return Err(anyhow!(
+                "Cannot rename to '{name}': worktree path '{}' already exists",
+                worktree_path.display()
+            ));
+        }
+        Ok(())
+    }
+
     fn check_name_availability_with_prefix(&self, name: &str, branch_prefix: &str) -> Result<bool> {
         let branch = format_branch_name(branch_prefix, name);
-        let worktree_path = self
-            .repo_path
-            .join(".schaltwerk")
-            .join("worktrees")
-            .join(name);
+        let worktree_path = self.worktree_path_for(name);
 
         let worktree_exists = worktree_path.exists();
         let session_exists = self.db_manager.session_exists(name);
@@ -88,11 +192,7 @@ impl SessionUtils {
 
         if self.check_name_availability_with_prefix(base_name, &branch_prefix)? {
             let branch = format_branch_name(&branch_prefix, base_name);
-            let worktree_path = self
-                .repo_path
-                .join(".schaltwerk")
-                .join("worktrees")
-                .join(base_name);
+            let worktree_path = self.worktree_path_for(base_name);
 
             self.cache_manager.reserve_name(base_name);
             return Ok((base_name.to_string(), branch, worktree_path));
@@ -104,11 +204,7 @@ impl SessionUtils {
 
             if self.check_name_availability_with_prefix(&candidate, &branch_prefix)? {
                 let branch = format_branch_name(&branch_prefix, &candidate);
-                let worktree_path = self
-                    .repo_path
-                    .join(".schaltwerk")
-                    .join("worktrees")
-                    .join(&candidate);
+                let worktree_path = self.worktree_path_for(&candidate);
 
                 self.cache_manager.reserve_name(&candidate);
                 return Ok((candidate, branch, worktree_path));
@@ -120,11 +216,7 @@ impl SessionUtils {
 
             if self.check_name_availability_with_prefix(&candidate, &branch_prefix)? {
                 let branch = format_branch_name(&branch_prefix, &candidate);
-                let worktree_path = self
-                    .repo_path
-                    .join(".schaltwerk")
-                    .join("worktrees")
-                    .join(&candidate);
+                let worktree_path = self.worktree_path_for(&candidate);
 
                 self.cache_manager.reserve_name(&candidate);
                 return Ok((candidate, branch, worktree_path));
@@ -169,58 +261,109 @@ impl SessionUtils {
         Ok(())
     }
 
-    pub fn cleanup_orphaned_worktrees(&self) -> Result<()> {
+    /// Lists every worktree in the project's worktree directory and says whether a live
+    /// session still owns it.
+    pub fn classify_worktrees(&self) -> Result<Vec<WorktreeClassification>> {
         let worktrees = git::list_worktrees(&self.repo_path)?;
         let sessions = self.db_manager.list_sessions()?;
-        // IMPORTANT: Only check against sessions that should have worktrees.
-        // Spec sessions should NOT have worktree directories, so we exclude them.
-        let sessions_with_worktrees: Vec<_> = sessions
+        // Canonicalize paths to handle symlinks (like /var -> /private/var on macOS)
+        let sessions_by_worktree: HashMap<PathBuf, _> = sessions
             .into_iter()
-            .filter(|s| s.session_state != SessionState::Spec)
-            .collect();
-        let canonical_session_worktrees: HashSet<PathBuf> = sessions_with_worktrees
-            .iter()
             .map(|s| {
-                s.worktree_path
+                let canonical = s
+                    .worktree_path
                     .canonicalize()
-                    .unwrap_or_else(|_| s.worktree_path.clone())
+                    .unwrap_or_else(|_| s.worktree_path.clone());
+                (canonical, s)
             })
             .collect();
 
+        let base_dir = self.worktree_base_dir();
+        let base_dir = base_dir.canonicalize().unwrap_or(base_dir);
+
+        let mut classified = Vec::new();
         for worktree_path in worktrees {
+            let canonical_worktree = worktree_path
+                .canonicalize()
+                .unwrap_or_else(|_| worktree_path.clone());
             if !worktree_path
                 .to_string_lossy()
                 .contains("/.schaltwerk/worktrees/")
+                && !canonical_worktree.starts_with(&base_dir)
             {
                 continue;
             }
 
-            // Canonicalize paths to handle symlinks (like /var -> /private/var on macOS)
-            let canonical_worktree = worktree_path
-                .canonicalize()
-                .unwrap_or_else(|_| worktree_path.clone());
+            let (class, session_name) = match sessions_by_worktree.get(&canonical_worktree) {
+                None => (WorktreeClass::Orphaned, None),
+                // Spec sessions should NOT have worktree directories.
+                Some(session)
+                    if session.status == SessionStatus::Cancelled
+                        || session.session_state == SessionState::Spec =>
+                {
+                    (WorktreeClass::Stale, Some(session.name.clone()))
+                }
+                Some(session) => (WorktreeClass::Tracked, Some(session.name.clone())),
+            };
+
+            classified.push(WorktreeClassification {
+                path: worktree_path,
+                class,
+                session_name,
+            });
+        }
 
-            let exists = canonical_session_worktrees.contains(&canonical_worktree);
+        Ok(classified)
+    }
 
-            if !exists {
-                log::info!(
-                    "Removing orphaned worktree: {} (no matching non-spec session found)",
+    /// Removes the given worktrees. Paths that are not orphaned or stale when the
+    /// call runs are skipped, so a preview that went out of date cannot delete
+    /// a live session's worktree. Returns the paths that were removed.
+    pub fn cleanup_orphaned_worktrees(&self, paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let prunable: HashMap<PathBuf, PathBuf> = self
+            .classify_worktrees()?
+            .into_iter()
+            .filter(|entry| entry.class != WorktreeClass::Tracked)
+            .map(|entry| {
+                let canonical = entry
+                    .path
+                    .canonicalize()
+                    .unwrap_or_else(|_| entry.path.clone());
+                (canonical, entry.path)
+            })
+            .collect();
+
+        let mut removed = Vec::new();
+        for requested in paths {
+            let canonical = requested
+                .canonicalize()
+                .unwrap_or_else(|_| requested.clone());
+            let Some(worktree_path) = prunable.get(&canonical) else {
+                log::warn!(
+                    "Skipping worktree cleanup for {}: not an orphaned or stale worktree",
+                    requested.display()
+                );
+                continue;
+            };
+
+            log::info!(
+                "Removing orphaned worktree: {} (no matching live session found)",
+                worktree_path.display()
+            );
+            let _ = git::remove_worktree(&self.repo_path, worktree_path);
+            if worktree_path.exists() {
+                log::debug!(
+                    "Forcefully removing worktree directory: {}",
                     worktree_path.display()
                 );
-                let _ = git::remove_worktree(&self.repo_path, &worktree_path);
-                if worktree_path.exists() {
-                    log::debug!(
-                        "Forcefully removing worktree directory: {}",
-                        worktree_path.display()
-                    );
-                    self.fast_remove_dir_in_background(&worktree_path);
-                }
+                self.fast_remove_dir_in_background(worktree_path);
             }
+            removed.push(worktree_path.clone());
         }
 
         self.cleanup_trash_directory()?;
 
-        Ok(())
+        Ok(removed)
     }
 
     fn fast_remove_dir_in_background(&self, path: &Path) {
@@ -285,7 +428,7 @@ impl SessionUtils {
     }
 
     fn cleanup_trash_directory(&self) -> Result<()> {
-        let worktrees_dir = self.repo_path.join(".schaltwerk/worktrees");
+        let worktrees_dir = self.worktree_base_dir();
         let trash_dir = worktrees_dir.join(".schaltwerk-trash");
 
         if !trash_dir.exists() {
@@ -429,6 +572,10 @@ impl SessionUtils {
                 .into_iter()
                 .filter(|s| s.info.ready_to_merge)
                 .collect(),
+            FilterMode::Group(group) => sessions
+                .into_iter()
+                .filter(|s| s.info.group.as_deref() == Some(group.as_str()))
+                .collect(),
         }
     }
 
@@ -437,6 +584,13 @@ impl SessionUtils {
         sessions: Vec<EnrichedSession>,
         sort_mode: &SortMode,
     ) -> Vec<EnrichedSession> {
+        // Grouping overrides the reviewed-last split so a group never gets torn apart.
+        if matches!(sort_mode, SortMode::Group) {
+            let mut sessions = sessions;
+            self.sort_sessions_by_mode(&mut sessions, sort_mode);
+            return sessions;
+        }
+
         let mut reviewed: Vec<EnrichedSession> = sessions
             .iter()
             .filter(|s| s.info.ready_to_merge)
@@ -504,9 +658,80 @@ impl SessionUtils {
                     }
                 });
             }
+            SortMode::Group => {
+                sessions.sort_by(|a, b| {
+                    let a_group = a.info.group.as_deref().map(str::to_lowercase);
+                    let b_group = b.info.group.as_deref().map(str::to_lowercase);
+                    match (a_group, b_group) {
+                        (Some(a_group), Some(b_group)) => a_group.cmp(&b_group),
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
+                    }
+                    .then_with(|| {
+                        a.info
+                            .session_id
+                            .to_lowercase()
+                            .cmp(&b.info.session_id.to_lowercase())
+                    })
+                });
+            }
+            SortMode::DiffSize => {
+                // Sessions with stats by size, then sessions without stats, then specs
+                let diff_rank = |s: &EnrichedSession| {
+                    if s.info.session_state == SessionState::Spec {
+                        return (2, 0);
+                    }
+                    match s.info.diff_stats.as_ref() {
+                        Some(stats) => (0, stats.additions + stats.deletions),
+                        None => (1, 0),
+                    }
+                };
+                sessions.sort_by(|a, b| {
+                    let (a_rank, a_size) = diff_rank(a);
+                    let (b_rank, b_size) = diff_rank(b);
+                    a_rank
+                        .cmp(&b_rank)
+                        .then_with(|| b_size.cmp(&a_size))
+                        .then_with(|| {
+                            let a_time = a.info.last_modified.or(a.info.created_at);
+                            let b_time = b.info.last_modified.or(b.info.created_at);
+                            b_time.cmp(&a_time)
+                        })
+                        .then_with(|| a.info.session_id.cmp(&b.info.session_id))
+                });
+            }
+            SortMode::TerminalActivity => {
+                sessions.sort_by(|a, b| {
+                    let a_time = Self::latest_activity(a);
+                    let b_time = Self::latest_activity(b);
+                    match (a_time, b_time) {
+                        (Some(a_time), Some(b_time)) => b_time.cmp(&a_time),
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
+                    }
+                    .then_with(|| a.info.session_id.cmp(&b.info.session_id))
+                });
+            }
         }
     }
 
+    /// The later of the stored activity timestamp and the newest output of any of the
+    /// session's terminals, falling back to the creation time.
+    fn latest_activity(session: &EnrichedSession) -> Option<DateTime<Utc>> {
+        let terminal = session
+            .terminal_details
+            .iter()
+            .filter_map(|t| t.last_activity)
+            .max();
+        session
+            .info
+            .last_modified
+            .max(terminal)
+            .or(session.info.created_at)
+    }
+
     pub fn validate_session_name(name: &str) -> bool {
         if name.is_empty() || name.len() > 100 {
             return false;
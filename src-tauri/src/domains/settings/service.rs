@@ -396,6 +396,18 @@ impl SettingsService {
         self.save()
     }
 
+    pub fn get_webhook_request_log_enabled(&self) -> bool {
+        self.settings.webhook_request_log_enabled
+    }
+
+    pub fn set_webhook_request_log_enabled(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), SettingsServiceError> {
+        self.settings.webhook_request_log_enabled = enabled;
+        self.save()
+    }
+
     pub fn get_last_project_parent_directory(&self) -> Option<String> {
         self.settings.last_project_parent_directory.clone()
     }
@@ -863,6 +875,7 @@ mod tests {
             custom_path: Some("/custom/droid".to_string()),
             auto_detect: false,
             detected_binaries: vec![],
+            version: None,
         };
 
         service
@@ -883,6 +896,7 @@ mod tests {
             custom_path: Some("/custom/qwen".to_string()),
             auto_detect: false,
             detected_binaries: vec![],
+            version: None,
         };
 
         service
@@ -903,6 +917,7 @@ mod tests {
             custom_path: Some("/custom/copilot".to_string()),
             auto_detect: false,
             detected_binaries: vec![],
+            version: None,
         };
 
         service
@@ -923,6 +938,7 @@ mod tests {
             custom_path: Some("/custom/kilo".to_string()),
             auto_detect: false,
             detected_binaries: vec![],
+            version: None,
         };
 
         service
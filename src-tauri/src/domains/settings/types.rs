@@ -264,6 +264,10 @@ pub struct AgentBinaryConfig {
     pub custom_path: Option<String>,
     pub auto_detect: bool,
     pub detected_binaries: Vec<DetectedBinary>,
+    /// Semver reported by `<binary> --version` for the effective binary, cached until
+    /// the next detection.
+    #[serde(default)]
+    pub version: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -315,6 +319,8 @@ pub struct Settings {
     pub last_project_parent_directory: Option<String>,
     #[serde(default)]
     pub agent_command_prefix: Option<String>,
+    #[serde(default = "default_true")]
+    pub webhook_request_log_enabled: bool,
 }
 
 impl Default for Settings {
@@ -339,6 +345,7 @@ impl Default for Settings {
             dev_error_toasts_enabled: default_true(),
             last_project_parent_directory: None,
             agent_command_prefix: None,
+            webhook_request_log_enabled: default_true(),
         }
     }
 }
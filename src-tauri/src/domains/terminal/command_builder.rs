@@ -1,5 +1,6 @@
 use super::CreateParams;
 use super::shell_invocation::{build_login_shell_invocation_with_shell, sh_quote_string};
+use crate::domains::agents::redaction::{redact_args, redact_command_line};
 use crate::shared::terminal_id::is_session_top_terminal_id;
 use portable_pty::CommandBuilder;
 use std::path::PathBuf;
@@ -48,7 +49,7 @@ pub async fn build_command_spec(
                 "Executing '{}' via login shell: program='{}', args={:?}",
                 app.command,
                 resolved_program,
-                resolved_args
+                redact_args(&resolved_args)
             );
         } else {
             log::info!("Resolved command '{}' to '{}'", app.command, resolved_program);
@@ -65,10 +66,13 @@ pub async fn build_command_spec(
                 })
                 .collect::<Vec<_>>()
                 .join(" ");
-            log::info!("EXACT COMMAND EXECUTION: {resolved_program} {args_str}");
+            log::info!(
+                "EXACT COMMAND EXECUTION: {resolved_program} {}",
+                redact_command_line(&args_str)
+            );
             log::info!(
                 "Command args array (each element is a separate argument): {:?}",
-                app.args
+                redact_args(&app.args)
             );
         }
 
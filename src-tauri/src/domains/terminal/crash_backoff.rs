@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Delay before the first relaunch after a crash; doubles with each further crash.
+pub const CRASH_BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// Upper bound for the relaunch delay.
+pub const CRASH_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// Consecutive crashes after which automatic relaunches stop.
+pub const MAX_CONSECUTIVE_CRASHES: u32 = 5;
+/// A run lasting at least this long counts as healthy and clears the crash count.
+pub const HEALTHY_RUN_THRESHOLD: Duration = Duration::from_secs(30);
+
+static AGENT_CRASH_BACKOFF: LazyLock<Mutex<CrashBackoff>> =
+    LazyLock::new(|| Mutex::new(CrashBackoff::default()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchDecision {
+    Proceed,
+    Delay(Duration),
+    GiveUp { crashes: u32 },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CrashRecord {
+    consecutive: u32,
+    last_crash: Instant,
+}
+
+/// Consecutive agent crashes per terminal id, used to space out relaunches.
+#[derive(Debug, Default)]
+pub struct CrashBackoff {
+    records: HashMap<String, CrashRecord>,
+}
+
+/// Relaunch delay after `crashes` consecutive crashes.
+pub fn backoff_delay(crashes: u32) -> Duration {
+    if crashes == 0 {
+        return Duration::ZERO;
+    }
+    let factor = 1u32.checked_shl(crashes - 1).unwrap_or(u32::MAX);
+    CRASH_BACKOFF_BASE
+        .saturating_mul(factor)
+        .min(CRASH_BACKOFF_MAX)
+}
+
+impl CrashBackoff {
+    /// Records an agent exit and returns the resulting consecutive crash count.
+    /// Long runs reset the count; short runs that failed add to it.
+    pub fn record_exit(
+        &mut self,
+        id: &str,
+        run_time: Duration,
+        success: bool,
+        now: Instant,
+    ) -> u32 {
+        if run_time >= HEALTHY_RUN_THRESHOLD {
+            self.records.remove(id);
+            return 0;
+        }
+        if success {
+            return self.crashes(id);
+        }
+
+        let record = self.records.entry(id.to_string()).or_insert(CrashRecord {
+            consecutive: 0,
+            last_crash: now,
+        });
+        record.consecutive += 1;
+        record.last_crash = now;
+        record.consecutive
+    }
+
+    pub fn crashes(&self, id: &str) -> u32 {
+        self.records.get(id).map_or(0, |r| r.consecutive)
+    }
+
+    pub fn decision(&self, id: &str, now: Instant) -> LaunchDecision {
+        let Some(record) = self.records.get(id) else {
+            return LaunchDecision::Proceed;
+        };
+        if record.consecutive >= MAX_CONSECUTIVE_CRASHES {
+            return LaunchDecision::GiveUp {
+                crashes: record.consecutive,
+            };
+        }
+        let remaining =
+            backoff_delay(record.consecutive).saturating_sub(now.duration_since(record.last_crash));
+        if remaining.is_zero() {
+            LaunchDecision::Proceed
+        } else {
+            LaunchDecision::Delay(remaining)
+        }
+    }
+
+    pub fn reset(&mut self, id: &str) {
+        self.records.remove(id);
+    }
+}
+
+fn with_backoff<T>(f: impl FnOnce(&mut CrashBackoff) -> T) -> T {
+    let mut guard = AGENT_CRASH_BACKOFF
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut guard)
+}
+
+/// Records the exit of the agent in terminal `id` in the shared tracker.
+pub fn record_agent_exit(id: &str, run_time: Duration, success: bool) -> u32 {
+    with_backoff(|b| b.record_exit(id, run_time, success, Instant::now()))
+}
+
+/// Whether the agent in terminal `id` may be launched now.
+pub fn agent_launch_decision(id: &str) -> LaunchDecision {
+    with_backoff(|b| b.decision(id, Instant::now()))
+}
+
+/// Clears the crash history of terminal `id`, e.g. after an explicit restart.
+pub fn reset_agent_crashes(id: &str) {
+    with_backoff(|b| b.reset(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHORT: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let schedule: Vec<u64> = (0..8).map(|n| backoff_delay(n).as_secs()).collect();
+        assert_eq!(schedule, vec![0, 1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(backoff_delay(u32::MAX), CRASH_BACKOFF_MAX);
+    }
+
+    #[test]
+    fn delays_relaunch_until_backoff_elapses() {
+        let mut backoff = CrashBackoff::default();
+        let start = Instant::now();
+        assert_eq!(backoff.decision("t", start), LaunchDecision::Proceed);
+
+        backoff.record_exit("t", SHORT, false, start);
+        backoff.record_exit("t", SHORT, false, start);
+        assert_eq!(
+            backoff.decision("t", start + Duration::from_millis(500)),
+            LaunchDecision::Delay(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            backoff.decision("t", start + Duration::from_secs(2)),
+            LaunchDecision::Proceed
+        );
+        assert_eq!(backoff.decision("other", start), LaunchDecision::Proceed);
+    }
+
+    #[test]
+    fn gives_up_after_max_crashes_until_reset_or_healthy_run() {
+        let mut backoff = CrashBackoff::default();
+        let now = Instant::now();
+        for expected in 1..=MAX_CONSECUTIVE_CRASHES {
+            assert_eq!(backoff.record_exit("t", SHORT, false, now), expected);
+        }
+        let later = now + CRASH_BACKOFF_MAX * 2;
+        assert_eq!(
+            backoff.decision("t", later),
+            LaunchDecision::GiveUp {
+                crashes: MAX_CONSECUTIVE_CRASHES
+            }
+        );
+
+        // A quick clean exit neither counts as a crash nor clears the history
+        assert_eq!(
+            backoff.record_exit("t", SHORT, true, later),
+            MAX_CONSECUTIVE_CRASHES
+        );
+
+        backoff.reset("t");
+        assert_eq!(backoff.decision("t", later), LaunchDecision::Proceed);
+
+        backoff.record_exit("t", SHORT, false, later);
+        assert_eq!(
+            backoff.record_exit("t", HEALTHY_RUN_THRESHOLD, false, later),
+            0
+        );
+        assert_eq!(backoff.decision("t", later), LaunchDecision::Proceed);
+    }
+}
@@ -0,0 +1,359 @@
+use super::crash_backoff::HEALTHY_RUN_THRESHOLD;
+use crate::infrastructure::database::{AgentRestartMode, AgentRestartPolicy};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::Duration;
+
+/// A non-zero exit counts as a crash only if the agent printed something this recently.
+/// Exits are noticed by a monitor polling up to 30s apart, hence the generous window.
+pub const CRASH_OUTPUT_WINDOW: Duration = Duration::from_secs(60);
+/// Upper bound for the delay between automatic restarts.
+pub const MAX_RESTART_DELAY: Duration = Duration::from_secs(300);
+/// Exit code of a process stopped with Ctrl+C; treated as a deliberate exit.
+const SIGINT_EXIT_CODE: u32 = 130;
+const TAIL_BYTES: usize = 4096;
+const TAIL_LINES: usize = 20;
+const MAX_ACCUMULATED_TAIL: usize = 8192;
+
+type AgentExitListener = Arc<dyn Fn(AgentExit) + Send + Sync>;
+
+static EXIT_LISTENER: LazyLock<RwLock<Option<AgentExitListener>>> =
+    LazyLock::new(|| RwLock::new(None));
+static RESTART_SUPERVISOR: LazyLock<Mutex<RestartSupervisor>> =
+    LazyLock::new(|| Mutex::new(RestartSupervisor::default()));
+
+/// How the agent process in an agent terminal ended.
+#[derive(Debug, Clone)]
+pub struct AgentExit {
+    pub terminal_id: String,
+    pub exit_code: u32,
+    pub success: bool,
+    pub run_time: Duration,
+    /// Time between the agent's last output and its exit.
+    pub since_last_output: Duration,
+    /// Last lines the agent printed, with escape sequences removed.
+    pub output_tail: String,
+}
+
+impl AgentExit {
+    /// Distinguishes a crash from a normal or user-initiated exit.
+    pub fn is_crash(&self) -> bool {
+        !self.success
+            && self.exit_code != SIGINT_EXIT_CODE
+            && self.since_last_output <= CRASH_OUTPUT_WINDOW
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartDecision {
+    Ignore,
+    Restart { attempt: u32, delay: Duration },
+    GiveUp { attempts: u32, stderr_tail: String },
+}
+
+#[derive(Debug, Default)]
+struct RestartRecord {
+    attempts: u32,
+    tail: String,
+}
+
+/// Automatic restart attempts per agent terminal.
+#[derive(Debug, Default)]
+pub struct RestartSupervisor {
+    records: HashMap<String, RestartRecord>,
+}
+
+/// Delay before restart `attempt` (1-based); doubles with each attempt.
+pub fn restart_delay(policy: &AgentRestartPolicy, attempt: u32) -> Duration {
+    let factor = 1u32
+        .checked_shl(attempt.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    Duration::from_secs(policy.backoff_secs)
+        .saturating_mul(factor)
+        .min(MAX_RESTART_DELAY)
+}
+
+impl RestartSupervisor {
+    pub fn on_exit(&mut self, policy: &AgentRestartPolicy, exit: &AgentExit) -> RestartDecision {
+        if exit.run_time >= HEALTHY_RUN_THRESHOLD {
+            self.records.remove(&exit.terminal_id);
+        }
+        if policy.mode == AgentRestartMode::Never || !exit.is_crash() {
+            self.records.remove(&exit.terminal_id);
+            return RestartDecision::Ignore;
+        }
+
+        let record = self.records.entry(exit.terminal_id.clone()).or_default();
+        record.push_tail(exit);
+
+        if record.attempts >= policy.max_retries {
+            let record = self.records.remove(&exit.terminal_id).unwrap_or_default();
+            return RestartDecision::GiveUp {
+                attempts: record.attempts,
+                stderr_tail: record.tail,
+            };
+        }
+
+        record.attempts += 1;
+        RestartDecision::Restart {
+            attempt: record.attempts,
+            delay: restart_delay(policy, record.attempts),
+        }
+    }
+
+    pub fn reset(&mut self, terminal_id: &str) {
+        self.records.remove(terminal_id);
+    }
+}
+
+impl RestartRecord {
+    fn push_tail(&mut self, exit: &AgentExit) {
+        if !self.tail.is_empty() {
+            self.tail.push('\n');
+        }
+        self.tail.push_str(&format!(
+            "--- run {} (exit code {}) ---\n{}",
+            self.attempts + 1,
+            exit.exit_code,
+            exit.output_tail
+        ));
+        if self.tail.len() > MAX_ACCUMULATED_TAIL {
+            let mut cut = self.tail.len() - MAX_ACCUMULATED_TAIL;
+            while !self.tail.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.tail.drain(..cut);
+        }
+    }
+}
+
+/// Extracts the last few readable lines from raw terminal output.
+pub fn output_tail(buffer: &[u8]) -> String {
+    let start = buffer.len().saturating_sub(TAIL_BYTES);
+    let text = String::from_utf8_lossy(&buffer[start..]);
+
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => {
+                if chars.peek() == Some(&'[') {
+                    chars.next();
+                    for ch in chars.by_ref() {
+                        if ch.is_ascii_alphabetic() || ch == '~' {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\r' => {}
+            _ => plain.push(ch),
+        }
+    }
+
+    let lines: Vec<&str> = plain
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(TAIL_LINES)..].join("\n")
+}
+
+/// Registers the callback that receives every agent exit. Replaces any earlier one.
+pub fn set_agent_exit_listener(listener: impl Fn(AgentExit) + Send + Sync + 'static) {
+    let mut guard = EXIT_LISTENER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = Some(Arc::new(listener));
+}
+
+pub(super) fn notify_agent_exit(exit: AgentExit) {
+    let listener = EXIT_LISTENER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(listener) = listener {
+        listener(exit);
+    }
+}
+
+fn with_supervisor<T>(f: impl FnOnce(&mut RestartSupervisor) -> T) -> T {
+    let mut guard = RESTART_SUPERVISOR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut guard)
+}
+
+/// Records an agent exit in the shared supervisor and decides whether to restart it.
+pub fn decide_agent_restart(policy: &AgentRestartPolicy, exit: &AgentExit) -> RestartDecision {
+    with_supervisor(|s| s.on_exit(policy, exit))
+}
+
+/// Forgets the restart attempts of terminal `id`, e.g. after a manual restart.
+pub fn reset_agent_restarts(id: &str) {
+    with_supervisor(|s| s.reset(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::terminal::TerminalManager;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::mpsc;
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_crash(max_retries: u32) -> AgentRestartPolicy {
+        AgentRestartPolicy {
+            mode: AgentRestartMode::OnCrash,
+            max_retries,
+            backoff_secs: 2,
+        }
+    }
+
+    fn crash(tail: &str) -> AgentExit {
+        AgentExit {
+            terminal_id: "session-alpha-top".to_string(),
+            exit_code: 1,
+            success: false,
+            run_time: Duration::from_secs(3),
+            since_last_output: Duration::from_millis(200),
+            output_tail: tail.to_string(),
+        }
+    }
+
+    #[test]
+    fn only_recent_non_zero_exits_count_as_crashes() {
+        assert!(crash("boom").is_crash());
+
+        let clean = AgentExit {
+            success: true,
+            exit_code: 0,
+            ..crash("")
+        };
+        assert!(!clean.is_crash());
+
+        let interrupted = AgentExit {
+            exit_code: SIGINT_EXIT_CODE,
+            ..crash("")
+        };
+        assert!(!interrupted.is_crash());
+
+        let silent = AgentExit {
+            since_last_output: CRASH_OUTPUT_WINDOW + Duration::from_secs(1),
+            ..crash("")
+        };
+        assert!(!silent.is_crash());
+    }
+
+    #[test]
+    fn restarts_with_backoff_then_gives_up_with_accumulated_tail() {
+        let mut supervisor = RestartSupervisor::default();
+        let policy = on_crash(2);
+
+        assert_eq!(
+            supervisor.on_exit(&policy, &crash("first")),
+            RestartDecision::Restart {
+                attempt: 1,
+                delay: Duration::from_secs(2)
+            }
+        );
+        assert_eq!(
+            supervisor.on_exit(&policy, &crash("second")),
+            RestartDecision::Restart {
+                attempt: 2,
+                delay: Duration::from_secs(4)
+            }
+        );
+        match supervisor.on_exit(&policy, &crash("third")) {
+            RestartDecision::GiveUp {
+                attempts,
+                stderr_tail,
+            } => {
+                assert_eq!(attempts, 2);
+                assert!(stderr_tail.contains("first"));
+                assert!(stderr_tail.ends_with("third"));
+            }
+            other => panic!("expected give-up, got {other:?}"),
+        }
+
+        // Giving up starts the count over for the next manual launch
+        assert!(matches!(
+            supervisor.on_exit(&policy, &crash("again")),
+            RestartDecision::Restart { attempt: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn never_policy_and_healthy_runs_do_not_accumulate() {
+        let mut supervisor = RestartSupervisor::default();
+        assert_eq!(
+            supervisor.on_exit(&AgentRestartPolicy::default(), &crash("boom")),
+            RestartDecision::Ignore
+        );
+
+        let policy = on_crash(3);
+        supervisor.on_exit(&policy, &crash("boom"));
+        supervisor.on_exit(&policy, &crash("boom"));
+        let after_healthy_run = AgentExit {
+            run_time: HEALTHY_RUN_THRESHOLD,
+            ..crash("boom")
+        };
+        assert!(matches!(
+            supervisor.on_exit(&policy, &after_healthy_run),
+            RestartDecision::Restart { attempt: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn output_tail_strips_escape_sequences() {
+        let raw = b"\x1b[32mstarting\x1b[0m\r\n\r\n\x1b[1;31mError: token expired\x1b[0m\r\n";
+        assert_eq!(output_tail(raw), "starting\nError: token expired");
+    }
+
+    #[tokio::test]
+    async fn reports_crash_of_scripted_agent() {
+        let terminal_id = format!(
+            "session-crashy-{}-{}-top",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let expected_id = terminal_id.clone();
+        set_agent_exit_listener(move |exit| {
+            if exit.terminal_id == expected_id {
+                let _ = tx.send(exit);
+            }
+        });
+
+        let manager = TerminalManager::new();
+        manager
+            .create_terminal_with_app(
+                terminal_id.clone(),
+                "/tmp".to_string(),
+                "sh".to_string(),
+                vec![
+                    "-c".to_string(),
+                    "sleep 0.5; echo 'fatal: model overloaded' >&2; exit 3".to_string(),
+                ],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let exit = tokio::time::timeout(Duration::from_secs(15), rx.recv())
+            .await
+            .expect("agent exit was not reported")
+            .unwrap();
+        assert_eq!(exit.exit_code, 3);
+        assert!(exit.is_crash());
+        assert!(exit.output_tail.contains("fatal: model overloaded"));
+
+        match decide_agent_restart(&on_crash(0), &exit) {
+            RestartDecision::GiveUp { stderr_tail, .. } => {
+                assert!(stderr_tail.contains("fatal: model overloaded"));
+            }
+            other => panic!("expected give-up, got {other:?}"),
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use super::crash_backoff::{self, MAX_CONSECUTIVE_CRASHES};
+use super::crash_supervisor::{self, AgentExit};
 use super::local::TerminalState;
+use super::process_registry::forget_pty_process;
 use crate::infrastructure::events::{SchaltEvent, emit_event};
 use log::{debug, error, info, warn};
 use portable_pty::{Child, ExitStatus, MasterPty};
 use std::collections::HashMap;
 use std::io::Write;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::AppHandle;
 use tokio::sync::{Mutex, RwLock};
 
@@ -127,18 +130,29 @@ async fn check_agent_health(
     }
 }
 
-async fn handle_agent_crash(terminal_id: String, status: ExitStatus, deps: LifecycleDeps) {
+async fn handle_agent_crash(
+    terminal_id: String,
+    status: ExitStatus,
+    consecutive_crashes: u32,
+    run_time: Duration,
+    deps: LifecycleDeps,
+) {
     error!("HANDLING AGENT CRASH for terminal: {terminal_id}");
 
     let agent_type = get_agent_type_from_terminal(&terminal_id).unwrap_or("unknown");
     let session_name = extract_session_name(&terminal_id);
 
-    let (buffer_size, last_seq) = {
+    let (buffer_size, last_seq, since_last_output, output_tail) = {
         let terminals_guard = deps.terminals.read().await;
         if let Some(state) = terminals_guard.get(&terminal_id) {
-            (state.buffer.len(), state.seq)
+            (
+                state.buffer.len(),
+                state.seq,
+                state.last_output.elapsed().unwrap_or_default(),
+                crash_supervisor::output_tail(&state.buffer),
+            )
         } else {
-            (0, 0)
+            (0, 0, Duration::MAX, String::new())
         }
     };
 
@@ -163,6 +177,8 @@ async fn handle_agent_crash(terminal_id: String, status: ExitStatus, deps: Lifec
             exit_code: Option<i32>,
             buffer_size: usize,
             last_seq: u64,
+            consecutive_crashes: u32,
+            auto_relaunch_disabled: bool,
         }
 
         let payload = AgentCrashPayload {
@@ -172,6 +188,8 @@ async fn handle_agent_crash(terminal_id: String, status: ExitStatus, deps: Lifec
             exit_code: Some(status.exit_code() as i32),
             buffer_size,
             last_seq,
+            consecutive_crashes,
+            auto_relaunch_disabled: consecutive_crashes >= MAX_CONSECUTIVE_CRASHES,
         };
 
         if let Err(e) = emit_event(handle, SchaltEvent::AgentCrashed, &payload) {
@@ -182,12 +200,23 @@ async fn handle_agent_crash(terminal_id: String, status: ExitStatus, deps: Lifec
     }
 
     log_agent_crash_details(&terminal_id, &status).await;
+
+    crash_supervisor::notify_agent_exit(AgentExit {
+        terminal_id,
+        exit_code: status.exit_code(),
+        success: status.success(),
+        run_time,
+        since_last_output,
+        output_tail,
+    });
 }
 
 pub(super) async fn cleanup_dead_terminal(id: String, deps: &LifecycleDeps) {
     info!("Cleaning up dead terminal: {id}");
 
-    deps.pty_children.lock().await.remove(&id);
+    if let Some(child) = deps.pty_children.lock().await.remove(&id) {
+        forget_pty_process(child.process_id());
+    }
     deps.pty_masters.lock().await.remove(&id);
     deps.pty_writers.lock().await.remove(&id);
     deps.terminals.write().await.remove(&id);
@@ -224,6 +253,7 @@ pub(super) async fn start_process_monitor(id: String, deps: LifecycleDeps) {
     let mut check_interval = tokio::time::Duration::from_secs(1);
     let max_interval = tokio::time::Duration::from_secs(30);
     let mut last_activity_check = Instant::now();
+    let started_at = Instant::now();
 
     tokio::spawn(async move {
         loop {
@@ -287,7 +317,19 @@ pub(super) async fn start_process_monitor(id: String, deps: LifecycleDeps) {
                         );
                     }
 
-                    handle_agent_crash(monitor_id.clone(), status, deps.clone()).await;
+                    let consecutive_crashes = crash_backoff::record_agent_exit(
+                        &monitor_id,
+                        started_at.elapsed(),
+                        status.success(),
+                    );
+                    handle_agent_crash(
+                        monitor_id.clone(),
+                        status,
+                        consecutive_crashes,
+                        started_at.elapsed(),
+                        deps.clone(),
+                    )
+                    .await;
                 } else {
                     info!("Terminal {monitor_id} process exited with status: {status:?}");
                     cleanup_dead_terminal(monitor_id.clone(), &deps).await;
@@ -5,12 +5,17 @@ use super::command_builder::build_command_spec;
 use super::control_sequences::{SanitizedOutput, SequenceResponse, WindowSizeRequest, sanitize_control_sequences};
 use super::idle_detection::{IdleDetector, IdleTransition};
 use super::lifecycle::{self, LifecycleDeps};
+use super::process_registry::{forget_pty_process, record_pty_process};
+use super::recording::TerminalRecordings;
 use super::submission::build_submission_payload;
 use super::visible::VisibleScreen;
 use super::{CreateParams, TerminalBackend, TerminalSnapshot};
+use crate::infrastructure::agent_status_bridge::{
+    record_agent_status_output, untrack_agent_terminal,
+};
 use crate::infrastructure::attention_bridge::update_session_attention_state;
 use crate::infrastructure::events::{SchaltEvent, emit_event};
-use crate::infrastructure::keep_awake_bridge::handle_terminal_attention;
+use crate::infrastructure::keep_awake_bridge::record_agent_output;
 use crate::shared::terminal_id::is_session_top_terminal_id;
 use log::{debug, error, info, trace, warn};
 use portable_pty::{Child, MasterPty, NativePtySystem, PtySize, PtySystem};
@@ -169,6 +174,8 @@ pub struct LocalPtyAdapter {
     initial_commands: Arc<Mutex<HashMap<String, InitialCommandState>>>,
     // Event broadcasting for deterministic testing
     output_event_sender: Arc<broadcast::Sender<(String, u64)>>, // (terminal_id, new_seq)
+    // Opt-in per-session copies of terminal output on disk
+    recordings: TerminalRecordings,
 }
 
 #[derive(Clone)]
@@ -181,6 +188,7 @@ struct ReaderState {
     pending_control_sequences: Arc<Mutex<HashMap<String, Vec<u8>>>>,
     initial_commands: Arc<Mutex<HashMap<String, InitialCommandState>>>,
     output_event_sender: Arc<broadcast::Sender<(String, u64)>>,
+    recordings: TerminalRecordings,
 }
 
 enum ReaderMessage {
@@ -226,9 +234,14 @@ impl LocalPtyAdapter {
             pending_control_sequences: Arc::new(Mutex::new(HashMap::new())),
             initial_commands: Arc::new(Mutex::new(HashMap::new())),
             output_event_sender: Arc::new(output_event_sender),
+            recordings: TerminalRecordings::default(),
         }
     }
 
+    pub fn recordings(&self) -> &TerminalRecordings {
+        &self.recordings
+    }
+
     pub async fn get_activity_status(&self, id: &str) -> Result<(bool, u64), String> {
         let terminals = self.terminals.read().await;
         if let Some(state) = terminals.get(id) {
@@ -242,6 +255,23 @@ impl LocalPtyAdapter {
         }
     }
 
+    /// Current `(rows, cols)` of terminal `id`.
+    pub async fn screen_size(&self, id: &str) -> Option<(u16, u16)> {
+        self.terminals
+            .read()
+            .await
+            .get(id)
+            .map(|state| state.screen.size())
+    }
+
+    pub async fn get_all_terminal_last_output(&self) -> Vec<(String, SystemTime)> {
+        let terminals = self.terminals.read().await;
+        terminals
+            .iter()
+            .map(|(id, state)| (id.clone(), state.last_output))
+            .collect()
+    }
+
     pub async fn get_all_terminal_activity(&self) -> Vec<(String, u64)> {
         let terminals = self.terminals.read().await;
         let mut results = Vec::new();
@@ -404,7 +434,6 @@ impl LocalPtyAdapter {
                             error!("Failed to emit TerminalAttention event: {e}");
                         }
 
-                        handle_terminal_attention(session_id.clone(), needs_attention);
                         update_session_attention_state(session_id, needs_attention);
                     }
                 }
@@ -474,6 +503,9 @@ impl LocalPtyAdapter {
         mut data: Vec<u8>,
         reader_state: &ReaderState,
     ) -> Result<(), String> {
+        // Record the raw bytes before any pending control sequence is merged back in
+        reader_state.recordings.record(id, &data);
+
         {
             let mut pending_guard = reader_state.pending_control_sequences.lock().await;
             if let Some(mut pending) = pending_guard.remove(id) {
@@ -589,6 +621,14 @@ impl LocalPtyAdapter {
                     }
                 }
 
+                if !sanitized.is_empty()
+                    && is_session_top_terminal_id(id)
+                    && let Some(session_id) = state.session_id.as_deref()
+                {
+                    record_agent_output(session_id);
+                    record_agent_status_output(id, &sanitized);
+                }
+
                 current_seq = Some(state.seq);
             }
         }
@@ -664,10 +704,12 @@ impl LocalPtyAdapter {
         };
 
         if let Some(mut child) = deps.pty_children.lock().await.remove(id) {
+            forget_pty_process(child.process_id());
             let _ = child.kill();
         }
 
         flush_terminal_output(&reader_state.coalescing_state, id).await;
+        reader_state.recordings.finish(id);
         lifecycle::cleanup_dead_terminal(id.to_string(), &deps).await;
         reader_state.coalescing_state.clear_for(id).await;
     }
@@ -704,6 +746,7 @@ impl LocalPtyAdapter {
                 pending_control_sequences: Arc::clone(&self.pending_control_sequences),
                 initial_commands: Arc::clone(&self.initial_commands),
                 output_event_sender: Arc::clone(&self.output_event_sender),
+                recordings: self.recordings.clone(),
             },
         );
 
@@ -884,6 +927,7 @@ impl TerminalBackend for LocalPtyAdapter {
             .map_err(|e| format!("Failed to get writer: {e}"))?;
 
         // Store the child and master in separate maps to avoid Sync issues
+        record_pty_process(child.process_id(), &id, session_id_from_terminal_id(&id));
         self.pty_children.lock().await.insert(id.clone(), child);
         self.pty_masters
             .lock()
@@ -896,6 +940,7 @@ impl TerminalBackend for LocalPtyAdapter {
             if let Some(child) = guard.get_mut(&id)
                 && let Ok(Some(status)) = child.try_wait()
             {
+                forget_pty_process(child.process_id());
                 drop(guard);
                 self.pty_children.lock().await.remove(&id);
                 self.pty_masters.lock().await.remove(&id);
@@ -927,6 +972,8 @@ impl TerminalBackend for LocalPtyAdapter {
 
         self.terminals.write().await.insert(id.clone(), state);
 
+        self.recordings.attach(&id);
+
         // Start reader agent and record the handle so we can abort on close
         self.spawn_reader_for(&id).await?;
 
@@ -1073,9 +1120,12 @@ impl TerminalBackend for LocalPtyAdapter {
 
         // Abort reader first to stop any further emission for this terminal id
         self.abort_reader(id).await;
+        self.recordings.finish(id);
+        untrack_agent_terminal(id);
 
         // Try to terminate the child process and wait deterministically without polling
         if let Some(mut child) = self.pty_children.lock().await.remove(id) {
+            forget_pty_process(child.process_id());
             #[cfg(unix)]
             let maybe_pid = child.process_id();
 
@@ -1193,6 +1243,7 @@ impl TerminalBackend for LocalPtyAdapter {
         let children: Vec<_> = self.pty_children.lock().await.drain().collect();
 
         for (id, mut child) in children {
+            forget_pty_process(child.process_id());
             #[cfg(unix)]
             if let Some(pid) = child.process_id() {
                 unsafe {
@@ -1,12 +1,13 @@
 use super::{
-    ApplicationSpec, CreateParams, LocalPtyAdapter, TerminalBackend, TerminalSnapshot,
-    get_effective_shell, submission::build_submission_payload,
+    ApplicationSpec, CreateParams, LocalPtyAdapter, TerminalBackend, TerminalLiveness,
+    TerminalSnapshot, get_effective_shell, submission::build_submission_payload,
+    visible::{PlainTextOptions, render_plain_text},
 };
 use crate::infrastructure::events::{SchaltEvent, emit_event};
 use log::{debug, error, info, warn};
 use std::{
     collections::{HashMap, HashSet},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
@@ -181,6 +182,27 @@ impl TerminalManager {
         Ok(())
     }
 
+    /// Sets where terminals of `session_name` created from now on record their output.
+    pub fn set_session_recording_target(&self, session_name: &str, dir: Option<PathBuf>) {
+        self.backend.recordings().set_session(session_name, dir);
+    }
+
+    /// Turns recording of `session_name`'s terminals on (into `dir`) or off, including
+    /// terminals that are already running.
+    pub async fn set_session_recording(&self, session_name: &str, dir: Option<PathBuf>) {
+        let recordings = self.backend.recordings();
+        let enabled = dir.is_some();
+        recordings.set_session(session_name, dir);
+        if enabled {
+            let ids: Vec<String> = self.active_ids.read().await.iter().cloned().collect();
+            for id in ids {
+                recordings.attach(&id);
+            }
+        } else {
+            recordings.finish_session(session_name);
+        }
+    }
+
     pub async fn create_terminal(&self, id: String, cwd: String) -> Result<(), String> {
         let start = std::time::Instant::now();
         let result = self.create_terminal_with_env(id.clone(), cwd, vec![]).await;
@@ -537,6 +559,21 @@ impl TerminalManager {
         Ok(snapshot)
     }
 
+    /// Renders the buffer of terminal `id` as the plain text the user sees.
+    pub async fn get_terminal_buffer_text(
+        &self,
+        id: String,
+        options: PlainTextOptions,
+    ) -> Result<String, String> {
+        let snapshot = self.backend.snapshot(&id, None).await?;
+        let (rows, cols) = self
+            .backend
+            .screen_size(&id)
+            .await
+            .ok_or_else(|| format!("Terminal {id} not found"))?;
+        Ok(render_plain_text(&snapshot.data, rows, cols, &options))
+    }
+
     pub async fn wait_for_output_change(&self, id: &str, min_seq: u64) -> Result<u64, String> {
         self.backend.wait_for_output_change(id, min_seq).await
     }
@@ -611,6 +648,23 @@ impl TerminalManager {
     pub async fn get_all_terminal_activity(&self) -> Vec<(String, u64)> {
         self.backend.get_all_terminal_activity().await
     }
+
+    /// Liveness of every open terminal, gathered in a single pass so callers that
+    /// describe many sessions do not take the terminal lock once per session.
+    pub async fn liveness_snapshot(&self) -> HashMap<String, TerminalLiveness> {
+        let mut snapshot = HashMap::new();
+        for (id, last_output) in self.backend.get_all_terminal_last_output().await {
+            let suspended = self.backend.is_suspended(&id).await.unwrap_or(false);
+            snapshot.insert(
+                id,
+                TerminalLiveness {
+                    suspended,
+                    last_activity: last_output.into(),
+                },
+            );
+        }
+        snapshot
+    }
 }
 
 #[cfg(test)]
@@ -69,6 +69,42 @@ mod tests {
         safe_close(&manager, &id).await;
     }
 
+    #[tokio::test]
+    async fn test_get_terminal_buffer_text_renders_plain_output() {
+        let manager = TerminalManager::new();
+        let id = unique_id("buffer-text");
+
+        manager
+            .create_terminal_with_app(
+                id.clone(),
+                "/tmp".to_string(),
+                "sh".to_string(),
+                vec![
+                    "-c".to_string(),
+                    "printf 'fetch 10%%\\rfetch 100%%\\n\\033[31mred\\033[0m done\\n'; sleep 5"
+                        .to_string(),
+                ],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let mut text = String::new();
+        for _ in 0..500 {
+            text = manager
+                .get_terminal_buffer_text(id.clone(), visible::PlainTextOptions::default())
+                .await
+                .expect("failed to render terminal buffer");
+            if text.contains("done") {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(text, "fetch 100%\nred done");
+
+        safe_close(&manager, &id).await;
+    }
+
     #[tokio::test]
     async fn test_paste_and_submit_bracketed_paste_mode() {
         let manager = TerminalManager::new();
@@ -23,6 +23,14 @@ pub struct TerminalSnapshot {
     pub data: Vec<u8>,
 }
 
+/// Point-in-time state of a live terminal, keyed by terminal id in
+/// [`TerminalManager::liveness_snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalLiveness {
+    pub suspended: bool,
+    pub last_activity: chrono::DateTime<chrono::Utc>,
+}
+
 #[async_trait::async_trait]
 pub trait TerminalBackend: Send + Sync {
     async fn create(&self, params: CreateParams) -> Result<(), String>;
@@ -65,12 +73,16 @@ pub mod ansi;
 pub mod coalescing;
 pub mod command_builder;
 pub mod control_sequences;
+pub mod crash_backoff;
+pub mod crash_supervisor;
 pub mod idle_detection;
 pub mod lifecycle;
 pub mod local;
 pub mod login_shell_env;
 pub mod manager;
 pub mod nvm;
+pub mod process_registry;
+pub mod recording;
 pub mod shell_invocation;
 pub mod submission;
 pub mod utf8_stream;
@@ -0,0 +1,381 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// File under the app config dir listing the PTY children of running instances.
+pub const PROCESS_REGISTRY_FILE: &str = "pty_processes.json";
+
+/// Platform lookups the registry needs to tell a leftover agent from an unrelated
+/// process that reused its PID.
+pub trait ProcessInspector: Send + Sync {
+    /// Opaque start time of the running process `pid`, `None` when there is none.
+    fn start_time(&self, pid: u32) -> Option<String>;
+    /// Kills `pid`, its process group and every descendant.
+    fn kill_tree(&self, pid: u32);
+}
+
+/// PTY child spawned by an instance, identified by PID and start time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyProcessRecord {
+    pub pid: u32,
+    pub start_time: String,
+    pub terminal_id: String,
+    pub session_id: Option<String>,
+    pub owner_pid: u32,
+    pub owner_start_time: Option<String>,
+}
+
+/// A PTY child left behind by an instance that did not shut down cleanly and was
+/// killed at startup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedProcess {
+    pub pid: u32,
+    pub terminal_id: String,
+    pub session_id: Option<String>,
+}
+
+/// On-disk list of spawned PTY children, so the next launch can kill agents that
+/// outlived a force-killed or crashed instance.
+pub struct PtyProcessRegistry {
+    path: PathBuf,
+    inspector: Arc<dyn ProcessInspector>,
+    owner_pid: u32,
+    owner_start_time: Option<String>,
+    lock: Mutex<()>,
+}
+
+impl PtyProcessRegistry {
+    pub fn new(path: PathBuf, inspector: Arc<dyn ProcessInspector>) -> Self {
+        Self::for_owner(path, inspector, std::process::id())
+    }
+
+    fn for_owner(path: PathBuf, inspector: Arc<dyn ProcessInspector>, owner_pid: u32) -> Self {
+        let owner_start_time = inspector.start_time(owner_pid);
+        Self {
+            path,
+            inspector,
+            owner_pid,
+            owner_start_time,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn record(&self, pid: u32, terminal_id: &str, session_id: Option<String>) {
+        let Some(start_time) = self.inspector.start_time(pid) else {
+            return;
+        };
+        let record = PtyProcessRecord {
+            pid,
+            start_time,
+            terminal_id: terminal_id.to_string(),
+            session_id,
+            owner_pid: self.owner_pid,
+            owner_start_time: self.owner_start_time.clone(),
+        };
+        self.update(|records| {
+            records.retain(|existing| existing.pid != pid);
+            records.push(record);
+        });
+    }
+
+    pub fn forget(&self, pid: u32) {
+        self.update(|records| records.retain(|record| record.pid != pid));
+    }
+
+    /// Kills every recorded process whose instance is gone and that is still the process
+    /// that was spawned, then drops all entries of dead instances. Entries of instances
+    /// that are still running are kept.
+    pub fn reap_orphans(&self) -> Vec<OrphanedProcess> {
+        let mut orphans = Vec::new();
+        self.update(|records| {
+            records.retain(|record| {
+                if self.owner_is_running(record) {
+                    return true;
+                }
+                if self.inspector.start_time(record.pid).as_ref() == Some(&record.start_time) {
+                    info!(
+                        "Killing orphaned process {} of terminal {}",
+                        record.pid, record.terminal_id
+                    );
+                    self.inspector.kill_tree(record.pid);
+                    orphans.push(OrphanedProcess {
+                        pid: record.pid,
+                        terminal_id: record.terminal_id.clone(),
+                        session_id: record.session_id.clone(),
+                    });
+                }
+                false
+            });
+        });
+        orphans
+    }
+
+    fn owner_is_running(&self, record: &PtyProcessRecord) -> bool {
+        record.owner_pid != self.owner_pid
+            && record.owner_start_time.is_some()
+            && self.inspector.start_time(record.owner_pid) == record.owner_start_time
+    }
+
+    fn update(&self, change: impl FnOnce(&mut Vec<PtyProcessRecord>)) {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut records = read_records(&self.path);
+        let before = records.clone();
+        change(&mut records);
+        if records != before
+            && let Err(e) = write_records(&self.path, &records)
+        {
+            warn!(
+                "Failed to update process registry {}: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+fn read_records(path: &Path) -> Vec<PtyProcessRecord> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(
+                "Discarding unreadable process registry {}: {e}",
+                path.display()
+            );
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn write_records(path: &Path, records: &[PtyProcessRecord]) -> std::io::Result<()> {
+    if records.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(records)?)?;
+    std::fs::rename(tmp, path)
+}
+
+static PROCESS_REGISTRY: OnceLock<PtyProcessRegistry> = OnceLock::new();
+static REAPED_ORPHANS: OnceLock<Vec<OrphanedProcess>> = OnceLock::new();
+
+/// Reaps the orphans left by earlier instances and starts recording the PTY children
+/// of this one. Until this runs, for example in tests, nothing is recorded.
+pub fn install_process_registry(registry: PtyProcessRegistry) {
+    let orphans = registry.reap_orphans();
+    if !orphans.is_empty() {
+        warn!(
+            "Killed {} process(es) left behind by a previous instance",
+            orphans.len()
+        );
+    }
+    let _ = REAPED_ORPHANS.set(orphans);
+    let _ = PROCESS_REGISTRY.set(registry);
+}
+
+pub fn process_registry() -> Option<&'static PtyProcessRegistry> {
+    PROCESS_REGISTRY.get()
+}
+
+/// Records a freshly spawned PTY child in the installed registry, if any.
+pub fn record_pty_process(pid: Option<u32>, terminal_id: &str, session_id: Option<String>) {
+    if let (Some(registry), Some(pid)) = (process_registry(), pid) {
+        registry.record(pid, terminal_id, session_id);
+    }
+}
+
+/// Drops a PTY child that exited or was killed from the installed registry, if any.
+pub fn forget_pty_process(pid: Option<u32>) {
+    if let (Some(registry), Some(pid)) = (process_registry(), pid) {
+        registry.forget(pid);
+    }
+}
+
+/// Processes killed at startup because a previous instance left them running.
+pub fn reaped_orphans() -> Vec<OrphanedProcess> {
+    REAPED_ORPHANS.get().cloned().unwrap_or_default()
+}
+
+/// [`ProcessInspector`] backed by `/proc` on Linux and `ps` on macOS.
+pub struct SystemProcessInspector;
+
+impl ProcessInspector for SystemProcessInspector {
+    #[cfg(target_os = "linux")]
+    fn start_time(&self, pid: u32) -> Option<String> {
+        // Field 22 of /proc/<pid>/stat, counted after the parenthesized command name
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let (_, fields) = stat.rsplit_once(')')?;
+        fields.split_whitespace().nth(19).map(str::to_string)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn start_time(&self, pid: u32) -> Option<String> {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "lstart=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        let start = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !start.is_empty()).then_some(start)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn start_time(&self, _pid: u32) -> Option<String> {
+        None
+    }
+
+    #[cfg(unix)]
+    fn kill_tree(&self, pid: u32) {
+        let mut pids = descendants(pid);
+        pids.push(pid);
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+        for pid in pids {
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGKILL);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn kill_tree(&self, _pid: u32) {}
+}
+
+/// Every descendant of `pid`, collected before anything is killed because killed
+/// parents hand their children over to init.
+#[cfg(unix)]
+fn descendants(pid: u32) -> Vec<u32> {
+    let mut found = Vec::new();
+    let mut pending = vec![pid];
+    while let Some(parent) = pending.pop() {
+        let Ok(output) = std::process::Command::new("pgrep")
+            .args(["-P", &parent.to_string()])
+            .output()
+        else {
+            break;
+        };
+        for child in String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.trim().parse::<u32>().ok())
+        {
+            found.push(child);
+            pending.push(child);
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct FakeInspector {
+        running: Mutex<HashMap<u32, String>>,
+        killed: Mutex<Vec<u32>>,
+    }
+
+    impl FakeInspector {
+        fn run(&self, pid: u32, start_time: &str) {
+            self.running
+                .lock()
+                .unwrap()
+                .insert(pid, start_time.to_string());
+        }
+
+        fn exit(&self, pid: u32) {
+            self.running.lock().unwrap().remove(&pid);
+        }
+    }
+
+    impl ProcessInspector for FakeInspector {
+        fn start_time(&self, pid: u32) -> Option<String> {
+            self.running.lock().unwrap().get(&pid).cloned()
+        }
+
+        fn kill_tree(&self, pid: u32) {
+            self.killed.lock().unwrap().push(pid);
+            self.exit(pid);
+        }
+    }
+
+    /// An instance with owner PID 100 recorded three terminals and then crashed.
+    fn crashed_instance(temp: &TempDir, inspector: &Arc<FakeInspector>) -> PathBuf {
+        let path = temp.path().join(PROCESS_REGISTRY_FILE);
+        inspector.run(100, "owner");
+        let previous = PtyProcessRegistry::for_owner(path.clone(), inspector.clone(), 100);
+        for (pid, terminal) in [(11, "session-stale-top"), (12, "session-reused-top")] {
+            inspector.run(pid, &format!("start-{pid}"));
+            previous.record(pid, terminal, None);
+        }
+        inspector.run(13, "start-13");
+        previous.record(13, "session-live-top", Some("live".to_string()));
+        inspector.exit(100);
+        path
+    }
+
+    #[test]
+    fn live_orphans_are_killed_and_stale_or_reused_pids_are_left_alone() {
+        let temp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::default());
+        let path = crashed_instance(&temp, &inspector);
+
+        // Process 11 is gone; PID 12 now belongs to an unrelated process
+        inspector.exit(11);
+        inspector.run(12, "someone-else");
+
+        let registry = PtyProcessRegistry::for_owner(path.clone(), inspector.clone(), 200);
+        let orphans = registry.reap_orphans();
+
+        assert_eq!(
+            orphans,
+            vec![OrphanedProcess {
+                pid: 13,
+                terminal_id: "session-live-top".to_string(),
+                session_id: Some("live".to_string()),
+            }]
+        );
+        assert_eq!(*inspector.killed.lock().unwrap(), vec![13]);
+        assert!(!path.exists(), "registry is cleared");
+    }
+
+    #[test]
+    fn processes_of_a_running_instance_are_kept() {
+        let temp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::default());
+        let path = crashed_instance(&temp, &inspector);
+        inspector.run(100, "owner");
+
+        let registry = PtyProcessRegistry::for_owner(path.clone(), inspector.clone(), 200);
+        assert!(registry.reap_orphans().is_empty());
+        assert!(inspector.killed.lock().unwrap().is_empty());
+        assert_eq!(read_records(&path).len(), 3);
+    }
+
+    #[test]
+    fn forgotten_processes_are_not_reaped() {
+        let temp = TempDir::new().unwrap();
+        let inspector = Arc::new(FakeInspector::default());
+        let path = temp.path().join(PROCESS_REGISTRY_FILE);
+        inspector.run(100, "owner");
+        let registry = PtyProcessRegistry::for_owner(path.clone(), inspector.clone(), 100);
+
+        inspector.run(21, "start-21");
+        registry.record(21, "session-a-top", Some("a".to_string()));
+        registry.forget(21);
+        assert!(!path.exists());
+
+        let next = PtyProcessRegistry::for_owner(path, inspector.clone(), 200);
+        assert!(next.reap_orphans().is_empty());
+        assert!(inspector.killed.lock().unwrap().is_empty());
+    }
+}
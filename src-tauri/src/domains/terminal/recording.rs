@@ -0,0 +1,421 @@
+use crate::shared::terminal_id::session_terminal_base;
+use log::warn;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// Size at which a recording file is rotated.
+pub const RECORDING_ROTATE_BYTES: u64 = 50 * 1024 * 1024;
+/// Buffered output is written out at least this often.
+pub const RECORDING_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+/// Buffered output is written out once it reaches this size.
+pub const RECORDING_FLUSH_BYTES: usize = 64 * 1024;
+
+const RECORDINGS_DIR: &str = "recordings";
+
+/// Directory holding the recordings of session `name` in `repo_path`.
+pub fn session_recordings_dir(repo_path: &Path, name: &str) -> PathBuf {
+    repo_path
+        .join(".schaltwerk")
+        .join(RECORDINGS_DIR)
+        .join(name)
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TerminalRecordingFile {
+    pub file_name: String,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Lists the `.log` files in `dir`, sorted by name. A missing directory yields no files.
+pub fn list_recordings(dir: &Path) -> io::Result<Vec<TerminalRecordingFile>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("log") {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        files.push(TerminalRecordingFile {
+            file_name: entry.file_name().to_string_lossy().into_owned(),
+            path: path.to_string_lossy().into_owned(),
+            size_bytes: metadata.len(),
+        });
+    }
+    files.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(files)
+}
+
+/// Appends the output of one terminal to `<dir>/<terminal_id>.log`.
+///
+/// Output is buffered in memory and written in batches. When the active file
+/// grows past the rotation size it is renamed to `<terminal_id>.<timestamp>.log`
+/// (plus a counter) and a fresh file is started.
+pub struct TerminalRecorder {
+    dir: PathBuf,
+    terminal_id: String,
+    file: File,
+    written: u64,
+    buffer: Vec<u8>,
+    last_flush: Instant,
+    rotate_at: u64,
+    rotations: u32,
+}
+
+impl TerminalRecorder {
+    pub fn open(dir: &Path, terminal_id: &str) -> io::Result<Self> {
+        Self::with_rotation(dir, terminal_id, RECORDING_ROTATE_BYTES)
+    }
+
+    pub fn with_rotation(dir: &Path, terminal_id: &str, rotate_at: u64) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{terminal_id}.log"));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            terminal_id: terminal_id.to_string(),
+            file,
+            written,
+            buffer: Vec::with_capacity(RECORDING_FLUSH_BYTES),
+            last_flush: Instant::now(),
+            rotate_at: rotate_at.max(1),
+            rotations: 0,
+        })
+    }
+
+    pub fn active_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.log", self.terminal_id))
+    }
+
+    pub fn record(&mut self, data: &[u8]) -> io::Result<()> {
+        self.buffer.extend_from_slice(data);
+        if self.buffer.len() >= RECORDING_FLUSH_BYTES
+            || self.last_flush.elapsed() >= RECORDING_FLUSH_INTERVAL
+        {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes buffered output if it has waited longer than the flush interval.
+    pub fn flush_if_stale(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() && self.last_flush.elapsed() >= RECORDING_FLUSH_INTERVAL {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.last_flush = Instant::now();
+        let mut pending = std::mem::take(&mut self.buffer);
+        let mut remaining = pending.as_slice();
+        while !remaining.is_empty() {
+            if self.written >= self.rotate_at {
+                self.rotate()?;
+            }
+            let room = (self.rotate_at - self.written).min(remaining.len() as u64) as usize;
+            self.file.write_all(&remaining[..room])?;
+            self.written += room as u64;
+            remaining = &remaining[room..];
+        }
+        pending.clear();
+        self.buffer = pending;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        // The counter keeps names unique when several rotations share a timestamp
+        self.rotations += 1;
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+        let rotated = self.dir.join(format!(
+            "{}.{stamp}-{:04}.log",
+            self.terminal_id, self.rotations
+        ));
+        fs::rename(self.active_path(), rotated)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.active_path())?;
+        self.written = 0;
+        Ok(())
+    }
+
+    /// Writes any buffered output and syncs the file to disk.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.flush()?;
+        self.file.sync_all()
+    }
+}
+
+#[derive(Default)]
+struct RecordingsInner {
+    /// Session name to the directory its terminals record into.
+    targets: HashMap<String, PathBuf>,
+    active: HashMap<String, TerminalRecorder>,
+}
+
+/// Tracks which sessions record their terminals and the open recorders.
+#[derive(Clone, Default)]
+pub struct TerminalRecordings {
+    inner: Arc<Mutex<RecordingsInner>>,
+    ticker_started: Arc<AtomicBool>,
+}
+
+fn belongs_to_session(terminal_id: &str, session_name: &str) -> bool {
+    terminal_id.starts_with(&format!("{}-", session_terminal_base(session_name)))
+}
+
+impl TerminalRecordings {
+    fn lock(&self) -> std::sync::MutexGuard<'_, RecordingsInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Enables recording for the terminals of `session_name` into `dir`, or disables it with `None`.
+    /// Only affects terminals attached afterwards; see [`Self::finish_session`].
+    pub fn set_session(&self, session_name: &str, dir: Option<PathBuf>) {
+        let mut inner = self.lock();
+        match dir {
+            Some(dir) => {
+                inner.targets.insert(session_name.to_string(), dir);
+            }
+            None => {
+                inner.targets.remove(session_name);
+            }
+        }
+    }
+
+    pub fn is_recording(&self, terminal_id: &str) -> bool {
+        self.lock().active.contains_key(terminal_id)
+    }
+
+    /// Starts recording `terminal_id` if its session has recording enabled.
+    pub fn attach(&self, terminal_id: &str) {
+        let mut inner = self.lock();
+        if inner.active.contains_key(terminal_id) {
+            return;
+        }
+        let Some(dir) = inner
+            .targets
+            .iter()
+            .find(|(name, _)| belongs_to_session(terminal_id, name))
+            .map(|(_, dir)| dir.clone())
+        else {
+            return;
+        };
+        match TerminalRecorder::open(&dir, terminal_id) {
+            Ok(recorder) => {
+                inner.active.insert(terminal_id.to_string(), recorder);
+            }
+            Err(e) => warn!("Failed to start recording terminal {terminal_id}: {e}"),
+        }
+        drop(inner);
+        self.ensure_ticker();
+    }
+
+    pub fn record(&self, terminal_id: &str, data: &[u8]) {
+        let mut inner = self.lock();
+        let Some(recorder) = inner.active.get_mut(terminal_id) else {
+            return;
+        };
+        if let Err(e) = recorder.record(data) {
+            warn!("Stopping recording of terminal {terminal_id}: {e}");
+            inner.active.remove(terminal_id);
+        }
+    }
+
+    /// Stops recording `terminal_id` and finalizes its file.
+    pub fn finish(&self, terminal_id: &str) {
+        let recorder = self.lock().active.remove(terminal_id);
+        if let Some(recorder) = recorder
+            && let Err(e) = recorder.finish()
+        {
+            warn!("Failed to finalize recording of terminal {terminal_id}: {e}");
+        }
+    }
+
+    /// Stops recording every terminal of `session_name`.
+    pub fn finish_session(&self, session_name: &str) {
+        let ids: Vec<String> = self
+            .lock()
+            .active
+            .keys()
+            .filter(|id| belongs_to_session(id, session_name))
+            .cloned()
+            .collect();
+        for id in ids {
+            self.finish(&id);
+        }
+    }
+
+    fn flush_stale(inner: &Mutex<RecordingsInner>) {
+        let mut inner = inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (id, recorder) in inner.active.iter_mut() {
+            if let Err(e) = recorder.flush_if_stale() {
+                warn!("Failed to flush recording of terminal {id}: {e}");
+            }
+        }
+    }
+
+    /// Flushes idle recorders periodically so quiet terminals don't hold output back.
+    fn ensure_ticker(&self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if self.ticker_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let weak: Weak<Mutex<RecordingsInner>> = Arc::downgrade(&self.inner);
+        handle.spawn(async move {
+            let mut interval = tokio::time::interval(RECORDING_FLUSH_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(inner) = weak.upgrade() else {
+                    break;
+                };
+                Self::flush_stale(&inner);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn recorded_bytes_match_written_output() {
+        let temp = TempDir::new().unwrap();
+        let mut recorder = TerminalRecorder::open(temp.path(), "session-a~1234abcd-top").unwrap();
+        let chunks: Vec<Vec<u8>> = (0..200)
+            .map(|i| format!("\x1b[32mline {i}\x1b[0m\r\n").into_bytes())
+            .chain(std::iter::once(vec![0xff, 0x00, 0x1b]))
+            .collect();
+        for chunk in &chunks {
+            recorder.record(chunk).unwrap();
+        }
+        let path = recorder.active_path();
+        recorder.finish().unwrap();
+
+        assert_eq!(fs::read(path).unwrap(), chunks.concat());
+    }
+
+    #[test]
+    fn rotates_once_file_reaches_limit() {
+        let temp = TempDir::new().unwrap();
+        let mut recorder = TerminalRecorder::with_rotation(temp.path(), "term", 100).unwrap();
+        let data: Vec<u8> = (0..250u32).map(|i| (i % 251) as u8).collect();
+        recorder.record(&data).unwrap();
+        recorder.finish().unwrap();
+
+        let files = list_recordings(temp.path()).unwrap();
+        assert_eq!(
+            files.len(),
+            3,
+            "expected two rotated files and the active one"
+        );
+        assert!(
+            files
+                .iter()
+                .any(|f| f.file_name == "term.log" && f.size_bytes == 50)
+        );
+
+        let mut rotated: Vec<_> = files.iter().filter(|f| f.file_name != "term.log").collect();
+        rotated.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+        let mut combined = Vec::new();
+        for file in rotated {
+            assert_eq!(file.size_bytes, 100);
+            combined.extend(fs::read(&file.path).unwrap());
+        }
+        combined.extend(fs::read(temp.path().join("term.log")).unwrap());
+        assert_eq!(combined, data);
+    }
+
+    #[test]
+    fn attaches_only_terminals_of_recorded_sessions() {
+        let temp = TempDir::new().unwrap();
+        let recordings = TerminalRecordings::default();
+        let dir = temp.path().join("alpha");
+        recordings.set_session("alpha", Some(dir.clone()));
+
+        let top = format!("{}-top", session_terminal_base("alpha"));
+        let other = format!("{}-top", session_terminal_base("beta"));
+        recordings.attach(&top);
+        recordings.attach(&other);
+        assert!(recordings.is_recording(&top));
+        assert!(!recordings.is_recording(&other));
+
+        recordings.record(&top, b"hello");
+        recordings.record(&other, b"ignored");
+        recordings.finish_session("alpha");
+        assert!(!recordings.is_recording(&top));
+
+        assert_eq!(fs::read(dir.join(format!("{top}.log"))).unwrap(), b"hello");
+        assert_eq!(list_recordings(&dir).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn records_pty_output_of_session_terminal() {
+        let temp = TempDir::new().unwrap();
+        let session = format!("recorded-{}", std::process::id());
+        let terminal_id = format!("{}-bottom", session_terminal_base(&session));
+        let dir = temp.path().join(&session);
+
+        let manager = crate::domains::terminal::TerminalManager::new();
+        manager.set_session_recording_target(&session, Some(dir.clone()));
+        manager
+            .create_terminal_with_app(
+                terminal_id.clone(),
+                "/tmp".to_string(),
+                "sh".to_string(),
+                vec!["-c".to_string(), "printf 'audit-trail-marker'".to_string()],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let log = dir.join(format!("{terminal_id}.log"));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let contents = fs::read(&log).unwrap_or_default();
+            if String::from_utf8_lossy(&contents).contains("audit-trail-marker") {
+                break;
+            }
+            assert!(Instant::now() < deadline, "output was not recorded");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let _ = manager.close_terminal(terminal_id).await;
+    }
+
+    #[test]
+    fn missing_directory_lists_no_recordings() {
+        let temp = TempDir::new().unwrap();
+        assert!(
+            list_recordings(&temp.path().join("nope"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,7 +1,19 @@
+use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use vt100::Parser;
 
+/// Upper bound on scrollback kept while rendering a buffer to plain text.
+pub const PLAIN_TEXT_MAX_SCROLLBACK: usize = 10_000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlainTextOptions {
+    pub trim_trailing_whitespace: bool,
+    /// Keep only this many lines from the end of the output.
+    pub max_lines: Option<usize>,
+}
+
 #[derive(Debug)]
 pub struct ScreenSnapshot {
     pub full_hash: u64,
@@ -22,6 +34,13 @@ impl VisibleScreen {
         }
     }
 
+    /// A screen that keeps up to `scrollback` lines scrolled off the top.
+    pub fn with_scrollback(rows: u16, cols: u16, scrollback: usize) -> Self {
+        Self {
+            parser: Parser::new(rows, cols, scrollback),
+        }
+    }
+
     pub fn feed_bytes(&mut self, bytes: &[u8]) {
         self.parser.process(bytes);
     }
@@ -73,4 +92,135 @@ impl VisibleScreen {
     pub fn size(&self) -> (u16, u16) {
         self.parser.screen().size()
     }
+
+    /// Rendered text of the scrollback followed by the screen, one entry per
+    /// physical row together with whether the row wraps into the next one.
+    fn rendered_rows(&mut self) -> Vec<(String, bool)> {
+        let screen = self.parser.screen_mut();
+        let (_, cols) = screen.size();
+        screen.set_scrollback(usize::MAX);
+        let scrolled = screen.scrollback();
+
+        let mut rows = Vec::new();
+        // Scrolling back by `offset` puts scrollback row `scrolled - offset` at the top
+        for offset in (1..=scrolled).rev() {
+            screen.set_scrollback(offset);
+            let text = screen.rows(0, cols).next().unwrap_or_default();
+            rows.push((text, screen.row_wrapped(0)));
+        }
+        screen.set_scrollback(0);
+        for (index, text) in screen.rows(0, cols).enumerate() {
+            let wrapped = u16::try_from(index).is_ok_and(|row| screen.row_wrapped(row));
+            rows.push((text, wrapped));
+        }
+        rows
+    }
+
+    /// The text a user would see, scrollback included, without escape sequences.
+    /// Lines wrapped by the terminal are joined back together.
+    pub fn plain_text(&mut self, options: &PlainTextOptions) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        let mut continues = false;
+        for (text, wrapped) in self.rendered_rows() {
+            match lines.last_mut() {
+                Some(last) if continues => last.push_str(&text),
+                _ => lines.push(text),
+            }
+            continues = wrapped;
+        }
+
+        if options.trim_trailing_whitespace {
+            for line in &mut lines {
+                line.truncate(line.trim_end().len());
+            }
+        }
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        if let Some(max) = options.max_lines {
+            let start = lines.len().saturating_sub(max);
+            lines.drain(..start);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Renders raw terminal output as plain text on a `rows` x `cols` screen.
+pub fn render_plain_text(bytes: &[u8], rows: u16, cols: u16, options: &PlainTextOptions) -> String {
+    let scrollback = options
+        .max_lines
+        .unwrap_or(PLAIN_TEXT_MAX_SCROLLBACK)
+        .min(PLAIN_TEXT_MAX_SCROLLBACK);
+    let mut screen = VisibleScreen::with_scrollback(rows.max(1), cols.max(1), scrollback);
+    screen.feed_bytes(bytes);
+    screen.plain_text(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(bytes: &[u8]) -> String {
+        render_plain_text(bytes, 5, 40, &PlainTextOptions::default())
+    }
+
+    #[test]
+    fn strips_sgr_color_codes() {
+        let raw =
+            b"\x1b[1;32mPASS\x1b[0m tests/api.rs\r\n\x1b[38;5;196mFAIL\x1b[39m tests/ui.rs\r\n";
+        assert_eq!(render(raw), "PASS tests/api.rs\nFAIL tests/ui.rs");
+    }
+
+    #[test]
+    fn applies_carriage_return_overwrites() {
+        let raw = b"Downloading [##        ] 20%\rDownloading [######    ] 60%\rDownloading [##########] 100%\r\ndone\r\n";
+        assert_eq!(render(raw), "Downloading [##########] 100%\ndone");
+    }
+
+    #[test]
+    fn applies_cursor_movement_and_erase() {
+        // Rewrite the previous line in place, as spinners and status lines do
+        let raw = b"building...\r\n\x1b[1A\x1b[2Kbuilt in 2s\r\n";
+        assert_eq!(render(raw), "built in 2s");
+    }
+
+    #[test]
+    fn alternate_screen_content_disappears_after_exit() {
+        let raw =
+            b"$ less notes.txt\r\n\x1b[?1049h\x1b[Hpager page one\x1b[?1049l$ echo ok\r\nok\r\n";
+        assert_eq!(render(raw), "$ less notes.txt\n$ echo ok\nok");
+    }
+
+    #[test]
+    fn shows_alternate_screen_while_active() {
+        let raw = b"$ top\r\n\x1b[?1049h\x1b[H\x1b[7mPID  CPU\x1b[0m\r\n1    0.5";
+        assert_eq!(render(raw), "PID  CPU\n1    0.5");
+    }
+
+    #[test]
+    fn includes_scrollback_and_joins_wrapped_lines() {
+        let mut raw = Vec::new();
+        for i in 0..12 {
+            raw.extend_from_slice(format!("line {i}\r\n").as_bytes());
+        }
+        raw.extend_from_slice("x".repeat(50).as_bytes());
+
+        let text = render(&raw);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "line 0");
+        assert_eq!(lines[11], "line 11");
+        assert_eq!(lines[12], "x".repeat(50));
+    }
+
+    #[test]
+    fn trims_whitespace_and_caps_lines_from_the_end() {
+        let raw = b"first   \r\nsecond\t \r\nthird  \r\n";
+        let options = PlainTextOptions {
+            trim_trailing_whitespace: true,
+            max_lines: Some(2),
+        };
+        assert_eq!(render_plain_text(raw, 5, 40, &options), "second\nthird");
+        assert!(render(raw).starts_with("first   \n"));
+    }
 }
@@ -3,6 +3,10 @@ use similar::{Algorithm, ChangeTag, TextDiff};
 
 const COLLAPSE_THRESHOLD: usize = 4;
 const CONTEXT_LINES: usize = 3;
+/// Lines longer than this (in bytes) get no intraline ranges.
+const INTRALINE_MAX_LINE_LEN: usize = 2000;
+/// Change blocks with more removed plus added lines than this get no intraline ranges.
+const INTRALINE_MAX_HUNK_LINES: usize = 500;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiffLine {
@@ -19,6 +23,19 @@ pub struct DiffLine {
     pub collapsed_count: Option<usize>,
     #[serde(rename = "collapsedLines")]
     pub collapsed_lines: Option<Vec<DiffLine>>,
+    /// Changed byte ranges within `content` of a removed line, relative to its pair.
+    #[serde(rename = "oldRanges", default, skip_serializing_if = "Option::is_none")]
+    pub old_ranges: Option<Vec<ByteRange>>,
+    /// Changed byte ranges within `content` of an added line, relative to its pair.
+    #[serde(rename = "newRanges", default, skip_serializing_if = "Option::is_none")]
+    pub new_ranges: Option<Vec<ByteRange>>,
+}
+
+/// Half-open byte range `start..end` into a line's content, always on char boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    pub start: usize,
+    pub end: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +79,12 @@ pub struct DiffResponse {
     pub is_binary: Option<bool>,
     #[serde(rename = "unsupportedReason")]
     pub unsupported_reason: Option<String>,
+    /// Headers of the hunks that can be discarded individually, in file order.
+    #[serde(rename = "hunkHeaders", default)]
+    pub hunk_headers: Vec<String>,
+    /// Git blob id of the worktree content the diff was computed from.
+    #[serde(rename = "contentHash")]
+    pub content_hash: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -113,6 +136,8 @@ pub fn compute_unified_diff(old_content: &str, new_content: &str) -> Vec<DiffLin
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 old_line_num += 1;
                 new_line_num += 1;
@@ -126,6 +151,8 @@ pub fn compute_unified_diff(old_content: &str, new_content: &str) -> Vec<DiffLin
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 old_line_num += 1;
             }
@@ -138,6 +165,8 @@ pub fn compute_unified_diff(old_content: &str, new_content: &str) -> Vec<DiffLin
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 new_line_num += 1;
             }
@@ -189,6 +218,8 @@ pub fn add_collapsible_sections(lines: Vec<DiffLine>) -> Vec<DiffLine> {
                         is_collapsible: Some(true),
                         collapsed_count: Some(collapsed_count),
                         collapsed_lines: Some(collapsed_lines),
+                        old_ranges: None,
+                        new_ranges: None,
                         old_line_number: lines[collapsed_start].old_line_number,
                         new_line_number: lines[collapsed_start].new_line_number,
                     });
@@ -277,6 +308,8 @@ pub fn add_collapsible_sections_split(split: SplitDiffResult) -> SplitDiffResult
                         is_collapsible: Some(true),
                         collapsed_count: Some(collapsed_count),
                         collapsed_lines: Some(collapsed_left),
+                        old_ranges: None,
+                        new_ranges: None,
                     });
 
                     right_processed.push(DiffLine {
@@ -287,6 +320,8 @@ pub fn add_collapsible_sections_split(split: SplitDiffResult) -> SplitDiffResult
                         is_collapsible: Some(true),
                         collapsed_count: Some(collapsed_count),
                         collapsed_lines: Some(collapsed_right),
+                        old_ranges: None,
+                        new_ranges: None,
                     });
                 }
 
@@ -320,13 +355,21 @@ fn flush_pending_split_changes(
     right_lines: &mut Vec<DiffLine>,
     pending_deletes: &mut Vec<(String, usize)>,
     pending_inserts: &mut Vec<(String, usize)>,
+    with_intraline: bool,
 ) {
+    let with_intraline =
+        with_intraline && pending_deletes.len() + pending_inserts.len() <= INTRALINE_MAX_HUNK_LINES;
     let mut deletes = pending_deletes.drain(..);
     let mut inserts = pending_inserts.drain(..);
 
     loop {
         match (deletes.next(), inserts.next()) {
             (Some((deleted_content, deleted_line)), Some((inserted_content, inserted_line))) => {
+                let (old_ranges, new_ranges) = if with_intraline {
+                    compute_intraline_ranges(&deleted_content, &inserted_content).unzip()
+                } else {
+                    (None, None)
+                };
                 left_lines.push(DiffLine {
                     content: deleted_content,
                     line_type: LineType::Removed,
@@ -335,6 +378,8 @@ fn flush_pending_split_changes(
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges,
+                    new_ranges: None,
                 });
                 right_lines.push(DiffLine {
                     content: inserted_content,
@@ -344,6 +389,8 @@ fn flush_pending_split_changes(
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges,
                 });
             }
             (Some((deleted_content, deleted_line)), None) => {
@@ -355,6 +402,8 @@ fn flush_pending_split_changes(
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 right_lines.push(DiffLine {
                     content: String::new(),
@@ -364,6 +413,8 @@ fn flush_pending_split_changes(
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
             }
             (None, Some((inserted_content, inserted_line))) => {
@@ -375,6 +426,8 @@ fn flush_pending_split_changes(
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 right_lines.push(DiffLine {
                     content: inserted_content,
@@ -384,6 +437,8 @@ fn flush_pending_split_changes(
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
             }
             (None, None) => break,
@@ -391,7 +446,65 @@ fn flush_pending_split_changes(
     }
 }
 
+/// Changed byte ranges of a removed line and the added line it is paired with, from a
+/// character-level diff. `None` when either line exceeds `INTRALINE_MAX_LINE_LEN`.
+pub fn compute_intraline_ranges(
+    old_line: &str,
+    new_line: &str,
+) -> Option<(Vec<ByteRange>, Vec<ByteRange>)> {
+    if old_line.len() > INTRALINE_MAX_LINE_LEN || new_line.len() > INTRALINE_MAX_LINE_LEN {
+        return None;
+    }
+
+    let diff = TextDiff::configure()
+        .algorithm(Algorithm::Myers)
+        .diff_chars(old_line, new_line);
+
+    let mut old_ranges: Vec<ByteRange> = Vec::new();
+    let mut new_ranges: Vec<ByteRange> = Vec::new();
+    let mut old_offset = 0;
+    let mut new_offset = 0;
+
+    for change in diff.iter_all_changes() {
+        let len = change.value().len();
+        match change.tag() {
+            ChangeTag::Equal => {
+                old_offset += len;
+                new_offset += len;
+            }
+            ChangeTag::Delete => {
+                push_byte_range(&mut old_ranges, old_offset, old_offset + len);
+                old_offset += len;
+            }
+            ChangeTag::Insert => {
+                push_byte_range(&mut new_ranges, new_offset, new_offset + len);
+                new_offset += len;
+            }
+        }
+    }
+
+    Some((old_ranges, new_ranges))
+}
+
+fn push_byte_range(ranges: &mut Vec<ByteRange>, start: usize, end: usize) {
+    match ranges.last_mut() {
+        Some(last) if last.end == start => last.end = end,
+        _ => ranges.push(ByteRange { start, end }),
+    }
+}
+
 pub fn compute_split_diff(old_content: &str, new_content: &str) -> SplitDiffResult {
+    compute_split_diff_with_intraline(old_content, new_content, false)
+}
+
+/// Like [`compute_split_diff`], additionally attaching intraline ranges to paired
+/// removed/added lines when `with_intraline` is set. Change blocks larger than
+/// `INTRALINE_MAX_HUNK_LINES` are left without ranges.
+pub fn compute_split_diff_with_intraline(
+    old_content: &str,
+    new_content: &str,
+    with_intraline: bool,
+) -> SplitDiffResult {
     let old_text = ensure_trailing_newline(old_content);
     let new_text = ensure_trailing_newline(new_content);
 
@@ -425,6 +538,7 @@ pub fn compute_split_diff(old_content: &str, new_content: &str) -> SplitDiffResu
                     &mut right_lines,
                     &mut pending_deletes,
                     &mut pending_inserts,
+                    with_intraline,
                 );
 
                 left_lines.push(DiffLine {
@@ -435,6 +549,8 @@ pub fn compute_split_diff(old_content: &str, new_content: &str) -> SplitDiffResu
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 right_lines.push(DiffLine {
                     content: content_str,
@@ -444,6 +560,8 @@ pub fn compute_split_diff(old_content: &str, new_content: &str) -> SplitDiffResu
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
                 old_idx += 1;
                 new_idx += 1;
@@ -464,6 +582,7 @@ pub fn compute_split_diff(old_content: &str, new_content: &str) -> SplitDiffResu
         &mut right_lines,
         &mut pending_deletes,
         &mut pending_inserts,
+        with_intraline,
     );
 
     SplitDiffResult {
@@ -788,6 +907,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
         }
 
@@ -816,6 +937,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
         }
 
@@ -853,6 +976,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
 
             // Add enough unchanged lines for collapsible section
@@ -865,6 +990,8 @@ mod tests {
                     is_collapsible: None,
                     collapsed_count: None,
                     collapsed_lines: None,
+                    old_ranges: None,
+                    new_ranges: None,
                 });
             }
         }
@@ -892,6 +1019,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
         }
 
@@ -903,6 +1032,8 @@ mod tests {
             is_collapsible: None,
             collapsed_count: None,
             collapsed_lines: None,
+            old_ranges: None,
+            new_ranges: None,
         });
 
         // Large unchanged block
@@ -915,6 +1046,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
         }
 
@@ -941,6 +1074,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
         }
 
@@ -967,6 +1102,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             });
         }
 
@@ -1240,6 +1377,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "removed".to_string(),
@@ -1249,6 +1388,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "added".to_string(),
@@ -1258,6 +1399,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1277,6 +1420,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "added 2".to_string(),
@@ -1286,6 +1431,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "removed 1".to_string(),
@@ -1295,6 +1442,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "removed 2".to_string(),
@@ -1304,6 +1453,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "removed 3".to_string(),
@@ -1313,6 +1464,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1332,6 +1485,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "collapsed removed".to_string(),
@@ -1341,6 +1496,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1352,6 +1509,8 @@ mod tests {
             is_collapsible: Some(true),
             collapsed_count: Some(2),
             collapsed_lines: Some(collapsed_lines),
+            old_ranges: None,
+            new_ranges: None,
         }];
 
         let stats = calculate_diff_stats(&lines);
@@ -1370,6 +1529,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 2".to_string(),
@@ -1379,6 +1540,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1411,6 +1574,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 2".to_string(),
@@ -1420,6 +1585,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "".to_string(),
@@ -1429,6 +1596,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1441,6 +1610,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 2 modified".to_string(),
@@ -1450,6 +1621,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 3".to_string(),
@@ -1459,6 +1632,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1483,6 +1658,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 2".to_string(),
@@ -1492,6 +1669,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1504,6 +1683,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 2 modified".to_string(),
@@ -1513,6 +1694,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
             DiffLine {
                 content: "line 3".to_string(),
@@ -1522,6 +1705,8 @@ mod tests {
                 is_collapsible: None,
                 collapsed_count: None,
                 collapsed_lines: None,
+                old_ranges: None,
+                new_ranges: None,
             },
         ];
 
@@ -1910,4 +2095,76 @@ mod tests {
         // Should handle mixed line endings
         assert!(result.len() >= 3);
     }
+
+    // ===== Intraline Tests =====
+
+    fn range_slices<'a>(line: &'a str, ranges: &[ByteRange]) -> Vec<&'a str> {
+        ranges
+            .iter()
+            .map(|r| {
+                assert!(line.is_char_boundary(r.start) && line.is_char_boundary(r.end));
+                &line[r.start..r.end]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_intraline_ranges_single_character_edit() {
+        let (old_ranges, new_ranges) =
+            compute_intraline_ranges("let value = 1;", "let value = 2;").unwrap();
+
+        assert_eq!(old_ranges, vec![ByteRange { start: 12, end: 13 }]);
+        assert_eq!(new_ranges, vec![ByteRange { start: 12, end: 13 }]);
+    }
+
+    #[test]
+    fn test_intraline_ranges_multibyte_content_stay_on_char_boundaries() {
+        let old = "naïve café → 日本語";
+        let new = "naive cafés → 日本人";
+
+        let (old_ranges, new_ranges) = compute_intraline_ranges(old, new).unwrap();
+
+        assert_eq!(range_slices(old, &old_ranges), vec!["ï", "語"]);
+        assert_eq!(range_slices(new, &new_ranges), vec!["i", "s", "人"]);
+    }
+
+    #[test]
+    fn test_split_diff_attaches_intraline_ranges_only_when_requested() {
+        let old = "same\nprintln!(\"grüße\");\nend\n";
+        let new = "same\nprintln!(\"grüßen\");\nend\n";
+
+        let plain = compute_split_diff(old, new);
+        assert!(plain.left_lines.iter().all(|l| l.old_ranges.is_none()));
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("oldRanges") && !json.contains("newRanges"));
+
+        let split = compute_split_diff_with_intraline(old, new, true);
+        let removed = &split.left_lines[1];
+        let added = &split.right_lines[1];
+        assert!(matches!(removed.line_type, LineType::Removed));
+        assert_eq!(removed.old_ranges, Some(Vec::new()));
+        assert_eq!(
+            range_slices(&added.content, added.new_ranges.as_ref().unwrap()),
+            vec!["n"]
+        );
+        assert!(split.left_lines[0].old_ranges.is_none());
+    }
+
+    #[test]
+    fn test_intraline_skipped_for_long_lines_and_large_hunks() {
+        let long_old = "a".repeat(INTRALINE_MAX_LINE_LEN + 1);
+        let long_new = format!("{long_old}b");
+        assert!(compute_intraline_ranges(&long_old, &long_new).is_none());
+
+        let old: String = (0..300).map(|i| format!("old {i}\n")).collect();
+        let new: String = (0..300).map(|i| format!("new {i}\n")).collect();
+        let split = compute_split_diff_with_intraline(&old, &new, true);
+        assert!(
+            split
+                .left_lines
+                .iter()
+                .chain(split.right_lines.iter())
+                .all(|l| l.old_ranges.is_none() && l.new_ranges.is_none())
+        );
+    }
 }
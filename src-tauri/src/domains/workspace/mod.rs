@@ -1,9 +1,10 @@
 pub mod diff_engine;
 pub mod file_index;
 pub mod file_utils;
+pub mod watch_filter;
 pub mod watcher;
 
 pub use diff_engine::*;
 pub use file_index::*;
 pub use file_utils::*;
-pub use watcher::FileWatcherManager;
+pub use watcher::{FileWatcherManager, SpecsDirChangeHandler};
@@ -0,0 +1,249 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use log::{error, warn};
+use notify_debouncer_mini::DebounceEventResult;
+use tokio::sync::mpsc;
+use tokio::time::{Instant, timeout_at};
+
+/// Globs every watcher skips before the project's `.gitignore` and overrides apply.
+pub const DEFAULT_WATCH_IGNORE_PATTERNS: &[&str] =
+    &[".git/**", "node_modules/**", "target/**", "dist/**"];
+
+/// Watcher notifications arriving within this window of the first one are merged into a
+/// single `FileChanges` payload.
+pub const CHANGE_BATCH_WINDOW: Duration = Duration::from_millis(300);
+
+/// Writes to the git index, HEAD and branch refs signal commits and branch moves, so
+/// they pass through even though `.git` is ignored.
+pub(crate) fn is_git_commit_signal(path: &Path) -> bool {
+    let Some(path_str) = path.to_str() else {
+        return false;
+    };
+    let in_linked_gitdir = path_str.contains("/.git/worktrees/");
+    path_str.ends_with("/.git/index")
+        || path_str.ends_with("/.git/HEAD")
+        || path_str.contains("/.git/refs/heads/")
+        || (in_linked_gitdir
+            && (path_str.ends_with("/index")
+                || path_str.ends_with("/HEAD")
+                || path_str.contains("/refs/heads/")))
+}
+
+/// Gitignore-style rules for one watched root: the defaults, then the root's
+/// `.gitignore`, then the project's patterns, later rules winning.
+pub struct WatchIgnore {
+    roots: Vec<PathBuf>,
+    matcher: Gitignore,
+}
+
+impl WatchIgnore {
+    pub fn new(root: &Path, extra_patterns: &[String]) -> Self {
+        let mut builder = GitignoreBuilder::new(root);
+        let add_patterns = |builder: &mut GitignoreBuilder, patterns: &[&str]| {
+            for pattern in patterns {
+                if let Err(e) = builder.add_line(None, pattern) {
+                    warn!("Ignoring invalid watcher pattern '{pattern}': {e}");
+                }
+            }
+        };
+        add_patterns(&mut builder, DEFAULT_WATCH_IGNORE_PATTERNS);
+        let gitignore = root.join(".gitignore");
+        if gitignore.is_file()
+            && let Some(e) = builder.add(&gitignore)
+        {
+            warn!("Failed to read {}: {e}", gitignore.display());
+        }
+        let extra: Vec<&str> = extra_patterns.iter().map(String::as_str).collect();
+        add_patterns(&mut builder, &extra);
+        let matcher = builder.build().unwrap_or_else(|e| {
+            warn!(
+                "Failed to build watcher ignore rules for {}: {e}",
+                root.display()
+            );
+            Gitignore::empty()
+        });
+
+        let mut roots = vec![root.to_path_buf()];
+        if let Ok(canonical) = root.canonicalize()
+            && canonical != root
+        {
+            roots.push(canonical);
+        }
+        Self { roots, matcher }
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        if is_git_commit_signal(path) {
+            return false;
+        }
+        let Some(relative) = self
+            .roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .filter(|relative| !relative.as_os_str().is_empty())
+        else {
+            return false;
+        };
+        self.matcher
+            .matched_path_or_any_parents(relative, path.is_dir())
+            .is_ignore()
+    }
+}
+
+/// Distinct paths reported within one batch window and the number of notifications
+/// that reported them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangeBatch {
+    pub paths: BTreeSet<PathBuf>,
+    pub change_count: u32,
+}
+
+impl ChangeBatch {
+    fn add(&mut self, result: DebounceEventResult, is_ignored: &dyn Fn(&Path) -> bool) {
+        match result {
+            Ok(events) => {
+                for event in events {
+                    if !is_ignored(&event.path) {
+                        self.change_count += 1;
+                        self.paths.insert(event.path);
+                    }
+                }
+            }
+            Err(e) => error!("File watcher error: {e:?}"),
+        }
+    }
+}
+
+/// Waits for the next watcher notification with a forwarded path and merges every
+/// notification arriving within `window` of it. Returns `None` once the watcher is gone.
+pub async fn next_change_batch(
+    rx: &mut mpsc::Receiver<DebounceEventResult>,
+    window: Duration,
+    is_ignored: &dyn Fn(&Path) -> bool,
+) -> Option<ChangeBatch> {
+    loop {
+        let mut batch = ChangeBatch::default();
+        batch.add(rx.recv().await?, is_ignored);
+        let deadline = Instant::now() + window;
+        while let Ok(Some(result)) = timeout_at(deadline, rx.recv()).await {
+            batch.add(result, is_ignored);
+        }
+        if !batch.paths.is_empty() {
+            return Some(batch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify_debouncer_mini::{DebouncedEvent, DebouncedEventKind};
+    use tempfile::TempDir;
+
+    fn notification(root: &Path, paths: &[&str]) -> DebounceEventResult {
+        Ok(paths
+            .iter()
+            .map(|path| DebouncedEvent::new(root.join(path), DebouncedEventKind::Any))
+            .collect())
+    }
+
+    #[test]
+    fn defaults_gitignore_and_overrides_are_applied_in_order() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+        let ignore = WatchIgnore::new(
+            root,
+            &["coverage/**".to_string(), "!dist/keep.js".to_string()],
+        );
+
+        for ignored in [
+            "node_modules/react/index.js",
+            "target/debug/app",
+            "dist/bundle.js",
+            ".git/objects/ab/cdef",
+            "server.log",
+            "build/out.o",
+            "coverage/lcov.info",
+        ] {
+            assert!(ignore.is_ignored(&root.join(ignored)), "{ignored}");
+        }
+        for watched in ["src/main.rs", "dist/keep.js", ".git/index", ".git/HEAD"] {
+            assert!(!ignore.is_ignored(&root.join(watched)), "{watched}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rapid_notifications_are_batched_and_deduplicated() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let ignore = WatchIgnore::new(&root, &[]);
+        let (tx, mut rx) = mpsc::channel(16);
+
+        tx.send(notification(&root, &["src/a.rs", "node_modules/x.js"]))
+            .await
+            .unwrap();
+        tx.send(notification(&root, &["src/a.rs", "src/b.rs"]))
+            .await
+            .unwrap();
+        tx.send(notification(&root, &["target/debug/app"]))
+            .await
+            .unwrap();
+        let late_root = root.clone();
+        let late = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(600)).await;
+            tx.send(notification(&late_root, &["src/c.rs"]))
+                .await
+                .unwrap();
+        });
+
+        let is_ignored = |path: &Path| ignore.is_ignored(path);
+        let batch = next_change_batch(&mut rx, CHANGE_BATCH_WINDOW, &is_ignored)
+            .await
+            .unwrap();
+        assert_eq!(
+            batch.paths,
+            BTreeSet::from([root.join("src/a.rs"), root.join("src/b.rs")])
+        );
+        assert_eq!(batch.change_count, 3);
+
+        let batch = next_change_batch(&mut rx, CHANGE_BATCH_WINDOW, &is_ignored)
+            .await
+            .unwrap();
+        assert_eq!(batch.paths, BTreeSet::from([root.join("src/c.rs")]));
+        assert_eq!(batch.change_count, 1);
+
+        late.await.unwrap();
+        assert!(
+            next_change_batch(&mut rx, CHANGE_BATCH_WINDOW, &is_ignored)
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn notifications_under_ignored_paths_only_emit_nothing() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let ignore = WatchIgnore::new(&root, &[]);
+        let (tx, mut rx) = mpsc::channel(16);
+
+        tx.send(notification(&root, &["node_modules/a.js", "dist/app.js"]))
+            .await
+            .unwrap();
+        tx.send(notification(&root, &[".git/objects/12/345"]))
+            .await
+            .unwrap();
+        drop(tx);
+
+        let is_ignored = |path: &Path| ignore.is_ignored(path);
+        assert!(
+            next_change_batch(&mut rx, CHANGE_BATCH_WINDOW, &is_ignored)
+                .await
+                .is_none()
+        );
+    }
+}
@@ -14,6 +14,9 @@ use tokio::task::spawn_blocking;
 use tokio::time::sleep;
 
 use super::file_index::refresh_project_files;
+use super::watch_filter::{
+    CHANGE_BATCH_WINDOW, ChangeBatch, WatchIgnore, is_git_commit_signal, next_change_batch,
+};
 
 use crate::domains::git::service as git;
 use crate::shared::merge_snapshot_gateway::MergeSnapshotGateway;
@@ -27,6 +30,12 @@ pub struct FileChangeEvent {
     pub change_summary: ChangeSummary,
     pub branch_info: BranchInfo,
     pub timestamp: u64,
+    /// Distinct paths, relative to the watched root, that changed in this batch.
+    #[serde(default)]
+    pub changed_paths: Vec<String>,
+    /// Watcher notifications merged into this payload.
+    #[serde(default)]
+    pub change_count: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +57,9 @@ pub struct BranchInfo {
 
 const ORCHESTRATOR_REFRESH_INTERVAL: Duration = Duration::from_millis(750);
 
+/// Short debounce so [`CHANGE_BATCH_WINDOW`] decides how notifications are grouped.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
 const ORCHESTRATOR_WATCHER_KEY: &str = "__orchestrator__";
 const ORCHESTRATOR_SESSION_NAME: &str = "orchestrator";
 static ORCHESTRATOR_REFRESH_PROVIDER: OnceLock<RwLock<Option<Arc<dyn OrchestratorIndexRefresh>>>> =
@@ -113,56 +125,53 @@ fn short_oid(repo: &Repository, oid: Oid) -> String {
 }
 
 impl FileWatcher {
+    /// Watches `worktree_path`, skipping paths matched by the default globs, the
+    /// worktree's `.gitignore` and `ignore_patterns`.
     pub fn new(
         session_name: String,
         worktree_path: PathBuf,
         base_branch: String,
+        ignore_patterns: &[String],
         app_handle: AppHandle,
     ) -> Result<Self, String> {
         let (tx, mut rx) = mpsc::channel(100);
 
-        let debouncer = new_debouncer(
-            Duration::from_millis(500),
-            move |result: DebounceEventResult| {
-                if let Err(e) = tx.blocking_send(result) {
-                    error!("Failed to send file watch event: {e}");
-                }
-            },
-        )
+        let debouncer = new_debouncer(WATCH_DEBOUNCE, move |result: DebounceEventResult| {
+            if let Err(e) = tx.blocking_send(result) {
+                error!("Failed to send file watch event: {e}");
+            }
+        })
         .map_err(|e| format!("Failed to create debouncer: {e}"))?;
 
         let session_name_clone = session_name.clone();
         let worktree_path_clone = worktree_path.clone();
         let base_branch_clone = base_branch.clone();
         let app_handle_clone = app_handle.clone();
+        let ignore = WatchIgnore::new(&worktree_path, ignore_patterns);
 
         tokio::spawn(async move {
-            while let Some(result) = rx.recv().await {
-                match result {
-                    Ok(events) => {
-                        trace!(
-                            "File watcher received {} events for session {}",
-                            events.len(),
-                            session_name_clone
-                        );
-
-                        if let Err(e) = Self::handle_file_changes(
-                            &session_name_clone,
-                            &worktree_path_clone,
-                            &base_branch_clone,
-                            &app_handle_clone,
-                            events,
-                        )
-                        .await
-                        {
-                            warn!(
-                                "Failed to handle file changes for session {session_name_clone}: {e}"
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        error!("File watcher error for session {session_name_clone}: {e:?}");
-                    }
+            let is_ignored =
+                |path: &Path| Self::should_ignore_path(path) || ignore.is_ignored(path);
+            while let Some(batch) =
+                next_change_batch(&mut rx, CHANGE_BATCH_WINDOW, &is_ignored).await
+            {
+                trace!(
+                    "File watcher batched {} changes to {} paths for session {}",
+                    batch.change_count,
+                    batch.paths.len(),
+                    session_name_clone
+                );
+
+                if let Err(e) = Self::handle_file_changes(
+                    &session_name_clone,
+                    &worktree_path_clone,
+                    &base_branch_clone,
+                    &app_handle_clone,
+                    batch,
+                )
+                .await
+                {
+                    warn!("Failed to handle file changes for session {session_name_clone}: {e}");
                 }
             }
         });
@@ -240,22 +249,14 @@ impl FileWatcher {
         worktree_path: &Path,
         base_branch: &str,
         app_handle: &AppHandle,
-        events: Vec<notify_debouncer_mini::DebouncedEvent>,
+        batch: ChangeBatch,
     ) -> Result<(), String> {
-        let should_ignore_event = events
-            .iter()
-            .all(|event| Self::should_ignore_path(&event.path));
-
-        if should_ignore_event {
-            return Ok(());
-        }
-
         // Identify commit signals so we can correlate immediate updates after commit
         let mut saw_index = false;
         let mut saw_head = false;
         let mut saw_refs = false;
-        for ev in &events {
-            if let Some(p) = ev.path.to_str() {
+        for path in &batch.paths {
+            if let Some(p) = path.to_str() {
                 // Standard repo layout
                 if p.ends_with("/.git/index") {
                     saw_index = true;
@@ -277,11 +278,7 @@ impl FileWatcher {
         }
         debug!(
             "Processing file changes for session {}: {} events (commit_signals index:{} head:{} refs:{})",
-            session_name,
-            events.len(),
-            saw_index,
-            saw_head,
-            saw_refs
+            session_name, batch.change_count, saw_index, saw_head, saw_refs
         );
 
         let changed_files = git::get_changed_files(worktree_path, base_branch)
@@ -304,12 +301,25 @@ impl FileWatcher {
             .unwrap()
             .as_millis() as u64;
 
+        let changed_paths = batch
+            .paths
+            .iter()
+            .map(|path| {
+                path.strip_prefix(worktree_path)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+
         let file_change_event = FileChangeEvent {
             session_name: session_name.to_string(),
             changed_files,
             change_summary,
             branch_info,
             timestamp,
+            changed_paths,
+            change_count: batch.change_count,
         };
 
         debug!(
@@ -409,16 +419,8 @@ impl FileWatcher {
         if let Some(path_str) = path.to_str() {
             // Treat critical .git files as signalers of commits/branch moves
             if path_str.contains("/.git/") {
-                let is_index = path_str.ends_with("/.git/index")
-                    || (path_str.contains("/.git/worktrees/") && path_str.ends_with("/index"));
-                let is_head = path_str.ends_with("/.git/HEAD")
-                    || (path_str.contains("/.git/worktrees/") && path_str.ends_with("/HEAD"));
-                let is_ref_head = path_str.contains("/.git/refs/heads/")
-                    || path_str.contains("/.git/worktrees/") && path_str.contains("/refs/heads/");
-                if is_index || is_head || is_ref_head {
-                    return false; // do not ignore -> we want immediate updates on commit
-                }
-                return true; // ignore other .git noise
+                // do not ignore commit signals -> we want immediate updates on commit
+                return !is_git_commit_signal(path);
             }
             path_str.contains("/node_modules/")
                 || path_str.contains("/target/")
@@ -572,8 +574,85 @@ impl FileWatcher {
     }
 }
 
+/// Receives the spec files that changed in a watched specs directory after debouncing.
+pub type SpecsDirChangeHandler = Arc<dyn Fn(Vec<PathBuf>) + Send + Sync>;
+
+fn is_spec_dir_noise(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return true;
+    };
+    file_name.ends_with('~') || file_name.ends_with(".swp") || file_name.ends_with(".tmp")
+}
+
+/// Collapses a debounced batch into the distinct paths worth syncing, dropping editor
+/// swap, backup and temp files.
+fn collect_spec_dir_changes(events: &[notify_debouncer_mini::DebouncedEvent]) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = events
+        .iter()
+        .map(|event| event.path.clone())
+        .filter(|path| !is_spec_dir_noise(path))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+pub struct SpecsDirWatcher {
+    _dir: PathBuf,
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl SpecsDirWatcher {
+    fn new(dir: PathBuf, on_change: SpecsDirChangeHandler) -> Result<Self, String> {
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(500),
+            move |result: DebounceEventResult| {
+                if let Err(e) = tx.blocking_send(result) {
+                    error!("Failed to send specs directory event: {e}");
+                }
+            },
+        )
+        .map_err(|e| format!("Failed to create debouncer: {e}"))?;
+
+        let dir_display = dir.display().to_string();
+        tokio::spawn(async move {
+            while let Some(result) = rx.recv().await {
+                match result {
+                    Ok(events) => {
+                        let paths = collect_spec_dir_changes(&events);
+                        if paths.is_empty() {
+                            continue;
+                        }
+                        let handler = Arc::clone(&on_change);
+                        if let Err(e) = spawn_blocking(move || handler(paths)).await {
+                            warn!("Spec directory sync task failed for {dir_display}: {e}");
+                        }
+                    }
+                    Err(e) => {
+                        error!("Specs directory watcher error for {dir_display}: {e:?}");
+                    }
+                }
+            }
+        });
+
+        debouncer
+            .watcher()
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to start watching {}: {e}", dir.display()))?;
+        info!("Started watching specs directory {}", dir.display());
+
+        Ok(Self {
+            _dir: dir,
+            _debouncer: debouncer,
+        })
+    }
+}
+
 pub struct FileWatcherManager {
     watchers: Arc<Mutex<HashMap<String, FileWatcher>>>,
+    specs_dir_watchers: Arc<Mutex<HashMap<PathBuf, SpecsDirWatcher>>>,
     app_handle: AppHandle,
     orchestrator_state: Arc<Mutex<OrchestratorRefreshState>>,
 }
@@ -582,6 +661,7 @@ impl FileWatcherManager {
     pub fn new(app_handle: AppHandle) -> Self {
         Self {
             watchers: Arc::new(Mutex::new(HashMap::new())),
+            specs_dir_watchers: Arc::new(Mutex::new(HashMap::new())),
             app_handle,
             orchestrator_state: Arc::new(Mutex::new(OrchestratorRefreshState {
                 last_refresh: None,
@@ -685,6 +765,7 @@ impl FileWatcherManager {
         session_name: String,
         worktree_path: PathBuf,
         base_branch: String,
+        ignore_patterns: &[String],
     ) -> Result<(), String> {
         let mut watchers = self.watchers.lock().await;
 
@@ -697,6 +778,7 @@ impl FileWatcherManager {
             session_name.clone(),
             worktree_path,
             base_branch,
+            ignore_patterns,
             self.app_handle.clone(),
         )?;
 
@@ -742,6 +824,7 @@ impl FileWatcherManager {
         &self,
         repo_path: PathBuf,
         base_branch: String,
+        ignore_patterns: &[String],
     ) -> Result<(), String> {
         let mut watchers = self.watchers.lock().await;
 
@@ -754,6 +837,7 @@ impl FileWatcherManager {
             ORCHESTRATOR_SESSION_NAME.to_string(),
             repo_path.clone(),
             base_branch,
+            ignore_patterns,
             self.app_handle.clone(),
         )?;
 
@@ -776,6 +860,35 @@ impl FileWatcherManager {
 
         Ok(())
     }
+
+    pub async fn start_watching_specs_dir(
+        &self,
+        dir: PathBuf,
+        on_change: SpecsDirChangeHandler,
+    ) -> Result<(), String> {
+        let mut watchers = self.specs_dir_watchers.lock().await;
+
+        if watchers.contains_key(&dir) {
+            debug!("Already watching specs directory {}", dir.display());
+            return Ok(());
+        }
+
+        let watcher = SpecsDirWatcher::new(dir.clone(), on_change)?;
+        watchers.insert(dir, watcher);
+        Ok(())
+    }
+
+    pub async fn stop_watching_specs_dir(&self, dir: &Path) -> Result<(), String> {
+        let mut watchers = self.specs_dir_watchers.lock().await;
+
+        if watchers.remove(dir).is_some() {
+            info!("Stopped watching specs directory {}", dir.display());
+        } else {
+            debug!("Specs directory {} was not being watched", dir.display());
+        }
+
+        Ok(())
+    }
 }
 
 impl OrchestratorIndexRefresh for FileWatcherManager {
@@ -1016,6 +1129,8 @@ mod tests {
                 head_commit: "def789ghi012".to_string(),
             },
             timestamp: 1234567890123,
+            changed_paths: Vec::new(),
+            change_count: 0,
         };
 
         let json = serde_json::to_string(&event);
@@ -1493,6 +1608,8 @@ mod tests {
                 head_commit: "def456".to_string(),
             },
             timestamp: 1234567890,
+            changed_paths: Vec::new(),
+            change_count: 0,
         };
 
         // Serialize and check size is reasonable
@@ -1522,6 +1639,8 @@ mod tests {
                 head_commit: "1234567890abcdef1234567890abcdef".to_string(),
             },
             timestamp: 1234567890123456789,
+            changed_paths: Vec::new(),
+            change_count: 0,
         };
 
         let json_large = serde_json::to_string(&event_with_files).unwrap();
@@ -1662,6 +1781,8 @@ mod tests {
                 head_commit: "abc123".to_string(),
             },
             timestamp: 1234567890,
+            changed_paths: Vec::new(),
+            change_count: 0,
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -1701,6 +1822,8 @@ mod tests {
                 head_commit: "def456".to_string(),
             },
             timestamp: 1234567890123,
+            changed_paths: Vec::new(),
+            change_count: 0,
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -1825,4 +1948,24 @@ mod tests {
         assert!(branch_info.base_commit.len() == 40); // SHA-1 hash length
         assert!(branch_info.head_commit.len() == 40);
     }
+
+    #[test]
+    fn spec_dir_changes_skip_editor_noise_and_dedupe() {
+        use notify_debouncer_mini::{DebouncedEvent, DebouncedEventKind};
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let event = |name: &str| DebouncedEvent::new(dir.join(name), DebouncedEventKind::Any);
+
+        let changes = collect_spec_dir_changes(&[
+            event("beta.md"),
+            event(".beta.md.swp"),
+            event("beta.md~"),
+            event("alpha.md.tmp"),
+            event("alpha.md"),
+            event("beta.md"),
+        ]);
+
+        assert_eq!(changes, vec![dir.join("alpha.md"), dir.join("beta.md")]);
+    }
 }
@@ -63,6 +63,21 @@ pub enum SchaltError {
         feature: String,
         platform: String,
     },
+    SkipPermissionsConfirmationRequired {
+        session_id: String,
+        agent_type: String,
+        token: String,
+    },
+    SkipPermissionsForbidden {
+        session_id: String,
+        agent_type: String,
+    },
+    StaleDiff {
+        path: String,
+    },
+    ReadOnlyMode {
+        command: String,
+    },
 }
 
 impl SchaltError {
@@ -173,6 +188,37 @@ impl fmt::Display for SchaltError {
             Self::NotSupported { feature, platform } => {
                 write!(f, "Feature '{feature}' is not supported on {platform}")
             }
+            Self::SkipPermissionsConfirmationRequired {
+                session_id,
+                agent_type,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Starting {agent_type} in session '{session_id}' with skipped permissions needs confirmation"
+                )
+            }
+            Self::SkipPermissionsForbidden {
+                session_id,
+                agent_type,
+            } => {
+                write!(
+                    f,
+                    "This project forbids starting {agent_type} in session '{session_id}' with skipped permissions"
+                )
+            }
+            Self::StaleDiff { path } => {
+                write!(
+                    f,
+                    "'{path}' changed since its diff was loaded; refresh and try again"
+                )
+            }
+            Self::ReadOnlyMode { command } => {
+                write!(
+                    f,
+                    "'{command}' is not available while the project is open read-only"
+                )
+            }
         }
     }
 }
@@ -184,3 +230,285 @@ impl From<SchaltError> for String {
         error.to_string()
     }
 }
+
+/// Stable, machine-readable category of a [`CommandError`].
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    NoActiveProject,
+    SessionNotFound,
+    SessionAlreadyExists,
+    InvalidSessionState,
+    WorktreeNotFound,
+    WorktreeAlreadyExists,
+    GitOperationFailed,
+    MergeConflict,
+    DatabaseError,
+    DatabaseBusy,
+    InvalidInput,
+    TerminalNotFound,
+    TerminalOperationFailed,
+    ProjectNotFound,
+    IoError,
+    AgentNotFound,
+    AgentBinaryMissing,
+    ConfigError,
+    NotSupported,
+    SkipPermissionsConfirmationRequired,
+    SkipPermissionsForbidden,
+    StaleDiff,
+    ReadOnlyMode,
+    Timeout,
+    Internal,
+}
+
+/// Error returned by Tauri commands. `code` lets the frontend branch on the failure,
+/// `message` is the same text the command used to return as a plain string, and
+/// `details` carries the originating [`SchaltError`] or library error data when known.
+#[derive(Debug, Serialize, Clone)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Option<serde_json::Value>,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    fn from_sqlite(error: &rusqlite::Error) -> Self {
+        let code = match error.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) => {
+                ErrorCode::DatabaseBusy
+            }
+            _ => ErrorCode::DatabaseError,
+        };
+        Self::new(code, error.to_string())
+    }
+
+    fn from_git(error: &git2::Error) -> Self {
+        let code = match error.code() {
+            git2::ErrorCode::Conflict
+            | git2::ErrorCode::MergeConflict
+            | git2::ErrorCode::Unmerged => ErrorCode::MergeConflict,
+            _ => ErrorCode::GitOperationFailed,
+        };
+        Self::new(code, error.message()).with_details(serde_json::json!({
+            "class": format!("{:?}", error.class()),
+            "code": format!("{:?}", error.code()),
+        }))
+    }
+}
+
+/// Recognises the untyped failures domain code reports through `anyhow!`/`format!`.
+fn classify_message(message: &str) -> Option<ErrorCode> {
+    let normalized = message.to_lowercase();
+    if normalized.contains("no active project") {
+        Some(ErrorCode::NoActiveProject)
+    } else if normalized.contains("database is locked") || normalized.contains("database is busy") {
+        Some(ErrorCode::DatabaseBusy)
+    } else if normalized.contains("failed to spawn command") {
+        Some(ErrorCode::AgentBinaryMissing)
+    } else if normalized.contains("session not found")
+        || (normalized.starts_with("session '") && normalized.contains("' not found"))
+    {
+        Some(ErrorCode::SessionNotFound)
+    } else if normalized.contains("merge conflict") {
+        Some(ErrorCode::MergeConflict)
+    } else {
+        None
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        error.message
+    }
+}
+
+impl From<SchaltError> for CommandError {
+    fn from(error: SchaltError) -> Self {
+        let code = match &error {
+            SchaltError::SessionNotFound { .. } => ErrorCode::SessionNotFound,
+            SchaltError::SessionAlreadyExists { .. } => ErrorCode::SessionAlreadyExists,
+            SchaltError::WorktreeNotFound { .. } => ErrorCode::WorktreeNotFound,
+            SchaltError::WorktreeAlreadyExists { .. } => ErrorCode::WorktreeAlreadyExists,
+            SchaltError::GitOperationFailed { .. } => ErrorCode::GitOperationFailed,
+            SchaltError::DatabaseError { message } => {
+                classify_message(message).unwrap_or(ErrorCode::DatabaseError)
+            }
+            SchaltError::InvalidInput { .. } => ErrorCode::InvalidInput,
+            SchaltError::TerminalNotFound { .. } => ErrorCode::TerminalNotFound,
+            SchaltError::TerminalOperationFailed { .. } => ErrorCode::TerminalOperationFailed,
+            SchaltError::ProjectNotFound { .. } => ErrorCode::ProjectNotFound,
+            SchaltError::IoError { .. } => ErrorCode::IoError,
+            SchaltError::MergeConflict { .. } => ErrorCode::MergeConflict,
+            SchaltError::InvalidSessionState { .. } => ErrorCode::InvalidSessionState,
+            SchaltError::AgentNotFound { .. } => ErrorCode::AgentNotFound,
+            SchaltError::ConfigError { .. } => ErrorCode::ConfigError,
+            SchaltError::NotSupported { .. } => ErrorCode::NotSupported,
+            SchaltError::SkipPermissionsConfirmationRequired { .. } => {
+                ErrorCode::SkipPermissionsConfirmationRequired
+            }
+            SchaltError::SkipPermissionsForbidden { .. } => ErrorCode::SkipPermissionsForbidden,
+            SchaltError::StaleDiff { .. } => ErrorCode::StaleDiff,
+            SchaltError::ReadOnlyMode { .. } => ErrorCode::ReadOnlyMode,
+        };
+        Self {
+            code,
+            message: error.to_string(),
+            details: serde_json::to_value(&error).ok(),
+        }
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        let code = classify_message(&message).unwrap_or(ErrorCode::Internal);
+        Self::new(code, message)
+    }
+}
+
+impl From<rusqlite::Error> for CommandError {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::from_sqlite(&error)
+    }
+}
+
+impl From<git2::Error> for CommandError {
+    fn from(error: git2::Error) -> Self {
+        Self::from_git(&error)
+    }
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(error: anyhow::Error) -> Self {
+        let message = error.to_string();
+        if let Some(schalt) = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<SchaltError>())
+        {
+            return Self {
+                message,
+                ..Self::from(schalt.clone())
+            };
+        }
+
+        let typed = error.chain().find_map(|cause| {
+            cause
+                .downcast_ref::<rusqlite::Error>()
+                .map(Self::from_sqlite)
+                .or_else(|| cause.downcast_ref::<git2::Error>().map(Self::from_git))
+        });
+        let code = classify_message(&message)
+            .or(typed.as_ref().map(|typed| typed.code))
+            .unwrap_or(ErrorCode::Internal);
+        Self {
+            code,
+            message,
+            details: typed.and_then(|typed| typed.details),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{Context, anyhow};
+
+    #[test]
+    fn schalt_errors_keep_their_code_message_and_payload() {
+        let error = CommandError::from(SchaltError::SessionNotFound {
+            session_id: "demo".to_string(),
+        });
+
+        assert_eq!(error.code, ErrorCode::SessionNotFound);
+        assert_eq!(error.message, "Session 'demo' not found");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "SessionNotFound");
+        assert_eq!(json["details"]["type"], "SessionNotFound");
+        assert_eq!(json["details"]["data"]["session_id"], "demo");
+    }
+
+    #[test]
+    fn missing_project_maps_to_no_active_project() {
+        let from_domain = CommandError::from(anyhow!("No active project"));
+        assert_eq!(from_domain.code, ErrorCode::NoActiveProject);
+
+        let from_getter =
+            CommandError::from("Failed to get Schaltwerk core: No active project".to_string());
+        assert_eq!(from_getter.code, ErrorCode::NoActiveProject);
+        assert_eq!(
+            String::from(from_getter),
+            "Failed to get Schaltwerk core: No active project"
+        );
+    }
+
+    #[test]
+    fn busy_database_maps_through_context() {
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            None,
+        );
+        let error = CommandError::from(
+            Err::<(), _>(busy)
+                .context("Failed to load session")
+                .unwrap_err(),
+        );
+
+        assert_eq!(error.code, ErrorCode::DatabaseBusy);
+        assert_eq!(error.message, "Failed to load session");
+    }
+
+    #[test]
+    fn git_conflicts_map_to_merge_conflict() {
+        let conflict = git2::Error::new(
+            git2::ErrorCode::MergeConflict,
+            git2::ErrorClass::Merge,
+            "conflicts prevent merge",
+        );
+        let error = CommandError::from(anyhow::Error::new(conflict).context("Merge failed"));
+
+        assert_eq!(error.code, ErrorCode::MergeConflict);
+        assert_eq!(error.message, "Merge failed");
+        assert!(error.details.is_some());
+    }
+
+    #[test]
+    fn schalt_error_wrapped_in_anyhow_keeps_its_code() {
+        let error = CommandError::from(anyhow::Error::new(SchaltError::SessionAlreadyExists {
+            session_id: "demo".to_string(),
+        }));
+
+        assert_eq!(error.code, ErrorCode::SessionAlreadyExists);
+    }
+
+    #[test]
+    fn spawn_failures_and_unknown_messages_are_classified() {
+        let spawn = CommandError::from(
+            "Failed to spawn command: No such file or directory (os error 2)".to_string(),
+        );
+        assert_eq!(spawn.code, ErrorCode::AgentBinaryMissing);
+
+        let unknown = CommandError::from("Something odd happened".to_string());
+        assert_eq!(unknown.code, ErrorCode::Internal);
+        assert_eq!(unknown.to_string(), "Something odd happened");
+    }
+}
@@ -36,6 +36,10 @@ pub enum SchaltEvent {
     OpenPrModal,
     OpenMergeModal,
     SelectAllRequested,
+    OperationsRecovered,
+    AutoPrCreated,
+    ScheduledSpecsChanged,
+    RunScriptCompleted,
 }
 
 impl SchaltEvent {
@@ -76,6 +80,10 @@ impl SchaltEvent {
             SchaltEvent::OpenPrModal => "schaltwerk:open-pr-modal",
             SchaltEvent::OpenMergeModal => "schaltwerk:open-merge-modal",
             SchaltEvent::SelectAllRequested => "schaltwerk:select-all-requested",
+            SchaltEvent::OperationsRecovered => "schaltwerk:operations-recovered",
+            SchaltEvent::AutoPrCreated => "schaltwerk:auto-pr-created",
+            SchaltEvent::ScheduledSpecsChanged => "schaltwerk:scheduled-specs-changed",
+            SchaltEvent::RunScriptCompleted => "schaltwerk:run-script-completed",
         }
     }
 }
@@ -102,6 +110,32 @@ pub struct CloneProgressPayload {
 pub struct ProjectValidationErrorPayload {
     pub path: String,
     pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_old_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationsRecoveredPayload {
+    pub project_path: String,
+    pub operations: Vec<schaltwerk::services::RecoveredOperation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledSpecsChangedPayload {
+    pub project_path: String,
+    pub entries: Vec<schaltwerk::services::ScheduledSpec>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunScriptCompletedPayload {
+    pub terminal_id: String,
+    pub session_name: Option<String>,
+    pub run_id: u64,
+    /// `None` when the run terminal closed before the script reported an exit code.
+    pub exit_code: Option<i32>,
 }
 
 pub fn emit_event<T: Serialize + Clone>(
@@ -0,0 +1,257 @@
+use crate::domains::agents::{AgentOutputScanner, AgentOutputStatus};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+/// Latest status parsed from the output of a session's agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionAgentStatus {
+    pub session_name: String,
+    pub status: AgentOutputStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An agent waiting on an approval prompt.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalRequest {
+    pub session_name: String,
+    pub terminal_id: String,
+    pub prompt: String,
+    pub since: DateTime<Utc>,
+}
+
+/// An approval prompt appeared (`prompt` is set) or was answered.
+#[derive(Debug, Clone)]
+pub struct ApprovalChange {
+    pub session_name: String,
+    pub terminal_id: String,
+    pub prompt: Option<String>,
+}
+
+struct TrackedAgent {
+    session_name: String,
+    scanner: AgentOutputScanner,
+    updated_at: DateTime<Utc>,
+    awaiting_since: Option<DateTime<Utc>>,
+}
+
+impl TrackedAgent {
+    fn snapshot(&self) -> SessionAgentStatus {
+        SessionAgentStatus {
+            session_name: self.session_name.clone(),
+            status: self.scanner.status().clone(),
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+type AgentStatusListener = Arc<dyn Fn(SessionAgentStatus) + Send + Sync>;
+type ApprovalListener = Arc<dyn Fn(ApprovalChange) + Send + Sync>;
+
+/// Agent terminals whose output is parsed, keyed by terminal id.
+static TRACKED_AGENTS: LazyLock<Mutex<HashMap<String, TrackedAgent>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static STATUS_LISTENER: LazyLock<RwLock<Option<AgentStatusListener>>> =
+    LazyLock::new(|| RwLock::new(None));
+static APPROVAL_LISTENER: LazyLock<RwLock<Option<ApprovalListener>>> =
+    LazyLock::new(|| RwLock::new(None));
+/// Terminal the user is looking at; its prompts do not raise attention.
+static FOCUSED_TERMINAL: LazyLock<RwLock<Option<String>>> = LazyLock::new(|| RwLock::new(None));
+
+fn with_tracked<T>(f: impl FnOnce(&mut HashMap<String, TrackedAgent>) -> T) -> T {
+    let mut guard = TRACKED_AGENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut guard)
+}
+
+/// Registers the callback that receives every status change. Replaces any earlier one.
+pub fn set_agent_status_listener(listener: impl Fn(SessionAgentStatus) + Send + Sync + 'static) {
+    let mut guard = STATUS_LISTENER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = Some(Arc::new(listener));
+}
+
+/// Registers the callback that receives approval prompts of unfocused terminals and
+/// their answers. Replaces any earlier one.
+pub fn set_approval_listener(listener: impl Fn(ApprovalChange) + Send + Sync + 'static) {
+    let mut guard = APPROVAL_LISTENER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = Some(Arc::new(listener));
+}
+
+pub fn set_focused_agent_terminal(terminal_id: Option<String>) {
+    let mut guard = FOCUSED_TERMINAL
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = terminal_id;
+}
+
+fn is_focused_terminal(terminal_id: &str) -> bool {
+    FOCUSED_TERMINAL
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_deref()
+        == Some(terminal_id)
+}
+
+/// Starts parsing the output of `terminal_id` with the parser for `agent_type`,
+/// discarding the status of an earlier run.
+pub fn track_agent_terminal(terminal_id: &str, session_name: &str, agent_type: &str) {
+    let tracked = TrackedAgent {
+        session_name: session_name.to_string(),
+        scanner: AgentOutputScanner::for_agent(agent_type),
+        updated_at: Utc::now(),
+        awaiting_since: None,
+    };
+    with_tracked(|agents| agents.insert(terminal_id.to_string(), tracked));
+}
+
+pub fn untrack_agent_terminal(terminal_id: &str) {
+    with_tracked(|agents| agents.remove(terminal_id));
+}
+
+/// Parses output of the agent terminal `terminal_id` and reports status changes.
+pub fn record_agent_status_output(terminal_id: &str, data: &[u8]) {
+    let changed = with_tracked(|agents| {
+        let agent = agents.get_mut(terminal_id)?;
+        let previous_prompt = agent.scanner.status().prompt.clone();
+        agent.scanner.feed(data)?;
+        agent.updated_at = Utc::now();
+
+        let prompt = agent.scanner.status().prompt.clone();
+        let approval = (prompt != previous_prompt).then(|| ApprovalChange {
+            session_name: agent.session_name.clone(),
+            terminal_id: terminal_id.to_string(),
+            prompt: prompt.clone(),
+        });
+        match (&previous_prompt, &prompt) {
+            (None, Some(_)) => agent.awaiting_since = Some(agent.updated_at),
+            (_, None) => agent.awaiting_since = None,
+            _ => {}
+        }
+        Some((agent.snapshot(), approval))
+    });
+    let Some((changed, approval)) = changed else {
+        return;
+    };
+
+    let listener = STATUS_LISTENER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(listener) = listener {
+        listener(changed);
+    }
+
+    let Some(approval) = approval else {
+        return;
+    };
+    if approval.prompt.is_some() && is_focused_terminal(terminal_id) {
+        return;
+    }
+    let listener = APPROVAL_LISTENER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(listener) = listener {
+        listener(approval);
+    }
+}
+
+/// Agents currently waiting on an approval prompt, oldest first.
+pub fn pending_approval_requests() -> Vec<ApprovalRequest> {
+    let mut requests: Vec<ApprovalRequest> = with_tracked(|agents| {
+        agents
+            .iter()
+            .filter_map(|(terminal_id, agent)| {
+                Some(ApprovalRequest {
+                    session_name: agent.session_name.clone(),
+                    terminal_id: terminal_id.clone(),
+                    prompt: agent.scanner.status().prompt.clone()?,
+                    since: agent.awaiting_since?,
+                })
+            })
+            .collect()
+    });
+    requests.sort_by_key(|request| request.since);
+    requests
+}
+
+/// Most recent status of the agents of `session_name`, if any output was recognised.
+pub fn agent_status_for_session(session_name: &str) -> Option<SessionAgentStatus> {
+    with_tracked(|agents| {
+        agents
+            .values()
+            .filter(|agent| {
+                agent.session_name == session_name
+                    && agent.scanner.status() != &AgentOutputStatus::default()
+            })
+            .max_by_key(|agent| agent.updated_at)
+            .map(TrackedAgent::snapshot)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::agents::AgentRunState;
+
+    #[test]
+    fn tracks_status_per_session_until_untracked() {
+        let terminal_id = "session-status-bridge~abc123-top";
+        track_agent_terminal(terminal_id, "status-bridge", "claude");
+        assert!(agent_status_for_session("status-bridge").is_none());
+
+        record_agent_status_output(terminal_id, "⏺ Bash(ls)\r\n".as_bytes());
+        let status = agent_status_for_session("status-bridge").unwrap();
+        assert_eq!(status.status.state, Some(AgentRunState::ToolRunning));
+        assert_eq!(status.status.last_tool.as_deref(), Some("Bash"));
+
+        untrack_agent_terminal(terminal_id);
+        assert!(agent_status_for_session("status-bridge").is_none());
+    }
+
+    #[test]
+    fn reports_approval_prompts_of_unfocused_terminals_until_answered() {
+        let terminal_id = "session-approval-bridge~def456-top";
+        let (tx, rx) = std::sync::mpsc::channel();
+        set_approval_listener(move |change| {
+            if change.terminal_id == terminal_id {
+                let _ = tx.send(change.prompt);
+            }
+        });
+        track_agent_terminal(terminal_id, "approval-bridge", "codex");
+
+        record_agent_status_output(
+            terminal_id,
+            b"Would you like to run the following command?\r\n  $ make deploy\r\n",
+        );
+        assert_eq!(
+            rx.try_recv().unwrap().as_deref(),
+            Some("Would you like to run the following command?")
+        );
+        assert!(
+            pending_approval_requests()
+                .iter()
+                .any(|request| request.session_name == "approval-bridge")
+        );
+
+        record_agent_status_output(terminal_id, "• Running make deploy\r\n".as_bytes());
+        assert_eq!(rx.try_recv().unwrap(), None);
+        assert!(
+            !pending_approval_requests()
+                .iter()
+                .any(|request| request.session_name == "approval-bridge")
+        );
+
+        set_focused_agent_terminal(Some(terminal_id.to_string()));
+        record_agent_status_output(terminal_id, b"Allow command? (y/n)");
+        assert!(rx.try_recv().is_err());
+        set_focused_agent_terminal(None);
+        untrack_agent_terminal(terminal_id);
+    }
+}
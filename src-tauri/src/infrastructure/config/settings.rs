@@ -279,6 +279,16 @@ impl SettingsManager {
             .map_err(|e| e.to_string())
     }
 
+    pub fn get_webhook_request_log_enabled(&self) -> bool {
+        self.service.get_webhook_request_log_enabled()
+    }
+
+    pub fn set_webhook_request_log_enabled(&mut self, enabled: bool) -> Result<(), String> {
+        self.service
+            .set_webhook_request_log_enabled(enabled)
+            .map_err(|e| e.to_string())
+    }
+
     pub fn get_last_project_parent_directory(&self) -> Option<String> {
         self.service.get_last_project_parent_directory()
     }
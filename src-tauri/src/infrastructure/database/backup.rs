@@ -0,0 +1,333 @@
+use super::connection::Database;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use rusqlite::{Connection, MAIN_DB};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Number of most recent backups that are always kept.
+pub const KEEP_RECENT_BACKUPS: usize = 5;
+/// Number of past weeks that keep their newest backup.
+pub const KEEP_WEEKLY_BACKUPS: i64 = 4;
+
+const BACKUP_PREFIX: &str = "sessions-";
+const BACKUP_EXTENSION: &str = ".db";
+const PRE_RESTORE_SUFFIX: &str = "-pre-restore";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+// Bytes 0..100 hold the SQLite header, whose change counters differ between
+// otherwise identical copies.
+const SQLITE_HEADER_LEN: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseBackup {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    pub pre_restore: bool,
+}
+
+/// Directory holding the database backups of the project at `repo_path`.
+pub fn backup_dir(repo_path: &Path) -> PathBuf {
+    repo_path.join(".schaltwerk").join("backups")
+}
+
+fn backup_id(created_at: DateTime<Utc>, pre_restore: bool) -> String {
+    let suffix = if pre_restore { PRE_RESTORE_SUFFIX } else { "" };
+    format!(
+        "{BACKUP_PREFIX}{}{suffix}{BACKUP_EXTENSION}",
+        created_at.format(TIMESTAMP_FORMAT)
+    )
+}
+
+fn parse_backup_id(id: &str) -> Option<(DateTime<Utc>, bool)> {
+    let stem = id
+        .strip_prefix(BACKUP_PREFIX)?
+        .strip_suffix(BACKUP_EXTENSION)?;
+    let (timestamp, pre_restore) = match stem.strip_suffix(PRE_RESTORE_SUFFIX) {
+        Some(timestamp) => (timestamp, true),
+        None => (stem, false),
+    };
+    let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()?
+        .and_utc();
+    Some((created_at, pre_restore))
+}
+
+/// Lists the backups in `dir`, newest first. A missing directory has no backups.
+pub fn list_backups(dir: &Path) -> Result<Vec<DatabaseBackup>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).context(format!("failed to read {}", dir.display())),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Some(id) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Some((created_at, pre_restore)) = parse_backup_id(&id) else {
+            continue;
+        };
+        backups.push(DatabaseBackup {
+            id,
+            created_at,
+            size_bytes: entry.metadata()?.len(),
+            pre_restore,
+        });
+    }
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(backups)
+}
+
+fn content_digest(path: &Path) -> Result<Vec<u8>> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let body = bytes.get(SQLITE_HEADER_LEN..).unwrap_or_default();
+    Ok(Sha256::digest(body).to_vec())
+}
+
+/// Copies the live database into `dir` with the SQLite backup API.
+///
+/// Regular backups are skipped (returning `None`) when the content matches the newest
+/// regular backup; pre-restore backups are always written.
+pub fn create_backup(
+    db: &Database,
+    dir: &Path,
+    now: DateTime<Utc>,
+    pre_restore: bool,
+) -> Result<Option<DatabaseBackup>> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create backup directory {}", dir.display()))?;
+
+    let id = backup_id(now, pre_restore);
+    let tmp_path = dir.join(format!(".{id}.tmp"));
+    {
+        let conn = db.get_conn()?;
+        conn.backup(MAIN_DB, &tmp_path, None)
+            .context("SQLite backup failed")?;
+    }
+
+    if !pre_restore {
+        let newest = list_backups(dir)?.into_iter().find(|b| !b.pre_restore);
+        if let Some(newest) = newest
+            && content_digest(&dir.join(&newest.id))? == content_digest(&tmp_path)?
+        {
+            std::fs::remove_file(&tmp_path)?;
+            log::debug!("Database unchanged since backup {}, skipping", newest.id);
+            return Ok(None);
+        }
+    }
+
+    let path = dir.join(&id);
+    std::fs::rename(&tmp_path, &path)
+        .with_context(|| format!("failed to move backup into {}", path.display()))?;
+    let size_bytes = std::fs::metadata(&path)?.len();
+    log::info!("Wrote database backup {}", path.display());
+
+    Ok(Some(DatabaseBackup {
+        id,
+        created_at: now,
+        size_bytes,
+        pre_restore,
+    }))
+}
+
+/// Picks the backups that fall outside the retention schedule: the `keep_recent`
+/// newest are kept, plus the newest backup of each of the last `keep_weeks` weeks
+/// not already covered by them. `backups` must be sorted newest first.
+pub fn backups_to_prune(
+    backups: &[DatabaseBackup],
+    keep_recent: usize,
+    keep_weeks: i64,
+    now: DateTime<Utc>,
+) -> Vec<DatabaseBackup> {
+    let mut kept_weeks = HashSet::new();
+    backups
+        .iter()
+        .enumerate()
+        .filter(|(index, backup)| {
+            let age = now.signed_duration_since(backup.created_at);
+            let week = age.num_weeks();
+            if *index < keep_recent {
+                kept_weeks.insert(week);
+                return false;
+            }
+            let in_window = age >= Duration::zero() && week < keep_weeks;
+            !(in_window && kept_weeks.insert(week))
+        })
+        .map(|(_, backup)| backup.clone())
+        .collect()
+}
+
+/// Deletes backups in `dir` that fall outside the default retention schedule.
+pub fn rotate_backups(dir: &Path, now: DateTime<Utc>) -> Result<usize> {
+    let backups = list_backups(dir)?;
+    let prune = backups_to_prune(&backups, KEEP_RECENT_BACKUPS, KEEP_WEEKLY_BACKUPS, now);
+    for backup in &prune {
+        std::fs::remove_file(dir.join(&backup.id))
+            .with_context(|| format!("failed to remove backup {}", backup.id))?;
+    }
+    Ok(prune.len())
+}
+
+/// Backs up the project database into [`backup_dir`] and applies rotation.
+pub fn backup_project_database(db: &Database, repo_path: &Path) -> Result<Option<DatabaseBackup>> {
+    let dir = backup_dir(repo_path);
+    let now = Utc::now();
+    let backup = create_backup(db, &dir, now, false)?;
+    let removed = rotate_backups(&dir, now)?;
+    if removed > 0 {
+        log::info!(
+            "Pruned {removed} old database backup(s) in {}",
+            dir.display()
+        );
+    }
+    Ok(backup)
+}
+
+/// Replaces the live database with backup `id` from `dir`, after saving the current
+/// state as a pre-restore backup. Returns that pre-restore backup.
+pub fn restore_backup(db: &Database, dir: &Path, id: &str) -> Result<DatabaseBackup> {
+    if parse_backup_id(id).is_none() {
+        return Err(anyhow!("Invalid backup id '{id}'"));
+    }
+    let source = dir.join(id);
+    if !source.is_file() {
+        return Err(anyhow!("Backup '{id}' not found"));
+    }
+    // Refuse files SQLite cannot read before touching the live database
+    Connection::open(&source)
+        .and_then(|conn| conn.query_row("PRAGMA schema_version", [], |row| row.get::<_, i64>(0)))
+        .with_context(|| format!("Backup '{id}' is not a readable database"))?;
+
+    let safety = create_backup(db, dir, Utc::now(), true)?
+        .ok_or_else(|| anyhow!("failed to save the current database before restoring"))?;
+
+    let mut conn = db.get_conn()?;
+    conn.restore(MAIN_DB, &source, None::<fn(rusqlite::backup::Progress)>)
+        .with_context(|| format!("failed to restore backup '{id}'"))?;
+    log::info!(
+        "Restored database from backup {id} (previous state saved as {})",
+        safety.id
+    );
+
+    Ok(safety)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn file_db(tmp: &TempDir) -> Database {
+        Database::new(Some(tmp.path().join("sessions.db"))).expect("db")
+    }
+
+    fn marker_count(db: &Database) -> i64 {
+        let conn = db.get_conn().unwrap();
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS backup_marker (value INTEGER)",
+            [],
+        )
+        .unwrap();
+        conn.query_row("SELECT COUNT(*) FROM backup_marker", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn add_marker(db: &Database) {
+        marker_count(db);
+        db.get_conn()
+            .unwrap()
+            .execute("INSERT INTO backup_marker (value) VALUES (1)", [])
+            .unwrap();
+    }
+
+    fn backup_at(created_at: DateTime<Utc>) -> DatabaseBackup {
+        DatabaseBackup {
+            id: backup_id(created_at, false),
+            created_at,
+            size_bytes: 0,
+            pre_restore: false,
+        }
+    }
+
+    #[test]
+    fn backup_ids_round_trip() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap();
+        assert_eq!(backup_id(at, false), "sessions-20260304-050607.db");
+        assert_eq!(parse_backup_id(&backup_id(at, true)), Some((at, true)));
+        assert_eq!(parse_backup_id("../sessions.db"), None);
+    }
+
+    #[test]
+    fn rotation_keeps_recent_and_one_per_week() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        // Two backups a day for five weeks, newest first
+        let backups: Vec<DatabaseBackup> = (0..70)
+            .map(|i| backup_at(now - Duration::hours(12 * i)))
+            .collect();
+
+        let pruned: HashSet<String> = backups_to_prune(&backups, 3, 2, now)
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+        let kept: Vec<&DatabaseBackup> =
+            backups.iter().filter(|b| !pruned.contains(&b.id)).collect();
+
+        let kept_ages: Vec<i64> = kept
+            .iter()
+            .map(|b| now.signed_duration_since(b.created_at).num_hours())
+            .collect();
+        // Three most recent (covering week 0), then the newest backup of week 1
+        assert_eq!(kept_ages, vec![0, 12, 24, 168]);
+    }
+
+    #[test]
+    fn unchanged_database_is_not_backed_up_twice() {
+        let tmp = TempDir::new().unwrap();
+        let db = file_db(&tmp);
+        let dir = tmp.path().join("backups");
+        let first = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+        assert!(create_backup(&db, &dir, first, false).unwrap().is_some());
+        assert!(
+            create_backup(&db, &dir, first + Duration::minutes(1), false)
+                .unwrap()
+                .is_none()
+        );
+
+        add_marker(&db);
+        assert!(
+            create_backup(&db, &dir, first + Duration::minutes(2), false)
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(list_backups(&dir).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn restore_saves_current_state_and_rolls_back() {
+        let tmp = TempDir::new().unwrap();
+        let db = file_db(&tmp);
+        let dir = tmp.path().join("backups");
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+        add_marker(&db);
+        let backup = create_backup(&db, &dir, at, false).unwrap().unwrap();
+        add_marker(&db);
+        assert_eq!(marker_count(&db), 2);
+
+        let safety = restore_backup(&db, &dir, &backup.id).unwrap();
+        assert!(safety.pre_restore);
+        assert_eq!(marker_count(&db), 1);
+
+        assert!(restore_backup(&db, &dir, "../sessions.db").is_err());
+        assert!(restore_backup(&db, &dir, "sessions-20200101-000000.db").is_err());
+    }
+}
@@ -1,8 +1,6 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use r2d2::{ManageConnection, Pool, PooledConnection};
-use rusqlite::Connection;
-#[cfg(test)]
-use rusqlite::OpenFlags;
+use rusqlite::{Connection, OpenFlags};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -11,7 +9,59 @@ use std::time::{Duration, Instant};
 use super::db_schema;
 
 const DEFAULT_POOL_SIZE: u32 = 4;
-const BUSY_TIMEOUT_MS: u64 = 5_000;
+const DEFAULT_BUSY_TIMEOUT_MS: u64 = 5_000;
+const SYNCHRONOUS_MODES: &[&str] = &["OFF", "NORMAL", "FULL", "EXTRA"];
+
+/// Per-connection SQLite settings. WAL lets readers carry on while one connection
+/// writes, and the busy timeout makes a blocked writer wait for the lock instead of
+/// failing with "database is locked".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteTuning {
+    pub wal: bool,
+    pub busy_timeout: Duration,
+    pub synchronous: String,
+}
+
+impl Default for SqliteTuning {
+    fn default() -> Self {
+        Self {
+            wal: true,
+            busy_timeout: Duration::from_millis(DEFAULT_BUSY_TIMEOUT_MS),
+            synchronous: "NORMAL".to_string(),
+        }
+    }
+}
+
+impl SqliteTuning {
+    /// Reads `SCHALTWERK_DB_WAL` (`0` or `false` disables WAL),
+    /// `SCHALTWERK_DB_BUSY_TIMEOUT_MS` and `SCHALTWERK_DB_SYNCHRONOUS` (`OFF`, `NORMAL`,
+    /// `FULL` or `EXTRA`). Unset or invalid values keep the defaults.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut tuning = Self::default();
+        if let Some(value) = lookup("SCHALTWERK_DB_WAL") {
+            tuning.wal = !matches!(value.trim().to_ascii_lowercase().as_str(), "0" | "false");
+        }
+        if let Some(value) = lookup("SCHALTWERK_DB_BUSY_TIMEOUT_MS") {
+            match value.trim().parse::<u64>() {
+                Ok(ms) => tuning.busy_timeout = Duration::from_millis(ms),
+                Err(_) => log::warn!("Ignoring invalid SCHALTWERK_DB_BUSY_TIMEOUT_MS '{value}'"),
+            }
+        }
+        if let Some(value) = lookup("SCHALTWERK_DB_SYNCHRONOUS") {
+            let mode = value.trim().to_ascii_uppercase();
+            if SYNCHRONOUS_MODES.contains(&mode.as_str()) {
+                tuning.synchronous = mode;
+            } else {
+                log::warn!("Ignoring invalid SCHALTWERK_DB_SYNCHRONOUS '{value}'");
+            }
+        }
+        tuning
+    }
+}
 
 #[derive(Clone)]
 pub struct Database {
@@ -21,19 +71,22 @@ pub struct Database {
 #[derive(Clone)]
 pub(crate) struct SqliteConnectionManager {
     config: SqliteConfig,
+    tuning: SqliteTuning,
 }
 
 #[derive(Clone)]
 pub(crate) enum SqliteConfig {
     File(PathBuf),
+    ReadOnly(PathBuf),
     #[cfg(test)]
     Memory(String),
 }
 
 impl SqliteConnectionManager {
-    fn file(path: PathBuf) -> Self {
+    fn file(path: PathBuf, tuning: SqliteTuning) -> Self {
         Self {
             config: SqliteConfig::File(path),
+            tuning,
         }
     }
 
@@ -46,19 +99,22 @@ impl SqliteConnectionManager {
         let uri = format!("file:schaltwerk_mem_{id}?mode=memory&cache=shared");
         Self {
             config: SqliteConfig::Memory(uri),
+            tuning: SqliteTuning::default(),
         }
     }
 
     fn configure(&self, conn: &Connection) -> rusqlite::Result<()> {
         conn.pragma_update(None, "foreign_keys", "ON")?;
-        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.pragma_update(None, "synchronous", &self.tuning.synchronous)?;
         // Rely on SQLite's built-in busy timeout instead of a custom spin loop.
-        conn.busy_timeout(Duration::from_millis(BUSY_TIMEOUT_MS))?;
+        conn.busy_timeout(self.tuning.busy_timeout)?;
 
+        // The journal mode is stored in the file, so switch back explicitly when WAL is off
+        let journal_mode = if self.tuning.wal { "WAL" } else { "DELETE" };
         if matches!(self.config, SqliteConfig::File(_))
-            && let Err(err) = conn.pragma_update(None, "journal_mode", "WAL")
+            && let Err(err) = conn.pragma_update(None, "journal_mode", journal_mode)
         {
-            log::warn!("Failed to enable WAL journal mode: {err}");
+            log::warn!("Failed to set {journal_mode} journal mode: {err}");
         }
         Ok(())
     }
@@ -71,6 +127,12 @@ impl ManageConnection for SqliteConnectionManager {
     fn connect(&self) -> Result<Self::Connection, Self::Error> {
         let conn = match &self.config {
             SqliteConfig::File(path) => Connection::open(path)?,
+            SqliteConfig::ReadOnly(path) => Connection::open_with_flags(
+                path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY
+                    | OpenFlags::SQLITE_OPEN_URI
+                    | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?,
             #[cfg(test)]
             SqliteConfig::Memory(uri) => Connection::open_with_flags(
                 uri,
@@ -114,7 +176,18 @@ impl Database {
             .filter(|size| *size > 0)
             .unwrap_or(DEFAULT_POOL_SIZE);
 
-        let manager = SqliteConnectionManager::file(path.clone());
+        Self::open(path, pool_size, SqliteTuning::from_env())
+    }
+
+    /// Opens the database file at `path` with explicit connection settings instead of
+    /// the ones from the environment.
+    #[cfg(test)]
+    pub fn new_with_tuning(path: PathBuf, tuning: SqliteTuning) -> Result<Self> {
+        Self::open(path, DEFAULT_POOL_SIZE, tuning)
+    }
+
+    fn open(path: PathBuf, pool_size: u32, tuning: SqliteTuning) -> Result<Self> {
+        let manager = SqliteConnectionManager::file(path, tuning);
 
         let pool = Pool::builder()
             .max_size(pool_size)
@@ -130,6 +203,35 @@ impl Database {
         Ok(db)
     }
 
+    /// Opens an existing database without write access. The schema is neither created
+    /// nor migrated and the journal mode is left as is, so this never takes a write lock;
+    /// every write through the returned handle fails.
+    pub fn open_read_only(path: PathBuf) -> Result<Self> {
+        if !path.exists() {
+            bail!(
+                "No database at {}; open the project once without --read-only first",
+                path.display()
+            );
+        }
+
+        let manager = SqliteConnectionManager {
+            config: SqliteConfig::ReadOnly(path),
+            tuning: SqliteTuning::from_env(),
+        };
+        let pool = Pool::builder()
+            .max_size(DEFAULT_POOL_SIZE)
+            .build(manager)
+            .context("failed to build read-only SQLite connection pool")?;
+
+        let db = Self {
+            pool: Arc::new(pool),
+        };
+
+        db_schema::verify_schema_is_current(&db)?;
+
+        Ok(db)
+    }
+
     pub(crate) fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
         let wait_start = Instant::now();
         let conn = self
@@ -162,6 +264,11 @@ impl Database {
         db_schema::initialize_schema(self)
     }
 
+    /// Highest schema migration applied to this database.
+    pub fn get_schema_version(&self) -> Result<i64> {
+        db_schema::schema_version(&self.get_conn()?)
+    }
+
     #[cfg(test)]
     pub fn new_in_memory() -> Result<Self> {
         let manager = SqliteConnectionManager::memory();
@@ -180,3 +287,166 @@ impl Database {
         Ok(db)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::sessions::db_sessions::SessionMethods;
+    use crate::domains::sessions::entity::GitStats;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::mpsc;
+    use std::thread;
+    use tempfile::TempDir;
+
+    fn stats(session_id: &str, files_changed: u32) -> GitStats {
+        GitStats {
+            session_id: session_id.to_string(),
+            files_changed,
+            lines_added: files_changed * 10,
+            lines_removed: files_changed,
+            has_uncommitted: true,
+            calculated_at: Utc::now(),
+            last_diff_change_ts: None,
+            head_oid: None,
+            parent_oid: None,
+            has_remote: false,
+            remote_ahead: None,
+            remote_behind: None,
+            remote_oid: None,
+        }
+    }
+
+    /// SQLite's own defaults: rollback journal and no busy timeout.
+    fn untuned() -> SqliteTuning {
+        SqliteTuning {
+            wal: false,
+            busy_timeout: Duration::ZERO,
+            synchronous: "FULL".to_string(),
+        }
+    }
+
+    /// Saves git stats through the pool while another connection holds the write lock
+    /// for a moment.
+    fn save_while_locked(db: &Database) -> Result<()> {
+        let holder = db.get_conn().unwrap();
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            holder.execute_batch("BEGIN IMMEDIATE").unwrap();
+            locked_tx.send(()).unwrap();
+            thread::sleep(Duration::from_millis(200));
+            holder.execute_batch("COMMIT").unwrap();
+        });
+        locked_rx.recv().unwrap();
+        let result = db.save_git_stats(&stats("contended", 1));
+        handle.join().unwrap();
+        result
+    }
+
+    #[test]
+    fn tuning_reads_overrides_and_ignores_invalid_values() {
+        let env = HashMap::from([
+            ("SCHALTWERK_DB_WAL", "false"),
+            ("SCHALTWERK_DB_BUSY_TIMEOUT_MS", "250"),
+            ("SCHALTWERK_DB_SYNCHRONOUS", "full"),
+        ]);
+        let tuning = SqliteTuning::from_lookup(|name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(
+            tuning,
+            SqliteTuning {
+                wal: false,
+                busy_timeout: Duration::from_millis(250),
+                synchronous: "FULL".to_string(),
+            }
+        );
+
+        let invalid = HashMap::from([
+            ("SCHALTWERK_DB_BUSY_TIMEOUT_MS", "soon"),
+            ("SCHALTWERK_DB_SYNCHRONOUS", "sometimes"),
+        ]);
+        let tuning = SqliteTuning::from_lookup(|name| invalid.get(name).map(|v| v.to_string()));
+        assert_eq!(tuning, SqliteTuning::default());
+    }
+
+    #[test]
+    fn concurrent_git_stats_writers_see_no_lock_errors() {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::new_with_tuning(tmp.path().join("sessions.db"), SqliteTuning::default())
+            .unwrap();
+        let journal_mode: String = db
+            .get_conn()
+            .unwrap()
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+
+        let writers: Vec<_> = (0..8)
+            .map(|writer| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    (0..25)
+                        .map(|round| {
+                            db.save_git_stats(&stats(&format!("session-{writer}"), round))
+                                .and_then(|()| db.save_git_stats(&stats("shared", round)))
+                        })
+                        .filter_map(Result::err)
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let errors: Vec<String> = writers
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+
+        assert!(errors.is_empty(), "lock errors: {errors:?}");
+        let rows: i64 = db
+            .get_conn()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM git_stats", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 9);
+    }
+
+    #[test]
+    fn busy_timeout_waits_for_the_lock_where_the_default_fails() {
+        let tmp = TempDir::new().unwrap();
+
+        let untuned_db =
+            Database::new_with_tuning(tmp.path().join("untuned.db"), untuned()).unwrap();
+        let err = save_while_locked(&untuned_db).unwrap_err();
+        assert!(err.to_string().contains("database is locked"), "{err}");
+
+        let tuned_db =
+            Database::new_with_tuning(tmp.path().join("tuned.db"), SqliteTuning::default())
+                .unwrap();
+        save_while_locked(&tuned_db).unwrap();
+    }
+
+    #[test]
+    fn read_only_database_reads_but_rejects_writes_and_missing_files() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("sessions.db");
+        let db = Database::new_with_tuning(path.clone(), SqliteTuning::default()).unwrap();
+        db.save_git_stats(&stats("existing", 2)).unwrap();
+        let version = db.get_schema_version().unwrap();
+
+        let read_only = Database::open_read_only(path).unwrap();
+        assert_eq!(read_only.get_schema_version().unwrap(), version);
+        assert_eq!(
+            read_only
+                .get_git_stats("existing")
+                .unwrap()
+                .unwrap()
+                .files_changed,
+            2
+        );
+        let err = read_only.save_git_stats(&stats("new", 1)).unwrap_err();
+        assert!(err.to_string().contains("readonly"), "{err}");
+
+        let err = Database::open_read_only(tmp.path().join("missing.db")).unwrap_err();
+        assert!(err.to_string().contains("without --read-only"), "{err}");
+        assert!(!tmp.path().join("missing.db").exists());
+    }
+}
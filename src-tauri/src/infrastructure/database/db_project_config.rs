@@ -4,9 +4,15 @@ use chrono::Utc;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
 pub const DEFAULT_BRANCH_PREFIX: &str = "";
+/// Seconds a session setup script may run before it is interrupted.
+pub const DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS: u64 = 30 * 60;
+/// Setup scripts of sessions created together that may run at the same time.
+pub const DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS: u32 = 3;
 
 fn normalize_branch_prefix(input: &str) -> String {
     let trimmed = input.trim();
@@ -14,9 +20,106 @@ fn normalize_branch_prefix(input: &str) -> String {
     trimmed.trim().to_string()
 }
 
+/// What session creation does when the requested branch name already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum BranchCollisionStrategy {
+    /// Append a random two-letter suffix, e.g. `feature-qx`.
+    #[default]
+    Suffix,
+    /// Append the lowest free number starting at two, e.g. `feature-2`.
+    Increment,
+    /// Refuse to create the session.
+    Error,
+}
+
+impl BranchCollisionStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BranchCollisionStrategy::Suffix => "suffix",
+            BranchCollisionStrategy::Increment => "increment",
+            BranchCollisionStrategy::Error => "error",
+        }
+    }
+}
+
+impl FromStr for BranchCollisionStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "suffix" => Ok(BranchCollisionStrategy::Suffix),
+            "increment" => Ok(BranchCollisionStrategy::Increment),
+            "error" => Ok(BranchCollisionStrategy::Error),
+            other => Err(anyhow!("Unknown branch collision strategy: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentRestartMode {
+    #[default]
+    Never,
+    /// Relaunch the agent (resuming its conversation) when it crashes.
+    OnCrash,
+}
+
+/// What to do when the agent in a session's top terminal crashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentRestartPolicy {
+    pub mode: AgentRestartMode,
+    pub max_retries: u32,
+    /// Delay before the first relaunch; doubles with each further attempt.
+    pub backoff_secs: u64,
+}
+
+impl Default for AgentRestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: AgentRestartMode::Never,
+            max_retries: 3,
+            backoff_secs: 2,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectSessionsSettings {
     pub filter_mode: String,
+    /// `None` on writes leaves the stored policy untouched.
+    #[serde(default)]
+    pub agent_restart_policy: Option<AgentRestartPolicy>,
+    /// Script run in each new session worktree. `None` on writes leaves it untouched.
+    #[serde(default)]
+    pub setup_script: Option<String>,
+    /// Hold agent starts until the setup script finished. `None` on writes leaves the
+    /// stored flag untouched.
+    #[serde(default)]
+    pub wait_for_setup: Option<bool>,
+    /// Seconds the setup script may run before it is interrupted; `0` disables the
+    /// limit. `None` on writes leaves the stored limit untouched.
+    #[serde(default)]
+    pub setup_script_timeout_secs: Option<u64>,
+    /// Setup scripts that may run at the same time, at least one. `None` on writes
+    /// leaves the stored cap untouched.
+    #[serde(default)]
+    pub max_parallel_setup_scripts: Option<u32>,
+    /// Gitignore-style globs the file watchers skip on top of the built-in defaults and
+    /// the project's `.gitignore`; `!pattern` watches a path again. `None` on writes
+    /// leaves the stored list untouched.
+    #[serde(default)]
+    pub watcher_ignore_patterns: Option<Vec<String>>,
+    /// Globs, relative to the repository root, of untracked files copied into every new
+    /// session worktree, such as `.env.local`. `None` on writes leaves the stored list
+    /// untouched.
+    #[serde(default)]
+    pub worktree_copy_patterns: Option<Vec<String>>,
+    /// Untracked directories, relative to the repository root, cloned into every new
+    /// session worktree, such as `node_modules`. Copy-on-write clones are used where the
+    /// filesystem supports them. `None` on writes leaves the stored list untouched.
+    #[serde(default)]
+    pub clone_dirs: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +127,12 @@ pub struct ProjectMergePreferences {
     pub auto_cancel_after_merge: bool,
     #[serde(default)]
     pub auto_cancel_after_pr: bool,
+    /// Run the test command in the session worktree before merging and abort on failure.
+    #[serde(default)]
+    pub require_passing_tests: bool,
+    /// Command for the pre-merge check; the project run script is used when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_command: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -36,6 +145,18 @@ pub struct HeaderActionConfig {
     pub color: Option<String>,
 }
 
+/// Reusable initial prompt with `{{placeholder}}` slots that are filled in when a
+/// session is created from it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTemplate {
+    pub name: String,
+    pub content: String,
+    /// Fallback values for placeholders the caller does not supply.
+    #[serde(default)]
+    pub defaults: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RunScript {
@@ -53,6 +174,9 @@ pub struct RunScript {
 pub struct ProjectGithubConfig {
     pub repository: String,
     pub default_branch: String,
+    /// Push the branch and open a draft PR whenever a session is marked reviewed.
+    #[serde(default)]
+    pub auto_pr_on_review: bool,
 }
 
 pub trait ProjectConfigMethods {
@@ -65,6 +189,12 @@ pub trait ProjectConfigMethods {
         repo_path: &Path,
         settings: &ProjectSessionsSettings,
     ) -> Result<()>;
+    fn get_project_watcher_ignore_patterns(&self, repo_path: &Path) -> Result<Vec<String>>;
+    fn get_project_worktree_copy_patterns(&self, repo_path: &Path) -> Result<Vec<String>>;
+    fn get_project_worktree_clone_dirs(&self, repo_path: &Path) -> Result<Vec<String>>;
+    /// Time limit of the setup script, `None` when the project disabled it.
+    fn get_project_setup_script_timeout(&self, repo_path: &Path) -> Result<Option<Duration>>;
+    fn get_project_max_parallel_setup_scripts(&self, repo_path: &Path) -> Result<usize>;
     fn get_project_branch_prefix(&self, repo_path: &Path) -> Result<String>;
     fn set_project_branch_prefix(&self, repo_path: &Path, branch_prefix: &str) -> Result<()>;
     fn get_project_environment_variables(
@@ -82,6 +212,43 @@ pub trait ProjectConfigMethods {
         repo_path: &Path,
         preferences: &ProjectMergePreferences,
     ) -> Result<()>;
+    fn get_project_merge_commit_trailers(&self, repo_path: &Path) -> Result<Vec<String>>;
+    fn set_project_merge_commit_trailers(
+        &self,
+        repo_path: &Path,
+        trailers: &[String],
+    ) -> Result<()>;
+    fn get_project_skip_permissions_policy(&self, repo_path: &Path) -> Result<Option<String>>;
+    fn set_project_skip_permissions_policy(&self, repo_path: &Path, policy: &str) -> Result<()>;
+    fn get_project_worktree_root(&self, repo_path: &Path) -> Result<Option<PathBuf>>;
+    fn set_project_worktree_root(&self, repo_path: &Path, root: Option<&Path>) -> Result<()>;
+    fn get_project_branch_collision_strategy(
+        &self,
+        repo_path: &Path,
+    ) -> Result<BranchCollisionStrategy>;
+    fn set_project_branch_collision_strategy(
+        &self,
+        repo_path: &Path,
+        strategy: BranchCollisionStrategy,
+    ) -> Result<()>;
+    fn get_project_prompt_templates(&self, repo_path: &Path) -> Result<Vec<PromptTemplate>>;
+    fn set_project_prompt_templates(
+        &self,
+        repo_path: &Path,
+        templates: &[PromptTemplate],
+    ) -> Result<()>;
+    fn get_project_database_backups_enabled(&self, repo_path: &Path) -> Result<bool>;
+    fn set_project_database_backups_enabled(&self, repo_path: &Path, enabled: bool) -> Result<()>;
+    fn get_project_keep_recordings(&self, repo_path: &Path) -> Result<bool>;
+    fn set_project_keep_recordings(&self, repo_path: &Path, keep: bool) -> Result<()>;
+    fn get_project_spec_file_mirror(&self, repo_path: &Path) -> Result<bool>;
+    fn set_project_spec_file_mirror(&self, repo_path: &Path, enabled: bool) -> Result<()>;
+    fn get_project_update_from_parent_on_start(&self, repo_path: &Path) -> Result<bool>;
+    fn set_project_update_from_parent_on_start(
+        &self,
+        repo_path: &Path,
+        enabled: bool,
+    ) -> Result<()>;
     fn get_project_action_buttons(&self, repo_path: &Path) -> Result<Vec<HeaderActionConfig>>;
     fn set_project_action_buttons(
         &self,
@@ -94,38 +261,748 @@ pub trait ProjectConfigMethods {
     fn set_project_github_config(
         &self,
         repo_path: &Path,
-        config: &ProjectGithubConfig,
-    ) -> Result<()>;
-    fn clear_project_github_config(&self, repo_path: &Path) -> Result<()>;
-}
-
-impl ProjectConfigMethods for Database {
-    fn get_project_setup_script(&self, repo_path: &Path) -> Result<Option<String>> {
+        config: &ProjectGithubConfig,
+    ) -> Result<()>;
+    fn clear_project_github_config(&self, repo_path: &Path) -> Result<()>;
+}
+
+impl ProjectConfigMethods for Database {
+    fn get_project_setup_script(&self, repo_path: &Path) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+
+        // Canonicalize the path for consistent storage/retrieval
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let result: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT setup_script FROM project_config WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(Some(script)) => Ok(Some(script)),
+            Ok(None) => Ok(None),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_setup_script(&self, repo_path: &Path, setup_script: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        // Canonicalize the path for consistent storage/retrieval
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    setup_script,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    setup_script = excluded.setup_script,
+                    updated_at = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), setup_script, now, now],
+        )?;
+
+        Ok(())
+    }
+
+    fn clear_project_setup_script(&self, repo_path: &Path) -> Result<()> {
+        let conn = self.get_conn()?;
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        conn.execute(
+            "UPDATE project_config SET setup_script = NULL WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_sessions_settings(&self, repo_path: &Path) -> Result<ProjectSessionsSettings> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        type SessionsSettingsRow = (
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            bool,
+            Option<i64>,
+            Option<i64>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+        );
+        let query_res: rusqlite::Result<SessionsSettingsRow> = conn.query_row(
+            "SELECT sessions_filter_mode, agent_restart_policy, setup_script,
+                    COALESCE(wait_for_setup, 0), setup_script_timeout_secs,
+                    max_parallel_setup_scripts, watcher_ignore_patterns, worktree_copy_patterns,
+                    worktree_clone_dirs
+                FROM project_config
+                WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                    row.get(8)?,
+                ))
+            },
+        );
+
+        match query_res {
+            Ok((
+                filter_opt,
+                policy_json,
+                setup_script,
+                wait_for_setup,
+                timeout_secs,
+                max_parallel,
+                ignore_json,
+                copy_json,
+                clone_json,
+            )) => Ok(ProjectSessionsSettings {
+                filter_mode: filter_opt.unwrap_or_else(|| "running".to_string()),
+                agent_restart_policy: Some(
+                    policy_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                ),
+                setup_script: setup_script.filter(|script| !script.trim().is_empty()),
+                wait_for_setup: Some(wait_for_setup),
+                setup_script_timeout_secs: Some(
+                    timeout_secs.map_or(DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS, |secs| {
+                        u64::try_from(secs).unwrap_or(0)
+                    }),
+                ),
+                max_parallel_setup_scripts: Some(
+                    max_parallel.map_or(DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS, |max| {
+                        u32::try_from(max).unwrap_or(1).max(1)
+                    }),
+                ),
+                watcher_ignore_patterns: Some(
+                    ignore_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                ),
+                worktree_copy_patterns: Some(
+                    copy_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                ),
+                clone_dirs: Some(
+                    clone_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                ),
+            }),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(ProjectSessionsSettings {
+                filter_mode: "running".to_string(),
+                agent_restart_policy: Some(AgentRestartPolicy::default()),
+                setup_script: None,
+                wait_for_setup: Some(false),
+                setup_script_timeout_secs: Some(DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS),
+                max_parallel_setup_scripts: Some(DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS),
+                watcher_ignore_patterns: Some(Vec::new()),
+                worktree_copy_patterns: Some(Vec::new()),
+                clone_dirs: Some(Vec::new()),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_sessions_settings(
+        &self,
+        repo_path: &Path,
+        settings: &ProjectSessionsSettings,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    sessions_filter_mode,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    sessions_filter_mode = excluded.sessions_filter_mode,
+                    updated_at           = excluded.updated_at",
+            params![
+                canonical_path.to_string_lossy(),
+                settings.filter_mode,
+                now,
+                now,
+            ],
+        )?;
+
+        if let Some(policy) = settings.agent_restart_policy {
+            conn.execute(
+                "UPDATE project_config SET agent_restart_policy = ?1 WHERE repository_path = ?2",
+                params![
+                    serde_json::to_string(&policy)?,
+                    canonical_path.to_string_lossy()
+                ],
+            )?;
+        }
+        if let Some(setup_script) = settings.setup_script.as_deref() {
+            conn.execute(
+                "UPDATE project_config SET setup_script = ?1 WHERE repository_path = ?2",
+                params![setup_script, canonical_path.to_string_lossy()],
+            )?;
+        }
+        if let Some(wait_for_setup) = settings.wait_for_setup {
+            conn.execute(
+                "UPDATE project_config SET wait_for_setup = ?1 WHERE repository_path = ?2",
+                params![wait_for_setup, canonical_path.to_string_lossy()],
+            )?;
+        }
+        if let Some(max_parallel) = settings.max_parallel_setup_scripts {
+            conn.execute(
+                "UPDATE project_config SET max_parallel_setup_scripts = ?1 WHERE repository_path = ?2",
+                params![max_parallel.max(1), canonical_path.to_string_lossy()],
+            )?;
+        }
+        if let Some(timeout_secs) = settings.setup_script_timeout_secs {
+            conn.execute(
+                "UPDATE project_config SET setup_script_timeout_secs = ?1 WHERE repository_path = ?2",
+                params![
+                    i64::try_from(timeout_secs).unwrap_or(i64::MAX),
+                    canonical_path.to_string_lossy()
+                ],
+            )?;
+        }
+        if let Some(patterns) = settings.watcher_ignore_patterns.as_ref() {
+            let patterns: Vec<&str> = patterns
+                .iter()
+                .map(|pattern| pattern.trim())
+                .filter(|pattern| !pattern.is_empty())
+                .collect();
+            conn.execute(
+                "UPDATE project_config SET watcher_ignore_patterns = ?1 WHERE repository_path = ?2",
+                params![
+                    serde_json::to_string(&patterns)?,
+                    canonical_path.to_string_lossy()
+                ],
+            )?;
+        }
+        if let Some(patterns) = settings.worktree_copy_patterns.as_ref() {
+            let patterns: Vec<&str> = patterns
+                .iter()
+                .map(|pattern| pattern.trim())
+                .filter(|pattern| !pattern.is_empty())
+                .collect();
+            conn.execute(
+                "UPDATE project_config SET worktree_copy_patterns = ?1 WHERE repository_path = ?2",
+                params![
+                    serde_json::to_string(&patterns)?,
+                    canonical_path.to_string_lossy()
+                ],
+            )?;
+        }
+        if let Some(dirs) = settings.clone_dirs.as_ref() {
+            let dirs: Vec<&str> = dirs
+                .iter()
+                .map(|dir| dir.trim().trim_end_matches('/'))
+                .filter(|dir| !dir.is_empty())
+                .collect();
+            conn.execute(
+                "UPDATE project_config SET worktree_clone_dirs = ?1 WHERE repository_path = ?2",
+                params![
+                    serde_json::to_string(&dirs)?,
+                    canonical_path.to_string_lossy()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn get_project_watcher_ignore_patterns(&self, repo_path: &Path) -> Result<Vec<String>> {
+        Ok(self
+            .get_project_sessions_settings(repo_path)?
+            .watcher_ignore_patterns
+            .unwrap_or_default())
+    }
+
+    fn get_project_worktree_copy_patterns(&self, repo_path: &Path) -> Result<Vec<String>> {
+        Ok(self
+            .get_project_sessions_settings(repo_path)?
+            .worktree_copy_patterns
+            .unwrap_or_default())
+    }
+
+    fn get_project_worktree_clone_dirs(&self, repo_path: &Path) -> Result<Vec<String>> {
+        Ok(self
+            .get_project_sessions_settings(repo_path)?
+            .clone_dirs
+            .unwrap_or_default())
+    }
+
+    fn get_project_setup_script_timeout(&self, repo_path: &Path) -> Result<Option<Duration>> {
+        let secs = self
+            .get_project_sessions_settings(repo_path)?
+            .setup_script_timeout_secs
+            .unwrap_or(DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS);
+        Ok((secs > 0).then(|| Duration::from_secs(secs)))
+    }
+
+    fn get_project_max_parallel_setup_scripts(&self, repo_path: &Path) -> Result<usize> {
+        let max = self
+            .get_project_sessions_settings(repo_path)?
+            .max_parallel_setup_scripts
+            .unwrap_or(DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS);
+        Ok(max.max(1) as usize)
+    }
+
+    fn get_project_branch_prefix(&self, repo_path: &Path) -> Result<String> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let result: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT branch_prefix FROM project_config WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(Some(value)) => Ok(normalize_branch_prefix(&value)),
+            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => {
+                Ok(DEFAULT_BRANCH_PREFIX.to_string())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_branch_prefix(&self, repo_path: &Path, branch_prefix: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let normalized = normalize_branch_prefix(branch_prefix);
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    branch_prefix,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    branch_prefix = excluded.branch_prefix,
+                    updated_at    = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), normalized, now, now,],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_environment_variables(
+        &self,
+        repo_path: &Path,
+    ) -> Result<HashMap<String, String>> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT environment_variables
+                FROM project_config
+                WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match query_res {
+            Ok(Some(json_str)) => {
+                let env_vars: HashMap<String, String> = serde_json::from_str(&json_str)?;
+                Ok(env_vars)
+            }
+            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(HashMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_environment_variables(
+        &self,
+        repo_path: &Path,
+        env_vars: &HashMap<String, String>,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let json_str = serde_json::to_string(env_vars)?;
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    environment_variables,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    environment_variables = excluded.environment_variables,
+                    updated_at            = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), json_str, now, now],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_merge_preferences(&self, repo_path: &Path) -> Result<ProjectMergePreferences> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let query_res: rusqlite::Result<(i64, i64, i64, Option<String>)> = conn.query_row(
+            "SELECT COALESCE(auto_cancel_after_merge, 1), COALESCE(auto_cancel_after_pr, 0),
+                    COALESCE(require_passing_tests, 0), merge_test_command
+             FROM project_config WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        );
+
+        let defaults = (true, false, false, None);
+        let (auto_cancel_merge, auto_cancel_pr, require_tests, test_command) = match query_res {
+            Ok((merge_raw, pr_raw, tests_raw, command)) => {
+                (merge_raw != 0, pr_raw != 0, tests_raw != 0, command)
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => defaults,
+            Err(e) => match e {
+                rusqlite::Error::SqliteFailure(_, _) => defaults,
+                other => return Err(other.into()),
+            },
+        };
+
+        Ok(ProjectMergePreferences {
+            auto_cancel_after_merge: auto_cancel_merge,
+            auto_cancel_after_pr: auto_cancel_pr,
+            require_passing_tests: require_tests,
+            test_command: test_command.filter(|command| !command.trim().is_empty()),
+        })
+    }
+
+    fn set_project_merge_preferences(
+        &self,
+        repo_path: &Path,
+        preferences: &ProjectMergePreferences,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+        let merge_value = if preferences.auto_cancel_after_merge {
+            1
+        } else {
+            0
+        };
+        let pr_value = if preferences.auto_cancel_after_pr { 1 } else { 0 };
+        let tests_value = if preferences.require_passing_tests { 1 } else { 0 };
+        let test_command = preferences
+            .test_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty());
+
+        conn.execute(
+            "INSERT INTO project_config (repository_path, auto_cancel_after_merge, auto_cancel_after_pr,
+                                            require_passing_tests, merge_test_command, created_at, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    auto_cancel_after_merge = excluded.auto_cancel_after_merge,
+                    auto_cancel_after_pr = excluded.auto_cancel_after_pr,
+                    require_passing_tests   = excluded.require_passing_tests,
+                    merge_test_command      = excluded.merge_test_command,
+                    updated_at              = excluded.updated_at",
+            params![
+                canonical_path.to_string_lossy(),
+                merge_value,
+                pr_value,
+                tests_value,
+                test_command,
+                now,
+                now
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_merge_commit_trailers(&self, repo_path: &Path) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT merge_commit_trailers
+                FROM project_config
+                WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match query_res {
+            Ok(Some(json_str)) => Ok(serde_json::from_str(&json_str)?),
+            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_merge_commit_trailers(
+        &self,
+        repo_path: &Path,
+        trailers: &[String],
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let json_str = serde_json::to_string(trailers)?;
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    merge_commit_trailers,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    merge_commit_trailers = excluded.merge_commit_trailers,
+                    updated_at            = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), json_str, now, now],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_skip_permissions_policy(&self, repo_path: &Path) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT skip_permissions_policy
+                FROM project_config
+                WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match query_res {
+            Ok(policy) => Ok(policy),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_skip_permissions_policy(&self, repo_path: &Path, policy: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    skip_permissions_policy,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    skip_permissions_policy = excluded.skip_permissions_policy,
+                    updated_at              = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), policy, now, now],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_worktree_root(&self, repo_path: &Path) -> Result<Option<PathBuf>> {
+        let conn = self.get_conn()?;
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT worktree_root FROM project_config WHERE repository_path = ?1",
+            params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match query_res {
+            Ok(root) => Ok(root.filter(|r| !r.trim().is_empty()).map(PathBuf::from)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_worktree_root(&self, repo_path: &Path, root: Option<&Path>) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+        let root = root.map(|r| r.to_string_lossy().to_string());
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    worktree_root,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    worktree_root = excluded.worktree_root,
+                    updated_at    = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), root, now, now],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_project_branch_collision_strategy(
+        &self,
+        repo_path: &Path,
+    ) -> Result<BranchCollisionStrategy> {
         let conn = self.get_conn()?;
 
-        // Canonicalize the path for consistent storage/retrieval
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let result: rusqlite::Result<Option<String>> = conn.query_row(
-            "SELECT setup_script FROM project_config WHERE repository_path = ?1",
+        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT branch_collision_strategy FROM project_config WHERE repository_path = ?1",
             params![canonical_path.to_string_lossy()],
             |row| row.get(0),
         );
 
-        match result {
-            Ok(Some(script)) => Ok(Some(script)),
-            Ok(None) => Ok(None),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        match query_res {
+            Ok(Some(value)) => value.parse(),
+            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => {
+                Ok(BranchCollisionStrategy::default())
+            }
             Err(e) => Err(e.into()),
         }
     }
 
-    fn set_project_setup_script(&self, repo_path: &Path, setup_script: &str) -> Result<()> {
+    fn set_project_branch_collision_strategy(
+        &self,
+        repo_path: &Path,
+        strategy: BranchCollisionStrategy,
+    ) -> Result<()> {
         let conn = self.get_conn()?;
         let now = Utc::now().timestamp();
 
-        // Canonicalize the path for consistent storage/retrieval
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
@@ -133,7 +1010,7 @@ impl ProjectConfigMethods for Database {
             "INSERT INTO project_config (
                     repository_path,
                     auto_cancel_after_merge,
-                    setup_script,
+                    branch_collision_strategy,
                     created_at,
                     updated_at
                 )
@@ -148,57 +1025,93 @@ impl ProjectConfigMethods for Database {
                     ?4
                 )
                 ON CONFLICT(repository_path) DO UPDATE SET
-                    setup_script = excluded.setup_script,
-                    updated_at = excluded.updated_at",
-            params![canonical_path.to_string_lossy(), setup_script, now, now],
+                    branch_collision_strategy = excluded.branch_collision_strategy,
+                    updated_at                = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), strategy.as_str(), now, now],
         )?;
 
         Ok(())
     }
 
-    fn clear_project_setup_script(&self, repo_path: &Path) -> Result<()> {
+    fn get_project_prompt_templates(&self, repo_path: &Path) -> Result<Vec<PromptTemplate>> {
         let conn = self.get_conn()?;
+
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        conn.execute(
-            "UPDATE project_config SET setup_script = NULL WHERE repository_path = ?1",
+        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
+            "SELECT prompt_templates FROM project_config WHERE repository_path = ?1",
             params![canonical_path.to_string_lossy()],
+            |row| row.get(0),
+        );
+
+        match query_res {
+            Ok(Some(json_str)) => Ok(serde_json::from_str(&json_str)?),
+            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_project_prompt_templates(
+        &self,
+        repo_path: &Path,
+        templates: &[PromptTemplate],
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = Utc::now().timestamp();
+
+        let canonical_path =
+            std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+        let json_str = serde_json::to_string(templates)?;
+
+        conn.execute(
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    prompt_templates,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
+                ON CONFLICT(repository_path) DO UPDATE SET
+                    prompt_templates = excluded.prompt_templates,
+                    updated_at       = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), json_str, now, now],
         )?;
 
         Ok(())
     }
 
-    fn get_project_sessions_settings(&self, repo_path: &Path) -> Result<ProjectSessionsSettings> {
+    fn get_project_database_backups_enabled(&self, repo_path: &Path) -> Result<bool> {
         let conn = self.get_conn()?;
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
-            "SELECT sessions_filter_mode
-                FROM project_config
-                WHERE repository_path = ?1",
+        let query_res: rusqlite::Result<Option<bool>> = conn.query_row(
+            "SELECT database_backups_enabled FROM project_config WHERE repository_path = ?1",
             params![canonical_path.to_string_lossy()],
             |row| row.get(0),
         );
 
         match query_res {
-            Ok(filter_opt) => Ok(ProjectSessionsSettings {
-                filter_mode: filter_opt.unwrap_or_else(|| "running".to_string()),
-            }),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(ProjectSessionsSettings {
-                filter_mode: "running".to_string(),
-            }),
+            Ok(enabled) => Ok(enabled.unwrap_or(true)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(true),
             Err(e) => Err(e.into()),
         }
     }
 
-    fn set_project_sessions_settings(
-        &self,
-        repo_path: &Path,
-        settings: &ProjectSessionsSettings,
-    ) -> Result<()> {
+    fn set_project_database_backups_enabled(&self, repo_path: &Path, enabled: bool) -> Result<()> {
         let conn = self.get_conn()?;
         let now = Utc::now().timestamp();
 
@@ -209,7 +1122,7 @@ impl ProjectConfigMethods for Database {
             "INSERT INTO project_config (
                     repository_path,
                     auto_cancel_after_merge,
-                    sessions_filter_mode,
+                    database_backups_enabled,
                     created_at,
                     updated_at
                 )
@@ -224,54 +1137,45 @@ impl ProjectConfigMethods for Database {
                     ?4
                 )
                 ON CONFLICT(repository_path) DO UPDATE SET
-                    sessions_filter_mode = excluded.sessions_filter_mode,
-                    updated_at           = excluded.updated_at",
-            params![
-                canonical_path.to_string_lossy(),
-                settings.filter_mode,
-                now,
-                now,
-            ],
+                    database_backups_enabled = excluded.database_backups_enabled,
+                    updated_at               = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), enabled, now, now],
         )?;
 
         Ok(())
     }
 
-    fn get_project_branch_prefix(&self, repo_path: &Path) -> Result<String> {
+    fn get_project_keep_recordings(&self, repo_path: &Path) -> Result<bool> {
         let conn = self.get_conn()?;
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let result: rusqlite::Result<Option<String>> = conn.query_row(
-            "SELECT branch_prefix FROM project_config WHERE repository_path = ?1",
+        let query_res: rusqlite::Result<Option<bool>> = conn.query_row(
+            "SELECT keep_recordings FROM project_config WHERE repository_path = ?1",
             params![canonical_path.to_string_lossy()],
             |row| row.get(0),
         );
 
-        match result {
-            Ok(Some(value)) => Ok(normalize_branch_prefix(&value)),
-            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => {
-                Ok(DEFAULT_BRANCH_PREFIX.to_string())
-            }
+        match query_res {
+            Ok(keep) => Ok(keep.unwrap_or(false)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
             Err(e) => Err(e.into()),
         }
     }
 
-    fn set_project_branch_prefix(&self, repo_path: &Path, branch_prefix: &str) -> Result<()> {
+    fn set_project_keep_recordings(&self, repo_path: &Path, keep: bool) -> Result<()> {
         let conn = self.get_conn()?;
         let now = Utc::now().timestamp();
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let normalized = normalize_branch_prefix(branch_prefix);
-
         conn.execute(
             "INSERT INTO project_config (
                     repository_path,
                     auto_cancel_after_merge,
-                    branch_prefix,
+                    keep_recordings,
                     created_at,
                     updated_at
                 )
@@ -286,59 +1190,45 @@ impl ProjectConfigMethods for Database {
                     ?4
                 )
                 ON CONFLICT(repository_path) DO UPDATE SET
-                    branch_prefix = excluded.branch_prefix,
-                    updated_at    = excluded.updated_at",
-            params![canonical_path.to_string_lossy(), normalized, now, now,],
+                    keep_recordings = excluded.keep_recordings,
+                    updated_at      = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), keep, now, now],
         )?;
 
         Ok(())
     }
 
-    fn get_project_environment_variables(
-        &self,
-        repo_path: &Path,
-    ) -> Result<HashMap<String, String>> {
+    fn get_project_spec_file_mirror(&self, repo_path: &Path) -> Result<bool> {
         let conn = self.get_conn()?;
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let query_res: rusqlite::Result<Option<String>> = conn.query_row(
-            "SELECT environment_variables
-                FROM project_config
-                WHERE repository_path = ?1",
+        let query_res: rusqlite::Result<Option<bool>> = conn.query_row(
+            "SELECT spec_file_mirror FROM project_config WHERE repository_path = ?1",
             params![canonical_path.to_string_lossy()],
             |row| row.get(0),
         );
 
         match query_res {
-            Ok(Some(json_str)) => {
-                let env_vars: HashMap<String, String> = serde_json::from_str(&json_str)?;
-                Ok(env_vars)
-            }
-            Ok(None) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(HashMap::new()),
+            Ok(enabled) => Ok(enabled.unwrap_or(false)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
             Err(e) => Err(e.into()),
         }
     }
 
-    fn set_project_environment_variables(
-        &self,
-        repo_path: &Path,
-        env_vars: &HashMap<String, String>,
-    ) -> Result<()> {
+    fn set_project_spec_file_mirror(&self, repo_path: &Path, enabled: bool) -> Result<()> {
         let conn = self.get_conn()?;
         let now = Utc::now().timestamp();
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let json_str = serde_json::to_string(env_vars)?;
-
         conn.execute(
             "INSERT INTO project_config (
                     repository_path,
                     auto_cancel_after_merge,
-                    environment_variables,
+                    spec_file_mirror,
                     created_at,
                     updated_at
                 )
@@ -353,67 +1243,66 @@ impl ProjectConfigMethods for Database {
                     ?4
                 )
                 ON CONFLICT(repository_path) DO UPDATE SET
-                    environment_variables = excluded.environment_variables,
-                    updated_at            = excluded.updated_at",
-            params![canonical_path.to_string_lossy(), json_str, now, now],
+                    spec_file_mirror = excluded.spec_file_mirror,
+                    updated_at       = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), enabled, now, now],
         )?;
 
         Ok(())
     }
 
-    fn get_project_merge_preferences(&self, repo_path: &Path) -> Result<ProjectMergePreferences> {
+    fn get_project_update_from_parent_on_start(&self, repo_path: &Path) -> Result<bool> {
         let conn = self.get_conn()?;
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let query_res: rusqlite::Result<(i64, i64)> = conn.query_row(
-            "SELECT COALESCE(auto_cancel_after_merge, 1), COALESCE(auto_cancel_after_pr, 0) FROM project_config WHERE repository_path = ?1",
+        let query_res: rusqlite::Result<Option<bool>> = conn.query_row(
+            "SELECT update_from_parent_on_start FROM project_config WHERE repository_path = ?1",
             params![canonical_path.to_string_lossy()],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+            |row| row.get(0),
         );
 
-        let (auto_cancel_merge, auto_cancel_pr) = match query_res {
-            Ok((merge_raw, pr_raw)) => (merge_raw != 0, pr_raw != 0),
-            Err(rusqlite::Error::QueryReturnedNoRows) => (true, false),
-            Err(e) => match e {
-                rusqlite::Error::SqliteFailure(_, _) => (true, false),
-                other => return Err(other.into()),
-            },
-        };
-
-        Ok(ProjectMergePreferences {
-            auto_cancel_after_merge: auto_cancel_merge,
-            auto_cancel_after_pr: auto_cancel_pr,
-        })
+        match query_res {
+            Ok(enabled) => Ok(enabled.unwrap_or(false)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
     }
 
-    fn set_project_merge_preferences(
+    fn set_project_update_from_parent_on_start(
         &self,
         repo_path: &Path,
-        preferences: &ProjectMergePreferences,
+        enabled: bool,
     ) -> Result<()> {
         let conn = self.get_conn()?;
         let now = Utc::now().timestamp();
 
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
-        let merge_value = if preferences.auto_cancel_after_merge {
-            1
-        } else {
-            0
-        };
-        let pr_value = if preferences.auto_cancel_after_pr { 1 } else { 0 };
 
         conn.execute(
-            "INSERT INTO project_config (repository_path, auto_cancel_after_merge, auto_cancel_after_pr,
-                                            created_at, updated_at)
-                VALUES (?1, ?2, ?3, ?4, ?5)
+            "INSERT INTO project_config (
+                    repository_path,
+                    auto_cancel_after_merge,
+                    update_from_parent_on_start,
+                    created_at,
+                    updated_at
+                )
+                VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT auto_cancel_after_merge FROM project_config WHERE repository_path = ?1),
+                        1
+                    ),
+                    ?2,
+                    ?3,
+                    ?4
+                )
                 ON CONFLICT(repository_path) DO UPDATE SET
-                    auto_cancel_after_merge = excluded.auto_cancel_after_merge,
-                    auto_cancel_after_pr = excluded.auto_cancel_after_pr,
-                    updated_at              = excluded.updated_at",
-            params![canonical_path.to_string_lossy(), merge_value, pr_value, now, now],
+                    update_from_parent_on_start = excluded.update_from_parent_on_start,
+                    updated_at                  = excluded.updated_at",
+            params![canonical_path.to_string_lossy(), enabled, now, now],
         )?;
 
         Ok(())
@@ -547,23 +1436,25 @@ impl ProjectConfigMethods for Database {
         let canonical_path =
             std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
-        let query_res: rusqlite::Result<(Option<String>, Option<String>)> = conn.query_row(
-            "SELECT github_repository, github_default_branch
-                FROM project_config
-                WHERE repository_path = ?1",
-            params![canonical_path.to_string_lossy()],
-            |row| Ok((row.get(0)?, row.get(1)?)),
-        );
+        let query_res: rusqlite::Result<(Option<String>, Option<String>, Option<bool>)> = conn
+            .query_row(
+                "SELECT github_repository, github_default_branch, github_auto_pr_on_review
+                    FROM project_config
+                    WHERE repository_path = ?1",
+                params![canonical_path.to_string_lossy()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            );
 
         match query_res {
-            Ok((Some(repository), default_branch_opt)) => {
+            Ok((Some(repository), default_branch_opt, auto_pr_on_review)) => {
                 let default_branch = default_branch_opt.unwrap_or_else(|| "main".to_string());
                 Ok(Some(ProjectGithubConfig {
                     repository,
                     default_branch,
+                    auto_pr_on_review: auto_pr_on_review.unwrap_or(false),
                 }))
             }
-            Ok((None, _)) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Ok((None, _, _)) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
         }
     }
@@ -595,6 +1486,7 @@ impl ProjectConfigMethods for Database {
                     auto_cancel_after_merge,
                     github_repository,
                     github_default_branch,
+                    github_auto_pr_on_review,
                     created_at,
                     updated_at
                 )
@@ -607,16 +1499,19 @@ impl ProjectConfigMethods for Database {
                     ?2,
                     ?3,
                     ?4,
-                    ?4
+                    ?5,
+                    ?5
                 )
                 ON CONFLICT(repository_path) DO UPDATE SET
                     github_repository = excluded.github_repository,
                     github_default_branch = excluded.github_default_branch,
+                    github_auto_pr_on_review = excluded.github_auto_pr_on_review,
                     updated_at = excluded.updated_at",
             params![
                 canonical_path.to_string_lossy(),
                 repository,
                 default_branch,
+                config.auto_pr_on_review,
                 now,
             ],
         )?;
@@ -654,6 +1549,7 @@ impl ProjectConfigMethods for Database {
                 ON CONFLICT(repository_path) DO UPDATE SET
                     github_repository = NULL,
                     github_default_branch = NULL,
+                    github_auto_pr_on_review = 0,
                     updated_at = excluded.updated_at",
             params![canonical_path.to_string_lossy(), now],
         )?;
@@ -693,6 +1589,7 @@ mod tests {
         let config = ProjectGithubConfig {
             repository: "owner/example".to_string(),
             default_branch: "main".to_string(),
+            auto_pr_on_review: true,
         };
 
         db.set_project_github_config(&repo_path, &config)
@@ -713,6 +1610,7 @@ mod tests {
         let config = ProjectGithubConfig {
             repository: "owner/example".to_string(),
             default_branch: "main".to_string(),
+            auto_pr_on_review: false,
         };
 
         db.set_project_github_config(&repo_path, &config)
@@ -728,6 +1626,182 @@ mod tests {
         assert!(loaded.is_none());
     }
 
+    #[test]
+    fn merge_commit_trailers_round_trip() {
+        let (_tmp, repo_path) = create_temp_repo_path();
+        let db = Database::new_in_memory().expect("db");
+
+        assert!(db.get_project_merge_commit_trailers(&repo_path).unwrap().is_empty());
+
+        let trailers = vec![
+            "Reviewed-by: Jane <jane@example.com>".to_string(),
+            "Co-authored-by: Bob <bob@example.com>".to_string(),
+        ];
+        db.set_project_merge_commit_trailers(&repo_path, &trailers)
+            .unwrap();
+        assert_eq!(
+            db.get_project_merge_commit_trailers(&repo_path).unwrap(),
+            trailers
+        );
+    }
+
+    #[test]
+    fn skip_permissions_policy_round_trip() {
+        let (_tmp, repo_path) = create_temp_repo_path();
+        let db = Database::new_in_memory().expect("db");
+
+        assert!(db
+            .get_project_skip_permissions_policy(&repo_path)
+            .unwrap()
+            .is_none());
+
+        db.set_project_skip_permissions_policy(&repo_path, "require-confirmation")
+            .unwrap();
+        assert_eq!(
+            db.get_project_skip_permissions_policy(&repo_path)
+                .unwrap()
+                .as_deref(),
+            Some("require-confirmation")
+        );
+    }
+
+    #[test]
+    fn branch_collision_strategy_round_trip() {
+        let (_tmp, repo_path) = create_temp_repo_path();
+        let db = Database::new_in_memory().expect("db");
+
+        assert_eq!(
+            db.get_project_branch_collision_strategy(&repo_path).unwrap(),
+            BranchCollisionStrategy::Suffix
+        );
+
+        db.set_project_branch_collision_strategy(&repo_path, BranchCollisionStrategy::Increment)
+            .unwrap();
+        assert_eq!(
+            db.get_project_branch_collision_strategy(&repo_path).unwrap(),
+            BranchCollisionStrategy::Increment
+        );
+    }
+
+    #[test]
+    fn optional_sessions_settings_survive_filter_only_updates() {
+        let (_tmp, repo_path) = create_temp_repo_path();
+        let db = Database::new_in_memory().expect("db");
+
+        let settings = db.get_project_sessions_settings(&repo_path).unwrap();
+        assert_eq!(
+            settings.agent_restart_policy,
+            Some(AgentRestartPolicy::default())
+        );
+        assert_eq!(
+            db.get_project_setup_script_timeout(&repo_path).unwrap(),
+            Some(Duration::from_secs(DEFAULT_SETUP_SCRIPT_TIMEOUT_SECS))
+        );
+        assert_eq!(
+            db.get_project_max_parallel_setup_scripts(&repo_path)
+                .unwrap(),
+            DEFAULT_MAX_PARALLEL_SETUP_SCRIPTS as usize
+        );
+
+        let policy = AgentRestartPolicy {
+            mode: AgentRestartMode::OnCrash,
+            max_retries: 5,
+            backoff_secs: 10,
+        };
+        db.set_project_sessions_settings(
+            &repo_path,
+            &ProjectSessionsSettings {
+                filter_mode: "spec".to_string(),
+                agent_restart_policy: Some(policy),
+                setup_script: Some("npm install".to_string()),
+                wait_for_setup: Some(true),
+                setup_script_timeout_secs: Some(0),
+                max_parallel_setup_scripts: Some(0),
+                watcher_ignore_patterns: Some(vec![" coverage/** ".to_string(), String::new()]),
+                worktree_copy_patterns: Some(vec![".env.local".to_string()]),
+                clone_dirs: Some(vec!["node_modules/".to_string(), " ".to_string()]),
+            },
+        )
+        .unwrap();
+        db.set_project_sessions_settings(
+            &repo_path,
+            &ProjectSessionsSettings {
+                filter_mode: "reviewed".to_string(),
+                agent_restart_policy: None,
+                setup_script: None,
+                wait_for_setup: None,
+                setup_script_timeout_secs: None,
+                max_parallel_setup_scripts: None,
+                watcher_ignore_patterns: None,
+                worktree_copy_patterns: None,
+                clone_dirs: None,
+            },
+        )
+        .unwrap();
+
+        let settings = db.get_project_sessions_settings(&repo_path).unwrap();
+        assert_eq!(settings.filter_mode, "reviewed");
+        assert_eq!(settings.agent_restart_policy, Some(policy));
+        assert_eq!(settings.setup_script.as_deref(), Some("npm install"));
+        assert_eq!(settings.wait_for_setup, Some(true));
+        assert_eq!(settings.setup_script_timeout_secs, Some(0));
+        assert_eq!(settings.max_parallel_setup_scripts, Some(1));
+        assert_eq!(
+            db.get_project_setup_script_timeout(&repo_path).unwrap(),
+            None
+        );
+        assert_eq!(
+            settings.watcher_ignore_patterns,
+            Some(vec!["coverage/**".to_string()])
+        );
+        assert_eq!(
+            db.get_project_worktree_copy_patterns(&repo_path).unwrap(),
+            vec![".env.local".to_string()]
+        );
+        assert_eq!(
+            db.get_project_worktree_clone_dirs(&repo_path).unwrap(),
+            vec!["node_modules".to_string()]
+        );
+    }
+
+    #[test]
+    fn worktree_root_round_trip_and_clear() {
+        let (_tmp, repo_path) = create_temp_repo_path();
+        let db = Database::new_in_memory().expect("db");
+
+        assert!(db.get_project_worktree_root(&repo_path).unwrap().is_none());
+
+        let root = PathBuf::from("/fast/disk/worktrees");
+        db.set_project_worktree_root(&repo_path, Some(&root)).unwrap();
+        assert_eq!(
+            db.get_project_worktree_root(&repo_path).unwrap(),
+            Some(root)
+        );
+
+        db.set_project_worktree_root(&repo_path, None).unwrap();
+        assert!(db.get_project_worktree_root(&repo_path).unwrap().is_none());
+    }
+
+    #[test]
+    fn prompt_templates_round_trip() {
+        let (_tmp, repo_path) = create_temp_repo_path();
+        let db = Database::new_in_memory().expect("db");
+
+        assert!(db.get_project_prompt_templates(&repo_path).unwrap().is_empty());
+
+        let templates = vec![PromptTemplate {
+            name: "bugfix".to_string(),
+            content: "Fix {{issue}} on {{branch}}".to_string(),
+            defaults: HashMap::from([("issue".to_string(), "the flaky test".to_string())]),
+        }];
+        db.set_project_prompt_templates(&repo_path, &templates)
+            .unwrap();
+        assert_eq!(
+            db.get_project_prompt_templates(&repo_path).unwrap(),
+            templates
+        );
+    }
+
     #[test]
     fn defaults_auto_cancel_true_for_new_project_rows() {
         let db = Database::new_in_memory().expect("db");
@@ -815,4 +1889,16 @@ mod tests {
 
         assert_eq!(loaded, "custom-prefix");
     }
+
+    #[test]
+    fn database_backups_enabled_defaults_to_true() {
+        let db = Database::new_in_memory().expect("db");
+        let (_tmp, repo_path) = create_temp_repo_path();
+
+        assert!(db.get_project_database_backups_enabled(&repo_path).unwrap());
+
+        db.set_project_database_backups_enabled(&repo_path, false)
+            .unwrap();
+        assert!(!db.get_project_database_backups_enabled(&repo_path).unwrap());
+    }
 }
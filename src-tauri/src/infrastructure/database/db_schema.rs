@@ -1,8 +1,91 @@
 use super::connection::Database;
+use anyhow::Context;
+use rusqlite::{Connection, Transaction, TransactionBehavior, params};
 
+/// A one-off schema or data change, applied once in `version` order and recorded in
+/// `schema_version`. Databases written by builds that predate the table may already
+/// carry the change, so `apply` must check for it before making it.
+struct Migration {
+    version: i64,
+    name: &'static str,
+    apply: fn(&Transaction<'_>) -> anyhow::Result<()>,
+}
+
+/// Append new migrations at the end; never renumber or edit one that has shipped.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline_schema",
+        apply: create_baseline_schema,
+    },
+    Migration {
+        version: 2,
+        name: "move_legacy_spec_sessions_to_specs",
+        apply: migrate_legacy_spec_sessions,
+    },
+    Migration {
+        version: 3,
+        name: "index_sessions_by_repository_and_state",
+        apply: index_sessions_by_repository_and_state,
+    },
+    Migration {
+        version: 4,
+        name: "add_project_worktree_copy_patterns",
+        apply: add_project_worktree_copy_patterns,
+    },
+    Migration {
+        version: 5,
+        name: "add_project_setup_script_timeout",
+        apply: add_project_setup_script_timeout,
+    },
+    Migration {
+        version: 6,
+        name: "add_project_setup_script_concurrency",
+        apply: add_project_setup_script_concurrency,
+    },
+    Migration {
+        version: 7,
+        name: "add_project_worktree_clone_dirs",
+        apply: add_project_worktree_clone_dirs,
+    },
+];
+
+/// Brings the database up to the newest schema this build knows. Fails if the database
+/// was written by a newer build, rather than running against columns it does not expect.
 pub fn initialize_schema(db: &Database) -> anyhow::Result<()> {
     let conn = db.get_conn()?;
+    apply_migrations(&conn, MIGRATIONS)?;
+    backfill_missing_defaults(&conn)
+}
 
+/// Checks that a database opened without write access matches the schema this build
+/// expects. Migrations cannot run on such a connection, so a database that still needs
+/// one is rejected just like one written by a newer build.
+pub fn verify_schema_is_current(db: &Database) -> anyhow::Result<()> {
+    let conn = db.get_conn()?;
+    let current = schema_version(&conn).context("database has no schema version")?;
+    let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+    if current > latest {
+        return Err(newer_schema_error(current, latest));
+    }
+    if current < latest {
+        anyhow::bail!(
+            "Database schema version {current} is older than version {latest}; open the project once without --read-only to upgrade it"
+        );
+    }
+    Ok(())
+}
+
+fn newer_schema_error(current: i64, latest: i64) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Database schema version {current} is newer than version {latest} supported by this build of Schaltwerk; update the app to open this database"
+    )
+}
+
+/// Every table, index and column that existed before migrations were versioned. Written
+/// with `IF NOT EXISTS` and tolerated `ADD COLUMN` failures, because databases from
+/// those builds already carry any subset of it.
+fn create_baseline_schema(conn: &Transaction<'_>) -> anyhow::Result<()> {
     // Main sessions table - consolidated schema
     conn.execute(
         "CREATE TABLE IF NOT EXISTS sessions (
@@ -76,7 +159,7 @@ pub fn initialize_schema(db: &Database) -> anyhow::Result<()> {
     )?;
 
     // Apply migrations for app_config
-    apply_app_config_migrations(&conn)?;
+    apply_app_config_migrations(conn)?;
 
     conn.execute(
         "INSERT OR IGNORE INTO app_config (
@@ -95,7 +178,7 @@ pub fn initialize_schema(db: &Database) -> anyhow::Result<()> {
     )?;
 
     // Apply migrations for sessions table
-    apply_sessions_migrations(&conn)?;
+    apply_sessions_migrations(conn)?;
 
     // Optional columns added by migrations need their indexes created after the migration runs.
     let _ = conn.execute(
@@ -155,8 +238,8 @@ pub fn initialize_schema(db: &Database) -> anyhow::Result<()> {
         [],
     )?;
 
-    // Apply migrations for specs table (including legacy spec rows)
-    apply_specs_migrations(&conn)?;
+    // Apply migrations for specs table
+    apply_specs_migrations(conn)?;
 
     // Create project_config table for project-specific settings
     conn.execute(
@@ -173,7 +256,7 @@ pub fn initialize_schema(db: &Database) -> anyhow::Result<()> {
     )?;
 
     // Apply migrations for project_config
-    apply_project_config_migrations(&conn)?;
+    apply_project_config_migrations(conn)?;
 
     // Create agent_binaries table for storing agent binary configurations
     conn.execute(
@@ -210,6 +293,254 @@ pub fn initialize_schema(db: &Database) -> anyhow::Result<()> {
         [],
     )?;
 
+    // Last computed diff stats per session, keyed by the oids they were computed against
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS git_stats (
+            session_id TEXT PRIMARY KEY,
+            files_changed INTEGER NOT NULL DEFAULT 0,
+            lines_added INTEGER NOT NULL DEFAULT 0,
+            lines_removed INTEGER NOT NULL DEFAULT 0,
+            has_uncommitted BOOLEAN NOT NULL DEFAULT FALSE,
+            calculated_at INTEGER NOT NULL,
+            last_diff_change_ts INTEGER,
+            head_oid TEXT,
+            parent_oid TEXT,
+            has_remote BOOLEAN NOT NULL DEFAULT FALSE,
+            remote_ahead INTEGER,
+            remote_behind INTEGER,
+            remote_oid TEXT
+        )",
+        [],
+    )?;
+    let _ = conn.execute(
+        "ALTER TABLE git_stats ADD COLUMN has_remote BOOLEAN NOT NULL DEFAULT FALSE",
+        [],
+    );
+    let _ = conn.execute("ALTER TABLE git_stats ADD COLUMN remote_ahead INTEGER", []);
+    let _ = conn.execute("ALTER TABLE git_stats ADD COLUMN remote_behind INTEGER", []);
+    let _ = conn.execute("ALTER TABLE git_stats ADD COLUMN remote_oid TEXT", []);
+
+    // Audit trail of session_state changes, newest rows last
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_state_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL,
+            from_state TEXT,
+            to_state TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            reason TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_session_state_history_session ON session_state_history(session_id, timestamp)",
+        [],
+    )?;
+
+    // In-flight session operations; rows without finished_at survived a crash
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS operation_journal (
+            id TEXT PRIMARY KEY,
+            repository_path TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            session_id TEXT,
+            session_name TEXT NOT NULL,
+            branch TEXT,
+            worktree_path TEXT,
+            owns_branch BOOLEAN NOT NULL DEFAULT FALSE,
+            base_commit TEXT,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER,
+            actor TEXT,
+            agent_type TEXT
+        )",
+        [],
+    )?;
+    let _ = conn.execute("ALTER TABLE operation_journal ADD COLUMN actor TEXT", []);
+    let _ = conn.execute(
+        "ALTER TABLE operation_journal ADD COLUMN agent_type TEXT",
+        [],
+    );
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_operation_journal_open ON operation_journal(repository_path, finished_at)",
+        [],
+    )?;
+
+    // Specs queued to start at a later time; times are UTC epoch seconds
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scheduled_specs (
+            id TEXT PRIMARY KEY,
+            repository_path TEXT NOT NULL,
+            spec_name TEXT NOT NULL,
+            not_before INTEGER NOT NULL,
+            base_branch TEXT,
+            agent_type TEXT,
+            skip_permissions BOOLEAN,
+            skip_if_late BOOLEAN NOT NULL DEFAULT FALSE,
+            status TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            finished_at INTEGER,
+            error TEXT
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_specs_repo_status ON scheduled_specs(repository_path, status)",
+        [],
+    )?;
+
+    // Worktree snapshots; the captured state lives in the git ref named by ref_name
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_snapshots (
+            id TEXT PRIMARY KEY,
+            repository_path TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            label TEXT NOT NULL,
+            ref_name TEXT NOT NULL,
+            head_oid TEXT NOT NULL,
+            dirty_files INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_session_snapshots_session ON session_snapshots(repository_path, session_id)",
+        [],
+    )?;
+
+    // Multi-root workspaces; repo_paths is a JSON array of repository paths
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS workspaces (
+            name TEXT PRIMARY KEY,
+            repo_paths TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Speeds up the per-repository session list queries, which filter on both columns.
+fn index_sessions_by_repository_and_state(conn: &Transaction<'_>) -> anyhow::Result<()> {
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sessions_repo_state ON sessions(repository_path, session_state)",
+        [],
+    )?;
+    Ok(())
+}
+
+fn add_project_worktree_copy_patterns(conn: &Transaction<'_>) -> anyhow::Result<()> {
+    conn.execute(
+        "ALTER TABLE project_config ADD COLUMN worktree_copy_patterns TEXT",
+        [],
+    )?;
+    Ok(())
+}
+
+fn add_project_setup_script_timeout(conn: &Transaction<'_>) -> anyhow::Result<()> {
+    conn.execute(
+        "ALTER TABLE project_config ADD COLUMN setup_script_timeout_secs INTEGER",
+        [],
+    )?;
+    Ok(())
+}
+
+fn add_project_setup_script_concurrency(conn: &Transaction<'_>) -> anyhow::Result<()> {
+    conn.execute(
+        "ALTER TABLE project_config ADD COLUMN max_parallel_setup_scripts INTEGER",
+        [],
+    )?;
+    Ok(())
+}
+
+fn add_project_worktree_clone_dirs(conn: &Transaction<'_>) -> anyhow::Result<()> {
+    conn.execute(
+        "ALTER TABLE project_config ADD COLUMN worktree_clone_dirs TEXT",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Fills columns that older code paths may still leave empty. Runs on every open.
+fn backfill_missing_defaults(conn: &Connection) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE sessions SET original_parent_branch = parent_branch WHERE original_parent_branch IS NULL",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE project_config SET branch_prefix = 'schaltwerk' WHERE branch_prefix IS NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Returns the highest migration version recorded in `schema_version`, or 0 for a
+/// database that has none.
+pub(super) fn schema_version(conn: &Connection) -> anyhow::Result<i64> {
+    Ok(conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+/// Applies every migration newer than the recorded schema version. Each one runs in its
+/// own immediate transaction together with its `schema_version` row, so a failure
+/// leaves neither the change nor the record behind and the next open retries it.
+fn apply_migrations(conn: &Connection, migrations: &[Migration]) -> anyhow::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    let current = schema_version(conn)?;
+    let latest = migrations.last().map_or(0, |m| m.version);
+    if current > latest {
+        return Err(newer_schema_error(current, latest));
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        // Another process may have applied it while we waited for the write lock
+        let already_applied = tx
+            .query_row(
+                "SELECT 1 FROM schema_version WHERE version = ?1",
+                params![migration.version],
+                |_| Ok(()),
+            )
+            .is_ok();
+        if already_applied {
+            continue;
+        }
+
+        (migration.apply)(&tx).with_context(|| {
+            format!(
+                "schema migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            params![
+                migration.version,
+                migration.name,
+                chrono::Utc::now().timestamp()
+            ],
+        )?;
+        tx.commit()?;
+        log::info!(
+            "Applied schema migration {} ({})",
+            migration.version,
+            migration.name
+        );
+    }
     Ok(())
 }
 
@@ -303,27 +634,54 @@ fn apply_sessions_migrations(conn: &rusqlite::Connection) -> anyhow::Result<()>
         "ALTER TABLE sessions ADD COLUMN original_parent_branch TEXT",
         [],
     );
-    // Backfill original_parent_branch from parent_branch for existing sessions
-    let _ = conn.execute(
-        "UPDATE sessions SET original_parent_branch = parent_branch WHERE original_parent_branch IS NULL",
-        [],
-    );
     // GitHub PR integration fields
     let _ = conn.execute("ALTER TABLE sessions ADD COLUMN pr_number INTEGER", []);
     let _ = conn.execute("ALTER TABLE sessions ADD COLUMN pr_url TEXT", []);
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN session_group TEXT", []);
+    let _ = conn.execute(
+        "ALTER TABLE sessions ADD COLUMN record_terminal_output INTEGER DEFAULT 0",
+        [],
+    );
     // Epic grouping (optional)
     let _ = conn.execute("ALTER TABLE sessions ADD COLUMN epic_id TEXT", []);
+    // Outcome tracking for per-agent analytics
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN reviewed_at INTEGER", []);
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN merged_at INTEGER", []);
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN retry_of TEXT", []);
+    // Per-session launch overrides and the record of the last launch (JSON)
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN launch_overrides TEXT", []);
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN launch_snapshot TEXT", []);
+    // Extra agent CLI args for this session only
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN extra_cli_args TEXT", []);
+    // Environment variables set or unset for this session's agents (JSON)
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN env_overrides TEXT", []);
+    // Human-readable label shared by all sessions of a version group
+    let _ = conn.execute(
+        "ALTER TABLE sessions ADD COLUMN version_group_label TEXT",
+        [],
+    );
+    // Stash created from the session worktree, if any
+    let _ = conn.execute("ALTER TABLE sessions ADD COLUMN stash_oid TEXT", []);
+    // Whether the last setup script run in the session worktree failed
+    let _ = conn.execute(
+        "ALTER TABLE sessions ADD COLUMN setup_failed INTEGER DEFAULT 0",
+        [],
+    );
     Ok(())
 }
 
-/// Apply migrations for the specs table and migrate legacy spec-state sessions.
+/// Apply migrations for the specs table
 fn apply_specs_migrations(conn: &rusqlite::Connection) -> anyhow::Result<()> {
     // Idempotent - silently fails if column already exists
     let _ = conn.execute("ALTER TABLE specs ADD COLUMN epic_id TEXT", []);
+    let _ = conn.execute("ALTER TABLE specs ADD COLUMN retry_of TEXT", []);
+    let _ = conn.execute("ALTER TABLE specs ADD COLUMN depends_on_session TEXT", []);
+    Ok(())
+}
 
-    let tx = conn.unchecked_transaction()?;
-
-    tx.execute(
+/// Moves rows left in `sessions` with the legacy `spec` state into the `specs` table.
+fn migrate_legacy_spec_sessions(conn: &Transaction<'_>) -> anyhow::Result<()> {
+    conn.execute(
         "INSERT INTO specs (id, name, display_name, epic_id, repository_path, repository_name, content, created_at, updated_at)
          SELECT s.id, s.name, s.display_name, s.epic_id,
                 s.repository_path, s.repository_name,
@@ -335,9 +693,7 @@ fn apply_specs_migrations(conn: &rusqlite::Connection) -> anyhow::Result<()> {
         [],
     )?;
 
-    tx.execute("DELETE FROM sessions WHERE session_state = 'spec'", [])?;
-
-    tx.commit()?;
+    conn.execute("DELETE FROM sessions WHERE session_state = 'spec'", [])?;
     Ok(())
 }
 
@@ -373,10 +729,6 @@ fn apply_project_config_migrations(conn: &rusqlite::Connection) -> anyhow::Resul
         "ALTER TABLE project_config ADD COLUMN branch_prefix TEXT",
         [],
     );
-    let _ = conn.execute(
-        "UPDATE project_config SET branch_prefix = 'schaltwerk' WHERE branch_prefix IS NULL",
-        [],
-    );
     let _ = conn.execute(
         "ALTER TABLE project_config ADD COLUMN auto_cancel_after_merge INTEGER DEFAULT 0",
         [],
@@ -389,17 +741,77 @@ fn apply_project_config_migrations(conn: &rusqlite::Connection) -> anyhow::Resul
         "ALTER TABLE project_config ADD COLUMN github_default_branch TEXT",
         [],
     );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN github_auto_pr_on_review INTEGER DEFAULT 0",
+        [],
+    );
     let _ = conn.execute(
         "ALTER TABLE project_config ADD COLUMN auto_cancel_after_pr INTEGER DEFAULT 0",
         [],
     );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN require_passing_tests INTEGER DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN merge_test_command TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN merge_commit_trailers TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN skip_permissions_policy TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN worktree_root TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN prompt_templates TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN database_backups_enabled INTEGER DEFAULT 1",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN branch_collision_strategy TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN agent_restart_policy TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN keep_recordings INTEGER DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN spec_file_mirror INTEGER DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN update_from_parent_on_start INTEGER DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN wait_for_setup INTEGER DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE project_config ADD COLUMN watcher_ignore_patterns TEXT",
+        [],
+    );
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::apply_specs_migrations;
-    use rusqlite::Connection;
+    use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn specs_migration_does_not_delete_on_insert_failure() {
@@ -459,7 +871,8 @@ mod tests {
         )
         .unwrap();
 
-        let result = apply_specs_migrations(&conn);
+        apply_specs_migrations(&conn).unwrap();
+        let result = apply_migrations(&conn, &MIGRATIONS[1..2]);
         assert!(
             result.is_err(),
             "migration should surface insert failure to avoid silent deletion"
@@ -476,5 +889,210 @@ mod tests {
             remaining, 1,
             "spec rows in sessions must not be deleted on failed insert"
         );
+        assert_eq!(
+            schema_version(&conn).unwrap(),
+            0,
+            "failed migration must not be recorded"
+        );
+    }
+
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    fn insert_legacy_spec(conn: &Connection, id: &str) {
+        conn.execute(
+            "INSERT INTO sessions (id, name, repository_path, repository_name, branch, parent_branch,
+                worktree_path, status, session_state, created_at, updated_at, initial_prompt)
+             VALUES (?1, ?1, '/repo', 'repo', ?1, 'main', '/repo/wt', 'spec', 'spec', 0, 0, '# spec')",
+            params![id],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn opening_old_schema_applies_pending_migrations() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("sessions.db");
+        {
+            // Sessions table as written by builds without the specs table or schema_version
+            let conn = Connection::open(&path).unwrap();
+            conn.execute(
+                "CREATE TABLE sessions (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    repository_path TEXT NOT NULL,
+                    repository_name TEXT NOT NULL,
+                    branch TEXT NOT NULL,
+                    parent_branch TEXT NOT NULL,
+                    worktree_path TEXT NOT NULL,
+                    status TEXT NOT NULL,
+                    session_state TEXT DEFAULT 'running',
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL,
+                    last_activity INTEGER,
+                    initial_prompt TEXT,
+                    ready_to_merge BOOLEAN DEFAULT FALSE,
+                    UNIQUE(repository_path, name)
+                )",
+                [],
+            )
+            .unwrap();
+            insert_legacy_spec(&conn, "old-spec");
+        }
+
+        let db = Database::new(Some(path)).unwrap();
+        let conn = db.get_conn().unwrap();
+
+        assert_eq!(
+            schema_version(&conn).unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+        assert_eq!(
+            count(&conn, "SELECT COUNT(*) FROM schema_version"),
+            MIGRATIONS.len() as i64
+        );
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM sessions"), 0);
+        let content: String = conn
+            .query_row(
+                "SELECT content FROM specs WHERE id = 'old-spec'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(content, "# spec");
+    }
+
+    #[test]
+    fn opening_current_schema_is_a_no_op() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("sessions.db");
+        let applied_at = {
+            let db = Database::new(Some(path.clone())).unwrap();
+            let conn = db.get_conn().unwrap();
+            // Rows like this are only moved by migration 2, which must not run again
+            insert_legacy_spec(&conn, "late-spec");
+            conn.execute("UPDATE schema_version SET applied_at = 42", [])
+                .unwrap();
+            count(&conn, "SELECT SUM(applied_at) FROM schema_version")
+        };
+
+        let db = Database::new(Some(path)).unwrap();
+        let conn = db.get_conn().unwrap();
+
+        assert_eq!(
+            count(&conn, "SELECT SUM(applied_at) FROM schema_version"),
+            applied_at
+        );
+        assert_eq!(
+            count(&conn, "SELECT COUNT(*) FROM schema_version"),
+            MIGRATIONS.len() as i64
+        );
+        assert_eq!(
+            count(
+                &conn,
+                "SELECT COUNT(*) FROM sessions WHERE session_state = 'spec'"
+            ),
+            1
+        );
+    }
+
+    fn has_index(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = ?1",
+            params![name],
+            |_| Ok(()),
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn fresh_database_is_at_latest_version() {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::new(Some(tmp.path().join("sessions.db"))).unwrap();
+
+        assert_eq!(
+            db.get_schema_version().unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+        let conn = db.get_conn().unwrap();
+        assert!(has_index(&conn, "idx_sessions_repo_state"));
+        let names = {
+            let mut stmt = conn
+                .prepare("SELECT name FROM schema_version ORDER BY version")
+                .unwrap();
+            stmt.query_map([], |row| row.get::<_, String>(0))
+                .unwrap()
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .unwrap()
+        };
+        assert_eq!(names, MIGRATIONS.iter().map(|m| m.name).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn intermediate_version_applies_only_newer_migrations() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("sessions.db");
+        {
+            let db = Database::new(Some(path.clone())).unwrap();
+            let conn = db.get_conn().unwrap();
+            // Roll back to a database that only went through the baseline
+            conn.execute_batch(
+                "DELETE FROM schema_version WHERE version > 1;
+                 DROP INDEX idx_sessions_repo_state;
+                 UPDATE schema_version SET applied_at = 42;",
+            )
+            .unwrap();
+            insert_legacy_spec(&conn, "pending-spec");
+        }
+
+        let db = Database::new(Some(path)).unwrap();
+        let conn = db.get_conn().unwrap();
+
+        assert_eq!(
+            db.get_schema_version().unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+        assert_eq!(
+            count(
+                &conn,
+                "SELECT applied_at FROM schema_version WHERE version = 1"
+            ),
+            42
+        );
+        assert!(has_index(&conn, "idx_sessions_repo_state"));
+        assert_eq!(
+            count(
+                &conn,
+                "SELECT COUNT(*) FROM specs WHERE id = 'pending-spec'"
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn database_from_newer_build_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("sessions.db");
+        let future = MIGRATIONS.last().unwrap().version + 1;
+        {
+            let db = Database::new(Some(path.clone())).unwrap();
+            db.get_conn()
+                .unwrap()
+                .execute(
+                    "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, 'future', 0)",
+                    params![future],
+                )
+                .unwrap();
+        }
+
+        let err = Database::new(Some(path))
+            .err()
+            .expect("newer schema must not open");
+        let message = format!("{err:#}");
+        assert!(
+            message.contains(&format!("schema version {future} is newer")),
+            "{message}"
+        );
     }
 }
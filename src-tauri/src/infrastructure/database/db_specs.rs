@@ -12,9 +12,15 @@ pub trait SpecMethods {
     fn get_spec_by_id(&self, id: &str) -> Result<Spec>;
     fn list_specs(&self, repo_path: &Path) -> Result<Vec<Spec>>;
     fn update_spec_content(&self, id: &str, content: &str) -> Result<()>;
+    /// Appends `content` on a new line in a single statement, so concurrent appends
+    /// cannot overwrite each other.
+    fn append_spec_content(&self, id: &str, content: &str) -> Result<()>;
     fn update_spec_display_name(&self, id: &str, display_name: &str) -> Result<()>;
     fn update_spec_epic_id(&self, id: &str, epic_id: Option<&str>) -> Result<()>;
     fn delete_spec(&self, id: &str) -> Result<()>;
+    fn set_spec_retry_of(&self, id: &str, session_id: Option<&str>) -> Result<()>;
+    fn get_spec_retry_of(&self, id: &str) -> Result<Option<String>>;
+    fn set_spec_depends_on_session(&self, id: &str, session_name: Option<&str>) -> Result<()>;
 }
 
 impl SpecMethods for Database {
@@ -25,8 +31,8 @@ impl SpecMethods for Database {
                 id, name, display_name,
                 epic_id,
                 repository_path, repository_name, content,
-                created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                created_at, updated_at, depends_on_session
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 spec.id,
                 spec.name,
@@ -37,6 +43,7 @@ impl SpecMethods for Database {
                 spec.content,
                 spec.created_at.timestamp(),
                 spec.updated_at.timestamp(),
+                spec.depends_on_session,
             ],
         )?;
         Ok(())
@@ -49,7 +56,7 @@ impl SpecMethods for Database {
             "SELECT id, name, display_name,
                     epic_id,
                     repository_path, repository_name, content,
-                    created_at, updated_at
+                    created_at, updated_at, depends_on_session
              FROM specs
              WHERE repository_path = ?1 AND name = ?2",
         )?;
@@ -64,7 +71,7 @@ impl SpecMethods for Database {
             "SELECT id, name, display_name,
                     epic_id,
                     repository_path, repository_name, content,
-                    created_at, updated_at
+                    created_at, updated_at, depends_on_session
              FROM specs
              WHERE id = ?1",
         )?;
@@ -78,7 +85,7 @@ impl SpecMethods for Database {
             "SELECT id, name, display_name,
                     epic_id,
                     repository_path, repository_name, content,
-                    created_at, updated_at
+                    created_at, updated_at, depends_on_session
              FROM specs
              WHERE repository_path = ?1
              ORDER BY updated_at DESC, created_at DESC, rowid DESC",
@@ -102,6 +109,21 @@ impl SpecMethods for Database {
         Ok(())
     }
 
+    fn append_spec_content(&self, id: &str, content: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE specs
+             SET content = CASE
+                 WHEN content = '' THEN ?1
+                 ELSE content || char(10) || ?1
+             END,
+             updated_at = ?2
+             WHERE id = ?3",
+            params![content, Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
     fn update_spec_display_name(&self, id: &str, display_name: &str) -> Result<()> {
         let conn = self.get_conn()?;
         conn.execute(
@@ -129,6 +151,34 @@ impl SpecMethods for Database {
         conn.execute("DELETE FROM specs WHERE id = ?1", params![id])?;
         Ok(())
     }
+
+    fn set_spec_retry_of(&self, id: &str, session_id: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE specs SET retry_of = ?1 WHERE id = ?2",
+            params![session_id, id],
+        )?;
+        Ok(())
+    }
+
+    fn get_spec_retry_of(&self, id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let retry_of = conn.query_row(
+            "SELECT retry_of FROM specs WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(retry_of)
+    }
+
+    fn set_spec_depends_on_session(&self, id: &str, session_name: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE specs SET depends_on_session = ?1, updated_at = ?2 WHERE id = ?3",
+            params![session_name, Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
 }
 
 fn row_to_spec(row: &Row<'_>) -> rusqlite::Result<Spec> {
@@ -148,5 +198,51 @@ fn row_to_spec(row: &Row<'_>) -> rusqlite::Result<Spec> {
             let ts: i64 = row.get(8)?;
             utc_from_epoch_seconds_lossy(ts)
         },
+        depends_on_session: row.get(9)?,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tempfile::TempDir;
+
+    #[test]
+    fn concurrent_appends_keep_both_contents() {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::new(Some(tmp.path().join("sessions.db"))).unwrap();
+        let now = Utc::now();
+        db.create_spec(&Spec {
+            id: "spec-1".to_string(),
+            name: "concurrent".to_string(),
+            display_name: None,
+            epic_id: None,
+            repository_path: tmp.path().to_path_buf(),
+            repository_name: "repo".to_string(),
+            content: "# Plan".to_string(),
+            created_at: now,
+            updated_at: now,
+            depends_on_session: None,
+        })
+        .unwrap();
+
+        let handles: Vec<_> = ["- first", "- second"]
+            .into_iter()
+            .map(|line| {
+                let db = db.clone();
+                thread::spawn(move || db.append_spec_content("spec-1", line).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let content = db.get_spec_by_id("spec-1").unwrap().content;
+        let lines: Vec<_> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "# Plan");
+        assert!(lines.contains(&"- first"));
+        assert!(lines.contains(&"- second"));
+    }
+}
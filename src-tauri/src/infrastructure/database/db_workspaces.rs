@@ -0,0 +1,120 @@
+use super::connection::Database;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+/// A multi-root workspace: sibling repositories managed together in one window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceDefinition {
+    pub name: String,
+    pub repo_paths: Vec<String>,
+}
+
+pub trait WorkspaceMethods {
+    fn save_workspace(&self, workspace: &WorkspaceDefinition) -> Result<()>;
+    fn list_workspaces(&self) -> Result<Vec<WorkspaceDefinition>>;
+    fn get_workspace(&self, name: &str) -> Result<Option<WorkspaceDefinition>>;
+    fn delete_workspace(&self, name: &str) -> Result<()>;
+}
+
+fn row_to_workspace(row: &rusqlite::Row<'_>) -> rusqlite::Result<(String, String)> {
+    Ok((row.get(0)?, row.get(1)?))
+}
+
+fn decode_workspace((name, repo_paths): (String, String)) -> Result<WorkspaceDefinition> {
+    let repo_paths = serde_json::from_str(&repo_paths)
+        .map_err(|e| anyhow!("Invalid repository list for workspace '{name}': {e}"))?;
+    Ok(WorkspaceDefinition { name, repo_paths })
+}
+
+impl WorkspaceMethods for Database {
+    fn save_workspace(&self, workspace: &WorkspaceDefinition) -> Result<()> {
+        if workspace.repo_paths.is_empty() {
+            return Err(anyhow!(
+                "Workspace '{}' needs at least one repository",
+                workspace.name
+            ));
+        }
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO workspaces (name, repo_paths, updated_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET repo_paths = excluded.repo_paths, updated_at = excluded.updated_at",
+            params![
+                workspace.name,
+                serde_json::to_string(&workspace.repo_paths)?,
+                Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn list_workspaces(&self) -> Result<Vec<WorkspaceDefinition>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare("SELECT name, repo_paths FROM workspaces ORDER BY name ASC")?;
+        let rows = stmt.query_map([], row_to_workspace)?;
+
+        let mut workspaces = Vec::new();
+        for row in rows {
+            workspaces.push(decode_workspace(row?)?);
+        }
+        Ok(workspaces)
+    }
+
+    fn get_workspace(&self, name: &str) -> Result<Option<WorkspaceDefinition>> {
+        let conn = self.get_conn()?;
+        conn.query_row(
+            "SELECT name, repo_paths FROM workspaces WHERE name = ?1",
+            params![name],
+            row_to_workspace,
+        )
+        .optional()?
+        .map(decode_workspace)
+        .transpose()
+    }
+
+    fn delete_workspace(&self, name: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM workspaces WHERE name = ?1", params![name])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(name: &str, repos: &[&str]) -> WorkspaceDefinition {
+        WorkspaceDefinition {
+            name: name.to_string(),
+            repo_paths: repos.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn workspaces_round_trip_and_update_in_place() {
+        let db = Database::new_in_memory().unwrap();
+        let platform = workspace("platform", &["/src/api", "/src/web", "/src/infra"]);
+        db.save_workspace(&platform).unwrap();
+        db.save_workspace(&workspace("docs", &["/src/docs"]))
+            .unwrap();
+
+        assert_eq!(db.get_workspace("platform").unwrap(), Some(platform));
+        let names: Vec<_> = db
+            .list_workspaces()
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert_eq!(names, vec!["docs", "platform"]);
+
+        let trimmed = workspace("platform", &["/src/api", "/src/web"]);
+        db.save_workspace(&trimmed).unwrap();
+        assert_eq!(db.get_workspace("platform").unwrap(), Some(trimmed));
+
+        db.delete_workspace("platform").unwrap();
+        assert!(db.get_workspace("platform").unwrap().is_none());
+        assert!(db.save_workspace(&workspace("empty", &[])).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use super::connection::Database;
+use super::db_schema;
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use rusqlite::{Connection, TransactionBehavior, params};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Cancelled sessions untouched for longer than this are deleted by maintenance.
+pub const CANCELLED_SESSION_RETENTION_DAYS: i64 = 90;
+
+/// Archive limit used when `app_config` has none, matching `get_archive_max_entries`.
+const DEFAULT_ARCHIVE_MAX_ENTRIES: i64 = 50;
+
+/// At most this many `integrity_check` messages end up in the report.
+const MAX_INTEGRITY_ERRORS: usize = 20;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+    pub bytes_reclaimed: u64,
+    pub integrity_ok: bool,
+    pub integrity_errors: Vec<String>,
+    pub cancelled_sessions_pruned: usize,
+    pub orphaned_git_stats_pruned: usize,
+    pub orphaned_history_pruned: usize,
+    pub archived_specs_pruned: usize,
+}
+
+impl MaintenanceReport {
+    pub fn rows_pruned(&self) -> usize {
+        self.cancelled_sessions_pruned
+            + self.orphaned_git_stats_pruned
+            + self.orphaned_history_pruned
+            + self.archived_specs_pruned
+    }
+}
+
+/// Size, per-table row counts and schema version of the database, for the settings UI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseInfo {
+    pub size_bytes: u64,
+    pub schema_version: i64,
+    pub table_row_counts: BTreeMap<String, i64>,
+}
+
+fn database_size(conn: &Connection) -> Result<u64> {
+    let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    Ok(u64::try_from(page_count * page_size).unwrap_or(0))
+}
+
+/// Returns the messages of `PRAGMA integrity_check`; empty when the database is sound.
+fn integrity_errors(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA integrity_check({MAX_INTEGRITY_ERRORS})"))?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+impl Database {
+    /// Checks integrity, prunes stale rows, then compacts the file with `VACUUM` and
+    /// refreshes the query planner statistics with `ANALYZE`. A database that fails the
+    /// integrity check is reported but left untouched.
+    ///
+    /// Only cancelled sessions and rows whose session no longer exists are pruned, so
+    /// running and reviewed sessions keep all their data. Archived specs are trimmed to
+    /// the newest `archive_max_entries` per repository.
+    ///
+    /// Pruning runs in an immediate transaction, so it waits for concurrent writers
+    /// (up to the busy timeout) instead of interleaving with them. Callers should still
+    /// make sure no session operation is in flight.
+    pub fn run_database_maintenance(&self) -> Result<MaintenanceReport> {
+        let mut conn = self.get_conn()?;
+        let size_before_bytes = database_size(&conn)?;
+
+        let integrity_errors = integrity_errors(&conn)?;
+        if !integrity_errors.is_empty() {
+            log::error!(
+                "Database integrity check failed, skipping maintenance: {}",
+                integrity_errors.join("; ")
+            );
+            return Ok(MaintenanceReport {
+                size_before_bytes,
+                size_after_bytes: size_before_bytes,
+                integrity_errors,
+                ..Default::default()
+            });
+        }
+
+        let cutoff = (Utc::now() - Duration::days(CANCELLED_SESSION_RETENTION_DAYS)).timestamp();
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .context("failed to acquire the database write lock")?;
+        let cancelled_sessions_pruned = tx.execute(
+            "DELETE FROM sessions WHERE status = 'cancelled' AND updated_at < ?1",
+            params![cutoff],
+        )?;
+        let orphaned_git_stats_pruned = tx.execute(
+            "DELETE FROM git_stats WHERE session_id NOT IN (SELECT id FROM sessions)",
+            [],
+        )?;
+        let orphaned_history_pruned = tx.execute(
+            "DELETE FROM session_state_history WHERE session_id NOT IN (SELECT id FROM sessions)",
+            [],
+        )?;
+        let archive_max_entries: i64 = tx
+            .query_row(
+                "SELECT archive_max_entries FROM app_config WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(DEFAULT_ARCHIVE_MAX_ENTRIES);
+        let archived_specs_pruned = tx.execute(
+            "DELETE FROM archived_specs WHERE rowid IN (
+                SELECT rowid FROM (
+                    SELECT rowid, ROW_NUMBER() OVER (
+                        PARTITION BY repository_path ORDER BY archived_at DESC, rowid DESC
+                    ) AS position
+                    FROM archived_specs
+                ) WHERE position > ?1
+            )",
+            params![archive_max_entries.max(0)],
+        )?;
+        tx.commit()?;
+
+        conn.execute_batch("VACUUM; ANALYZE;")
+            .context("failed to compact the database")?;
+        let size_after_bytes = database_size(&conn)?;
+
+        let report = MaintenanceReport {
+            size_before_bytes,
+            size_after_bytes,
+            bytes_reclaimed: size_before_bytes.saturating_sub(size_after_bytes),
+            integrity_ok: true,
+            integrity_errors: Vec::new(),
+            cancelled_sessions_pruned,
+            orphaned_git_stats_pruned,
+            orphaned_history_pruned,
+            archived_specs_pruned,
+        };
+        log::info!(
+            "Database maintenance pruned {} row(s) and reclaimed {} byte(s)",
+            report.rows_pruned(),
+            report.bytes_reclaimed
+        );
+        Ok(report)
+    }
+
+    pub fn get_database_info(&self) -> Result<DatabaseInfo> {
+        let conn = self.get_conn()?;
+        let tables = {
+            let mut stmt = conn.prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+                 ORDER BY name",
+            )?;
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let mut table_row_counts = BTreeMap::new();
+        for table in tables {
+            let count: i64 = conn.query_row(
+                &format!("SELECT COUNT(*) FROM \"{}\"", table.replace('"', "\"\"")),
+                [],
+                |row| row.get(0),
+            )?;
+            table_row_counts.insert(table, count);
+        }
+
+        Ok(DatabaseInfo {
+            size_bytes: database_size(&conn)?,
+            schema_version: db_schema::schema_version(&conn)?,
+            table_row_counts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn insert_session(conn: &Connection, id: &str, status: &str, updated_at: i64) {
+        conn.execute(
+            "INSERT INTO sessions (id, name, repository_path, repository_name, branch,
+                parent_branch, worktree_path, status, created_at, updated_at)
+             VALUES (?1, ?1, '/repo', 'repo', ?1, 'main', '/repo/wt', ?2, ?3, ?3)",
+            params![id, status, updated_at],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO git_stats (session_id, calculated_at) VALUES (?1, ?2)",
+            params![id, updated_at],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO session_state_history (session_id, to_state, timestamp, reason)
+             VALUES (?1, 'running', ?2, 'test')",
+            params![id, updated_at],
+        )
+        .unwrap();
+    }
+
+    fn ids(conn: &Connection, sql: &str) -> Vec<String> {
+        let mut stmt = conn.prepare(sql).unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn maintenance_prunes_stale_and_orphaned_rows() {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::new(Some(tmp.path().join("sessions.db"))).unwrap();
+        let now = Utc::now().timestamp();
+        let long_ago = now - (CANCELLED_SESSION_RETENTION_DAYS + 1) * 24 * 60 * 60;
+        {
+            let conn = db.get_conn().unwrap();
+            insert_session(&conn, "active", "active", long_ago);
+            insert_session(&conn, "recently-cancelled", "cancelled", now);
+            insert_session(&conn, "old-cancelled", "cancelled", long_ago);
+            insert_session(&conn, "deleted", "active", now);
+            conn.execute("DELETE FROM sessions WHERE id = 'deleted'", [])
+                .unwrap();
+            // Freed pages only go back to the filesystem on VACUUM
+            conn.execute_batch(
+                "CREATE TABLE filler (data BLOB);
+                 INSERT INTO filler VALUES (zeroblob(1000000));
+                 DROP TABLE filler;",
+            )
+            .unwrap();
+        }
+
+        let report = db.run_database_maintenance().unwrap();
+
+        assert_eq!(report.cancelled_sessions_pruned, 1);
+        assert_eq!(report.orphaned_git_stats_pruned, 2);
+        assert_eq!(report.orphaned_history_pruned, 2);
+        assert_eq!(report.rows_pruned(), 5);
+        assert!(report.bytes_reclaimed > 0, "{report:?}");
+
+        let conn = db.get_conn().unwrap();
+        let expected = vec!["active".to_string(), "recently-cancelled".to_string()];
+        assert_eq!(ids(&conn, "SELECT id FROM sessions ORDER BY id"), expected);
+        assert_eq!(
+            ids(
+                &conn,
+                "SELECT session_id FROM git_stats ORDER BY session_id"
+            ),
+            expected
+        );
+        assert_eq!(
+            ids(
+                &conn,
+                "SELECT session_id FROM session_state_history ORDER BY session_id"
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn maintenance_trims_archived_specs_per_repository() {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::new(Some(tmp.path().join("sessions.db"))).unwrap();
+        {
+            let conn = db.get_conn().unwrap();
+            conn.execute(
+                "UPDATE app_config SET archive_max_entries = 2 WHERE id = 1",
+                [],
+            )
+            .unwrap();
+            for (id, repo, archived_at) in [
+                ("a1", "/a", 1),
+                ("a2", "/a", 2),
+                ("a3", "/a", 3),
+                ("a4", "/a", 4),
+                ("b1", "/b", 1),
+            ] {
+                conn.execute(
+                    "INSERT INTO archived_specs (id, session_name, repository_path,
+                        repository_name, content, archived_at)
+                     VALUES (?1, ?1, ?2, 'repo', 'spec', ?3)",
+                    params![id, repo, archived_at],
+                )
+                .unwrap();
+            }
+        }
+
+        let report = db.run_database_maintenance().unwrap();
+
+        assert!(report.integrity_ok);
+        assert!(report.integrity_errors.is_empty());
+        assert_eq!(report.archived_specs_pruned, 2);
+        assert_eq!(report.rows_pruned(), 2);
+        let conn = db.get_conn().unwrap();
+        assert_eq!(
+            ids(&conn, "SELECT id FROM archived_specs ORDER BY id"),
+            vec!["a3".to_string(), "a4".to_string(), "b1".to_string()]
+        );
+    }
+
+    #[test]
+    fn database_info_reports_row_counts_and_schema_version() {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::new(Some(tmp.path().join("sessions.db"))).unwrap();
+        {
+            let conn = db.get_conn().unwrap();
+            insert_session(&conn, "one", "active", 1);
+            insert_session(&conn, "two", "cancelled", 2);
+        }
+
+        let info = db.get_database_info().unwrap();
+
+        assert!(info.size_bytes > 0);
+        assert_eq!(
+            info.schema_version,
+            db_schema::schema_version(&db.get_conn().unwrap()).unwrap()
+        );
+        assert!(info.schema_version >= 1);
+        assert_eq!(info.table_row_counts.get("sessions"), Some(&2));
+        assert_eq!(info.table_row_counts.get("git_stats"), Some(&2));
+        assert_eq!(info.table_row_counts.get("archived_specs"), Some(&0));
+        assert!(
+            info.table_row_counts
+                .keys()
+                .all(|table| !table.starts_with("sqlite_"))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod backup;
 pub mod connection;
 pub mod db_app_config;
 pub mod db_archived_specs;
@@ -5,14 +6,19 @@ pub mod db_epics;
 pub mod db_project_config;
 pub mod db_schema;
 pub mod db_specs;
+pub mod db_workspaces;
+pub mod maintenance;
 pub mod timestamps;
 
 pub use connection::Database;
 pub use db_app_config::AppConfigMethods;
 pub use db_epics::EpicMethods;
 pub use db_project_config::{
-    DEFAULT_BRANCH_PREFIX, HeaderActionConfig, ProjectConfigMethods, ProjectGithubConfig,
-    ProjectMergePreferences, ProjectSessionsSettings, RunScript,
+    AgentRestartMode, AgentRestartPolicy, BranchCollisionStrategy, DEFAULT_BRANCH_PREFIX,
+    HeaderActionConfig, ProjectConfigMethods, ProjectGithubConfig, ProjectMergePreferences,
+    ProjectSessionsSettings, RunScript,
 };
 pub use db_schema::initialize_schema;
 pub use db_specs::SpecMethods;
+pub use db_workspaces::{WorkspaceDefinition, WorkspaceMethods};
+pub use maintenance::{DatabaseInfo, MaintenanceReport};
@@ -9,10 +9,15 @@ pub enum SchaltEvent {
     ArchiveUpdated,
     SessionCancelling,
     CancelError,
+    SessionCreationProgress,
     TerminalCreated,
 
     SessionActivity,
+    SessionIdle,
+    SessionResumed,
+    SessionAgentStatus,
     SessionGitStats,
+    SessionRefreshed,
     TerminalAttention,
     TerminalClosed,
     TerminalForceScroll,
@@ -30,14 +35,19 @@ pub enum SchaltEvent {
     GitOperationFailed,
     ProjectFilesUpdated,
     GitHubStatusChanged,
+    GitHubPrStatusChanged,
     DevBackendError,
     SetupScriptRequested,
+    SetupScriptFailed,
+    SetupScriptOutput,
+    SetupScriptProgress,
     OrchestratorLaunchFailed,
     DiffBaseBranchChanged,
     ProjectValidationError,
     OpenPrModal,
     OpenMergeModal,
     SelectAllRequested,
+    MergeCheckOutput,
 }
 
 impl SchaltEvent {
@@ -49,10 +59,15 @@ impl SchaltEvent {
             SchaltEvent::ArchiveUpdated => "schaltwerk:archive-updated",
             SchaltEvent::SessionCancelling => "schaltwerk:session-cancelling",
             SchaltEvent::CancelError => "schaltwerk:cancel-error",
+            SchaltEvent::SessionCreationProgress => "schaltwerk:session-creation-progress",
             SchaltEvent::TerminalCreated => "schaltwerk:terminal-created",
 
             SchaltEvent::SessionActivity => "schaltwerk:session-activity",
+            SchaltEvent::SessionIdle => "schaltwerk:session-idle",
+            SchaltEvent::SessionResumed => "schaltwerk:session-resumed",
+            SchaltEvent::SessionAgentStatus => "schaltwerk:session-agent-status",
             SchaltEvent::SessionGitStats => "schaltwerk:session-git-stats",
+            SchaltEvent::SessionRefreshed => "schaltwerk:session-refreshed",
             SchaltEvent::TerminalAttention => "schaltwerk:terminal-attention",
             SchaltEvent::TerminalClosed => "schaltwerk:terminal-closed",
             SchaltEvent::TerminalForceScroll => "schaltwerk:terminal-force-scroll",
@@ -72,14 +87,19 @@ impl SchaltEvent {
             SchaltEvent::GitOperationFailed => "schaltwerk:git-operation-failed",
             SchaltEvent::ProjectFilesUpdated => "schaltwerk:project-files-updated",
             SchaltEvent::GitHubStatusChanged => "schaltwerk:github-status-changed",
+            SchaltEvent::GitHubPrStatusChanged => "schaltwerk:github-pr-status-changed",
             SchaltEvent::DevBackendError => "schaltwerk:dev-backend-error",
             SchaltEvent::SetupScriptRequested => "schaltwerk:setup-script-request",
+            SchaltEvent::SetupScriptFailed => "schaltwerk:setup-script-failed",
+            SchaltEvent::SetupScriptOutput => "schaltwerk:setup-script-output",
+            SchaltEvent::SetupScriptProgress => "schaltwerk:setup-script-progress",
             SchaltEvent::OrchestratorLaunchFailed => "schaltwerk:orchestrator-launch-failed",
             SchaltEvent::DiffBaseBranchChanged => "schaltwerk:diff-base-branch-changed",
             SchaltEvent::ProjectValidationError => "schaltwerk:project-validation-error",
             SchaltEvent::OpenPrModal => "schaltwerk:open-pr-modal",
             SchaltEvent::OpenMergeModal => "schaltwerk:open-merge-modal",
             SchaltEvent::SelectAllRequested => "schaltwerk:select-all-requested",
+            SchaltEvent::MergeCheckOutput => "schaltwerk:merge-check-output",
         }
     }
 }
@@ -134,6 +154,18 @@ mod tests {
             SchaltEvent::SetupScriptRequested.as_str(),
             "schaltwerk:setup-script-request"
         );
+        assert_eq!(
+            SchaltEvent::SetupScriptFailed.as_str(),
+            "schaltwerk:setup-script-failed"
+        );
+        assert_eq!(
+            SchaltEvent::SetupScriptOutput.as_str(),
+            "schaltwerk:setup-script-output"
+        );
+        assert_eq!(
+            SchaltEvent::SetupScriptProgress.as_str(),
+            "schaltwerk:setup-script-progress"
+        );
         assert_eq!(
             SchaltEvent::OrchestratorLaunchFailed.as_str(),
             "schaltwerk:orchestrator-launch-failed"
@@ -146,5 +178,9 @@ mod tests {
             SchaltEvent::SelectAllRequested.as_str(),
             "schaltwerk:select-all-requested"
         );
+        assert_eq!(
+            SchaltEvent::SessionCreationProgress.as_str(),
+            "schaltwerk:session-creation-progress"
+        );
     }
 }
@@ -1,16 +1,39 @@
 use crate::domains::power::global_service::get_global_keep_awake_service;
+use crate::domains::sessions::activity::{
+    AgentActivityMonitor, AgentActivitySink, agent_idle_window_from_env,
+};
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+use std::time::Instant;
 
-/// Forward terminal attention events to the global keep-awake service without creating
-/// domain-to-domain dependencies.
-pub fn handle_terminal_attention(session_id: String, needs_attention: bool) {
-    if let Some(service) = get_global_keep_awake_service() {
-        tauri::async_runtime::spawn(async move {
-            if let Err(err) = service
-                .handle_session_activity(session_id, needs_attention)
-                .await
-            {
-                log::debug!("Keep-awake service failed to handle session activity: {err}");
-            }
-        });
+/// Forward changes of the active agent set to the global keep-awake service without
+/// creating domain-to-domain dependencies.
+struct KeepAwakeAgentSink;
+
+impl AgentActivitySink for KeepAwakeAgentSink {
+    fn active_agents_changed(&self, active: HashSet<String>) {
+        if let Some(service) = get_global_keep_awake_service() {
+            tauri::async_runtime::spawn(async move {
+                if let Err(err) = service.sync_active_agents(active).await {
+                    log::debug!("Keep-awake service failed to sync active agents: {err}");
+                }
+            });
+        }
     }
 }
+
+static AGENT_ACTIVITY: LazyLock<Arc<AgentActivityMonitor>> = LazyLock::new(|| {
+    Arc::new(AgentActivityMonitor::new(
+        agent_idle_window_from_env(),
+        Box::new(KeepAwakeAgentSink),
+    ))
+});
+
+pub fn agent_activity_monitor() -> Arc<AgentActivityMonitor> {
+    AGENT_ACTIVITY.clone()
+}
+
+/// Records output of the agent terminal of `session_id`.
+pub fn record_agent_output(session_id: &str) {
+    AGENT_ACTIVITY.record_output(session_id, Instant::now());
+}
@@ -1,23 +1,107 @@
 use chrono::Local;
 use env_logger::Builder;
-use log::LevelFilter;
+use log::{LevelFilter, Log, Metadata, Record};
 use std::env;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, SystemTime};
 
+mod recent;
+
+pub use recent::{LogEntry, RECENT_LOG_CAPACITY, export_recent_logs, recent_logs};
+
 static LOG_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
 static LOG_FILE_WRITER: Mutex<Option<BufWriter<File>>> = Mutex::new(None);
 static LOGGER_INITIALIZED: Mutex<bool> = Mutex::new(false);
 static DEV_ERROR_DISPATCH: Mutex<Option<Arc<DevErrorCallback>>> = Mutex::new(None);
+static LOGGER: OnceLock<&'static RuntimeLevelLogger> = OnceLock::new();
 
 const DEFAULT_RETENTION_HOURS: u64 = 72;
 const SECONDS_PER_HOUR: u64 = 3_600;
 
 type DevErrorCallback = dyn Fn(&str, Option<&str>) + Send + Sync;
 
+const NO_LEVEL_OVERRIDE: usize = usize::MAX;
+
+/// Filters records with the configured per-module levels, or with a single level set at
+/// runtime through [`set_log_level`], keeps a copy of each record in the recent-logs
+/// buffer and hands it to the console and file output.
+struct RuntimeLevelLogger {
+    default_filter: env_logger::Logger,
+    output: env_logger::Logger,
+    level_override: AtomicUsize,
+}
+
+impl RuntimeLevelLogger {
+    fn new(default_filter: env_logger::Logger, output: env_logger::Logger) -> Self {
+        Self {
+            default_filter,
+            output,
+            level_override: AtomicUsize::new(NO_LEVEL_OVERRIDE),
+        }
+    }
+
+    fn level_override(&self) -> Option<LevelFilter> {
+        match self.level_override.load(Ordering::Relaxed) {
+            NO_LEVEL_OVERRIDE => None,
+            0 => Some(LevelFilter::Off),
+            1 => Some(LevelFilter::Error),
+            2 => Some(LevelFilter::Warn),
+            3 => Some(LevelFilter::Info),
+            4 => Some(LevelFilter::Debug),
+            _ => Some(LevelFilter::Trace),
+        }
+    }
+
+    fn set_level_override(&self, level: Option<LevelFilter>) {
+        let encoded = level.map_or(NO_LEVEL_OVERRIDE, |level| level as usize);
+        self.level_override.store(encoded, Ordering::Relaxed);
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.level_override()
+            .unwrap_or_else(|| self.default_filter.filter())
+    }
+}
+
+impl Log for RuntimeLevelLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        match self.level_override() {
+            Some(level) => metadata.level() <= level,
+            None => self.default_filter.enabled(metadata),
+        }
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let accepted = match self.level_override() {
+            Some(level) => record.level() <= level,
+            None => self.default_filter.matches(record),
+        };
+        if !accepted {
+            return;
+        }
+        recent::RECENT_LOGS.push(LogEntry::from_record(record));
+        self.output.log(record);
+    }
+
+    fn flush(&self) {
+        self.output.flush();
+    }
+}
+
+/// Replaces the configured per-module levels with a single level for every module
+/// until the next call; `None` restores the configured levels. No-op before
+/// [`init_logging`].
+pub fn set_log_level(level: Option<LevelFilter>) {
+    if let Some(logger) = LOGGER.get() {
+        logger.set_level_override(level);
+        log::set_max_level(logger.max_level());
+    }
+}
+
 #[derive(Debug)]
 struct LoggingConfig {
     file_logging_enabled: bool,
@@ -130,22 +214,25 @@ pub fn init_logging() {
     if cfg!(test) {
         builder.is_test(true);
     }
+    // Records are filtered by RuntimeLevelLogger before they reach the output
+    builder.filter_level(LevelFilter::Trace);
 
     // Set log level from env or default to DEBUG for our crates, INFO for others
+    let mut filters = Builder::new();
     if let Ok(rust_log) = env::var("RUST_LOG") {
-        builder.parse_filters(&rust_log);
+        filters.parse_filters(&rust_log);
     } else if config.file_logging_enabled {
         // Our crate (schaltwerk) - set to Debug to see all our logs
-        builder.filter_module("schaltwerk", LevelFilter::Debug);
+        filters.filter_module("schaltwerk", LevelFilter::Debug);
 
         // Third-party crates we care about
-        builder.filter_module("portable_pty", LevelFilter::Info);
-        builder.filter_module("tauri", LevelFilter::Info);
+        filters.filter_module("portable_pty", LevelFilter::Info);
+        filters.filter_module("tauri", LevelFilter::Info);
 
         // Everything else defaults to Warn
-        builder.filter_level(LevelFilter::Warn);
+        filters.filter_level(LevelFilter::Warn);
     } else {
-        builder.filter_level(LevelFilter::Warn);
+        filters.filter_level(LevelFilter::Warn);
     }
 
     // Custom format with timestamps and module info
@@ -206,9 +293,15 @@ pub fn init_logging() {
     // Write to stderr (which Tauri will capture)
     builder.target(env_logger::Target::Stderr);
 
-    // Initialize the logger
     // Initialize the logger; subsequent calls are prevented by guard above
-    builder.init();
+    let logger: &'static RuntimeLevelLogger = Box::leak(Box::new(RuntimeLevelLogger::new(
+        filters.build(),
+        builder.build(),
+    )));
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(logger.max_level());
+        let _ = LOGGER.set(logger);
+    }
 
     // Force stderr to be line-buffered for immediate output
     // This ensures logs appear immediately in development
@@ -454,4 +547,50 @@ mod tests {
             "expected captured messages to include the emitted error log"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_logged_records_reach_recent_logs() {
+        init_logging();
+
+        log::warn!("recent logs smoke test");
+
+        let entries = recent_logs(LevelFilter::Warn, Some(50), Some(module_path!()));
+        assert!(
+            entries
+                .iter()
+                .any(|entry| entry.message == "recent logs smoke test"),
+            "expected the warning in recent logs, got {entries:?}"
+        );
+    }
+
+    #[test]
+    fn test_runtime_level_replaces_configured_filters() {
+        let logger = RuntimeLevelLogger::new(
+            Builder::new().filter_level(LevelFilter::Warn).build(),
+            Builder::new().is_test(true).build(),
+        );
+        let metadata = |level| {
+            Metadata::builder()
+                .level(level)
+                .target("schaltwerk::terminal")
+                .build()
+        };
+
+        assert!(logger.enabled(&metadata(log::Level::Warn)));
+        assert!(!logger.enabled(&metadata(log::Level::Debug)));
+        assert_eq!(logger.max_level(), LevelFilter::Warn);
+
+        logger.set_level_override(Some(LevelFilter::Debug));
+        assert!(logger.enabled(&metadata(log::Level::Debug)));
+        assert!(!logger.enabled(&metadata(log::Level::Trace)));
+        assert_eq!(logger.max_level(), LevelFilter::Debug);
+
+        logger.set_level_override(Some(LevelFilter::Error));
+        assert!(!logger.enabled(&metadata(log::Level::Warn)));
+
+        logger.set_level_override(None);
+        assert!(logger.enabled(&metadata(log::Level::Warn)));
+        assert!(!logger.enabled(&metadata(log::Level::Info)));
+    }
 }
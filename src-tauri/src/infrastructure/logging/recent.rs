@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use log::{Level, LevelFilter, Record};
+use serde::{Serialize, Serializer};
+use std::collections::VecDeque;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Number of log records kept in memory for bug reports.
+pub const RECENT_LOG_CAPACITY: usize = 5_000;
+
+pub(super) static RECENT_LOGS: LogRingBuffer = LogRingBuffer::new(RECENT_LOG_CAPACITY);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+fn serialize_level<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(level.as_str())
+}
+
+impl LogEntry {
+    pub fn from_record(record: &Record<'_>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        }
+    }
+}
+
+/// Fixed-size buffer of the most recent log records. The lock is only held to push or
+/// copy entries; records are formatted before it is taken, so logging from hot paths
+/// such as terminal output does not queue up behind readers.
+pub struct LogRingBuffer {
+    capacity: usize,
+    entries: Mutex<VecDeque<LogEntry>>,
+}
+
+impl LogRingBuffer {
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// The newest `limit` entries at or above `level` whose target starts with
+    /// `module_prefix`, oldest first.
+    pub fn query(
+        &self,
+        level: LevelFilter,
+        limit: Option<usize>,
+        module_prefix: Option<&str>,
+    ) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut matching: Vec<LogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.level <= level)
+            .filter(|entry| module_prefix.is_none_or(|prefix| entry.target.starts_with(prefix)))
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+}
+
+/// Recent log records of this process, see [`LogRingBuffer::query`].
+pub fn recent_logs(
+    level: LevelFilter,
+    limit: Option<usize>,
+    module_prefix: Option<&str>,
+) -> Vec<LogEntry> {
+    RECENT_LOGS.query(level, limit, module_prefix)
+}
+
+/// Writes all buffered records to `path` in the log file format and returns how many
+/// were written.
+pub fn export_recent_logs(path: &Path) -> std::io::Result<usize> {
+    let entries = recent_logs(LevelFilter::Trace, None, None);
+    let mut writer = BufWriter::new(std::fs::File::create(path)?);
+    for entry in &entries {
+        writeln!(
+            writer,
+            "[{} {:<5} {}] {}",
+            entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S%.3f"),
+            entry.level,
+            entry.target,
+            entry.message
+        )?;
+    }
+    writer.flush()?;
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, target: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn messages(entries: &[LogEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.message.as_str()).collect()
+    }
+
+    #[test]
+    fn query_filters_by_level_module_and_limit() {
+        let buffer = LogRingBuffer::new(10);
+        buffer.push(entry(Level::Debug, "schaltwerk::terminal", "chunk"));
+        buffer.push(entry(Level::Warn, "schaltwerk::git", "slow fetch"));
+        buffer.push(entry(Level::Error, "tauri::ipc", "ipc failed"));
+        buffer.push(entry(Level::Info, "schaltwerk::git", "fetched"));
+
+        assert_eq!(
+            messages(&buffer.query(LevelFilter::Warn, None, None)),
+            vec!["slow fetch", "ipc failed"]
+        );
+        assert_eq!(
+            messages(&buffer.query(LevelFilter::Trace, None, Some("schaltwerk::git"))),
+            vec!["slow fetch", "fetched"]
+        );
+        assert_eq!(
+            messages(&buffer.query(LevelFilter::Trace, Some(2), None)),
+            vec!["ipc failed", "fetched"]
+        );
+        assert!(buffer.query(LevelFilter::Off, None, None).is_empty());
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped_once_full() {
+        let buffer = LogRingBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(entry(Level::Info, "schaltwerk", &format!("line {i}")));
+        }
+
+        assert_eq!(
+            messages(&buffer.query(LevelFilter::Trace, None, None)),
+            vec!["line 2", "line 3", "line 4"]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod agent_status_bridge;
 pub mod attention_bridge;
 pub mod config;
 pub mod database;
@@ -23,11 +23,12 @@ mod macos_prefs;
 mod mcp_api;
 mod permissions;
 mod projects;
+mod read_only;
 mod startup;
 mod updater;
 
 use crate::commands::sessions_refresh::{SessionsRefreshReason, request_sessions_refresh};
-use crate::errors::SchaltError;
+use crate::errors::{CommandError, ErrorCode, SchaltError};
 use clap::Parser;
 use once_cell::sync::Lazy;
 use schaltwerk::domains::power::global_service::{
@@ -37,11 +38,9 @@ use schaltwerk::domains::{attention::AttentionStateRegistry, git::repository};
 use schaltwerk::infrastructure::config::SettingsManager;
 use schaltwerk::project_manager::ProjectManager;
 use schaltwerk::schaltwerk_core::db_app_config::AppConfigMethods;
+use schaltwerk::schaltwerk_core::db_project_config::ProjectConfigMethods;
 use schaltwerk::services::ServiceHandles;
-use schaltwerk::shared::terminal_id::{
-    legacy_terminal_id_for_session_top, previous_hashed_terminal_id_for_session_top,
-    previous_tilde_hashed_terminal_id_for_session_top, terminal_id_for_session_top,
-};
+use schaltwerk::shared::terminal_id::terminal_id_for_session_top;
 use schaltwerk::domains::terminal::submission::submission_options_for_agent;
 use schaltwerk::utils::env_adapter::EnvAdapter;
 use std::path::PathBuf;
@@ -365,7 +364,7 @@ pub async fn get_schaltwerk_core()
 }
 
 pub async fn get_core_read()
--> Result<OwnedRwLockReadGuard<schaltwerk::schaltwerk_core::SchaltwerkCore>, String> {
+-> Result<OwnedRwLockReadGuard<schaltwerk::schaltwerk_core::SchaltwerkCore>, CommandError> {
     let call_id = uuid::Uuid::new_v4();
     let start = std::time::Instant::now();
     log::debug!("get_core_read start call_id={call_id}");
@@ -409,13 +408,16 @@ pub async fn get_core_read()
                     "get_core_read timed out (5s) call_id={call_id}; failed to inspect last writer"
                 );
             }
-            Err("Timed out waiting for core read lock".to_string())
+            Err(CommandError::new(
+                ErrorCode::Timeout,
+                "Timed out waiting for core read lock",
+            ))
         }
     }
 }
 
 pub async fn get_core_write()
--> Result<OwnedRwLockWriteGuard<schaltwerk::schaltwerk_core::SchaltwerkCore>, String> {
+-> Result<OwnedRwLockWriteGuard<schaltwerk::schaltwerk_core::SchaltwerkCore>, CommandError> {
     let call_id = uuid::Uuid::new_v4();
     let start = std::time::Instant::now();
     log::debug!("get_core_write start call_id={call_id}");
@@ -447,7 +449,10 @@ pub async fn get_core_write()
         }
         Err(_) => {
             log::error!("get_core_write timed out (5s) call_id={call_id}");
-            Err("Timed out waiting for core write lock".to_string())
+            Err(CommandError::new(
+                ErrorCode::Timeout,
+                "Timed out waiting for core write lock",
+            ))
         }
     }
 }
@@ -463,13 +468,17 @@ pub async fn get_file_watcher_manager()
 #[tauri::command]
 async fn start_file_watcher(session_name: String) -> Result<(), SchaltError> {
     if session_name == "orchestrator" {
-        let (repo_path, configured_branch) = {
+        let (repo_path, configured_branch, ignore_patterns) = {
             let core = get_core_read()
                 .await
                 .map_err(|e| SchaltError::DatabaseError {
                     message: e.to_string(),
                 })?;
             let repo_path = core.repo_path.clone();
+            let ignore_patterns = core
+                .db
+                .get_project_watcher_ignore_patterns(&repo_path)
+                .unwrap_or_default();
             let configured_branch = core
                 .db
                 .get_default_base_branch()
@@ -477,7 +486,7 @@ async fn start_file_watcher(session_name: String) -> Result<(), SchaltError> {
                     message: e.to_string(),
                 })?
                 .filter(|value| !value.trim().is_empty());
-            (repo_path, configured_branch)
+            (repo_path, configured_branch, ignore_patterns)
         };
 
         let base_branch = configured_branch.unwrap_or_else(|| {
@@ -494,7 +503,7 @@ async fn start_file_watcher(session_name: String) -> Result<(), SchaltError> {
                 })?;
 
         return watcher_manager
-            .start_watching_orchestrator(repo_path.clone(), base_branch)
+            .start_watching_orchestrator(repo_path.clone(), base_branch, &ignore_patterns)
             .await
             .map_err(|e| {
                 SchaltError::io(
@@ -505,13 +514,17 @@ async fn start_file_watcher(session_name: String) -> Result<(), SchaltError> {
             });
     }
 
-    let session_manager = {
+    let (session_manager, ignore_patterns) = {
         let core = get_core_read()
             .await
             .map_err(|e| SchaltError::DatabaseError {
                 message: e.to_string(),
             })?;
-        core.session_manager()
+        let ignore_patterns = core
+            .db
+            .get_project_watcher_ignore_patterns(&core.repo_path)
+            .unwrap_or_default();
+        (core.session_manager(), ignore_patterns)
     };
 
     let sessions =
@@ -542,6 +555,7 @@ async fn start_file_watcher(session_name: String) -> Result<(), SchaltError> {
             session_name,
             worktree_path.clone(),
             session.info.base_branch,
+            &ignore_patterns,
         )
         .await
         .map_err(|e| SchaltError::io("start_watching_session", worktree_path.to_string_lossy(), e))
@@ -632,9 +646,39 @@ fn calculate_project_port(project_path: &str) -> u16 {
 }
 
 async fn start_webhook_server(app: tauri::AppHandle) -> bool {
-    async fn handle_webhook(
+    async fn serve_webhook(
+        app: tauri::AppHandle,
+        req: Request<IncomingBody>,
+    ) -> Result<Response<mcp_api::terminal_api::ResponseBody>, hyper::Error> {
+        // Terminal streams stay open until the client leaves, so they bypass request
+        // buffering and logging.
+        if let Some(session_name) =
+            mcp_api::terminal_stream_session_name(req.method(), req.uri().path())
+        {
+            return Ok(mcp_api::stream_session_terminal(&session_name).await);
+        }
+        handle_logged_webhook(app, req)
+            .await
+            .map(|response| response.map(mcp_api::terminal_api::full_body))
+    }
+
+    async fn handle_logged_webhook(
         app: tauri::AppHandle,
         req: Request<IncomingBody>,
+    ) -> Result<Response<String>, hyper::Error> {
+        let (req, body) = mcp_api::request_log::buffer_request(req).await?;
+        let recorder =
+            mcp_api::request_log::RequestRecorder::start(req.method(), req.uri().path(), &body);
+        let response = handle_webhook(app, req).await;
+        if let Some(recorder) = recorder {
+            recorder.finish(&response);
+        }
+        response
+    }
+
+    async fn handle_webhook(
+        app: tauri::AppHandle,
+        req: Request<mcp_api::request_log::RequestBody>,
     ) -> Result<Response<String>, hyper::Error> {
         let method = req.method();
         let path = req.uri().path();
@@ -807,17 +851,8 @@ async fn start_webhook_server(app: tauri::AppHandle) -> bool {
                         }
 
                         let primary_terminal_id = terminal_id_for_session_top(session_name);
-                        let mut candidate_ids = Vec::new();
-                        for candidate in [
-                            primary_terminal_id.clone(),
-                            previous_tilde_hashed_terminal_id_for_session_top(session_name),
-                            previous_hashed_terminal_id_for_session_top(session_name),
-                            legacy_terminal_id_for_session_top(session_name),
-                        ] {
-                            if !candidate_ids.contains(&candidate) {
-                                candidate_ids.push(candidate);
-                            }
-                        }
+                        let candidate_ids =
+                            mcp_api::terminal_api::session_top_terminal_candidates(session_name);
 
                         let mut delivered_terminal_id = primary_terminal_id.clone();
                         let mut delivered = false;
@@ -945,6 +980,13 @@ async fn start_webhook_server(app: tauri::AppHandle) -> bool {
         }
     }
 
+    match get_settings_manager(&app).await {
+        Ok(settings_manager) => mcp_api::request_log::set_enabled(
+            settings_manager.lock().await.get_webhook_request_log_enabled(),
+        ),
+        Err(e) => log::warn!("Failed to read webhook request log setting: {e}"),
+    }
+
     // Calculate project-specific port
     let project_manager = get_project_manager().await;
     let base_port = if let Some(active_project) = project_manager.current_project_path().await {
@@ -987,7 +1029,7 @@ async fn start_webhook_server(app: tauri::AppHandle) -> bool {
             if let Err(err) = http1::Builder::new()
                 .serve_connection(
                     io,
-                    service_fn(move |req| handle_webhook(app_clone.clone(), req)),
+                    service_fn(move |req| serve_webhook(app_clone.clone(), req)),
                 )
                 .await
             {
@@ -1126,6 +1168,10 @@ fn main() {
     log::info!("Startup directory: {}", log_dir.display());
 
     let initial_directory = startup::validate_cli_directory(start_dir.as_deref());
+    let read_only = cli.read_only;
+    if read_only {
+        log::info!("Opening in read-only mode");
+    }
 
     // Create cleanup guard that will run on exit
     let _cleanup_guard = cleanup::TerminalCleanupGuard;
@@ -1153,21 +1199,26 @@ fn main() {
             }
         });
 
-    let run_result = builder.invoke_handler(tauri::generate_handler![
+    let run_result = builder.invoke_handler(read_only::guard_invoke_handler(read_only, tauri::generate_handler![
             // Development info
             get_development_info,
             github_get_status,
             github_authenticate,
             github_connect_project,
             github_create_reviewed_pr,
+            github_set_auto_pr_on_review,
             github_search_issues,
             github_get_issue_details,
+            github_issue_to_spec,
+            github_create_session_from_issue,
             github_search_prs,
             github_get_pr_details,
             github_create_session_pr,
             github_preview_pr,
             github_get_pr_review_comments,
             github_get_pr_feedback,
+            github_mark_pr_ready,
+            github_get_lookup_cache_stats,
             // Permission commands
             permissions::check_folder_access,
             permissions::trigger_folder_permission_request,
@@ -1179,6 +1230,7 @@ fn main() {
             create_terminal,
             create_terminal_with_size,
             create_run_terminal,
+            run_project_script,
             write_terminal,
             paste_and_submit_terminal,
             resize_terminal,
@@ -1186,6 +1238,7 @@ fn main() {
             terminal_exists,
             terminals_exist_bulk,
             get_terminal_buffer,
+            get_terminal_buffer_text,
             get_terminal_activity_status,
             get_all_terminal_activity,
             register_session_terminals,
@@ -1204,6 +1257,10 @@ fn main() {
             path_exists,
             get_environment_variable,
             get_app_version,
+            get_orphaned_processes,
+            get_recent_logs,
+            set_log_level,
+            export_logs_to_file,
             clipboard_write_text,
             check_for_updates_now,
             restart_app,
@@ -1220,12 +1277,19 @@ fn main() {
             // Para core commands
             schaltwerk_core_create_session,
             schaltwerk_core_rename_version_group,
+            schaltwerk_core_set_version_group_label,
             schaltwerk_core_list_sessions,
+            schaltwerk_core_list_sessions_awaiting_approval,
+            schaltwerk_core_set_focused_terminal,
             schaltwerk_core_list_epics,
             schaltwerk_core_create_epic,
             schaltwerk_core_update_epic,
             schaltwerk_core_delete_epic,
             schaltwerk_core_set_item_epic,
+            schaltwerk_core_set_session_group,
+            schaltwerk_core_list_session_groups,
+            schaltwerk_core_set_terminal_recording,
+            schaltwerk_core_get_terminal_recordings,
             schaltwerk_core_list_enriched_sessions,
             schaltwerk_core_list_enriched_sessions_sorted,
             schaltwerk_core_get_session,
@@ -1234,25 +1298,58 @@ fn main() {
             schaltwerk_core_cancel_session,
             schaltwerk_core_convert_session_to_draft,
             schaltwerk_core_update_git_stats,
+            schaltwerk_core_fetch_remote_status,
+            schaltwerk_core_refresh_session,
+            generate_commit_message,
+            schaltwerk_core_classify_worktrees,
             schaltwerk_core_cleanup_orphaned_worktrees,
             schaltwerk_core_start_claude,
             schaltwerk_core_start_claude_with_restart,
             schaltwerk_core_start_claude_orchestrator,
             schaltwerk_core_start_session_agent,
             schaltwerk_core_start_session_agent_with_restart,
+            schaltwerk_core_restart_session_preserving_context,
+            schaltwerk_core_restart_crashed_agent,
+            schaltwerk_core_get_session_launch_overrides,
+            schaltwerk_core_set_session_launch_overrides,
+            schaltwerk_core_get_session_extra_cli_args,
+            schaltwerk_core_set_session_extra_cli_args,
+            schaltwerk_core_get_session_env_vars,
+            schaltwerk_core_set_session_env_vars,
+            schaltwerk_core_get_session_launch_snapshot,
+            schaltwerk_core_preview_session_launch_command,
             schaltwerk_core_start_fresh_orchestrator,
             schaltwerk_core_reset_orchestrator,
             schaltwerk_core_reset_session_worktree,
+            schaltwerk_core_stash_session_changes,
+            schaltwerk_core_unstash_session_changes,
+            schaltwerk_core_create_session_snapshot,
+            schaltwerk_core_list_session_snapshots,
+            schaltwerk_core_restore_session_snapshot,
+            schaltwerk_core_recreate_session_branch,
             schaltwerk_core_discard_file_in_session,
+            schaltwerk_core_discard_files_in_session,
+            schaltwerk_core_preview_discard_file_in_session,
+            schaltwerk_core_revert_file_to_commit,
+            schaltwerk_core_list_scheduled_specs,
+            schaltwerk_core_schedule_spec,
+            schaltwerk_core_cancel_scheduled_spec,
+            schaltwerk_core_run_setup_script,
+            schaltwerk_core_generate_session_name,
             schaltwerk_core_discard_file_in_orchestrator,
+            schaltwerk_core_discard_hunk_in_session,
+            schaltwerk_core_discard_hunk_in_orchestrator,
             schaltwerk_core_set_skip_permissions,
             schaltwerk_core_get_skip_permissions,
             schaltwerk_core_set_orchestrator_skip_permissions,
             schaltwerk_core_get_orchestrator_skip_permissions,
             schaltwerk_core_get_merge_preview,
             schaltwerk_core_get_merge_preview_with_worktree,
+            schaltwerk_core_suggest_merge_commit_message,
+            schaltwerk_core_preview_update_from_parent,
             schaltwerk_core_merge_session_to_main,
             schaltwerk_core_update_session_from_parent,
+            schaltwerk_core_update_all_sessions_from_parent,
             schaltwerk_core_mark_session_ready,
             schaltwerk_core_has_uncommitted_changes,
             schaltwerk_core_unmark_session_ready,
@@ -1270,10 +1367,28 @@ fn main() {
             schaltwerk_core_create_spec_session,
             schaltwerk_core_update_session_state,
             schaltwerk_core_update_spec_content,
+            schaltwerk_core_set_spec_dependency,
             schaltwerk_core_append_spec_content,
+            schaltwerk_core_get_agent_stats,
+            schaltwerk_core_get_session_commits,
+            schaltwerk_core_cherry_pick_session_commits,
+            schaltwerk_core_run_db_maintenance,
+            schaltwerk_core_get_database_info,
+            schaltwerk_core_get_session_history,
+            schaltwerk_core_confirm_skip_permissions,
+            schaltwerk_core_export_specs_to_directory,
+            schaltwerk_core_export_spec_to_file,
+            schaltwerk_core_import_specs_from_directory,
+            schaltwerk_core_watch_specs_dir,
+            schaltwerk_core_unwatch_specs_dir,
             schaltwerk_core_link_session_to_pr,
             schaltwerk_core_unlink_session_from_pr,
             schaltwerk_core_rename_draft_session,
+            schaltwerk_core_fork_session,
+            schaltwerk_core_create_version_group,
+            schaltwerk_core_diff_version_group,
+            schaltwerk_core_get_version_group_comparison,
+            schaltwerk_core_select_version_winner,
             schaltwerk_core_rename_session_display_name,
             schaltwerk_core_list_sessions_by_state,
             schaltwerk_core_archive_spec_session,
@@ -1293,7 +1408,12 @@ fn main() {
             diff_commands::get_changed_files_from_main,
             diff_commands::has_remote_tracking_branch,
             diff_commands::get_orchestrator_working_changes,
+            diff_commands::stage_orchestrator_file,
+            diff_commands::unstage_orchestrator_file,
+            diff_commands::stage_all_orchestrator_changes,
+            diff_commands::commit_orchestrator_changes,
             diff_commands::get_file_diff_from_main,
+            diff_commands::compare_sessions,
             diff_commands::get_current_branch_name,
             diff_commands::get_base_branch_name,
             diff_commands::get_commit_comparison_info,
@@ -1307,6 +1427,7 @@ fn main() {
             file_commands::read_project_file,
             // Project commands
             get_recent_projects,
+            list_all_projects_summary,
             add_recent_project,
             update_recent_project_timestamp,
             remove_recent_project,
@@ -1322,6 +1443,14 @@ fn main() {
             repository_is_empty,
             get_active_project_path,
             close_project,
+            list_workspaces,
+            save_workspace,
+            delete_workspace,
+            open_workspace,
+            schaltwerk_core_list_workspace_sessions,
+            schaltwerk_core_relocate_project,
+            list_database_backups,
+            restore_database_backup,
             // Settings commands
             get_project_default_base_branch,
             set_project_default_base_branch,
@@ -1347,6 +1476,9 @@ fn main() {
             get_dev_error_toasts_enabled,
             set_auto_update_enabled,
             set_dev_error_toasts_enabled,
+            get_webhook_request_log_enabled,
+            set_webhook_request_log_enabled,
+            get_webhook_request_log,
             get_last_project_parent_directory,
             set_last_project_parent_directory,
             get_keyboard_shortcuts,
@@ -1359,6 +1491,25 @@ fn main() {
             set_project_environment_variables,
             get_project_merge_preferences,
             set_project_merge_preferences,
+            get_project_merge_commit_trailers,
+            set_project_merge_commit_trailers,
+            get_project_skip_permissions_policy,
+            set_project_skip_permissions_policy,
+            get_project_worktree_root,
+            set_project_worktree_root,
+            get_project_branch_collision_strategy,
+            set_project_branch_collision_strategy,
+            get_project_database_backups_enabled,
+            set_project_database_backups_enabled,
+            get_project_keep_recordings,
+            set_project_keep_recordings,
+            get_project_spec_file_mirror,
+            set_project_spec_file_mirror,
+            get_project_update_from_parent_on_start,
+            set_project_update_from_parent_on_start,
+            get_project_prompt_templates,
+            save_project_prompt_template,
+            delete_project_prompt_template,
             get_project_action_buttons,
             set_project_action_buttons,
             reset_project_action_buttons_to_defaults,
@@ -1370,6 +1521,11 @@ fn main() {
             get_global_keep_awake_state,
             enable_global_keep_awake,
             disable_global_keep_awake,
+            get_global_keep_awake_max_minutes,
+            set_global_keep_awake_max_minutes,
+            get_global_keep_awake_auto_mode,
+            set_global_keep_awake_auto_mode,
+            set_global_keep_awake_activity_minutes,
             // Agent binary commands
             detect_agent_binaries,
             get_agent_binary_config,
@@ -1378,6 +1534,7 @@ fn main() {
             get_all_agent_binary_configs,
             detect_all_agent_binaries,
             refresh_agent_binary_detection,
+            agent_config_health,
             // File watcher commands
             start_file_watcher,
             stop_file_watcher,
@@ -1392,7 +1549,7 @@ fn main() {
             set_amp_mcp_servers,
             get_agent_command_prefix,
             set_agent_command_prefix
-        ])
+        ]))
         .setup(move |app| {
             if ATTENTION_REGISTRY.get().is_none() {
                 let registry = Arc::new(Mutex::new(AttentionStateRegistry::default()));
@@ -1423,7 +1580,11 @@ fn main() {
             let project_manager: Arc<ProjectManager> = if let Some(existing) = PROJECT_MANAGER.get() {
                 existing.clone()
             } else {
-                let pm = Arc::new(ProjectManager::new());
+                let pm = Arc::new(if read_only {
+                    ProjectManager::new_read_only()
+                } else {
+                    ProjectManager::new()
+                });
                 let _ = PROJECT_MANAGER.set(pm.clone());
                 pm
             };
@@ -1431,11 +1592,31 @@ fn main() {
             let services = ServiceHandles::new(Arc::clone(&project_manager), app.handle().clone());
             app.manage(services);
 
+            // Kill agents a crashed instance left running before this one spawns its own
+            if !read_only {
+                match app.path().app_config_dir() {
+                    Ok(config_dir) => {
+                        use schaltwerk::domains::terminal::process_registry::{
+                            PROCESS_REGISTRY_FILE, PtyProcessRegistry, SystemProcessInspector,
+                            install_process_registry,
+                        };
+                        install_process_registry(PtyProcessRegistry::new(
+                            config_dir.join(PROCESS_REGISTRY_FILE),
+                            Arc::new(SystemProcessInspector),
+                        ));
+                    }
+                    Err(e) => log::warn!("Process registry unavailable: {e}"),
+                }
+            }
+
             // Initialize global keep-awake service
             match GlobalInhibitorService::initialize(app.handle().clone()) {
                 Ok(service) => {
                     set_global_keep_awake_service(service.clone());
                     app.manage(service);
+                    tauri::async_runtime::spawn(
+                        schaltwerk::services::power::apply_persisted_activity_window(),
+                    );
                     log::info!("Global keep-awake service initialized");
                 }
                 Err(e) => {
@@ -1453,9 +1634,12 @@ fn main() {
                 }).await.ok().flatten();
 
                 if let Some(window) = app_handle.get_webview_window("main") {
-                    let title = if let Some(branch) = branch_name {
+                    let mut title = if let Some(branch) = branch_name {
                         if !branch.is_empty() { format!("Schaltwerk - {branch}") } else { "Schaltwerk".to_string() }
                     } else { "Schaltwerk".to_string() };
+                    if read_only {
+                        title.push_str(" (read-only)");
+                    }
 
                     if let Err(e) = window.set_title(&title) {
                         log::warn!("Failed to set window title: {e}");
@@ -1505,6 +1689,7 @@ fn main() {
                             let payload = events::ProjectValidationErrorPayload {
                                 path: path_str.clone(),
                                 error,
+                                detected_old_path: None,
                             };
                             if let Err(e) = emit_event(&app_handle, SchaltEvent::ProjectValidationError, &payload) {
                                 log::error!("Failed to emit project-validation-error event: {e}");
@@ -1572,6 +1757,12 @@ fn main() {
                 // Small delay to let UI appear first
                 sleep(Duration::from_millis(50)).await;
 
+                // Everything below writes to project databases or repositories
+                if read_only {
+                    log::info!("Read-only mode: background services and webhook server disabled");
+                    return;
+                }
+
                 // Start terminal monitoring
 
 
@@ -1583,7 +1774,11 @@ fn main() {
                         match get_core_read().await {
                             Ok(core) => {
                                 let db = Arc::new(core.db.clone());
-                                schaltwerk::domains::sessions::activity::start_activity_tracking_with_app(db, activity_handle.clone());
+                                schaltwerk::domains::sessions::activity::start_activity_tracking_with_app(
+                                    db,
+                                    activity_handle.clone(),
+                                    schaltwerk::infrastructure::keep_awake_bridge::agent_activity_monitor(),
+                                );
                                 break;
                             }
                             Err(e) => {
@@ -1594,6 +1789,19 @@ fn main() {
                     }
                 });
 
+                // Start scheduled specs once their time arrives
+                tokio::spawn(commands::run_spec_scheduler(app_handle.clone()));
+
+                // Watch CI and review status of open session PRs
+                tokio::spawn(commands::run_pr_status_poller(app_handle.clone()));
+
+                // Retry name generation for sessions still waiting on their agent-generated name
+                tokio::spawn(commands::run_session_name_retrier(app_handle.clone()));
+
+                // Relaunch crashed agents according to each project's restart policy
+                commands::install_agent_crash_supervisor(app_handle.clone());
+                commands::install_agent_status_events(app_handle.clone());
+
                 // Start webhook server for MCP notifications
                 let webhook_handle = app_handle.clone();
                 tokio::spawn(async move {
@@ -1610,6 +1818,10 @@ fn main() {
         })
         .on_window_event(|_window, event| {
             if let tauri::WindowEvent::CloseRequested { .. } = event {
+                // Give in-flight session operations a bounded chance to finish so they
+                // do not leave half-built worktrees behind for the recovery pass.
+                schaltwerk::services::wait_for_in_flight_operations(std::time::Duration::from_secs(5));
+
                 // Kill all terminal child processes synchronously before exit
                 tauri::async_runtime::block_on(async {
                     let manager = get_project_manager().await;
@@ -1,8 +1,7 @@
 use http_body_util::BodyExt;
 use hyper::{
     HeaderMap, Method, Request, Response, StatusCode,
-    body::Incoming,
-    header::{CONTENT_TYPE, HeaderValue},
+    header::{CACHE_CONTROL, CONTENT_TYPE, HeaderValue},
 };
 use log::{debug, error, info, warn};
 use serde::Serialize;
@@ -15,11 +14,15 @@ use crate::commands::github::{CreateSessionPrArgs, github_create_session_pr_impl
 use crate::commands::schaltwerk_core::{
     MergeCommandError, merge_session_with_events, schaltwerk_core_cancel_session,
     schaltwerk_core_start_claude_orchestrator, schaltwerk_core_start_session_agent_with_restart,
-    StartAgentParams,
+    StartAgentParams, schaltwerk_core_cancel_scheduled_spec, schaltwerk_core_list_scheduled_specs,
+    schaltwerk_core_schedule_spec, spawn_session_setup, schaltwerk_core_create_version_group,
 };
 use crate::commands::sessions_refresh::{SessionsRefreshReason, request_sessions_refresh};
 use crate::mcp_api::diff_api::{DiffApiError, DiffChunkRequest, DiffScope, SummaryQuery};
-use crate::{REQUEST_PROJECT_OVERRIDE, get_core_read, get_core_write, SETTINGS_MANAGER};
+use crate::mcp_api::request_log::RequestBody;
+use crate::{
+    REQUEST_PROJECT_OVERRIDE, SETTINGS_MANAGER, get_core_read, get_core_write, get_terminal_manager,
+};
 use schaltwerk::infrastructure::database::db_project_config::ProjectConfigMethods;
 use schaltwerk::schaltwerk_core::db_app_config::AppConfigMethods;
 use schaltwerk::shared::terminal_id::terminal_id_for_orchestrator_top;
@@ -29,11 +32,15 @@ use schaltwerk::domains::merge::MergeMode;
 use schaltwerk::domains::sessions::entity::{Session, Spec};
 use schaltwerk::infrastructure::events::{emit_event, SchaltEvent};
 use schaltwerk::schaltwerk_core::{SessionManager, SessionState};
+use schaltwerk::domains::agents::manifest::AgentManifest;
+use schaltwerk::domains::sessions::utils::SessionUtils;
 
 mod diff_api;
+pub mod request_log;
+pub mod terminal_api;
 
 pub async fn handle_mcp_request(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     // Preserve project affinity from MCP clients (terminals) using the header
@@ -54,7 +61,7 @@ pub async fn handle_mcp_request(
 }
 
 async fn handle_mcp_request_inner(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     let method = req.method().clone();
@@ -84,11 +91,19 @@ async fn handle_mcp_request_inner(
             delete_draft(&name, app).await
         }
         (&Method::POST, "/api/sessions") => create_session(req, app).await,
+        (&Method::POST, "/api/sessions/start") => start_session(req, app).await,
+        (&Method::POST, "/api/version-groups") => create_version_group(req, app).await,
         (&Method::GET, path) if path.starts_with("/api/sessions/") && path.ends_with("/spec") => {
             let name = extract_session_name_for_action(path, "/spec");
             get_session_spec(&name).await
         }
         (&Method::GET, "/api/sessions") => list_sessions(req).await,
+        (&Method::GET, path)
+            if path.starts_with("/api/sessions/") && path.ends_with("/terminal") =>
+        {
+            let name = extract_session_name_for_action(path, "/terminal");
+            get_session_terminal(&name, req.uri().query()).await
+        }
         (&Method::GET, path)
             if path.starts_with("/api/sessions/") && path.ends_with("/pr-feedback") =>
         {
@@ -159,9 +174,16 @@ async fn handle_mcp_request_inner(
         (&Method::POST, "/api/project/run-script/execute") => execute_project_run_script().await,
         (&Method::GET, "/api/epics") => list_epics().await,
         (&Method::POST, "/api/epics") => create_epic(req, app).await,
+        (&Method::GET, "/api/scheduled-specs") => list_scheduled_specs().await,
+        (&Method::POST, "/api/scheduled-specs") => schedule_spec(req, app).await,
+        (&Method::DELETE, path) if path.starts_with("/api/scheduled-specs/") => {
+            let id = extract_draft_name(path, "/api/scheduled-specs/");
+            cancel_scheduled_spec(&id, app).await
+        }
         (&Method::GET, "/api/current-spec-mode-session") => {
             get_current_spec_mode_session(app).await
         }
+        (&Method::GET, "/api/debug/requests") => get_request_log(req).await,
         _ => Ok(not_found_response()),
     }
 }
@@ -206,6 +228,15 @@ fn extract_session_name_for_action(path: &str, action: &str) -> String {
         .to_string()
 }
 
+/// Session name of a `GET /api/sessions/{name}/terminal/stream` request. These are
+/// answered with a streaming body, outside of [`handle_mcp_request`].
+pub fn terminal_stream_session_name(method: &Method, path: &str) -> Option<String> {
+    let suffix = "/terminal/stream";
+    (method == Method::GET && path.starts_with("/api/sessions/") && path.ends_with(suffix))
+        .then(|| extract_session_name_for_action(path, suffix))
+        .filter(|name| !name.is_empty())
+}
+
 fn not_found_response() -> Response<String> {
     let mut response = Response::new("Not Found".to_string());
     *response.status_mut() = StatusCode::NOT_FOUND;
@@ -258,7 +289,7 @@ fn json_error_response(status: StatusCode, message: String) -> Response<String>
     json_response(status, body)
 }
 
-async fn diff_summary(req: Request<Incoming>) -> Result<Response<String>, hyper::Error> {
+async fn diff_summary(req: Request<RequestBody>) -> Result<Response<String>, hyper::Error> {
     let query = req.uri().query().unwrap_or("");
     let mut session_param: Option<String> = None;
     let mut cursor_param: Option<String> = None;
@@ -307,7 +338,7 @@ async fn diff_summary(req: Request<Incoming>) -> Result<Response<String>, hyper:
     Ok(json_response(StatusCode::OK, json))
 }
 
-async fn diff_chunk(req: Request<Incoming>) -> Result<Response<String>, hyper::Error> {
+async fn diff_chunk(req: Request<RequestBody>) -> Result<Response<String>, hyper::Error> {
     let query = req.uri().query().unwrap_or("");
     let mut session_param: Option<String> = None;
     let mut cursor_param: Option<String> = None;
@@ -555,6 +586,7 @@ mod tests {
             content: content.unwrap_or_default().to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            depends_on_session: None,
         }
     }
 
@@ -734,10 +766,107 @@ mod tests {
         assert_eq!(payload.agent_type.as_deref(), Some("opencode"));
         assert_eq!(payload.prompt.as_deref(), Some("go"));
     }
+
+    async fn start_request_body(json: &'static str) -> hyper::body::Bytes {
+        let request = Request::post("/api/sessions/start")
+            .body(
+                http_body_util::Full::new(hyper::body::Bytes::from_static(json.as_bytes()))
+                    .map_err(|never| match never {})
+                    .boxed(),
+            )
+            .expect("request");
+        request
+            .into_body()
+            .collect()
+            .await
+            .expect("body")
+            .to_bytes()
+    }
+
+    fn error_code(response: &Response<String>) -> String {
+        let body: serde_json::Value = serde_json::from_str(response.body()).expect("json body");
+        body["code"].as_str().expect("code").to_string()
+    }
+
+    #[tokio::test]
+    async fn start_session_rejects_invalid_name_with_code() {
+        let body = start_request_body(r#"{ "name": "no spaces allowed", "prompt": "hi" }"#).await;
+        let response = parse_start_session_request(&body)
+            .expect_err("invalid name should be rejected")
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(&response), "invalid_session_name");
+
+        let body = start_request_body(r#"{ "prompt": "hi" }"#).await;
+        let response = parse_start_session_request(&body)
+            .expect_err("missing name should be rejected")
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(&response), "invalid_request");
+    }
+
+    #[test]
+    fn start_session_reports_missing_agent_binary() {
+        let missing = ensure_agent_binary("claude", Some("/nonexistent/bin/claude"))
+            .expect_err("missing binary should be rejected")
+            .into_response();
+        assert_eq!(missing.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(&missing), "agent_binary_not_found");
+
+        let unknown = ensure_agent_binary("not-an-agent", None)
+            .expect_err("unknown agent should be rejected")
+            .into_response();
+        assert_eq!(error_code(&unknown), "unknown_agent_type");
+
+        assert!(ensure_agent_binary("terminal", None).is_ok());
+    }
+
+    #[tokio::test]
+    async fn start_session_creates_running_session_with_unique_name() {
+        let (_tmp, repo_path) = init_test_repo();
+        let manager = create_manager(&repo_path);
+        let body = start_request_body(
+            r#"{ "name": "mcp-task", "prompt": "Implement the thing", "agent_type": "claude", "version_group_id": "grp", "version_number": 1 }"#,
+        )
+        .await;
+        let request = parse_start_session_request(&body).expect("valid request");
+
+        let first = create_session_for_start(&manager, &request).expect("first session");
+        assert_eq!(first.name, "mcp-task");
+        assert_eq!(first.session_state, SessionState::Running);
+        assert_eq!(first.version_group_id.as_deref(), Some("grp"));
+        assert!(first.worktree_path.exists());
+
+        let second = create_session_for_start(&manager, &request).expect("duplicate name");
+        assert_ne!(second.name, first.name);
+        assert!(second.name.starts_with("mcp-task-"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&second).unwrap()).unwrap();
+        assert_eq!(json["name"], serde_json::json!(second.name));
+        assert_eq!(json["branch"], serde_json::json!(second.branch));
+        assert!(json["worktree_path"].is_string());
+    }
+
+    #[tokio::test]
+    async fn start_session_prompt_only_skips_spec() {
+        let (_tmp, repo_path) = init_test_repo();
+        let manager = create_manager(&repo_path);
+        let body = start_request_body(
+            r#"{ "name": "quick-fix", "prompt": "Fix the typo", "prompt_only": true }"#,
+        )
+        .await;
+        let request = parse_start_session_request(&body).expect("valid request");
+
+        let session = create_session_for_start(&manager, &request).expect("session");
+        assert_eq!(session.initial_prompt.as_deref(), Some("Fix the typo"));
+        assert!(session.worktree_path.exists());
+        assert!(manager.list_specs().expect("specs").is_empty());
+    }
 }
 
 async fn create_draft(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     let body = req.into_body();
@@ -959,7 +1088,7 @@ async fn get_spec_content(name: &str) -> Result<Response<String>, hyper::Error>
 }
 
 async fn update_spec_content(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1026,7 +1155,7 @@ async fn update_spec_content(
 }
 
 async fn start_spec_session(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1048,6 +1177,7 @@ async fn start_spec_session(
     let skip_permissions = payload["skip_permissions"].as_bool();
     let version_group_id = payload["version_group_id"].as_str().map(|s| s.to_string());
     let version_number = payload["version_number"].as_i64().map(|n| n as i32);
+    let force = payload["force"].as_bool().unwrap_or(false);
 
     let manager = match get_core_write().await {
         Ok(core) => core.session_manager(),
@@ -1068,9 +1198,13 @@ async fn start_spec_session(
         version_number,
         agent_type,
         skip_permissions,
+        force,
     ) {
-        Ok(_session) => {
+        Ok(session) => {
             info!("Started spec session via API: {name}");
+            if let Err(e) = spawn_session_setup(&app, &session).await {
+                warn!("Failed to start setup script for session '{name}': {e}");
+            }
             request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
             Ok(Response::new("OK".to_string()))
         }
@@ -1125,7 +1259,7 @@ async fn delete_draft(name: &str, app: tauri::AppHandle) -> Result<Response<Stri
 }
 
 async fn create_session(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     let body = req.into_body();
@@ -1186,6 +1320,7 @@ async fn create_session(
         was_auto_generated,
         version_group_id: None,
         version_number: None,
+        version_group_label: None,
         epic_id: epic_id.as_deref(),
         agent_type: agent_type.as_deref(),
         skip_permissions,
@@ -1195,6 +1330,9 @@ async fn create_session(
     match manager.create_session_with_agent(params) {
         Ok(session) => {
             info!("Created session via API: {name}");
+            if let Err(e) = spawn_session_setup(&app, &session).await {
+                warn!("Failed to start setup script for session '{name}': {e}");
+            }
             request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
 
             let json = serde_json::to_string(&session).unwrap_or_else(|e| {
@@ -1214,7 +1352,341 @@ async fn create_session(
     }
 }
 
-async fn list_sessions(req: Request<Incoming>) -> Result<Response<String>, hyper::Error> {
+#[derive(Debug, serde::Deserialize)]
+struct CreateVersionGroupRequest {
+    base_name: String,
+    #[serde(default)]
+    prompt: Option<String>,
+    count: usize,
+    #[serde(default)]
+    agent_configs: Vec<schaltwerk::services::VersionAgentConfig>,
+    #[serde(default)]
+    base_branch: Option<String>,
+    #[serde(default)]
+    version_group_label: Option<String>,
+}
+
+async fn create_version_group(
+    req: Request<RequestBody>,
+    app: tauri::AppHandle,
+) -> Result<Response<String>, hyper::Error> {
+    let body_bytes = req.into_body().collect().await?.to_bytes();
+    let request: CreateVersionGroupRequest = match serde_json::from_slice(&body_bytes) {
+        Ok(request) => request,
+        Err(e) => {
+            error!("Failed to parse version group request: {e}");
+            return Ok(json_error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid JSON payload: {e}"),
+            ));
+        }
+    };
+
+    let base_name = request.base_name.clone();
+    match schaltwerk_core_create_version_group(
+        app,
+        request.base_name,
+        request.prompt,
+        request.count,
+        Some(request.agent_configs),
+        request.base_branch,
+        request.version_group_label,
+    )
+    .await
+    {
+        Ok(created) => {
+            info!(
+                "Created version group '{}' with {} versions of '{base_name}' via API",
+                created.version_group_id,
+                created.sessions.len()
+            );
+            let json = serde_json::to_string(&created).unwrap_or_else(|e| {
+                error!("Failed to serialize version group: {e}");
+                "{}".to_string()
+            });
+            Ok(json_response(StatusCode::CREATED, json))
+        }
+        Err(e) => {
+            error!("Failed to create version group '{base_name}': {e}");
+            Ok(json_error_response(StatusCode::BAD_REQUEST, e))
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct StartSessionRequest {
+    name: String,
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    base_branch: Option<String>,
+    #[serde(default)]
+    agent_type: Option<String>,
+    #[serde(default)]
+    skip_permissions: Option<bool>,
+    #[serde(default)]
+    version_group_id: Option<String>,
+    #[serde(default)]
+    version_number: Option<i32>,
+    #[serde(default)]
+    version_group_label: Option<String>,
+    #[serde(default)]
+    prompt_only: bool,
+}
+
+/// Failure of `POST /api/sessions/start`, carrying a stable `code` for MCP clients.
+#[derive(Debug)]
+struct StartSessionError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+impl StartSessionError {
+    fn bad_request(code: &'static str, message: String) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            code,
+            message,
+        }
+    }
+
+    fn internal(code: &'static str, message: String) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            code,
+            message,
+        }
+    }
+
+    fn into_response(self) -> Response<String> {
+        let body = serde_json::json!({ "error": self.message, "code": self.code }).to_string();
+        json_response(self.status, body)
+    }
+}
+
+fn parse_start_session_request(
+    body_bytes: &[u8],
+) -> Result<StartSessionRequest, StartSessionError> {
+    let request: StartSessionRequest = serde_json::from_slice(body_bytes).map_err(|e| {
+        StartSessionError::bad_request("invalid_request", format!("Invalid JSON payload: {e}"))
+    })?;
+    if !SessionUtils::validate_session_name(&request.name) {
+        return Err(StartSessionError::bad_request(
+            "invalid_session_name",
+            format!(
+                "Invalid session name '{}': use only letters, numbers, hyphens, and underscores",
+                request.name
+            ),
+        ));
+    }
+    Ok(request)
+}
+
+/// Checks that the binary configured for `agent` can actually be launched.
+fn ensure_agent_binary(agent: &str, binary: Option<&str>) -> Result<(), StartSessionError> {
+    let Some(definition) = AgentManifest::get(agent) else {
+        return Err(StartSessionError::bad_request(
+            "unknown_agent_type",
+            format!("Unknown agent type '{agent}'"),
+        ));
+    };
+    let binary = binary.unwrap_or(&definition.binary_name);
+    let found = if binary.contains(std::path::MAIN_SEPARATOR) {
+        std::path::Path::new(binary).is_file()
+    } else {
+        which::which(binary).is_ok()
+    };
+    if found {
+        Ok(())
+    } else {
+        Err(StartSessionError::bad_request(
+            "agent_binary_not_found",
+            format!("Binary '{binary}' for agent '{agent}' was not found"),
+        ))
+    }
+}
+
+fn create_session_for_start(
+    manager: &SessionManager,
+    request: &StartSessionRequest,
+) -> Result<Session, StartSessionError> {
+    let prompt = request.prompt.as_deref().unwrap_or_default();
+    let result = if request.prompt_only {
+        use schaltwerk::domains::sessions::service::SessionCreationParams;
+
+        manager.create_session_with_agent(SessionCreationParams {
+            name: &request.name,
+            prompt: Some(prompt).filter(|p| !p.trim().is_empty()),
+            base_branch: request.base_branch.as_deref(),
+            custom_branch: None,
+            use_existing_branch: false,
+            sync_with_origin: false,
+            was_auto_generated: false,
+            version_group_id: request.version_group_id.as_deref(),
+            version_number: request.version_number,
+            version_group_label: request.version_group_label.as_deref(),
+            epic_id: None,
+            agent_type: request.agent_type.as_deref(),
+            skip_permissions: request.skip_permissions,
+            pr_number: None,
+        })
+    } else {
+        manager.create_and_start_spec_session_with_config(
+            &request.name,
+            prompt,
+            request.base_branch.as_deref(),
+            request.version_group_id.as_deref(),
+            request.version_number,
+            request.agent_type.as_deref(),
+            request.skip_permissions,
+        )
+    };
+    result.map_err(|e| {
+        StartSessionError::internal(
+            "session_creation_failed",
+            format!("Failed to create session '{}': {e}", request.name),
+        )
+    })
+}
+
+async fn start_session(
+    req: Request<RequestBody>,
+    app: tauri::AppHandle,
+) -> Result<Response<String>, hyper::Error> {
+    let body_bytes = req.into_body().collect().await?.to_bytes();
+    let request = match parse_start_session_request(&body_bytes) {
+        Ok(request) => request,
+        Err(err) => return Ok(err.into_response()),
+    };
+
+    let (manager, default_agent) = match get_core_write().await {
+        Ok(core) => (
+            core.session_manager(),
+            core.db
+                .get_agent_type()
+                .unwrap_or_else(|_| "claude".to_string()),
+        ),
+        Err(e) => {
+            error!("Failed to get schaltwerk core: {e}");
+            return Ok(StartSessionError::internal(
+                "internal_error",
+                format!("Internal error: {e}"),
+            )
+            .into_response());
+        }
+    };
+
+    let agent = request.agent_type.clone().unwrap_or(default_agent);
+    let configured_binary = match SETTINGS_MANAGER.get() {
+        Some(settings_manager) => settings_manager
+            .lock()
+            .await
+            .get_effective_binary_path(&agent)
+            .ok(),
+        None => None,
+    };
+    if let Err(err) = ensure_agent_binary(&agent, configured_binary.as_deref()) {
+        return Ok(err.into_response());
+    }
+
+    let session = match create_session_for_start(&manager, &request) {
+        Ok(session) => session,
+        Err(err) => {
+            error!("Failed to create session via start API: {}", err.message);
+            return Ok(err.into_response());
+        }
+    };
+    info!(
+        "Created session '{}' via start API (requested name '{}')",
+        session.name, request.name
+    );
+    request_sessions_refresh(&app, SessionsRefreshReason::SessionLifecycle);
+
+    if let Err(e) = schaltwerk_core_start_session_agent_with_restart(
+        app.clone(),
+        StartAgentParams {
+            session_name: session.name.clone(),
+            force_restart: false,
+            cols: None,
+            rows: None,
+            terminal_id: None,
+            agent_type: None,
+            prompt: None,
+            skip_prompt: None,
+            skip_permissions: None,
+        },
+    )
+    .await
+    {
+        error!("Failed to launch agent for session '{}': {e}", session.name);
+        return Ok(StartSessionError::internal(
+            "agent_launch_failed",
+            format!(
+                "Session '{}' was created but its agent failed to start: {e}",
+                session.name
+            ),
+        )
+        .into_response());
+    }
+
+    let json = serde_json::to_string(&session).unwrap_or_else(|e| {
+        error!("Failed to serialize session: {e}");
+        "{}".to_string()
+    });
+    Ok(json_response(StatusCode::CREATED, json))
+}
+
+async fn get_session_terminal(
+    name: &str,
+    query: Option<&str>,
+) -> Result<Response<String>, hyper::Error> {
+    let lines = match terminal_api::parse_tail_lines(query) {
+        Ok(lines) => lines,
+        Err(message) => return Ok(json_error_response(StatusCode::BAD_REQUEST, message)),
+    };
+    let manager = match get_terminal_manager().await {
+        Ok(manager) => manager,
+        Err(e) => return Ok(json_error_response(StatusCode::INTERNAL_SERVER_ERROR, e)),
+    };
+
+    match terminal_api::session_terminal_tail(&manager, name, lines).await {
+        Ok(tail) => {
+            let json = serde_json::to_string(&tail).unwrap_or_else(|e| {
+                error!("Failed to serialize terminal output: {e}");
+                "{}".to_string()
+            });
+            Ok(json_response(StatusCode::OK, json))
+        }
+        Err((status, message)) => Ok(json_error_response(status, message)),
+    }
+}
+
+/// Streams the session's top terminal output as server-sent events.
+pub async fn stream_session_terminal(name: &str) -> Response<terminal_api::ResponseBody> {
+    let error = |status: StatusCode, message: String| {
+        json_error_response(status, message).map(terminal_api::full_body)
+    };
+    let manager = match get_terminal_manager().await {
+        Ok(manager) => manager,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    let Some(terminal_id) = terminal_api::resolve_session_top_terminal(&manager, name).await else {
+        return error(
+            StatusCode::NOT_FOUND,
+            format!("No running terminal for session '{name}'"),
+        );
+    };
+
+    info!("Streaming terminal {terminal_id} of session '{name}'");
+    let mut response = Response::new(terminal_api::stream_terminal_output(manager, terminal_id));
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    response
+}
+
+async fn list_sessions(req: Request<RequestBody>) -> Result<Response<String>, hyper::Error> {
     // Parse query parameters
     let query = req.uri().query().unwrap_or("");
     let mut filter_state: Option<SessionState> = None;
@@ -1341,7 +1813,7 @@ struct MergeSessionResponse {
 }
 
 async fn merge_session(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1469,7 +1941,7 @@ struct PullRequestResponse {
 }
 
 async fn create_pull_request(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1546,7 +2018,7 @@ struct PreparePrResponse {
 }
 
 async fn prepare_pull_request(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1592,10 +2064,7 @@ async fn prepare_pull_request(
         }
     };
 
-    let mode_str = payload.mode.map(|m| match m {
-        MergeMode::Squash => "squash".to_string(),
-        MergeMode::Reapply => "reapply".to_string(),
-    });
+    let mode_str = payload.mode.map(|m| m.as_str().to_string());
 
     let event_payload = OpenPrModalPayload {
         session_name: name.to_string(),
@@ -1651,7 +2120,7 @@ struct PrepareMergeResponse {
 }
 
 async fn prepare_merge(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1697,10 +2166,7 @@ async fn prepare_merge(
         }
     };
 
-    let mode_str = payload.mode.map(|m| match m {
-        MergeMode::Squash => "squash".to_string(),
-        MergeMode::Reapply => "reapply".to_string(),
-    });
+    let mode_str = payload.mode.map(|m| m.as_str().to_string());
 
     let event_payload = OpenMergeModalPayload {
         session_name: name.to_string(),
@@ -1840,7 +2306,7 @@ async fn convert_session_to_spec(
 }
 
 async fn link_session_pr(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -1984,7 +2450,7 @@ async fn get_project_setup_script(
 }
 
 async fn set_project_setup_script(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     let body = req.into_body();
@@ -2181,7 +2647,7 @@ async fn list_epics() -> Result<Response<String>, hyper::Error> {
 }
 
 async fn create_epic(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     let body = req.into_body();
@@ -2239,6 +2705,101 @@ async fn create_epic(
     }
 }
 
+async fn list_scheduled_specs() -> Result<Response<String>, hyper::Error> {
+    match schaltwerk_core_list_scheduled_specs().await {
+        Ok(entries) => {
+            let json = serde_json::to_string(&entries).unwrap_or_else(|e| {
+                error!("Failed to serialize scheduled specs: {e}");
+                "[]".to_string()
+            });
+            Ok(json_response(StatusCode::OK, json))
+        }
+        Err(e) => {
+            error!("Failed to list scheduled specs: {e}");
+            Ok(json_error_response(StatusCode::INTERNAL_SERVER_ERROR, e))
+        }
+    }
+}
+
+async fn schedule_spec(
+    req: Request<RequestBody>,
+    app: tauri::AppHandle,
+) -> Result<Response<String>, hyper::Error> {
+    let body = req.into_body();
+    let body_bytes = body.collect().await?.to_bytes();
+    let payload: serde_json::Value = match serde_json::from_slice(&body_bytes) {
+        Ok(p) => p,
+        Err(e) => {
+            error!("Failed to parse schedule spec request: {e}");
+            return Ok(json_error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid JSON: {e}"),
+            ));
+        }
+    };
+
+    let Some(name) = payload["spec_name"]
+        .as_str()
+        .filter(|n| !n.trim().is_empty())
+    else {
+        return Ok(json_error_response(
+            StatusCode::BAD_REQUEST,
+            "Missing or empty 'spec_name' field".to_string(),
+        ));
+    };
+    let Some(not_before) = payload["not_before"].as_str() else {
+        return Ok(json_error_response(
+            StatusCode::BAD_REQUEST,
+            "Missing 'not_before' field (RFC 3339 timestamp)".to_string(),
+        ));
+    };
+
+    match schaltwerk_core_schedule_spec(
+        app,
+        name.to_string(),
+        not_before.to_string(),
+        payload["base_branch"].as_str().map(String::from),
+        payload["agent_type"].as_str().map(String::from),
+        payload["skip_permissions"].as_bool(),
+        payload["skip_if_late"].as_bool(),
+    )
+    .await
+    {
+        Ok(entry) => {
+            info!("Scheduled spec via API: {name} at {not_before}");
+            let json = serde_json::to_string(&entry).unwrap_or_else(|e| {
+                error!("Failed to serialize scheduled spec: {e}");
+                "{}".to_string()
+            });
+            Ok(json_response(StatusCode::CREATED, json))
+        }
+        Err(e) => {
+            error!("Failed to schedule spec: {e}");
+            Ok(json_error_response(StatusCode::BAD_REQUEST, e))
+        }
+    }
+}
+
+async fn cancel_scheduled_spec(
+    id: &str,
+    app: tauri::AppHandle,
+) -> Result<Response<String>, hyper::Error> {
+    match schaltwerk_core_cancel_scheduled_spec(app, id.to_string()).await {
+        Ok(entry) => {
+            info!("Cancelled scheduled spec via API: {id}");
+            let json = serde_json::to_string(&entry).unwrap_or_else(|e| {
+                error!("Failed to serialize scheduled spec: {e}");
+                "{}".to_string()
+            });
+            Ok(json_response(StatusCode::OK, json))
+        }
+        Err(e) => {
+            error!("Failed to cancel scheduled spec '{id}': {e}");
+            Ok(json_error_response(StatusCode::BAD_REQUEST, e))
+        }
+    }
+}
+
 async fn get_current_spec_mode_session(
     _app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -2247,6 +2808,26 @@ async fn get_current_spec_mode_session(
     Ok(error_response(StatusCode::NOT_FOUND, "Spec mode session tracking not yet implemented. Use schaltwerk_draft_update with explicit session name.".to_string()))
 }
 
+async fn get_request_log(req: Request<RequestBody>) -> Result<Response<String>, hyper::Error> {
+    let query = req.uri().query().unwrap_or("");
+    let limit_param = form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == "limit")
+        .map(|(_, value)| value.into_owned());
+
+    let limit = match parse_optional_usize(limit_param, "limit") {
+        Ok(limit) => limit,
+        Err(err) => return Ok(diff_error_response(err)),
+    };
+
+    match serde_json::to_string(&request_log::snapshot(limit)) {
+        Ok(json) => Ok(json_response(StatusCode::OK, json)),
+        Err(e) => Ok(json_error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to serialize request log: {e}"),
+        )),
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 struct ResetSessionRequest {
@@ -2293,7 +2874,7 @@ fn parse_reset_selection_request(body_bytes: &[u8]) -> Result<ResetSelectionRequ
 }
 
 async fn reset_selection(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
     let body_bytes = req.into_body().collect().await?.to_bytes();
@@ -2495,12 +3076,12 @@ async fn reset_session_with_payload(
 
     match result {
         Ok(command) => Ok(Response::new(command)),
-        Err(err) => Ok(error_response(StatusCode::BAD_REQUEST, err)),
+        Err(err) => Ok(error_response(StatusCode::BAD_REQUEST, err.to_string())),
     }
 }
 
 async fn reset_session(
-    req: Request<Incoming>,
+    req: Request<RequestBody>,
     name: &str,
     app: tauri::AppHandle,
 ) -> Result<Response<String>, hyper::Error> {
@@ -739,6 +739,7 @@ mod tests {
             display_name: None,
             version_group_id: None,
             version_number: None,
+            version_group_label: None,
             epic_id: None,
             repository_path: repo_path.to_path_buf(),
             repository_name: repo_name,
@@ -762,6 +763,7 @@ mod tests {
             amp_thread_id: None,
             pr_number: None,
             pr_url: None,
+            group: None,
         }
     }
 
@@ -0,0 +1,311 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Instant;
+
+use http_body_util::{BodyExt, Full, combinators::BoxBody};
+use hyper::body::{Bytes, Incoming};
+use hyper::{Method, Request, Response, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Request body handed to the webhook and MCP API handlers. The server reads the body
+/// once up front so the request log can keep a copy of it.
+pub type RequestBody = BoxBody<Bytes, hyper::Error>;
+
+const MAX_ENTRIES: usize = 200;
+const MAX_BODY_CHARS: usize = 2048;
+const REDACTED: &str = "[redacted]";
+const SENSITIVE_KEYS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "authorization",
+    "api_key",
+    "apikey",
+    "credential",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookRequestEntry {
+    pub method: String,
+    pub path: String,
+    pub timestamp_ms: i64,
+    pub status: u16,
+    pub latency_ms: u64,
+    pub body: Option<String>,
+    pub body_truncated: bool,
+    pub client_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointCounter {
+    pub endpoint: String,
+    pub requests: u64,
+    pub client_errors: u64,
+    pub server_errors: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookRequestLog {
+    pub enabled: bool,
+    pub entries: Vec<WebhookRequestEntry>,
+    pub endpoints: Vec<EndpointCounter>,
+}
+
+#[derive(Default)]
+struct RequestLog {
+    entries: VecDeque<WebhookRequestEntry>,
+    counters: BTreeMap<String, EndpointCounter>,
+}
+
+impl RequestLog {
+    fn push(&mut self, entry: WebhookRequestEntry) {
+        let endpoint = endpoint_key(&entry.method, &entry.path);
+        let counter = self
+            .counters
+            .entry(endpoint.clone())
+            .or_insert_with(|| EndpointCounter {
+                endpoint,
+                ..EndpointCounter::default()
+            });
+        counter.requests += 1;
+        match entry.status {
+            400..=499 => counter.client_errors += 1,
+            500..=599 => counter.server_errors += 1,
+            _ => {}
+        }
+
+        while self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    fn snapshot(&self, limit: Option<usize>) -> (Vec<WebhookRequestEntry>, Vec<EndpointCounter>) {
+        let limit = limit.unwrap_or(MAX_ENTRIES);
+        let entries = self.entries.iter().rev().take(limit).cloned().collect();
+        (entries, self.counters.values().cloned().collect())
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static LOG: LazyLock<Mutex<RequestLog>> = LazyLock::new(|| Mutex::new(RequestLog::default()));
+
+fn log() -> MutexGuard<'static, RequestLog> {
+    LOG.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Returns the most recent entries first, together with per-endpoint counters.
+pub fn snapshot(limit: Option<usize>) -> WebhookRequestLog {
+    let (entries, endpoints) = log().snapshot(limit);
+    WebhookRequestLog {
+        enabled: is_enabled(),
+        entries,
+        endpoints,
+    }
+}
+
+/// Reads the incoming body so it can be both logged and handed on to the handlers.
+pub async fn buffer_request(
+    req: Request<Incoming>,
+) -> Result<(Request<RequestBody>, Bytes), hyper::Error> {
+    let (parts, body) = req.into_parts();
+    let bytes = body.collect().await?.to_bytes();
+    let body = Full::new(bytes.clone())
+        .map_err(|never| match never {})
+        .boxed();
+    Ok((Request::from_parts(parts, body), bytes))
+}
+
+/// Tracks one request from arrival until its response is known.
+pub struct RequestRecorder {
+    method: Method,
+    path: String,
+    body: Bytes,
+    started_at: Instant,
+    timestamp_ms: i64,
+}
+
+impl RequestRecorder {
+    pub fn start(method: &Method, path: &str, body: &Bytes) -> Option<Self> {
+        if !is_enabled() || path == "/api/debug/requests" {
+            return None;
+        }
+        Some(Self {
+            method: method.clone(),
+            path: path.to_string(),
+            body: body.clone(),
+            started_at: Instant::now(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+
+    pub fn finish(self, response: &Result<Response<String>, hyper::Error>) {
+        let (status, client_error) = match response {
+            Ok(response) => {
+                let status = response.status();
+                let client_error = status
+                    .is_client_error()
+                    .then(|| truncate(response.body()).0);
+                (status, client_error)
+            }
+            Err(e) => (StatusCode::BAD_REQUEST, Some(e.to_string())),
+        };
+        let (body, body_truncated) = summarize_body(&self.body);
+
+        log().push(WebhookRequestEntry {
+            method: self.method.to_string(),
+            path: self.path,
+            timestamp_ms: self.timestamp_ms,
+            status: status.as_u16(),
+            latency_ms: self.started_at.elapsed().as_millis() as u64,
+            body,
+            body_truncated,
+            client_error,
+        });
+    }
+}
+
+/// Groups requests by route rather than by the session or spec they address.
+fn endpoint_key(method: &str, path: &str) -> String {
+    let normalized = ["/api/sessions/", "/api/specs/"]
+        .iter()
+        .find_map(|prefix| {
+            let rest = path.strip_prefix(prefix)?;
+            let suffix = rest.find('/').map(|idx| &rest[idx..]).unwrap_or_default();
+            Some(format!("{prefix}:name{suffix}"))
+        })
+        .unwrap_or_else(|| path.to_string());
+    format!("{method} {normalized}")
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace('-', "_");
+    SENSITIVE_KEYS
+        .iter()
+        .any(|sensitive| key.contains(sensitive))
+}
+
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_key(key) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+fn truncate(text: &str) -> (String, bool) {
+    match text.char_indices().nth(MAX_BODY_CHARS) {
+        Some((idx, _)) => (text[..idx].to_string(), true),
+        None => (text.to_string(), false),
+    }
+}
+
+/// Redacts secrets before truncating so a cut never exposes part of a secret value.
+/// Bodies that are not JSON are dropped if they mention a sensitive key at all.
+fn summarize_body(body: &Bytes) -> (Option<String>, bool) {
+    if body.is_empty() {
+        return (None, false);
+    }
+    let text = String::from_utf8_lossy(body);
+    let redacted = match serde_json::from_str::<Value>(&text) {
+        Ok(mut value) => {
+            redact_json(&mut value);
+            value.to_string()
+        }
+        Err(_) if is_sensitive_key(&text) => REDACTED.to_string(),
+        Err(_) => text.into_owned(),
+    };
+    let (body, truncated) = truncate(&redacted);
+    (Some(body), truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, path: &str, status: u16) -> WebhookRequestEntry {
+        WebhookRequestEntry {
+            method: method.to_string(),
+            path: path.to_string(),
+            timestamp_ms: 0,
+            status,
+            latency_ms: 1,
+            body: None,
+            body_truncated: false,
+            client_error: None,
+        }
+    }
+
+    #[test]
+    fn buffer_is_bounded_and_counters_group_by_route() {
+        let mut log = RequestLog::default();
+        for i in 0..MAX_ENTRIES + 5 {
+            log.push(entry("GET", &format!("/api/sessions/session-{i}"), 200));
+        }
+        log.push(entry("POST", "/api/sessions/alpha/merge", 409));
+
+        let (entries, endpoints) = log.snapshot(Some(3));
+        assert_eq!(log.entries.len(), MAX_ENTRIES);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].path, "/api/sessions/alpha/merge");
+        assert_eq!(
+            endpoints,
+            vec![
+                EndpointCounter {
+                    endpoint: "GET /api/sessions/:name".to_string(),
+                    requests: (MAX_ENTRIES + 5) as u64,
+                    client_errors: 0,
+                    server_errors: 0,
+                },
+                EndpointCounter {
+                    endpoint: "POST /api/sessions/:name/merge".to_string(),
+                    requests: 1,
+                    client_errors: 1,
+                    server_errors: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn bodies_are_redacted_and_capped() {
+        let body = Bytes::from(
+            r#"{"name":"alpha","github_token":"ghp_abc","nested":{"Authorization":"Bearer x"}}"#,
+        );
+        let (summary, truncated) = summarize_body(&body);
+        let summary = summary.unwrap();
+        assert!(!truncated);
+        assert!(summary.contains("alpha"));
+        assert!(!summary.contains("ghp_abc"));
+        assert!(!summary.contains("Bearer x"));
+
+        let (summary, _) = summarize_body(&Bytes::from("password=hunter2"));
+        assert_eq!(summary.as_deref(), Some(REDACTED));
+
+        let long = Bytes::from("x".repeat(MAX_BODY_CHARS + 10));
+        let (summary, truncated) = summarize_body(&long);
+        assert!(truncated);
+        assert_eq!(summary.unwrap().len(), MAX_BODY_CHARS);
+    }
+}
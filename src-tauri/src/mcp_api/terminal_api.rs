@@ -0,0 +1,326 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use http_body_util::{BodyExt, Full, StreamBody, combinators::UnsyncBoxBody};
+use hyper::StatusCode;
+use hyper::body::{Bytes, Frame};
+use log::debug;
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use schaltwerk::domains::terminal::TerminalManager;
+use schaltwerk::domains::terminal::visible::PlainTextOptions;
+use schaltwerk::shared::terminal_id::{
+    legacy_terminal_id_for_session_top, previous_hashed_terminal_id_for_session_top,
+    previous_tilde_hashed_terminal_id_for_session_top, terminal_id_for_session_top,
+};
+
+/// Body type of webhook server responses, which may be streamed.
+pub type ResponseBody = UnsyncBoxBody<Bytes, Infallible>;
+
+pub const DEFAULT_TAIL_LINES: usize = 200;
+const MAX_TAIL_LINES: usize = 10_000;
+/// Output events buffered for a stream client before new ones are dropped.
+const STREAM_BUFFER_EVENTS: usize = 32;
+
+pub fn full_body(body: String) -> ResponseBody {
+    Full::new(Bytes::from(body)).boxed_unsync()
+}
+
+/// Top terminal ids a session may be running under, current naming scheme first.
+/// Shared with the follow-up webhook so both reach terminals created by older builds.
+pub fn session_top_terminal_candidates(session_name: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    for candidate in [
+        terminal_id_for_session_top(session_name),
+        previous_tilde_hashed_terminal_id_for_session_top(session_name),
+        previous_hashed_terminal_id_for_session_top(session_name),
+        legacy_terminal_id_for_session_top(session_name),
+    ] {
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+pub async fn resolve_session_top_terminal(
+    manager: &TerminalManager,
+    session_name: &str,
+) -> Option<String> {
+    for candidate in session_top_terminal_candidates(session_name) {
+        if let Ok(true) = manager.terminal_exists(&candidate).await {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+pub fn parse_tail_lines(query: Option<&str>) -> Result<usize, String> {
+    let value = query.and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "lines")
+            .map(|(_, value)| value.into_owned())
+    });
+    let Some(value) = value else {
+        return Ok(DEFAULT_TAIL_LINES);
+    };
+    match value.parse::<usize>() {
+        Ok(lines) if (1..=MAX_TAIL_LINES).contains(&lines) => Ok(lines),
+        _ => Err(format!(
+            "Invalid 'lines' value '{value}': expected a number between 1 and {MAX_TAIL_LINES}"
+        )),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TerminalTail {
+    pub session_name: String,
+    pub terminal_id: String,
+    pub lines: usize,
+    pub text: String,
+}
+
+/// Renders the last `lines` lines of the session's top terminal as plain text.
+pub async fn session_terminal_tail(
+    manager: &TerminalManager,
+    session_name: &str,
+    lines: usize,
+) -> Result<TerminalTail, (StatusCode, String)> {
+    let terminal_id = resolve_session_top_terminal(manager, session_name)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("No running terminal for session '{session_name}'"),
+            )
+        })?;
+    let text = manager
+        .get_terminal_buffer_text(
+            terminal_id.clone(),
+            PlainTextOptions {
+                trim_trailing_whitespace: true,
+                max_lines: Some(lines),
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(TerminalTail {
+        session_name: session_name.to_string(),
+        terminal_id,
+        lines,
+        text,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forwarded {
+    Sent,
+    Dropped,
+    Closed,
+}
+
+/// Hands output events to a stream client through a bounded queue. While the client
+/// is not reading, new events are dropped instead of queued; the next event that gets
+/// through reports how many were lost so the client can refetch a snapshot.
+pub struct OutputForwarder {
+    sender: mpsc::Sender<Bytes>,
+    dropped: u64,
+}
+
+#[derive(Serialize)]
+struct OutputEvent<'a> {
+    seq: u64,
+    data: &'a str,
+    dropped: u64,
+}
+
+impl OutputForwarder {
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<Bytes>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        (Self { sender, dropped: 0 }, receiver)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    pub fn offer(&mut self, seq: u64, data: &[u8]) -> Forwarded {
+        let text = String::from_utf8_lossy(data);
+        let event = OutputEvent {
+            seq,
+            data: &text,
+            dropped: self.dropped,
+        };
+        let payload = serde_json::to_string(&event).unwrap_or_default();
+        let frame = Bytes::from(format!("event: output\ndata: {payload}\n\n"));
+        match self.sender.try_send(frame) {
+            Ok(()) => {
+                self.dropped = 0;
+                Forwarded::Sent
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.dropped += 1;
+                Forwarded::Dropped
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => Forwarded::Closed,
+        }
+    }
+}
+
+/// Streams output appended to `terminal_id` as server-sent events. The pump stops once
+/// the terminal goes away or the client disconnects and the body is dropped.
+pub fn stream_terminal_output(manager: Arc<TerminalManager>, terminal_id: String) -> ResponseBody {
+    let (mut forwarder, receiver) = OutputForwarder::new(STREAM_BUFFER_EVENTS);
+
+    tokio::spawn(async move {
+        let mut seq = match manager.get_terminal_buffer(terminal_id.clone(), None).await {
+            Ok(snapshot) => snapshot.seq,
+            Err(e) => {
+                debug!("Terminal stream for {terminal_id} ended before start: {e}");
+                return;
+            }
+        };
+        while !forwarder.is_closed() {
+            let next = match manager.wait_for_output_change(&terminal_id, seq).await {
+                Ok(next) => next,
+                Err(e) => {
+                    debug!("Terminal stream for {terminal_id} ended: {e}");
+                    break;
+                }
+            };
+            if next <= seq {
+                continue;
+            }
+            let snapshot = match manager
+                .get_terminal_buffer(terminal_id.clone(), Some(seq))
+                .await
+            {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    debug!("Terminal stream for {terminal_id} ended: {e}");
+                    break;
+                }
+            };
+            seq = snapshot.seq;
+            if forwarder.offer(snapshot.seq, &snapshot.data) == Forwarded::Closed {
+                break;
+            }
+        }
+    });
+
+    let events = futures::stream::unfold(receiver, |mut receiver| async move {
+        let frame = receiver.recv().await?;
+        Some((Ok::<_, Infallible>(Frame::data(frame)), receiver))
+    });
+    StreamBody::new(events).boxed_unsync()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn event_json(frame: &Bytes) -> serde_json::Value {
+        let text = std::str::from_utf8(frame).unwrap();
+        let payload = text
+            .strip_prefix("event: output\ndata: ")
+            .and_then(|rest| rest.strip_suffix("\n\n"))
+            .expect("server-sent event framing");
+        serde_json::from_str(payload).unwrap()
+    }
+
+    #[test]
+    fn candidates_start_with_current_id_and_include_legacy_variants() {
+        let candidates = session_top_terminal_candidates("my-session");
+        assert_eq!(candidates[0], terminal_id_for_session_top("my-session"));
+        assert!(candidates.contains(&legacy_terminal_id_for_session_top("my-session")));
+        let mut unique = candidates.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), candidates.len());
+    }
+
+    #[test]
+    fn tail_lines_defaults_and_rejects_out_of_range_values() {
+        assert_eq!(parse_tail_lines(None), Ok(DEFAULT_TAIL_LINES));
+        assert_eq!(parse_tail_lines(Some("lines=50")), Ok(50));
+        assert!(parse_tail_lines(Some("lines=0")).is_err());
+        assert!(parse_tail_lines(Some("lines=abc")).is_err());
+        assert!(parse_tail_lines(Some("lines=1000000")).is_err());
+    }
+
+    #[test]
+    fn forwarder_drops_events_while_client_stalls() {
+        let (mut forwarder, mut receiver) = OutputForwarder::new(2);
+
+        assert_eq!(forwarder.offer(1, b"one"), Forwarded::Sent);
+        assert_eq!(forwarder.offer(2, b"two"), Forwarded::Sent);
+        assert_eq!(forwarder.offer(3, b"three"), Forwarded::Dropped);
+        assert_eq!(forwarder.offer(4, b"four"), Forwarded::Dropped);
+
+        let first = event_json(&receiver.try_recv().unwrap());
+        assert_eq!(
+            (first["seq"].as_u64(), first["dropped"].as_u64()),
+            (Some(1), Some(0))
+        );
+        assert_eq!(first["data"], "one");
+        receiver.try_recv().unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        assert_eq!(forwarder.offer(5, b"five"), Forwarded::Sent);
+        let resumed = event_json(&receiver.try_recv().unwrap());
+        assert_eq!(resumed["seq"].as_u64(), Some(5));
+        assert_eq!(resumed["dropped"].as_u64(), Some(2));
+
+        assert_eq!(forwarder.offer(6, b"six"), Forwarded::Sent);
+        let next = event_json(&receiver.try_recv().unwrap());
+        assert_eq!(next["dropped"].as_u64(), Some(0));
+
+        drop(receiver);
+        assert!(forwarder.is_closed());
+        assert_eq!(forwarder.offer(7, b"seven"), Forwarded::Closed);
+    }
+
+    #[tokio::test]
+    async fn tail_reads_legacy_terminal_of_session() {
+        let manager = TerminalManager::new();
+        let session_name = format!("tail-{}", std::process::id());
+        let terminal_id = legacy_terminal_id_for_session_top(&session_name);
+        manager
+            .create_terminal_with_app(
+                terminal_id.clone(),
+                "/tmp".to_string(),
+                "sh".to_string(),
+                vec![
+                    "-c".to_string(),
+                    "printf 'one\\ntwo\\nthree\\n'; sleep 5".to_string(),
+                ],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let mut tail = None;
+        for _ in 0..500 {
+            let current = session_terminal_tail(&manager, &session_name, 2)
+                .await
+                .expect("terminal resolves");
+            if current.text.contains("three") {
+                tail = Some(current);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let tail = tail.expect("output rendered");
+        assert_eq!(tail.terminal_id, terminal_id);
+        assert_eq!(tail.text, "two\nthree");
+
+        let missing = session_terminal_tail(&manager, "no-such-session", 10)
+            .await
+            .expect_err("unknown session");
+        assert_eq!(missing.0, StatusCode::NOT_FOUND);
+
+        let _ = manager.close_terminal(terminal_id).await;
+    }
+}
@@ -1,5 +1,7 @@
 use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
 use log::{debug, info, warn};
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::io::ErrorKind;
@@ -7,7 +9,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::domains::sessions::db_sessions::SessionMethods;
+use crate::domains::sessions::entity::{EnrichedSession, SessionState, SessionStatus};
+use crate::domains::sessions::relocation::database_has_records;
+use crate::domains::sessions::terminals::attach_terminal_details;
 use crate::domains::terminal::TerminalManager;
+use crate::infrastructure::database::Database;
 use crate::schaltwerk_core::SchaltwerkCore;
 
 fn canonicalize_project_path(path: &Path) -> Result<PathBuf> {
@@ -39,6 +46,19 @@ fn strip_extended_path_prefix(path: PathBuf) -> PathBuf {
     path
 }
 
+/// Session counts of a recent project, read from its database without opening it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectSessionSummary {
+    pub path: String,
+    pub name: String,
+    /// The project directory is gone or its database could not be read.
+    pub unavailable: bool,
+    pub running_sessions: usize,
+    pub reviewed_sessions: usize,
+    pub specs: usize,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
 /// Represents a single project with its own terminals and sessions
 pub struct Project {
     pub path: PathBuf,
@@ -48,7 +68,16 @@ pub struct Project {
 
 impl Project {
     pub fn new(path: PathBuf) -> Result<Self> {
-        info!("Creating new project for path: {}", path.display());
+        Self::open(path, false)
+    }
+
+    /// Opens the project. A read-only project uses an existing database without write
+    /// access and does not re-enable terminal recordings.
+    pub fn open(path: PathBuf, read_only: bool) -> Result<Self> {
+        info!(
+            "Creating new project for path: {} (read-only: {read_only})",
+            path.display()
+        );
 
         // Each project gets its own terminal manager
         let terminal_manager = Arc::new(TerminalManager::new());
@@ -56,17 +85,20 @@ impl Project {
         // Get the global app data directory for project databases
         let db_path = Self::get_project_db_path(&path)?;
 
-        // Create project data directory if it doesn't exist
-        if let Some(parent) = db_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
         info!("Using database at: {}", db_path.display());
 
-        let schaltwerk_core = Arc::new(RwLock::new(SchaltwerkCore::new_with_repo_path(
-            Some(db_path),
-            path.clone(),
-        )?));
+        let core = if read_only {
+            SchaltwerkCore::new_read_only(db_path, path.clone())?
+        } else {
+            // Create project data directory if it doesn't exist
+            if let Some(parent) = db_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let core = SchaltwerkCore::new_with_repo_path(Some(db_path), path.clone())?;
+            Self::restore_terminal_recordings(&core, &terminal_manager);
+            core
+        };
+        let schaltwerk_core = Arc::new(RwLock::new(core));
 
         Ok(Self {
             path,
@@ -75,15 +107,33 @@ impl Project {
         })
     }
 
+    /// Re-enables terminal recording for sessions that had it turned on.
+    fn restore_terminal_recordings(core: &SchaltwerkCore, terminal_manager: &TerminalManager) {
+        let manager = core.session_manager();
+        match manager.list_recording_sessions() {
+            Ok(names) => {
+                for name in names {
+                    let dir = manager.session_recordings_dir(&name);
+                    terminal_manager.set_session_recording_target(&name, Some(dir));
+                }
+            }
+            Err(e) => warn!("Failed to restore terminal recordings: {e}"),
+        }
+    }
+
     /// Get the database path for a project in the global app data directory
     fn get_project_db_path(project_path: &Path) -> Result<PathBuf> {
+        let canonical_path = canonicalize_project_path(project_path)?;
+        Self::project_db_path_for_canonical(&canonical_path)
+    }
+
+    fn project_db_path_for_canonical(canonical_path: &Path) -> Result<PathBuf> {
         // Get the app data directory (same location as settings)
         let data_dir =
             dirs::data_dir().ok_or_else(|| anyhow!("Failed to get app data directory"))?;
 
         // Create a unique folder name for this project using a hash
         // This ensures uniqueness even for projects with the same name in different locations
-        let canonical_path = canonicalize_project_path(project_path)?;
         let path_str = canonical_path.to_string_lossy();
 
         // Create a hash of the full path
@@ -118,6 +168,62 @@ impl Project {
         Ok(project_data_dir.join("sessions.db"))
     }
 
+    /// Summarizes the sessions of the project at `path` straight from its database, so
+    /// projects other than the active one can be inspected without switching to them.
+    pub fn session_summary(path: &str, name: &str) -> ProjectSessionSummary {
+        let summary = canonicalize_project_path(Path::new(path)).and_then(|canonical| {
+            let db_path = Self::project_db_path_for_canonical(&canonical)?;
+            summarize_project_database(path, name, &canonical, &db_path)
+        });
+        summary.unwrap_or_else(|e| {
+            debug!("Project {path} is unavailable for summary: {e}");
+            ProjectSessionSummary {
+                path: path.to_string(),
+                name: name.to_string(),
+                unavailable: true,
+                ..Default::default()
+            }
+        })
+    }
+
+    /// Moves the database recorded for a repository that used to live at `old_path` into
+    /// the data folder of `new_path`, and returns the database path to use from now on.
+    /// A database already created for the new path is only replaced while it is empty.
+    pub fn adopt_relocated_database(old_path: &Path, new_path: &Path) -> Result<PathBuf> {
+        let new_db_path = Self::get_project_db_path(new_path)?;
+        let old_db_path = Self::project_db_path_for_canonical(old_path)?;
+        let (Some(old_dir), Some(new_dir)) = (old_db_path.parent(), new_db_path.parent()) else {
+            return Ok(new_db_path);
+        };
+
+        if old_dir == new_dir || !old_db_path.exists() {
+            return Ok(new_db_path);
+        }
+
+        if new_db_path.exists() {
+            let existing = Database::new(Some(new_db_path.clone()))?;
+            if database_has_records(&existing)? {
+                return Err(anyhow!(
+                    "Refusing to replace the existing project database at {}",
+                    new_db_path.display()
+                ));
+            }
+            drop(existing);
+            std::fs::remove_dir_all(new_dir)?;
+        }
+
+        if let Some(parent) = new_dir.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(old_dir, new_dir)?;
+        info!(
+            "Moved project database from {} to {}",
+            old_dir.display(),
+            new_dir.display()
+        );
+        Ok(new_db_path)
+    }
+
     #[cfg(test)]
     pub fn new_in_memory(path: PathBuf) -> Result<Self> {
         // Each project gets its own terminal manager
@@ -140,10 +246,50 @@ impl Project {
     }
 }
 
+fn summarize_project_database(
+    path: &str,
+    name: &str,
+    repo_path: &Path,
+    db_path: &Path,
+) -> Result<ProjectSessionSummary> {
+    let mut summary = ProjectSessionSummary {
+        path: path.to_string(),
+        name: name.to_string(),
+        ..Default::default()
+    };
+    // Projects that were never opened have no database yet; don't create one here.
+    if !db_path.exists() {
+        return Ok(summary);
+    }
+
+    let db = Database::new(Some(db_path.to_path_buf()))?;
+    for session in db.list_sessions(repo_path)? {
+        if session.status == SessionStatus::Cancelled {
+            continue;
+        }
+        match session.session_state {
+            SessionState::Spec => summary.specs += 1,
+            SessionState::Processing | SessionState::Running => summary.running_sessions += 1,
+            SessionState::Reviewed => summary.reviewed_sessions += 1,
+        }
+        let activity = session.last_activity.unwrap_or(session.updated_at);
+        if summary.last_activity.is_none_or(|latest| activity > latest) {
+            summary.last_activity = Some(activity);
+        }
+    }
+    Ok(summary)
+}
+
 /// Manages multiple projects and their resources
 pub struct ProjectManager {
     projects: Arc<RwLock<HashMap<PathBuf, Arc<Project>>>>,
     current_project: Arc<RwLock<Option<PathBuf>>>,
+    /// Member repositories of the open multi-root workspace, in definition order.
+    /// Empty when a single project is open.
+    workspace_members: Arc<RwLock<Vec<PathBuf>>>,
+    /// Projects are opened with read-only databases and nothing is written to the
+    /// repositories while opening them.
+    read_only: bool,
 }
 
 impl Default for ProjectManager {
@@ -157,9 +303,23 @@ impl ProjectManager {
         Self {
             projects: Arc::new(RwLock::new(HashMap::new())),
             current_project: Arc::new(RwLock::new(None)),
+            workspace_members: Arc::new(RwLock::new(Vec::new())),
+            read_only: false,
+        }
+    }
+
+    /// A manager for `--read-only` windows; see [`Project::open`].
+    pub fn new_read_only() -> Self {
+        Self {
+            read_only: true,
+            ..Self::new()
         }
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Initialize or switch to a project
     pub async fn switch_to_project(&self, path: PathBuf) -> Result<Arc<Project>> {
         log::info!(
@@ -211,7 +371,7 @@ impl ProjectManager {
                 existing.clone()
             } else {
                 info!("🆕 Creating new project instance for: {}", path.display());
-                let new_project = match Project::new(path.clone()) {
+                let new_project = match Project::open(path.clone(), self.read_only) {
                     Ok(p) => Arc::new(p),
                     Err(e) => {
                         log::error!("❌ Failed to create project: {e}");
@@ -224,7 +384,9 @@ impl ProjectManager {
         };
 
         // Ensure .schaltwerk is excluded from git (outside the projects lock).
-        if let Err(e) = Self::ensure_schaltwerk_excluded(&path) {
+        if !self.read_only
+            && let Err(e) = Self::ensure_schaltwerk_excluded(&path)
+        {
             log::warn!("Failed to ensure .schaltwerk exclusion: {e}");
         }
 
@@ -232,9 +394,108 @@ impl ProjectManager {
         *self.current_project.write().await = Some(path.clone());
         log::info!("✅ Current project set to: {}", path.display());
 
+        // Opening a repository outside the workspace leaves the workspace.
+        let mut members = self.workspace_members.write().await;
+        if !members.contains(&path) {
+            members.clear();
+        }
+        drop(members);
+
         Ok(project)
     }
 
+    /// Open a multi-root workspace: every member repository gets its own loaded project
+    /// and core, and the first member becomes the current project.
+    pub async fn open_workspace(&self, repo_paths: &[PathBuf]) -> Result<Vec<Arc<Project>>> {
+        let create: fn(PathBuf) -> Result<Project> = if self.read_only {
+            |path| Project::open(path, true)
+        } else {
+            Project::new
+        };
+        self.open_workspace_with(repo_paths, create).await
+    }
+
+    async fn open_workspace_with(
+        &self,
+        repo_paths: &[PathBuf],
+        create: fn(PathBuf) -> Result<Project>,
+    ) -> Result<Vec<Arc<Project>>> {
+        if repo_paths.is_empty() {
+            return Err(anyhow!("A workspace needs at least one repository"));
+        }
+
+        let mut members = Vec::with_capacity(repo_paths.len());
+        for path in repo_paths {
+            let path = canonicalize_project_path(path)?;
+            let project = {
+                let mut projects = self.projects.write().await;
+                match projects.get(&path) {
+                    Some(existing) => existing.clone(),
+                    None => {
+                        let project = Arc::new(create(path.clone())?);
+                        projects.insert(path.clone(), project.clone());
+                        project
+                    }
+                }
+            };
+            if !self.read_only
+                && let Err(e) = Self::ensure_schaltwerk_excluded(&path)
+            {
+                log::warn!("Failed to ensure .schaltwerk exclusion: {e}");
+            }
+            if !members.iter().any(|m: &Arc<Project>| m.path == path) {
+                members.push(project);
+            }
+        }
+
+        *self.workspace_members.write().await = members.iter().map(|p| p.path.clone()).collect();
+        *self.current_project.write().await = Some(members[0].path.clone());
+        info!(
+            "Opened workspace with {} repositories; current project {}",
+            members.len(),
+            members[0].path.display()
+        );
+        Ok(members)
+    }
+
+    /// Projects the window manages: the workspace members when a workspace is open,
+    /// otherwise just the current project.
+    pub async fn workspace_projects(&self) -> Vec<Arc<Project>> {
+        let members = self.workspace_members.read().await.clone();
+        if members.is_empty() {
+            return self.current_project().await.into_iter().collect();
+        }
+        let projects = self.projects.read().await;
+        members
+            .iter()
+            .filter_map(|path| projects.get(path).cloned())
+            .collect()
+    }
+
+    /// Sessions of every workspace project, grouped by repository in workspace order,
+    /// each labelled with the name of the repository it belongs to.
+    pub async fn list_workspace_sessions(&self) -> Result<Vec<EnrichedSession>> {
+        let mut sessions = Vec::new();
+        for project in self.workspace_projects().await {
+            let label = project
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| project.path.to_string_lossy().to_string());
+            let manager = project.schaltwerk_core.read().await.session_manager();
+            let mut member_sessions = manager.list_enriched_sessions().map_err(|e| {
+                anyhow!("Failed to list sessions of {}: {e}", project.path.display())
+            })?;
+            let liveness = project.terminal_manager.liveness_snapshot().await;
+            attach_terminal_details(&mut member_sessions, &liveness);
+            for session in &mut member_sessions {
+                session.info.repo_label = Some(label.clone());
+            }
+            sessions.extend(member_sessions);
+        }
+        Ok(sessions)
+    }
+
     /// Ensures .schaltwerk folder is excluded from git using .git/info/exclude
     fn ensure_schaltwerk_excluded(project_path: &Path) -> Result<()> {
         let git_dir = project_path.join(".git");
@@ -449,6 +710,11 @@ impl ProjectManager {
         if current.as_ref() == Some(&key_removed) {
             *current = None;
         }
+        drop(current);
+        self.workspace_members
+            .write()
+            .await
+            .retain(|member| *member != key_removed);
 
         info!("✅ Removed project from manager: {}", key_removed.display());
         Ok(())
@@ -494,7 +760,7 @@ impl ProjectManager {
 
         drop(projects);
 
-        let project = Project::new(canonical_path.clone())?;
+        let project = Project::open(canonical_path.clone(), self.read_only)?;
         let arc_project = Arc::new(project);
 
         let mut projects_write = self.projects.write().await;
@@ -551,7 +817,7 @@ impl ProjectManager {
         drop(projects);
 
         // Load the project but don't switch to it as current
-        let project = Project::new(canonical_path.clone())?;
+        let project = Project::open(canonical_path.clone(), self.read_only)?;
         let arc_project = Arc::new(project);
 
         // Store it in the projects map
@@ -585,11 +851,21 @@ impl ProjectManager {
 
         Ok(project)
     }
+
+    #[cfg(test)]
+    pub async fn open_workspace_in_memory(
+        &self,
+        repo_paths: &[PathBuf],
+    ) -> Result<Vec<Arc<Project>>> {
+        self.open_workspace_with(repo_paths, Project::new_in_memory)
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domains::sessions::entity::Session;
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -751,6 +1027,188 @@ mod tests {
         assert!(projects.get(&path).is_none());
     }
 
+    fn init_git_repo(path: &Path) {
+        std::fs::create_dir_all(path).unwrap();
+        for args in [
+            vec!["init", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["commit", "--allow-empty", "-m", "init"],
+        ] {
+            let status = std::process::Command::new("git")
+                .args(&args)
+                .current_dir(path)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workspace_lists_sessions_across_member_repositories() {
+        let mgr = ProjectManager::new();
+        let tmp = TempDir::new().unwrap();
+        let api = tmp.path().join("api");
+        let web = tmp.path().join("web");
+        init_git_repo(&api);
+        init_git_repo(&web);
+
+        let members = mgr
+            .open_workspace_in_memory(&[api.clone(), web.clone(), api.clone()])
+            .await
+            .unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(Arc::ptr_eq(
+            &members[0],
+            &mgr.current_project().await.unwrap()
+        ));
+
+        for (project, spec) in [(&members[0], "api-spec"), (&members[1], "web-spec")] {
+            let manager = project.schaltwerk_core.read().await.session_manager();
+            manager.create_spec_session(spec, "# spec").unwrap();
+        }
+
+        let sessions = mgr.list_workspace_sessions().await.unwrap();
+        let labelled: Vec<_> = sessions
+            .iter()
+            .map(|s| (s.info.session_id.as_str(), s.info.repo_label.as_deref()))
+            .collect();
+        assert_eq!(
+            labelled,
+            vec![("api-spec", Some("api")), ("web-spec", Some("web"))]
+        );
+
+        // Closing a member drops it from the workspace listing
+        mgr.remove_project(&members[1].path).await.unwrap();
+        let remaining = mgr.list_workspace_sessions().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].info.repo_label.as_deref(), Some("api"));
+    }
+
+    #[tokio::test]
+    async fn test_workspace_projects_fall_back_to_current_project() {
+        let mgr = ProjectManager::new();
+        assert!(mgr.workspace_projects().await.is_empty());
+        assert!(mgr.open_workspace_in_memory(&[]).await.is_err());
+
+        let tmp = TempDir::new().unwrap();
+        let project = mgr
+            .switch_to_project_in_memory(tmp.path().to_path_buf())
+            .await
+            .unwrap();
+        let projects = mgr.workspace_projects().await;
+        assert_eq!(projects.len(), 1);
+        assert!(Arc::ptr_eq(&projects[0], &project));
+    }
+
+    fn seeded_session(repo: &Path, name: &str, state: SessionState) -> Session {
+        Session {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            display_name: None,
+            version_group_id: None,
+            version_number: None,
+            version_group_label: None,
+            epic_id: None,
+            repository_path: repo.to_path_buf(),
+            repository_name: "repo".into(),
+            branch: format!("schaltwerk/{name}"),
+            parent_branch: "main".into(),
+            original_parent_branch: Some("main".into()),
+            worktree_path: repo.join(".schaltwerk").join("worktrees").join(name),
+            status: if state == SessionState::Spec {
+                SessionStatus::Spec
+            } else {
+                SessionStatus::Active
+            },
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_activity: None,
+            initial_prompt: None,
+            ready_to_merge: state == SessionState::Reviewed,
+            original_agent_type: None,
+            original_skip_permissions: None,
+            pending_name_generation: false,
+            was_auto_generated: false,
+            spec_content: None,
+            session_state: state,
+            resume_allowed: true,
+            amp_thread_id: None,
+            pr_number: None,
+            pr_url: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_session_summary_counts_each_project_separately() {
+        let tmp = TempDir::new().unwrap();
+        let alpha = tmp.path().join("alpha");
+        let beta = tmp.path().join("beta");
+        std::fs::create_dir_all(&alpha).unwrap();
+        std::fs::create_dir_all(&beta).unwrap();
+        let alpha_db = tmp.path().join("alpha.db");
+        let beta_db = tmp.path().join("beta.db");
+
+        let db = Database::new(Some(alpha_db.clone())).unwrap();
+        let mut recent = seeded_session(&alpha, "recent", SessionState::Running);
+        let latest = Utc::now() + chrono::Duration::hours(1);
+        recent.last_activity = Some(latest);
+        db.create_session(&recent).unwrap();
+        db.create_session(&seeded_session(&alpha, "busy", SessionState::Processing))
+            .unwrap();
+        db.create_session(&seeded_session(&alpha, "done", SessionState::Reviewed))
+            .unwrap();
+        let mut cancelled = seeded_session(&alpha, "gone", SessionState::Running);
+        cancelled.status = SessionStatus::Cancelled;
+        db.create_session(&cancelled).unwrap();
+        drop(db);
+
+        let db = Database::new(Some(beta_db.clone())).unwrap();
+        for name in ["spec-a", "spec-b"] {
+            db.create_session(&seeded_session(&beta, name, SessionState::Spec))
+                .unwrap();
+        }
+        db.create_session(&seeded_session(&beta, "review", SessionState::Reviewed))
+            .unwrap();
+        drop(db);
+
+        let alpha_summary =
+            summarize_project_database("alpha", "alpha", &alpha, &alpha_db).unwrap();
+        assert!(!alpha_summary.unavailable);
+        assert_eq!(alpha_summary.running_sessions, 2);
+        assert_eq!(alpha_summary.reviewed_sessions, 1);
+        assert_eq!(alpha_summary.specs, 0);
+        assert_eq!(
+            alpha_summary.last_activity.map(|t| t.timestamp()),
+            Some(latest.timestamp())
+        );
+
+        let beta_summary = summarize_project_database("beta", "beta", &beta, &beta_db).unwrap();
+        assert_eq!(beta_summary.running_sessions, 0);
+        assert_eq!(beta_summary.reviewed_sessions, 1);
+        assert_eq!(beta_summary.specs, 2);
+        assert!(beta_summary.last_activity.is_some());
+
+        let never_opened = tmp.path().join("never-opened.db");
+        let empty = summarize_project_database("beta", "beta", &beta, &never_opened).unwrap();
+        assert_eq!(
+            empty.running_sessions + empty.reviewed_sessions + empty.specs,
+            0
+        );
+        assert!(!never_opened.exists());
+    }
+
+    #[test]
+    fn test_session_summary_marks_missing_project_unavailable() {
+        let tmp = TempDir::new().unwrap();
+        let missing = tmp.path().join("deleted-project");
+        let summary = Project::session_summary(&missing.to_string_lossy(), "deleted-project");
+        assert!(summary.unavailable);
+        assert_eq!(summary.name, "deleted-project");
+        assert_eq!(summary.last_activity, None);
+    }
+
     #[tokio::test]
     async fn test_remove_project_allows_fresh_reinitialization() {
         let mgr = ProjectManager::new();
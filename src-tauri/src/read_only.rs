@@ -0,0 +1,281 @@
+use crate::errors::SchaltError;
+use tauri::Runtime;
+use tauri::ipc::Invoke;
+
+/// Commands a `--read-only` window may run: they only read sessions, diffs, git history
+/// and settings, or touch nothing but in-memory UI state. Everything else is rejected
+/// with [`SchaltError::ReadOnlyMode`], so commands added later stay blocked until they
+/// are listed here.
+const READ_ONLY_COMMANDS: &[&str] = &[
+    // App and environment
+    "get_development_info",
+    "get_current_directory",
+    "path_exists",
+    "directory_exists",
+    "is_git_repository",
+    "get_environment_variable",
+    "get_app_version",
+    "get_orphaned_processes",
+    "get_recent_logs",
+    "set_log_level",
+    "export_logs_to_file",
+    "clipboard_write_text",
+    "open_external_url",
+    "open_in_vscode",
+    "open_in_app",
+    "list_available_open_apps",
+    "get_default_open_app",
+    "report_attention_snapshot",
+    "schaltwerk_core_log_frontend_message",
+    "check_folder_access",
+    "get_permission_diagnostics",
+    // Projects
+    "initialize_project",
+    "close_project",
+    "get_active_project_path",
+    "get_recent_projects",
+    "list_all_projects_summary",
+    "list_workspaces",
+    "open_workspace",
+    "get_project_default_branch",
+    "get_project_default_base_branch",
+    "list_project_branches",
+    "repository_is_empty",
+    "list_database_backups",
+    // Sessions and specs
+    "schaltwerk_core_list_sessions",
+    "schaltwerk_core_list_sessions_awaiting_approval",
+    "schaltwerk_core_list_sessions_by_state",
+    "schaltwerk_core_list_enriched_sessions",
+    "schaltwerk_core_list_enriched_sessions_sorted",
+    "schaltwerk_core_list_workspace_sessions",
+    "schaltwerk_core_list_epics",
+    "schaltwerk_core_list_session_groups",
+    "schaltwerk_core_list_session_snapshots",
+    "schaltwerk_core_list_scheduled_specs",
+    "schaltwerk_core_list_archived_specs",
+    "schaltwerk_core_list_project_files",
+    "schaltwerk_core_get_session",
+    "schaltwerk_core_get_spec",
+    "schaltwerk_core_get_session_agent_content",
+    "schaltwerk_core_get_session_commits",
+    "schaltwerk_core_get_session_history",
+    "schaltwerk_core_get_session_launch_overrides",
+    "schaltwerk_core_get_session_extra_cli_args",
+    "schaltwerk_core_get_session_env_vars",
+    "schaltwerk_core_get_session_launch_snapshot",
+    "schaltwerk_core_preview_session_launch_command",
+    "schaltwerk_core_get_terminal_recordings",
+    "schaltwerk_core_get_agent_stats",
+    "schaltwerk_core_get_database_info",
+    "schaltwerk_core_get_archive_max_entries",
+    "schaltwerk_core_has_uncommitted_changes",
+    "schaltwerk_core_get_merge_preview",
+    "schaltwerk_core_get_merge_preview_with_worktree",
+    "schaltwerk_core_preview_update_from_parent",
+    "schaltwerk_core_preview_discard_file_in_session",
+    "schaltwerk_core_diff_version_group",
+    "schaltwerk_core_get_version_group_comparison",
+    "schaltwerk_core_get_skip_permissions",
+    "schaltwerk_core_get_orchestrator_skip_permissions",
+    "schaltwerk_core_get_agent_type",
+    "schaltwerk_core_get_orchestrator_agent_type",
+    "schaltwerk_core_get_font_sizes",
+    "schaltwerk_core_get_theme",
+    "schaltwerk_core_get_language",
+    // Diffs and git history
+    "get_changed_files_from_main",
+    "has_remote_tracking_branch",
+    "get_orchestrator_working_changes",
+    "get_file_diff_from_main",
+    "compare_sessions",
+    "get_current_branch_name",
+    "get_base_branch_name",
+    "get_commit_comparison_info",
+    "compute_commit_unified_diff",
+    "compute_unified_diff_backend",
+    "compute_split_diff_backend",
+    "get_git_history",
+    "get_commit_files",
+    "get_commit_file_contents",
+    "get_git_graph_history",
+    "get_git_graph_commit_files",
+    "read_project_file",
+    "start_file_watcher",
+    "stop_file_watcher",
+    "is_file_watcher_active",
+    "get_active_file_watchers",
+    // Terminal output of a running instance is not shared, but the UI polls for it
+    "terminal_exists",
+    "terminals_exist_bulk",
+    "get_terminal_buffer",
+    "get_terminal_buffer_text",
+    "get_terminal_activity_status",
+    "get_all_terminal_activity",
+    // Settings
+    "get_agent_env_vars",
+    "get_agent_cli_args",
+    "get_agent_preferences",
+    "get_agent_initial_command",
+    "get_agent_command_prefix",
+    "get_agent_binary_config",
+    "get_all_agent_binary_configs",
+    "get_effective_agent_binary_path",
+    "agent_config_health",
+    "get_terminal_ui_preferences",
+    "get_terminal_settings",
+    "list_installed_fonts",
+    "get_diff_view_preferences",
+    "get_session_preferences",
+    "get_auto_update_enabled",
+    "get_dev_error_toasts_enabled",
+    "get_webhook_request_log_enabled",
+    "get_webhook_request_log",
+    "get_last_project_parent_directory",
+    "get_keyboard_shortcuts",
+    "get_tutorial_completed",
+    "get_project_settings",
+    "get_project_sessions_settings",
+    "get_project_environment_variables",
+    "get_project_merge_preferences",
+    "get_project_merge_commit_trailers",
+    "get_project_skip_permissions_policy",
+    "get_project_worktree_root",
+    "get_project_branch_collision_strategy",
+    "get_project_database_backups_enabled",
+    "get_project_keep_recordings",
+    "get_project_spec_file_mirror",
+    "get_project_update_from_parent_on_start",
+    "get_project_prompt_templates",
+    "get_project_action_buttons",
+    "get_project_run_script",
+    "get_global_keep_awake_state",
+    "get_global_keep_awake_max_minutes",
+    "get_global_keep_awake_auto_mode",
+    "get_mcp_status",
+    "get_amp_mcp_servers",
+    // GitHub lookups
+    "github_get_status",
+    "github_search_issues",
+    "github_get_issue_details",
+    "github_search_prs",
+    "github_get_pr_details",
+    "github_get_pr_review_comments",
+    "github_get_pr_feedback",
+    "github_get_lookup_cache_stats",
+];
+
+pub fn check_command(command: &str) -> Result<(), SchaltError> {
+    if READ_ONLY_COMMANDS.contains(&command) {
+        Ok(())
+    } else {
+        Err(SchaltError::ReadOnlyMode {
+            command: command.to_string(),
+        })
+    }
+}
+
+/// Wraps the app's invoke handler so that, in read-only mode, commands outside
+/// [`READ_ONLY_COMMANDS`] are rejected before they run.
+pub fn guard_invoke_handler<R: Runtime>(
+    read_only: bool,
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if read_only && let Err(error) = check_command(invoke.message.command()) {
+            log::warn!("Rejected in read-only mode: {error}");
+            invoke.resolver.reject(error);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{create_terminal, get_app_version};
+    use tauri::test::{
+        INVOKE_KEY, MockRuntime, get_ipc_response, mock_builder, mock_context, noop_assets,
+    };
+    use tauri::{App, WebviewWindow, WebviewWindowBuilder};
+
+    fn app(read_only: bool) -> (App<MockRuntime>, WebviewWindow<MockRuntime>) {
+        let app = mock_builder()
+            .invoke_handler(guard_invoke_handler(
+                read_only,
+                tauri::generate_handler![get_app_version, create_terminal],
+            ))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        (app, webview)
+    }
+
+    fn invoke(
+        webview: &WebviewWindow<MockRuntime>,
+        cmd: &str,
+    ) -> Result<tauri::ipc::InvokeResponseBody, serde_json::Value> {
+        get_ipc_response(
+            webview,
+            tauri::webview::InvokeRequest {
+                cmd: cmd.into(),
+                callback: tauri::ipc::CallbackFn(0),
+                error: tauri::ipc::CallbackFn(1),
+                url: "http://tauri.localhost".parse().unwrap(),
+                body: tauri::ipc::InvokeBody::default(),
+                headers: Default::default(),
+                invoke_key: INVOKE_KEY.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn read_only_mode_rejects_mutating_commands_and_runs_reads() {
+        let (_app, webview) = app(true);
+
+        let version = invoke(&webview, "get_app_version").unwrap();
+        assert_eq!(
+            version.deserialize::<String>().unwrap(),
+            env!("CARGO_PKG_VERSION")
+        );
+
+        let rejected = invoke(&webview, "create_terminal").unwrap_err();
+        assert_eq!(
+            rejected,
+            serde_json::json!({
+                "type": "ReadOnlyMode",
+                "data": { "command": "create_terminal" }
+            })
+        );
+    }
+
+    #[test]
+    fn commands_pass_through_outside_read_only_mode() {
+        let (_app, webview) = app(false);
+
+        let error = invoke(&webview, "create_terminal").unwrap_err();
+        assert_ne!(error["type"], "ReadOnlyMode");
+    }
+
+    #[test]
+    fn allowlist_covers_reads_and_excludes_writes() {
+        for command in [
+            "schaltwerk_core_list_enriched_sessions",
+            "compute_unified_diff_backend",
+            "get_git_history",
+        ] {
+            assert!(check_command(command).is_ok(), "{command}");
+        }
+        for command in [
+            "schaltwerk_core_create_session",
+            "schaltwerk_core_merge_session_to_main",
+            "create_terminal",
+            "schaltwerk_core_set_theme",
+        ] {
+            assert!(check_command(command).is_err(), "{command}");
+        }
+    }
+}
@@ -49,6 +49,17 @@ impl SchaltwerkCore {
         Ok(Self { db, repo_path })
     }
 
+    /// Opens the project database without write access; see [`Database::open_read_only`].
+    pub fn new_read_only(db_path: PathBuf, repo_path: PathBuf) -> Result<Self> {
+        log::info!(
+            "Creating read-only SchaltwerkCore for repo path: {}",
+            repo_path.display()
+        );
+        let db = Database::open_read_only(db_path)?;
+
+        Ok(Self { db, repo_path })
+    }
+
     pub fn session_manager(&self) -> SessionManager {
         SessionManager::new(self.db.clone(), self.repo_path.clone())
     }
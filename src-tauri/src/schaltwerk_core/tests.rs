@@ -173,6 +173,512 @@ fn test_create_session_with_custom_branch_prefix() {
     assert!(branches.contains("custom/prefixed-feature"));
 }
 
+#[test]
+fn test_create_session_from_template_renders_initial_prompt() {
+    use crate::domains::sessions::service::SessionCreationParams;
+    use crate::infrastructure::database::db_project_config::PromptTemplate;
+    use std::collections::HashMap;
+
+    let env = TestEnvironment::new().unwrap();
+    let db = env.get_database().unwrap();
+    db.set_project_branch_prefix(&env.repo_path, "team")
+        .unwrap();
+    db.set_project_prompt_templates(
+        &env.repo_path,
+        &[PromptTemplate {
+            name: "ticket".to_string(),
+            content: "Fix {{ticket}} on {{branch}} from {{base_branch}} for {{owner}}".to_string(),
+            defaults: HashMap::from([("owner".to_string(), "qa".to_string())]),
+        }],
+    )
+    .unwrap();
+    let manager = SessionManager::new(db.clone(), env.repo_path.clone());
+    let params = || SessionCreationParams {
+        name: "from-template",
+        prompt: None,
+        base_branch: None,
+        custom_branch: None,
+        use_existing_branch: false,
+        sync_with_origin: false,
+        was_auto_generated: false,
+        version_group_id: None,
+        version_number: None,
+        version_group_label: None,
+        epic_id: None,
+        agent_type: None,
+        skip_permissions: None,
+        pr_number: None,
+    };
+
+    let err = manager
+        .create_session_from_template("ticket", &HashMap::new(), params())
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("No value for template placeholder(s): ticket"));
+    assert!(manager.list_sessions().unwrap().is_empty());
+
+    let vars = HashMap::from([("ticket".to_string(), "ABC-1".to_string())]);
+    let session = manager
+        .create_session_from_template("ticket", &vars, params())
+        .unwrap();
+    assert_eq!(
+        session.initial_prompt,
+        Some(format!(
+            "Fix ABC-1 on team/from-template from {} for qa",
+            session.parent_branch
+        ))
+    );
+
+    assert!(
+        manager
+            .create_session_from_template("unknown", &vars, params())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_session_launch_overrides_round_trip_and_clear() {
+    use crate::domains::sessions::entity::{SessionLaunchOverrides, SessionLaunchSnapshot};
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    manager.create_session("tuned", None, None).unwrap();
+
+    assert_eq!(
+        manager.get_session_launch_overrides("tuned").unwrap(),
+        SessionLaunchOverrides::default()
+    );
+    assert!(
+        manager
+            .get_session_launch_snapshot("tuned")
+            .unwrap()
+            .is_none()
+    );
+
+    let saved = manager
+        .set_session_launch_overrides(
+            "tuned",
+            SessionLaunchOverrides {
+                model: Some(" opus ".to_string()),
+                extra_flags: Some("  ".to_string()),
+            },
+        )
+        .unwrap();
+    let expected = SessionLaunchOverrides {
+        model: Some("opus".to_string()),
+        extra_flags: None,
+    };
+    assert_eq!(saved, expected);
+    assert_eq!(
+        manager.get_session_launch_overrides("tuned").unwrap(),
+        expected
+    );
+
+    let snapshot = SessionLaunchSnapshot {
+        agent_type: "claude".to_string(),
+        command: "claude --model opus".to_string(),
+        overrides: expected.clone(),
+        force_restart: false,
+        launched_at: chrono::Utc::now(),
+    };
+    manager.record_session_launch("tuned", &snapshot).unwrap();
+    assert_eq!(
+        manager.get_session_launch_snapshot("tuned").unwrap(),
+        Some(snapshot)
+    );
+
+    manager
+        .set_session_launch_overrides("tuned", SessionLaunchOverrides::default())
+        .unwrap();
+    assert_eq!(
+        manager.get_session_launch_overrides("tuned").unwrap(),
+        SessionLaunchOverrides::default()
+    );
+}
+
+#[test]
+fn test_session_extra_cli_args_validated_and_cleared() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    manager.create_session("tuned", None, None).unwrap();
+    assert!(
+        manager
+            .get_session_extra_cli_args("tuned")
+            .unwrap()
+            .is_none()
+    );
+
+    let saved = manager
+        .set_session_extra_cli_args("tuned", Some(" --add-dir {repo_root} "))
+        .unwrap();
+    assert_eq!(saved.as_deref(), Some("--add-dir {repo_root}"));
+    assert_eq!(
+        manager
+            .get_session_extra_cli_args("tuned")
+            .unwrap()
+            .as_deref(),
+        Some("--add-dir {repo_root}")
+    );
+
+    assert!(
+        manager
+            .set_session_extra_cli_args("tuned", Some("--dir {workspace}"))
+            .is_err()
+    );
+    assert!(
+        manager
+            .set_session_extra_cli_args("tuned", Some("--name 'unterminated"))
+            .is_err()
+    );
+    assert_eq!(
+        manager
+            .get_session_extra_cli_args("tuned")
+            .unwrap()
+            .as_deref(),
+        Some("--add-dir {repo_root}")
+    );
+
+    manager
+        .set_session_extra_cli_args("tuned", Some("  "))
+        .unwrap();
+    assert!(
+        manager
+            .get_session_extra_cli_args("tuned")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_session_env_overrides_round_trip_and_clear() {
+    use crate::domains::agents::EnvOverrides;
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    manager.create_session("tuned", None, None).unwrap();
+    assert!(
+        manager
+            .get_session_env_overrides("tuned")
+            .unwrap()
+            .is_empty()
+    );
+
+    let saved = manager
+        .set_session_env_overrides(
+            "tuned",
+            EnvOverrides::from([
+                (" RUST_LOG ".to_string(), Some("debug".to_string())),
+                ("API_KEY".to_string(), None),
+            ]),
+        )
+        .unwrap();
+    let expected = EnvOverrides::from([
+        ("API_KEY".to_string(), None),
+        ("RUST_LOG".to_string(), Some("debug".to_string())),
+    ]);
+    assert_eq!(saved, expected);
+    assert_eq!(
+        manager.get_session_env_overrides("tuned").unwrap(),
+        expected
+    );
+
+    assert!(
+        manager
+            .set_session_env_overrides(
+                "tuned",
+                EnvOverrides::from([("A=B".to_string(), Some("1".to_string()))]),
+            )
+            .is_err()
+    );
+    assert_eq!(
+        manager.get_session_env_overrides("tuned").unwrap(),
+        expected
+    );
+
+    manager
+        .set_session_env_overrides("tuned", EnvOverrides::new())
+        .unwrap();
+    assert!(
+        manager
+            .get_session_env_overrides("tuned")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_reset_refuses_session_with_stash_unless_discarded() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("stashy", None, None).unwrap();
+    let file = session.worktree_path.join("wip.txt");
+    std::fs::write(&file, "work in progress").unwrap();
+
+    manager
+        .stash_session_changes("stashy", Some("wip"))
+        .unwrap();
+    assert!(!file.exists());
+    assert!(manager.stash_session_changes("stashy", None).is_err());
+
+    let err = manager
+        .reset_session_worktree("stashy", false)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("stashed changes"), "unexpected error: {err}");
+
+    manager.reset_session_worktree("stashy", true).unwrap();
+    assert!(manager.unstash_session_changes("stashy").is_err());
+    assert!(!file.exists());
+}
+
+#[cfg(test)]
+fn commit_all(dir: &std::path::Path, message: &str) -> git2::Oid {
+    for args in [vec!["add", "-A"], vec!["commit", "-m", message]] {
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+    git2::Repository::open(dir)
+        .unwrap()
+        .head()
+        .unwrap()
+        .target()
+        .unwrap()
+}
+
+#[test]
+fn test_reset_session_to_ancestor_commit() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("rewind", None, None).unwrap();
+
+    std::fs::write(session.worktree_path.join("first.txt"), "first").unwrap();
+    let first = commit_all(&session.worktree_path, "first");
+    std::fs::write(session.worktree_path.join("second.txt"), "second").unwrap();
+    commit_all(&session.worktree_path, "second");
+    std::fs::write(session.worktree_path.join("scratch.txt"), "untracked").unwrap();
+
+    manager
+        .reset_session_to_commit("rewind", &first.to_string())
+        .unwrap();
+
+    let repo = git2::Repository::open(&session.worktree_path).unwrap();
+    let head = repo.head().unwrap();
+    assert_eq!(head.target(), Some(first));
+    assert_eq!(
+        head.name(),
+        Some(format!("refs/heads/{}", session.branch).as_str())
+    );
+    assert!(session.worktree_path.join("first.txt").exists());
+    assert!(!session.worktree_path.join("second.txt").exists());
+    assert!(!session.worktree_path.join("scratch.txt").exists());
+}
+
+#[test]
+fn test_reset_session_to_commit_rejects_unrelated_commit() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("rewind", None, None).unwrap();
+
+    std::fs::write(session.worktree_path.join("work.txt"), "session work").unwrap();
+    let session_head = commit_all(&session.worktree_path, "session work");
+    std::fs::write(env.repo_path.join("main.txt"), "main work").unwrap();
+    let unrelated = commit_all(&env.repo_path, "main work");
+
+    let err = manager
+        .reset_session_to_commit("rewind", &unrelated.to_string())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not an ancestor"), "unexpected error: {err}");
+
+    let repo = git2::Repository::open(&session.worktree_path).unwrap();
+    assert_eq!(repo.head().unwrap().target(), Some(session_head));
+    assert!(session.worktree_path.join("work.txt").exists());
+}
+
+#[test]
+fn test_preview_discard_file_shows_session_changes() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("preview", None, None).unwrap();
+
+    std::fs::write(session.worktree_path.join("work.txt"), "committed\n").unwrap();
+    commit_all(&session.worktree_path, "session work");
+    std::fs::write(session.worktree_path.join("work.txt"), "edited\n").unwrap();
+
+    let preview = manager.preview_discard_file("preview", "work.txt").unwrap();
+    assert!(
+        preview.contains("+edited\n"),
+        "unexpected preview: {preview}"
+    );
+    assert!(
+        !preview.contains("-committed"),
+        "unexpected preview: {preview}"
+    );
+
+    manager
+        .discard_file_in_session("preview", "work.txt")
+        .unwrap();
+    assert_eq!(
+        manager.preview_discard_file("preview", "work.txt").unwrap(),
+        ""
+    );
+
+    let err = manager
+        .preview_discard_file("preview", ".schaltwerk/state.json")
+        .unwrap_err();
+    assert!(err.to_string().contains("Refusing"));
+}
+
+#[test]
+fn test_discard_files_in_session_reports_each_path() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("bulk", None, None).unwrap();
+    let worktree = &session.worktree_path;
+
+    std::fs::write(worktree.join("README.md"), "edited").unwrap();
+    std::fs::write(worktree.join("scratch.txt"), "new file").unwrap();
+    std::fs::create_dir_all(worktree.join(".schaltwerk")).unwrap();
+    std::fs::write(worktree.join(".schaltwerk/notes.md"), "internal").unwrap();
+    let outside = worktree.parent().unwrap().join("outside.txt");
+    std::fs::write(&outside, "outside").unwrap();
+
+    let paths = [
+        "README.md".to_string(),
+        ".schaltwerk/notes.md".to_string(),
+        "../outside.txt".to_string(),
+        "scratch.txt".to_string(),
+    ];
+    let outcomes = manager.discard_files_in_session("bulk", &paths).unwrap();
+
+    let summary: Vec<(&str, bool)> = outcomes
+        .iter()
+        .map(|(path, result)| (path.as_str(), result.is_ok()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("README.md", true),
+            (".schaltwerk/notes.md", false),
+            ("../outside.txt", false),
+            ("scratch.txt", true),
+        ]
+    );
+    assert!(
+        outcomes[1]
+            .1
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("Refusing")
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(worktree.join("README.md")).unwrap(),
+        "# Test Repository"
+    );
+    assert!(!worktree.join("scratch.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(worktree.join(".schaltwerk/notes.md")).unwrap(),
+        "internal"
+    );
+    assert!(outside.exists());
+}
+
+#[cfg(test)]
+fn delete_branch_behind_worktree(env: &TestEnvironment, branch: &str) {
+    let output = Command::new("git")
+        .args(["update-ref", "-d", &format!("refs/heads/{branch}")])
+        .current_dir(&env.repo_path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!git::branch_exists(&env.repo_path, branch).unwrap());
+}
+
+#[test]
+fn test_recreate_session_branch_after_external_deletion() {
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("orphaned", None, None).unwrap();
+
+    std::fs::write(session.worktree_path.join("done.txt"), "committed").unwrap();
+    for args in [
+        vec!["add", "done.txt"],
+        vec!["commit", "-m", "session work"],
+    ] {
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(&session.worktree_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+    let session_commit = git2::Repository::open(&session.worktree_path)
+        .unwrap()
+        .head()
+        .unwrap()
+        .target()
+        .unwrap();
+    std::fs::write(session.worktree_path.join("wip.txt"), "uncommitted").unwrap();
+
+    delete_branch_behind_worktree(&env, &session.branch);
+
+    let enriched = manager.list_enriched_sessions().unwrap();
+    let info = &enriched
+        .iter()
+        .find(|s| s.info.session_id == "orphaned")
+        .unwrap()
+        .info;
+    assert!(info.branch_missing);
+
+    manager.recreate_session_branch("orphaned").unwrap();
+
+    let repo = git2::Repository::open(&env.repo_path).unwrap();
+    let branch = repo
+        .find_branch(&session.branch, git2::BranchType::Local)
+        .unwrap();
+    assert_eq!(branch.get().target(), Some(session_commit));
+    assert!(session.worktree_path.join("wip.txt").exists());
+    assert!(manager.recreate_session_branch("orphaned").is_err());
+
+    let enriched = manager.list_enriched_sessions().unwrap();
+    let info = &enriched
+        .iter()
+        .find(|s| s.info.session_id == "orphaned")
+        .unwrap()
+        .info;
+    assert!(!info.branch_missing);
+    assert!(info.diff_stats.is_some());
+}
+
+#[tokio::test]
+async fn test_cancel_session_with_externally_deleted_branch() {
+    use crate::schaltwerk_core::{CancellationConfig, StandaloneCancellationCoordinator};
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let session = manager.create_session("branchless", None, None).unwrap();
+
+    delete_branch_behind_worktree(&env, &session.branch);
+    let result = StandaloneCancellationCoordinator::new(env.repo_path.clone(), session.clone())
+        .cancel_filesystem_only(CancellationConfig::default())
+        .await
+        .unwrap();
+
+    assert!(
+        result.errors.is_empty(),
+        "unexpected errors: {:?}",
+        result.errors
+    );
+    assert!(result.worktree_removed);
+    assert!(!result.branch_deleted);
+    assert!(!session.worktree_path.exists());
+}
+
 #[test]
 fn test_create_multiple_sessions() {
     let env = TestEnvironment::new().unwrap();
@@ -422,6 +928,73 @@ fn test_get_session_task_content_returns_empty_after_spec_archive() {
     assert!(after_archive.1.is_none());
 }
 
+#[test]
+fn test_session_agent_content_not_modified_round_trip() {
+    use crate::domains::sessions::entity::SessionAgentContent;
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let spec = manager
+        .create_spec_session("etag-spec", "Initial plan")
+        .unwrap();
+
+    let first = manager.get_session_agent_content(&spec.name, None).unwrap();
+    assert!(matches!(
+        &first,
+        SessionAgentContent::Modified { spec_content: Some(content), .. } if content == "Initial plan"
+    ));
+    let etag = first.etag().to_string();
+
+    assert_eq!(
+        manager
+            .get_session_agent_content(&spec.name, Some(&etag))
+            .unwrap(),
+        SessionAgentContent::NotModified { etag: etag.clone() }
+    );
+    assert!(matches!(
+        manager
+            .get_session_agent_content(&spec.name, Some("stale"))
+            .unwrap(),
+        SessionAgentContent::Modified { .. }
+    ));
+}
+
+#[test]
+fn test_spec_edits_change_agent_content_etag() {
+    use crate::domains::sessions::cache::spec_content_etag;
+    use crate::domains::sessions::entity::SessionAgentContent;
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let spec = manager
+        .create_spec_session("etag-edit", "Initial plan")
+        .unwrap();
+    let initial = spec_content_etag(&(Some("Initial plan".to_string()), None));
+
+    manager
+        .update_spec_content(&spec.name, "Revised plan")
+        .unwrap();
+    let updated = spec_content_etag(&(Some("Revised plan".to_string()), None));
+    assert_ne!(initial, updated);
+    assert_eq!(
+        manager
+            .get_session_agent_content(&spec.name, Some(&initial))
+            .unwrap(),
+        SessionAgentContent::Modified {
+            spec_content: Some("Revised plan".to_string()),
+            initial_prompt: None,
+            etag: updated.clone(),
+        }
+    );
+
+    manager.append_spec_content(&spec.name, "\nMore").unwrap();
+    let appended = manager
+        .get_session_agent_content(&spec.name, Some(&updated))
+        .unwrap();
+    assert!(matches!(appended, SessionAgentContent::Modified { .. }));
+    assert_ne!(appended.etag(), updated);
+}
+
 #[test]
 fn test_cancel_session() {
     let env = TestEnvironment::new().unwrap();
@@ -828,8 +1401,11 @@ fn test_cleanup_orphaned_worktrees() {
             .collect::<Vec<_>>()
     );
 
-    // Run cleanup
-    manager.cleanup_orphaned_worktrees().unwrap();
+    // Run cleanup on the orphan and, defensively, on the live session's worktree
+    let removed = manager
+        .cleanup_orphaned_worktrees(&[orphan_path.clone(), session1.worktree_path.clone()])
+        .unwrap();
+    assert_eq!(removed.len(), 1, "only the orphan should be removed");
 
     // Debug: Check what worktrees exist after cleanup
     let worktrees = git::list_worktrees(&env.repo_path).unwrap();
@@ -843,6 +1419,123 @@ fn test_cleanup_orphaned_worktrees() {
     );
 }
 
+#[test]
+fn test_custom_worktree_root_holds_session_worktrees() {
+    let env = TestEnvironment::new().unwrap();
+    let root_dir = TempDir::new().unwrap();
+    let worktree_root = root_dir.path().join("fast-disk");
+    env.get_database()
+        .unwrap()
+        .set_project_worktree_root(&env.repo_path, Some(&worktree_root))
+        .unwrap();
+    let manager = env.get_session_manager().unwrap();
+
+    let session = manager.create_session("remote-repo", None, None).unwrap();
+
+    assert_eq!(session.worktree_path, worktree_root.join("remote-repo"));
+    assert!(session.worktree_path.join(".git").exists());
+    assert!(
+        !env.repo_path
+            .join(".schaltwerk")
+            .join("worktrees")
+            .join("remote-repo")
+            .exists()
+    );
+    let classified = manager.classify_worktrees().unwrap();
+    assert_eq!(classified.len(), 1);
+    assert_eq!(classified[0].session_name.as_deref(), Some("remote-repo"));
+
+    manager.cancel_session("remote-repo").unwrap();
+    assert!(!session.worktree_path.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unwritable_worktree_root_fails_session_creation() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let env = TestEnvironment::new().unwrap();
+    let root_dir = TempDir::new().unwrap();
+    let worktree_root = root_dir.path().join("read-only");
+    std::fs::create_dir_all(&worktree_root).unwrap();
+    std::fs::set_permissions(&worktree_root, std::fs::Permissions::from_mode(0o555)).unwrap();
+    if std::fs::write(worktree_root.join("probe"), b"").is_ok() {
+        // Running as root; permission bits are not enforced.
+        return;
+    }
+    env.get_database()
+        .unwrap()
+        .set_project_worktree_root(&env.repo_path, Some(&worktree_root))
+        .unwrap();
+    let manager = env.get_session_manager().unwrap();
+
+    let err = manager
+        .create_session("blocked", None, None)
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("is not writable"), "unexpected error: {err}");
+    std::fs::set_permissions(&worktree_root, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn test_classify_worktrees_reports_tracked_orphaned_and_stale() {
+    use crate::domains::sessions::entity::WorktreeClass;
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+    let db = env.get_database().unwrap();
+
+    let tracked = manager.create_session("tracked", None, None).unwrap();
+    let stale = manager.create_session("stale", None, None).unwrap();
+    db.update_session_status(&stale.id, SessionStatus::Cancelled)
+        .unwrap();
+
+    let orphan_path = env
+        .repo_path
+        .join(".schaltwerk")
+        .join("worktrees")
+        .join("orphan");
+    Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            orphan_path.to_str().unwrap(),
+            "-b",
+            "orphan",
+        ])
+        .current_dir(&env.repo_path)
+        .output()
+        .unwrap();
+
+    let classes: std::collections::HashMap<String, (WorktreeClass, Option<String>)> = manager
+        .classify_worktrees()
+        .unwrap()
+        .into_iter()
+        .map(|entry| {
+            let name = entry.path.file_name().unwrap().to_string_lossy().to_string();
+            (name, (entry.class, entry.session_name))
+        })
+        .collect();
+
+    assert_eq!(classes.len(), 3, "main worktree must be excluded: {classes:?}");
+    assert_eq!(
+        classes["tracked"],
+        (WorktreeClass::Tracked, Some("tracked".to_string()))
+    );
+    assert_eq!(
+        classes["stale"],
+        (WorktreeClass::Stale, Some("stale".to_string()))
+    );
+    assert_eq!(classes["orphan"], (WorktreeClass::Orphaned, None));
+
+    let removed = manager.cleanup_all_orphaned_worktrees().unwrap();
+    assert_eq!(removed.len(), 2);
+    assert!(tracked.worktree_path.exists());
+    assert!(!stale.worktree_path.exists());
+    assert!(!orphan_path.exists());
+}
+
 #[test]
 fn test_cleanup_orphaned_worktrees_fast_moves_trash_dir() {
     let env = TestEnvironment::new().unwrap();
@@ -858,7 +1551,7 @@ fn test_cleanup_orphaned_worktrees_fast_moves_trash_dir() {
         std::fs::write(trash_dir.join(format!("file_{i}.txt")), "x").unwrap();
     }
 
-    manager.cleanup_orphaned_worktrees().unwrap();
+    manager.cleanup_orphaned_worktrees(&[]).unwrap();
 
     assert!(
         !trash_dir.exists(),
@@ -1179,7 +1872,7 @@ fn test_spec_to_versions_with_grouping_links_all_versions() {
 
     // Start the spec as version 1 within the group
     let main = manager
-        .start_spec_session(&spec.name, None, Some(gid), Some(1))
+        .start_spec_session(&spec.name, None, Some(gid), Some(1), false)
         .unwrap();
 
     // Create and start two more versions with names derived from the spec name
@@ -1401,7 +2094,7 @@ fn test_convert_running_session_to_draft() {
 
     // Start the spec session (convert to running)
     let running_session = manager
-        .start_spec_session("auth-feature", None, None, None)
+        .start_spec_session("auth-feature", None, None, None, false)
         .unwrap();
     assert_eq!(running_session.session_state, SessionState::Running);
     assert_eq!(running_session.status, SessionStatus::Active);
@@ -1444,7 +2137,7 @@ fn test_convert_reviewed_session_to_draft() {
         .unwrap();
 
     let running = manager
-        .start_spec_session("review-test", None, None, None)
+        .start_spec_session("review-test", None, None, None, false)
         .unwrap();
     assert_eq!(running.session_state, SessionState::Running);
 
@@ -1482,7 +2175,7 @@ fn test_convert_session_to_draft_preserves_content() {
 
     // Start the spec session
     let running = manager
-        .start_spec_session("auth-system", None, None, None)
+        .start_spec_session("auth-system", None, None, None, false)
         .unwrap();
 
     // Convert back to spec
@@ -1522,7 +2215,7 @@ fn test_spec_session_ai_renaming_potential() {
 
     // Start the spec session (convert to running)
     let session = manager
-        .start_spec_session("spec-renaming-test", None, None, None)
+        .start_spec_session("spec-renaming-test", None, None, None, false)
         .unwrap();
 
     // Get the updated session
@@ -1674,6 +2367,44 @@ fn test_follow_up_unmarks_reviewed_and_sets_running() {
     );
 }
 
+#[test]
+fn test_follow_up_unreview_records_state_history() {
+    use crate::domains::sessions::entity::StateTransitionReason;
+
+    let env = TestEnvironment::new().unwrap();
+    let manager = env.get_session_manager().unwrap();
+
+    let session = manager
+        .create_session("followup-history", None, None)
+        .unwrap();
+    manager.mark_session_as_reviewed(&session.name).unwrap();
+    assert!(manager.unmark_reviewed_on_follow_up(&session.name).unwrap());
+    // A second follow-up is a no-op and must not add a row
+    assert!(!manager.unmark_reviewed_on_follow_up(&session.name).unwrap());
+
+    let history = manager.get_session_state_history(&session.name).unwrap();
+    let transitions: Vec<_> = history
+        .iter()
+        .map(|entry| (entry.from_state.clone(), entry.to_state.clone(), entry.reason))
+        .collect();
+    assert_eq!(
+        transitions,
+        vec![
+            (
+                Some(SessionState::Running),
+                SessionState::Reviewed,
+                StateTransitionReason::MarkReady
+            ),
+            (
+                Some(SessionState::Reviewed),
+                SessionState::Running,
+                StateTransitionReason::FollowUp
+            ),
+        ]
+    );
+    assert!(history.iter().all(|entry| entry.session_id == session.id));
+}
+
 #[test]
 fn test_follow_up_noop_for_running_unreviewed() {
     let env = TestEnvironment::new().unwrap();
@@ -1793,7 +2524,7 @@ fn test_codex_spec_start_respects_resume_gate() {
 
     // Start the spec session (converts to running and sets resume_allowed=false)
     let running = manager
-        .start_spec_session("codex_spec", None, None, None)
+        .start_spec_session("codex_spec", None, None, None, false)
         .unwrap();
 
     // Prepare a fake Codex sessions directory in a temporary HOME
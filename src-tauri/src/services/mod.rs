@@ -1,6 +1,8 @@
 pub mod mcp;
 pub mod power;
 pub mod projects;
+pub mod run_scripts;
+pub mod setup_scripts;
 pub mod sessions;
 pub mod terminals;
 
@@ -19,30 +21,62 @@ use terminals::{
 
 use crate::project_manager::ProjectManager;
 
+pub use crate::domains::agents::config_health::{
+    AgentConfigHealth, DEFAULT_PROBE_TIMEOUT as AGENT_PROBE_TIMEOUT, VERSION_PROBE_TIMEOUT,
+    collect_agent_config_health, detect_agent_binary_version,
+};
 pub use crate::domains::agents::{
-    AgentLaunchSpec, manifest::AgentManifest, naming, parse_agent_command,
+    AgentLaunchSpec, commit_message, manifest::AgentManifest, naming, parse_agent_command,
 };
 pub use crate::domains::attention::AttentionStateRegistry;
 pub use crate::domains::git::{
-    CommitFileChange, HistoryProviderSnapshot, get_commit_file_changes, get_git_history,
-    get_git_history_with_head,
+    CommitFileChange, HistoryFilter, HistoryProviderSnapshot, get_commit_file_changes,
+    get_git_history, get_git_history_with_head,
     github_cli::{
-        CommandOutput, CommandRunner, CreatePrOptions, CreateSessionPrOptions, GitHubCli,
+        CommandOutput, CommandRunner, CreateDraftPrOptions, CreatePrOptions,
+        CreateSessionPrOptions, GitHubCli,
         GitHubCliError, GitHubIssueComment, GitHubIssueDetails, GitHubIssueLabel,
         GitHubIssueSummary, GitHubPrDetails, GitHubPrReview, GitHubPrReviewComment,
-        GitHubPrSummary, GitHubStatusCheck, PrCommitMode, PrContent, sanitize_branch_name,
+        GitHubPrStatus, GitHubPrSummary, GitHubStatusCheck, PrChecksOutcome, PrCommitMode,
+        PrContent, github_lookup_cache, pr_status_poll_delay, pr_status_poll_interval_from_env,
+        sanitize_branch_component, sanitize_branch_name,
     },
 };
 pub use crate::domains::git::{repository, worktrees};
 pub use crate::domains::merge::{
-    MergeMode, MergeOutcome, MergePreview, MergeService, UpdateFromParentStatus,
-    UpdateSessionFromParentResult, types::MergeStateSnapshot, update_session_from_parent,
+    BatchUpdateProgress, BatchUpdateReport, CherryPickOutcome, MergeMode, MergeOutcome,
+    MergePreview, MergeService, SessionCommit, UpdateFromParentStatus, UpdatePreview,
+    UpdateSessionFromParentResult, normalize_commit_trailers, types::MergeStateSnapshot,
+    update_all_sessions_from_parent, update_session_from_parent,
 };
 pub use crate::domains::power::types::GlobalState;
+pub use crate::domains::sessions::agent_stats::{AgentStatsPeriod, AgentStatsRow};
 pub use crate::domains::sessions::db_sessions::SessionMethods;
 pub use crate::domains::sessions::entity::EnrichedSession;
+pub use crate::domains::sessions::db_schedule::ScheduledSpec;
+pub use crate::domains::sessions::db_snapshots::SessionSnapshot;
+pub use crate::domains::sessions::schedule::scheduled_max_running_from_env;
+pub use crate::domains::sessions::service::{
+    IssueSessionOptions, ScheduleSpecOptions, SessionGitRefresh, SkippedSpecFile,
+    SpecImportReport, SpecSyncReport, SpecSyncState, VersionAgentConfig, VersionComparisonEntry,
+    VersionGroupCreation, VersionGroupDiff, VersionGroupParams, VersionPairSummary,
+    VersionWinnerSelection, issue_session_name, render_issue_prompt, spec_files_in_directory,
+};
+pub use crate::domains::sessions::lifecycle::recovery::{
+    JournalGuard, RecoveredOperation, cancel_journal_entry, in_flight_operations,
+    recover_interrupted_operations, wait_for_in_flight_operations,
+};
+pub use crate::domains::sessions::relocation::{
+    RelocationFailure, RelocationReport, detect_relocated_repository, relocate_project,
+};
+pub use crate::domains::sessions::skip_permissions::{
+    SkipPermissionsError, SkipPermissionsPolicy, confirm_skip_permissions,
+};
+pub use crate::domains::sessions::terminals::attach_terminal_details;
 pub use crate::domains::sessions::entity::{
-    EnrichedSession as EnrichedSessionEntity, FilterMode, Session, SessionState, SortMode,
+    EnrichedSession as EnrichedSessionEntity, FilterMode, GitStats, Session,
+    SessionLaunchOverrides, SessionLaunchSnapshot, SessionState, SessionStateTransition, SortMode,
+    StateTransitionReason, WorktreeClass, WorktreeClassification,
 };
 pub use crate::shared::format_branch_name;
 pub use crate::domains::settings::{
@@ -50,6 +84,7 @@ pub use crate::domains::settings::{
     TerminalSettings, TerminalUIPreferences,
 };
 pub use crate::domains::terminal::TerminalSnapshot;
+pub use crate::domains::terminal::visible::PlainTextOptions;
 pub use crate::domains::terminal::{
     build_login_shell_invocation_with_shell, get_effective_shell,
     manager::CreateTerminalWithAppAndSizeParams, sh_quote_string, shell_invocation_to_posix,
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use crate::domains::power::global_service::GlobalInhibitorService;
 use crate::domains::power::global_service::get_global_keep_awake_service;
 use crate::domains::power::types::GlobalState;
+use crate::infrastructure::keep_awake_bridge::agent_activity_monitor;
 
 pub type DynPowerService = Arc<GlobalInhibitorService>;
 
@@ -29,6 +30,56 @@ pub async fn get_global_keep_awake_state() -> Result<GlobalState, String> {
     Ok(service.broadcast_state().await)
 }
 
+pub async fn get_global_keep_awake_max_minutes() -> Result<u64, String> {
+    let service =
+        get_power_service().ok_or_else(|| "Keep-awake service not initialized".to_string())?;
+    Ok(service.max_inhibit_minutes().await)
+}
+
+pub async fn set_global_keep_awake_max_minutes(minutes: u64) -> Result<GlobalState, String> {
+    let service =
+        get_power_service().ok_or_else(|| "Keep-awake service not initialized".to_string())?;
+    service
+        .set_max_inhibit_minutes(minutes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn get_global_keep_awake_auto_mode() -> Result<bool, String> {
+    let service =
+        get_power_service().ok_or_else(|| "Keep-awake service not initialized".to_string())?;
+    Ok(service.auto_mode().await)
+}
+
+pub async fn set_global_keep_awake_auto_mode(enabled: bool) -> Result<GlobalState, String> {
+    let service =
+        get_power_service().ok_or_else(|| "Keep-awake service not initialized".to_string())?;
+    service
+        .set_auto_mode(enabled)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn set_global_keep_awake_activity_minutes(minutes: u64) -> Result<(), String> {
+    let service =
+        get_power_service().ok_or_else(|| "Keep-awake service not initialized".to_string())?;
+    let window = service
+        .set_auto_activity_minutes(minutes)
+        .await
+        .map_err(|e| e.to_string())?;
+    agent_activity_monitor().set_idle_window(window);
+    Ok(())
+}
+
+/// Applies the persisted activity window to the agent activity tracker at startup.
+pub async fn apply_persisted_activity_window() {
+    if let Some(service) = get_power_service()
+        && let Some(window) = service.auto_activity_window().await
+    {
+        agent_activity_monitor().set_idle_window(window);
+    }
+}
+
 pub async fn handle_terminal_attention(
     session_id: String,
     is_idle: bool,
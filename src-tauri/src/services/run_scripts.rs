@@ -0,0 +1,393 @@
+use crate::infrastructure::database::RunScript;
+use crate::services::terminals::{CreateRunTerminalRequest, TerminalsService};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+/// Printed by the decorated run command once the script exits; the run terminal UI
+/// watches for the same marker.
+pub const RUN_EXIT_SENTINEL_PREFIX: &str = "__SCHALTWERK_RUN_EXIT__=";
+const LOOPBACK_HOSTS: [&str; 4] = ["localhost:", "127.0.0.1:", "0.0.0.0:", "[::1]:"];
+const MAX_PENDING_OUTPUT: usize = 4096;
+
+static RUNNING_SERVICES: LazyLock<RunningServicesRegistry> =
+    LazyLock::new(RunningServicesRegistry::default);
+
+pub fn run_terminal_id(session_name: Option<&str>) -> String {
+    match session_name {
+        Some(name) => format!("run-terminal-{name}"),
+        None => "run-terminal-orchestrator".to_string(),
+    }
+}
+
+/// Wraps `command` so the shell reports its exit code through the run sentinel.
+pub fn decorate_run_command(command: &str) -> String {
+    format!(
+        "__schaltwerk_exit_code=0; {command}; __schaltwerk_exit_code=$?; \
+         printf '{RUN_EXIT_SENTINEL_PREFIX}%s\\r' \"$__schaltwerk_exit_code\"; \
+         printf '\\r\\033[K'; unset __schaltwerk_exit_code\n"
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunScriptLaunch {
+    pub terminal_id: String,
+    pub session_name: Option<String>,
+    pub cwd: String,
+    pub command: String,
+    pub env: Vec<(String, String)>,
+}
+
+impl RunScriptLaunch {
+    /// Resolves where and how `script` runs. `base_dir` is the session worktree, or the
+    /// repository root for the orchestrator. A relative script working directory is
+    /// joined onto it; an absolute one is only honoured for the orchestrator so session
+    /// runs never leave their worktree. Script variables override project ones.
+    pub fn new(
+        session_name: Option<&str>,
+        base_dir: &Path,
+        script: &RunScript,
+        project_env: HashMap<String, String>,
+    ) -> Self {
+        let cwd = match script
+            .working_directory
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map(Path::new)
+        {
+            Some(dir) if dir.is_relative() => base_dir.join(dir),
+            Some(dir) if session_name.is_none() => dir.to_path_buf(),
+            _ => base_dir.to_path_buf(),
+        };
+
+        let mut env: BTreeMap<String, String> = project_env.into_iter().collect();
+        env.extend(script.environment_variables.clone());
+
+        Self {
+            terminal_id: run_terminal_id(session_name),
+            session_name: session_name.map(str::to_string),
+            cwd: cwd.to_string_lossy().to_string(),
+            command: script.command.clone(),
+            env: env.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningService {
+    /// Distinguishes successive runs in the same terminal.
+    pub run_id: u64,
+    pub terminal_id: String,
+    pub session_name: Option<String>,
+    pub command: String,
+    pub cwd: String,
+    /// Loopback ports the script announced in its output.
+    pub ports: Vec<u16>,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Run scripts started by the backend, keyed by run terminal id.
+#[derive(Debug, Default)]
+pub struct RunningServicesRegistry {
+    next_run_id: AtomicU64,
+    services: Mutex<HashMap<String, RunningService>>,
+}
+
+impl RunningServicesRegistry {
+    pub fn global() -> &'static Self {
+        &RUNNING_SERVICES
+    }
+
+    fn services(&self) -> MutexGuard<'_, HashMap<String, RunningService>> {
+        self.services
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Claims the launch's terminal. An active run is replaced only when `restart` is set.
+    fn begin(
+        &self,
+        launch: &RunScriptLaunch,
+        restart: bool,
+    ) -> Result<(RunningService, Option<RunningService>), String> {
+        let mut services = self.services();
+        if !restart && services.contains_key(&launch.terminal_id) {
+            return Err(format!(
+                "Run script is already running in {}; stop it or restart",
+                launch.terminal_id
+            ));
+        }
+
+        let service = RunningService {
+            run_id: self.next_run_id.fetch_add(1, Ordering::Relaxed) + 1,
+            terminal_id: launch.terminal_id.clone(),
+            session_name: launch.session_name.clone(),
+            command: launch.command.clone(),
+            cwd: launch.cwd.clone(),
+            ports: Vec::new(),
+            started_at: Utc::now(),
+        };
+        let replaced = services.insert(launch.terminal_id.clone(), service.clone());
+        Ok((service, replaced))
+    }
+
+    pub fn get(&self, terminal_id: &str) -> Option<RunningService> {
+        self.services().get(terminal_id).cloned()
+    }
+
+    pub fn list(&self) -> Vec<RunningService> {
+        let mut services: Vec<_> = self.services().values().cloned().collect();
+        services.sort_by(|a, b| a.terminal_id.cmp(&b.terminal_id));
+        services
+    }
+
+    /// Returns true when `port` was not yet known for the run.
+    pub fn record_port(&self, terminal_id: &str, run_id: u64, port: u16) -> bool {
+        match self.services().get_mut(terminal_id) {
+            Some(service) if service.run_id == run_id && !service.ports.contains(&port) => {
+                service.ports.push(port);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the run unless a restart already replaced it.
+    pub fn finish(&self, terminal_id: &str, run_id: u64) -> Option<RunningService> {
+        let mut services = self.services();
+        if services.get(terminal_id)?.run_id != run_id {
+            return None;
+        }
+        services.remove(terminal_id)
+    }
+}
+
+/// Starts the run script in a fresh run terminal. The previous run terminal is closed so
+/// the new one picks up the current working directory and environment.
+pub async fn launch_run_script(
+    terminals: &dyn TerminalsService,
+    registry: &RunningServicesRegistry,
+    launch: RunScriptLaunch,
+    restart: bool,
+) -> Result<RunningService, String> {
+    let (service, replaced) = registry.begin(&launch, restart)?;
+    if let Some(previous) = replaced {
+        log::info!(
+            "Restarting run script in {} (previous run {})",
+            launch.terminal_id,
+            previous.run_id
+        );
+    }
+
+    let result = async {
+        if terminals
+            .terminal_exists(launch.terminal_id.clone())
+            .await?
+        {
+            terminals.close_terminal(launch.terminal_id.clone()).await?;
+        }
+        terminals
+            .create_run_terminal(CreateRunTerminalRequest {
+                id: launch.terminal_id.clone(),
+                cwd: launch.cwd.clone(),
+                env: Some(launch.env.clone()),
+                cols: None,
+                rows: None,
+            })
+            .await?;
+        terminals
+            .write_terminal(
+                launch.terminal_id.clone(),
+                decorate_run_command(&launch.command).into_bytes(),
+            )
+            .await
+    }
+    .await;
+
+    if let Err(e) = result {
+        registry.finish(&launch.terminal_id, service.run_id);
+        return Err(e);
+    }
+    Ok(service)
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RunOutputSignals {
+    pub ports: Vec<u16>,
+    pub exit_code: Option<i32>,
+}
+
+/// Scans run terminal output line by line for announced loopback ports and the exit
+/// sentinel. Partial lines are kept until the rest arrives.
+#[derive(Debug, Default)]
+pub struct RunOutputScanner {
+    pending: String,
+}
+
+impl RunOutputScanner {
+    pub fn feed(&mut self, chunk: &[u8]) -> RunOutputSignals {
+        self.pending.push_str(&String::from_utf8_lossy(chunk));
+
+        let mut signals = RunOutputSignals::default();
+        while let Some(end) = self.pending.find(['\n', '\r']) {
+            let line = strip_ansi(&self.pending[..end]);
+            self.pending.drain(..=end);
+
+            if let Some(code) = exit_code_in(&line) {
+                signals.exit_code = Some(code);
+            }
+            for port in ports_in(&line) {
+                if !signals.ports.contains(&port) {
+                    signals.ports.push(port);
+                }
+            }
+        }
+
+        if self.pending.len() > MAX_PENDING_OUTPUT {
+            let mut cut = self.pending.len() - MAX_PENDING_OUTPUT;
+            while !self.pending.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.pending.drain(..cut);
+        }
+        signals
+    }
+}
+
+fn exit_code_in(line: &str) -> Option<i32> {
+    let (_, rest) = line.split_once(RUN_EXIT_SENTINEL_PREFIX)?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+fn ports_in(line: &str) -> Vec<u16> {
+    let lower = line.to_ascii_lowercase();
+    let mut ports = Vec::new();
+    for host in LOOPBACK_HOSTS {
+        for (index, _) in lower.match_indices(host) {
+            let digits: String = lower[index + host.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            if let Ok(port) = digits.parse::<u16>()
+                && port != 0
+                && !ports.contains(&port)
+            {
+                ports.push(port);
+            }
+        }
+    }
+    ports
+}
+
+fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            result.push(ch);
+        } else if chars.peek() == Some(&'[') {
+            chars.next();
+            for ch in chars.by_ref() {
+                if ch.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn script(working_directory: Option<&str>) -> RunScript {
+        RunScript {
+            command: "npm run dev".to_string(),
+            working_directory: working_directory.map(str::to_string),
+            environment_variables: HashMap::from([("PORT".to_string(), "4000".to_string())]),
+            preview_localhost_on_click: false,
+        }
+    }
+
+    #[test]
+    fn launch_resolves_cwd_and_layers_script_env_over_project_env() {
+        let worktree = PathBuf::from("/repo/.schaltwerk/worktrees/alpha");
+        let project_env = HashMap::from([
+            ("PORT".to_string(), "3000".to_string()),
+            ("API_URL".to_string(), "http://api".to_string()),
+        ]);
+
+        let launch =
+            RunScriptLaunch::new(Some("alpha"), &worktree, &script(Some("web")), project_env);
+        assert_eq!(launch.terminal_id, "run-terminal-alpha");
+        assert_eq!(launch.cwd, worktree.join("web").to_string_lossy());
+        assert_eq!(
+            launch.env,
+            vec![
+                ("API_URL".to_string(), "http://api".to_string()),
+                ("PORT".to_string(), "4000".to_string()),
+            ]
+        );
+
+        let session = RunScriptLaunch::new(
+            Some("alpha"),
+            &worktree,
+            &script(Some("/elsewhere")),
+            HashMap::new(),
+        );
+        assert_eq!(session.cwd, worktree.to_string_lossy());
+        let orchestrator = RunScriptLaunch::new(
+            None,
+            Path::new("/repo"),
+            &script(Some("/elsewhere")),
+            HashMap::new(),
+        );
+        assert_eq!(orchestrator.terminal_id, "run-terminal-orchestrator");
+        assert_eq!(orchestrator.cwd, "/elsewhere");
+    }
+
+    #[test]
+    fn scanner_reports_ports_and_exit_code_across_chunks() {
+        let mut scanner = RunOutputScanner::default();
+
+        let echo = scanner.feed(decorate_run_command("npm run dev").as_bytes());
+        assert_eq!(echo, RunOutputSignals::default());
+
+        assert_eq!(
+            scanner.feed(b"  Local: http://local"),
+            RunOutputSignals::default()
+        );
+        let ready = scanner.feed(b"host:\x1b[1m5173\x1b[22m/\r\n");
+        assert_eq!(ready.ports, vec![5173]);
+        assert_eq!(ready.exit_code, None);
+
+        let done = scanner.feed(b"__SCHALTWERK_RUN_EXIT__=130\r");
+        assert_eq!(done.exit_code, Some(130));
+    }
+
+    #[test]
+    fn finished_run_does_not_remove_its_restarted_replacement() {
+        let registry = RunningServicesRegistry::default();
+        let launch = RunScriptLaunch::new(None, Path::new("/repo"), &script(None), HashMap::new());
+
+        let (first, _) = registry.begin(&launch, false).unwrap();
+        assert!(registry.begin(&launch, false).is_err());
+        let (second, replaced) = registry.begin(&launch, true).unwrap();
+        assert_eq!(replaced.map(|s| s.run_id), Some(first.run_id));
+
+        assert!(!registry.record_port(&launch.terminal_id, first.run_id, 3000));
+        assert!(registry.record_port(&launch.terminal_id, second.run_id, 3000));
+        assert!(registry.finish(&launch.terminal_id, first.run_id).is_none());
+        let finished = registry.finish(&launch.terminal_id, second.run_id).unwrap();
+        assert_eq!(finished.ports, vec![3000]);
+        assert!(registry.list().is_empty());
+    }
+}
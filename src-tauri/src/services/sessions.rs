@@ -1,4 +1,5 @@
 use crate::domains::sessions::entity::EnrichedSession;
+use crate::domains::sessions::terminals::attach_terminal_details;
 use crate::project_manager::ProjectManager;
 use crate::schaltwerk_core::SchaltwerkCore;
 use async_trait::async_trait;
@@ -81,6 +82,15 @@ impl SessionsBackend for ProjectSessionsBackend {
         let start = std::time::Instant::now();
         log::debug!("ProjectSessionsBackend list_enriched_sessions start call_id={call_id}");
 
+        // One snapshot for the whole listing; never consult the terminal manager per session
+        let terminal_liveness = match self.project_manager.current_terminal_manager().await {
+            Ok(manager) => manager.liveness_snapshot().await,
+            Err(e) => {
+                log::debug!("ProjectSessionsBackend call_id={call_id} no terminal manager: {e}");
+                Default::default()
+            }
+        };
+
         let core = self.get_core().await?;
         let core_wait = std::time::Instant::now();
         let core = core.read().await;
@@ -98,6 +108,10 @@ impl SessionsBackend for ProjectSessionsBackend {
         let manager = core.session_manager();
         let result = manager
             .list_enriched_sessions()
+            .map(|mut sessions| {
+                attach_terminal_details(&mut sessions, &terminal_liveness);
+                sessions
+            })
             .map_err(|err| err.to_string());
 
         match &result {
@@ -153,6 +167,7 @@ mod tests {
                 display_name: None,
                 version_group_id: None,
                 version_number: None,
+                version_group_label: None,
                 epic: None,
                 branch: format!("{name}-branch"),
                 worktree_path: "/tmp".to_string(),
@@ -174,9 +189,18 @@ mod tests {
                 session_state: SessionState::Running,
                 pr_number: None,
                 pr_url: None,
+                group: None,
+                skip_permissions_active: false,
+                has_stashed_changes: false,
+                setup_failed: false,
+                setup_running: false,
+                branch_missing: false,
+                repo_label: None,
+                depends_on_session: None,
             },
             status: None,
             terminals: vec![],
+            terminal_details: vec![],
             attention_required: None,
         }
     }
@@ -0,0 +1,594 @@
+use crate::domains::sessions::entity::Session;
+use crate::domains::terminal::TerminalManager;
+use crate::domains::terminal::sh_quote_string;
+use crate::domains::terminal::visible::PlainTextOptions;
+use crate::services::run_scripts::{RunOutputScanner, decorate_run_command};
+use crate::shared::terminal_id::terminal_id_for_session_bottom;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Written into the worktree once setup succeeded. Agent launches skip their own
+/// setup step while it exists.
+pub const SETUP_DONE_MARKER: &str = ".schaltwerk/setup.done";
+/// Terminal lines reported alongside a failed setup.
+pub const SETUP_FAILURE_TAIL_LINES: usize = 40;
+
+static SETUP_RUNS: LazyLock<SetupRunsRegistry> = LazyLock::new(SetupRunsRegistry::default);
+static SETUP_SLOTS: LazyLock<SetupSlots> = LazyLock::new(SetupSlots::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SetupStatus {
+    /// Waiting for a free slot, see [`SetupSlots`].
+    Queued,
+    Running,
+    Succeeded,
+    /// `exit_code` is `None` when the terminal went away before the script finished.
+    Failed {
+        exit_code: Option<i32>,
+    },
+    /// The script ran past the project's time limit and was interrupted.
+    TimedOut,
+}
+
+impl SetupStatus {
+    pub fn is_finished(self) -> bool {
+        !matches!(self, SetupStatus::Queued | SetupStatus::Running)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupScriptLaunch {
+    pub session_name: String,
+    pub terminal_id: String,
+    pub worktree_path: PathBuf,
+    /// Temporary copy of the script with the session variables exported up front.
+    pub script_path: PathBuf,
+}
+
+impl SetupScriptLaunch {
+    /// Writes `script` to a temporary file that exports the project variables and the
+    /// session variables the setup script has always been given.
+    pub fn prepare(
+        session: &Session,
+        script: &str,
+        project_env: HashMap<String, String>,
+    ) -> std::io::Result<Self> {
+        let mut env: BTreeMap<String, String> = project_env.into_iter().collect();
+        env.insert(
+            "WORKTREE_PATH".to_string(),
+            absolute(&session.worktree_path),
+        );
+        env.insert("REPO_PATH".to_string(), absolute(&session.repository_path));
+        env.insert("SESSION_NAME".to_string(), session.name.clone());
+        env.insert("BRANCH_NAME".to_string(), session.branch.clone());
+
+        let mut contents = String::new();
+        for (key, value) in &env {
+            if is_shell_identifier(key) {
+                contents.push_str(&format!("export {key}={}\n", sh_quote_string(value)));
+            }
+        }
+        contents.push_str(script);
+        contents.push('\n');
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let script_path = std::env::temp_dir().join(format!(
+            "schalt_setup_{}_{timestamp}.sh",
+            session.name.replace('/', "_")
+        ));
+        std::fs::write(&script_path, contents)?;
+
+        Ok(Self {
+            session_name: session.name.clone(),
+            terminal_id: terminal_id_for_session_bottom(&session.name),
+            worktree_path: session.worktree_path.clone(),
+            script_path,
+        })
+    }
+
+    /// Line typed into the terminal. It runs the script from the worktree root, removes
+    /// the temporary file, records the done marker on success and reports the exit code
+    /// through the run sentinel.
+    pub fn command(&self) -> String {
+        let worktree = sh_quote_string(&self.worktree_path.to_string_lossy());
+        let script = sh_quote_string(&self.script_path.to_string_lossy());
+        let marker = sh_quote_string(SETUP_DONE_MARKER);
+        decorate_run_command(&format!(
+            "cd {worktree} && sh {script}; __schaltwerk_setup_status=$?; rm -f {script}; \
+             [ \"$__schaltwerk_setup_status\" -ne 0 ] || {{ mkdir -p .schaltwerk && : > {marker}; }}; \
+             (exit \"$__schaltwerk_setup_status\")"
+        ))
+    }
+}
+
+fn absolute(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+fn is_shell_identifier(key: &str) -> bool {
+    key.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Setup runs started by the backend, keyed by session name. Finished runs stay
+/// recorded so agent launches know setup was handled outside their terminal.
+#[derive(Debug, Default)]
+pub struct SetupRunsRegistry {
+    runs: Mutex<HashMap<String, watch::Sender<SetupStatus>>>,
+}
+
+impl SetupRunsRegistry {
+    pub fn global() -> &'static Self {
+        &SETUP_RUNS
+    }
+
+    fn runs(&self) -> MutexGuard<'_, HashMap<String, watch::Sender<SetupStatus>>> {
+        self.runs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Queues setup for `session_name`; fails while a previous run is queued or active.
+    pub fn begin(&self, session_name: &str) -> Result<(), String> {
+        let mut runs = self.runs();
+        if let Some(run) = runs.get(session_name) {
+            if !run.borrow().is_finished() {
+                return Err(format!(
+                    "Setup script is already running for session '{session_name}'"
+                ));
+            }
+            run.send_replace(SetupStatus::Queued);
+            return Ok(());
+        }
+        runs.insert(
+            session_name.to_string(),
+            watch::Sender::new(SetupStatus::Queued),
+        );
+        Ok(())
+    }
+
+    pub fn status(&self, session_name: &str) -> Option<SetupStatus> {
+        self.runs().get(session_name).map(|run| *run.borrow())
+    }
+
+    pub fn finish(&self, session_name: &str, status: SetupStatus) {
+        if let Some(run) = self.runs().get(session_name) {
+            run.send_replace(status);
+        }
+    }
+
+    /// Waits until the setup of `session_name` is no longer queued or running. Returns
+    /// `None` when the backend never ran setup for the session.
+    pub async fn wait(&self, session_name: &str) -> Option<SetupStatus> {
+        let mut receiver = self.runs().get(session_name)?.subscribe();
+        let status = receiver
+            .wait_for(|status| status.is_finished())
+            .await
+            .map(|status| *status)
+            .ok();
+        status.or_else(|| self.status(session_name))
+    }
+}
+
+/// Counting semaphore shared by all setup runs of this process. The limit is passed
+/// on each acquisition, so a project's cap applies to the runs it starts without
+/// resizing the pool.
+#[derive(Debug)]
+pub struct SetupSlots {
+    running: watch::Sender<usize>,
+}
+
+impl Default for SetupSlots {
+    fn default() -> Self {
+        Self {
+            running: watch::Sender::new(0),
+        }
+    }
+}
+
+impl SetupSlots {
+    pub fn global() -> &'static Self {
+        &SETUP_SLOTS
+    }
+
+    /// Waits until fewer than `limit` setup scripts run and takes a slot. The slot is
+    /// released when the returned guard is dropped.
+    pub async fn acquire(&self, limit: usize) -> SetupSlot<'_> {
+        let limit = limit.max(1);
+        let mut receiver = self.running.subscribe();
+        loop {
+            let acquired = self.running.send_if_modified(|running| {
+                if *running < limit {
+                    *running += 1;
+                    true
+                } else {
+                    false
+                }
+            });
+            if acquired {
+                return SetupSlot { slots: self };
+            }
+            // Cannot fail: the sender lives in `self`
+            let _ = receiver.changed().await;
+        }
+    }
+
+    pub fn running(&self) -> usize {
+        *self.running.borrow()
+    }
+}
+
+/// A taken [`SetupSlots`] slot.
+#[derive(Debug)]
+pub struct SetupSlot<'a> {
+    slots: &'a SetupSlots,
+}
+
+impl Drop for SetupSlot<'_> {
+    fn drop(&mut self) {
+        self.slots
+            .running
+            .send_modify(|running| *running = running.saturating_sub(1));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupOutcome {
+    pub status: SetupStatus,
+    /// Last lines of the terminal, collected only when setup failed.
+    pub output_tail: String,
+}
+
+/// Types the setup command into the session's bottom terminal, creating the terminal
+/// when the UI has not opened it yet, and marks the run queued with
+/// [`SetupRunsRegistry::begin`] as running. Returns the output sequence number the
+/// command was written at, to be handed to [`wait_for_setup_script`].
+pub async fn launch_setup_script(
+    manager: &TerminalManager,
+    registry: &SetupRunsRegistry,
+    launch: &SetupScriptLaunch,
+) -> Result<u64, String> {
+    registry.finish(&launch.session_name, SetupStatus::Running);
+
+    let result = async {
+        if !manager.terminal_exists(&launch.terminal_id).await? {
+            manager
+                .create_terminal(
+                    launch.terminal_id.clone(),
+                    launch.worktree_path.to_string_lossy().to_string(),
+                )
+                .await?;
+        }
+        let seq = manager
+            .get_terminal_buffer(launch.terminal_id.clone(), None)
+            .await?
+            .seq;
+        manager
+            .write_terminal(launch.terminal_id.clone(), launch.command().into_bytes())
+            .await?;
+        Ok(seq)
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&launch.script_path);
+        registry.finish(
+            &launch.session_name,
+            SetupStatus::Failed { exit_code: None },
+        );
+    }
+    result
+}
+
+/// Follows the terminal output written after `from_seq` until the setup command
+/// reports its exit code, the terminal goes away or `timeout` elapses, and records the
+/// result. Output is handed to `on_output` chunk by chunk as it arrives. A timed out
+/// script is interrupted with Ctrl-C.
+pub async fn wait_for_setup_script(
+    manager: &TerminalManager,
+    registry: &SetupRunsRegistry,
+    launch: &SetupScriptLaunch,
+    from_seq: u64,
+    timeout: Option<Duration>,
+    mut on_output: impl FnMut(&str),
+) -> SetupOutcome {
+    let terminal_id = &launch.terminal_id;
+    let watch = async {
+        let mut scanner = RunOutputScanner::default();
+        let mut seq = from_seq;
+        loop {
+            let next = match manager.wait_for_output_change(terminal_id, seq).await {
+                Ok(next) => next,
+                Err(e) => {
+                    log::debug!("Setup watcher for {terminal_id} ended: {e}");
+                    return None;
+                }
+            };
+            if next <= seq {
+                continue;
+            }
+            let snapshot = match manager
+                .get_terminal_buffer(terminal_id.clone(), Some(seq))
+                .await
+            {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    log::debug!("Setup watcher for {terminal_id} ended: {e}");
+                    return None;
+                }
+            };
+            seq = snapshot.seq;
+            on_output(&String::from_utf8_lossy(&snapshot.data));
+            if let Some(code) = scanner.feed(&snapshot.data).exit_code {
+                return Some(code);
+            }
+        }
+    };
+    let finished = match timeout {
+        Some(limit) => tokio::time::timeout(limit, watch).await.ok(),
+        None => Some(watch.await),
+    };
+
+    let status = match finished {
+        Some(Some(0)) => SetupStatus::Succeeded,
+        Some(exit_code) => SetupStatus::Failed { exit_code },
+        None => {
+            log::warn!(
+                "Setup script for session '{}' timed out, interrupting it",
+                launch.session_name
+            );
+            if let Err(e) = manager
+                .write_terminal(terminal_id.clone(), vec![0x03])
+                .await
+            {
+                log::warn!("Failed to interrupt setup script in {terminal_id}: {e}");
+            }
+            // The interrupted command line never reaches its own cleanup
+            let _ = std::fs::remove_file(&launch.script_path);
+            SetupStatus::TimedOut
+        }
+    };
+    let output_tail = if status == SetupStatus::Succeeded {
+        String::new()
+    } else {
+        manager
+            .get_terminal_buffer_text(
+                terminal_id.clone(),
+                PlainTextOptions {
+                    trim_trailing_whitespace: true,
+                    max_lines: Some(SETUP_FAILURE_TAIL_LINES),
+                },
+            )
+            .await
+            .unwrap_or_default()
+    };
+    registry.finish(&launch.session_name, status);
+    SetupOutcome {
+        status,
+        output_tail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::sessions::service::SessionManager;
+    use crate::infrastructure::database::Database;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn session(name: &str) -> (Session, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("README.md"), "Initial").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "init"]);
+
+        let db = Database::new(Some(temp.path().join("test.db"))).unwrap();
+        let manager = SessionManager::new(db, repo);
+        let session = manager
+            .create_session_with_auto_flag(name, None, Some("main"), false, None, None)
+            .unwrap();
+        (session, temp)
+    }
+
+    async fn run_setup(
+        session: &Session,
+        script: &str,
+        timeout: Option<Duration>,
+    ) -> (SetupOutcome, String) {
+        let manager = TerminalManager::new();
+        let registry = SetupRunsRegistry::default();
+        let launch = SetupScriptLaunch::prepare(
+            session,
+            script,
+            HashMap::from([("GREETING".to_string(), "hello".to_string())]),
+        )
+        .unwrap();
+
+        registry.begin(&session.name).unwrap();
+        let from_seq = launch_setup_script(&manager, &registry, &launch)
+            .await
+            .unwrap();
+        assert_eq!(registry.status(&session.name), Some(SetupStatus::Running));
+        let mut streamed = String::new();
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(20),
+            wait_for_setup_script(&manager, &registry, &launch, from_seq, timeout, |chunk| {
+                streamed.push_str(chunk)
+            }),
+        )
+        .await
+        .expect("setup finishes");
+        assert_eq!(registry.wait(&session.name).await, Some(outcome.status));
+        assert!(!launch.script_path.exists());
+
+        let _ = manager.close_terminal(launch.terminal_id.clone()).await;
+        (outcome, streamed)
+    }
+
+    #[tokio::test]
+    async fn successful_setup_marks_worktree_done() {
+        let (session, _temp) = session("setup-ok");
+
+        let (outcome, _) = run_setup(
+            &session,
+            "printf '%s %s' \"$GREETING\" \"$SESSION_NAME\" > greeting.txt",
+            Some(Duration::from_secs(15)),
+        )
+        .await;
+
+        assert_eq!(outcome.status, SetupStatus::Succeeded);
+        let greeting = std::fs::read_to_string(session.worktree_path.join("greeting.txt")).unwrap();
+        assert_eq!(greeting, "hello setup-ok");
+        assert!(session.worktree_path.join(SETUP_DONE_MARKER).exists());
+    }
+
+    #[tokio::test]
+    async fn failing_setup_reports_exit_code_and_output() {
+        let (session, _temp) = session("setup-broken");
+
+        let (outcome, streamed) =
+            run_setup(&session, "echo dependency install failed; exit 3", None).await;
+
+        assert_eq!(outcome.status, SetupStatus::Failed { exit_code: Some(3) });
+        assert!(
+            outcome.output_tail.contains("dependency install failed"),
+            "unexpected output: {}",
+            outcome.output_tail
+        );
+        assert!(!session.worktree_path.join(SETUP_DONE_MARKER).exists());
+        assert!(
+            streamed.contains("dependency install failed"),
+            "output was not streamed: {streamed}"
+        );
+    }
+
+    #[tokio::test]
+    async fn setup_past_its_timeout_is_interrupted() {
+        let (session, _temp) = session("setup-slow");
+
+        let (outcome, _) = run_setup(
+            &session,
+            "echo starting; sleep 30; touch finished.txt",
+            Some(Duration::from_millis(1500)),
+        )
+        .await;
+
+        assert_eq!(outcome.status, SetupStatus::TimedOut);
+        assert!(
+            outcome.output_tail.contains("starting"),
+            "unexpected output: {}",
+            outcome.output_tail
+        );
+        assert!(!session.worktree_path.join("finished.txt").exists());
+        assert!(!session.worktree_path.join(SETUP_DONE_MARKER).exists());
+    }
+
+    #[test]
+    fn registry_rejects_overlapping_runs_and_allows_reruns() {
+        let registry = SetupRunsRegistry::default();
+        assert_eq!(registry.status("alpha"), None);
+
+        registry.begin("alpha").unwrap();
+        assert!(registry.begin("alpha").is_err());
+        registry.finish("alpha", SetupStatus::Running);
+        assert!(registry.begin("alpha").is_err());
+        registry.finish("alpha", SetupStatus::Failed { exit_code: Some(1) });
+        registry.begin("alpha").unwrap();
+        assert_eq!(registry.status("alpha"), Some(SetupStatus::Queued));
+    }
+
+    #[tokio::test]
+    async fn setups_run_in_parallel_up_to_the_slot_limit() {
+        let temp = TempDir::new().unwrap();
+        let running_dir = temp.path().join("running");
+        std::fs::create_dir_all(&running_dir).unwrap();
+        let counts = temp.path().join("counts");
+        let script = "mkdir \"$RUNNING_DIR/$SESSION_NAME\"; \
+                      ls \"$RUNNING_DIR\" | wc -l >> \"$COUNTS\"; \
+                      sleep 1; rmdir \"$RUNNING_DIR/$SESSION_NAME\"; \
+                      [ \"$SESSION_NAME\" != setup-par-1 ]";
+        let project_env = HashMap::from([
+            (
+                "RUNNING_DIR".to_string(),
+                running_dir.to_string_lossy().to_string(),
+            ),
+            ("COUNTS".to_string(), counts.to_string_lossy().to_string()),
+        ]);
+
+        let manager = TerminalManager::new();
+        let registry = SetupRunsRegistry::default();
+        let slots = SetupSlots::default();
+        let sessions: Vec<_> = (0..3).map(|i| session(&format!("setup-par-{i}"))).collect();
+        let run = |session: &Session| {
+            let launch = SetupScriptLaunch::prepare(session, script, project_env.clone()).unwrap();
+            let (manager, registry, slots) = (&manager, &registry, &slots);
+            registry.begin(&session.name).unwrap();
+            async move {
+                let _slot = slots.acquire(2).await;
+                assert!(slots.running() <= 2);
+                let from_seq = launch_setup_script(manager, registry, &launch)
+                    .await
+                    .unwrap();
+                let outcome =
+                    wait_for_setup_script(manager, registry, &launch, from_seq, None, |_| {}).await;
+                let _ = manager.close_terminal(launch.terminal_id.clone()).await;
+                outcome.status
+            }
+        };
+
+        let statuses = tokio::time::timeout(Duration::from_secs(30), async {
+            tokio::join!(
+                run(&sessions[0].0),
+                run(&sessions[1].0),
+                run(&sessions[2].0)
+            )
+        })
+        .await
+        .expect("setups finish");
+
+        // The failing setup does not stop the others
+        assert_eq!(
+            statuses,
+            (
+                SetupStatus::Succeeded,
+                SetupStatus::Failed { exit_code: Some(1) },
+                SetupStatus::Succeeded
+            )
+        );
+        let max_running = std::fs::read_to_string(&counts)
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.trim().parse::<usize>().ok())
+            .max();
+        assert_eq!(max_running, Some(2), "two setups overlap, never three");
+        assert_eq!(slots.running(), 0);
+    }
+}
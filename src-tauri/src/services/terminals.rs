@@ -1,5 +1,6 @@
 use crate::domains::terminal::{
     TerminalManager, TerminalSnapshot, manager::CreateTerminalWithAppAndSizeParams,
+    visible::PlainTextOptions,
 };
 use crate::project_manager::ProjectManager;
 use crate::schaltwerk_core::db_project_config::ProjectConfigMethods;
@@ -61,6 +62,11 @@ pub trait TerminalsBackend: Send + Sync {
         id: String,
         from_seq: Option<u64>,
     ) -> Result<TerminalSnapshot, String>;
+    async fn get_terminal_buffer_text(
+        &self,
+        id: String,
+        options: PlainTextOptions,
+    ) -> Result<String, String>;
     async fn get_terminal_activity_status(&self, id: String) -> Result<(bool, u64), String>;
     async fn get_all_terminal_activity(&self) -> Result<Vec<(String, u64)>, String>;
     async fn register_session_terminals(
@@ -109,6 +115,17 @@ pub trait TerminalsService: Send + Sync {
         id: String,
         from_seq: Option<u64>,
     ) -> Result<TerminalSnapshot, String>;
+    async fn get_terminal_buffer_if_changed(
+        &self,
+        id: String,
+        from_seq: Option<u64>,
+        if_none_match: u64,
+    ) -> Result<TerminalSnapshot, String>;
+    async fn get_terminal_buffer_text(
+        &self,
+        id: String,
+        options: PlainTextOptions,
+    ) -> Result<String, String>;
     async fn get_terminal_activity_status(&self, id: String) -> Result<(bool, u64), String>;
     async fn get_all_terminal_activity(&self) -> Result<Vec<(String, u64)>, String>;
     async fn register_session_terminals(
@@ -234,6 +251,35 @@ impl<B: TerminalsBackend> TerminalsServiceImpl<B> {
             .map_err(|err| Self::map_err(&format!("Failed to read buffer for terminal {id}"), err))
     }
 
+    /// Like [`Self::get_terminal_buffer`], but when the terminal is still at sequence
+    /// `if_none_match` the returned snapshot has that sequence and no data, and the
+    /// buffer is not copied.
+    pub async fn get_terminal_buffer_if_changed(
+        &self,
+        id: String,
+        from_seq: Option<u64>,
+        if_none_match: u64,
+    ) -> Result<TerminalSnapshot, String> {
+        let probe = self
+            .get_terminal_buffer(id.clone(), Some(if_none_match))
+            .await?;
+        if probe.seq == if_none_match || from_seq == Some(if_none_match) {
+            return Ok(probe);
+        }
+        self.get_terminal_buffer(id, from_seq).await
+    }
+
+    pub async fn get_terminal_buffer_text(
+        &self,
+        id: String,
+        options: PlainTextOptions,
+    ) -> Result<String, String> {
+        self.backend
+            .get_terminal_buffer_text(id.clone(), options)
+            .await
+            .map_err(|err| Self::map_err(&format!("Failed to render buffer for terminal {id}"), err))
+    }
+
     pub async fn get_terminal_activity_status(&self, id: String) -> Result<(bool, u64), String> {
         self.backend
             .get_terminal_activity_status(id.clone())
@@ -363,6 +409,24 @@ where
         TerminalsServiceImpl::get_terminal_buffer(self, id, from_seq).await
     }
 
+    async fn get_terminal_buffer_if_changed(
+        &self,
+        id: String,
+        from_seq: Option<u64>,
+        if_none_match: u64,
+    ) -> Result<TerminalSnapshot, String> {
+        TerminalsServiceImpl::get_terminal_buffer_if_changed(self, id, from_seq, if_none_match)
+            .await
+    }
+
+    async fn get_terminal_buffer_text(
+        &self,
+        id: String,
+        options: PlainTextOptions,
+    ) -> Result<String, String> {
+        TerminalsServiceImpl::get_terminal_buffer_text(self, id, options).await
+    }
+
     async fn get_terminal_activity_status(&self, id: String) -> Result<(bool, u64), String> {
         TerminalsServiceImpl::get_terminal_activity_status(self, id).await
     }
@@ -610,6 +674,15 @@ impl TerminalsBackend for TerminalManagerBackend {
         manager.get_terminal_buffer(id, from_seq).await
     }
 
+    async fn get_terminal_buffer_text(
+        &self,
+        id: String,
+        options: PlainTextOptions,
+    ) -> Result<String, String> {
+        let manager = self.terminal_manager().await?;
+        manager.get_terminal_buffer_text(id, options).await
+    }
+
     async fn get_terminal_activity_status(&self, id: String) -> Result<(bool, u64), String> {
         let manager = self.terminal_manager().await?;
         manager.get_terminal_activity_status(id).await
@@ -731,6 +804,14 @@ mod tests {
             panic!("unused in test backend");
         }
 
+        async fn get_terminal_buffer_text(
+            &self,
+            _id: String,
+            _options: PlainTextOptions,
+        ) -> Result<String, String> {
+            panic!("unused in test backend");
+        }
+
         async fn get_terminal_activity_status(&self, _id: String) -> Result<(bool, u64), String> {
             panic!("unused in test backend");
         }
@@ -828,6 +909,14 @@ mod tests {
             panic!("unused in test backend");
         }
 
+        async fn get_terminal_buffer_text(
+            &self,
+            _id: String,
+            _options: PlainTextOptions,
+        ) -> Result<String, String> {
+            panic!("unused in test backend");
+        }
+
         async fn get_terminal_activity_status(&self, _id: String) -> Result<(bool, u64), String> {
             panic!("unused in test backend");
         }
@@ -928,6 +1017,14 @@ mod tests {
             panic!("unused in test backend");
         }
 
+        async fn get_terminal_buffer_text(
+            &self,
+            _id: String,
+            _options: PlainTextOptions,
+        ) -> Result<String, String> {
+            panic!("unused in test backend");
+        }
+
         async fn get_terminal_activity_status(&self, _id: String) -> Result<(bool, u64), String> {
             panic!("unused in test backend");
         }
@@ -1025,6 +1122,14 @@ mod tests {
             panic!("unused in test backend");
         }
 
+        async fn get_terminal_buffer_text(
+            &self,
+            _id: String,
+            _options: PlainTextOptions,
+        ) -> Result<String, String> {
+            panic!("unused in test backend");
+        }
+
         async fn get_terminal_activity_status(&self, _id: String) -> Result<(bool, u64), String> {
             panic!("unused in test backend");
         }
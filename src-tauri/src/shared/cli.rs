@@ -19,6 +19,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 EXAMPLES:
     schaltwerk                    # Open homescreen to select a project
     schaltwerk /path/to/project   # Open specific Git repository
+    schaltwerk --read-only DIR    # Inspect a project without changing it
     schaltwerk --version, -V      # Show version information
     schaltwerk --help, -h         # Show this help message
 "
@@ -27,6 +28,11 @@ pub struct Cli {
     /// Path to a Git repository to open. Opens the homescreen if omitted.
     #[arg(value_name = "DIR")]
     pub dir: Option<PathBuf>,
+
+    /// Open the project without writing to its database or repository, e.g. next to
+    /// another running instance. Sessions, diffs and git history stay viewable.
+    #[arg(long)]
+    pub read_only: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -78,6 +84,14 @@ mod tests {
     fn parses_no_args() {
         let cli = parse_from::<[&str; 0], &str>([]);
         assert!(cli.dir.is_none());
+        assert!(!cli.read_only);
+    }
+
+    #[test]
+    fn parses_read_only_flag_with_dir() {
+        let cli = parse_from(["--read-only", "/tmp/repo"]);
+        assert!(cli.read_only);
+        assert_eq!(cli.dir.as_deref(), Some(std::path::Path::new("/tmp/repo")));
     }
 
     #[test]
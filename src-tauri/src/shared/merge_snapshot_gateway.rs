@@ -122,6 +122,8 @@ mod tests {
             parent_branch: "main".into(),
             squash_commands: vec![],
             reapply_commands: vec![],
+            rebase_continue_commands: vec![],
+            rebase_continue_summary: String::new(),
             default_commit_message: "Merge feature".into(),
             has_conflicts: true,
             conflicting_paths: vec!["src/lib.rs".into()],
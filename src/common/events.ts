@@ -5,10 +5,15 @@ export enum SchaltEvent {
   ArchiveUpdated = 'schaltwerk:archive-updated',
   SessionCancelling = 'schaltwerk:session-cancelling',
   CancelError = 'schaltwerk:cancel-error',
+  SessionCreationProgress = 'schaltwerk:session-creation-progress',
   TerminalCreated = 'schaltwerk:terminal-created',
 
   SessionActivity = 'schaltwerk:session-activity',
+  SessionIdle = 'schaltwerk:session-idle',
+  SessionResumed = 'schaltwerk:session-resumed',
+  SessionAgentStatus = 'schaltwerk:session-agent-status',
   SessionGitStats = 'schaltwerk:session-git-stats',
+  SessionRefreshed = 'schaltwerk:session-refreshed',
   TerminalAttention = 'schaltwerk:terminal-attention',
   TerminalClosed = 'schaltwerk:terminal-closed',
   TerminalAgentStarted = 'schaltwerk:terminal-agent-started',
@@ -26,9 +31,13 @@ export enum SchaltEvent {
   GitOperationFailed = 'schaltwerk:git-operation-failed',
   ProjectFilesUpdated = 'schaltwerk:project-files-updated',
   GitHubStatusChanged = 'schaltwerk:github-status-changed',
+  GitHubPrStatusChanged = 'schaltwerk:github-pr-status-changed',
   AppUpdateResult = 'schaltwerk:app-update-result',
   DevBackendError = 'schaltwerk:dev-backend-error',
   SetupScriptRequested = 'schaltwerk:setup-script-request',
+  SetupScriptFailed = 'schaltwerk:setup-script-failed',
+  SetupScriptOutput = 'schaltwerk:setup-script-output',
+  SetupScriptProgress = 'schaltwerk:setup-script-progress',
   CloneProgress = 'schaltwerk:clone-progress',
   OrchestratorLaunchFailed = 'schaltwerk:orchestrator-launch-failed',
   DiffBaseBranchChanged = 'schaltwerk:diff-base-branch-changed',
@@ -36,9 +45,71 @@ export enum SchaltEvent {
   OpenPrModal = 'schaltwerk:open-pr-modal',
   OpenMergeModal = 'schaltwerk:open-merge-modal',
   SelectAllRequested = 'schaltwerk:select-all-requested',
+  OperationsRecovered = 'schaltwerk:operations-recovered',
+  AutoPrCreated = 'schaltwerk:auto-pr-created',
+  ScheduledSpecsChanged = 'schaltwerk:scheduled-specs-changed',
+  RunScriptCompleted = 'schaltwerk:run-script-completed',
+  MergeCheckOutput = 'schaltwerk:merge-check-output',
 }
 
 
+export interface RecoveredOperation {
+  kind: 'create' | 'cancel' | 'merge'
+  session_name: string
+  action: 'rolled_back' | 'completed' | 'no_changes' | 'needs_repair'
+  detail: string
+}
+
+export interface OperationsRecoveredPayload {
+  projectPath: string
+  operations: RecoveredOperation[]
+}
+
+export interface ScheduledSpec {
+  id: string
+  spec_name: string
+  not_before: string
+  base_branch: string | null
+  agent_type: string | null
+  skip_permissions: boolean | null
+  skip_if_late: boolean
+  status: 'pending' | 'started' | 'skipped' | 'failed' | 'cancelled'
+  created_at: string
+  finished_at: string | null
+  error: string | null
+}
+
+export interface ScheduledSpecsChangedPayload {
+  projectPath: string
+  entries: ScheduledSpec[]
+}
+
+export type SessionCreationStep =
+  | 'reserving_name'
+  | 'creating_worktree'
+  | 'copying_overrides'
+  | 'computing_stats'
+  | 'ready'
+  | 'failed'
+
+export interface SessionCreationProgressPayload {
+  sessionName: string
+  step: SessionCreationStep
+  error?: string
+}
+
+export interface MergeCheckOutputPayload {
+  session_name: string
+  line: string
+}
+
+export interface RunScriptCompletedPayload {
+  terminalId: string
+  sessionName: string | null
+  runId: number
+  exitCode: number | null
+}
+
 export interface SessionActivityUpdated {
   session_id: string
   session_name: string
@@ -48,6 +119,17 @@ export interface SessionActivityUpdated {
   is_blocked: boolean | null
 }
 
+export interface SessionIdleChangedPayload {
+  session_id: string
+  idle: boolean
+}
+
+export interface SessionAgentStatusPayload {
+  session_name: string
+  status: AgentOutputStatus
+  updated_at: string
+}
+
 export interface SessionGitStatsUpdated {
   session_id: string
   session_name: string
@@ -79,6 +161,7 @@ export interface PtyDataPayload {
 export interface GlobalKeepAwakeStatePayload {
   state: 'disabled' | 'active' | 'auto_paused'
   activeCount?: number
+  reason?: 'agent-activity' | 'manual'
 }
 
 export interface ChangedFile {
@@ -89,6 +172,22 @@ export interface ChangedFile {
   changes: number
   is_binary?: boolean
   previous_path?: string
+  staged?: boolean
+}
+
+export interface SessionRefreshedPayload {
+  stats: SessionGitStatsUpdated
+  remote: {
+    has_remote: boolean
+    ahead: number | null
+    behind: number | null
+  }
+  worktree: {
+    operation: 'merge' | 'rebase' | 'cherry_pick' | 'revert' | 'bisect' | 'apply_mailbox' | null
+    head_detached: boolean
+    current_branch: string | null
+  }
+  changed_files: ChangedFile[]
 }
 
 export interface BranchInfo {
@@ -103,9 +202,10 @@ export interface GitOperationPayload {
   session_branch: string
   parent_branch: string
   mode: string
-  operation: 'merge'
+  operation: 'merge' | 'draft_pr' | 'update_from_parent' | 'cherry_pick'
   commit?: string
-  status?: 'started' | 'success' | 'conflict' | 'error'
+  status?: 'started' | 'success' | 'conflict' | 'skipped' | 'error'
+  batch_id?: string
 }
 
 export interface GitOperationFailedPayload extends GitOperationPayload {
@@ -124,6 +224,19 @@ export interface GitHubStatusPayload {
   repository?: GitHubRepositoryPayload | null
 }
 
+export interface GitHubPrStatusChangedPayload {
+  projectPath: string
+  sessionName: string
+  prNumber: number
+  prUrl?: string | null
+  state: string
+  reviewDecision?: string | null
+  checks: 'passing' | 'failing' | 'pending' | 'none'
+  passingChecks: number
+  failingChecks: number
+  pendingChecks: number
+}
+
 export interface GitHubPrPayload {
   branch: string
   url: string
@@ -164,6 +277,30 @@ export interface SetupScriptRequestPayload {
   pending_confirmation?: boolean
 }
 
+export interface SetupScriptFailedPayload {
+  session_name: string
+  exit_code: number | null
+  timed_out: boolean
+  output_tail: string
+}
+
+export type SetupScriptStatus =
+  | { state: 'queued' }
+  | { state: 'running' }
+  | { state: 'succeeded' }
+  | { state: 'failed'; exit_code: number | null }
+  | { state: 'timed_out' }
+
+export interface SetupScriptProgressPayload {
+  session_name: string
+  status: SetupScriptStatus
+}
+
+export interface SetupScriptOutputPayload {
+  session_name: string
+  chunk: string
+}
+
 export interface OrchestratorLaunchFailedPayload {
   terminal_id: string
   error: string
@@ -177,6 +314,7 @@ export interface DiffBaseBranchChangedPayload {
 export interface ProjectValidationErrorPayload {
   path: string
   error: string
+  detectedOldPath?: string
 }
 
 export interface OpenPrModalPayload {
@@ -188,13 +326,19 @@ export interface OpenPrModalPayload {
   mode?: 'squash' | 'reapply'
 }
 
+export interface AutoPrCreatedPayload {
+  sessionName: string
+  branch: string
+  url: string
+}
+
 export interface OpenMergeModalPayload {
   sessionName: string
   mode?: 'squash' | 'reapply'
   commitMessage?: string
 }
 
-import { type EnrichedSession, type Epic } from '../types/session'
+import { type AgentOutputStatus, type EnrichedSession, type Epic } from '../types/session'
 
 export interface SessionsRefreshedEventPayload {
   projectPath: string
@@ -228,10 +372,20 @@ export type EventPayloadMap = {
   [SchaltEvent.TerminalCreated]: { terminal_id: string, cwd: string }
 
   [SchaltEvent.SessionActivity]: SessionActivityUpdated
+  [SchaltEvent.SessionIdle]: SessionIdleChangedPayload
+  [SchaltEvent.SessionResumed]: SessionIdleChangedPayload
+  [SchaltEvent.SessionAgentStatus]: SessionAgentStatusPayload
   [SchaltEvent.SessionGitStats]: SessionGitStatsUpdated
-  [SchaltEvent.TerminalAttention]: { session_id: string, terminal_id: string, needs_attention: boolean }
+  [SchaltEvent.SessionRefreshed]: SessionRefreshedPayload
+  [SchaltEvent.TerminalAttention]: {
+    session_id: string
+    terminal_id: string
+    needs_attention: boolean
+    reason?: 'approval-request'
+    prompt?: string | null
+  }
   [SchaltEvent.TerminalClosed]: { terminal_id: string }
-  [SchaltEvent.TerminalAgentStarted]: { terminal_id: string, session_name?: string }
+  [SchaltEvent.TerminalAgentStarted]: { terminal_id: string, session_name?: string, attempt?: number }
   [SchaltEvent.TerminalForceScroll]: { terminal_id: string }
   [SchaltEvent.GlobalKeepAwakeStateChanged]: GlobalKeepAwakeStatePayload
   [SchaltEvent.PtyData]: PtyDataPayload
@@ -242,6 +396,8 @@ export type EventPayloadMap = {
     session_name: string
     changed_files: ChangedFile[]
     branch_info: BranchInfo
+    changed_paths?: string[]
+    change_count?: number
   }
   [SchaltEvent.FollowUpMessage]: FollowUpMessagePayload
   [SchaltEvent.Selection]: SelectionPayload
@@ -250,9 +406,13 @@ export type EventPayloadMap = {
   [SchaltEvent.GitOperationFailed]: GitOperationFailedPayload
   [SchaltEvent.ProjectFilesUpdated]: string[]
   [SchaltEvent.GitHubStatusChanged]: GitHubStatusPayload
+  [SchaltEvent.GitHubPrStatusChanged]: GitHubPrStatusChangedPayload
   [SchaltEvent.AppUpdateResult]: AppUpdateResultPayload
   [SchaltEvent.DevBackendError]: DevBackendErrorPayload
   [SchaltEvent.SetupScriptRequested]: SetupScriptRequestPayload
+  [SchaltEvent.SetupScriptFailed]: SetupScriptFailedPayload
+  [SchaltEvent.SetupScriptOutput]: SetupScriptOutputPayload
+  [SchaltEvent.SetupScriptProgress]: SetupScriptProgressPayload
   [SchaltEvent.CloneProgress]: CloneProgressPayload
   [SchaltEvent.OrchestratorLaunchFailed]: OrchestratorLaunchFailedPayload
   [SchaltEvent.DiffBaseBranchChanged]: DiffBaseBranchChangedPayload
@@ -260,4 +420,10 @@ export type EventPayloadMap = {
   [SchaltEvent.OpenPrModal]: OpenPrModalPayload
   [SchaltEvent.OpenMergeModal]: OpenMergeModalPayload
   [SchaltEvent.SelectAllRequested]: null
+  [SchaltEvent.OperationsRecovered]: OperationsRecoveredPayload
+  [SchaltEvent.AutoPrCreated]: AutoPrCreatedPayload
+  [SchaltEvent.ScheduledSpecsChanged]: ScheduledSpecsChangedPayload
+  [SchaltEvent.RunScriptCompleted]: RunScriptCompletedPayload
+  [SchaltEvent.MergeCheckOutput]: MergeCheckOutputPayload
+  [SchaltEvent.SessionCreationProgress]: SessionCreationProgressPayload
 }
@@ -191,6 +191,10 @@ export interface Translations {
       mergeDefaultsDesc: string
       autoCancelAfterMerge: string
       mergeToggleNote: string
+      requirePassingTests: string
+      testCommand: string
+      testCommandPlaceholder: string
+      requirePassingTestsNote: string
     }
     projectRun: {
       worktreeSetup: string
@@ -0,0 +1,25 @@
+import { invoke } from '@tauri-apps/api/core'
+import { TauriCommands } from './tauriCommands'
+import type { SessionAgentContent } from '../types/session'
+
+/**
+ * Fetches the spec content and initial prompt of a session. Pass the etag of content
+ * already held to get a body-less `not_modified` answer while it is unchanged.
+ */
+export function fetchSessionAgentContent(
+  name: string,
+  ifNoneMatch: string | null = null
+): Promise<SessionAgentContent> {
+  return invoke<SessionAgentContent>(TauriCommands.SchaltwerkCoreGetSessionAgentContent, {
+    name,
+    ifNoneMatch,
+  })
+}
+
+/** Spec content and initial prompt of a session, always fetched in full. */
+export async function loadSessionAgentContent(
+  name: string
+): Promise<[string | null, string | null]> {
+  const content = await fetchSessionAgentContent(name)
+  return content.status === 'modified' ? [content.spec_content, content.initial_prompt] : [null, null]
+}
@@ -1,8 +1,14 @@
 // Centralized Tauri command names used by the frontend
 export const TauriCommands = {
   AddRecentProject: 'add_recent_project',
+  AgentConfigHealth: 'agent_config_health',
   CheckFolderAccess: 'check_folder_access',
   CloseProject: 'close_project',
+  ListWorkspaces: 'list_workspaces',
+  SaveWorkspace: 'save_workspace',
+  DeleteWorkspace: 'delete_workspace',
+  OpenWorkspace: 'open_workspace',
+  SchaltwerkCoreListWorkspaceSessions: 'schaltwerk_core_list_workspace_sessions',
   CloseTerminal: 'close_terminal',
   ComputeSplitDiffBackend: 'compute_split_diff_backend',
   ComputeUnifiedDiffBackend: 'compute_unified_diff_backend',
@@ -12,18 +18,24 @@ export const TauriCommands = {
   GitHubAuthenticate: 'github_authenticate',
   GitHubConnectProject: 'github_connect_project',
   GitHubCreateReviewedPr: 'github_create_reviewed_pr',
+  GitHubSetAutoPrOnReview: 'github_set_auto_pr_on_review',
   GitHubCreateSessionPr: 'github_create_session_pr',
   GitHubSearchIssues: 'github_search_issues',
   GitHubGetIssueDetails: 'github_get_issue_details',
+  GitHubIssueToSpec: 'github_issue_to_spec',
+  GitHubCreateSessionFromIssue: 'github_create_session_from_issue',
   GitHubSearchPrs: 'github_search_prs',
   GitHubGetPrDetails: 'github_get_pr_details',
   GitHubPreviewPr: 'github_preview_pr',
   GitHubGetPrReviewComments: 'github_get_pr_review_comments',
   GitHubGetPrFeedback: 'github_get_pr_feedback',
+  GitHubMarkPrReady: 'github_mark_pr_ready',
+  GitHubGetLookupCacheStats: 'github_get_lookup_cache_stats',
   CreateNewProject: 'create_new_project',
   CreateRunTerminal: 'create_run_terminal',
   CreateTerminal: 'create_terminal',
   CreateTerminalWithSize: 'create_terminal_with_size',
+  DeleteProjectPromptTemplate: 'delete_project_prompt_template',
   DirectoryExists: 'directory_exists',
   EnsureFolderPermission: 'ensure_folder_permission',
   GetPermissionDiagnostics: 'get_permission_diagnostics',
@@ -35,8 +47,14 @@ export const TauriCommands = {
   GetAgentPreferences: 'get_agent_preferences',
   GetAllAgentBinaryConfigs: 'get_all_agent_binary_configs',
   GetAppVersion: 'get_app_version',
+  GetOrphanedProcesses: 'get_orphaned_processes',
+  GetRecentLogs: 'get_recent_logs',
+  SetLogLevel: 'set_log_level',
+  ExportLogsToFile: 'export_logs_to_file',
   GetAutoUpdateEnabled: 'get_auto_update_enabled',
   GetDevErrorToastsEnabled: 'get_dev_error_toasts_enabled',
+  GetWebhookRequestLog: 'get_webhook_request_log',
+  GetWebhookRequestLogEnabled: 'get_webhook_request_log_enabled',
   GetBaseBranchName: 'get_base_branch_name',
   GetChangedFilesFromMain: 'get_changed_files_from_main',
   HasRemoteTrackingBranch: 'has_remote_tracking_branch',
@@ -50,6 +68,7 @@ export const TauriCommands = {
   GetKeyboardShortcuts: 'get_keyboard_shortcuts',
   GetLastProjectParentDirectory: 'get_last_project_parent_directory',
   GetFileDiffFromMain: 'get_file_diff_from_main',
+  CompareSessions: 'compare_sessions',
   ReadProjectFile: 'read_project_file',
   GetGitGraphHistory: 'get_git_graph_history',
   GetGitGraphCommitFiles: 'get_git_graph_commit_files',
@@ -59,6 +78,10 @@ export const TauriCommands = {
   GetAgentCommandPrefix: 'get_agent_command_prefix',
   SetAgentCommandPrefix: 'set_agent_command_prefix',
   GetOrchestratorWorkingChanges: 'get_orchestrator_working_changes',
+  StageOrchestratorFile: 'stage_orchestrator_file',
+  UnstageOrchestratorFile: 'unstage_orchestrator_file',
+  StageAllOrchestratorChanges: 'stage_all_orchestrator_changes',
+  CommitOrchestratorChanges: 'commit_orchestrator_changes',
   GetProjectActionButtons: 'get_project_action_buttons',
   GetProjectDefaultBaseBranch: 'get_project_default_base_branch',
   GetProjectDefaultBranch: 'get_project_default_branch',
@@ -66,11 +89,24 @@ export const TauriCommands = {
   GetProjectRunScript: 'get_project_run_script',
   GetProjectSessionsSettings: 'get_project_sessions_settings',
   GetGlobalKeepAwakeState: 'get_global_keep_awake_state',
+  GetGlobalKeepAwakeMaxMinutes: 'get_global_keep_awake_max_minutes',
+  GetGlobalKeepAwakeAutoMode: 'get_global_keep_awake_auto_mode',
   GetProjectSettings: 'get_project_settings',
   GetProjectMergePreferences: 'get_project_merge_preferences',
+  GetProjectMergeCommitTrailers: 'get_project_merge_commit_trailers',
+  GetProjectSkipPermissionsPolicy: 'get_project_skip_permissions_policy',
+  GetProjectWorktreeRoot: 'get_project_worktree_root',
+  GetProjectBranchCollisionStrategy: 'get_project_branch_collision_strategy',
+  GetProjectDatabaseBackupsEnabled: 'get_project_database_backups_enabled',
+  GetProjectKeepRecordings: 'get_project_keep_recordings',
+  GetProjectPromptTemplates: 'get_project_prompt_templates',
+  GetProjectSpecFileMirror: 'get_project_spec_file_mirror',
+  GetProjectUpdateFromParentOnStart: 'get_project_update_from_parent_on_start',
   GetRecentProjects: 'get_recent_projects',
+  ListAllProjectsSummary: 'list_all_projects_summary',
   GetSessionPreferences: 'get_session_preferences',
   GetTerminalBuffer: 'get_terminal_buffer',
+  GetTerminalBufferText: 'get_terminal_buffer_text',
   GetTerminalBacklog: 'get_terminal_backlog',
   GetTerminalSettings: 'get_terminal_settings',
   GetTerminalUiPreferences: 'get_terminal_ui_preferences',
@@ -80,6 +116,7 @@ export const TauriCommands = {
   ListAvailableOpenApps: 'list_available_open_apps',
   ListInstalledFonts: 'list_installed_fonts',
   ListProjectBranches: 'list_project_branches',
+  ListDatabaseBackups: 'list_database_backups',
   OpenInApp: 'open_in_app',
   PasteAndSubmitTerminal: 'paste_and_submit_terminal',
   PathExists: 'path_exists',
@@ -99,12 +136,24 @@ export const TauriCommands = {
   RemoveMcpForProject: 'remove_mcp_for_project',
   RemoveRecentProject: 'remove_recent_project',
   RepositoryIsEmpty: 'repository_is_empty',
+  RestoreDatabaseBackup: 'restore_database_backup',
   OpenDocumentsPrivacySettings: 'open_documents_privacy_settings',
   ResetFolderPermissions: 'reset_folder_permissions',
   OpenExternalUrl: 'open_external_url',
   ResizeTerminal: 'resize_terminal',
+  RunProjectScript: 'run_project_script',
   SchaltwerkCoreArchiveSpecSession: 'schaltwerk_core_archive_spec_session',
   SchaltwerkCoreCancelSession: 'schaltwerk_core_cancel_session',
+  SchaltwerkCoreClassifyWorktrees: 'schaltwerk_core_classify_worktrees',
+  SchaltwerkCoreCreateVersionGroup: 'schaltwerk_core_create_version_group',
+  SchaltwerkCoreDiffVersionGroup: 'schaltwerk_core_diff_version_group',
+  SchaltwerkCoreGetVersionGroupComparison: 'schaltwerk_core_get_version_group_comparison',
+  SchaltwerkCoreSelectVersionWinner: 'schaltwerk_core_select_version_winner',
+  SchaltwerkCoreFetchRemoteStatus: 'schaltwerk_core_fetch_remote_status',
+  SchaltwerkCoreForkSession: 'schaltwerk_core_fork_session',
+  SchaltwerkCoreRefreshSession: 'schaltwerk_core_refresh_session',
+  GenerateCommitMessage: 'generate_commit_message',
+  SchaltwerkCoreCleanupOrphanedWorktrees: 'schaltwerk_core_cleanup_orphaned_worktrees',
   SchaltwerkCoreConvertSessionToDraft: 'schaltwerk_core_convert_session_to_draft',
   SchaltwerkCoreCreateSession: 'schaltwerk_core_create_session',
   SchaltwerkCoreCloneProject: 'schaltwerk_core_clone_project',
@@ -123,17 +172,25 @@ export const TauriCommands = {
   SchaltwerkCoreGetOrchestratorSkipPermissions: 'schaltwerk_core_get_orchestrator_skip_permissions',
   SchaltwerkCoreGetMergePreview: 'schaltwerk_core_get_merge_preview',
   SchaltwerkCoreGetMergePreviewWithWorktree: 'schaltwerk_core_get_merge_preview_with_worktree',
+  SchaltwerkCoreSuggestMergeCommitMessage: 'schaltwerk_core_suggest_merge_commit_message',
+  SchaltwerkCorePreviewUpdateFromParent: 'schaltwerk_core_preview_update_from_parent',
   SchaltwerkCoreHasUncommittedChanges: 'schaltwerk_core_has_uncommitted_changes',
   SchaltwerkCoreListArchivedSpecs: 'schaltwerk_core_list_archived_specs',
   SchaltwerkCoreListEpics: 'schaltwerk_core_list_epics',
   SchaltwerkCoreCreateEpic: 'schaltwerk_core_create_epic',
   SchaltwerkCoreUpdateEpic: 'schaltwerk_core_update_epic',
   SchaltwerkCoreDeleteEpic: 'schaltwerk_core_delete_epic',
+  SchaltwerkCoreSetFocusedTerminal: 'schaltwerk_core_set_focused_terminal',
   SchaltwerkCoreSetItemEpic: 'schaltwerk_core_set_item_epic',
+  SchaltwerkCoreSetSessionGroup: 'schaltwerk_core_set_session_group',
+  SchaltwerkCoreListSessionGroups: 'schaltwerk_core_list_session_groups',
+  SchaltwerkCoreSetTerminalRecording: 'schaltwerk_core_set_terminal_recording',
+  SchaltwerkCoreGetTerminalRecordings: 'schaltwerk_core_get_terminal_recordings',
   SchaltwerkCoreListEnrichedSessions: 'schaltwerk_core_list_enriched_sessions',
   SchaltwerkCoreListProjectFiles: 'schaltwerk_core_list_project_files',
   SchaltwerkCoreListCodexModels: 'schaltwerk_core_list_codex_models',
   SchaltwerkCoreListSessionsByState: 'schaltwerk_core_list_sessions_by_state',
+  SchaltwerkCoreListSessionsAwaitingApproval: 'schaltwerk_core_list_sessions_awaiting_approval',
   SchaltwerkCoreLinkSessionToPr: 'schaltwerk_core_link_session_to_pr',
   SchaltwerkCoreUnlinkSessionFromPr: 'schaltwerk_core_unlink_session_from_pr',
   SchaltwerkCoreLogFrontendMessage: 'schaltwerk_core_log_frontend_message',
@@ -141,11 +198,29 @@ export const TauriCommands = {
   SchaltwerkCoreMarkSessionReady: 'schaltwerk_core_mark_session_ready',
   SchaltwerkCoreRenameSessionDisplayName: 'schaltwerk_core_rename_session_display_name',
   SchaltwerkCoreRenameVersionGroup: 'schaltwerk_core_rename_version_group',
+  SchaltwerkCoreSetVersionGroupLabel: 'schaltwerk_core_set_version_group_label',
+  SchaltwerkCoreRelocateProject: 'schaltwerk_core_relocate_project',
   SchaltwerkCoreResetOrchestrator: 'schaltwerk_core_reset_orchestrator',
   SchaltwerkCoreResetSessionWorktree: 'schaltwerk_core_reset_session_worktree',
+  SchaltwerkCoreStashSessionChanges: 'schaltwerk_core_stash_session_changes',
+  SchaltwerkCoreRecreateSessionBranch: 'schaltwerk_core_recreate_session_branch',
+  SchaltwerkCoreUnstashSessionChanges: 'schaltwerk_core_unstash_session_changes',
+  SchaltwerkCoreCreateSessionSnapshot: 'schaltwerk_core_create_session_snapshot',
+  SchaltwerkCoreListSessionSnapshots: 'schaltwerk_core_list_session_snapshots',
+  SchaltwerkCoreRestoreSessionSnapshot: 'schaltwerk_core_restore_session_snapshot',
+  SchaltwerkCoreRunSetupScript: 'schaltwerk_core_run_setup_script',
   // Discard changes for a single file (session or orchestrator)
   SchaltwerkCoreDiscardFileInSession: 'schaltwerk_core_discard_file_in_session',
+  SchaltwerkCoreDiscardFilesInSession: 'schaltwerk_core_discard_files_in_session',
+  SchaltwerkCorePreviewDiscardFileInSession: 'schaltwerk_core_preview_discard_file_in_session',
+  SchaltwerkCoreRevertFileToCommit: 'schaltwerk_core_revert_file_to_commit',
+  SchaltwerkCoreListScheduledSpecs: 'schaltwerk_core_list_scheduled_specs',
+  SchaltwerkCoreScheduleSpec: 'schaltwerk_core_schedule_spec',
+  SchaltwerkCoreCancelScheduledSpec: 'schaltwerk_core_cancel_scheduled_spec',
+  SchaltwerkCoreGenerateSessionName: 'schaltwerk_core_generate_session_name',
   SchaltwerkCoreDiscardFileInOrchestrator: 'schaltwerk_core_discard_file_in_orchestrator',
+  SchaltwerkCoreDiscardHunkInSession: 'schaltwerk_core_discard_hunk_in_session',
+  SchaltwerkCoreDiscardHunkInOrchestrator: 'schaltwerk_core_discard_hunk_in_orchestrator',
   SchaltwerkCoreRestoreArchivedSpec: 'schaltwerk_core_restore_archived_spec',
   SchaltwerkCoreSetAgentType: 'schaltwerk_core_set_agent_type',
   SchaltwerkCoreSetArchiveMaxEntries: 'schaltwerk_core_set_archive_max_entries',
@@ -161,11 +236,35 @@ export const TauriCommands = {
   SchaltwerkCoreStartClaudeWithRestart: 'schaltwerk_core_start_claude_with_restart',
   SchaltwerkCoreStartSessionAgent: 'schaltwerk_core_start_session_agent',
   SchaltwerkCoreStartSessionAgentWithRestart: 'schaltwerk_core_start_session_agent_with_restart',
+  SchaltwerkCoreRestartSessionPreservingContext: 'schaltwerk_core_restart_session_preserving_context',
+  SchaltwerkCoreRestartCrashedAgent: 'schaltwerk_core_restart_crashed_agent',
+  SchaltwerkCoreGetSessionLaunchOverrides: 'schaltwerk_core_get_session_launch_overrides',
+  SchaltwerkCoreSetSessionLaunchOverrides: 'schaltwerk_core_set_session_launch_overrides',
+  SchaltwerkCoreGetSessionExtraCliArgs: 'schaltwerk_core_get_session_extra_cli_args',
+  SchaltwerkCoreSetSessionExtraCliArgs: 'schaltwerk_core_set_session_extra_cli_args',
+  SchaltwerkCoreGetSessionEnvVars: 'schaltwerk_core_get_session_env_vars',
+  SchaltwerkCoreSetSessionEnvVars: 'schaltwerk_core_set_session_env_vars',
+  SchaltwerkCoreGetSessionLaunchSnapshot: 'schaltwerk_core_get_session_launch_snapshot',
+  SchaltwerkCorePreviewSessionLaunchCommand: 'schaltwerk_core_preview_session_launch_command',
   SchaltwerkCoreUnmarkReady: 'schaltwerk_core_unmark_ready',
   SchaltwerkCoreUnmarkSessionReady: 'schaltwerk_core_unmark_session_ready',
   SchaltwerkCoreUpdateSpecContent: 'schaltwerk_core_update_spec_content',
+  SchaltwerkCoreSetSpecDependency: 'schaltwerk_core_set_spec_dependency',
+  SchaltwerkCoreGetAgentStats: 'schaltwerk_core_get_agent_stats',
+  SchaltwerkCoreGetSessionCommits: 'schaltwerk_core_get_session_commits',
+  SchaltwerkCoreCherryPickSessionCommits: 'schaltwerk_core_cherry_pick_session_commits',
+  SchaltwerkCoreRunDbMaintenance: 'schaltwerk_core_run_db_maintenance',
+  SchaltwerkCoreGetDatabaseInfo: 'schaltwerk_core_get_database_info',
+  SchaltwerkCoreGetSessionHistory: 'schaltwerk_core_get_session_history',
+  SchaltwerkCoreConfirmSkipPermissions: 'schaltwerk_core_confirm_skip_permissions',
+  SchaltwerkCoreExportSpecsToDirectory: 'schaltwerk_core_export_specs_to_directory',
+  SchaltwerkCoreExportSpecToFile: 'schaltwerk_core_export_spec_to_file',
+  SchaltwerkCoreImportSpecsFromDirectory: 'schaltwerk_core_import_specs_from_directory',
+  SchaltwerkCoreWatchSpecsDir: 'schaltwerk_core_watch_specs_dir',
+  SchaltwerkCoreUnwatchSpecsDir: 'schaltwerk_core_unwatch_specs_dir',
   SchaltwerkCoreMergeSessionToMain: 'schaltwerk_core_merge_session_to_main',
   SchaltwerkCoreUpdateSessionFromParent: 'schaltwerk_core_update_session_from_parent',
+  SchaltwerkCoreUpdateAllSessionsFromParent: 'schaltwerk_core_update_all_sessions_from_parent',
   SetAgentBinaryPath: 'set_agent_binary_path',
   SetAgentCliArgs: 'set_agent_cli_args',
   SetAgentEnvVars: 'set_agent_env_vars',
@@ -182,11 +281,24 @@ export const TauriCommands = {
   SetProjectSessionsSettings: 'set_project_sessions_settings',
   SetProjectSettings: 'set_project_settings',
   SetProjectMergePreferences: 'set_project_merge_preferences',
+  SetProjectMergeCommitTrailers: 'set_project_merge_commit_trailers',
+  SetProjectSkipPermissionsPolicy: 'set_project_skip_permissions_policy',
+  SetProjectWorktreeRoot: 'set_project_worktree_root',
+  SetProjectBranchCollisionStrategy: 'set_project_branch_collision_strategy',
+  SetProjectDatabaseBackupsEnabled: 'set_project_database_backups_enabled',
+  SetProjectKeepRecordings: 'set_project_keep_recordings',
+  SetProjectSpecFileMirror: 'set_project_spec_file_mirror',
+  SetProjectUpdateFromParentOnStart: 'set_project_update_from_parent_on_start',
+  SaveProjectPromptTemplate: 'save_project_prompt_template',
   SetSessionPreferences: 'set_session_preferences',
   SetAutoUpdateEnabled: 'set_auto_update_enabled',
   EnableGlobalKeepAwake: 'enable_global_keep_awake',
   DisableGlobalKeepAwake: 'disable_global_keep_awake',
+  SetGlobalKeepAwakeMaxMinutes: 'set_global_keep_awake_max_minutes',
+  SetGlobalKeepAwakeAutoMode: 'set_global_keep_awake_auto_mode',
+  SetGlobalKeepAwakeActivityMinutes: 'set_global_keep_awake_activity_minutes',
   SetDevErrorToastsEnabled: 'set_dev_error_toasts_enabled',
+  SetWebhookRequestLogEnabled: 'set_webhook_request_log_enabled',
   SetTerminalCollapsed: 'set_terminal_collapsed',
   SetTerminalDividerPosition: 'set_terminal_divider_position',
   SetTerminalSettings: 'set_terminal_settings',
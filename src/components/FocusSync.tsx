@@ -1,9 +1,12 @@
 import { useEffect } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { TauriCommands } from '../common/tauriCommands'
 import { useSelection } from '../hooks/useSelection'
 import { useFocus } from '../contexts/FocusContext'
+import { logger } from '../utils/logger'
 
 export function FocusSync() {
-  const { selection } = useSelection()
+  const { selection, terminals } = useSelection()
   const { getFocusForSession, setCurrentFocus, currentFocus } = useFocus()
 
   useEffect(() => {
@@ -26,5 +29,18 @@ export function FocusSync() {
     }
   }, [selection, getFocusForSession, setCurrentFocus, currentFocus])
 
+  // Approval prompts in the visible agent terminal don't need to raise attention
+  const focusedTerminal = selection.kind === 'session' && terminals.top ? terminals.top : null
+  useEffect(() => {
+    const report = async () => {
+      try {
+        await invoke(TauriCommands.SchaltwerkCoreSetFocusedTerminal, { terminalId: focusedTerminal })
+      } catch (error) {
+        logger.debug('[FocusSync] Failed to report focused terminal', error)
+      }
+    }
+    void report()
+  }, [focusedTerminal])
+
   return null
 }
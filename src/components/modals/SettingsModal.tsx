@@ -94,6 +94,7 @@ interface AgentBinaryConfig {
     custom_path: string | null
     auto_detect: boolean
     detected_binaries: DetectedBinary[]
+    version?: string | null
 }
 
 interface CategoryConfig {
@@ -1126,6 +1127,39 @@ export function SettingsModal({ open, onClose, onOpenTutorial, initialTab }: Pro
                         <p className="text-caption text-text-muted mt-2">
                             {t.settings.projectGeneral.mergeToggleNote}
                         </p>
+                        <label className="flex items-center gap-3 text-sm text-text-primary mt-4">
+                            <input
+                                type="checkbox"
+                                checked={mergePreferences.requirePassingTests ?? false}
+                                onChange={(event) => {
+                                    setMergePreferences(prev => ({
+                                        ...prev,
+                                        requirePassingTests: event.target.checked,
+                                    }))
+                                    setHasUnsavedChanges(true)
+                                }}
+                                className="rounded border-border-strong bg-bg-elevated text-accent-blue focus:ring-accent-blue"
+                            />
+                            <span>{t.settings.projectGeneral.requirePassingTests}</span>
+                        </label>
+                        {mergePreferences.requirePassingTests && (
+                            <div className="mt-3">
+                                <label className="block text-caption text-text-tertiary mb-1">{t.settings.projectGeneral.testCommand}</label>
+                                <input
+                                    type="text"
+                                    value={mergePreferences.testCommand ?? ''}
+                                    onChange={(e) => {
+                                        setMergePreferences(prev => ({ ...prev, testCommand: e.target.value || null }))
+                                        setHasUnsavedChanges(true)
+                                    }}
+                                    placeholder={t.settings.projectGeneral.testCommandPlaceholder}
+                                    className="w-full bg-bg-tertiary text-text-primary rounded px-3 py-2 border border-white/10 placeholder-text-muted focus:outline-none focus:border-[var(--color-border-focus)] transition-colors"
+                                />
+                            </div>
+                        )}
+                        <p className="text-caption text-text-muted mt-2">
+                            {t.settings.projectGeneral.requirePassingTestsNote}
+                        </p>
                     </div>
                 </div>
             </div>
@@ -1606,7 +1640,15 @@ fi`}
                                     <div className="font-mono text-body text-green-400">
                                         {binaryConfigs[activeAgentTab].custom_path}
                                     </div>
-                                    <div className="text-caption text-text-muted">{t.settings.environment.customPath}</div>
+                                    <div className="flex items-center gap-2 text-caption">
+                                        <span className="text-text-muted">{t.settings.environment.customPath}</span>
+                                        {binaryConfigs[activeAgentTab].version && (
+                                            <>
+                                                <span className="text-text-muted">•</span>
+                                                <span className="text-text-tertiary">v{binaryConfigs[activeAgentTab].version}</span>
+                                            </>
+                                        )}
+                                    </div>
                                     <button
                                         onClick={() => { void handleBinaryPathChange(activeAgentTab, null) }}
                                         className="settings-btn-text text-caption"
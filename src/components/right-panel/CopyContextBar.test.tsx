@@ -93,7 +93,7 @@ describe('CopyContextBar', () => {
     mockInvoke.mockImplementation(async (cmd: string, _args?: unknown) => {
       switch (cmd) {
         case TauriCommands.SchaltwerkCoreGetSessionAgentContent:
-          return ['# Spec content', null]
+          return { status: 'modified', spec_content: '# Spec content', initial_prompt: null, etag: 'etag' }
         case TauriCommands.GetChangedFilesFromMain:
           return [makeChangedFile({ path: 'file1.txt', change_type: 'modified' })]
         case TauriCommands.ComputeUnifiedDiffBackend:
@@ -158,7 +158,7 @@ describe('CopyContextBar', () => {
   it('disables spec when not available while keeping diff/files enabled', async () => {
     mockInvoke.mockImplementation(async (cmd: string) => {
       if (cmd === TauriCommands.SchaltwerkCoreGetSessionAgentContent) {
-        return [null, null]
+        return { status: 'modified', spec_content: null, initial_prompt: null, etag: 'etag' }
       }
       if (cmd === TauriCommands.GetChangedFilesFromMain) {
         return [makeChangedFile({ path: 'file.txt', change_type: 'modified' })]
@@ -204,7 +204,7 @@ describe('CopyContextBar', () => {
     mockInvoke.mockImplementation(async (cmd: string, args?: unknown) => {
       switch (cmd) {
         case TauriCommands.SchaltwerkCoreGetSessionAgentContent:
-          return ['# Spec content', null]
+          return { status: 'modified', spec_content: '# Spec content', initial_prompt: null, etag: 'etag' }
         case TauriCommands.GetChangedFilesFromMain:
           return [
             makeChangedFile({ path: 'file1.txt', change_type: 'modified' }),
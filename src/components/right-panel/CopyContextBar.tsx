@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { TauriCommands } from '../../common/tauriCommands'
+import { loadSessionAgentContent } from '../../common/sessionAgentContent'
 import { useToast } from '../../common/toast/ToastProvider'
 import { useTranslation } from '../../common/i18n'
 import { listenEvent, SchaltEvent } from '../../common/eventSystem'
@@ -146,7 +147,7 @@ export function CopyContextBar({ sessionName }: CopyContextBarProps) {
 
   const fetchSpecText = useCallback(async () => {
     if (specCacheRef.current !== null) return specCacheRef.current
-    const [draftContent, initialPrompt] = await invoke<[string | null, string | null]>(TauriCommands.SchaltwerkCoreGetSessionAgentContent, { name: sessionName })
+    const [draftContent, initialPrompt] = await loadSessionAgentContent(sessionName)
     const specText = (draftContent ?? initialPrompt ?? '').trimEnd()
     specCacheRef.current = specText
     return specText
@@ -222,7 +223,7 @@ export function CopyContextBar({ sessionName }: CopyContextBarProps) {
   const loadInitialData = useCallback(async () => {
     try {
       const [specPair, files] = await Promise.all([
-        invoke<[string | null, string | null]>(TauriCommands.SchaltwerkCoreGetSessionAgentContent, { name: sessionName }),
+        loadSessionAgentContent(sessionName),
         invoke<ChangedFile[]>(TauriCommands.GetChangedFilesFromMain, { sessionName }),
       ])
 
@@ -296,7 +297,7 @@ export function CopyContextBar({ sessionName }: CopyContextBarProps) {
           const match = targetSessions.find((session) => session.info?.session_id === sessionName)
           if (!match) return
           try {
-            const specPair = await invoke<[string | null, string | null]>(TauriCommands.SchaltwerkCoreGetSessionAgentContent, { name: sessionName })
+            const specPair = await loadSessionAgentContent(sessionName)
             const specText = (specPair?.[0] ?? specPair?.[1] ?? '').trimEnd()
             specCacheRef.current = specText
             setAvailability(prev => ({ ...prev, spec: specText.length > 0 }))
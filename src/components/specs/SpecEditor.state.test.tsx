@@ -119,7 +119,7 @@ describe('SpecEditor spec content persistence', () => {
         case TauriCommands.SchaltwerkCoreListEnrichedSessions:
           return [baseSession]
         case TauriCommands.SchaltwerkCoreGetSessionAgentContent:
-          return [baseSession.info.spec_content, null]
+          return { status: 'modified', spec_content: baseSession.info.spec_content, initial_prompt: null, etag: 'etag' }
         case TauriCommands.GetProjectSessionsSettings:
           return { filter_mode: 'all', sort_mode: 'name' }
         case TauriCommands.SetProjectSessionsSettings:
@@ -77,7 +77,7 @@ describe('SpecEditor keyboard shortcuts', () => {
 
     vi.mocked(invoke).mockImplementation(async (cmd) => {
       if (cmd === TauriCommands.SchaltwerkCoreGetSessionAgentContent) {
-        return ['Test spec content', null]
+        return { status: 'modified', spec_content: 'Test spec content', initial_prompt: null, etag: 'etag' }
       }
       if (cmd === TauriCommands.SchaltwerkCoreUpdateSpecContent) {
         return undefined
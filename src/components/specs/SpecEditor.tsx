@@ -1,5 +1,6 @@
 import { useEffect, useRef, useState, useMemo, useCallback } from 'react'
 import { TauriCommands } from '../../common/tauriCommands'
+import { loadSessionAgentContent } from '../../common/sessionAgentContent'
 import { invoke } from '@tauri-apps/api/core'
 import { VscCopy, VscPlay, VscEye, VscEdit, VscBeaker, VscComment } from 'react-icons/vsc'
 import { AnimatedText } from '../common/AnimatedText'
@@ -127,10 +128,7 @@ export function SpecEditor({ sessionName, onStart, disableFocusShortcut = false,
 
     void (async () => {
       try {
-        const [draftContent, initialPrompt] = await invoke<[string | null, string | null]>(
-          TauriCommands.SchaltwerkCoreGetSessionAgentContent,
-          { name: sessionName }
-        )
+        const [draftContent, initialPrompt] = await loadSessionAgentContent(sessionName)
 
         if (cancelled) return
 
@@ -18,6 +18,7 @@ export interface AgentBinaryConfig {
     custom_path: string | null
     auto_detect: boolean
     detected_binaries: DetectedBinary[]
+    version?: string | null
 }
 
 export type AgentType =
@@ -14,6 +14,7 @@ export interface CreateReviewedPrArgs {
   defaultBranch?: string
   commitMessage?: string
   repository?: string
+  draft?: boolean
 }
 
 export interface GithubIntegrationValue {
@@ -174,6 +175,7 @@ export function useGithubIntegration(): GithubIntegrationValue {
             defaultBranch,
             commitMessage: args.commitMessage,
             repository: repositoryName,
+            draft: args.draft,
           }
         })
 
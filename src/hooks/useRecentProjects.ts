@@ -10,6 +10,16 @@ export interface RecentProject {
   lastOpened: number
 }
 
+export interface ProjectSessionSummary {
+  path: string
+  name: string
+  unavailable: boolean
+  running_sessions: number
+  reviewed_sessions: number
+  specs: number
+  last_activity?: string | null
+}
+
 interface UseRecentProjectsOptions {
   onOpenProject: (path: string) => void
   onOperationSuccess?: () => void
@@ -84,6 +84,8 @@ interface SessionPreferences {
 export interface ProjectMergePreferences {
     autoCancelAfterMerge: boolean
     autoCancelAfterPr: boolean
+    requirePassingTests?: boolean
+    testCommand?: string | null
 }
 
 export interface SettingsSaveResult {
@@ -180,7 +182,9 @@ export const useSettings = () => {
         await invoke(TauriCommands.SetProjectMergePreferences, {
             preferences: {
                 auto_cancel_after_merge: mergePreferences.autoCancelAfterMerge,
-                auto_cancel_after_pr: mergePreferences.autoCancelAfterPr
+                auto_cancel_after_pr: mergePreferences.autoCancelAfterPr,
+                require_passing_tests: mergePreferences.requirePassingTests,
+                test_command: mergePreferences.testCommand,
             }
         })
     }, [])
@@ -360,12 +364,17 @@ export const useSettings = () => {
 
     const loadMergePreferences = useCallback(async (): Promise<ProjectMergePreferences> => {
         try {
-            const preferences = await invoke<{ auto_cancel_after_merge?: boolean; auto_cancel_after_pr?: boolean }>(
-                TauriCommands.GetProjectMergePreferences
-            )
+            const preferences = await invoke<{
+                auto_cancel_after_merge?: boolean
+                auto_cancel_after_pr?: boolean
+                require_passing_tests?: boolean
+                test_command?: string | null
+            }>(TauriCommands.GetProjectMergePreferences)
             return {
                 autoCancelAfterMerge: preferences?.auto_cancel_after_merge !== false,
                 autoCancelAfterPr: preferences?.auto_cancel_after_pr === true,
+                requirePassingTests: preferences?.require_passing_tests,
+                testCommand: preferences?.test_command,
             }
         } catch (error) {
             if (isProjectUnavailableError(error)) {
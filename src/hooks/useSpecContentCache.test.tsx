@@ -15,6 +15,13 @@ vi.mock('@tauri-apps/api/core', () => ({
   invoke: vi.fn(),
 }))
 
+const agentContent = (specContent: string | null, initialPrompt: string | null, etag = 'etag-1') => ({
+  status: 'modified' as const,
+  spec_content: specContent,
+  initial_prompt: initialPrompt,
+  etag,
+})
+
 describe('useSpecContentCache', () => {
   const mockInvoke = vi.mocked(TauriCore.invoke)
 
@@ -28,7 +35,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('fetches content from backend on first load', async () => {
-    mockInvoke.mockResolvedValue(['Draft content', 'Initial prompt'])
+    mockInvoke.mockResolvedValue(agentContent('Draft content', 'Initial prompt'))
 
     const { result } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -45,12 +52,12 @@ describe('useSpecContentCache', () => {
     expect(mockInvoke).toHaveBeenCalledTimes(1)
     expect(mockInvoke).toHaveBeenCalledWith(
       'schaltwerk_core_get_session_agent_content',
-      { name: 'test-session' }
+      { name: 'test-session', ifNoneMatch: null }
     )
   })
 
   it('uses draft content over initial prompt when both are present', async () => {
-    mockInvoke.mockResolvedValue(['Draft content', 'Initial prompt'])
+    mockInvoke.mockResolvedValue(agentContent('Draft content', 'Initial prompt'))
 
     const { result } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -63,7 +70,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('falls back to initial prompt when draft content is null', async () => {
-    mockInvoke.mockResolvedValue([null, 'Initial prompt'])
+    mockInvoke.mockResolvedValue(agentContent(null, 'Initial prompt'))
 
     const { result } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -76,7 +83,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('uses empty string when both draft and prompt are null', async () => {
-    mockInvoke.mockResolvedValue([null, null])
+    mockInvoke.mockResolvedValue(agentContent(null, null))
 
     const { result } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -89,7 +96,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('caches content for running sessions and skips backend on second load', async () => {
-    mockInvoke.mockResolvedValue(['Cached content', null])
+    mockInvoke.mockResolvedValue(agentContent('Cached content', null))
 
     const { result: result1 } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -112,7 +119,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('caches content for reviewed sessions and skips backend on second load', async () => {
-    mockInvoke.mockResolvedValue(['Reviewed content', null])
+    mockInvoke.mockResolvedValue(agentContent('Reviewed content', null))
 
     const { result: result1 } = renderHook(() =>
       useSpecContentCache('test-session', 'reviewed')
@@ -135,8 +142,8 @@ describe('useSpecContentCache', () => {
   })
 
   it('always fetches content for spec sessions (not cached)', async () => {
-    mockInvoke.mockResolvedValueOnce(['First fetch', null])
-    mockInvoke.mockResolvedValueOnce(['Second fetch', null])
+    mockInvoke.mockResolvedValueOnce(agentContent('First fetch', null))
+    mockInvoke.mockResolvedValueOnce(agentContent('Second fetch', null))
 
     const { result: result1 } = renderHook(() =>
       useSpecContentCache('test-session', 'spec')
@@ -175,7 +182,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('updateContent updates content and cache', async () => {
-    mockInvoke.mockResolvedValue(['Initial content', null])
+    mockInvoke.mockResolvedValue(agentContent('Initial content', null))
 
     const { result } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -198,7 +205,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('invalidateCache removes session from cache', async () => {
-    mockInvoke.mockResolvedValue(['Cached content', null])
+    mockInvoke.mockResolvedValue(agentContent('Cached content', null))
 
     const { result: result1 } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -214,7 +221,7 @@ describe('useSpecContentCache', () => {
     })
 
     mockInvoke.mockClear()
-    mockInvoke.mockResolvedValue(['Fresh content', null])
+    mockInvoke.mockResolvedValue(agentContent('Fresh content', null))
 
     const { result: result2 } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -228,7 +235,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('invalidateSpecCache function removes specific session from cache', async () => {
-    mockInvoke.mockResolvedValue(['Cached content', null])
+    mockInvoke.mockResolvedValue(agentContent('Cached content', null))
 
     const { result } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -242,7 +249,7 @@ describe('useSpecContentCache', () => {
     invalidateSpecCache('test-session')
 
     mockInvoke.mockClear()
-    mockInvoke.mockResolvedValue(['Fresh content', null])
+    mockInvoke.mockResolvedValue(agentContent('Fresh content', null))
 
     const { result: result2 } = renderHook(() =>
       useSpecContentCache('test-session', 'running')
@@ -256,7 +263,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('clearAllSpecCache clears entire cache', async () => {
-    mockInvoke.mockResolvedValue(['Session 1 content', null])
+    mockInvoke.mockResolvedValue(agentContent('Session 1 content', null))
 
     const { result: result1 } = renderHook(() =>
       useSpecContentCache('session-1', 'running')
@@ -265,7 +272,7 @@ describe('useSpecContentCache', () => {
     await flushPromises()
     expect(result1.current.loading).toBe(false)
 
-    mockInvoke.mockResolvedValue(['Session 2 content', null])
+    mockInvoke.mockResolvedValue(agentContent('Session 2 content', null))
 
     const { result: result2 } = renderHook(() =>
       useSpecContentCache('session-2', 'running')
@@ -279,7 +286,7 @@ describe('useSpecContentCache', () => {
     clearAllSpecCache()
 
     mockInvoke.mockClear()
-    mockInvoke.mockResolvedValue(['Fresh session 1', null])
+    mockInvoke.mockResolvedValue(agentContent('Fresh session 1', null))
 
     const { result: result3 } = renderHook(() =>
       useSpecContentCache('session-1', 'running')
@@ -298,7 +305,7 @@ describe('useSpecContentCache', () => {
       mockInvoke.mockImplementation(
         () =>
           new Promise((resolve) => {
-            setTimeout(() => resolve(['Content', null]), 10)
+            setTimeout(() => resolve(agentContent('Content', null)), 10)
           })
       )
 
@@ -314,7 +321,7 @@ describe('useSpecContentCache', () => {
 
       expect(mockInvoke).toHaveBeenCalledWith(
         'schaltwerk_core_get_session_agent_content',
-        { name: 'session-2' }
+        { name: 'session-2', ifNoneMatch: null }
       )
     } finally {
       vi.useRealTimers()
@@ -322,7 +329,7 @@ describe('useSpecContentCache', () => {
   })
 
   it('handles switching between sessionStates correctly', async () => {
-    mockInvoke.mockResolvedValue(['Content', null])
+    mockInvoke.mockResolvedValue(agentContent('Content', null))
 
     const { result, rerender } = renderHook(
       ({ sessionState }: { sessionState: 'spec' | 'processing' | 'running' | 'reviewed' }) =>
@@ -349,4 +356,29 @@ describe('useSpecContentCache', () => {
 
     expect(mockInvoke).not.toHaveBeenCalled()
   })
+
+  it('revalidates spec content with its etag and keeps it when not modified', async () => {
+    mockInvoke.mockResolvedValueOnce(agentContent('Spec body', null, 'etag-spec'))
+
+    const { result: result1 } = renderHook(() =>
+      useSpecContentCache('test-session', 'spec')
+    )
+
+    await flushPromises()
+    expect(result1.current.content).toBe('Spec body')
+
+    mockInvoke.mockResolvedValueOnce({ status: 'not_modified', etag: 'etag-spec' })
+
+    const { result: result2 } = renderHook(() =>
+      useSpecContentCache('test-session', 'spec')
+    )
+
+    await flushPromises()
+    expect(result2.current.loading).toBe(false)
+    expect(result2.current.content).toBe('Spec body')
+    expect(mockInvoke).toHaveBeenLastCalledWith(
+      'schaltwerk_core_get_session_agent_content',
+      { name: 'test-session', ifNoneMatch: 'etag-spec' }
+    )
+  })
 })
@@ -1,11 +1,11 @@
 import { useCallback, useEffect, useState } from 'react'
-import { TauriCommands } from '../common/tauriCommands'
-import { invoke } from '@tauri-apps/api/core'
+import { fetchSessionAgentContent } from '../common/sessionAgentContent'
 import { logger } from '../utils/logger'
 
 interface SpecCacheEntry {
   content: string
   isStatic: boolean
+  etag: string | null
 }
 
 const specCache = new Map<string, SpecCacheEntry>()
@@ -39,14 +39,18 @@ export function useSpecContentCache(
 
     setError(null)
 
-    invoke<[string | null, string | null]>(TauriCommands.SchaltwerkCoreGetSessionAgentContent, { name: sessionName })
-      .then(([draftContent, initialPrompt]) => {
+    fetchSessionAgentContent(sessionName, cached?.etag ?? null)
+      .then((response) => {
         if (!mounted) return
-        const text: string = draftContent ?? initialPrompt ?? ''
+        const text: string =
+          response.status === 'modified'
+            ? response.spec_content ?? response.initial_prompt ?? ''
+            : cached?.content ?? ''
 
         specCache.set(sessionName, {
           content: text,
           isStatic,
+          etag: response.etag,
         })
 
         setContent(text)
@@ -71,6 +75,7 @@ export function useSpecContentCache(
     specCache.set(sessionName, {
       content: value,
       isStatic: false,
+      etag: null,
     })
   }, [sessionName])
 
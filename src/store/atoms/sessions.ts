@@ -787,6 +787,10 @@ const loadingStateAtom = atom<boolean>(false)
 const settingsLoadedAtom = atom<boolean>(false)
 const autoCancelAfterMergeStateAtom = atom<boolean>(true)
 const autoCancelAfterPrStateAtom = atom<boolean>(false)
+const mergeCheckPreferencesStateAtom = atom<{ require_passing_tests: boolean; test_command: string | null }>({
+    require_passing_tests: false,
+    test_command: null,
+})
 const currentSelectionStateAtom = atom<string | null>(null)
 
 let lastPersistedFilterMode: FilterMode | null = null
@@ -1145,7 +1149,12 @@ export const initializeSessionsSettingsActionAtom = atom(
         }
 
         try {
-            const prefs = await invoke<{ auto_cancel_after_merge?: boolean; auto_cancel_after_pr?: boolean } | null>(TauriCommands.GetProjectMergePreferences)
+            const prefs = await invoke<{
+                auto_cancel_after_merge?: boolean
+                auto_cancel_after_pr?: boolean
+                require_passing_tests?: boolean
+                test_command?: string | null
+            } | null>(TauriCommands.GetProjectMergePreferences)
             if (prefs && typeof prefs.auto_cancel_after_merge === 'boolean') {
                 set(autoCancelAfterMergeStateAtom, prefs.auto_cancel_after_merge)
             } else {
@@ -1156,6 +1165,10 @@ export const initializeSessionsSettingsActionAtom = atom(
             } else {
                 set(autoCancelAfterPrStateAtom, false)
             }
+            set(mergeCheckPreferencesStateAtom, {
+                require_passing_tests: prefs?.require_passing_tests === true,
+                test_command: prefs?.test_command ?? null,
+            })
         } catch (error) {
             logger.warn('[SessionsAtoms] Failed to load project merge preferences', error)
             set(autoCancelAfterMergeStateAtom, true)
@@ -1179,6 +1192,7 @@ export const updateAutoCancelAfterMergeActionAtom = atom(
                 preferences: {
                     auto_cancel_after_merge: input.value,
                     auto_cancel_after_pr: get(autoCancelAfterPrStateAtom),
+                    ...get(mergeCheckPreferencesStateAtom),
                 },
             })
         } catch (error) {
@@ -1203,6 +1217,7 @@ export const updateAutoCancelAfterPrActionAtom = atom(
                 preferences: {
                     auto_cancel_after_merge: get(autoCancelAfterMergeStateAtom),
                     auto_cancel_after_pr: input.value,
+                    ...get(mergeCheckPreferencesStateAtom),
                 },
             })
         } catch (error) {
@@ -12,6 +12,7 @@ interface TerminalBufferResponse {
   seq: number
   startSeq: number
   data: string
+  notModified?: boolean
 }
 
 interface PluginMessage {
@@ -8,6 +8,13 @@ export interface LineInfo {
   isCollapsible?: boolean
   collapsedCount?: number
   collapsedLines?: LineInfo[]
+  oldRanges?: ByteRange[]
+  newRanges?: ByteRange[]
+}
+
+interface ByteRange {
+  start: number
+  end: number
 }
 
 export interface SplitDiffResult {
@@ -32,6 +39,8 @@ export interface DiffResponse {
   isLargeFile: boolean
   isBinary?: boolean
   unsupportedReason?: string
+  hunkHeaders?: string[]
+  contentHash?: string | null
 }
 
 export interface SplitDiffResponse {
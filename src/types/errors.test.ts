@@ -1,8 +1,11 @@
 import { describe, it, expect } from 'vitest'
 import {
+  getErrorCode,
   getErrorMessage,
+  getSkipPermissionsConfirmation,
   isSchaltError,
   isSessionMissingError,
+  type CommandError,
   type SchaltError
 } from './errors'
 
@@ -34,4 +37,29 @@ describe('SchaltError helpers', () => {
     expect(getErrorMessage(err)).toBe('custom failure')
     expect(getErrorMessage('plain error')).toBe('plain error')
   })
+
+  it('reads code and message from command errors', () => {
+    const error: CommandError = {
+      code: 'SessionNotFound',
+      message: "Session 'demo' not found",
+      details: { type: 'SessionNotFound', data: { session_id: 'demo' } }
+    }
+    expect(getErrorCode(error)).toBe('SessionNotFound')
+    expect(getErrorMessage(error)).toBe("Session 'demo' not found")
+    expect(isSessionMissingError(error)).toBe(true)
+    expect(getErrorCode('plain error')).toBeNull()
+  })
+
+  it('unwraps skip-permissions confirmations from command error details', () => {
+    const error: CommandError = {
+      code: 'SkipPermissionsConfirmationRequired',
+      message: 'needs confirmation',
+      details: {
+        type: 'SkipPermissionsConfirmationRequired',
+        data: { session_id: 'demo', agent_type: 'claude', token: 'abc' }
+      }
+    }
+    expect(getSkipPermissionsConfirmation(error)?.data.token).toBe('abc')
+    expect(getSkipPermissionsConfirmation({ code: 'Internal', message: 'x', details: null })).toBeNull()
+  })
 })
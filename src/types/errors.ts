@@ -20,6 +20,68 @@ export type SchaltError =
     }
   | { type: 'AgentNotFound'; data: { agent_name: string } }
   | { type: 'ConfigError'; data: { key: string; message: string } }
+  | {
+      type: 'SkipPermissionsConfirmationRequired'
+      data: { session_id: string; agent_type: string; token: string }
+    }
+  | { type: 'SkipPermissionsForbidden'; data: { session_id: string; agent_type: string } }
+  | { type: 'StaleDiff'; data: { path: string } }
+  | { type: 'ReadOnlyMode'; data: { command: string } }
+
+export type ErrorCode =
+  | 'NoActiveProject'
+  | 'SessionNotFound'
+  | 'SessionAlreadyExists'
+  | 'InvalidSessionState'
+  | 'WorktreeNotFound'
+  | 'WorktreeAlreadyExists'
+  | 'GitOperationFailed'
+  | 'MergeConflict'
+  | 'DatabaseError'
+  | 'DatabaseBusy'
+  | 'InvalidInput'
+  | 'TerminalNotFound'
+  | 'TerminalOperationFailed'
+  | 'ProjectNotFound'
+  | 'IoError'
+  | 'AgentNotFound'
+  | 'AgentBinaryMissing'
+  | 'ConfigError'
+  | 'NotSupported'
+  | 'SkipPermissionsConfirmationRequired'
+  | 'SkipPermissionsForbidden'
+  | 'StaleDiff'
+  | 'ReadOnlyMode'
+  | 'Timeout'
+  | 'Internal'
+
+export interface CommandError {
+  code: ErrorCode
+  message: string
+  details: unknown
+}
+
+export function isCommandError(error: unknown): error is CommandError {
+  if (typeof error !== 'object' || error === null) {
+    return false
+  }
+  const candidate = error as Record<string, unknown>
+  return typeof candidate.code === 'string' && typeof candidate.message === 'string'
+}
+
+export function getErrorCode(error: unknown): ErrorCode | null {
+  return isCommandError(error) ? error.code : null
+}
+
+function asSchaltError(error: unknown): SchaltError | null {
+  if (isSchaltError(error)) {
+    return error
+  }
+  if (isCommandError(error) && isSchaltError(error.details)) {
+    return error.details
+  }
+  return null
+}
 
 export function isSchaltError(error: unknown): error is SchaltError {
   if (typeof error !== 'object' || error === null) {
@@ -30,6 +92,10 @@ export function isSchaltError(error: unknown): error is SchaltError {
 }
 
 export function getErrorMessage(error: unknown): string {
+  if (isCommandError(error)) {
+    return error.message
+  }
+
   if (isSchaltError(error)) {
     switch (error.type) {
       case 'SessionNotFound':
@@ -62,6 +128,14 @@ export function getErrorMessage(error: unknown): string {
         return `Agent '${error.data.agent_name}' not found`
       case 'ConfigError':
         return `Configuration error for key '${error.data.key}': ${error.data.message}`
+      case 'SkipPermissionsConfirmationRequired':
+        return `Starting ${error.data.agent_type} in session '${error.data.session_id}' with skipped permissions needs confirmation`
+      case 'SkipPermissionsForbidden':
+        return `This project forbids starting ${error.data.agent_type} in session '${error.data.session_id}' with skipped permissions`
+      case 'StaleDiff':
+        return `'${error.data.path}' changed since its diff was loaded; refresh and try again`
+      case 'ReadOnlyMode':
+        return `'${error.data.command}' is not available while the project is open read-only`
       default: {
         const _exhaustive: never = error
         return `Unknown error: ${_exhaustive}`
@@ -81,5 +155,12 @@ export function getErrorMessage(error: unknown): string {
 }
 
 export function isSessionMissingError(error: unknown): boolean {
-  return isSchaltError(error) && error.type === 'SessionNotFound'
+  return getErrorCode(error) === 'SessionNotFound' || asSchaltError(error)?.type === 'SessionNotFound'
+}
+
+export function getSkipPermissionsConfirmation(
+  error: unknown
+): Extract<SchaltError, { type: 'SkipPermissionsConfirmationRequired' }> | null {
+  const schaltError = asSchaltError(error)
+  return schaltError?.type === 'SkipPermissionsConfirmationRequired' ? schaltError : null
 }
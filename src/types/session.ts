@@ -57,6 +57,7 @@ export interface SessionInfo {
     display_name?: string
     version_group_id?: string
     version_number?: number
+    version_group_label?: string
     epic?: Epic
     branch: string
     worktree_path: string
@@ -88,13 +89,52 @@ export interface SessionInfo {
     attention_required?: boolean
     pr_number?: number
     pr_url?: string
+    group?: string
+    skip_permissions_active?: boolean
+    has_stashed_changes?: boolean
+    setup_failed?: boolean
+    setup_running?: boolean
+    branch_missing?: boolean
+    repo_label?: string
+    depends_on_session?: string
 }
 
+export type SessionAgentContent =
+    | { status: 'modified'; spec_content: string | null; initial_prompt: string | null; etag: string }
+    | { status: 'not_modified'; etag: string }
+
 export interface DiffStats {
     files_changed: number
     additions: number
     deletions: number
     insertions: number
+    has_remote?: boolean
+    remote_ahead?: number
+    remote_behind?: number
+}
+
+export interface SessionLaunchOverrides {
+    model?: string | null
+    extraFlags?: string | null
+}
+
+// A null value unsets a variable inherited from the agent or project environment
+export type SessionEnvOverrides = Record<string, string | null>
+
+export interface SessionLaunchSnapshot {
+    agentType: string
+    command: string
+    overrides: SessionLaunchOverrides
+    forceRestart: boolean
+    launchedAt: string
+}
+
+export type AgentRunState = 'thinking' | 'tool-running' | 'awaiting-input' | 'done'
+
+export interface AgentOutputStatus {
+    state: AgentRunState | null
+    last_tool: string | null
+    tokens: number | null
 }
 
 export interface SessionMonitorStatus {
@@ -103,12 +143,25 @@ export interface SessionMonitorStatus {
     test_status: 'passed' | 'failed' | 'unknown'
     diff_stats?: DiffStats
     last_update: string
+    agent_status?: AgentOutputStatus
+}
+
+export type SessionTerminalKind = 'agent' | 'shell' | 'run' | 'extra'
+
+export interface SessionTerminal {
+    id: string
+    kind: SessionTerminalKind
+    exists: boolean
+    suspended: boolean
+    last_activity: string | null
 }
 
 export interface EnrichedSession {
     info: SessionInfo
     status?: SessionMonitorStatus
+    // Canonical top/bottom ids only; kept while consumers move to terminal_details
     terminals: string[]
+    terminal_details?: SessionTerminal[]
     attention_required?: boolean
 }
 
@@ -119,6 +172,7 @@ export interface RawSession {
     display_name?: string
     version_group_id?: string
     version_number?: number
+    version_group_label?: string | null
     epic_id?: string | null
     repository_path: string
     repository_name: string
@@ -145,4 +199,5 @@ export interface RawSession {
     }
     pr_number?: number
     pr_url?: string
+    group?: string | null
 }
@@ -9,3 +9,33 @@ export type SettingsCategory =
   | 'terminal'
   | 'sessions'
   | 'version'
+
+export interface WebhookRequestEntry {
+  method: string
+  path: string
+  timestampMs: number
+  status: number
+  latencyMs: number
+  body: string | null
+  bodyTruncated: boolean
+  clientError: string | null
+}
+
+export interface WebhookEndpointCounter {
+  endpoint: string
+  requests: number
+  clientErrors: number
+  serverErrors: number
+}
+
+export interface WebhookRequestLog {
+  enabled: boolean
+  entries: WebhookRequestEntry[]
+  endpoints: WebhookEndpointCounter[]
+}
+
+export interface PromptTemplate {
+  name: string
+  content: string
+  defaults: Record<string, string>
+}
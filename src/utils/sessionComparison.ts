@@ -23,6 +23,7 @@ const SESSION_INFO_KEYS: ReadonlyArray<keyof SessionInfo> = [
     'is_blocked',
     'version_group_id',
     'version_number',
+    'version_group_label',
     'original_agent_type',
     'spec_content',
     'diff_stats',